@@ -0,0 +1,162 @@
+//! Derive macros for `odbc-api`. Use them through the `derive` feature of `odbc-api`, rather than
+//! depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Literal, Span};
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Index, Member};
+
+/// Implements `odbc_api::TextRecord` for a struct with named fields. Each field is a column named
+/// like the field and must implement `odbc_api::ToSqlText`.
+#[proc_macro_derive(TextRecord)]
+pub fn derive_text_record(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match text_record(input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn text_record(input: DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    Span::call_site(),
+                    "TextRecord can only be derived for structs with named fields.",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "TextRecord can only be derived for structs.",
+            ))
+        }
+    };
+    let idents = fields
+        .iter()
+        .map(|field| {
+            field
+                .ident
+                .as_ref()
+                .ok_or_else(|| Error::new_spanned(field, "TextRecord requires named fields."))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let column_names = idents.iter().map(|ident| ident.to_string());
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::odbc_api::TextRecord for #name #ty_generics #where_clause {
+            fn column_names() -> &'static [&'static str] {
+                &[#(#column_names),*]
+            }
+
+            fn text_values(&self) -> ::std::vec::Vec<::std::option::Option<::std::string::String>> {
+                ::std::vec![#(::odbc_api::ToSqlText::to_sql_text(&self.#idents)),*]
+            }
+        }
+    })
+}
+
+/// Implements `odbc_api::InputParameterCollection` for a struct. Each field is bound as an input
+/// parameter, in the order of declaration, and must implement `odbc_api::parameter::InputParameter`.
+#[proc_macro_derive(InputParameterCollection)]
+pub fn derive_input_parameter_collection(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match input_parameter_collection(input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn input_parameter_collection(input: DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "InputParameterCollection can only be derived for structs.",
+            ))
+        }
+    };
+    let members: Vec<_> = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(index)),
+        })
+        .collect();
+    let parameter_numbers = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let number: u16 = (index + 1)
+                .try_into()
+                .map_err(|_| Error::new_spanned(field, "Too many fields to bind as parameters."))?;
+            Ok(Literal::u16_suffixed(number))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        unsafe impl #impl_generics ::odbc_api::InputParameterCollection for #name #ty_generics
+            #where_clause
+        {
+            fn parameter_set_size(&self) -> usize {
+                1
+            }
+
+            unsafe fn bind_input_parameters_to(
+                &self,
+                stmt: &mut impl ::odbc_api::handles::Statement,
+            ) -> ::std::result::Result<(), ::odbc_api::Error> {
+                #(
+                    ::odbc_api::ParameterTupleElement::bind_to(
+                        &mut &self.#members,
+                        #parameter_numbers,
+                        stmt,
+                    )?;
+                )*
+                ::std::result::Result::Ok(())
+            }
+
+            fn parameter_previews(&self) -> ::std::vec::Vec<::std::string::String> {
+                ::std::vec![#(::odbc_api::ParameterTupleElement::preview(&&self.#members)),*]
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, parse_str, DeriveInput};
+
+    use super::{input_parameter_collection, text_record};
+
+    #[test]
+    fn text_record_for_tuple_struct() {
+        let input: DeriveInput = parse_quote! { struct Row(i32, String); };
+
+        let error = text_record(input).unwrap_err();
+
+        assert_eq!(
+            "TextRecord can only be derived for structs with named fields.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn too_many_fields_to_bind_as_parameters() {
+        let fields = vec!["i32"; usize::from(u16::MAX) + 1].join(", ");
+        let input: DeriveInput = parse_str(&format!("struct Params({fields});")).unwrap();
+
+        let error = input_parameter_collection(input).unwrap_err();
+
+        assert_eq!("Too many fields to bind as parameters.", error.to_string());
+    }
+}
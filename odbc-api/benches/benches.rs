@@ -1,18 +1,55 @@
 use std::iter;
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use odbc_api::buffers::{BufferDesc, ColumnarAnyBuffer};
+use odbc_api::buffers::{BufferDesc, ColumnBuffer, ColumnarAnyBuffer, TextColumn};
 
 fn falliable_buffer_allocation(capacity: usize, max_str_len: usize) {
     let description = BufferDesc::Text { max_str_len };
     ColumnarAnyBuffer::try_from_descs(capacity, iter::once(description)).unwrap();
 }
 
+#[allow(deprecated)]
 fn infalliable_buffer_allocation(capacity: usize, max_str_len: usize) {
     let description = BufferDesc::Text { max_str_len };
     ColumnarAnyBuffer::from_descs(capacity, [description]);
 }
 
+/// Wide text column with `num_rows` rows, each holding `text`.
+fn wide_text_column(text: &str, num_rows: usize) -> TextColumn<u16> {
+    let text: Vec<u16> = text.encode_utf16().collect();
+    let mut column = TextColumn::new(num_rows, text.len());
+    for index in 0..num_rows {
+        column.set_value(index, Some(&text));
+    }
+    column
+}
+
+/// Decodes every value in `column` to UTF-8, reusing the same `String`.
+fn decode_with_push_utf8(column: &TextColumn<u16>, num_rows: usize) {
+    let view = column.view(num_rows);
+    let mut out = String::new();
+    for index in 0..num_rows {
+        out.clear();
+        view.push_utf8(index, &mut out);
+        black_box(&out);
+    }
+}
+
+/// Decodes every value in `column` to UTF-8 one character at a time, as a baseline.
+fn decode_char_by_char(column: &TextColumn<u16>, num_rows: usize) {
+    let view = column.view(num_rows);
+    let mut out = String::new();
+    for index in 0..num_rows {
+        out.clear();
+        let text = view.get(index).unwrap();
+        out.extend(
+            char::decode_utf16(text.iter().copied())
+                .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER)),
+        );
+        black_box(&out);
+    }
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("fallibale buffer allocation", |b| {
         b.iter(|| {
@@ -30,5 +67,26 @@ fn criterion_benchmark(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, criterion_benchmark);
+fn decode_wide_text_benchmark(c: &mut Criterion) {
+    let num_rows = 1000;
+    let ascii = wide_text_column(
+        &"Lorem ipsum dolor sit amet, consectetur. ".repeat(6),
+        num_rows,
+    );
+    let mixed = wide_text_column(&"Grüße aus Köln, 東京 und Zürich. ".repeat(6), num_rows);
+    c.bench_function("decode ascii wide text with push_utf8", |b| {
+        b.iter(|| decode_with_push_utf8(&ascii, num_rows))
+    });
+    c.bench_function("decode ascii wide text char by char", |b| {
+        b.iter(|| decode_char_by_char(&ascii, num_rows))
+    });
+    c.bench_function("decode mixed wide text with push_utf8", |b| {
+        b.iter(|| decode_with_push_utf8(&mixed, num_rows))
+    });
+    c.bench_function("decode mixed wide text char by char", |b| {
+        b.iter(|| decode_char_by_char(&mixed, num_rows))
+    });
+}
+
+criterion_group!(benches, criterion_benchmark, decode_wide_text_benchmark);
 criterion_main!(benches);
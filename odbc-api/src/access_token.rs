@@ -0,0 +1,103 @@
+use std::mem::size_of;
+
+use crate::Error;
+
+/// An access token (e.g. obtained from Azure Active Directory for a managed identity), laid out as
+/// the `ACCESSTOKEN` structure expected by the Microsoft ODBC Driver for SQL Server for its
+/// `SQL_COPT_SS_ACCESS_TOKEN` connection attribute. Use it with
+/// [`crate::Environment::connect_with_connection_string_and_access_token`].
+///
+/// The structure consists of a 32 Bit length, followed by the token encoded as UTF-16LE.
+///
+/// ```
+/// use odbc_api::AccessToken;
+///
+/// let token = AccessToken::new("ab")?;
+/// assert_eq!(&[4, 0, 0, 0, b'a', 0, b'b', 0], token.as_bytes());
+/// # Ok::<(), odbc_api::Error>(())
+/// ```
+#[derive(Clone)]
+pub struct AccessToken {
+    /// Storage for the `ACCESSTOKEN` structure. We use `u32` rather than `u8` elements so the
+    /// leading `DWORD` holding the length is properly aligned.
+    buffer: Vec<u32>,
+    /// Length of the structure in bytes. The last element of `buffer` may contain padding.
+    len_bytes: usize,
+}
+
+impl AccessToken {
+    /// Encodes `token` in the layout required by the driver. Fails with
+    /// [`Error::TooLargeAccessToken`] if the encoded token does not fit into the 32 Bit length of
+    /// the structure.
+    pub fn new(token: &str) -> Result<Self, Error> {
+        let data: Vec<u8> = token.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let data_size = data_size(data.len())?;
+        let len_bytes = size_of::<u32>() + data.len();
+
+        let mut bytes = Vec::with_capacity(len_bytes);
+        // Windows is little endian, and so are the platforms the driver is available on.
+        bytes.extend_from_slice(&data_size.to_le_bytes());
+        bytes.extend_from_slice(&data);
+
+        let buffer = bytes
+            .chunks(size_of::<u32>())
+            .map(|chunk| {
+                let mut word = [0u8; 4];
+                word[..chunk.len()].copy_from_slice(chunk);
+                u32::from_ne_bytes(word)
+            })
+            .collect();
+        Ok(Self { buffer, len_bytes })
+    }
+
+    /// The `ACCESSTOKEN` structure as bytes, as it is passed to the driver.
+    pub fn as_bytes(&self) -> &[u8] {
+        // Safe: `buffer` is valid for at least `len_bytes` bytes, and `u8` has no alignment
+        // requirements.
+        unsafe { std::slice::from_raw_parts(self.buffer.as_ptr() as *const u8, self.len_bytes) }
+    }
+}
+
+/// Length of the token in bytes, as stored in the leading `DWORD` of the structure.
+fn data_size(len_bytes: usize) -> Result<u32, Error> {
+    len_bytes
+        .try_into()
+        .map_err(|_| Error::TooLargeAccessToken { len_bytes })
+}
+
+/// Do not leak the token into logs.
+impl std::fmt::Debug for AccessToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AccessToken").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Error;
+
+    use super::{data_size, AccessToken};
+
+    #[test]
+    fn layout_of_access_token() {
+        let token = AccessToken::new("abc").unwrap();
+        assert_eq!(&[6, 0, 0, 0, b'a', 0, b'b', 0, b'c', 0], token.as_bytes());
+        // Must be aligned for the driver to read the leading `DWORD`.
+        assert_eq!(0, token.as_bytes().as_ptr() as usize % 4);
+    }
+
+    #[test]
+    fn empty_access_token() {
+        assert_eq!(&[0, 0, 0, 0], AccessToken::new("").unwrap().as_bytes());
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn too_large_access_token() {
+        let len_bytes = u32::MAX as usize + 1;
+        assert!(matches!(
+            data_size(len_bytes),
+            Err(Error::TooLargeAccessToken { len_bytes: actual }) if actual == len_bytes
+        ));
+    }
+}
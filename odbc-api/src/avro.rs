@@ -0,0 +1,385 @@
+//! Export result sets as Apache Avro object container files. Requires the `avro` feature.
+//!
+//! The schema is derived from the result set metadata. Fetched batches are streamed to a writer,
+//! each batch as one Avro block, so arbitrarily large result sets can be exported with constant
+//! memory. Blocks are not compressed. Encoding is done by the `apache-avro` crate.
+//!
+//! ```no_run
+//! use odbc_api::{avro::AvroExport, Connection};
+//! use std::fs::File;
+//!
+//! fn export_birthdays(conn: &Connection<'_>) -> Result<(), Box<dyn std::error::Error>> {
+//!     let cursor = conn.execute("SELECT name, birthday FROM Birthdays", ())?.unwrap();
+//!     let export = AvroExport::new(cursor, "Birthday", 1000, Some(4096))?;
+//!     let num_rows = export.write_to(File::create("birthdays.avro")?)?;
+//!     println!("Exported {num_rows} rows.");
+//!     Ok(())
+//! }
+//! ```
+
+use std::io::Write;
+
+use apache_avro::{types::Value, Schema, Writer};
+use thiserror::Error as ThisError;
+
+use crate::{
+    buffers::{max_str_len_with_limit, AnySlice, BufferDesc, ColumnarAnyBuffer, NullableSlice},
+    calendar::{days_since_epoch, micros_since_epoch, millis_since_midnight},
+    sys::{Date, Time, Timestamp},
+    BlockCursor, Cursor, Error,
+};
+
+/// Error emitted by [`AvroExport::write_to`].
+#[derive(Debug, ThisError)]
+pub enum AvroError {
+    /// Fetching a batch from the data source failed.
+    #[error("Fetching rows for Avro export failed:\n{0}")]
+    Odbc(#[from] Error),
+    /// Encoding the records or writing the Avro container failed.
+    #[error("Writing Avro container failed:\n{0}")]
+    Avro(Box<apache_avro::Error>),
+}
+
+impl From<apache_avro::Error> for AvroError {
+    fn from(source: apache_avro::Error) -> Self {
+        AvroError::Avro(Box::new(source))
+    }
+}
+
+/// Fetches the rows of a cursor and writes them as an Avro object container file. Each row is a
+/// record with one field for each column. Field names are derived from the column names, with
+/// characters not allowed in Avro names replaced by `_`.
+///
+/// All fields are nullable, i.e. a union of `"null"` and the type of the column. Types are mapped
+/// as follows:
+///
+/// * Integers up to 32 Bit are mapped to `int`, `BIGINT` to `long`.
+/// * `REAL` and `FLOAT` are mapped to `float` or `double`, depending on their precision.
+/// * `BIT` is mapped to `boolean`.
+/// * Dates are mapped to `int` with logical type `date`.
+/// * Times are mapped to `int` with logical type `time-millis`.
+/// * Timestamps are mapped to `long` with logical type `local-timestamp-micros`, since ODBC
+///   timestamps do not carry a time zone.
+/// * Binary data is mapped to `bytes`.
+/// * Everything else, including `DECIMAL` and `NUMERIC`, is fetched as text and mapped to
+///   `string`.
+pub struct AvroExport<C: Cursor> {
+    block_cursor: BlockCursor<C, ColumnarAnyBuffer>,
+    schema_json: String,
+    schema: Schema,
+    field_names: Vec<String>,
+}
+
+impl<C> AvroExport<C>
+where
+    C: Cursor,
+{
+    /// Derives the schema from the metadata of `cursor` and binds a buffer holding up to
+    /// `batch_size` rows. `record_name` is the name of the Avro record type.
+    ///
+    /// Text is fetched as UTF-16 and converted to UTF-8 regardless of the system encoding.
+    /// `max_str_len` limits the number of UTF-16 code units reserved for each text value. It is
+    /// also used for columns whose maximum length is not reported by the driver. Should any value
+    /// not fit into the buffer, fetching fails rather than exporting truncated text.
+    pub fn new(
+        mut cursor: C,
+        record_name: &str,
+        batch_size: usize,
+        max_str_len: Option<usize>,
+    ) -> Result<Self, Error> {
+        let quirks = cursor.quirks();
+        let num_cols: u16 = cursor.num_result_cols()?.try_into().unwrap();
+        let mut column_names = Vec::new();
+        let mut avro_types = Vec::new();
+        let mut descs = Vec::new();
+        for col_index in 1..(num_cols + 1) {
+            let data_type = cursor.col_data_type(col_index)?;
+            // We always bind nullable buffers, since drivers are not reliable in reporting
+            // nullability.
+            let desc = match quirks.buffer_desc(data_type, true) {
+                Some(BufferDesc::Text { .. }) | None => BufferDesc::WText {
+                    max_str_len: max_str_len_with_limit(
+                        data_type.utf16_len().unwrap_or(0),
+                        max_str_len,
+                    ),
+                },
+                Some(desc) => desc,
+            };
+            column_names.push(cursor.col_name(col_index)?);
+            avro_types.push(avro_type(&desc));
+            descs.push(desc);
+        }
+        let field_names = field_names(&column_names);
+        let schema_json = record_schema(record_name, &field_names, &avro_types);
+        let schema = Schema::parse_str(&schema_json)
+            .expect("Schema derived from result set metadata must be valid");
+        let buffer = ColumnarAnyBuffer::try_from_descs(batch_size, descs)?;
+        let block_cursor = cursor.bind_buffer(buffer)?;
+        Ok(Self {
+            block_cursor,
+            schema_json,
+            schema,
+            field_names,
+        })
+    }
+
+    /// Avro schema of the records, as JSON.
+    pub fn schema(&self) -> &str {
+        &self.schema_json
+    }
+
+    /// Writes the header of the container, followed by one block for each batch fetched from the
+    /// cursor. Returns the number of rows written.
+    pub fn write_to(self, writer: impl Write) -> Result<usize, AvroError> {
+        let Self {
+            mut block_cursor,
+            schema,
+            field_names,
+            ..
+        } = self;
+        let mut writer = Writer::new(&schema, writer);
+        let mut num_rows = 0;
+        while let Some(batch) = block_cursor.fetch_with_truncation_check(true)? {
+            write_batch(&mut writer, &field_names, batch)?;
+            num_rows += batch.num_rows();
+        }
+        // Also writes the header, should the result set be empty.
+        writer
+            .into_inner()?
+            .flush()
+            .map_err(apache_avro::Error::WriteBytes)?;
+        Ok(num_rows)
+    }
+}
+
+/// Avro type of the values held by a buffer, as JSON.
+fn avro_type(desc: &BufferDesc) -> &'static str {
+    match desc {
+        BufferDesc::I8 { .. }
+        | BufferDesc::I16 { .. }
+        | BufferDesc::I32 { .. }
+        | BufferDesc::U8 { .. } => r#""int""#,
+        BufferDesc::I64 { .. } => r#""long""#,
+        BufferDesc::F32 { .. } => r#""float""#,
+        BufferDesc::F64 { .. } => r#""double""#,
+        BufferDesc::Bit { .. } => r#""boolean""#,
+        BufferDesc::Date { .. } => r#"{"type":"int","logicalType":"date"}"#,
+        BufferDesc::Time { .. } => r#"{"type":"int","logicalType":"time-millis"}"#,
+        BufferDesc::Timestamp { .. } => r#"{"type":"long","logicalType":"local-timestamp-micros"}"#,
+        BufferDesc::Binary { .. } => r#""bytes""#,
+        _ => r#""string""#,
+    }
+}
+
+/// Sanitized field names for the columns. Field names must be unique, so duplicates are suffixed
+/// with a number.
+fn field_names(column_names: &[String]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for column_name in column_names {
+        let mut name = avro_name(column_name);
+        if names.contains(&name) {
+            let mut suffix = 1;
+            while names.contains(&format!("{name}_{suffix}")) {
+                suffix += 1;
+            }
+            name = format!("{name}_{suffix}");
+        }
+        names.push(name);
+    }
+    names
+}
+
+/// Schema of a record with a nullable field for each column.
+fn record_schema(record_name: &str, field_names: &[String], avro_types: &[&str]) -> String {
+    let fields: Vec<_> = field_names
+        .iter()
+        .zip(avro_types)
+        .map(|(name, avro_type)| {
+            format!(r#"{{"name":"{name}","type":["null",{avro_type}],"default":null}}"#)
+        })
+        .collect();
+    format!(
+        r#"{{"type":"record","name":"{}","fields":[{}]}}"#,
+        avro_name(record_name),
+        fields.join(",")
+    )
+}
+
+/// Avro names start with a letter or `_`, followed by letters, digits or `_`. Other characters are
+/// replaced with `_`.
+fn avro_name(name: &str) -> String {
+    let mut avro_name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !avro_name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        avro_name.insert(0, '_');
+    }
+    avro_name
+}
+
+/// Appends the records of a batch and flushes them as one block.
+fn write_batch<W: Write>(
+    writer: &mut Writer<'_, W>,
+    field_names: &[String],
+    batch: &ColumnarAnyBuffer,
+) -> Result<(), AvroError> {
+    // Avro encodes records row by row, so we convert each column into the fields of the rows
+    // first.
+    let mut rows = vec![Vec::with_capacity(field_names.len()); batch.num_rows()];
+    for (buffer_index, name) in field_names.iter().enumerate() {
+        let values = column_values(batch.column(buffer_index));
+        for (row, value) in rows.iter_mut().zip(values) {
+            row.push((name.clone(), value));
+        }
+    }
+    let records: Vec<_> = rows.into_iter().map(Value::Record).collect();
+    writer.extend_from_slice(&records)?;
+    Ok(())
+}
+
+/// Values of a column as members of the union `["null", T]`.
+fn column_values(column: AnySlice<'_>) -> Vec<Value> {
+    match column {
+        AnySlice::Text(view) => view
+            .iter()
+            .map(|text| union(text.map(|text| Value::String(String::from_utf8_lossy(text).into()))))
+            .collect(),
+        AnySlice::WText(view) => (0..view.len())
+            .map(|index| {
+                let mut text = String::new();
+                let is_some = view.push_utf8(index, &mut text);
+                union(is_some.then_some(Value::String(text)))
+            })
+            .collect(),
+        AnySlice::Binary(view) => view
+            .iter()
+            .map(|bytes| union(bytes.map(|bytes| Value::Bytes(bytes.to_vec()))))
+            .collect(),
+        AnySlice::Date(values) => required(values, date),
+        AnySlice::Time(values) => required(values, time),
+        AnySlice::Timestamp(values) => required(values, timestamp),
+        AnySlice::F64(values) => required(values, |&v| Value::Double(v)),
+        AnySlice::F32(values) => required(values, |&v| Value::Float(v)),
+        AnySlice::I8(values) => required(values, |&v| Value::Int(v.into())),
+        AnySlice::I16(values) => required(values, |&v| Value::Int(v.into())),
+        AnySlice::I32(values) => required(values, |&v| Value::Int(v)),
+        AnySlice::I64(values) => required(values, |&v| Value::Long(v)),
+        AnySlice::U8(values) => required(values, |&v| Value::Int(v.into())),
+        AnySlice::Bit(values) => required(values, |v| Value::Boolean(v.as_bool())),
+        AnySlice::NullableDate(values) => nullable(values, date),
+        AnySlice::NullableTime(values) => nullable(values, time),
+        AnySlice::NullableTimestamp(values) => nullable(values, timestamp),
+        AnySlice::NullableF64(values) => nullable(values, |&v| Value::Double(v)),
+        AnySlice::NullableF32(values) => nullable(values, |&v| Value::Float(v)),
+        AnySlice::NullableI8(values) => nullable(values, |&v| Value::Int(v.into())),
+        AnySlice::NullableI16(values) => nullable(values, |&v| Value::Int(v.into())),
+        AnySlice::NullableI32(values) => nullable(values, |&v| Value::Int(v)),
+        AnySlice::NullableI64(values) => nullable(values, |&v| Value::Long(v)),
+        AnySlice::NullableU8(values) => nullable(values, |&v| Value::Int(v.into())),
+        AnySlice::NullableBit(values) => nullable(values, |v| Value::Boolean(v.as_bool())),
+    }
+}
+
+/// Values of a buffer without indicators. The schema declares every field nullable, so we still
+/// need to wrap them in the union.
+fn required<T>(values: &[T], convert: impl Fn(&T) -> Value) -> Vec<Value> {
+    values.iter().map(|v| union(Some(convert(v)))).collect()
+}
+
+fn nullable<T>(values: NullableSlice<'_, T>, convert: impl Fn(&T) -> Value) -> Vec<Value> {
+    values.map(|v| union(v.map(&convert))).collect()
+}
+
+/// Value of the union `["null", T]`.
+fn union(value: Option<Value>) -> Value {
+    match value {
+        None => Value::Union(0, Box::new(Value::Null)),
+        Some(value) => Value::Union(1, Box::new(value)),
+    }
+}
+
+fn date(date: &Date) -> Value {
+    // ODBC years are 16 Bit, so the number of days always fits into 32 Bit.
+    Value::Date(days_since_epoch(date) as i32)
+}
+
+fn time(time: &Time) -> Value {
+    // Less than a day worth of milliseconds.
+    Value::TimeMillis(millis_since_midnight(time) as i32)
+}
+
+fn timestamp(ts: &Timestamp) -> Value {
+    Value::LocalTimestampMicros(micros_since_epoch(ts))
+}
+
+#[cfg(test)]
+mod tests {
+    use apache_avro::{types::Value, Reader, Schema, Writer};
+
+    use crate::buffers::{AnyBuffer, BufferDesc, ColumnarAnyBuffer};
+
+    use super::{avro_name, field_names, record_schema, write_batch};
+
+    #[test]
+    fn schema_with_sanitized_unique_names() {
+        let field_names = field_names(&["id".to_owned(), "1st name".to_owned(), "id".to_owned()]);
+        let schema = record_schema(
+            "My Record",
+            &field_names,
+            &[r#""long""#, r#""string""#, r#""int""#],
+        );
+        assert_eq!(
+            r#"{"type":"record","name":"My_Record","fields":[{"name":"id","type":["null","long"],"default":null},{"name":"_1st_name","type":["null","string"],"default":null},{"name":"id_1","type":["null","int"],"default":null}]}"#,
+            schema
+        );
+        assert_eq!("_", avro_name(""));
+    }
+
+    #[test]
+    fn read_back_written_batch() {
+        let mut numbers = AnyBuffer::try_from_desc(2, BufferDesc::I32 { nullable: true }).unwrap();
+        let mut text = AnyBuffer::try_from_desc(2, BufferDesc::Text { max_str_len: 10 }).unwrap();
+        if let AnyBuffer::NullableI32(column) = &mut numbers {
+            column.writer_n(2).write([Some(42), None].into_iter());
+        }
+        if let AnyBuffer::Text(column) = &mut text {
+            column.set_value(0, Some(b"Hello"));
+            column.set_value(1, None);
+        }
+        let mut batch =
+            unsafe { ColumnarAnyBuffer::new_unchecked(2, vec![(1, numbers), (2, text)]) };
+        batch.set_num_rows(2);
+        let field_names = field_names(&["a".to_owned(), "b".to_owned()]);
+        let schema = Schema::parse_str(&record_schema(
+            "Row",
+            &field_names,
+            &[r#""int""#, r#""string""#],
+        ))
+        .unwrap();
+
+        let mut writer = Writer::new(&schema, Vec::new());
+        write_batch(&mut writer, &field_names, &batch).unwrap();
+        let container = writer.into_inner().unwrap();
+
+        let records: Vec<_> = Reader::new(container.as_slice())
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let null = || Box::new(Value::Null);
+        let expected = vec![
+            Value::Record(vec![
+                ("a".to_owned(), Value::Union(1, Box::new(Value::Int(42)))),
+                (
+                    "b".to_owned(),
+                    Value::Union(1, Box::new(Value::String("Hello".to_owned()))),
+                ),
+            ]),
+            Value::Record(vec![
+                ("a".to_owned(), Value::Union(0, null())),
+                ("b".to_owned(), Value::Union(0, null())),
+            ]),
+        ];
+        assert_eq!(expected, records);
+    }
+}
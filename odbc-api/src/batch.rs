@@ -0,0 +1,412 @@
+use thiserror::Error as ThisError;
+
+use crate::{
+    execute::row_count,
+    handles::{SqlText, Statement, StatementImpl},
+    Error,
+};
+
+/// Controls how [`crate::Connection::execute_batch`] submits a script containing multiple
+/// statements to the data source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchMode {
+    /// Send the entire script in one roundtrip and let the driver execute it as a batch. Results of
+    /// the individual statements are then iterated using `SQLMoreResults`. Not every driver
+    /// supports batches. E.g. Microsoft SQL Server does, while SQLite would silently only execute
+    /// the first statement.
+    Driver,
+    /// Split the script into individual statements on the client side using
+    /// [`split_sql_statements`] and execute them one after another on the same statement handle.
+    /// This works with every driver, but requires one roundtrip per statement.
+    Split,
+}
+
+/// Describes the result of a single statement executed as part of a batch. See
+/// [`crate::Connection::execute_batch`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatementOutcome {
+    /// The statement created a result set with `num_cols` columns. The result set has been
+    /// discarded without fetching its rows.
+    ResultSet {
+        /// Number of columns in the result set.
+        num_cols: u16,
+    },
+    /// The statement did not create a result set. Holds the number of rows affected, if known.
+    RowCount(Option<usize>),
+}
+
+/// Splits an SQL script into individual statements at each `;` which is not part of a string
+/// literal, quoted identifier or comment. Statements are returned with surrounding whitespace
+/// trimmed and without the terminating `;`. Statements consisting only of whitespace and comments
+/// are skipped.
+///
+/// Understood are single quoted string literals (`'it''s'`), double quoted (`"a;b"`) and bracketed
+/// (`[a;b]`) identifiers, PostgreSQL dollar quoted strings (`$$a;b$$`, `$tag$a;b$tag$`), line
+/// comments (`-- ;`) and block comments (`/* ; */`). Procedural blocks containing `;` within
+/// `BEGIN ... END` can not be recognized and must be executed on their own.
+///
+/// ```
+/// use odbc_api::split_sql_statements;
+///
+/// let script = "INSERT INTO t (a) VALUES ('x;y'); -- comment;\nSELECT a FROM t;";
+/// assert_eq!(
+///     vec!["INSERT INTO t (a) VALUES ('x;y')", "-- comment;\nSELECT a FROM t"],
+///     split_sql_statements(script)
+/// );
+/// ```
+pub fn split_sql_statements(script: &str) -> Vec<&str> {
+    split_sql_script(script, &ScriptDelimiter::Semicolon)
+        .into_iter()
+        .map(|statement| statement.text)
+        .collect()
+}
+
+/// Separates the statements of an SQL script. See [`split_sql_script`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptDelimiter {
+    /// `;` outside of string literals, quoted identifiers and comments. Splits like
+    /// [`split_sql_statements`].
+    Semicolon,
+    /// A line consisting only of this keyword, compared case insensitively. E.g. `GO` in scripts
+    /// written for Microsoft SQL Server. `;` does not separate anything in this mode, so each part
+    /// may contain several statements, which are sent to the data source together.
+    Line(String),
+}
+
+/// A statement of an SQL script, as returned by [`split_sql_script`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScriptStatement<'s> {
+    /// Text of the statement, with surrounding whitespace and the delimiter removed.
+    pub text: &'s str,
+    /// Line of the script the statement starts in. The first line is `1`.
+    pub line: usize,
+}
+
+/// Splits an SQL script into individual statements at each `delimiter`, which is not part of a
+/// string literal, quoted identifier or comment. In addition to the text of each statement, the
+/// line it starts in is reported, so errors can point to the offending statement in the script.
+/// Statements consisting only of whitespace and comments are skipped.
+///
+/// ```
+/// use odbc_api::{split_sql_script, ScriptDelimiter, ScriptStatement};
+///
+/// let script = "CREATE TABLE t (a INT);\nINSERT INTO t VALUES (1);\ngo\nSELECT a FROM t\nGO\n";
+/// assert_eq!(
+///     vec![
+///         ScriptStatement { text: "CREATE TABLE t (a INT);\nINSERT INTO t VALUES (1);", line: 1 },
+///         ScriptStatement { text: "SELECT a FROM t", line: 4 },
+///     ],
+///     split_sql_script(script, &ScriptDelimiter::Line("GO".to_owned()))
+/// );
+/// ```
+pub fn split_sql_script<'s>(
+    script: &'s str,
+    delimiter: &ScriptDelimiter,
+) -> Vec<ScriptStatement<'s>> {
+    let bytes = script.as_bytes();
+    let mut statements = Vec::new();
+    let mut start = 0;
+    let mut lines = LineCounter::default();
+    // `true` once we encountered anything in the current statement which is neither whitespace
+    // nor a comment.
+    let mut has_content = false;
+    let mut index = 0;
+
+    // All delimiters we are looking for are ASCII, so it is safe to operate on bytes. Multi byte
+    // UTF-8 sequences never contain bytes in the ASCII range.
+    while index < bytes.len() {
+        if let ScriptDelimiter::Line(keyword) = delimiter {
+            if index == 0 || bytes[index - 1] == b'\n' {
+                let line_end = find_from(bytes, index, b"\n").unwrap_or(bytes.len());
+                if script[index..line_end].trim().eq_ignore_ascii_case(keyword) {
+                    if has_content {
+                        statements.push(script_statement(script, start, index, &mut lines));
+                    }
+                    has_content = false;
+                    index = (line_end + 1).min(bytes.len());
+                    start = index;
+                    continue;
+                }
+            }
+        }
+        match bytes[index] {
+            b';' if *delimiter == ScriptDelimiter::Semicolon => {
+                if has_content {
+                    statements.push(script_statement(script, start, index, &mut lines));
+                }
+                has_content = false;
+                index += 1;
+                start = index;
+            }
+            b'-' if bytes.get(index + 1) == Some(&b'-') => {
+                index = find_from(bytes, index + 2, b"\n").map_or(bytes.len(), |end| end + 1);
+            }
+            b'/' if bytes.get(index + 1) == Some(&b'*') => {
+                index = find_from(bytes, index + 2, b"*/").map_or(bytes.len(), |end| end + 2);
+            }
+            quote @ (b'\'' | b'"') => {
+                has_content = true;
+                // A doubled quote is an escaped quote and keeps us within the literal. Treating it
+                // like two consecutive literals yields the same boundaries.
+                index = find_from(bytes, index + 1, &[quote]).map_or(bytes.len(), |end| end + 1);
+            }
+            b'[' => {
+                has_content = true;
+                index = find_from(bytes, index + 1, b"]").map_or(bytes.len(), |end| end + 1);
+            }
+            b'$' => {
+                has_content = true;
+                index = dollar_quote_end(bytes, index).unwrap_or(index + 1);
+            }
+            other => {
+                if !other.is_ascii_whitespace() {
+                    has_content = true;
+                }
+                index += 1;
+            }
+        }
+    }
+    if has_content {
+        statements.push(script_statement(script, start, bytes.len(), &mut lines));
+    }
+    statements
+}
+
+/// Trims the statement in `script[start..end]` and determines the line it starts in.
+fn script_statement<'s>(
+    script: &'s str,
+    start: usize,
+    end: usize,
+    lines: &mut LineCounter,
+) -> ScriptStatement<'s> {
+    let raw = &script[start..end];
+    let offset = start + raw.len() - raw.trim_start().len();
+    ScriptStatement {
+        text: raw.trim(),
+        line: lines.line_at(script.as_bytes(), offset),
+    }
+}
+
+/// Determines line numbers for increasing offsets, so each part of the script is only scanned for
+/// line breaks once.
+struct LineCounter {
+    /// Offset up to which line breaks have been counted.
+    offset: usize,
+    /// Line `offset` is in.
+    line: usize,
+}
+
+impl Default for LineCounter {
+    fn default() -> Self {
+        Self { offset: 0, line: 1 }
+    }
+}
+
+impl LineCounter {
+    /// Line the byte at `offset` is in. `offset` must not be smaller than in the previous call.
+    fn line_at(&mut self, bytes: &[u8], offset: usize) -> usize {
+        self.line += bytes[self.offset..offset]
+            .iter()
+            .filter(|&&b| b == b'\n')
+            .count();
+        self.offset = offset;
+        self.line
+    }
+}
+
+/// Position of the first occurrence of `pattern` in `bytes` at or after `from`.
+fn find_from(bytes: &[u8], from: usize, pattern: &[u8]) -> Option<usize> {
+    bytes
+        .get(from..)?
+        .windows(pattern.len())
+        .position(|window| window == pattern)
+        .map(|pos| pos + from)
+}
+
+/// If a PostgreSQL dollar quoted string starts at `start`, returns the index just past its end.
+fn dollar_quote_end(bytes: &[u8], start: usize) -> Option<usize> {
+    // Tag may be empty (`$$`) or consist of identifier characters (`$tag$`).
+    let tag_len = bytes[start + 1..]
+        .iter()
+        .position(|&b| !(b.is_ascii_alphanumeric() || b == b'_'))?;
+    if bytes.get(start + 1 + tag_len) != Some(&b'$') {
+        return None;
+    }
+    let tag = &bytes[start..start + tag_len + 2];
+    let body_start = start + tag.len();
+    let end = find_from(bytes, body_start, tag).map_or(bytes.len(), |end| end + tag.len());
+    Some(end)
+}
+
+/// Executes `script` on `statement` according to `mode` and reports one outcome for each result
+/// generated by the data source.
+pub(crate) fn execute_batch(
+    statement: &mut StatementImpl<'_>,
+    script: &str,
+    mode: BatchMode,
+) -> Result<Vec<StatementOutcome>, Error> {
+    let mut outcomes = Vec::new();
+    match mode {
+        BatchMode::Driver => execute_and_collect(statement, script, &mut outcomes)?,
+        BatchMode::Split => {
+            for query in split_sql_statements(script) {
+                execute_and_collect(statement, query, &mut outcomes)?;
+            }
+        }
+    }
+    Ok(outcomes)
+}
+
+/// Emitted by [`crate::Connection::execute_script`]. Points to the statement of the script which
+/// failed, so migrations can report it and resume after fixing it.
+#[derive(Debug, ThisError)]
+#[error(
+    "Executing the statement starting in line {line} of the script failed. The \
+    {statement_index} statements before it have been executed successfully. Cause:\n{source}"
+)]
+pub struct ScriptError {
+    /// Zero based index of the failed statement within the statements of the script. All
+    /// statements before it have been executed successfully.
+    pub statement_index: usize,
+    /// Line of the script the failed statement starts in. The first line is `1`.
+    pub line: usize,
+    /// Text of the failed statement.
+    pub statement: String,
+    /// Outcomes of the statements executed successfully.
+    pub outcomes: Vec<StatementOutcome>,
+    /// The error reported by the data source.
+    pub source: Error,
+}
+
+/// Splits `script` at each `delimiter` and executes the statements one after another, stopping at
+/// the first one which fails.
+pub(crate) fn execute_script(
+    statement: &mut StatementImpl<'_>,
+    script: &str,
+    delimiter: &ScriptDelimiter,
+) -> Result<Vec<StatementOutcome>, ScriptError> {
+    let mut outcomes = Vec::new();
+    for (statement_index, query) in split_sql_script(script, delimiter).into_iter().enumerate() {
+        if let Err(source) = execute_and_collect(statement, query.text, &mut outcomes) {
+            return Err(ScriptError {
+                statement_index,
+                line: query.line,
+                statement: query.text.to_owned(),
+                outcomes,
+                source,
+            });
+        }
+    }
+    Ok(outcomes)
+}
+
+/// Executes `query` directly and pushes an outcome for each of its results into `outcomes`.
+pub(crate) fn execute_and_collect(
+    stmt: &mut StatementImpl<'_>,
+    query: &str,
+    outcomes: &mut Vec<StatementOutcome>,
+) -> Result<(), Error> {
+    let query = SqlText::new(query);
+    // `NoData` is returned e.g. by `UPDATE` statements which did not affect any row. No parameters
+    // are bound, so we never expect `NeedData`.
+    unsafe { stmt.exec_direct(&query) }.into_result_with(&*stmt, false, Some(()), None)?;
+    loop {
+        let num_cols = stmt.num_result_cols().into_result(&*stmt)?;
+        let outcome = if num_cols > 0 {
+            StatementOutcome::ResultSet {
+                num_cols: num_cols.try_into().unwrap(),
+            }
+        } else {
+            StatementOutcome::RowCount(row_count(&*stmt)?)
+        };
+        outcomes.push(outcome);
+        // Discards any rows of the current result set and advances to the next one.
+        if !unsafe { stmt.more_results() }.into_result_bool(&*stmt)? {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split_sql_script, split_sql_statements, ScriptDelimiter, ScriptStatement};
+
+    #[test]
+    fn split_simple_statements() {
+        let statements = split_sql_statements("SELECT 1; SELECT 2;\n SELECT 3");
+        assert_eq!(vec!["SELECT 1", "SELECT 2", "SELECT 3"], statements);
+    }
+
+    #[test]
+    fn skip_empty_statements() {
+        let statements = split_sql_statements(";; SELECT 1;  ;\n-- only a comment;\n/* ; */");
+        assert_eq!(vec!["SELECT 1"], statements);
+    }
+
+    #[test]
+    fn ignore_semicolons_in_literals_and_identifiers() {
+        let script = "SELECT 'a;''b' AS \"c;d\", [e;f] FROM t; SELECT 2";
+        let statements = split_sql_statements(script);
+        assert_eq!(
+            vec!["SELECT 'a;''b' AS \"c;d\", [e;f] FROM t", "SELECT 2"],
+            statements
+        );
+    }
+
+    #[test]
+    fn ignore_semicolons_in_comments() {
+        let script = "SELECT 1 -- a;b\n; SELECT /* c;d */ 2";
+        let statements = split_sql_statements(script);
+        assert_eq!(vec!["SELECT 1 -- a;b", "SELECT /* c;d */ 2"], statements);
+    }
+
+    #[test]
+    fn ignore_semicolons_in_dollar_quotes() {
+        let script = "SELECT $$a;b$$; SELECT $tag$c;$$;d$tag$; SELECT $1";
+        let statements = split_sql_statements(script);
+        assert_eq!(
+            vec!["SELECT $$a;b$$", "SELECT $tag$c;$$;d$tag$", "SELECT $1"],
+            statements
+        );
+    }
+
+    #[test]
+    fn report_line_of_each_statement() {
+        let script = "\n-- Migration 1\nSELECT 1;\n\n  SELECT\n2; SELECT 3";
+        let statements = split_sql_script(script, &ScriptDelimiter::Semicolon);
+        assert_eq!(
+            vec![
+                ScriptStatement {
+                    text: "-- Migration 1\nSELECT 1",
+                    line: 2
+                },
+                ScriptStatement {
+                    text: "SELECT\n2",
+                    line: 5
+                },
+                ScriptStatement {
+                    text: "SELECT 3",
+                    line: 6
+                },
+            ],
+            statements
+        );
+    }
+
+    #[test]
+    fn split_at_batch_separator_lines() {
+        let script = "SELECT 'GO';\nSELECT 1\n  go  \r\n/*\nGO\n*/ SELECT 2; SELECT 3\nGOTO\nGO";
+        let texts: Vec<_> = split_sql_script(script, &ScriptDelimiter::Line("GO".to_owned()))
+            .into_iter()
+            .map(|statement| statement.text)
+            .collect();
+        assert_eq!(
+            vec![
+                "SELECT 'GO';\nSELECT 1",
+                "/*\nGO\n*/ SELECT 2; SELECT 3\nGOTO"
+            ],
+            texts
+        );
+    }
+}
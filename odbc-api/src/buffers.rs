@@ -2,12 +2,28 @@
 
 mod any_buffer;
 mod bin_column;
+mod bin_encoding;
+mod caller_owned;
+mod cell_mut;
+mod cell_transform;
+mod cell_value;
+mod charset_validation;
+mod coercion;
+mod column_statistics;
 mod column_with_indicator;
 mod columnar;
+#[cfg(any(feature = "lz4", feature = "zstd"))]
+mod compression;
+mod decimal_column;
 mod description;
+mod growth;
 mod indicator;
 mod item;
+mod result_hash;
+mod row;
+mod serialization;
 mod text_column;
+mod validity;
 
 #[allow(deprecated)]
 pub use self::{
@@ -16,12 +32,33 @@ pub use self::{
         ColumnarAnyBuffer,
     },
     bin_column::{BinColumn, BinColumnIt, BinColumnSliceMut, BinColumnView},
+    caller_owned::{CallerOwnedBuffer, CallerOwnedColumn},
+    cell_mut::{CellMut, CellsMut},
+    cell_transform::{CellTransforms, RowTransform},
+    cell_value::{CellValue, InvalidCellConversion},
+    charset_validation::{CharsetFinding, CharsetIssue, CharsetReport},
+    coercion::{CoerceTo, TypeClass, TypeCoercion},
+    column_statistics::{ColumnStatistics, NumericRange},
     column_with_indicator::{NullableSlice, NullableSliceMut},
     columnar::{ColumnBuffer, ColumnarBuffer, TextRowSet},
-    description::{BufferDesc, BufferDescription, BufferKind},
+    decimal_column::{
+        DecimalColumn, DecimalColumnIt, DecimalColumnSliceMut, DecimalColumnView, DecimalOverflow,
+        InvalidDecimal,
+    },
+    description::{max_str_len_with_limit, BufferDesc, BufferDescription, BufferKind, TextBinding},
+    growth::{GrowableColumn, GrowthPolicy},
     indicator::Indicator,
     item::Item,
+    result_hash::{ResultSetHasher, RowOrder},
+    row::Row,
     text_column::{
-        CharColumn, TextColumn, TextColumnIt, TextColumnSliceMut, TextColumnView, WCharColumn,
+        CharColumn, TextColumn, TextColumnIt, TextColumnSliceMut, TextColumnStrIt, TextColumnView,
+        WCharColumn,
     },
+    validity::ValidityBitmap,
 };
+
+#[cfg(any(feature = "lz4", feature = "zstd"))]
+pub use self::compression::{CompressedBatch, Compression};
+
+pub(crate) use self::cell_value::cell_to_text;
@@ -6,7 +6,7 @@ use crate::{
     columnar_bulk_inserter::BoundInputSlice,
     error::TooLargeBufferSize,
     handles::{CData, CDataMut, HasDataType, StatementRef},
-    Bit, DataType, Error,
+    Bit, DataType, Error, FetchStatistics,
 };
 
 use super::{
@@ -94,6 +94,11 @@ impl AnyBuffer {
     }
 
     /// Map buffer description to actual buffer.
+    ///
+    /// Panics, or aborts the process, if not enough memory is available for the allocation.
+    /// Buffer sizes are often derived from metadata reported by the data source, which may be
+    /// huge, so prefer [`Self::try_from_desc`].
+    #[deprecated = "Use try_from_desc instead, which reports too large buffers as error"]
     pub fn from_desc(max_rows: usize, desc: BufferDesc) -> Self {
         let fallible_allocations = false;
         Self::impl_from_desc(max_rows, desc, fallible_allocations).unwrap()
@@ -327,7 +332,7 @@ pub type ColumnarAnyBuffer = ColumnarBuffer<AnyBuffer>;
 
 impl ColumnarAnyBuffer {
     /// Allocates a [`ColumnarBuffer`] fitting the buffer descriptions.
-    #[deprecated = "Use from_descs instead"]
+    #[deprecated = "Use try_from_descs instead"]
     #[allow(deprecated)]
     pub fn from_description(
         capacity: usize,
@@ -337,6 +342,12 @@ impl ColumnarAnyBuffer {
     }
 
     /// Allocates a [`ColumnarBuffer`] fitting the buffer descriptions.
+    ///
+    /// Panics, or aborts the process, if not enough memory is available for the allocation.
+    /// Buffer sizes are often derived from metadata reported by the data source, which may be
+    /// huge, so prefer [`Self::try_from_descs`].
+    #[deprecated = "Use try_from_descs instead, which reports too large buffers as error"]
+    #[allow(deprecated)]
     pub fn from_descs(capacity: usize, descs: impl IntoIterator<Item = BufferDesc>) -> Self {
         let mut column_index = 0;
         let columns = descs
@@ -365,8 +376,8 @@ impl ColumnarAnyBuffer {
 
     /// Allocates a [`ColumnarBuffer`] fitting the buffer descriptions. If not enough memory is
     /// available to allocate the buffers this function fails with
-    /// [`Error::TooLargeColumnBufferSize`]. This function is slower than [`Self::from_description`]
-    /// which would just panic if not enough memory is available for allocation.
+    /// [`Error::TooLargeColumnBufferSize`], rather than panicking or aborting the process. The error
+    /// tells which buffer could not be allocated.
     pub fn try_from_descs(
         capacity: usize,
         descs: impl IntoIterator<Item = BufferDesc>,
@@ -388,7 +399,7 @@ impl ColumnarAnyBuffer {
     /// the column, the buffer is supposed to bind to. This allows you also to ignore columns in a
     /// result set, by not binding them at all. There is no restriction on the order of column
     /// indices passed, but the function will panic, if the indices are not unique.
-    #[deprecated = "use from_descs_and_indices"]
+    #[deprecated = "use try_from_descs_and_indices"]
     #[allow(deprecated)]
     pub fn from_description_and_indices(
         max_rows: usize,
@@ -404,6 +415,11 @@ impl ColumnarAnyBuffer {
     /// the column, the buffer is supposed to bind to. This allows you also to ignore columns in a
     /// result set, by not binding them at all. There is no restriction on the order of column
     /// indices passed, but the function will panic, if the indices are not unique.
+    ///
+    /// Panics, or aborts the process, if not enough memory is available for the allocation.
+    /// Prefer [`Self::try_from_descs_and_indices`].
+    #[deprecated = "Use try_from_descs_and_indices instead, which reports too large buffers as error"]
+    #[allow(deprecated)]
     pub fn from_descs_and_indices(
         max_rows: usize,
         description: impl Iterator<Item = (u16, BufferDesc)>,
@@ -425,6 +441,29 @@ impl ColumnarAnyBuffer {
 
         ColumnarBuffer::new(columns)
     }
+
+    /// Like [`Self::try_from_descs`], but binds each buffer to the one based column index passed
+    /// together with its description. Columns of the result set may be ignored by not binding them
+    /// at all. If not enough memory is available to allocate the buffers this function fails with
+    /// [`Error::TooLargeColumnBufferSize`].
+    ///
+    /// # Panics
+    ///
+    /// If the indices are not unique.
+    pub fn try_from_descs_and_indices(
+        max_rows: usize,
+        description: impl Iterator<Item = (u16, BufferDesc)>,
+    ) -> Result<ColumnarBuffer<AnyBuffer>, Error> {
+        let columns: Vec<_> = description
+            .enumerate()
+            .map(|(buffer_index, (col_index, buffer_desc))| {
+                let buffer = AnyBuffer::try_from_desc(max_rows, buffer_desc)
+                    .map_err(|source| source.add_context(buffer_index.try_into().unwrap()))?;
+                Ok((col_index, buffer))
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok(ColumnarBuffer::new(columns))
+    }
 }
 
 #[deprecated(note = "Use new name `AnySlice` instead")]
@@ -435,6 +474,26 @@ pub type AnyColumnView<'a> = AnySlice<'a>;
 /// For columns of fixed size types, which are guaranteed to not contain null, a direct access to
 /// the slice is offered. Buffers over nullable columns can be accessed via an iterator over
 /// options.
+///
+/// Code which only expects one kind of column can use accessors like [`Self::as_text_view`],
+/// [`Self::as_slice`] or [`Self::as_nullable_slice`] instead of matching all variants.
+///
+/// ```
+/// use odbc_api::buffers::AnySlice;
+///
+/// /// Sum of a column, which may or may not be nullable. `None` for other types.
+/// fn sum(view: AnySlice<'_>) -> Option<f64> {
+///     if let Some(values) = view.as_slice::<f64>() {
+///         Some(values.iter().sum())
+///     } else {
+///         view.as_nullable_slice::<f64>()
+///             .map(|values| values.flatten().sum())
+///     }
+/// }
+///
+/// assert_eq!(Some(3.5), sum(AnySlice::F64(&[1.0, 2.5])));
+/// assert_eq!(None, sum(AnySlice::I32(&[1, 2])));
+/// ```
 #[derive(Debug, Clone, Copy)]
 pub enum AnySlice<'a> {
     /// Nullable character data in the system encoding.
@@ -662,6 +721,66 @@ unsafe impl ColumnBuffer for AnyBuffer {
         }
     }
 
+    fn memory_usage(&self) -> usize {
+        match self {
+            AnyBuffer::Binary(col) => col.memory_usage(),
+            AnyBuffer::Text(col) => col.memory_usage(),
+            AnyBuffer::WText(col) => col.memory_usage(),
+            AnyBuffer::Date(col) => col.memory_usage(),
+            AnyBuffer::Time(col) => col.memory_usage(),
+            AnyBuffer::Timestamp(col) => col.memory_usage(),
+            AnyBuffer::F64(col) => col.memory_usage(),
+            AnyBuffer::F32(col) => col.memory_usage(),
+            AnyBuffer::I8(col) => col.memory_usage(),
+            AnyBuffer::I16(col) => col.memory_usage(),
+            AnyBuffer::I32(col) => col.memory_usage(),
+            AnyBuffer::I64(col) => col.memory_usage(),
+            AnyBuffer::U8(col) => col.memory_usage(),
+            AnyBuffer::Bit(col) => col.memory_usage(),
+            AnyBuffer::NullableDate(col) => col.memory_usage(),
+            AnyBuffer::NullableTime(col) => col.memory_usage(),
+            AnyBuffer::NullableTimestamp(col) => col.memory_usage(),
+            AnyBuffer::NullableF64(col) => col.memory_usage(),
+            AnyBuffer::NullableF32(col) => col.memory_usage(),
+            AnyBuffer::NullableI8(col) => col.memory_usage(),
+            AnyBuffer::NullableI16(col) => col.memory_usage(),
+            AnyBuffer::NullableI32(col) => col.memory_usage(),
+            AnyBuffer::NullableI64(col) => col.memory_usage(),
+            AnyBuffer::NullableU8(col) => col.memory_usage(),
+            AnyBuffer::NullableBit(col) => col.memory_usage(),
+        }
+    }
+
+    fn record_statistics(&self, valid_rows: usize, statistics: &mut FetchStatistics) {
+        match self {
+            AnyBuffer::Binary(col) => col.record_statistics(valid_rows, statistics),
+            AnyBuffer::Text(col) => col.record_statistics(valid_rows, statistics),
+            AnyBuffer::WText(col) => col.record_statistics(valid_rows, statistics),
+            AnyBuffer::Date(col) => col.record_statistics(valid_rows, statistics),
+            AnyBuffer::Time(col) => col.record_statistics(valid_rows, statistics),
+            AnyBuffer::Timestamp(col) => col.record_statistics(valid_rows, statistics),
+            AnyBuffer::F64(col) => col.record_statistics(valid_rows, statistics),
+            AnyBuffer::F32(col) => col.record_statistics(valid_rows, statistics),
+            AnyBuffer::I8(col) => col.record_statistics(valid_rows, statistics),
+            AnyBuffer::I16(col) => col.record_statistics(valid_rows, statistics),
+            AnyBuffer::I32(col) => col.record_statistics(valid_rows, statistics),
+            AnyBuffer::I64(col) => col.record_statistics(valid_rows, statistics),
+            AnyBuffer::U8(col) => col.record_statistics(valid_rows, statistics),
+            AnyBuffer::Bit(col) => col.record_statistics(valid_rows, statistics),
+            AnyBuffer::NullableDate(col) => col.record_statistics(valid_rows, statistics),
+            AnyBuffer::NullableTime(col) => col.record_statistics(valid_rows, statistics),
+            AnyBuffer::NullableTimestamp(col) => col.record_statistics(valid_rows, statistics),
+            AnyBuffer::NullableF64(col) => col.record_statistics(valid_rows, statistics),
+            AnyBuffer::NullableF32(col) => col.record_statistics(valid_rows, statistics),
+            AnyBuffer::NullableI8(col) => col.record_statistics(valid_rows, statistics),
+            AnyBuffer::NullableI16(col) => col.record_statistics(valid_rows, statistics),
+            AnyBuffer::NullableI32(col) => col.record_statistics(valid_rows, statistics),
+            AnyBuffer::NullableI64(col) => col.record_statistics(valid_rows, statistics),
+            AnyBuffer::NullableU8(col) => col.record_statistics(valid_rows, statistics),
+            AnyBuffer::NullableBit(col) => col.record_statistics(valid_rows, statistics),
+        }
+    }
+
     fn view(&self, valid_rows: usize) -> AnySlice {
         match self {
             AnyBuffer::Binary(col) => AnySlice::Binary(col.view(valid_rows)),
@@ -726,9 +845,11 @@ unsafe impl ColumnBuffer for AnyBuffer {
 
 #[cfg(test)]
 mod tests {
-    use crate::buffers::{AnySlice, AnySliceMut, ColumnBuffer};
+    use std::mem::size_of;
+
+    use crate::buffers::{AnySlice, AnySliceMut, CharColumn, ColumnBuffer, TextColumn};
 
-    use super::AnyBuffer;
+    use super::{AnyBuffer, OptI32Column};
 
     #[test]
     fn slice_should_only_contain_part_of_the_buffer() {
@@ -739,6 +860,19 @@ mod tests {
         assert_eq!(Some([1, 2].as_slice()), view.as_slice::<i32>());
     }
 
+    #[test]
+    fn memory_usage_includes_values_and_indicators() {
+        let buffer = AnyBuffer::I32(vec![1, 2, 3]);
+        assert_eq!(12, buffer.memory_usage());
+
+        let buffer = AnyBuffer::NullableI32(OptI32Column::new(3));
+        assert_eq!(12 + 3 * size_of::<isize>(), buffer.memory_usage());
+
+        // Two strings of up to four characters, each with terminating zero.
+        let buffer = AnyBuffer::Text(CharColumn::new(2, 4));
+        assert_eq!(10 + 2 * size_of::<isize>(), buffer.memory_usage());
+    }
+
     #[test]
     fn slice_should_be_none_if_types_mismatch() {
         let buffer = [1, 2, 3];
@@ -753,6 +887,35 @@ mod tests {
         assert_eq!(None, view.as_slice::<i16>());
     }
 
+    #[test]
+    fn nullable_slice_get_and_iterate_by_reference() {
+        let mut buffer = AnyBuffer::NullableI32(OptI32Column::new(3));
+        if let AnyBuffer::NullableI32(column) = &mut buffer {
+            column.writer_n(2).write([Some(1), None].into_iter());
+        }
+
+        let slice = buffer.view(2).as_nullable_slice::<i32>().unwrap();
+
+        assert_eq!(Some(&1), slice.get(0));
+        assert_eq!(None, slice.get(1));
+        assert_eq!(
+            vec![Some(&1), None],
+            (&slice).into_iter().collect::<Vec<_>>()
+        );
+        // Iterating by reference leaves the original slice intact.
+        assert_eq!(2, slice.len());
+    }
+
+    #[test]
+    fn text_view_should_be_none_for_other_kinds() {
+        let buffer = AnyBuffer::WText(TextColumn::new(2, 4));
+        let view = buffer.view(2);
+
+        assert!(view.as_text_view().is_none());
+        assert!(view.as_bin_view().is_none());
+        assert_eq!(2, view.as_w_text_view().unwrap().len());
+    }
+
     #[test]
     fn nullable_slice_should_be_none_if_buffer_is_non_nullable() {
         let buffer = [1, 2, 3];
@@ -1,14 +1,16 @@
 use crate::{
-    buffers::Indicator,
+    buffers::{CellsMut, GrowthPolicy, Indicator},
     columnar_bulk_inserter::BoundInputSlice,
     error::TooLargeBufferSize,
     handles::{CData, CDataMut, HasDataType, Statement, StatementRef},
-    DataType, Error,
+    DataType, Error, FetchStatistics,
 };
 
+use super::bin_encoding::{push_base64, push_hex};
+
 use log::debug;
 use odbc_sys::{CDataType, NULL_DATA};
-use std::{cmp::min, ffi::c_void};
+use std::{cmp::min, ffi::c_void, mem::size_of};
 
 /// A buffer intended to be bound to a column of a cursor. Elements of the buffer will contain a
 /// variable amount of bytes up to a maximum length. Since elements of this type have variable
@@ -33,7 +35,7 @@ impl BinColumn {
     /// types like VARBINARY(MAX), or IMAGE. On the downside, this method is potentially slower than
     /// new.
     pub fn try_new(batch_size: usize, element_size: usize) -> Result<Self, TooLargeBufferSize> {
-        let len = element_size * batch_size;
+        let len = TooLargeBufferSize::checked_len::<u8>(batch_size, element_size)?;
         let mut values = Vec::new();
         values
             .try_reserve_exact(len)
@@ -52,7 +54,8 @@ impl BinColumn {
     /// This will allocate a value and indicator buffer for `batch_size` elements. Each value may
     /// have a maximum length of `max_len`.
     pub fn new(batch_size: usize, element_size: usize) -> Self {
-        let len = element_size * batch_size;
+        let len = TooLargeBufferSize::checked_len::<u8>(batch_size, element_size)
+            .expect("Binary column buffer must not exceed the limits of the platform.");
         let mut values = Vec::new();
         values.reserve_exact(len);
         values.resize(len, 0);
@@ -110,7 +113,9 @@ impl BinColumn {
     pub fn set_max_len(&mut self, new_max_len: usize) {
         let batch_size = self.indicators.len();
         // Allocate a new buffer large enough to hold a batch of strings with maximum length.
-        let new_values = vec![0u8; new_max_len * batch_size];
+        let len = TooLargeBufferSize::checked_len::<u8>(batch_size, new_max_len)
+            .expect("Binary column buffer must not exceed the limits of the platform.");
+        let new_values = vec![0u8; len];
         // Set all indicators to NULL
         self.fill_null(0, batch_size);
         self.values = new_values;
@@ -122,6 +127,21 @@ impl BinColumn {
         self.max_len
     }
 
+    /// Mutable access to the first `num_rows` elements, e.g. to transform fetched values in place
+    /// before inserting them into another table.
+    ///
+    /// # Panics
+    ///
+    /// If `num_rows` is larger than the capacity of the buffer.
+    pub fn iter_mut(&mut self, num_rows: usize) -> CellsMut<'_, u8> {
+        CellsMut::new(
+            &mut self.values,
+            &mut self.indicators[..num_rows],
+            self.max_len,
+            self.max_len,
+        )
+    }
+
     /// View of the first `num_rows` values of a binary column.
     ///
     /// Num rows may not exceed the actually amount of valid num_rows filled be the ODBC API. The
@@ -141,22 +161,42 @@ impl BinColumn {
     /// element length.
     pub fn set_value(&mut self, index: usize, input: Option<&[u8]>) {
         if let Some(input) = input {
-            self.indicators[index] = input.len().try_into().unwrap();
-            if input.len() > self.max_len {
-                panic!(
-                    "Tried to insert a value into a binary buffer which is larger than the maximum \
-                    allowed element length for the buffer."
-                );
-            }
-            let start = self.max_len * index;
-            let end = start + input.len();
-            let buf = &mut self.values[start..end];
-            buf.copy_from_slice(input);
+            self.set_mut(index, input.len()).copy_from_slice(input);
         } else {
             self.indicators[index] = NULL_DATA;
         }
     }
 
+    /// Can be used to set a value at a specific row index without performing a memcopy on an input
+    /// slice and instead provides direct access to the underlying buffer.
+    ///
+    /// In situations there the memcopy can not be avoided anyway [`Self::set_value`] is likely to
+    /// be more convenient. This method is very useful if you want to write a value of known length
+    /// into the buffer, without materializing it first. E.g. a hash or a serialized key.
+    ///
+    /// # Example: Write big endian integer to binary column.
+    ///
+    /// ```
+    /// use odbc_api::buffers::BinColumn;
+    ///
+    /// fn write_key(col: &mut BinColumn, index: usize, key: u64) {
+    ///     col.set_mut(index, 8).copy_from_slice(&key.to_be_bytes());
+    /// }
+    /// ```
+    pub fn set_mut(&mut self, index: usize, length: usize) -> &mut [u8] {
+        if length > self.max_len {
+            panic!(
+                "Tried to insert a value into a binary buffer which is larger than the maximum \
+                allowed element length for the buffer."
+            );
+        }
+        self.indicators[index] =
+            isize::try_from(length).expect("Length of value must fit into its indicator.");
+        let start = self.max_len * index;
+        let end = start + length;
+        &mut self.values[start..end]
+    }
+
     /// Fills the column with NULL, between From and To
     pub fn fill_null(&mut self, from: usize, to: usize) {
         for index in from..to {
@@ -188,7 +228,9 @@ impl BinColumn {
 
         let batch_size = self.indicators.len();
         // Allocate a new buffer large enough to hold a batch of elements with maximum length.
-        let mut new_values = vec![0; new_max_len * batch_size];
+        let len = TooLargeBufferSize::checked_len::<u8>(batch_size, new_max_len)
+            .expect("Binary column buffer must not exceed the limits of the platform.");
+        let mut new_values = vec![0; len];
         // Copy values from old to new buffer.
         let max_copy_length = min(self.max_len, new_max_len);
         for ((&indicator, old_value), new_value) in self
@@ -228,23 +270,49 @@ impl BinColumn {
     pub fn append(&mut self, index: usize, bytes: Option<&[u8]>) {
         if let Some(bytes) = bytes {
             if bytes.len() > self.max_len {
-                let new_max_len = (bytes.len() as f64 * 1.2) as usize;
+                let new_max_len = GrowthPolicy::Factor(1.2)
+                    .new_max_len(self.max_len, bytes.len())
+                    .expect("Growing by a factor always succeeds");
                 self.resize_max_element_length(new_max_len, index)
             }
 
             let offset = index * self.max_len;
             self.values[offset..offset + bytes.len()].copy_from_slice(bytes);
             // And of course set the indicator correctly.
-            self.indicators[index] = bytes.len().try_into().unwrap();
+            self.indicators[index] =
+                isize::try_from(bytes.len()).expect("Length of value must fit into its indicator.");
         } else {
             self.indicators[index] = NULL_DATA;
         }
     }
 
+    /// Provides access to the raw underlying value buffer. Normal applications should have little
+    /// reason to call this method. Yet it may be useful for writing bindings which copy directly
+    /// from the ODBC in memory representation into other kinds of buffers.
+    ///
+    /// The buffer contains the bytes for every non null valid element, padded to the maximum
+    /// element length. The content of the padding bytes is undefined. For the actual value length
+    /// call [`Self::content_length_at`]. Any element starts at index * [`Self::max_len`].
+    pub fn raw_value_buffer(&self, num_valid_rows: usize) -> &[u8] {
+        &self.values[..self.max_len * num_valid_rows]
+    }
+
     /// Maximum number of elements this buffer can hold.
     pub fn capacity(&self) -> usize {
         self.indicators.len()
     }
+
+    /// Number of bytes allocated for values and indicators.
+    pub fn memory_usage(&self) -> usize {
+        self.values.len() + self.indicators.len() * size_of::<isize>()
+    }
+
+    /// Accounts for the first `num_rows` values in `statistics`.
+    pub(crate) fn record_statistics(&self, num_rows: usize, statistics: &mut FetchStatistics) {
+        for &indicator in &self.indicators[..num_rows] {
+            statistics.record_cell(Indicator::from_isize(indicator), self.max_len);
+        }
+    }
 }
 
 unsafe impl<'a> BoundInputSlice<'a> for BinColumn {
@@ -281,6 +349,11 @@ impl<'a> BinColumnSliceMut<'a> {
         self.column.set_value(row_index, element)
     }
 
+    /// Maximum length of elements in bytes, which can be held without resizing the buffer.
+    pub fn max_len(&self) -> usize {
+        self.column.max_len()
+    }
+
     /// Ensures that the buffer is large enough to hold elements of `element_length`. Does nothing
     /// if the buffer is already large enough. Otherwise it will reallocate and rebind the buffer.
     /// The first `num_rows_to_copy_elements` will be copied from the old value buffer to the new
@@ -289,13 +362,28 @@ impl<'a> BinColumnSliceMut<'a> {
         &mut self,
         element_length: usize,
         num_rows_to_copy: usize,
+    ) -> Result<(), Error> {
+        self.grow_to_fit(element_length, num_rows_to_copy, GrowthPolicy::Exact)
+    }
+
+    /// Like [`Self::ensure_max_element_length`], but `policy` decides the new maximum element
+    /// length, should the buffer need to grow. Fails with [`Error::TooLargeValueForBuffer`] if the
+    /// policy does not allow the buffer to grow.
+    pub fn grow_to_fit(
+        &mut self,
+        element_length: usize,
+        num_rows_to_copy: usize,
+        policy: GrowthPolicy,
     ) -> Result<(), Error> {
         // Column buffer is not large enough to hold the element. We must allocate a larger buffer
         // in order to hold it. This invalidates the pointers previously bound to the statement. So
         // we rebind them.
         if element_length > self.column.max_len() {
+            let new_max_len = policy
+                .new_max_len(self.column.max_len(), element_length)
+                .ok_or(Error::TooLargeValueForBuffer)?;
             self.column
-                .resize_max_element_length(element_length, num_rows_to_copy);
+                .resize_max_element_length(new_max_len, num_rows_to_copy);
             unsafe {
                 self.stmt
                     .bind_input_parameter(self.parameter_index, self.column)
@@ -304,6 +392,19 @@ impl<'a> BinColumnSliceMut<'a> {
         }
         Ok(())
     }
+
+    /// Can be used to set a value at a specific row index without performing a memcopy on an input
+    /// slice and instead provides direct access to the underlying buffer. See
+    /// [`BinColumn::set_mut`]. Use [`Self::ensure_max_element_length`] first, should `length`
+    /// exceed the maximum element length of the buffer.
+    pub fn set_mut(&mut self, index: usize, length: usize) -> &mut [u8] {
+        self.column.set_mut(index, length)
+    }
+
+    /// Mutable access to the first `num_rows` elements. See [`BinColumn::iter_mut`].
+    pub fn iter_mut(&mut self, num_rows: usize) -> CellsMut<'_, u8> {
+        self.column.iter_mut(num_rows)
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -323,8 +424,15 @@ impl<'c> BinColumnView<'c> {
         self.num_rows == 0
     }
 
-    /// Slice of text at the specified row index without terminating zero.
+    /// Value at the specified row index. `None` if the value is `NULL`.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is not smaller than [`Self::len`].
     pub fn get(&self, index: usize) -> Option<&'c [u8]> {
+        if index >= self.num_rows {
+            panic!("Row index points beyond the range of valid values.")
+        }
         self.col.value_at(index)
     }
 
@@ -336,6 +444,107 @@ impl<'c> BinColumnView<'c> {
             col: self.col,
         }
     }
+
+    /// Length of value at the specified position. This is different from an indicator as it refers
+    /// to the length of the value in the buffer, not to the length of the value in the datasource.
+    /// The two things are different for truncated values.
+    pub fn content_length_at(&self, row_index: usize) -> Option<usize> {
+        if row_index >= self.num_rows {
+            panic!("Row index points beyond the range of valid values.")
+        }
+        self.col.content_length_at(row_index)
+    }
+
+    /// Provides access to the raw underlying value buffer. See [`BinColumn::raw_value_buffer`].
+    pub fn raw_value_buffer(&self) -> &'c [u8] {
+        self.col.raw_value_buffer(self.num_rows)
+    }
+
+    /// Maximum length of elements in bytes.
+    pub fn max_len(&self) -> usize {
+        self.col.max_len()
+    }
+
+    /// Appends the value at `index` as lowercase hexadecimal digits to `out`. Returns `false` if
+    /// the value is `NULL`, in which case nothing is appended. Reusing `out` avoids allocating a
+    /// new `String` for each value, e.g. while writing binary keys into a log or a text buffer.
+    ///
+    /// ```
+    /// use odbc_api::buffers::BinColumnView;
+    ///
+    /// fn log_keys(keys: BinColumnView<'_>) {
+    ///     let mut line = String::new();
+    ///     for index in 0..keys.len() {
+    ///         line.clear();
+    ///         line.push_str("0x");
+    ///         if keys.push_hex(index, &mut line) {
+    ///             println!("{line}");
+    ///         } else {
+    ///             println!("NULL");
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn push_hex(&self, index: usize, out: &mut String) -> bool {
+        if let Some(bytes) = self.get(index) {
+            push_hex(bytes, out);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Appends the value at `index` encoded as base64 (standard alphabet, padded) to `out`. Returns
+    /// `false` if the value is `NULL`, in which case nothing is appended.
+    pub fn push_base64(&self, index: usize, out: &mut String) -> bool {
+        if let Some(bytes) = self.get(index) {
+            push_base64(bytes, out);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Iterator over the valid elements of the binary buffer as lowercase hexadecimal strings.
+    pub fn iter_hex(&self) -> impl Iterator<Item = Option<String>> + 'c {
+        self.iter().map(|value| {
+            value.map(|bytes| {
+                let mut text = String::new();
+                push_hex(bytes, &mut text);
+                text
+            })
+        })
+    }
+
+    /// Iterator over the valid elements of the binary buffer as base64 strings (standard alphabet,
+    /// padded).
+    pub fn iter_base64(&self) -> impl Iterator<Item = Option<String>> + 'c {
+        self.iter().map(|value| {
+            value.map(|bytes| {
+                let mut text = String::new();
+                push_base64(bytes, &mut text);
+                text
+            })
+        })
+    }
+}
+
+impl<'c> IntoIterator for BinColumnView<'c> {
+    type Item = Option<&'c [u8]>;
+    type IntoIter = BinColumnIt<'c>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'c> IntoIterator for &BinColumnView<'c> {
+    type Item = Option<&'c [u8]>;
+    type IntoIter = BinColumnIt<'c>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
 
 /// Iterator over a binary column. See [`crate::buffers::AnyColumnView`]
@@ -381,7 +590,7 @@ unsafe impl CData for BinColumn {
     }
 
     fn buffer_length(&self) -> isize {
-        self.max_len.try_into().unwrap()
+        isize::try_from(self.max_len).expect("Element length must fit into an ODBC length.")
     }
 }
 
@@ -405,10 +614,50 @@ unsafe impl CDataMut for BinColumn {
 
 #[cfg(test)]
 mod test {
-    use crate::error::TooLargeBufferSize;
+    use crate::{error::TooLargeBufferSize, sys::NULL_DATA};
 
     use super::BinColumn;
 
+    #[test]
+    fn write_value_in_place() {
+        let mut column = BinColumn::new(2, 4);
+        column.set_mut(0, 3).copy_from_slice(&[1, 2, 3]);
+        column.set_value(1, None);
+
+        assert_eq!(Some(&[1u8, 2, 3][..]), column.value_at(0));
+        assert_eq!(None, column.value_at(1));
+        assert_eq!(&[1, 2, 3], &column.raw_value_buffer(1)[..3]);
+        assert_eq!(8, column.raw_value_buffer(2).len());
+        assert_eq!(NULL_DATA, column.indicators[1]);
+    }
+
+    #[test]
+    fn view_values_as_text() {
+        let mut column = BinColumn::new(2, 4);
+        column.set_value(0, Some(&[0xde, 0xad, 0xbe, 0xef]));
+        column.set_value(1, None);
+        let view = column.view(2);
+
+        assert_eq!(
+            vec![Some("deadbeef".to_owned()), None],
+            view.iter_hex().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![Some("3q2+7w==".to_owned()), None],
+            view.iter_base64().collect::<Vec<_>>()
+        );
+        let mut out = String::new();
+        assert!(!view.push_hex(1, &mut out));
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_mut_beyond_max_len() {
+        let mut column = BinColumn::new(1, 4);
+        column.set_mut(0, 5);
+    }
+
     #[test]
     fn allocating_too_big_a_binary_column() {
         let two_gib = 2_147_483_648;
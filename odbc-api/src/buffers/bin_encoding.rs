@@ -0,0 +1,69 @@
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Standard alphabet of RFC 4648.
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Appends `bytes` as lowercase hexadecimal digits to `out`.
+pub fn push_hex(bytes: &[u8], out: &mut String) {
+    out.reserve(bytes.len() * 2);
+    for &byte in bytes {
+        out.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+        out.push(HEX_DIGITS[(byte & 0xf) as usize] as char);
+    }
+}
+
+/// Appends `bytes` encoded as base64 to `out`. Uses the standard alphabet and pads the output with
+/// `=`.
+pub fn push_base64(bytes: &[u8], out: &mut String) {
+    out.reserve(bytes.len() / 3 * 4 + 4);
+    let symbol = |index: u32| BASE64_ALPHABET[(index & 0x3f) as usize] as char;
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |acc, (pos, &byte)| {
+            acc | (byte as u32) << (16 - 8 * pos)
+        });
+        out.push(symbol(group >> 18));
+        out.push(symbol(group >> 12));
+        if chunk.len() > 1 {
+            out.push(symbol(group >> 6));
+        } else {
+            out.push('=');
+        }
+        if chunk.len() > 2 {
+            out.push(symbol(group));
+        } else {
+            out.push('=');
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{push_base64, push_hex};
+
+    fn base64(bytes: &[u8]) -> String {
+        let mut out = String::new();
+        push_base64(bytes, &mut out);
+        out
+    }
+
+    #[test]
+    fn hex() {
+        let mut out = "0x".to_owned();
+        push_hex(&[0, 1, 0xab, 0xff], &mut out);
+        assert_eq!("0x0001abff", out);
+    }
+
+    /// Test vectors of RFC 4648
+    #[test]
+    fn base64_test_vectors() {
+        assert_eq!("", base64(b""));
+        assert_eq!("Zg==", base64(b"f"));
+        assert_eq!("Zm8=", base64(b"fo"));
+        assert_eq!("Zm9v", base64(b"foo"));
+        assert_eq!("Zm9vYg==", base64(b"foob"));
+        assert_eq!("Zm9vYmE=", base64(b"fooba"));
+        assert_eq!("Zm9vYmFy", base64(b"foobar"));
+        assert_eq!("/+8=", base64(&[0xff, 0xef]));
+    }
+}
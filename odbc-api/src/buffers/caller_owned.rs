@@ -0,0 +1,263 @@
+use std::{collections::HashSet, ffi::c_void, mem::size_of};
+
+use odbc_sys::{CDataType, NULL_DATA};
+
+use crate::{
+    fixed_sized::Pod,
+    handles::{CData, CDataMut, Statement, StatementRef},
+    Error, RowSetBuffer,
+};
+
+use super::Indicator;
+
+/// A memory region owned by the application, which ODBC fills with the values of a single column.
+/// E.g. the value buffer of an Arrow array builder, or a slot in a shared memory ring buffer.
+/// Binding such regions with a [`CallerOwnedBuffer`] allows fetching values directly into their
+/// final destination, without copying them out of e.g. a [`crate::buffers::ColumnarAnyBuffer`].
+///
+/// The region holds the values only. Indicators, and therefore the information which values are
+/// `NULL`, are maintained by [`CallerOwnedBuffer`].
+///
+/// This trait is implemented for mutable slices of fixed sized types like `i32` or `f64`.
+///
+/// # Safety
+///
+/// * [`Self::as_mut_ptr`] must point to at least `capacity() * element_len()` bytes of writable
+///   memory.
+/// * The memory must stay valid and must not be moved, for as long as it is bound to a cursor.
+///   I.e. until the [`crate::BlockCursor`] it is bound to is unbound or dropped.
+/// * The owner of the memory must be fine with the driver writing any value of the C type
+///   described by [`Self::cdata_type`] into it, and must not read or write it, while a fetch is
+///   in progress.
+pub unsafe trait CallerOwnedColumn {
+    /// C type the driver converts the values of the column into.
+    fn cdata_type(&self) -> CDataType;
+
+    /// Length of a single element in bytes. For character data this must include the terminating
+    /// zero.
+    fn element_len(&self) -> usize;
+
+    /// Maximum number of elements the region can hold.
+    fn capacity(&self) -> usize;
+
+    /// Start of the memory region.
+    fn as_mut_ptr(&mut self) -> *mut c_void;
+}
+
+unsafe impl<T> CallerOwnedColumn for &mut [T]
+where
+    T: Pod,
+{
+    fn cdata_type(&self) -> CDataType {
+        T::C_DATA_TYPE
+    }
+
+    fn element_len(&self) -> usize {
+        size_of::<T>()
+    }
+
+    fn capacity(&self) -> usize {
+        self.len()
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut c_void {
+        <[T]>::as_mut_ptr(self) as *mut c_void
+    }
+}
+
+/// Binds memory regions provided by the application (see [`CallerOwnedColumn`]) to a cursor, in
+/// order to fetch values directly into them. This buffer holds the indicators of the columns. So
+/// while values are read from the regions themselves, use [`Self::indicator`] to learn which of
+/// them are `NULL`.
+///
+/// ```no_run
+/// use odbc_api::{buffers::CallerOwnedBuffer, Connection, Cursor, Error};
+///
+/// fn fetch_ids(conn: &Connection<'_>, ids: &mut [i64]) -> Result<usize, Error> {
+///     let cursor = conn.execute("SELECT id FROM Birthdays", ())?.unwrap();
+///     // Safety: `ids` is borrowed for the lifetime of the buffer, so it can not be moved or
+///     // accessed while bound.
+///     let buffer = unsafe { CallerOwnedBuffer::new(vec![(1, ids)]) };
+///     let mut block_cursor = cursor.bind_buffer(buffer)?;
+///     let num_rows = block_cursor.fetch()?.map(|batch| batch.num_rows()).unwrap_or(0);
+///     Ok(num_rows)
+/// }
+/// ```
+pub struct CallerOwnedBuffer<C> {
+    /// A mutable pointer to num_rows_fetched is passed to the C-API. It is used to write back the
+    /// number of fetched rows. Heap allocated, so the pointer stays valid, even if the buffer is
+    /// moved in memory.
+    num_rows: Box<usize>,
+    /// Minimum capacity of all bound regions.
+    row_capacity: usize,
+    /// Column number and bound region.
+    columns: Vec<(u16, C)>,
+    /// One indicator array per column. The inner `Vec`s are never resized, so bound pointers stay
+    /// valid.
+    indicators: Vec<Vec<isize>>,
+}
+
+impl<C> CallerOwnedBuffer<C>
+where
+    C: CallerOwnedColumn,
+{
+    /// Create a buffer binding each region to the column with the associated (one based) column
+    /// number. The number of rows fetched at once is the minimal capacity of all regions.
+    ///
+    /// # Safety
+    ///
+    /// Each region must uphold the invariants described at [`CallerOwnedColumn`].
+    ///
+    /// # Panics
+    ///
+    /// If a column number is used more than once.
+    pub unsafe fn new(columns: Vec<(u16, C)>) -> Self {
+        let mut indices = HashSet::new();
+        if columns
+            .iter()
+            .any(move |&(col_index, _)| !indices.insert(col_index))
+        {
+            panic!("Column indices must be unique.")
+        }
+        let row_capacity = columns
+            .iter()
+            .map(|(_, col)| col.capacity())
+            .min()
+            .unwrap_or(0);
+        let indicators = columns
+            .iter()
+            .map(|_| vec![NULL_DATA; row_capacity])
+            .collect();
+        Self {
+            num_rows: Box::new(0),
+            row_capacity,
+            columns,
+            indicators,
+        }
+    }
+
+    /// Number of valid rows in the buffer.
+    pub fn num_rows(&self) -> usize {
+        *self.num_rows
+    }
+
+    /// Number of bound columns.
+    pub fn num_cols(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Indicator of the value at `row_index` in the region with index `buffer_index`. I.e. whether
+    /// the value is `NULL`, and for variable sized types, its length.
+    ///
+    /// # Panics
+    ///
+    /// If `row_index` is not smaller than [`Self::num_rows`].
+    pub fn indicator(&self, buffer_index: usize, row_index: usize) -> Indicator {
+        Indicator::from_isize(self.indicators(buffer_index)[row_index])
+    }
+
+    /// `true` if the value at `row_index` in the region with index `buffer_index` is not `NULL`.
+    pub fn is_valid(&self, buffer_index: usize, row_index: usize) -> bool {
+        self.indicator(buffer_index, row_index) != Indicator::Null
+    }
+
+    /// Raw indicators of the valid rows in the region with index `buffer_index`.
+    pub fn indicators(&self, buffer_index: usize) -> &[isize] {
+        &self.indicators[buffer_index][..*self.num_rows]
+    }
+
+    /// Number of bytes allocated by this buffer for indicators. Regions owned by the application
+    /// are not included.
+    pub fn memory_usage(&self) -> usize {
+        self.indicators
+            .iter()
+            .map(|indicators| indicators.len() * size_of::<isize>())
+            .sum()
+    }
+
+    /// Gives the regions back to the application, once the buffer is no longer bound.
+    pub fn into_columns(self) -> Vec<(u16, C)> {
+        self.columns
+    }
+}
+
+unsafe impl<C> RowSetBuffer for CallerOwnedBuffer<C>
+where
+    C: CallerOwnedColumn,
+{
+    fn bind_type(&self) -> usize {
+        0 // Specify columnar binding
+    }
+
+    fn row_array_size(&self) -> usize {
+        self.row_capacity
+    }
+
+    fn mut_num_fetch_rows(&mut self) -> &mut usize {
+        self.num_rows.as_mut()
+    }
+
+    unsafe fn bind_colmuns_to_cursor(&mut self, mut cursor: StatementRef<'_>) -> Result<(), Error> {
+        for (buffer_index, ((col_number, column), indicators)) in self
+            .columns
+            .iter_mut()
+            .zip(&mut self.indicators)
+            .enumerate()
+        {
+            // ODBC describes the length of an element as a signed integer.
+            let element_len = isize::try_from(column.element_len()).map_err(|_| {
+                Error::TooLargeColumnBufferSize {
+                    buffer_index: buffer_index as u16,
+                    num_elements: column.capacity(),
+                    element_size: column.element_len(),
+                }
+            })?;
+            let mut target = BindTarget {
+                cdata_type: column.cdata_type(),
+                value: column.as_mut_ptr(),
+                element_len,
+                indicators: indicators.as_mut_ptr(),
+            };
+            cursor
+                .bind_col(*col_number, &mut target)
+                .into_result(&cursor)?;
+        }
+        Ok(())
+    }
+}
+
+/// Describes a region and its indicators to `SQLBindCol`.
+struct BindTarget {
+    cdata_type: CDataType,
+    value: *mut c_void,
+    element_len: isize,
+    indicators: *mut isize,
+}
+
+unsafe impl CData for BindTarget {
+    fn cdata_type(&self) -> CDataType {
+        self.cdata_type
+    }
+
+    fn indicator_ptr(&self) -> *const isize {
+        self.indicators
+    }
+
+    fn value_ptr(&self) -> *const c_void {
+        self.value
+    }
+
+    fn buffer_length(&self) -> isize {
+        self.element_len
+    }
+}
+
+unsafe impl CDataMut for BindTarget {
+    fn mut_indicator_ptr(&mut self) -> *mut isize {
+        self.indicators
+    }
+
+    fn mut_value_ptr(&mut self) -> *mut c_void {
+        self.value
+    }
+}
@@ -0,0 +1,187 @@
+use std::{cmp::min, mem::size_of, slice};
+
+use odbc_sys::NULL_DATA;
+
+use super::Indicator;
+
+/// Iterator over mutable elements of a variable sized column buffer, i.e. a text or binary column.
+/// See [`crate::buffers::TextColumn::iter_mut`] or [`crate::buffers::BinColumn::iter_mut`].
+#[derive(Debug)]
+pub struct CellsMut<'a, C> {
+    /// Values of the remaining elements. Each element occupies `stride` characters.
+    values: &'a mut [C],
+    indicators: slice::IterMut<'a, isize>,
+    /// Distance between the start of two consecutive elements. May be larger than `max_len` to
+    /// account for terminating zeroes.
+    stride: usize,
+    /// Maximum length of an element, excluding any terminating zero.
+    max_len: usize,
+}
+
+impl<'a, C> CellsMut<'a, C> {
+    /// `indicators` must only hold the elements to iterate over.
+    pub(crate) fn new(
+        values: &'a mut [C],
+        indicators: &'a mut [isize],
+        stride: usize,
+        max_len: usize,
+    ) -> Self {
+        debug_assert!(max_len <= stride);
+        Self {
+            values,
+            indicators: indicators.iter_mut(),
+            stride,
+            max_len,
+        }
+    }
+}
+
+impl<'a, C> Iterator for CellsMut<'a, C> {
+    type Item = CellMut<'a, C>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let indicator = self.indicators.next()?;
+        let values = std::mem::take(&mut self.values);
+        let (element, rest) = values.split_at_mut(self.stride);
+        self.values = rest;
+        Some(CellMut {
+            value: &mut element[..self.max_len],
+            indicator,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indicators.size_hint()
+    }
+}
+
+impl<'a, C> ExactSizeIterator for CellsMut<'a, C> {}
+
+/// Mutable access to a single element of a text or binary column buffer. Allows for transforming
+/// values in place, e.g. to trim or uppercase text fetched from one data source, before inserting
+/// it into another one.
+#[derive(Debug)]
+pub struct CellMut<'a, C> {
+    /// Memory reserved for the element, excluding any terminating zero.
+    value: &'a mut [C],
+    indicator: &'a mut isize,
+}
+
+impl<'a, C> CellMut<'a, C> {
+    /// Value of the element. `None` if the element is `NULL`. Truncated values are cut off at the
+    /// maximum element length of the buffer.
+    pub fn get(&self) -> Option<&[C]> {
+        let length = self.len()?;
+        Some(&self.value[..length])
+    }
+
+    /// Mutable value of the element. `None` if the element is `NULL`.
+    pub fn get_mut(&mut self) -> Option<&mut [C]> {
+        let length = self.len()?;
+        Some(&mut self.value[..length])
+    }
+
+    /// Shortens the element to `length` characters. Has no effect if the element is `NULL` or
+    /// already is not longer than `length`.
+    pub fn truncate(&mut self, length: usize)
+    where
+        C: Default,
+    {
+        if let Some(current) = self.len() {
+            if length < current {
+                self.set_len(length);
+            }
+        }
+    }
+
+    /// Replaces the element with `value`. Use `None` to set it to `NULL`.
+    ///
+    /// # Panics
+    ///
+    /// If `value` is larger than the maximum element length of the buffer.
+    pub fn set(&mut self, value: Option<&[C]>)
+    where
+        C: Default + Copy,
+    {
+        if let Some(value) = value {
+            assert!(
+                value.len() <= self.value.len(),
+                "Value is larger than the maximum element length of the buffer."
+            );
+            self.value[..value.len()].copy_from_slice(value);
+            self.set_len(value.len());
+        } else {
+            self.set_null();
+        }
+    }
+
+    /// Sets the element to `NULL`.
+    pub fn set_null(&mut self) {
+        *self.indicator = NULL_DATA;
+    }
+
+    /// Length of the value in the buffer, `None` if the element is `NULL`.
+    fn len(&self) -> Option<usize> {
+        match Indicator::from_isize(*self.indicator) {
+            Indicator::Null => None,
+            Indicator::NoTotal => Some(self.value.len()),
+            Indicator::Length(length_in_bytes) => {
+                Some(min(self.value.len(), length_in_bytes / size_of::<C>()))
+            }
+        }
+    }
+
+    fn set_len(&mut self, length: usize)
+    where
+        C: Default,
+    {
+        *self.indicator = (length * size_of::<C>()).try_into().unwrap();
+        // Text buffers reserve an additional character for the terminating zero after the element,
+        // so we only need to write it, if the value is shorter than the maximum length.
+        if let Some(terminator) = self.value.get_mut(length) {
+            *terminator = C::default();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use odbc_sys::{NO_TOTAL, NULL_DATA};
+
+    use super::CellsMut;
+
+    #[test]
+    fn trim_values_in_place() {
+        // Two elements of length three, each followed by a terminating zero.
+        let mut values = *b"ab \0cd\0\0";
+        let mut indicators = [3, NULL_DATA];
+
+        for mut cell in CellsMut::new(&mut values, &mut indicators, 4, 3) {
+            let trimmed_len = cell
+                .get()
+                .map(|text| text.len() - text.iter().rev().take_while(|&&c| c == b' ').count());
+            if let Some(length) = trimmed_len {
+                cell.truncate(length);
+            }
+        }
+
+        assert_eq!(b"ab\0\0cd\0\0", &values);
+        assert_eq!([2, NULL_DATA], indicators);
+    }
+
+    #[test]
+    fn modify_values() {
+        let mut values = *b"abcd";
+        let mut indicators = [NO_TOTAL, 1];
+
+        let mut cells = CellsMut::new(&mut values, &mut indicators, 2, 2);
+        let mut first = cells.next().unwrap();
+        first.get_mut().unwrap().make_ascii_uppercase();
+        assert_eq!(Some(&b"AB"[..]), first.get());
+        let mut second = cells.next().unwrap();
+        second.set(Some(b"x"));
+        assert!(cells.next().is_none());
+
+        assert_eq!(b"ABx\0", &values);
+    }
+}
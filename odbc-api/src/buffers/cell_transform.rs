@@ -0,0 +1,201 @@
+use std::{fmt, sync::Arc};
+
+use crate::{ColumnDescription, Error, ResultSetMetadata};
+
+use super::{CellValue, Row};
+
+/// Function replacing the value of a cell, e.g. with a hash or `NULL`.
+type Transform = dyn Fn(CellValue) -> CellValue + Send + Sync;
+
+/// Transformations applied to the values of individual columns, identified by name, as rows are
+/// copied out of the bound buffer. Enables extraction tools to enforce masking policies, like
+/// hashing email addresses or removing social security numbers, in a single place rather than
+/// post-processing every consumer's output.
+///
+/// A policy is independent of a specific result set and may be reused across queries. Use
+/// [`Self::for_result_set`] to resolve it against the columns of a cursor.
+///
+/// ```
+/// use odbc_api::{
+///     buffers::{CellTransforms, CellValue, Row, TypeCoercion},
+///     Cursor, Error,
+/// };
+///
+/// fn masked_users(mut cursor: impl Cursor, batch_size: usize) -> Result<Vec<Row>, Error> {
+///     let policy = CellTransforms::new()
+///         .null_column("ssn")
+///         .column("email", |value| match value {
+///             CellValue::Text(text) => CellValue::Text(format!("<{} bytes>", text.len()).into()),
+///             other => other,
+///         });
+///     let transform = policy.for_result_set(&mut cursor)?;
+///     let buffer = TypeCoercion::new().buffer_for_cursor(batch_size, &mut cursor)?;
+///     let mut block_cursor = cursor.bind_buffer(buffer)?;
+///     let mut rows = Vec::new();
+///     while let Some(batch) = block_cursor.fetch()? {
+///         rows.extend(batch.rows().map(|row| transform.apply(row)));
+///     }
+///     Ok(rows)
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct CellTransforms {
+    by_column: Vec<(String, Arc<Transform>)>,
+}
+
+impl CellTransforms {
+    /// Set of rules, which does not transform any column yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace each value of the column named `name` with the result of `transform`. Names are
+    /// compared using the [`crate::ColumnNameMatcher`] of the quirks of the result set. Replaces
+    /// a previous rule for the same name.
+    pub fn column(
+        mut self,
+        name: &str,
+        transform: impl Fn(CellValue) -> CellValue + Send + Sync + 'static,
+    ) -> Self {
+        self.by_column.retain(|(other, _)| other != name);
+        self.by_column.push((name.to_owned(), Arc::new(transform)));
+        self
+    }
+
+    /// Replace each value of the column named `name` with [`CellValue::Null`].
+    pub fn null_column(self, name: &str) -> Self {
+        self.column(name, |_| CellValue::Null)
+    }
+
+    /// Resolves the rules against the columns of a result set. Rules naming columns, which are not
+    /// part of the result set, are ignored.
+    pub fn for_result_set(
+        &self,
+        metadata: &mut impl ResultSetMetadata,
+    ) -> Result<RowTransform, Error> {
+        let quirks = metadata.quirks();
+        let keys: Vec<_> = self
+            .by_column
+            .iter()
+            .map(|(name, transform)| (quirks.column_name_matcher.key(name).into_owned(), transform))
+            .collect();
+        let num_cols: u16 = metadata.num_result_cols()?.try_into().unwrap();
+        let mut by_index = Vec::with_capacity(num_cols.into());
+        let mut column = ColumnDescription::default();
+        for column_number in 1..=num_cols {
+            metadata.describe_col(column_number, &mut column)?;
+            let name = column.name_to_string().unwrap_or_default();
+            let key = quirks.column_name_matcher.key(&name);
+            let transform = keys
+                .iter()
+                .find(|(other, _)| *other == key)
+                .map(|&(_, transform)| transform.clone());
+            by_index.push(transform);
+        }
+        Ok(RowTransform { by_index })
+    }
+}
+
+impl fmt::Debug for CellTransforms {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CellTransforms")
+            .field(
+                "columns",
+                &self
+                    .by_column
+                    .iter()
+                    .map(|(name, _)| name)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+/// [`CellTransforms`] resolved against the columns of a result set. Applies the transformation of
+/// each column to the cell at the same position of a [`Row`].
+#[derive(Clone, Default)]
+pub struct RowTransform {
+    /// One entry for each column of the result set, `None` for columns kept as they are.
+    by_index: Vec<Option<Arc<Transform>>>,
+}
+
+impl RowTransform {
+    /// `true` if no column of the result set is transformed.
+    pub fn is_identity(&self) -> bool {
+        self.by_index.iter().all(Option::is_none)
+    }
+
+    /// Replaces the cells of `row` with their transformed values. Cells past the columns of the
+    /// result set are kept as they are.
+    pub fn apply(&self, row: Row) -> Row {
+        if self.is_identity() {
+            return row;
+        }
+        let cells = row
+            .into_cells()
+            .into_iter()
+            .enumerate()
+            .map(|(index, cell)| match self.by_index.get(index) {
+                Some(Some(transform)) => transform(cell),
+                _ => cell,
+            })
+            .collect();
+        Row::new(cells)
+    }
+}
+
+impl fmt::Debug for RowTransform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RowTransform")
+            .field(
+                "transformed",
+                &self
+                    .by_index
+                    .iter()
+                    .map(Option::is_some)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::buffers::{CellValue, Row};
+
+    use super::RowTransform;
+
+    #[test]
+    fn transform_cells_by_position() {
+        let transform = RowTransform {
+            by_index: vec![
+                None,
+                Some(Arc::new(|_| CellValue::Null)),
+                Some(Arc::new(|value| match value {
+                    CellValue::Text(text) => CellValue::Text(text.to_ascii_uppercase()),
+                    other => other,
+                })),
+            ],
+        };
+        let row = Row::new(vec![
+            CellValue::I64(1),
+            CellValue::Text(b"123-45-6789".to_vec()),
+            CellValue::Text(b"alice@example.com".to_vec()),
+        ]);
+
+        let row = transform.apply(row);
+
+        assert_eq!(
+            Row::new(vec![
+                CellValue::I64(1),
+                CellValue::Null,
+                CellValue::Text(b"ALICE@EXAMPLE.COM".to_vec()),
+            ]),
+            row
+        );
+        assert!(!transform.is_identity());
+        assert!(RowTransform::default().is_identity());
+    }
+}
@@ -0,0 +1,395 @@
+use std::fmt::Write;
+
+use odbc_sys::{Date, Guid, Time, Timestamp};
+use thiserror::Error;
+
+use crate::calendar::{format_date, format_time, format_timestamp};
+
+use super::decimal_column::format_scaled;
+
+/// Owned value of a single cell of a result set. Unlike the views into the buffers it does not
+/// borrow anything, so it can be sent to other threads or stored beyond the lifetime of the buffer
+/// it has been copied from.
+///
+/// It is intended as the one canonical representation for dynamically typed consumers, like REPLs
+/// or bindings to scripting languages. Use its `TryFrom` implementations to convert it into Rust
+/// types.
+///
+/// ```
+/// use odbc_api::buffers::CellValue;
+///
+/// let id: i32 = CellValue::I64(42).try_into().unwrap();
+/// assert_eq!(42, id);
+///
+/// let name: Option<String> = CellValue::Null.try_into().unwrap();
+/// assert_eq!(None, name);
+///
+/// // Out of range values are not truncated, but an error.
+/// assert!(u8::try_from(CellValue::I64(256)).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellValue {
+    /// The cell is `NULL`.
+    Null,
+    /// Character data in the system encoding.
+    Text(Vec<u8>),
+    /// Character data encoded in UTF-16.
+    WText(Vec<u16>),
+    Binary(Vec<u8>),
+    /// Integers of any width.
+    I64(i64),
+    /// Floating point numbers of any width.
+    F64(f64),
+    /// Fixed point number with the value `value / 10^scale`. See
+    /// [`crate::buffers::DecimalColumn`].
+    Decimal {
+        value: i64,
+        scale: i16,
+    },
+    Date(Date),
+    Time(Time),
+    Timestamp(Timestamp),
+    Bit(bool),
+    Guid(Guid),
+}
+
+impl CellValue {
+    /// `true` if the cell is `NULL`.
+    pub fn is_null(&self) -> bool {
+        matches!(self, CellValue::Null)
+    }
+
+    /// Name of the variant, used in error messages.
+    fn kind(&self) -> &'static str {
+        match self {
+            CellValue::Null => "NULL",
+            CellValue::Text(_) => "text",
+            CellValue::WText(_) => "wide text",
+            CellValue::Binary(_) => "binary",
+            CellValue::I64(_) => "integer",
+            CellValue::F64(_) => "floating point",
+            CellValue::Decimal { .. } => "decimal",
+            CellValue::Date(_) => "date",
+            CellValue::Time(_) => "time",
+            CellValue::Timestamp(_) => "timestamp",
+            CellValue::Bit(_) => "bit",
+            CellValue::Guid(_) => "GUID",
+        }
+    }
+}
+
+/// A [`CellValue`] could not be converted into the requested Rust type, because it holds a value
+/// of a different type, or because its value is out of range for the requested type. The original
+/// value is returned as part of the error.
+#[derive(Debug, Error, Clone, PartialEq)]
+#[error("Can not convert {} cell value into {target}.", .value.kind())]
+pub struct InvalidCellConversion {
+    /// Name of the type the value should have been converted into.
+    pub target: &'static str,
+    /// The value which could not be converted.
+    pub value: CellValue,
+}
+
+impl CellValue {
+    fn invalid<T>(self, target: &'static str) -> Result<T, InvalidCellConversion> {
+        Err(InvalidCellConversion {
+            target,
+            value: self,
+        })
+    }
+}
+
+/// Implements `TryFrom<CellValue>` for `$target` and `Option<$target>`, the latter mapping
+/// [`CellValue::Null`] to `None`.
+macro_rules! impl_try_from_cell_value {
+    ($target:ty, |$value:ident| $convert:expr) => {
+        impl TryFrom<CellValue> for $target {
+            type Error = InvalidCellConversion;
+
+            fn try_from($value: CellValue) -> Result<Self, Self::Error> {
+                $convert
+            }
+        }
+
+        impl TryFrom<CellValue> for Option<$target> {
+            type Error = InvalidCellConversion;
+
+            fn try_from(value: CellValue) -> Result<Self, Self::Error> {
+                if value.is_null() {
+                    Ok(None)
+                } else {
+                    value.try_into().map(Some)
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_try_from_cell_value_for_integer {
+    ($($target:ty),*) => {
+        $(impl_try_from_cell_value!($target, |value| match value {
+            CellValue::I64(i) => match i.try_into() {
+                Ok(i) => Ok(i),
+                Err(_) => value.invalid(stringify!($target)),
+            },
+            other => other.invalid(stringify!($target)),
+        });)*
+    };
+}
+
+impl_try_from_cell_value_for_integer!(i8, i16, i32, u8, u16, u32, u64);
+
+impl_try_from_cell_value!(i64, |value| match value {
+    CellValue::I64(i) => Ok(i),
+    other => other.invalid("i64"),
+});
+
+impl_try_from_cell_value!(f64, |value| match value {
+    CellValue::F64(f) => Ok(f),
+    CellValue::Decimal { value, scale } => Ok(value as f64 / 10f64.powi(scale.into())),
+    other => other.invalid("f64"),
+});
+
+impl_try_from_cell_value!(bool, |value| match value {
+    CellValue::Bit(b) => Ok(b),
+    other => other.invalid("bool"),
+});
+
+impl_try_from_cell_value!(String, |value| match value {
+    CellValue::Text(bytes) => match String::from_utf8(bytes) {
+        Ok(text) => Ok(text),
+        Err(error) => CellValue::Text(error.into_bytes()).invalid("String"),
+    },
+    CellValue::WText(units) => match String::from_utf16(&units) {
+        Ok(text) => Ok(text),
+        Err(_) => CellValue::WText(units).invalid("String"),
+    },
+    CellValue::Decimal { value, scale } => Ok(format_decimal(value, scale)),
+    other => other.invalid("String"),
+});
+
+impl_try_from_cell_value!(Vec<u8>, |value| match value {
+    CellValue::Binary(bytes) | CellValue::Text(bytes) => Ok(bytes),
+    other => other.invalid("Vec<u8>"),
+});
+
+impl_try_from_cell_value!(Date, |value| match value {
+    CellValue::Date(date) => Ok(date),
+    CellValue::Timestamp(Timestamp {
+        year,
+        month,
+        day,
+        hour: 0,
+        minute: 0,
+        second: 0,
+        fraction: 0,
+    }) => Ok(Date { year, month, day }),
+    other => other.invalid("Date"),
+});
+
+impl_try_from_cell_value!(Time, |value| match value {
+    CellValue::Time(time) => Ok(time),
+    other => other.invalid("Time"),
+});
+
+impl_try_from_cell_value!(Timestamp, |value| match value {
+    CellValue::Timestamp(timestamp) => Ok(timestamp),
+    CellValue::Date(Date { year, month, day }) => Ok(Timestamp {
+        year,
+        month,
+        day,
+        hour: 0,
+        minute: 0,
+        second: 0,
+        fraction: 0,
+    }),
+    other => other.invalid("Timestamp"),
+});
+
+impl_try_from_cell_value!(Guid, |value| match value {
+    CellValue::Guid(guid) => Ok(guid),
+    other => other.invalid("Guid"),
+});
+
+/// Text representation of `value / 10^scale`. A negative scale appends zeroes.
+fn format_decimal(value: i64, scale: i16) -> String {
+    if scale >= 0 {
+        format_scaled(value, scale as u32)
+    } else if value == 0 {
+        "0".to_owned()
+    } else {
+        format!("{value}{}", "0".repeat(scale.unsigned_abs().into()))
+    }
+}
+
+/// Text representation of any value, except binary data. Text is required to be valid UTF-8 or
+/// UTF-16 respectively. `target` names the buffer type in the error.
+pub(crate) fn cell_to_text(
+    cell: CellValue,
+    target: &'static str,
+) -> Result<Option<String>, InvalidCellConversion> {
+    let text = match cell {
+        CellValue::Null => return Ok(None),
+        CellValue::Text(_) | CellValue::WText(_) => {
+            String::try_from(cell).map_err(|error| InvalidCellConversion {
+                target,
+                value: error.value,
+            })?
+        }
+        CellValue::Binary(_) => return cell.invalid(target),
+        CellValue::I64(i) => i.to_string(),
+        CellValue::F64(f) => f.to_string(),
+        CellValue::Decimal { value, scale } => format_decimal(value, scale),
+        CellValue::Date(d) => format_date(&d),
+        CellValue::Time(t) => format_time(&t),
+        CellValue::Timestamp(ts) => format_timestamp(&ts, ' '),
+        CellValue::Bit(b) => if b { "1" } else { "0" }.to_owned(),
+        CellValue::Guid(g) => {
+            let mut text = format!("{:08x}-{:04x}-{:04x}-", g.d1, g.d2, g.d3);
+            for (index, byte) in g.d4.iter().enumerate() {
+                if index == 2 {
+                    text.push('-');
+                }
+                write!(text, "{byte:02x}").unwrap();
+            }
+            text
+        }
+    };
+    Ok(Some(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use odbc_sys::{Date, Guid, Timestamp};
+
+    use super::{cell_to_text, CellValue, InvalidCellConversion};
+
+    #[test]
+    fn integers_are_range_checked() {
+        assert_eq!(Ok(255u8), CellValue::I64(255).try_into());
+        assert_eq!(
+            Err(InvalidCellConversion {
+                target: "u8",
+                value: CellValue::I64(-1)
+            }),
+            u8::try_from(CellValue::I64(-1))
+        );
+    }
+
+    #[test]
+    fn null_converts_only_into_option() {
+        assert_eq!(Ok(None), Option::<i64>::try_from(CellValue::Null));
+        assert_eq!(Ok(Some(3)), Option::<i64>::try_from(CellValue::I64(3)));
+        assert!(i64::try_from(CellValue::Null).is_err());
+    }
+
+    #[test]
+    fn text_into_string() {
+        let narrow = CellValue::Text(b"Hello".to_vec());
+        let wide = CellValue::WText("Grüße".encode_utf16().collect());
+        let decimal = CellValue::Decimal {
+            value: -1234,
+            scale: 2,
+        };
+
+        assert_eq!(Ok("Hello".to_owned()), narrow.try_into());
+        assert_eq!(Ok("Grüße".to_owned()), wide.try_into());
+        assert_eq!(Ok("-12.34".to_owned()), decimal.try_into());
+    }
+
+    #[test]
+    fn decimal_with_negative_scale_into_string() {
+        let decimal = CellValue::Decimal {
+            value: 12,
+            scale: -2,
+        };
+        let zero = CellValue::Decimal {
+            value: 0,
+            scale: -2,
+        };
+
+        assert_eq!(
+            cell_to_text(decimal.clone(), "text").unwrap(),
+            String::try_from(decimal).ok()
+        );
+        assert_eq!(Ok("0".to_owned()), zero.try_into());
+    }
+
+    #[test]
+    fn invalid_utf8_returns_original_bytes() {
+        let error = String::try_from(CellValue::Text(vec![b'a', 0xff])).unwrap_err();
+
+        assert_eq!(CellValue::Text(vec![b'a', 0xff]), error.value);
+        assert_eq!(
+            "Can not convert text cell value into String.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn date_and_timestamp_convert_into_each_other() {
+        let date = Date {
+            year: 2021,
+            month: 3,
+            day: 20,
+        };
+        let midnight = Timestamp {
+            year: 2021,
+            month: 3,
+            day: 20,
+            ..Default::default()
+        };
+        let afternoon = Timestamp {
+            hour: 14,
+            ..midnight
+        };
+
+        assert_eq!(Ok(midnight), CellValue::Date(date).try_into());
+        assert_eq!(Ok(date), CellValue::Timestamp(midnight).try_into());
+        assert!(Date::try_from(CellValue::Timestamp(afternoon)).is_err());
+    }
+
+    #[test]
+    fn format_values_as_text() {
+        let timestamp = Timestamp {
+            year: 2021,
+            month: 3,
+            day: 20,
+            hour: 15,
+            minute: 24,
+            second: 12,
+            fraction: 120_000_000,
+        };
+        let guid = Guid {
+            d1: 0x0102_0304,
+            d2: 0x0506,
+            d3: 0x0708,
+            d4: [9, 10, 11, 12, 13, 14, 15, 16],
+        };
+        let text = |cell| cell_to_text(cell, "text").unwrap().unwrap();
+
+        assert_eq!(
+            "2021-03-20 15:24:12.12",
+            text(CellValue::Timestamp(timestamp))
+        );
+        assert_eq!(
+            "-12.34",
+            text(CellValue::Decimal {
+                value: -1234,
+                scale: 2
+            })
+        );
+        assert_eq!(
+            "1200",
+            text(CellValue::Decimal {
+                value: 12,
+                scale: -2
+            })
+        );
+        assert_eq!(
+            "01020304-0506-0708-090a-0b0c0d0e0f10",
+            text(CellValue::Guid(guid))
+        );
+        assert!(cell_to_text(CellValue::Binary(vec![1]), "text").is_err());
+    }
+}
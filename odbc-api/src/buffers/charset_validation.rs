@@ -0,0 +1,285 @@
+use std::{char::decode_utf16, str};
+
+use super::{AnySlice, ColumnarAnyBuffer};
+
+/// Kind of problem found in a text value by [`ColumnarAnyBuffer::charset_report`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CharsetIssue {
+    /// Narrow text, which is not valid UTF-8, or wide text, which is not valid UTF-16 (i.e.
+    /// contains unpaired surrogates).
+    InvalidEncoding,
+    /// A control character other than tab, line feed or carriage return.
+    ControlCharacter,
+    /// A `NUL` character within the value. Many downstream systems treat it as the end of the
+    /// string, or reject it altogether.
+    InteriorNul,
+}
+
+/// Location of a [`CharsetIssue`] within a row set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CharsetFinding {
+    /// Zero based index of the row within the row set.
+    pub row: usize,
+    /// Zero based index of the column within the buffer.
+    pub column: usize,
+    pub issue: CharsetIssue,
+    /// Offset of the first occurrence of the issue within the value. In bytes for narrow text, in
+    /// UTF-16 code units for wide text.
+    pub position: usize,
+}
+
+/// Text values of a row set, which would be rejected or misinterpreted by strict downstream
+/// systems. Created by [`ColumnarAnyBuffer::charset_report`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CharsetReport {
+    /// Each kind of issue is reported once per value, at its first occurrence. Ordered by column,
+    /// then by row.
+    pub findings: Vec<CharsetFinding>,
+}
+
+impl CharsetReport {
+    /// `true` if no issues have been found.
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// Number of values affected by `issue`.
+    pub fn count(&self, issue: CharsetIssue) -> usize {
+        self.findings.iter().filter(|f| f.issue == issue).count()
+    }
+
+    /// Zero based indices of rows with at least one issue, ascending and without duplicates.
+    pub fn rows(&self) -> Vec<usize> {
+        let mut rows: Vec<_> = self.findings.iter().map(|f| f.row).collect();
+        rows.sort_unstable();
+        rows.dedup();
+        rows
+    }
+}
+
+impl ColumnarAnyBuffer {
+    /// Validates the text columns of the current row set. Narrow text is expected to be UTF-8,
+    /// wide text UTF-16. Other columns, including binary ones, are not inspected. This pass is
+    /// optional and intended for data quality audits, e.g. before loading into a system which
+    /// rejects invalid text.
+    ///
+    /// ```
+    /// use odbc_api::{buffers::{CharsetIssue, ColumnarAnyBuffer}, Cursor, Error};
+    ///
+    /// fn audit(cursor: impl Cursor, buffer: ColumnarAnyBuffer) -> Result<(), Error> {
+    ///     let mut block_cursor = cursor.bind_buffer(buffer)?;
+    ///     let mut row_offset = 0;
+    ///     while let Some(batch) = block_cursor.fetch()? {
+    ///         for finding in batch.charset_report().findings {
+    ///             println!(
+    ///                 "Row {}, column {}: {:?}",
+    ///                 row_offset + finding.row,
+    ///                 finding.column + 1,
+    ///                 finding.issue
+    ///             );
+    ///         }
+    ///         row_offset += batch.num_rows();
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn charset_report(&self) -> CharsetReport {
+        let mut report = CharsetReport::default();
+        for column in 0..self.num_cols() {
+            let mut push = |row, issues: FirstIssues| {
+                report
+                    .findings
+                    .extend(issues.findings().map(|(issue, position)| CharsetFinding {
+                        row,
+                        column,
+                        issue,
+                        position,
+                    }))
+            };
+            match self.column(column) {
+                AnySlice::Text(view) => {
+                    for (row, value) in view.iter().enumerate() {
+                        if let Some(value) = value {
+                            push(row, narrow_issues(value));
+                        }
+                    }
+                }
+                AnySlice::WText(view) => {
+                    for row in 0..view.len() {
+                        if let Some(value) = view.get(row) {
+                            push(row, wide_issues(value));
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+        report
+    }
+}
+
+/// Position of the first occurrence of each kind of issue within a single value.
+#[derive(Default)]
+struct FirstIssues {
+    invalid_encoding: Option<usize>,
+    control_character: Option<usize>,
+    interior_nul: Option<usize>,
+}
+
+impl FirstIssues {
+    fn invalid_encoding(&mut self, position: usize) {
+        self.invalid_encoding.get_or_insert(position);
+    }
+
+    fn char(&mut self, c: char, position: usize) {
+        if c == '\0' {
+            self.interior_nul.get_or_insert(position);
+        } else if c.is_control() && !matches!(c, '\t' | '\n' | '\r') {
+            self.control_character.get_or_insert(position);
+        }
+    }
+
+    fn findings(self) -> impl Iterator<Item = (CharsetIssue, usize)> {
+        [
+            (CharsetIssue::InvalidEncoding, self.invalid_encoding),
+            (CharsetIssue::ControlCharacter, self.control_character),
+            (CharsetIssue::InteriorNul, self.interior_nul),
+        ]
+        .into_iter()
+        .filter_map(|(issue, position)| position.map(|position| (issue, position)))
+    }
+}
+
+fn narrow_issues(value: &[u8]) -> FirstIssues {
+    let mut issues = FirstIssues::default();
+    let mut offset = 0;
+    let mut rest = value;
+    // Check the valid parts in between invalid sequences for control characters, too.
+    loop {
+        let (valid, error) = match str::from_utf8(rest) {
+            Ok(valid) => (valid, None),
+            Err(error) => {
+                let valid = str::from_utf8(&rest[..error.valid_up_to()]).unwrap();
+                (valid, Some(error))
+            }
+        };
+        for (index, c) in valid.char_indices() {
+            issues.char(c, offset + index);
+        }
+        let Some(error) = error else {
+            break;
+        };
+        issues.invalid_encoding(offset + error.valid_up_to());
+        // An incomplete sequence at the end of the value has no error length.
+        let skip = error.valid_up_to() + error.error_len().unwrap_or(rest.len());
+        if skip >= rest.len() {
+            break;
+        }
+        rest = &rest[skip..];
+        offset += skip;
+    }
+    issues
+}
+
+fn wide_issues(value: &[u16]) -> FirstIssues {
+    let mut issues = FirstIssues::default();
+    let mut position = 0;
+    for c in decode_utf16(value.iter().copied()) {
+        match c {
+            Ok(c) => {
+                issues.char(c, position);
+                position += c.len_utf16();
+            }
+            Err(_) => {
+                issues.invalid_encoding(position);
+                position += 1;
+            }
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::buffers::{AnyBuffer, CharColumn, ColumnarAnyBuffer, WCharColumn};
+
+    use super::{narrow_issues, wide_issues, CharsetFinding, CharsetIssue};
+
+    fn findings(issues: super::FirstIssues) -> Vec<(CharsetIssue, usize)> {
+        issues.findings().collect()
+    }
+
+    #[test]
+    fn narrow_text() {
+        assert!(findings(narrow_issues("Grüße\tWelt\r\n".as_bytes())).is_empty());
+        assert_eq!(
+            vec![(CharsetIssue::InteriorNul, 1)],
+            findings(narrow_issues(b"a\0b"))
+        );
+        // Control character after an invalid sequence, incomplete sequence at the end
+        assert_eq!(
+            vec![
+                (CharsetIssue::InvalidEncoding, 1),
+                (CharsetIssue::ControlCharacter, 3)
+            ],
+            findings(narrow_issues(b"a\xffb\x07c\xc3"))
+        );
+    }
+
+    #[test]
+    fn wide_text() {
+        let text: Vec<u16> = "a\u{1F600}\u{1b}".encode_utf16().collect();
+        assert_eq!(
+            vec![(CharsetIssue::ControlCharacter, 3)],
+            findings(wide_issues(&text))
+        );
+        assert_eq!(
+            vec![(CharsetIssue::InvalidEncoding, 1)],
+            findings(wide_issues(&[0x61, 0xD800, 0x62]))
+        );
+    }
+
+    #[test]
+    fn report_for_row_set() {
+        let mut narrow = CharColumn::new(3, 10);
+        narrow.set_value(0, Some(b"fine"));
+        narrow.set_value(1, None);
+        narrow.set_value(2, Some(b"\xff\0"));
+        let mut wide = WCharColumn::new(3, 10);
+        wide.set_value(0, Some(&[0x0, 0x61]));
+        let mut buffer = ColumnarAnyBuffer::new(vec![
+            (1, AnyBuffer::Text(narrow)),
+            (2, AnyBuffer::WText(wide)),
+        ]);
+        buffer.set_num_rows(3);
+
+        let report = buffer.charset_report();
+
+        assert_eq!(
+            vec![
+                CharsetFinding {
+                    row: 2,
+                    column: 0,
+                    issue: CharsetIssue::InvalidEncoding,
+                    position: 0
+                },
+                CharsetFinding {
+                    row: 2,
+                    column: 0,
+                    issue: CharsetIssue::InteriorNul,
+                    position: 1
+                },
+                CharsetFinding {
+                    row: 0,
+                    column: 1,
+                    issue: CharsetIssue::InteriorNul,
+                    position: 0
+                },
+            ],
+            report.findings
+        );
+        assert_eq!(vec![0, 2], report.rows());
+        assert_eq!(2, report.count(CharsetIssue::InteriorNul));
+        assert!(!report.is_clean());
+    }
+}
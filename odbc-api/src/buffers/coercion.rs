@@ -0,0 +1,227 @@
+use crate::{
+    buffers::{max_str_len_with_limit, BufferDesc, ColumnarAnyBuffer, TextRowSet},
+    ColumnDescription, DataType, Error, ResultSetMetadata,
+};
+
+/// Coarse classification of the [`DataType`] of a column. Used by [`TypeCoercion`] to apply a rule
+/// to all columns of similar type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TypeClass {
+    /// Character data, narrow or wide, including `XML`.
+    Text,
+    Binary,
+    /// `TINYINT`, `SMALLINT`, `INTEGER` and `BIGINT`.
+    Integer,
+    /// `DECIMAL` and `NUMERIC`.
+    Decimal,
+    /// `FLOAT`, `REAL` and `DOUBLE`.
+    Float,
+    Date,
+    Time,
+    Timestamp,
+    Bit,
+    /// Types unknown to this crate or specific to the data source.
+    Other,
+}
+
+impl TypeClass {
+    pub fn of(data_type: DataType) -> Self {
+        match data_type {
+            DataType::Char { .. }
+            | DataType::Varchar { .. }
+            | DataType::LongVarchar { .. }
+            | DataType::WChar { .. }
+            | DataType::WVarchar { .. }
+            | DataType::WLongVarchar { .. }
+            | DataType::Xml { .. } => TypeClass::Text,
+            DataType::Binary { .. }
+            | DataType::Varbinary { .. }
+            | DataType::LongVarbinary { .. } => TypeClass::Binary,
+            DataType::TinyInt | DataType::SmallInt | DataType::Integer | DataType::BigInt => {
+                TypeClass::Integer
+            }
+            DataType::Decimal { .. } | DataType::Numeric { .. } => TypeClass::Decimal,
+            DataType::Float { .. } | DataType::Real | DataType::Double => TypeClass::Float,
+            DataType::Date => TypeClass::Date,
+            DataType::Time { .. } => TypeClass::Time,
+            DataType::Timestamp { .. } => TypeClass::Timestamp,
+            DataType::Bit => TypeClass::Bit,
+            DataType::Unknown | DataType::Other { .. } => TypeClass::Other,
+        }
+    }
+}
+
+/// Representation a column is fetched in. See [`TypeCoercion`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoerceTo {
+    /// The buffer [`crate::Quirks::buffer_desc`] chooses for the type of the column. Columns
+    /// without a native buffer are fetched as [`CoerceTo::Text`], as are text columns.
+    Native,
+    /// Text in the system encoding, long enough for the UTF-8 representation of the column.
+    Text,
+    /// 64 Bit integer, converted by the driver.
+    I64,
+    /// 64 Bit floating point, converted by the driver.
+    F64,
+    /// Exactly this buffer.
+    Buffer(BufferDesc),
+}
+
+/// Rules choosing the C type each column of a result set is fetched as, so conversions happen in
+/// the driver, rather than being scattered across application code. Rules for individual column
+/// names take precedence over rules for a [`TypeClass`]. Columns no rule applies to are fetched
+/// [`CoerceTo::Native`]ly.
+///
+/// ```
+/// use odbc_api::{
+///     buffers::{CoerceTo, TypeClass, TypeCoercion},
+///     Cursor, Error,
+/// };
+///
+/// fn print_amounts(mut cursor: impl Cursor) -> Result<(), Error> {
+///     // Keep all digits of decimals, but fetch the id as integer, whatever its type.
+///     let coercion = TypeCoercion::new()
+///         .class(TypeClass::Decimal, CoerceTo::Text)
+///         .column("id", CoerceTo::I64);
+///     let buffer = coercion.buffer_for_cursor(1000, &mut cursor)?;
+///     let mut block_cursor = cursor.bind_buffer(buffer)?;
+///     while let Some(batch) = block_cursor.fetch()? {
+///         for row in batch.rows() {
+///             println!("{row:?}");
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TypeCoercion {
+    by_class: Vec<(TypeClass, CoerceTo)>,
+    by_column: Vec<(String, CoerceTo)>,
+    max_str_len: Option<usize>,
+}
+
+impl TypeCoercion {
+    /// Without any rules, all columns are fetched as their default buffer kind.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fetch columns of `class` as `target`. Replaces a previous rule for the same class.
+    pub fn class(mut self, class: TypeClass, target: CoerceTo) -> Self {
+        self.by_class.retain(|(other, _)| *other != class);
+        self.by_class.push((class, target));
+        self
+    }
+
+    /// Fetch the column named `name` as `target`. Names are compared using the
+    /// [`crate::ColumnNameMatcher`] of the quirks of the result set. Replaces a previous rule for
+    /// the same name.
+    pub fn column(mut self, name: &str, target: CoerceTo) -> Self {
+        self.by_column.retain(|(other, _)| other != name);
+        self.by_column.push((name.to_owned(), target));
+        self
+    }
+
+    /// Upper bound for the length of text buffers. See [`max_str_len_with_limit`]. Without it,
+    /// long text columns, and columns of unknown length, are capped to
+    /// [`TextRowSet::LONG_TEXT_LIMIT`].
+    pub fn max_str_len(mut self, max_str_len: Option<usize>) -> Self {
+        self.max_str_len = max_str_len;
+        self
+    }
+
+    /// Describes a buffer for each column of the result set, according to the rules.
+    pub fn buffer_descs(
+        &self,
+        metadata: &mut impl ResultSetMetadata,
+    ) -> Result<Vec<BufferDesc>, Error> {
+        let quirks = metadata.quirks();
+        let keys: Vec<_> = self
+            .by_column
+            .iter()
+            .map(|(name, target)| (quirks.column_name_matcher.key(name).into_owned(), *target))
+            .collect();
+        let num_cols: u16 = metadata.num_result_cols()?.try_into().unwrap();
+        let mut descs = Vec::with_capacity(num_cols.into());
+        let mut column = ColumnDescription::default();
+        for column_number in 1..=num_cols {
+            metadata.describe_col(column_number, &mut column)?;
+            let data_type = metadata.col_data_type(column_number)?;
+            let nullable = column.could_be_nullable();
+            let name = column.name_to_string().unwrap_or_default();
+            let key = quirks.column_name_matcher.key(&name);
+            let by_column = keys.iter().find(|(other, _)| *other == key);
+            let by_class = || {
+                self.by_class
+                    .iter()
+                    .find(|(class, _)| *class == TypeClass::of(data_type))
+                    .map(|(_, target)| *target)
+            };
+            let target = by_column
+                .map(|(_, target)| *target)
+                .or_else(by_class)
+                .unwrap_or(CoerceTo::Native);
+            let text = || {
+                let reported_len = data_type.utf8_len().unwrap_or(0);
+                let limit = match self.max_str_len {
+                    None if data_type.is_long_text() || reported_len == 0 => {
+                        Some(TextRowSet::LONG_TEXT_LIMIT)
+                    }
+                    limit => limit,
+                };
+                BufferDesc::Text {
+                    max_str_len: max_str_len_with_limit(reported_len, limit),
+                }
+            };
+            let desc = match target {
+                CoerceTo::Native => match quirks.buffer_desc(data_type, nullable) {
+                    // Text length is subject to the same limits, whether coerced or not.
+                    Some(BufferDesc::Text { .. }) | None => text(),
+                    Some(desc) => desc,
+                },
+                CoerceTo::Text => text(),
+                CoerceTo::I64 => BufferDesc::I64 { nullable },
+                CoerceTo::F64 => BufferDesc::F64 { nullable },
+                CoerceTo::Buffer(desc) => desc,
+            };
+            descs.push(desc);
+        }
+        Ok(descs)
+    }
+
+    /// Buffer holding up to `capacity` rows of the result set, with the columns described by
+    /// [`Self::buffer_descs`].
+    pub fn buffer_for_cursor(
+        &self,
+        capacity: usize,
+        metadata: &mut impl ResultSetMetadata,
+    ) -> Result<ColumnarAnyBuffer, Error> {
+        let descs = self.buffer_descs(metadata)?;
+        ColumnarAnyBuffer::try_from_descs(capacity, descs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DataType;
+
+    use super::TypeClass;
+
+    #[test]
+    fn classify_data_types() {
+        assert_eq!(
+            TypeClass::Text,
+            TypeClass::of(DataType::WVarchar { length: 5 })
+        );
+        assert_eq!(TypeClass::Integer, TypeClass::of(DataType::BigInt));
+        assert_eq!(
+            TypeClass::Decimal,
+            TypeClass::of(DataType::Numeric {
+                precision: 10,
+                scale: 2
+            })
+        );
+        assert_eq!(TypeClass::Float, TypeClass::of(DataType::Real));
+        assert_eq!(TypeClass::Other, TypeClass::of(DataType::Unknown));
+    }
+}
@@ -0,0 +1,229 @@
+use super::{AnySlice, ColumnarAnyBuffer};
+
+/// Summary of the values of a single column within a fetched row set. Computed by
+/// [`AnySlice::statistics`] or for all columns at once by
+/// [`ColumnarAnyBuffer::column_statistics`]. Useful to drive resizing decisions of text buffers or
+/// data quality reports.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColumnStatistics {
+    /// Number of rows in the row set.
+    pub num_rows: usize,
+    /// Number of `NULL` values.
+    pub null_count: usize,
+    /// Smallest and largest value of numeric columns. `None` for other columns or if all values
+    /// are `NULL`.
+    pub range: Option<NumericRange>,
+    /// Length of the longest value of text or binary columns. In bytes for narrow text and binary
+    /// columns, in UTF-16 code units for wide text columns. `None` for other columns or if all
+    /// values are `NULL`. Truncated values count with their length in the buffer.
+    pub max_len: Option<usize>,
+}
+
+/// Smallest and largest value of a numeric column. See [`ColumnStatistics::range`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumericRange {
+    /// Range of integer columns.
+    Integer { min: i64, max: i64 },
+    /// Range of floating point columns. `NaN`s are ignored.
+    Float { min: f64, max: f64 },
+}
+
+impl<'a> AnySlice<'a> {
+    /// Null count and, depending on the type of the column, value range or maximum length of the
+    /// values in this column.
+    ///
+    /// ```
+    /// use odbc_api::buffers::{AnySlice, NumericRange};
+    ///
+    /// fn print_range(column: AnySlice<'_>) {
+    ///     match column.statistics().range {
+    ///         Some(NumericRange::Integer { min, max }) => println!("From {min} to {max}"),
+    ///         Some(NumericRange::Float { min, max }) => println!("From {min} to {max}"),
+    ///         None => println!("No range"),
+    ///     }
+    /// }
+    /// ```
+    pub fn statistics(&self) -> ColumnStatistics {
+        match *self {
+            AnySlice::Text(view) => lengths(
+                view.len(),
+                (0..view.len()).map(|i| view.get(i).map(<[u8]>::len)),
+            ),
+            AnySlice::WText(view) => lengths(
+                view.len(),
+                (0..view.len()).map(|i| view.get(i).map(<[u16]>::len)),
+            ),
+            AnySlice::Binary(view) => lengths(
+                view.len(),
+                (0..view.len()).map(|i| view.get(i).map(<[u8]>::len)),
+            ),
+            AnySlice::F64(values) => floats(values.len(), values.iter().map(|&v| Some(v))),
+            AnySlice::F32(values) => floats(values.len(), values.iter().map(|&v| Some(v.into()))),
+            AnySlice::I8(values) => integers(values.len(), values.iter().map(|&v| Some(v.into()))),
+            AnySlice::I16(values) => integers(values.len(), values.iter().map(|&v| Some(v.into()))),
+            AnySlice::I32(values) => integers(values.len(), values.iter().map(|&v| Some(v.into()))),
+            AnySlice::I64(values) => integers(values.len(), values.iter().map(|&v| Some(v))),
+            AnySlice::U8(values) => integers(values.len(), values.iter().map(|&v| Some(v.into()))),
+            AnySlice::Date(values) => no_nulls(values.len()),
+            AnySlice::Time(values) => no_nulls(values.len()),
+            AnySlice::Timestamp(values) => no_nulls(values.len()),
+            AnySlice::Bit(values) => no_nulls(values.len()),
+            AnySlice::NullableF64(values) => floats(values.len(), values.map(|v| v.copied())),
+            AnySlice::NullableF32(values) => {
+                floats(values.len(), values.map(|v| v.map(|&v| v.into())))
+            }
+            AnySlice::NullableI8(values) => {
+                integers(values.len(), values.map(|v| v.map(|&v| v.into())))
+            }
+            AnySlice::NullableI16(values) => {
+                integers(values.len(), values.map(|v| v.map(|&v| v.into())))
+            }
+            AnySlice::NullableI32(values) => {
+                integers(values.len(), values.map(|v| v.map(|&v| v.into())))
+            }
+            AnySlice::NullableI64(values) => integers(values.len(), values.map(|v| v.copied())),
+            AnySlice::NullableU8(values) => {
+                integers(values.len(), values.map(|v| v.map(|&v| v.into())))
+            }
+            AnySlice::NullableDate(values) => nulls(values.len(), values.map(|v| v.map(|_| ()))),
+            AnySlice::NullableTime(values) => nulls(values.len(), values.map(|v| v.map(|_| ()))),
+            AnySlice::NullableTimestamp(values) => {
+                nulls(values.len(), values.map(|v| v.map(|_| ())))
+            }
+            AnySlice::NullableBit(values) => nulls(values.len(), values.map(|v| v.map(|_| ()))),
+        }
+    }
+}
+
+impl ColumnarAnyBuffer {
+    /// Statistics for each column of the current row set. See [`AnySlice::statistics`].
+    pub fn column_statistics(&self) -> Vec<ColumnStatistics> {
+        (0..self.num_cols())
+            .map(|index| self.column(index).statistics())
+            .collect()
+    }
+}
+
+fn no_nulls(num_rows: usize) -> ColumnStatistics {
+    ColumnStatistics {
+        num_rows,
+        null_count: 0,
+        range: None,
+        max_len: None,
+    }
+}
+
+fn nulls(num_rows: usize, values: impl Iterator<Item = Option<()>>) -> ColumnStatistics {
+    ColumnStatistics {
+        null_count: values.filter(Option::is_none).count(),
+        ..no_nulls(num_rows)
+    }
+}
+
+fn lengths(num_rows: usize, lengths: impl Iterator<Item = Option<usize>>) -> ColumnStatistics {
+    let mut statistics = no_nulls(num_rows);
+    for length in lengths {
+        match length {
+            None => statistics.null_count += 1,
+            Some(length) => {
+                statistics.max_len = Some(statistics.max_len.map_or(length, |m| m.max(length)))
+            }
+        }
+    }
+    statistics
+}
+
+fn integers(num_rows: usize, values: impl Iterator<Item = Option<i64>>) -> ColumnStatistics {
+    let mut statistics = no_nulls(num_rows);
+    let mut range: Option<(i64, i64)> = None;
+    for value in values {
+        match value {
+            None => statistics.null_count += 1,
+            Some(v) => {
+                range = Some(range.map_or((v, v), |(min, max)| (min.min(v), max.max(v))));
+            }
+        }
+    }
+    statistics.range = range.map(|(min, max)| NumericRange::Integer { min, max });
+    statistics
+}
+
+fn floats(num_rows: usize, values: impl Iterator<Item = Option<f64>>) -> ColumnStatistics {
+    let mut statistics = no_nulls(num_rows);
+    let mut range: Option<(f64, f64)> = None;
+    for value in values {
+        match value {
+            None => statistics.null_count += 1,
+            Some(v) if v.is_nan() => (),
+            Some(v) => {
+                range = Some(range.map_or((v, v), |(min, max)| (min.min(v), max.max(v))));
+            }
+        }
+    }
+    statistics.range = range.map(|(min, max)| NumericRange::Float { min, max });
+    statistics
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::buffers::{
+        column_with_indicator::ColumnWithIndicator, AnyBuffer, AnySlice, CharColumn,
+        ColumnarAnyBuffer,
+    };
+
+    use super::NumericRange;
+
+    #[test]
+    fn integer_range_ignores_nulls() {
+        let mut column = ColumnWithIndicator::<i32>::new(4);
+        let mut writer = column.writer_n(3);
+        writer.set_cell(0, Some(5));
+        writer.set_cell(1, None);
+        writer.set_cell(2, Some(-3));
+        let mut buffer = ColumnarAnyBuffer::new(vec![(1, AnyBuffer::NullableI32(column))]);
+        buffer.set_num_rows(3);
+
+        let statistics = buffer.column_statistics();
+
+        assert_eq!(1, statistics.len());
+        assert_eq!(3, statistics[0].num_rows);
+        assert_eq!(1, statistics[0].null_count);
+        assert_eq!(
+            Some(NumericRange::Integer { min: -3, max: 5 }),
+            statistics[0].range
+        );
+        assert_eq!(None, statistics[0].max_len);
+    }
+
+    #[test]
+    fn float_range_ignores_nan() {
+        let values = [1.5, f64::NAN, -0.5];
+
+        let statistics = AnySlice::F64(&values).statistics();
+
+        assert_eq!(0, statistics.null_count);
+        assert_eq!(
+            Some(NumericRange::Float {
+                min: -0.5,
+                max: 1.5
+            }),
+            statistics.range
+        );
+    }
+
+    #[test]
+    fn max_len_of_text() {
+        let mut column = CharColumn::new(3, 10);
+        column.set_value(0, Some(b"Hello"));
+        column.set_value(1, Some(b"Hi"));
+        column.set_value(2, None);
+        let mut buffer = ColumnarAnyBuffer::new(vec![(1, AnyBuffer::Text(column))]);
+        buffer.set_num_rows(3);
+
+        let statistics = buffer.column(0).statistics();
+
+        assert_eq!(1, statistics.null_count);
+        assert_eq!(Some(5), statistics.max_len);
+        assert_eq!(None, statistics.range);
+    }
+}
@@ -1,6 +1,8 @@
 use crate::{
+    buffers::{Indicator, ValidityBitmap},
     fixed_sized::{Bit, Pod},
     handles::{CData, CDataMut},
+    FetchStatistics,
 };
 use odbc_sys::{Date, Time, Timestamp, NULL_DATA};
 use std::{
@@ -71,6 +73,18 @@ where
     pub fn capacity(&self) -> usize {
         self.indicators.len()
     }
+
+    /// Number of bytes allocated for values and indicators.
+    pub fn memory_usage(&self) -> usize {
+        self.values.len() * size_of::<T>() + self.indicators.len() * size_of::<isize>()
+    }
+
+    /// Accounts for the first `num_rows` values in `statistics`.
+    pub(crate) fn record_statistics(&self, num_rows: usize, statistics: &mut FetchStatistics) {
+        for &indicator in &self.indicators[..num_rows] {
+            statistics.record_fixed_sized_cell(Indicator::from_isize(indicator), size_of::<T>());
+        }
+    }
 }
 
 /// Iterates over the elements of a column buffer. Returned by
@@ -92,6 +106,23 @@ impl<'a, T> NullableSlice<'a, T> {
         self.values.len()
     }
 
+    /// Value at the specified row index. `None` if the value is `NULL`. Indices are relative to
+    /// the elements not yet consumed by iterating over the slice.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is not smaller than [`Self::len`].
+    pub fn get(&self, index: usize) -> Option<&'a T> {
+        if index >= self.len() {
+            panic!("Row index points beyond the range of valid values.")
+        }
+        if self.indicators[index] == NULL_DATA {
+            None
+        } else {
+            Some(&self.values[index])
+        }
+    }
+
     /// Read access to the underlying raw value and indicator buffer.
     ///
     /// The number of elements in the buffer is equal to the number of rows returned in the current
@@ -122,6 +153,26 @@ impl<'a, T> NullableSlice<'a, T> {
     pub fn raw_values(&self) -> (&'a [T], &'a [isize]) {
         (self.values, self.indicators)
     }
+
+    /// Packed bitmap of the elements which are not `NULL`, computed from the indicators. Together
+    /// with the values returned by [`Self::raw_values`] this is the representation columnar
+    /// formats like Apache Arrow use.
+    ///
+    /// ```
+    /// use odbc_api::buffers::{NullableSlice, ValidityBitmap};
+    ///
+    /// /// Values and validity, as required to build an Arrow array.
+    /// fn to_arrow_parts(odbc_slice: NullableSlice<i32>) -> (Vec<i32>, Option<ValidityBitmap>) {
+    ///     let (values, _indicators) = odbc_slice.raw_values();
+    ///     let validity = odbc_slice.validity();
+    ///     // Arrow allows omitting the bitmap, if there are no NULLs.
+    ///     let validity = (validity.null_count() != 0).then_some(validity);
+    ///     (values.to_vec(), validity)
+    /// }
+    /// ```
+    pub fn validity(&self) -> ValidityBitmap {
+        ValidityBitmap::from_indicators(self.indicators)
+    }
 }
 
 impl<'a, T> Iterator for NullableSlice<'a, T> {
@@ -141,6 +192,25 @@ impl<'a, T> Iterator for NullableSlice<'a, T> {
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for NullableSlice<'a, T> {}
+
+impl<'a, T> IntoIterator for &NullableSlice<'a, T> {
+    type Item = Option<&'a T>;
+    type IntoIter = NullableSlice<'a, T>;
+
+    /// Iterates over a copy of the slice, leaving this one untouched.
+    fn into_iter(self) -> Self::IntoIter {
+        NullableSlice {
+            indicators: self.indicators,
+            values: self.values,
+        }
+    }
 }
 
 unsafe impl<T> CData for ColumnWithIndicator<T>
@@ -274,6 +344,25 @@ impl<'a, T> NullableSliceMut<'a, T> {
     pub fn raw_values(&mut self) -> (&mut [T], &mut [isize]) {
         (self.values, self.indicators)
     }
+
+    /// Mutable access to all elements of the slice. `NULL` elements are `None`. Use
+    /// [`Self::set_cell`] to change whether an element is `NULL`.
+    ///
+    /// ```
+    /// use odbc_api::buffers::NullableSliceMut;
+    ///
+    /// fn scale(odbc_slice: &mut NullableSliceMut<f64>, factor: f64) {
+    ///     for value in odbc_slice.iter_mut().flatten() {
+    ///         *value *= factor;
+    ///     }
+    /// }
+    /// ```
+    pub fn iter_mut(&mut self) -> impl ExactSizeIterator<Item = Option<&mut T>> + '_ {
+        self.values
+            .iter_mut()
+            .zip(self.indicators.iter())
+            .map(|(value, &indicator)| (indicator != NULL_DATA).then_some(value))
+    }
 }
 
 impl<'a, T> NullableSliceMut<'a, T> {
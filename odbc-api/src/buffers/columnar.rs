@@ -1,6 +1,6 @@
 use std::{
-    cmp::min,
-    collections::HashSet,
+    collections::{HashMap, HashSet},
+    mem::size_of,
     str::{from_utf8, Utf8Error},
 };
 
@@ -10,10 +10,10 @@ use crate::{
     handles::{CDataMut, Statement, StatementRef},
     parameter::WithDataType,
     result_set_metadata::utf8_display_sizes,
-    Error, ResultSetMetadata, RowSetBuffer,
+    Error, FetchStatistics, ResultSetMetadata, RowSetBuffer,
 };
 
-use super::{Indicator, TextColumn};
+use super::{max_str_len_with_limit, Indicator, TextColumn};
 
 impl<C: ColumnBuffer> ColumnarBuffer<C> {
     /// Create a new instance from columns with unique indicies. Capacity of the buffer will be the
@@ -22,7 +22,7 @@ impl<C: ColumnBuffer> ColumnarBuffer<C> {
     ///
     /// You do not want to call this constructor directly unless you want to provide your own buffer
     /// implentation. Most users of this crate may want to use the constructors like
-    /// [`crate::buffers::ColumnarAnyBuffer::from_description`] or
+    /// [`crate::buffers::ColumnarAnyBuffer::try_from_descs`] or
     /// [`crate::buffers::TextRowSet::from_max_str_lens`] instead.
     pub fn new(columns: Vec<(u16, C)>) -> Self {
         // Assert capacity
@@ -79,6 +79,36 @@ impl<C: ColumnBuffer> ColumnarBuffer<C> {
     pub fn column(&self, buffer_index: usize) -> C::View<'_> {
         self.columns[buffer_index].1.view(*self.num_rows)
     }
+
+    /// Number of bytes allocated for values and indicators of all columns. See
+    /// [`ColumnBuffer::memory_usage`].
+    pub fn memory_usage(&self) -> usize {
+        self.columns
+            .iter()
+            .map(|(_, column)| column.memory_usage())
+            .sum()
+    }
+
+    /// Number of bytes allocated for values and indicators of the column at `buffer_index`.
+    pub fn column_memory_usage(&self, buffer_index: usize) -> usize {
+        self.columns[buffer_index].1.memory_usage()
+    }
+
+    /// Column buffers in the order they have been passed to the constructor.
+    pub(crate) fn column_buffers(&self) -> impl Iterator<Item = &C> {
+        self.columns.iter().map(|(_, column)| column)
+    }
+
+    /// Column buffers together with the one based index of the column they are bound to.
+    pub(crate) fn columns(&self) -> &[(u16, C)] {
+        &self.columns
+    }
+
+    /// Sets the number of valid rows. Callers must ensure the rows are initialized.
+    pub(crate) fn set_num_rows(&mut self, num_rows: usize) {
+        assert!(num_rows <= self.row_capacity);
+        *self.num_rows = num_rows;
+    }
 }
 
 unsafe impl<C> RowSetBuffer for ColumnarBuffer<C>
@@ -103,6 +133,12 @@ where
         }
         Ok(())
     }
+
+    fn record_statistics(&self, statistics: &mut FetchStatistics) {
+        for (_, column) in &self.columns {
+            column.record_statistics(*self.num_rows, statistics);
+        }
+    }
 }
 
 /// A columnar buffer intended to be bound with [crate::Cursor::bind_buffer] in order to obtain
@@ -148,6 +184,19 @@ pub unsafe trait ColumnBuffer: CDataMut {
 
     /// Current capacity of the column
     fn capacity(&self) -> usize;
+
+    /// Number of bytes allocated for values and indicators of this column. Useful for applications
+    /// which want to stay within a memory budget, to decide on a batch size. The default
+    /// implementation reports `0`, all column buffers of this crate override it.
+    fn memory_usage(&self) -> usize {
+        0
+    }
+
+    /// Accounts for the first `valid_rows` values in `statistics`. Called by
+    /// [`crate::BlockCursor`] after each fetch. The default implementation records nothing.
+    fn record_statistics(&self, valid_rows: usize, statistics: &mut FetchStatistics) {
+        let _ = (valid_rows, statistics);
+    }
 }
 
 unsafe impl<T> ColumnBuffer for WithDataType<T>
@@ -167,6 +216,14 @@ where
     fn capacity(&self) -> usize {
         self.value.capacity()
     }
+
+    fn memory_usage(&self) -> usize {
+        self.value.memory_usage()
+    }
+
+    fn record_statistics(&self, valid_rows: usize, statistics: &mut FetchStatistics) {
+        self.value.record_statistics(valid_rows, statistics)
+    }
 }
 
 unsafe impl<'a, T> BoundInputSlice<'a> for WithDataType<T>
@@ -262,6 +319,10 @@ where
 pub type TextRowSet = ColumnarBuffer<TextColumn<u8>>;
 
 impl TextRowSet {
+    /// Upper bound for the length of long text columns (e.g. `XML`, `TEXT` or `VARCHAR(max)`)
+    /// used by [`Self::for_cursor`], if no limit has been specified for them.
+    pub const LONG_TEXT_LIMIT: usize = 4096;
+
     /// The resulting text buffer is not in any way tied to the cursor, other than that its buffer
     /// sizes a tailor fitted to result set the cursor is iterating over.
     ///
@@ -280,34 +341,71 @@ impl TextRowSet {
     ///   sometimes drivers are just not that good at it. This argument allows you to specify an
     ///   upper bound for the length of character data. Any size reported by the driver is capped to
     ///   this value. In case the database returns a size of 0 (which some systems used to indicate)
-    ///   arbitrariely large values, the element size is set to upper bound.
+    ///   arbitrariely large values, the element size is set to upper bound. Without an upper bound,
+    ///   long text columns like `XML` or `VARCHAR(max)` are capped to [`Self::LONG_TEXT_LIMIT`].
     pub fn for_cursor(
         batch_size: usize,
         cursor: &mut impl ResultSetMetadata,
         max_str_limit: Option<usize>,
     ) -> Result<TextRowSet, Error> {
-        let buffers = utf8_display_sizes(cursor)?
+        Self::for_cursor_with_limits(batch_size, cursor, max_str_limit, &HashMap::new())
+    }
+
+    /// Same as [`Self::for_cursor`], but allows to specify the upper bound for the length of
+    /// character data for individual columns. This way e.g. a single column holding large JSON
+    /// documents does not force a large limit onto all other columns.
+    ///
+    /// # Parameters
+    ///
+    /// * `batch_size`: The maximum number of rows the buffer is able to hold.
+    /// * `cursor`: Used to query the display size for each column of the row set.
+    /// * `max_str_limit`: Upper bound for the length of character data of columns without an entry
+    ///   in `column_limits`. See [`Self::for_cursor`].
+    /// * `column_limits`: Maps column numbers (starting at `1`) to an upper bound for the length of
+    ///   character data in that column. Takes precedence over `max_str_limit`.
+    ///
+    /// Columns without any limit, which hold long text (see [`crate::DataType::is_long_text`]), are
+    /// capped to [`Self::LONG_TEXT_LIMIT`]. Their reported size is often `0` or several GiB,
+    /// neither of which is a sensible buffer size. Values exceeding the limit are truncated, which
+    /// can be detected using the indicators, or fetched in full using
+    /// [`crate::CursorRow::get_text`].
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use odbc_api::{buffers::TextRowSet, Cursor};
+    ///
+    /// fn text_buffer(cursor: &mut impl Cursor) -> Result<TextRowSet, odbc_api::Error> {
+    ///     // Allow up to 64 KiB for the JSON document in the third column, but cap every other
+    ///     // column to 255 characters.
+    ///     let column_limits = HashMap::from([(3, 65536)]);
+    ///     TextRowSet::for_cursor_with_limits(1000, cursor, Some(255), &column_limits)
+    /// }
+    /// ```
+    pub fn for_cursor_with_limits(
+        batch_size: usize,
+        cursor: &mut impl ResultSetMetadata,
+        max_str_limit: Option<usize>,
+        column_limits: &HashMap<u16, usize>,
+    ) -> Result<TextRowSet, Error> {
+        let reported_lens: Vec<_> = utf8_display_sizes(cursor)?.collect();
+        let buffers = reported_lens
+            .into_iter()
             .enumerate()
             .map(|(buffer_index, reported_len)| {
                 let buffer_index = buffer_index as u16;
                 let col_index = buffer_index + 1;
                 let max_str_len = reported_len?;
-                let buffer = if let Some(upper_bound) = max_str_limit {
-                    let max_str_len = if max_str_len == 0 {
-                        upper_bound
-                    } else {
-                        min(max_str_len, upper_bound)
-                    };
-                    TextColumn::new(batch_size, max_str_len)
-                } else {
-                    TextColumn::try_new(batch_size, max_str_len).map_err(|source| {
-                        Error::TooLargeColumnBufferSize {
-                            buffer_index,
-                            num_elements: source.num_elements,
-                            element_size: source.element_size,
-                        }
-                    })?
+                let limit = match column_limits.get(&col_index).copied().or(max_str_limit) {
+                    Some(limit) => Some(limit),
+                    None if cursor.col_data_type(col_index)?.is_long_text() => {
+                        Some(Self::LONG_TEXT_LIMIT)
+                    }
+                    None => None,
                 };
+                // Even limited columns may be too large, e.g. if the batch size is huge.
+                let buffer =
+                    TextColumn::try_new(batch_size, max_str_len_with_limit(max_str_len, limit))
+                        .map_err(|source| source.add_context(buffer_index))?;
 
                 Ok((col_index, buffer))
             })
@@ -405,6 +503,14 @@ where
     fn capacity(&self) -> usize {
         self.len()
     }
+
+    fn memory_usage(&self) -> usize {
+        self.len() * size_of::<T>()
+    }
+
+    fn record_statistics(&self, valid_rows: usize, statistics: &mut FetchStatistics) {
+        statistics.num_bytes += valid_rows * size_of::<T>();
+    }
 }
 
 #[cfg(test)]
@@ -414,8 +520,19 @@ mod tests {
 
     #[test]
     #[should_panic(expected = "Column indices must be unique.")]
+    #[allow(deprecated)]
     fn assert_unique_column_indices() {
         let bd = BufferDesc::I32 { nullable: false };
         ColumnarAnyBuffer::from_descs_and_indices(1, [(1, bd), (2, bd), (1, bd)].iter().cloned());
     }
+
+    #[test]
+    #[should_panic(expected = "Column indices must be unique.")]
+    fn try_from_descs_and_indices_asserts_unique_column_indices() {
+        let bd = BufferDesc::I32 { nullable: false };
+        let _ = ColumnarAnyBuffer::try_from_descs_and_indices(
+            1,
+            [(1, bd), (2, bd), (1, bd)].iter().cloned(),
+        );
+    }
 }
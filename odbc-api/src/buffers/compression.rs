@@ -0,0 +1,173 @@
+use std::io::{self, Read, Write};
+
+use super::ColumnarAnyBuffer;
+
+/// Algorithm used to compress a batch by [`ColumnarAnyBuffer::write_compressed_to`] and
+/// [`CompressedBatch::new`]. Requires the `lz4` or the `zstd` feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// LZ4 frame format. Fast, with a moderate compression ratio. Requires the `lz4` feature.
+    #[cfg(feature = "lz4")]
+    Lz4,
+    /// Zstandard with the given compression level, e.g. `3`. Better compression ratio than
+    /// [`Self::Lz4`] at the cost of speed. Requires the `zstd` feature.
+    #[cfg(feature = "zstd")]
+    Zstd {
+        /// `1` (fastest) up to `22` (smallest). `0` selects the default level of Zstandard.
+        level: i32,
+    },
+}
+
+impl ColumnarAnyBuffer {
+    /// Like [`Self::write_to`], but compresses the batch with `compression`. Text columns with
+    /// many repeated values, or values much shorter than the maximum string length, shrink
+    /// considerably. Use [`Self::read_compressed_from`] with the same `compression` to restore
+    /// the buffer.
+    ///
+    /// ```
+    /// use odbc_api::buffers::{BufferDesc, ColumnarAnyBuffer, Compression};
+    ///
+    /// # #[cfg(feature = "lz4")]
+    /// # {
+    /// let buffer =
+    ///     ColumnarAnyBuffer::try_from_descs(100, [BufferDesc::Text { max_str_len: 50 }]).unwrap();
+    /// let mut bytes = Vec::new();
+    /// buffer.write_compressed_to(&mut bytes, Compression::Lz4).unwrap();
+    /// let restored =
+    ///     ColumnarAnyBuffer::read_compressed_from(bytes.as_slice(), Compression::Lz4).unwrap();
+    /// assert_eq!(0, restored.num_rows());
+    /// # }
+    /// ```
+    pub fn write_compressed_to(&self, out: impl Write, compression: Compression) -> io::Result<()> {
+        match compression {
+            #[cfg(feature = "lz4")]
+            Compression::Lz4 => {
+                let mut encoder = lz4_flex::frame::FrameEncoder::new(out);
+                self.write_to(&mut encoder)?;
+                encoder.finish().map_err(io::Error::from)?;
+            }
+            #[cfg(feature = "zstd")]
+            Compression::Zstd { level } => {
+                let mut encoder = zstd::stream::Encoder::new(out, level)?;
+                self.write_to(&mut encoder)?;
+                encoder.finish()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Restores a buffer written with [`Self::write_compressed_to`]. `compression` must match the
+    /// one the buffer has been written with.
+    pub fn read_compressed_from(input: impl Read, compression: Compression) -> io::Result<Self> {
+        match compression {
+            #[cfg(feature = "lz4")]
+            Compression::Lz4 => Self::read_from(lz4_flex::frame::FrameDecoder::new(input)),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd { .. } => Self::read_from(zstd::stream::Decoder::new(input)?),
+        }
+    }
+}
+
+/// A fetched batch, held compressed in memory. Intended to keep large extracts within a memory
+/// budget during downstream processing, e.g. if all batches must be fetched before the first one
+/// can be processed. Requires the `lz4` or the `zstd` feature.
+///
+/// ```no_run
+/// use odbc_api::{buffers::{ColumnarAnyBuffer, CompressedBatch, Compression}, Cursor};
+///
+/// fn fetch_all(
+///     cursor: impl Cursor,
+///     buffer: ColumnarAnyBuffer,
+///     compression: Compression,
+/// ) -> Result<Vec<CompressedBatch>, Box<dyn std::error::Error>> {
+///     let mut block_cursor = cursor.bind_buffer(buffer)?;
+///     let mut batches = Vec::new();
+///     while let Some(batch) = block_cursor.fetch()? {
+///         batches.push(CompressedBatch::new(batch, compression)?);
+///     }
+///     Ok(batches)
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CompressedBatch {
+    compression: Compression,
+    bytes: Vec<u8>,
+}
+
+impl CompressedBatch {
+    /// Compresses the valid rows of `buffer`.
+    pub fn new(buffer: &ColumnarAnyBuffer, compression: Compression) -> io::Result<Self> {
+        let mut bytes = Vec::new();
+        buffer.write_compressed_to(&mut bytes, compression)?;
+        bytes.shrink_to_fit();
+        Ok(Self { compression, bytes })
+    }
+
+    /// Algorithm the batch has been compressed with.
+    pub fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    /// Size of the compressed batch in bytes.
+    pub fn compressed_len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Restores the batch. The buffer has the same capacity, column indices, buffer kinds and
+    /// valid rows as the one passed to [`Self::new`].
+    pub fn decompress(&self) -> io::Result<ColumnarAnyBuffer> {
+        ColumnarAnyBuffer::read_compressed_from(self.bytes.as_slice(), self.compression)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::buffers::{AnyBuffer, AnySlice, BufferDesc, ColumnarAnyBuffer};
+
+    use super::{CompressedBatch, Compression};
+
+    /// Batch of 1000 rows with only a handful of distinct, short values in a text column.
+    fn repetitive_text() -> ColumnarAnyBuffer {
+        let mut text =
+            AnyBuffer::try_from_desc(1000, BufferDesc::Text { max_str_len: 50 }).unwrap();
+        if let AnyBuffer::Text(column) = &mut text {
+            for index in 0..1000 {
+                let value = ["DE", "FR", "IT"][index % 3];
+                column.set_value(index, (index % 7 != 0).then_some(value.as_bytes()));
+            }
+        }
+        let mut buffer = unsafe { ColumnarAnyBuffer::new_unchecked(1000, vec![(1, text)]) };
+        buffer.set_num_rows(1000);
+        buffer
+    }
+
+    fn assert_round_trip(compression: Compression) {
+        let buffer = repetitive_text();
+
+        let batch = CompressedBatch::new(&buffer, compression).unwrap();
+        let restored = batch.decompress().unwrap();
+
+        let mut uncompressed = Vec::new();
+        buffer.write_to(&mut uncompressed).unwrap();
+        assert!(batch.compressed_len() * 10 < uncompressed.len());
+        assert_eq!(1000, restored.num_rows());
+        let (AnySlice::Text(expected), AnySlice::Text(actual)) =
+            (buffer.column(0), restored.column(0))
+        else {
+            panic!("Expected text columns")
+        };
+        assert!(expected.iter().eq(actual.iter()));
+    }
+
+    #[cfg(feature = "lz4")]
+    #[test]
+    fn lz4_round_trip() {
+        assert_round_trip(Compression::Lz4)
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn zstd_round_trip() {
+        assert_round_trip(Compression::Zstd { level: 3 })
+    }
+}
@@ -0,0 +1,472 @@
+use std::ffi::c_void;
+
+use odbc_sys::CDataType;
+use thiserror::Error as ThisError;
+
+use crate::{
+    columnar_bulk_inserter::BoundInputSlice,
+    handles::{CData, CDataMut, HasDataType, StatementRef},
+    DataType, FetchStatistics,
+};
+
+use super::{CharColumn, ColumnBuffer, TextColumnIt, TextColumnView};
+
+/// A column buffer for fixed-point numbers like `DECIMAL`, `NUMERIC` or the `MONEY` and
+/// `SMALLMONEY` types of Microsoft SQL Server. Values are represented as 64 Bit integers scaled by
+/// `10^scale`, e.g. `12.34` is `1234` for a scale of `2`. In contrast to binding such columns as
+/// `f64` no precision is lost.
+///
+/// Values are transferred in their text representation, which is supported by virtually any driver,
+/// and parsed (or formatted) exactly, without rounding.
+///
+/// # Example
+///
+/// ```no_run
+/// use odbc_api::{buffers::{ColumnarBuffer, DecimalColumn}, Connection, Cursor};
+///
+/// fn print_prices(conn: &Connection<'_>) -> Result<(), odbc_api::Error> {
+///     let cursor = conn.execute("SELECT price FROM products", ())?.unwrap();
+///     let mut buffer = ColumnarBuffer::new(vec![(1, DecimalColumn::money(1000))]);
+///     let mut cursor = cursor.bind_buffer(&mut buffer)?;
+///     while let Some(batch) = cursor.fetch()? {
+///         for price in batch.column(0).iter() {
+///             // Scaled by 10^4. E.g. `12.3400` is `123400`.
+///             println!("{:?}", price.expect("Price must fit into a 64 Bit integer."));
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct DecimalColumn {
+    text: CharColumn,
+    precision: usize,
+    scale: i16,
+}
+
+impl DecimalColumn {
+    /// A buffer with room for `batch_size` values with up to `precision` digits in total, `scale`
+    /// of which are fractional digits.
+    ///
+    /// # Panics
+    ///
+    /// If the values can not be represented as 64 Bit integers, i.e. if `precision` is larger than
+    /// `19`, or if `scale` is not within `0..=precision`.
+    pub fn new(batch_size: usize, precision: usize, scale: i16) -> Self {
+        assert!(
+            precision <= 19,
+            "Decimals with a precision of {precision} can not be represented as 64 Bit integers."
+        );
+        assert!(
+            scale >= 0 && scale as usize <= precision,
+            "Scale {scale} must be within 0 and the precision {precision}."
+        );
+        // Sign, decimal point and a leading zero, in case there are no integer digits.
+        let max_str_len = precision + 3;
+        Self {
+            text: CharColumn::new(batch_size, max_str_len),
+            precision,
+            scale,
+        }
+    }
+
+    /// Buffer for the `MONEY` type of Microsoft SQL Server, with a precision of `19` and a scale of
+    /// `4`. Its range is identical to that of `i64`.
+    pub fn money(batch_size: usize) -> Self {
+        Self::new(batch_size, 19, 4)
+    }
+
+    /// Buffer for the `SMALLMONEY` type of Microsoft SQL Server, with a precision of `10` and a
+    /// scale of `4`.
+    pub fn small_money(batch_size: usize) -> Self {
+        Self::new(batch_size, 10, 4)
+    }
+
+    /// Number of fractional digits. Values are scaled by `10^scale`.
+    pub fn scale(&self) -> i16 {
+        self.scale
+    }
+
+    /// Total number of digits.
+    pub fn precision(&self) -> usize {
+        self.precision
+    }
+
+    /// Sets the value at `index` to `NULL` or to `value / 10^scale`. Fails, without changing the
+    /// buffer, if `value` has more digits than the precision of the column.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn set_value(&mut self, index: usize, value: Option<i64>) -> Result<(), DecimalOverflow> {
+        if let Some(value) = value {
+            if num_digits(value) > self.precision {
+                return Err(DecimalOverflow {
+                    value,
+                    precision: self.precision,
+                    scale: self.scale,
+                });
+            }
+        }
+        let text = value.map(|value| format_scaled(value, self.scale as u32));
+        self.text
+            .set_value(index, text.as_ref().map(String::as_bytes));
+        Ok(())
+    }
+}
+
+unsafe impl ColumnBuffer for DecimalColumn {
+    type View<'a> = DecimalColumnView<'a>;
+
+    fn view(&self, valid_rows: usize) -> DecimalColumnView<'_> {
+        DecimalColumnView {
+            text: self.text.view(valid_rows),
+            scale: self.scale as u32,
+        }
+    }
+
+    fn fill_default(&mut self, from: usize, to: usize) {
+        self.text.fill_null(from, to)
+    }
+
+    fn capacity(&self) -> usize {
+        self.text.capacity()
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.text.memory_usage()
+    }
+
+    fn record_statistics(&self, valid_rows: usize, statistics: &mut FetchStatistics) {
+        self.text.record_statistics(valid_rows, statistics)
+    }
+}
+
+unsafe impl CData for DecimalColumn {
+    fn cdata_type(&self) -> CDataType {
+        self.text.cdata_type()
+    }
+
+    fn indicator_ptr(&self) -> *const isize {
+        self.text.indicator_ptr()
+    }
+
+    fn value_ptr(&self) -> *const c_void {
+        self.text.value_ptr()
+    }
+
+    fn buffer_length(&self) -> isize {
+        self.text.buffer_length()
+    }
+}
+
+unsafe impl CDataMut for DecimalColumn {
+    fn mut_indicator_ptr(&mut self) -> *mut isize {
+        self.text.mut_indicator_ptr()
+    }
+
+    fn mut_value_ptr(&mut self) -> *mut c_void {
+        self.text.mut_value_ptr()
+    }
+}
+
+impl HasDataType for DecimalColumn {
+    fn data_type(&self) -> DataType {
+        DataType::Decimal {
+            precision: self.precision,
+            scale: self.scale,
+        }
+    }
+}
+
+unsafe impl<'a> BoundInputSlice<'a> for DecimalColumn {
+    type SliceMut = DecimalColumnSliceMut<'a>;
+
+    unsafe fn as_view_mut(
+        &'a mut self,
+        _parameter_index: u16,
+        _stmt: StatementRef<'a>,
+    ) -> Self::SliceMut {
+        // The buffer is large enough for any value, so it never has to be rebound.
+        DecimalColumnSliceMut { column: self }
+    }
+}
+
+/// Allows filling a bound [`DecimalColumn`] with parameter values.
+pub struct DecimalColumnSliceMut<'a> {
+    column: &'a mut DecimalColumn,
+}
+
+impl<'a> DecimalColumnSliceMut<'a> {
+    /// Sets the value at `row_index` to `NULL` or to `value / 10^scale`. See
+    /// [`DecimalColumn::set_value`].
+    pub fn set_cell(
+        &mut self,
+        row_index: usize,
+        value: Option<i64>,
+    ) -> Result<(), DecimalOverflow> {
+        self.column.set_value(row_index, value)
+    }
+}
+
+/// Read only access to the valid part of a [`DecimalColumn`].
+#[derive(Debug, Clone, Copy)]
+pub struct DecimalColumnView<'c> {
+    text: TextColumnView<'c, u8>,
+    scale: u32,
+}
+
+impl<'c> DecimalColumnView<'c> {
+    /// Number of valid elements.
+    pub fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    /// `true` if the view holds no elements.
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Value at `index` scaled by `10^scale`, or `None` for `NULL`.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is not smaller than [`Self::len`].
+    pub fn get(&self, index: usize) -> Result<Option<i64>, InvalidDecimal> {
+        parse_cell(self.text.get(index), self.scale)
+    }
+
+    /// Iterator over the valid elements of the column. See [`Self::get`].
+    pub fn iter(&self) -> DecimalColumnIt<'c> {
+        DecimalColumnIt {
+            text: self.text.iter(),
+            scale: self.scale,
+        }
+    }
+
+    /// Access to the underlying text representation of the values, as returned by the driver.
+    pub fn raw(&self) -> TextColumnView<'c, u8> {
+        self.text
+    }
+}
+
+impl<'c> IntoIterator for DecimalColumnView<'c> {
+    type Item = Result<Option<i64>, InvalidDecimal>;
+    type IntoIter = DecimalColumnIt<'c>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'c> IntoIterator for &DecimalColumnView<'c> {
+    type Item = Result<Option<i64>, InvalidDecimal>;
+    type IntoIter = DecimalColumnIt<'c>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over a [`DecimalColumnView`]. See [`DecimalColumnView::iter`].
+#[derive(Debug)]
+pub struct DecimalColumnIt<'c> {
+    text: TextColumnIt<'c, u8>,
+    scale: u32,
+}
+
+impl<'c> Iterator for DecimalColumnIt<'c> {
+    type Item = Result<Option<i64>, InvalidDecimal>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.text.next().map(|cell| parse_cell(cell, self.scale))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.text.size_hint()
+    }
+}
+
+impl<'c> ExactSizeIterator for DecimalColumnIt<'c> {}
+
+/// The text representation of a value returned by the driver could not be represented as a 64 Bit
+/// integer scaled by `10^scale`. Either because it is not a decimal number, because it has
+/// non-zero digits beyond the scale, or because it is out of range.
+#[derive(Debug, Clone, PartialEq, Eq, ThisError)]
+#[error(
+    "The value '{text}' returned by the data source can not be represented as a 64 Bit integer \
+    scaled by 10^{scale}."
+)]
+pub struct InvalidDecimal {
+    /// Text representation of the value as returned by the driver.
+    pub text: String,
+    /// Scale of the column buffer.
+    pub scale: u32,
+}
+
+/// A value passed to [`DecimalColumn::set_value`] has more digits than the precision of the column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ThisError)]
+#[error(
+    "The value {value} scaled by 10^{scale} has more digits than the precision {precision} of the \
+    decimal column."
+)]
+pub struct DecimalOverflow {
+    /// Value passed to the buffer, scaled by `10^scale`.
+    pub value: i64,
+    /// Maximum number of digits of the column.
+    pub precision: usize,
+    /// Number of fractional digits of the column.
+    pub scale: i16,
+}
+
+/// Number of decimal digits of `value`, ignoring the sign.
+fn num_digits(value: i64) -> usize {
+    value
+        .unsigned_abs()
+        .checked_ilog10()
+        .map_or(1, |log| log as usize + 1)
+}
+
+fn parse_cell(cell: Option<&[u8]>, scale: u32) -> Result<Option<i64>, InvalidDecimal> {
+    cell.map(|text| {
+        parse_scaled(text, scale).ok_or_else(|| InvalidDecimal {
+            text: String::from_utf8_lossy(text).into_owned(),
+            scale,
+        })
+    })
+    .transpose()
+}
+
+/// Parses the text representation of a decimal number, e.g. `-12.3400` or `.5`, into an integer
+/// scaled by `10^scale`.
+fn parse_scaled(text: &[u8], scale: u32) -> Option<i64> {
+    let text = trim_ascii_whitespace(text);
+    let (negative, text) = match text.first() {
+        Some(b'-') => (true, &text[1..]),
+        Some(b'+') => (false, &text[1..]),
+        _ => (false, text),
+    };
+    let (integer, fraction) = match text.iter().position(|&c| c == b'.') {
+        Some(pos) => (&text[..pos], &text[pos + 1..]),
+        None => (text, &[][..]),
+    };
+    if integer.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    let scale = scale as usize;
+    // Digits beyond the scale are only acceptable, if they do not change the value.
+    let (fraction, excess) = fraction.split_at(fraction.len().min(scale));
+    if excess.iter().any(|&c| c != b'0') {
+        return None;
+    }
+    let mut magnitude: i128 = 0;
+    let padding = scale - fraction.len();
+    for &c in integer.iter().chain(fraction) {
+        if !c.is_ascii_digit() {
+            return None;
+        }
+        magnitude = magnitude.checked_mul(10)?.checked_add((c - b'0') as i128)?;
+    }
+    magnitude = magnitude.checked_mul(10i128.checked_pow(padding as u32)?)?;
+    let value = if negative { -magnitude } else { magnitude };
+    value.try_into().ok()
+}
+
+/// Formats `value / 10^scale` as text, e.g. `-1234` with scale `2` as `-12.34`.
+pub(super) fn format_scaled(value: i64, scale: u32) -> String {
+    let sign = if value < 0 { "-" } else { "" };
+    let magnitude = (value as i128).abs();
+    if scale == 0 {
+        return format!("{sign}{magnitude}");
+    }
+    let factor = 10i128.pow(scale);
+    let width = scale as usize;
+    format!(
+        "{sign}{}.{:0width$}",
+        magnitude / factor,
+        magnitude % factor
+    )
+}
+
+fn trim_ascii_whitespace(text: &[u8]) -> &[u8] {
+    let start = text
+        .iter()
+        .position(|c| !c.is_ascii_whitespace())
+        .unwrap_or(text.len());
+    let end = text
+        .iter()
+        .rposition(|c| !c.is_ascii_whitespace())
+        .map_or(start, |pos| pos + 1);
+    &text[start..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::buffers::ColumnBuffer;
+
+    use super::{format_scaled, parse_scaled, DecimalColumn, DecimalOverflow, InvalidDecimal};
+
+    #[test]
+    fn parse_decimal_text() {
+        assert_eq!(Some(123400), parse_scaled(b"12.34", 4));
+        assert_eq!(Some(-5000), parse_scaled(b"-.5", 4));
+        assert_eq!(Some(12), parse_scaled(b" 12.0000 ", 0));
+        assert_eq!(Some(i64::MAX), parse_scaled(b"922337203685477.5807", 4));
+        assert_eq!(Some(i64::MIN), parse_scaled(b"-922337203685477.5808", 4));
+        assert_eq!(None, parse_scaled(b"922337203685477.5808", 4));
+        assert_eq!(None, parse_scaled(b"1.23456", 4));
+        assert_eq!(None, parse_scaled(b"$12", 4));
+        assert_eq!(None, parse_scaled(b".", 4));
+        assert_eq!(None, parse_scaled(b"", 4));
+    }
+
+    #[test]
+    fn format_decimal_text() {
+        assert_eq!("12.3400", format_scaled(123400, 4));
+        assert_eq!("-0.0005", format_scaled(-5, 4));
+        assert_eq!("-922337203685477.5808", format_scaled(i64::MIN, 4));
+        assert_eq!("42", format_scaled(42, 0));
+    }
+
+    #[test]
+    fn values_round_trip_through_buffer() {
+        let mut column = DecimalColumn::money(3);
+        column.set_value(0, Some(i64::MIN)).unwrap();
+        column.set_value(1, None).unwrap();
+        column.set_value(2, Some(123)).unwrap();
+
+        let view = column.view(3);
+
+        assert_eq!(Ok(Some(i64::MIN)), view.get(0));
+        assert_eq!(
+            vec![Ok(Some(i64::MIN)), Ok(None), Ok(Some(123))],
+            view.iter().collect::<Vec<Result<_, InvalidDecimal>>>()
+        );
+        assert_eq!(Some(&b"0.0123"[..]), view.raw().get(2));
+    }
+
+    #[test]
+    fn value_exceeding_precision() {
+        let mut column = DecimalColumn::new(1, 5, 2);
+        column.set_value(0, Some(-99_999)).unwrap();
+
+        let result = column.set_value(0, Some(100_000));
+
+        assert_eq!(
+            Err(DecimalOverflow {
+                value: 100_000,
+                precision: 5,
+                scale: 2
+            }),
+            result
+        );
+        // Buffer keeps the previous value
+        assert_eq!(Ok(Some(-99_999)), column.view(1).get(0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn precision_too_large_for_i64() {
+        DecimalColumn::new(1, 20, 2);
+    }
+}
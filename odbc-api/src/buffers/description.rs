@@ -4,6 +4,30 @@ use odbc_sys::{Date, Time, Timestamp};
 
 use crate::{Bit, DataType};
 
+/// Length of a text buffer for a column, given the length derived from its metadata (e.g. by
+/// [`DataType::utf8_len`]) and an optional upper bound. This is the rule applied by
+/// [`crate::buffers::TextRowSet::for_cursor`]:
+///
+/// * Without an upper bound the reported length is used as is.
+/// * A reported length of `0` means the driver does not know the maximum length, so the upper
+///   bound is used.
+/// * Otherwise the reported length is capped to the upper bound.
+///
+/// ```
+/// use odbc_api::buffers::max_str_len_with_limit;
+///
+/// assert_eq!(40, max_str_len_with_limit(40, None));
+/// assert_eq!(40, max_str_len_with_limit(40, Some(100)));
+/// assert_eq!(10, max_str_len_with_limit(40, Some(10)));
+/// assert_eq!(100, max_str_len_with_limit(0, Some(100)));
+/// ```
+pub fn max_str_len_with_limit(reported_len: usize, upper_bound: Option<usize>) -> usize {
+    match upper_bound {
+        Some(limit) if reported_len == 0 || reported_len > limit => limit,
+        _ => reported_len,
+    }
+}
+
 /// Describes a column of a [`crate::buffers::ColumnarBuffer`].
 ///
 /// While related to to the [`crate::DataType`] of the column this is bound to, the Buffer type is
@@ -110,8 +134,54 @@ pub enum BufferDesc {
     },
 }
 
+/// Chooses whether text columns are bound as narrow (`Text`) or wide (`WText`) buffers. Used by
+/// [`BufferDesc::from_data_type_with_text_binding`] to pick the C type per column.
+///
+/// Some drivers return narrow data faster, since they must not convert it. Yet narrow text is
+/// encoded in the system locale or the collation of the column, so columns of national character
+/// types, like `NVARCHAR`, may require wide binding to fetch values correctly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TextBinding {
+    /// Binds every text column as [`BufferDesc::Text`].
+    #[default]
+    Narrow,
+    /// Binds every text column as [`BufferDesc::WText`].
+    Wide,
+    /// Binds columns reported as [`DataType::WChar`], [`DataType::WVarchar`] or
+    /// [`DataType::WLongVarchar`] as [`BufferDesc::WText`], all other text columns as
+    /// [`BufferDesc::Text`].
+    MatchColumn,
+}
+
 impl BufferDesc {
     pub fn from_data_type(data_type: DataType, nullable: bool) -> Option<Self> {
+        Self::from_data_type_with_text_binding(data_type, nullable, TextBinding::Narrow)
+    }
+
+    /// Same as [`Self::from_data_type`], but `text_binding` chooses whether text columns are bound
+    /// as narrow or wide buffers. The maximum string length is the same, either way.
+    ///
+    /// ```
+    /// use odbc_api::{buffers::{BufferDesc, TextBinding}, DataType};
+    ///
+    /// let name = DataType::WVarchar { length: 40 };
+    /// let code = DataType::Char { length: 3 };
+    /// let binding = TextBinding::MatchColumn;
+    ///
+    /// assert_eq!(
+    ///     Some(BufferDesc::WText { max_str_len: 40 }),
+    ///     BufferDesc::from_data_type_with_text_binding(name, true, binding)
+    /// );
+    /// assert_eq!(
+    ///     Some(BufferDesc::Text { max_str_len: 3 }),
+    ///     BufferDesc::from_data_type_with_text_binding(code, true, binding)
+    /// );
+    /// ```
+    pub fn from_data_type_with_text_binding(
+        data_type: DataType,
+        nullable: bool,
+        text_binding: TextBinding,
+    ) -> Option<Self> {
         let buffer_desc = match data_type {
             DataType::Numeric { precision, scale }
             | DataType::Decimal { precision, scale } if scale == 0 && precision < 3 => BufferDesc::I8 { nullable },
@@ -137,7 +207,9 @@ impl BufferDesc {
             // Currently no special buffers for fixed lengths text implemented.
             | DataType::WChar {length }
             | DataType::Char { length }
-            | DataType::LongVarchar { length } => BufferDesc::Text { max_str_len : length },
+            | DataType::LongVarchar { length }
+            | DataType::WLongVarchar { length }
+            | DataType::Xml { length } => BufferDesc::Text { max_str_len : length },
             // Specialized buffers for Numeric and decimal are not yet supported.
             | DataType::Numeric { precision: _, scale: _ }
             | DataType::Decimal { precision: _, scale: _ }
@@ -146,6 +218,18 @@ impl BufferDesc {
             | DataType::Float { precision: _ }
             | DataType::Other { data_type: _, column_size: _, decimal_digits: _ } => return None,
         };
+        let wide = match text_binding {
+            TextBinding::Narrow => false,
+            TextBinding::Wide => true,
+            TextBinding::MatchColumn => matches!(
+                data_type,
+                DataType::WChar { .. } | DataType::WVarchar { .. } | DataType::WLongVarchar { .. }
+            ),
+        };
+        let buffer_desc = match buffer_desc {
+            BufferDesc::Text { max_str_len } if wide => BufferDesc::WText { max_str_len },
+            other => other,
+        };
         Some(buffer_desc)
     }
 
@@ -392,7 +476,9 @@ impl BufferKind {
             // Currently no special buffers for fixed lengths text implemented.
             | DataType::WChar {length }
             | DataType::Char { length }
-            | DataType::LongVarchar { length } => BufferKind::Text { max_str_len : length },
+            | DataType::LongVarchar { length }
+            | DataType::WLongVarchar { length }
+            | DataType::Xml { length } => BufferKind::Text { max_str_len : length },
             // Specialized buffers for Numeric and decimal are not yet supported.
             | DataType::Numeric { precision: _, scale: _ }
             | DataType::Decimal { precision: _, scale: _ }
@@ -438,4 +524,35 @@ mod tests {
         assert_eq!(8, BufferDesc::I64 { nullable: false }.bytes_per_row());
         assert_eq!(1, BufferDesc::U8 { nullable: false }.bytes_per_row());
     }
+
+    #[test]
+    fn bind_text_wide_or_narrow() {
+        let desc = |data_type, text_binding| {
+            BufferDesc::from_data_type_with_text_binding(data_type, true, text_binding).unwrap()
+        };
+        let varchar = DataType::Varchar { length: 10 };
+        let nvarchar = DataType::WVarchar { length: 10 };
+
+        assert_eq!(
+            BufferDesc::Text { max_str_len: 10 },
+            desc(nvarchar, TextBinding::Narrow)
+        );
+        assert_eq!(
+            BufferDesc::WText { max_str_len: 10 },
+            desc(varchar, TextBinding::Wide)
+        );
+        assert_eq!(
+            BufferDesc::Text { max_str_len: 10 },
+            desc(varchar, TextBinding::MatchColumn)
+        );
+        assert_eq!(
+            BufferDesc::WText { max_str_len: 10 },
+            desc(nvarchar, TextBinding::MatchColumn)
+        );
+        // Non text columns are not affected
+        assert_eq!(
+            BufferDesc::I32 { nullable: true },
+            desc(DataType::Integer, TextBinding::Wide)
+        );
+    }
 }
@@ -0,0 +1,127 @@
+use super::{AnyBuffer, BinColumn, TextColumn};
+
+/// Decides how much a text or binary column buffer grows, once a value to append exceeds its
+/// maximum element length. Growing means allocating a new buffer for the entire batch, copying the
+/// values already appended and rebinding it. Policies allocating more than required trade memory
+/// for fewer of these reallocations, should the values keep getting longer.
+///
+/// See [`crate::ColumnarBulkInserter::set_growth_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GrowthPolicy {
+    /// Grow to exactly the length of the value. Uses the least memory, but reallocates each time
+    /// a longer value is appended.
+    #[default]
+    Exact,
+    /// Grow to the length of the value times the factor. E.g. `1.2` reserves an additional 20%
+    /// for even longer values. Factors smaller than `1.0` are treated like `1.0`.
+    Factor(f64),
+    /// Double the maximum element length until the value fits. The number of reallocations is
+    /// logarithmic in the length of the longest value.
+    Exponential,
+    /// Do not grow at all. Appending a value which exceeds the maximum element length causes an
+    /// [`crate::Error::TooLargeValueForBuffer`].
+    Fail,
+}
+
+impl GrowthPolicy {
+    /// New maximum element length for a buffer with `max_len`, which must hold a value of
+    /// `required` length. `None` if the buffer must not grow. Returns `max_len` if the value
+    /// already fits.
+    pub fn new_max_len(self, max_len: usize, required: usize) -> Option<usize> {
+        if required <= max_len {
+            return Some(max_len);
+        }
+        match self {
+            GrowthPolicy::Exact => Some(required),
+            GrowthPolicy::Factor(factor) => {
+                let grown = (required as f64 * factor) as usize;
+                Some(grown.max(required))
+            }
+            GrowthPolicy::Exponential => {
+                let mut grown = max_len.max(1);
+                while grown < required {
+                    // Fall back to the required length rather than overflowing.
+                    grown = grown.checked_mul(2).unwrap_or(required);
+                }
+                Some(grown)
+            }
+            GrowthPolicy::Fail => None,
+        }
+    }
+}
+
+/// Column buffers holding elements of up to a maximum length, which can grow while preserving
+/// the values they already hold. Allows [`crate::ColumnarBulkInserter`] to grow several columns of
+/// a batch at once. See [`crate::ColumnarBulkInserter::reserve_for`].
+pub trait GrowableColumn {
+    /// Maximum length of an element, which can be held without growing the buffer. `None` for
+    /// buffers holding elements of fixed size, which never need to grow.
+    fn max_element_len(&self) -> Option<usize>;
+
+    /// Reallocates the buffer to hold elements of up to `new_max_len`, copying the first
+    /// `num_rows` values. Does nothing for buffers holding elements of fixed size.
+    fn resize_max_element_len(&mut self, new_max_len: usize, num_rows: usize);
+}
+
+impl<C> GrowableColumn for TextColumn<C>
+where
+    C: Default + Copy,
+{
+    fn max_element_len(&self) -> Option<usize> {
+        Some(self.max_len())
+    }
+
+    fn resize_max_element_len(&mut self, new_max_len: usize, num_rows: usize) {
+        self.resize_max_str(new_max_len, num_rows)
+    }
+}
+
+impl GrowableColumn for BinColumn {
+    fn max_element_len(&self) -> Option<usize> {
+        Some(self.max_len())
+    }
+
+    fn resize_max_element_len(&mut self, new_max_len: usize, num_rows: usize) {
+        self.resize_max_element_length(new_max_len, num_rows)
+    }
+}
+
+impl GrowableColumn for AnyBuffer {
+    fn max_element_len(&self) -> Option<usize> {
+        match self {
+            AnyBuffer::Binary(column) => column.max_element_len(),
+            AnyBuffer::Text(column) => column.max_element_len(),
+            AnyBuffer::WText(column) => column.max_element_len(),
+            _ => None,
+        }
+    }
+
+    fn resize_max_element_len(&mut self, new_max_len: usize, num_rows: usize) {
+        match self {
+            AnyBuffer::Binary(column) => column.resize_max_element_len(new_max_len, num_rows),
+            AnyBuffer::Text(column) => column.resize_max_element_len(new_max_len, num_rows),
+            AnyBuffer::WText(column) => column.resize_max_element_len(new_max_len, num_rows),
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GrowthPolicy;
+
+    #[test]
+    fn new_max_len() {
+        assert_eq!(Some(10), GrowthPolicy::Fail.new_max_len(10, 10));
+        assert_eq!(None, GrowthPolicy::Fail.new_max_len(10, 11));
+        assert_eq!(Some(11), GrowthPolicy::Exact.new_max_len(10, 11));
+        assert_eq!(Some(13), GrowthPolicy::Factor(1.2).new_max_len(10, 11));
+        assert_eq!(Some(11), GrowthPolicy::Factor(0.5).new_max_len(10, 11));
+        assert_eq!(Some(40), GrowthPolicy::Exponential.new_max_len(10, 31));
+        assert_eq!(Some(4), GrowthPolicy::Exponential.new_max_len(0, 3));
+        assert_eq!(
+            Some(usize::MAX),
+            GrowthPolicy::Exponential.new_max_len(usize::MAX / 2 + 1, usize::MAX)
+        );
+    }
+}
@@ -0,0 +1,339 @@
+use super::{CellValue, ColumnarAnyBuffer};
+
+/// Computes a deterministic hash over the values of a result set, batch by batch. Intended for
+/// data reconciliation jobs, which compare the result of the same query on two different systems,
+/// without holding either of them in memory.
+///
+/// The hash is computed over normalized cell values, so it does not depend on the buffer types
+/// chosen to fetch the columns, or on how the rows are split into batches:
+///
+/// * Integers of all widths and decimals without fractional digits hash like 64 bit integers.
+/// * Narrow and wide text hash like their UTF-8 representation.
+/// * Trailing zeroes of decimals are ignored, i.e. `1.50` hashes like `1.5`.
+/// * Floats of all widths hash like 64 bit floats. Positive and negative zero hash alike, and so
+///   do all `NaN`s.
+///
+/// The hash function is FNV-1a, so hashes are stable across platforms and versions of this crate.
+/// It is not a cryptographic hash.
+///
+/// ```
+/// use odbc_api::{buffers::{BufferDesc, ColumnarAnyBuffer, ResultSetHasher, RowOrder}, Cursor};
+///
+/// fn checksum(cursor: impl Cursor, descs: &[BufferDesc]) -> Result<u64, odbc_api::Error> {
+///     let buffer = ColumnarAnyBuffer::try_from_descs(1000, descs.iter().copied())?;
+///     let mut block_cursor = cursor.bind_buffer(buffer)?;
+///     let mut hasher = ResultSetHasher::new(RowOrder::Ordered);
+///     while let Some(batch) = block_cursor.fetch()? {
+///         hasher.update(batch);
+///     }
+///     Ok(hasher.finish())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResultSetHasher {
+    order: RowOrder,
+    /// Running hash for ordered results. Sum of all row hashes for unordered ones.
+    state: u64,
+    num_rows: u64,
+}
+
+/// Whether the order of the rows contributes to the hash computed by [`ResultSetHasher`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowOrder {
+    /// The rows must be in the same order to yield the same hash. Use for queries with an
+    /// `ORDER BY` clause.
+    Ordered,
+    /// Any permutation of the rows yields the same hash. Use if the order in which the rows are
+    /// returned is not defined. Keep in mind that this makes it impossible to detect swapped rows.
+    Unordered,
+}
+
+impl ResultSetHasher {
+    pub fn new(order: RowOrder) -> Self {
+        let state = match order {
+            RowOrder::Ordered => Fnv1a::new().0,
+            RowOrder::Unordered => 0,
+        };
+        Self {
+            order,
+            state,
+            num_rows: 0,
+        }
+    }
+
+    /// Accounts for all rows in `batch`. Returns the hash of the batch alone, which depends on the
+    /// order of its rows. Comparing these is only meaningful if both sides use the same batch
+    /// size.
+    pub fn update(&mut self, batch: &ColumnarAnyBuffer) -> u64 {
+        let mut batch_hash = Fnv1a::new();
+        for row_index in 0..batch.num_rows() {
+            let row_hash = row_hash(batch, row_index);
+            batch_hash.write_u64(row_hash);
+            match self.order {
+                RowOrder::Ordered => {
+                    let mut state = Fnv1a(self.state);
+                    state.write_u64(row_hash);
+                    self.state = state.0;
+                }
+                RowOrder::Unordered => self.state = self.state.wrapping_add(row_hash),
+            }
+        }
+        self.num_rows += batch.num_rows() as u64;
+        batch_hash.0
+    }
+
+    /// Number of rows accounted for so far.
+    pub fn num_rows(&self) -> u64 {
+        self.num_rows
+    }
+
+    /// Hash of all rows accounted for so far. Includes the number of rows, so an empty result
+    /// differs from a result with only empty rows.
+    pub fn finish(&self) -> u64 {
+        let mut hash = Fnv1a(self.state);
+        hash.write_u64(self.num_rows);
+        hash.0
+    }
+}
+
+/// Hash of the normalized values in the row at `row_index`.
+fn row_hash(batch: &ColumnarAnyBuffer, row_index: usize) -> u64 {
+    let mut hash = Fnv1a::new();
+    for col_index in 0..batch.num_cols() {
+        hash_cell(&mut hash, &batch.column(col_index).cell(row_index));
+    }
+    hash.0
+}
+
+/// Each variant is prefixed with a tag and variable length data with its length, so different
+/// rows are unlikely to yield the same sequence of bytes.
+fn hash_cell(hash: &mut Fnv1a, cell: &CellValue) {
+    match cell {
+        CellValue::Null => hash.write_u8(0),
+        CellValue::Text(bytes) => hash_text(hash, bytes),
+        CellValue::WText(units) => hash_text(hash, String::from_utf16_lossy(units).as_bytes()),
+        CellValue::Binary(bytes) => {
+            hash.write_u8(2);
+            hash.write_u64(bytes.len() as u64);
+            hash.write(bytes);
+        }
+        CellValue::I64(value) => hash_integer(hash, *value),
+        CellValue::F64(value) => {
+            hash.write_u8(4);
+            let normalized = if value.is_nan() {
+                f64::NAN
+            } else if *value == 0. {
+                0.
+            } else {
+                *value
+            };
+            hash.write_u64(normalized.to_bits());
+        }
+        &CellValue::Decimal {
+            mut value,
+            mut scale,
+        } => {
+            while scale > 0 && value % 10 == 0 {
+                value /= 10;
+                scale -= 1;
+            }
+            if scale == 0 {
+                hash_integer(hash, value)
+            } else {
+                hash.write_u8(5);
+                hash.write_u64(value as u64);
+                hash.write_u64(scale as u64);
+            }
+        }
+        CellValue::Date(date) => {
+            hash.write_u8(6);
+            hash.write_u64(date.year as u64);
+            hash.write_u8(date.month as u8);
+            hash.write_u8(date.day as u8);
+        }
+        CellValue::Time(time) => {
+            hash.write_u8(7);
+            hash.write_u8(time.hour as u8);
+            hash.write_u8(time.minute as u8);
+            hash.write_u8(time.second as u8);
+        }
+        CellValue::Timestamp(timestamp) => {
+            hash.write_u8(8);
+            hash.write_u64(timestamp.year as u64);
+            hash.write_u8(timestamp.month as u8);
+            hash.write_u8(timestamp.day as u8);
+            hash.write_u8(timestamp.hour as u8);
+            hash.write_u8(timestamp.minute as u8);
+            hash.write_u8(timestamp.second as u8);
+            hash.write_u64(timestamp.fraction.into());
+        }
+        CellValue::Bit(value) => {
+            hash.write_u8(9);
+            hash.write_u8(*value as u8);
+        }
+        CellValue::Guid(guid) => {
+            hash.write_u8(10);
+            hash.write_u64(guid.d1.into());
+            hash.write_u64(guid.d2.into());
+            hash.write_u64(guid.d3.into());
+            hash.write(&guid.d4);
+        }
+    }
+}
+
+fn hash_text(hash: &mut Fnv1a, utf8: &[u8]) {
+    hash.write_u8(1);
+    hash.write_u64(utf8.len() as u64);
+    hash.write(utf8);
+}
+
+fn hash_integer(hash: &mut Fnv1a, value: i64) {
+    hash.write_u8(3);
+    hash.write_u64(value as u64);
+}
+
+/// 64 Bit FNV-1a hash. Simple and, unlike the hasher of the standard library, guaranteed to be
+/// stable.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u8(&mut self, value: u8) {
+        self.write(&[value])
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::buffers::{
+        column_with_indicator::ColumnWithIndicator, AnyBuffer, CharColumn, ColumnarAnyBuffer,
+        WCharColumn,
+    };
+
+    use super::{hash_cell, CellValue, Fnv1a, ResultSetHasher, RowOrder};
+
+    fn cell_hash(cell: CellValue) -> u64 {
+        let mut hash = Fnv1a::new();
+        hash_cell(&mut hash, &cell);
+        hash.0
+    }
+
+    fn int_batch(values: &[i32]) -> ColumnarAnyBuffer {
+        let mut column = ColumnWithIndicator::<i32>::new(values.len());
+        let mut writer = column.writer_n(values.len());
+        for (index, &value) in values.iter().enumerate() {
+            writer.set_cell(index, Some(value));
+        }
+        let mut buffer = ColumnarAnyBuffer::new(vec![(1, AnyBuffer::NullableI32(column))]);
+        buffer.set_num_rows(values.len());
+        buffer
+    }
+
+    #[test]
+    fn fnv1a_reference_value() {
+        let mut hash = Fnv1a::new();
+        hash.write(b"a");
+        assert_eq!(0xaf63_dc4c_8601_ec8c, hash.0);
+    }
+
+    #[test]
+    fn normalize_cell_values() {
+        assert_eq!(
+            cell_hash(CellValue::I64(15)),
+            cell_hash(CellValue::Decimal {
+                value: 1500,
+                scale: 2
+            })
+        );
+        assert_eq!(
+            cell_hash(CellValue::Decimal {
+                value: 15,
+                scale: 1
+            }),
+            cell_hash(CellValue::Decimal {
+                value: 150,
+                scale: 2
+            })
+        );
+        assert_eq!(
+            cell_hash(CellValue::F64(0.)),
+            cell_hash(CellValue::F64(-0.))
+        );
+        assert_ne!(
+            cell_hash(CellValue::Text(b"1".to_vec())),
+            cell_hash(CellValue::I64(1))
+        );
+        assert_ne!(
+            cell_hash(CellValue::Null),
+            cell_hash(CellValue::Text(Vec::new()))
+        );
+    }
+
+    #[test]
+    fn narrow_and_wide_text_hash_alike() {
+        let mut narrow = CharColumn::new(1, 10);
+        narrow.set_value(0, Some("Grüße".as_bytes()));
+        let mut narrow = ColumnarAnyBuffer::new(vec![(1, AnyBuffer::Text(narrow))]);
+        narrow.set_num_rows(1);
+        let mut wide = WCharColumn::new(1, 10);
+        let text: Vec<u16> = "Grüße".encode_utf16().collect();
+        wide.set_value(0, Some(&text));
+        let mut wide = ColumnarAnyBuffer::new(vec![(1, AnyBuffer::WText(wide))]);
+        wide.set_num_rows(1);
+
+        let mut narrow_hasher = ResultSetHasher::new(RowOrder::Ordered);
+        narrow_hasher.update(&narrow);
+        let mut wide_hasher = ResultSetHasher::new(RowOrder::Ordered);
+        wide_hasher.update(&wide);
+
+        assert_eq!(narrow_hasher.finish(), wide_hasher.finish());
+    }
+
+    #[test]
+    fn hash_is_independent_of_batch_boundaries() {
+        let mut one_batch = ResultSetHasher::new(RowOrder::Ordered);
+        one_batch.update(&int_batch(&[1, 2, 3]));
+        let mut two_batches = ResultSetHasher::new(RowOrder::Ordered);
+        two_batches.update(&int_batch(&[1, 2]));
+        two_batches.update(&int_batch(&[3]));
+
+        assert_eq!(one_batch.finish(), two_batches.finish());
+        assert_eq!(3, two_batches.num_rows());
+    }
+
+    #[test]
+    fn order_matters_only_for_ordered_results() {
+        let hash = |order, values: &[i32]| {
+            let mut hasher = ResultSetHasher::new(order);
+            hasher.update(&int_batch(values));
+            hasher.finish()
+        };
+
+        assert_ne!(
+            hash(RowOrder::Ordered, &[1, 2]),
+            hash(RowOrder::Ordered, &[2, 1])
+        );
+        assert_eq!(
+            hash(RowOrder::Unordered, &[1, 2]),
+            hash(RowOrder::Unordered, &[2, 1])
+        );
+        assert_ne!(
+            hash(RowOrder::Unordered, &[1, 2]),
+            hash(RowOrder::Unordered, &[1, 3])
+        );
+    }
+}
@@ -0,0 +1,302 @@
+use odbc_sys::NULL_DATA;
+
+use super::{AnySlice, CellValue, ColumnarAnyBuffer, InvalidCellConversion, NullableSlice};
+
+/// Owned copy of a single row of a result set. Produced by [`ColumnarAnyBuffer::row`] and
+/// [`ColumnarAnyBuffer::rows`].
+///
+/// ```
+/// use std::sync::mpsc::Sender;
+/// use odbc_api::{buffers::{ColumnarAnyBuffer, Row}, Cursor, Error};
+///
+/// /// Fetches all rows and sends them to a consumer on another thread.
+/// fn send_rows(
+///     cursor: impl Cursor,
+///     buffer: ColumnarAnyBuffer,
+///     sender: Sender<Row>,
+/// ) -> Result<(), Error> {
+///     let mut block_cursor = cursor.bind_buffer(buffer)?;
+///     while let Some(batch) = block_cursor.fetch()? {
+///         for row in batch.rows() {
+///             // Consumer hung up, no need to fetch more rows.
+///             if sender.send(row).is_err() {
+///                 return Ok(());
+///             }
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row(Vec<CellValue>);
+
+impl Row {
+    /// Row holding `cells`, in the order of the columns of the result set.
+    pub fn new(cells: Vec<CellValue>) -> Self {
+        Self(cells)
+    }
+
+    /// Number of cells in the row.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// `true` if the row has no cells.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Cell at the zero based position `index`. `None` if out of bounds.
+    pub fn get(&self, index: usize) -> Option<&CellValue> {
+        self.0.get(index)
+    }
+
+    /// Converts a copy of the cell at the zero based position `index` into `T`.
+    ///
+    /// ```
+    /// use odbc_api::buffers::{CellValue, Row};
+    ///
+    /// let row = Row::new(vec![CellValue::I64(42), CellValue::Null]);
+    /// assert_eq!(Ok(42), row.try_get::<i32>(0));
+    /// assert_eq!(Ok(None), row.try_get::<Option<i32>>(1));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn try_get<T>(&self, index: usize) -> Result<T, InvalidCellConversion>
+    where
+        T: TryFrom<CellValue, Error = InvalidCellConversion>,
+    {
+        self.0[index].clone().try_into()
+    }
+
+    /// All cells of the row, in the order of the columns of the result set.
+    pub fn cells(&self) -> &[CellValue] {
+        &self.0
+    }
+
+    /// Takes ownership of the cells of the row, e.g. to move them into another data structure.
+    pub fn into_cells(self) -> Vec<CellValue> {
+        self.0
+    }
+}
+
+impl From<Vec<CellValue>> for Row {
+    fn from(cells: Vec<CellValue>) -> Self {
+        Self(cells)
+    }
+}
+
+impl<'a> AnySlice<'a> {
+    /// Owned copy of the cell at `row_index`.
+    ///
+    /// # Panics
+    ///
+    /// If `row_index` is out of bounds.
+    pub fn cell(&self, row_index: usize) -> CellValue {
+        match *self {
+            AnySlice::Text(view) => view
+                .get(row_index)
+                .map_or(CellValue::Null, |text| CellValue::Text(text.to_vec())),
+            AnySlice::WText(view) => view
+                .get(row_index)
+                .map_or(CellValue::Null, |text| CellValue::WText(text.to_vec())),
+            AnySlice::Binary(view) => view
+                .get(row_index)
+                .map_or(CellValue::Null, |bytes| CellValue::Binary(bytes.to_vec())),
+            AnySlice::Date(values) => CellValue::Date(values[row_index]),
+            AnySlice::Time(values) => CellValue::Time(values[row_index]),
+            AnySlice::Timestamp(values) => CellValue::Timestamp(values[row_index]),
+            AnySlice::F64(values) => CellValue::F64(values[row_index]),
+            AnySlice::F32(values) => CellValue::F64(values[row_index].into()),
+            AnySlice::I8(values) => CellValue::I64(values[row_index].into()),
+            AnySlice::I16(values) => CellValue::I64(values[row_index].into()),
+            AnySlice::I32(values) => CellValue::I64(values[row_index].into()),
+            AnySlice::I64(values) => CellValue::I64(values[row_index]),
+            AnySlice::U8(values) => CellValue::I64(values[row_index].into()),
+            AnySlice::Bit(values) => CellValue::Bit(values[row_index].as_bool()),
+            AnySlice::NullableDate(values) => nullable(values, row_index, CellValue::Date),
+            AnySlice::NullableTime(values) => nullable(values, row_index, CellValue::Time),
+            AnySlice::NullableTimestamp(values) => {
+                nullable(values, row_index, CellValue::Timestamp)
+            }
+            AnySlice::NullableF64(values) => nullable(values, row_index, CellValue::F64),
+            AnySlice::NullableF32(values) => {
+                nullable(values, row_index, |v| CellValue::F64(v.into()))
+            }
+            AnySlice::NullableI8(values) => {
+                nullable(values, row_index, |v| CellValue::I64(v.into()))
+            }
+            AnySlice::NullableI16(values) => {
+                nullable(values, row_index, |v| CellValue::I64(v.into()))
+            }
+            AnySlice::NullableI32(values) => {
+                nullable(values, row_index, |v| CellValue::I64(v.into()))
+            }
+            AnySlice::NullableI64(values) => nullable(values, row_index, CellValue::I64),
+            AnySlice::NullableU8(values) => {
+                nullable(values, row_index, |v| CellValue::I64(v.into()))
+            }
+            AnySlice::NullableBit(values) => {
+                nullable(values, row_index, |v| CellValue::Bit(v.as_bool()))
+            }
+        }
+    }
+}
+
+impl ColumnarAnyBuffer {
+    /// Owned copy of the row at `row_index` of the current row set.
+    ///
+    /// # Panics
+    ///
+    /// If `row_index` is not smaller than [`Self::num_rows`].
+    pub fn row(&self, row_index: usize) -> Row {
+        assert!(
+            row_index < self.num_rows(),
+            "Row index {row_index} is out of bounds. The row set has {} rows.",
+            self.num_rows()
+        );
+        Row((0..self.num_cols())
+            .map(|col_index| self.column(col_index).cell(row_index))
+            .collect())
+    }
+
+    /// Owned copies of all rows of the current row set.
+    pub fn rows(&self) -> impl Iterator<Item = Row> + '_ {
+        (0..self.num_rows()).map(|row_index| self.row(row_index))
+    }
+
+    /// Owned copies of the rows of the current row set for which `predicate` returns `true`.
+    /// `predicate` is called with the buffer and the zero based index of the row, before the row
+    /// is copied. Inspect individual cells with [`Self::column`], in order to drop unwanted rows
+    /// without copying them first.
+    ///
+    /// ```
+    /// use odbc_api::{buffers::{CellValue, ColumnarAnyBuffer, Row}, Cursor, Error};
+    ///
+    /// /// Columns: 1 name, 2 is_deleted
+    /// fn active_users(cursor: impl Cursor, buffer: ColumnarAnyBuffer) -> Result<Vec<Row>, Error> {
+    ///     let mut block_cursor = cursor.bind_buffer(buffer)?;
+    ///     let mut users = Vec::new();
+    ///     while let Some(batch) = block_cursor.fetch()? {
+    ///         users.extend(batch.rows_where(|batch, row_index| {
+    ///             batch.column(1).cell(row_index) != CellValue::Bit(true)
+    ///         }));
+    ///     }
+    ///     Ok(users)
+    /// }
+    /// ```
+    pub fn rows_where<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&ColumnarAnyBuffer, usize) -> bool + 'a,
+    ) -> impl Iterator<Item = Row> + 'a {
+        (0..self.num_rows())
+            .filter(move |&row_index| predicate(self, row_index))
+            .map(|row_index| self.row(row_index))
+    }
+}
+
+fn nullable<T: Copy>(
+    values: NullableSlice<'_, T>,
+    row_index: usize,
+    to_cell: impl FnOnce(T) -> CellValue,
+) -> CellValue {
+    let (values, indicators) = values.raw_values();
+    if indicators[row_index] == NULL_DATA {
+        CellValue::Null
+    } else {
+        to_cell(values[row_index])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::mpsc::channel, thread};
+
+    use crate::buffers::{
+        column_with_indicator::ColumnWithIndicator, AnyBuffer, CharColumn, ColumnarAnyBuffer,
+    };
+
+    use super::{CellValue, Row};
+
+    #[test]
+    fn copy_rows_out_of_buffer() {
+        let mut ids = ColumnWithIndicator::<i32>::new(2);
+        let mut writer = ids.writer_n(2);
+        writer.set_cell(0, Some(1));
+        writer.set_cell(1, None);
+        let mut names = CharColumn::new(2, 10);
+        names.set_value(0, Some(b"Hello"));
+        names.set_value(1, Some(b"World"));
+        let mut buffer = ColumnarAnyBuffer::new(vec![
+            (1, AnyBuffer::NullableI32(ids)),
+            (2, AnyBuffer::Text(names)),
+        ]);
+        buffer.set_num_rows(2);
+
+        let rows: Vec<Row> = buffer.rows().collect();
+
+        assert_eq!(
+            vec![
+                Row::new(vec![CellValue::I64(1), CellValue::Text(b"Hello".to_vec())]),
+                Row::new(vec![CellValue::Null, CellValue::Text(b"World".to_vec())]),
+            ],
+            rows
+        );
+    }
+
+    #[test]
+    fn send_rows_to_other_thread() {
+        let mut names = CharColumn::new(1, 10);
+        names.set_value(0, Some(b"Hello"));
+        let mut buffer = ColumnarAnyBuffer::new(vec![(1, AnyBuffer::Text(names))]);
+        buffer.set_num_rows(1);
+        let (sender, receiver) = channel();
+
+        let consumer = thread::spawn(move || receiver.iter().collect::<Vec<Row>>());
+        for row in buffer.rows() {
+            sender.send(row).unwrap();
+        }
+        drop(sender);
+        let rows = consumer.join().unwrap();
+
+        assert_eq!(Some(&CellValue::Text(b"Hello".to_vec())), rows[0].get(0));
+    }
+
+    #[test]
+    fn filter_rows_before_copying() {
+        let mut ids = ColumnWithIndicator::<i32>::new(3);
+        let mut writer = ids.writer_n(3);
+        writer.set_cell(0, Some(1));
+        writer.set_cell(1, Some(2));
+        writer.set_cell(2, None);
+        let mut buffer = ColumnarAnyBuffer::new(vec![(1, AnyBuffer::NullableI32(ids))]);
+        buffer.set_num_rows(3);
+        let mut num_calls = 0;
+
+        let rows: Vec<Row> = buffer
+            .rows_where(|batch, row_index| {
+                num_calls += 1;
+                batch.column(0).cell(row_index) != CellValue::I64(2)
+            })
+            .collect();
+
+        assert_eq!(
+            vec![
+                Row::new(vec![CellValue::I64(1)]),
+                Row::new(vec![CellValue::Null]),
+            ],
+            rows
+        );
+        assert_eq!(3, num_calls);
+    }
+
+    #[test]
+    #[should_panic]
+    fn row_index_out_of_bounds() {
+        let buffer = ColumnarAnyBuffer::new(vec![(1, AnyBuffer::Text(CharColumn::new(1, 10)))]);
+
+        buffer.row(0);
+    }
+}
@@ -0,0 +1,609 @@
+//! Compact binary format used to write the valid rows of a [`ColumnarAnyBuffer`] to a writer and
+//! read them back again. See [`ColumnarAnyBuffer::write_to`].
+//!
+//! All integers are little endian. The format of a buffer is:
+//!
+//! * The magic bytes `ODBCBUF` followed by a one byte format version.
+//! * Number of valid rows as `u64`, number of columns as `u32`.
+//! * For each column its one based column index as `u16` followed by the column.
+//!
+//! The format of a column is:
+//!
+//! * Kind of the buffer as `u8`. The most significant bit is set for nullable fixed sized types.
+//! * For text and binary buffers the maximum element length as `u64`.
+//! * Capacity and number of valid rows, each as `u64`.
+//! * The valid rows. Fixed size values are stored densely. Nullable fixed sized values are
+//!   preceded by a validity bitmap (see [`ValidityBitmap`]) and only values which are not `NULL`
+//!   are stored. Variable sized values are each preceded by their length as `u64`, with `u64::MAX`
+//!   representing `NULL`.
+
+use std::io::{self, Read, Write};
+
+use odbc_sys::{Date, Time, Timestamp};
+
+use crate::Bit;
+
+use super::{
+    column_with_indicator::ColumnWithIndicator, AnyBuffer, AnySlice, BinColumn, BufferDesc,
+    ColumnBuffer, ColumnarAnyBuffer, ColumnarBuffer, NullableSlice, TextColumn, ValidityBitmap,
+};
+
+const MAGIC: &[u8; 7] = b"ODBCBUF";
+const VERSION: u8 = 1;
+
+/// Length prefix marking a `NULL` in a text or binary column.
+const NULL_LENGTH: u64 = u64::MAX;
+
+/// Set in the kind of a column, if it is a nullable fixed size buffer.
+const NULLABLE: u8 = 0x80;
+
+impl ColumnarAnyBuffer {
+    /// Writes the valid rows of the buffer to `out`, e.g. to spill a fetched batch to disk, or to
+    /// send it to another process. Use [`Self::read_from`] to restore the buffer. Indicators of
+    /// truncated values are not preserved, only the part of the value held by the buffer.
+    ///
+    /// The format is compact and independent of the platform, but not guaranteed to be stable
+    /// between versions of this crate. A module level comment in the source code describes it.
+    /// Consider wrapping `out` into a [`std::io::BufWriter`]. With the `lz4` or `zstd` feature,
+    /// `write_compressed_to` compresses the written batch.
+    ///
+    /// ```
+    /// use odbc_api::buffers::{BufferDesc, ColumnarAnyBuffer};
+    ///
+    /// let buffer =
+    ///     ColumnarAnyBuffer::try_from_descs(100, [BufferDesc::I32 { nullable: true }]).unwrap();
+    /// let mut bytes = Vec::new();
+    /// buffer.write_to(&mut bytes).unwrap();
+    /// let restored = ColumnarAnyBuffer::read_from(&mut bytes.as_slice()).unwrap();
+    /// assert_eq!(0, restored.num_rows());
+    /// ```
+    pub fn write_to(&self, mut out: impl Write) -> io::Result<()> {
+        let mut header = Vec::with_capacity(MAGIC.len() + 13);
+        header.extend_from_slice(MAGIC);
+        header.push(VERSION);
+        put_u64(&mut header, self.num_rows());
+        header.extend_from_slice(&u32::try_from(self.num_cols()).unwrap().to_le_bytes());
+        out.write_all(&header)?;
+        for (column_index, (col_number, buffer)) in self.columns().iter().enumerate() {
+            out.write_all(&col_number.to_le_bytes())?;
+            buffer
+                .write_to(self.num_rows(), &mut out)
+                .map_err(|source| {
+                    io::Error::new(
+                        source.kind(),
+                        format!("Failed to write column buffer {column_index}: {source}"),
+                    )
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Restores a buffer written with [`Self::write_to`]. The buffer has the same capacity, column
+    /// indices, buffer kinds and valid rows as the original. Fails with
+    /// [`std::io::ErrorKind::InvalidData`] if `input` does not hold a buffer in the expected
+    /// format. Consider wrapping `input` into a [`std::io::BufReader`].
+    pub fn read_from(mut input: impl Read) -> io::Result<Self> {
+        let mut magic = [0u8; 8];
+        input.read_exact(&mut magic)?;
+        if &magic[..7] != MAGIC {
+            return Err(invalid_data(
+                "Input does not start with magic bytes of a buffer.",
+            ));
+        }
+        if magic[7] != VERSION {
+            return Err(invalid_data(format!(
+                "Unsupported version of buffer format: {}",
+                magic[7]
+            )));
+        }
+        let num_rows = get_u64(&mut input)?;
+        let mut num_cols = [0u8; 4];
+        input.read_exact(&mut num_cols)?;
+        let num_cols = u32::from_le_bytes(num_cols);
+
+        let mut columns = Vec::new();
+        for _ in 0..num_cols {
+            let mut col_number = [0u8; 2];
+            input.read_exact(&mut col_number)?;
+            let (buffer, column_num_rows) = AnyBuffer::read_from(&mut input)?;
+            if column_num_rows != num_rows {
+                return Err(invalid_data(
+                    "Number of rows of column does not match the number of rows of the buffer.",
+                ));
+            }
+            columns.push((u16::from_le_bytes(col_number), buffer));
+        }
+        let capacity = columns
+            .iter()
+            .map(|(_, buffer)| buffer.capacity())
+            .min()
+            .unwrap_or(0);
+        for (index, (col_number, _)) in columns.iter().enumerate() {
+            if columns[..index]
+                .iter()
+                .any(|(other, _)| other == col_number)
+            {
+                return Err(invalid_data("Column indices must be unique."));
+            }
+        }
+        if num_rows > capacity {
+            return Err(invalid_data("Number of rows exceeds capacity of buffer."));
+        }
+        // Safe: Indices are unique and every column has at least `capacity`.
+        let mut buffer = unsafe { ColumnarBuffer::new_unchecked(capacity, columns) };
+        buffer.set_num_rows(num_rows);
+        Ok(buffer)
+    }
+}
+
+impl AnyBuffer {
+    /// Writes the first `num_rows` elements of the buffer to `out`, together with its kind and
+    /// capacity. See [`ColumnarAnyBuffer::write_to`].
+    ///
+    /// # Panics
+    ///
+    /// If `num_rows` exceeds the capacity of the buffer.
+    pub fn write_to(&self, num_rows: usize, mut out: impl Write) -> io::Result<()> {
+        let mut bytes = Vec::new();
+        let view = self.view(num_rows);
+        let (kind, max_len) = kind_and_max_len(&view);
+        bytes.push(kind);
+        if let Some(max_len) = max_len {
+            put_u64(&mut bytes, max_len);
+        }
+        put_u64(&mut bytes, self.capacity());
+        put_u64(&mut bytes, num_rows);
+        match view {
+            AnySlice::Text(view) => put_variable(&mut bytes, view.iter()),
+            AnySlice::WText(view) => put_variable(&mut bytes, (0..num_rows).map(|i| view.get(i))),
+            AnySlice::Binary(view) => put_variable(&mut bytes, view.iter()),
+            AnySlice::Date(values) => put_values(&mut bytes, values),
+            AnySlice::Time(values) => put_values(&mut bytes, values),
+            AnySlice::Timestamp(values) => put_values(&mut bytes, values),
+            AnySlice::F64(values) => put_values(&mut bytes, values),
+            AnySlice::F32(values) => put_values(&mut bytes, values),
+            AnySlice::I8(values) => put_values(&mut bytes, values),
+            AnySlice::I16(values) => put_values(&mut bytes, values),
+            AnySlice::I32(values) => put_values(&mut bytes, values),
+            AnySlice::I64(values) => put_values(&mut bytes, values),
+            AnySlice::U8(values) => put_values(&mut bytes, values),
+            AnySlice::Bit(values) => put_values(&mut bytes, values),
+            AnySlice::NullableDate(slice) => put_nullable(&mut bytes, slice),
+            AnySlice::NullableTime(slice) => put_nullable(&mut bytes, slice),
+            AnySlice::NullableTimestamp(slice) => put_nullable(&mut bytes, slice),
+            AnySlice::NullableF64(slice) => put_nullable(&mut bytes, slice),
+            AnySlice::NullableF32(slice) => put_nullable(&mut bytes, slice),
+            AnySlice::NullableI8(slice) => put_nullable(&mut bytes, slice),
+            AnySlice::NullableI16(slice) => put_nullable(&mut bytes, slice),
+            AnySlice::NullableI32(slice) => put_nullable(&mut bytes, slice),
+            AnySlice::NullableI64(slice) => put_nullable(&mut bytes, slice),
+            AnySlice::NullableU8(slice) => put_nullable(&mut bytes, slice),
+            AnySlice::NullableBit(slice) => put_nullable(&mut bytes, slice),
+        }
+        out.write_all(&bytes)
+    }
+
+    /// Restores a buffer written with [`Self::write_to`]. Returns the buffer together with its
+    /// number of valid rows.
+    pub fn read_from(mut input: impl Read) -> io::Result<(Self, usize)> {
+        let mut kind = [0u8; 1];
+        input.read_exact(&mut kind)?;
+        let desc = read_desc(kind[0], &mut input)?;
+        let capacity = get_u64(&mut input)?;
+        let num_rows = get_u64(&mut input)?;
+        if num_rows > capacity {
+            return Err(invalid_data(
+                "Number of rows exceeds capacity of column buffer.",
+            ));
+        }
+        let mut buffer = AnyBuffer::try_from_desc(capacity, desc).map_err(|_| {
+            invalid_data("Capacity and element size of column buffer are too large.")
+        })?;
+        match &mut buffer {
+            AnyBuffer::Text(column) => read_text(&mut input, column, num_rows)?,
+            AnyBuffer::WText(column) => read_text(&mut input, column, num_rows)?,
+            AnyBuffer::Binary(column) => read_binary(&mut input, column, num_rows)?,
+            AnyBuffer::Date(values) => read_values(&mut input, &mut values[..num_rows])?,
+            AnyBuffer::Time(values) => read_values(&mut input, &mut values[..num_rows])?,
+            AnyBuffer::Timestamp(values) => read_values(&mut input, &mut values[..num_rows])?,
+            AnyBuffer::F64(values) => read_values(&mut input, &mut values[..num_rows])?,
+            AnyBuffer::F32(values) => read_values(&mut input, &mut values[..num_rows])?,
+            AnyBuffer::I8(values) => read_values(&mut input, &mut values[..num_rows])?,
+            AnyBuffer::I16(values) => read_values(&mut input, &mut values[..num_rows])?,
+            AnyBuffer::I32(values) => read_values(&mut input, &mut values[..num_rows])?,
+            AnyBuffer::I64(values) => read_values(&mut input, &mut values[..num_rows])?,
+            AnyBuffer::U8(values) => read_values(&mut input, &mut values[..num_rows])?,
+            AnyBuffer::Bit(values) => read_values(&mut input, &mut values[..num_rows])?,
+            AnyBuffer::NullableDate(column) => read_nullable(&mut input, column, num_rows)?,
+            AnyBuffer::NullableTime(column) => read_nullable(&mut input, column, num_rows)?,
+            AnyBuffer::NullableTimestamp(column) => read_nullable(&mut input, column, num_rows)?,
+            AnyBuffer::NullableF64(column) => read_nullable(&mut input, column, num_rows)?,
+            AnyBuffer::NullableF32(column) => read_nullable(&mut input, column, num_rows)?,
+            AnyBuffer::NullableI8(column) => read_nullable(&mut input, column, num_rows)?,
+            AnyBuffer::NullableI16(column) => read_nullable(&mut input, column, num_rows)?,
+            AnyBuffer::NullableI32(column) => read_nullable(&mut input, column, num_rows)?,
+            AnyBuffer::NullableI64(column) => read_nullable(&mut input, column, num_rows)?,
+            AnyBuffer::NullableU8(column) => read_nullable(&mut input, column, num_rows)?,
+            AnyBuffer::NullableBit(column) => read_nullable(&mut input, column, num_rows)?,
+        }
+        Ok((buffer, num_rows))
+    }
+}
+
+/// Kind of the buffer, and the maximum element length for variable sized buffers.
+fn kind_and_max_len(view: &AnySlice<'_>) -> (u8, Option<usize>) {
+    match view {
+        AnySlice::Binary(view) => (0, Some(view.max_len())),
+        AnySlice::Text(view) => (1, Some(view.max_len())),
+        AnySlice::WText(view) => (2, Some(view.max_len())),
+        AnySlice::Date(_) => (3, None),
+        AnySlice::Time(_) => (4, None),
+        AnySlice::Timestamp(_) => (5, None),
+        AnySlice::F64(_) => (6, None),
+        AnySlice::F32(_) => (7, None),
+        AnySlice::I8(_) => (8, None),
+        AnySlice::I16(_) => (9, None),
+        AnySlice::I32(_) => (10, None),
+        AnySlice::I64(_) => (11, None),
+        AnySlice::U8(_) => (12, None),
+        AnySlice::Bit(_) => (13, None),
+        AnySlice::NullableDate(_) => (3 | NULLABLE, None),
+        AnySlice::NullableTime(_) => (4 | NULLABLE, None),
+        AnySlice::NullableTimestamp(_) => (5 | NULLABLE, None),
+        AnySlice::NullableF64(_) => (6 | NULLABLE, None),
+        AnySlice::NullableF32(_) => (7 | NULLABLE, None),
+        AnySlice::NullableI8(_) => (8 | NULLABLE, None),
+        AnySlice::NullableI16(_) => (9 | NULLABLE, None),
+        AnySlice::NullableI32(_) => (10 | NULLABLE, None),
+        AnySlice::NullableI64(_) => (11 | NULLABLE, None),
+        AnySlice::NullableU8(_) => (12 | NULLABLE, None),
+        AnySlice::NullableBit(_) => (13 | NULLABLE, None),
+    }
+}
+
+/// Inverse of [`kind_and_max_len`]. Reads the maximum element length from `input` if required.
+fn read_desc(kind: u8, input: &mut impl Read) -> io::Result<BufferDesc> {
+    let nullable = kind & NULLABLE != 0;
+    let desc = match kind & !NULLABLE {
+        0 => BufferDesc::Binary {
+            length: get_u64(input)?,
+        },
+        1 => BufferDesc::Text {
+            max_str_len: get_u64(input)?,
+        },
+        2 => BufferDesc::WText {
+            max_str_len: get_u64(input)?,
+        },
+        3 => BufferDesc::Date { nullable },
+        4 => BufferDesc::Time { nullable },
+        5 => BufferDesc::Timestamp { nullable },
+        6 => BufferDesc::F64 { nullable },
+        7 => BufferDesc::F32 { nullable },
+        8 => BufferDesc::I8 { nullable },
+        9 => BufferDesc::I16 { nullable },
+        10 => BufferDesc::I32 { nullable },
+        11 => BufferDesc::I64 { nullable },
+        12 => BufferDesc::U8 { nullable },
+        13 => BufferDesc::Bit { nullable },
+        _ => {
+            return Err(invalid_data(format!(
+                "Unknown kind of column buffer: {kind}"
+            )))
+        }
+    };
+    if nullable && kind & !NULLABLE < 3 {
+        return Err(invalid_data(format!(
+            "Unknown kind of column buffer: {kind}"
+        )));
+    }
+    Ok(desc)
+}
+
+fn put_values<T: Element>(out: &mut Vec<u8>, values: &[T]) {
+    out.reserve(values.len() * T::SIZE);
+    for value in values {
+        value.put(out);
+    }
+}
+
+fn put_nullable<T: Element>(out: &mut Vec<u8>, slice: NullableSlice<'_, T>) {
+    let (values, indicators) = slice.raw_values();
+    let validity = ValidityBitmap::from_indicators(indicators);
+    out.extend_from_slice(validity.as_bytes());
+    for (index, value) in values.iter().enumerate() {
+        if validity.is_valid(index) {
+            value.put(out);
+        }
+    }
+}
+
+fn put_variable<'a, T: Element + 'a>(
+    out: &mut Vec<u8>,
+    elements: impl Iterator<Item = Option<&'a [T]>>,
+) {
+    for element in elements {
+        if let Some(element) = element {
+            put_u64(out, element.len());
+            put_values(out, element);
+        } else {
+            out.extend_from_slice(&NULL_LENGTH.to_le_bytes());
+        }
+    }
+}
+
+fn read_values<T: Element>(input: &mut impl Read, values: &mut [T]) -> io::Result<()> {
+    let mut bytes = vec![0u8; values.len() * T::SIZE];
+    input.read_exact(&mut bytes)?;
+    for (value, bytes) in values.iter_mut().zip(bytes.chunks_exact(T::SIZE)) {
+        *value = T::get(bytes);
+    }
+    Ok(())
+}
+
+fn read_nullable<T: Element>(
+    input: &mut impl Read,
+    column: &mut ColumnWithIndicator<T>,
+    num_rows: usize,
+) -> io::Result<()> {
+    // One byte for every eight rows, rounded up.
+    let mut validity = vec![0u8; (0..num_rows).step_by(8).len()];
+    input.read_exact(&mut validity)?;
+    let is_valid = |index: usize| validity[index / 8] & (1 << (index % 8)) != 0;
+    let num_valid = (0..num_rows).filter(|&index| is_valid(index)).count();
+    let mut valid_values = vec![T::default(); num_valid];
+    read_values(input, &mut valid_values)?;
+    let mut valid_values = valid_values.into_iter();
+    let mut writer = column.writer_n(num_rows);
+    for index in 0..num_rows {
+        let cell = if is_valid(index) {
+            valid_values.next()
+        } else {
+            None
+        };
+        writer.set_cell(index, cell);
+    }
+    Ok(())
+}
+
+fn read_text<C: Element>(
+    input: &mut impl Read,
+    column: &mut TextColumn<C>,
+    num_rows: usize,
+) -> io::Result<()> {
+    let max_len = column.max_len();
+    read_variable(input, num_rows, max_len, |index, element| {
+        column.set_value(index, element)
+    })
+}
+
+fn read_binary(input: &mut impl Read, column: &mut BinColumn, num_rows: usize) -> io::Result<()> {
+    let max_len = column.max_len();
+    read_variable(input, num_rows, max_len, |index, element| {
+        column.set_value(index, element)
+    })
+}
+
+fn read_variable<T: Element>(
+    input: &mut impl Read,
+    num_rows: usize,
+    max_len: usize,
+    mut set_value: impl FnMut(usize, Option<&[T]>),
+) -> io::Result<()> {
+    let mut element = Vec::new();
+    for index in 0..num_rows {
+        let mut length = [0u8; 8];
+        input.read_exact(&mut length)?;
+        let length = u64::from_le_bytes(length);
+        if length == NULL_LENGTH {
+            set_value(index, None);
+            continue;
+        }
+        if length > max_len as u64 {
+            return Err(invalid_data(
+                "Element is longer than the maximum element length of the column buffer.",
+            ));
+        }
+        element.resize(length as usize, T::default());
+        read_values(input, &mut element)?;
+        set_value(index, Some(&element));
+    }
+    Ok(())
+}
+
+fn put_u64(out: &mut Vec<u8>, value: usize) {
+    out.extend_from_slice(&(value as u64).to_le_bytes());
+}
+
+fn get_u64(input: &mut impl Read) -> io::Result<usize> {
+    let mut bytes = [0u8; 8];
+    input.read_exact(&mut bytes)?;
+    usize::try_from(u64::from_le_bytes(bytes))
+        .map_err(|_| invalid_data("Length or number of rows exceeds address space."))
+}
+
+fn invalid_data(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+/// Values of fixed size with a platform independent representation.
+trait Element: Default + Copy {
+    /// Number of bytes the value occupies in the format.
+    const SIZE: usize;
+
+    fn put(&self, out: &mut Vec<u8>);
+
+    /// `bytes` holds exactly [`Self::SIZE`] bytes.
+    fn get(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_element_for_number {
+    ($($t:ty),*) => {
+        $(
+            impl Element for $t {
+                const SIZE: usize = std::mem::size_of::<$t>();
+
+                fn put(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn get(bytes: &[u8]) -> Self {
+                    <$t>::from_le_bytes(bytes.try_into().unwrap())
+                }
+            }
+        )*
+    };
+}
+
+impl_element_for_number!(f64, f32, i8, i16, i32, i64, u8, u16);
+
+impl Element for Bit {
+    const SIZE: usize = 1;
+
+    fn put(&self, out: &mut Vec<u8>) {
+        out.push(self.0);
+    }
+
+    fn get(bytes: &[u8]) -> Self {
+        Bit(bytes[0])
+    }
+}
+
+impl Element for Date {
+    const SIZE: usize = 6;
+
+    fn put(&self, out: &mut Vec<u8>) {
+        self.year.put(out);
+        self.month.put(out);
+        self.day.put(out);
+    }
+
+    fn get(bytes: &[u8]) -> Self {
+        Date {
+            year: i16::get(&bytes[0..2]),
+            month: u16::get(&bytes[2..4]),
+            day: u16::get(&bytes[4..6]),
+        }
+    }
+}
+
+impl Element for Time {
+    const SIZE: usize = 6;
+
+    fn put(&self, out: &mut Vec<u8>) {
+        self.hour.put(out);
+        self.minute.put(out);
+        self.second.put(out);
+    }
+
+    fn get(bytes: &[u8]) -> Self {
+        Time {
+            hour: u16::get(&bytes[0..2]),
+            minute: u16::get(&bytes[2..4]),
+            second: u16::get(&bytes[4..6]),
+        }
+    }
+}
+
+impl Element for Timestamp {
+    const SIZE: usize = 16;
+
+    fn put(&self, out: &mut Vec<u8>) {
+        self.year.put(out);
+        self.month.put(out);
+        self.day.put(out);
+        self.hour.put(out);
+        self.minute.put(out);
+        self.second.put(out);
+        out.extend_from_slice(&self.fraction.to_le_bytes());
+    }
+
+    fn get(bytes: &[u8]) -> Self {
+        Timestamp {
+            year: i16::get(&bytes[0..2]),
+            month: u16::get(&bytes[2..4]),
+            day: u16::get(&bytes[4..6]),
+            hour: u16::get(&bytes[6..8]),
+            minute: u16::get(&bytes[8..10]),
+            second: u16::get(&bytes[10..12]),
+            fraction: u32::from_le_bytes(bytes[12..16].try_into().unwrap()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use odbc_sys::Timestamp;
+
+    use crate::buffers::{AnyBuffer, AnySlice, BufferDesc, ColumnBuffer, ColumnarAnyBuffer};
+
+    #[test]
+    fn round_trip() {
+        let mut text = AnyBuffer::try_from_desc(4, BufferDesc::Text { max_str_len: 5 }).unwrap();
+        let mut wtext = AnyBuffer::try_from_desc(4, BufferDesc::WText { max_str_len: 3 }).unwrap();
+        let mut integers = AnyBuffer::try_from_desc(4, BufferDesc::I32 { nullable: true }).unwrap();
+        let mut timestamps =
+            AnyBuffer::try_from_desc(4, BufferDesc::Timestamp { nullable: false }).unwrap();
+        if let AnyBuffer::Text(column) = &mut text {
+            column.set_value(0, Some(b"Hello"));
+            column.set_value(1, None);
+            column.set_value(2, Some(b""));
+        }
+        if let AnyBuffer::WText(column) = &mut wtext {
+            let text: Vec<u16> = "äöü".encode_utf16().collect();
+            column.set_value(0, Some(&text));
+            column.fill_null(1, 3);
+        }
+        if let AnyBuffer::NullableI32(column) = &mut integers {
+            column
+                .writer_n(3)
+                .write([Some(1), None, Some(3)].into_iter());
+        }
+        let timestamp = Timestamp {
+            year: 2023,
+            month: 2,
+            day: 3,
+            hour: 4,
+            minute: 5,
+            second: 6,
+            fraction: 7,
+        };
+        if let AnyBuffer::Timestamp(values) = &mut timestamps {
+            values[0..3].fill(timestamp);
+        }
+        let mut buffer =
+            ColumnarAnyBuffer::new(vec![(1, text), (2, wtext), (4, integers), (5, timestamps)]);
+        buffer.set_num_rows(3);
+
+        let mut bytes = Vec::new();
+        buffer.write_to(&mut bytes).unwrap();
+        let restored = ColumnarAnyBuffer::read_from(bytes.as_slice()).unwrap();
+
+        assert_eq!(3, restored.num_rows());
+        assert_eq!(4, restored.columns()[0].1.capacity());
+        assert_eq!(4, restored.columns()[2].0);
+        let texts: Vec<_> = restored.column(0).as_text_view().unwrap().iter().collect();
+        assert_eq!(vec![Some(&b"Hello"[..]), None, Some(&b""[..])], texts);
+        let wtext = restored.column(1).as_w_text_view().unwrap();
+        assert_eq!("äöü", String::from_utf16(wtext.get(0).unwrap()).unwrap());
+        assert_eq!(None, wtext.get(2));
+        let integers: Vec<_> = restored
+            .column(2)
+            .as_nullable_slice::<i32>()
+            .unwrap()
+            .map(|value| value.copied())
+            .collect();
+        assert_eq!(vec![Some(1), None, Some(3)], integers);
+        match restored.column(3) {
+            AnySlice::Timestamp(values) => assert_eq!(&[timestamp; 3], values),
+            _ => panic!("Expected timestamp column"),
+        }
+    }
+
+    #[test]
+    fn reject_invalid_input() {
+        let error = ColumnarAnyBuffer::read_from(&b"NOTABUFFER"[..])
+            .err()
+            .unwrap();
+        assert_eq!(std::io::ErrorKind::InvalidData, error.kind());
+    }
+}
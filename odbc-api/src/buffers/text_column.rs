@@ -1,15 +1,15 @@
 use crate::{
     columnar_bulk_inserter::BoundInputSlice,
     error::TooLargeBufferSize,
-    handles::{CData, CDataMut, HasDataType, Statement, StatementRef},
-    DataType, Error,
+    handles::{slice_to_utf8_lossy_into, CData, CDataMut, HasDataType, Statement, StatementRef},
+    DataType, Error, FetchStatistics,
 };
 
-use super::{ColumnBuffer, Indicator};
+use super::{CellsMut, ColumnBuffer, GrowthPolicy, Indicator};
 
 use log::debug;
 use odbc_sys::{CDataType, NULL_DATA};
-use std::{cmp::min, ffi::c_void, mem::size_of, panic};
+use std::{borrow::Cow, cmp::min, ffi::c_void, mem::size_of, panic, string::FromUtf16Error};
 use widestring::U16Str;
 
 /// A column buffer for character data. The actual encoding used may depend on your system locale.
@@ -51,8 +51,8 @@ impl<C> TextColumn<C> {
         C: Default + Copy,
     {
         // Element size is +1 to account for terminating zero
-        let element_size = max_str_len + 1;
-        let len = element_size * batch_size;
+        let element_size = max_str_len.saturating_add(1);
+        let len = TooLargeBufferSize::checked_len::<C>(batch_size, element_size)?;
         let mut values = Vec::new();
         values
             .try_reserve_exact(len)
@@ -78,8 +78,9 @@ impl<C> TextColumn<C> {
         C: Default + Copy,
     {
         // Element size is +1 to account for terminating zero
-        let element_size = max_str_len + 1;
-        let len = element_size * batch_size;
+        let element_size = max_str_len.saturating_add(1);
+        let len = TooLargeBufferSize::checked_len::<C>(batch_size, element_size)
+            .expect("Text column buffer must not exceed the limits of the platform.");
         let mut values = Vec::new();
         values.reserve_exact(len);
         values.resize(len, C::default());
@@ -109,6 +110,11 @@ impl<C> TextColumn<C> {
         self.max_str_len
     }
 
+    /// Number of bytes allocated for values (including terminating zeroes) and indicators.
+    pub fn memory_usage(&self) -> usize {
+        self.values.len() * size_of::<C>() + self.indicators.len() * size_of::<isize>()
+    }
+
     /// Indicator value at the specified position. Useful to detect truncation of data.
     ///
     /// The column buffer does not know how many elements were in the last row group, and therefore
@@ -162,7 +168,10 @@ impl<C> TextColumn<C> {
 
         let batch_size = self.indicators.len();
         // Allocate a new buffer large enough to hold a batch of strings with maximum length.
-        let mut new_values = vec![C::default(); (new_max_str_len + 1) * batch_size];
+        let len =
+            TooLargeBufferSize::checked_len::<C>(batch_size, new_max_str_len.saturating_add(1))
+                .expect("Text column buffer must not exceed the limits of the platform.");
+        let mut new_values = vec![C::default(); len];
         // Copy values from old to new buffer.
         let max_copy_length = min(self.max_str_len, new_max_str_len);
         for ((&indicator, old_value), new_value) in self
@@ -244,7 +253,10 @@ impl<C> TextColumn<C> {
                 allowed string length for the buffer."
             );
         }
-        self.indicators[index] = (length * size_of::<C>()).try_into().unwrap();
+        self.indicators[index] = length
+            .checked_mul(size_of::<C>())
+            .and_then(|len| isize::try_from(len).ok())
+            .expect("Length of value must fit into its indicator.");
         let start = (self.max_str_len + 1) * index;
         let end = start + length;
         // Let's insert a terminating zero at the end to be on the safe side, in case the ODBC
@@ -254,6 +266,52 @@ impl<C> TextColumn<C> {
         &mut self.values[start..end]
     }
 
+    /// Mutable access to the first `num_rows` elements, e.g. to transform fetched values in place
+    /// before inserting them into another table.
+    ///
+    /// ```
+    /// use odbc_api::buffers::TextColumn;
+    ///
+    /// fn uppercase(column: &mut TextColumn<u8>, num_rows: usize) {
+    ///     for mut cell in column.iter_mut(num_rows) {
+    ///         if let Some(text) = cell.get_mut() {
+    ///             text.make_ascii_uppercase();
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `num_rows` is larger than the capacity of the buffer.
+    pub fn iter_mut(&mut self, num_rows: usize) -> CellsMut<'_, C> {
+        CellsMut::new(
+            &mut self.values,
+            &mut self.indicators[..num_rows],
+            self.max_str_len + 1,
+            self.max_str_len,
+        )
+    }
+
+    /// Removes trailing spaces from the first `num_rows` elements in place, by shortening their
+    /// length. Useful to get rid of the padding of fixed width `CHAR(n)` columns once, before the
+    /// values are processed further, e.g. inserted as parameters into another table. To skip the
+    /// padding while reading values instead, see [`TextColumnView::trim_trailing_spaces`].
+    ///
+    /// # Panics
+    ///
+    /// If `num_rows` is larger than the capacity of the buffer.
+    pub fn trim_trailing_spaces(&mut self, num_rows: usize)
+    where
+        C: Default + From<u8> + PartialEq,
+    {
+        for mut cell in self.iter_mut(num_rows) {
+            if let Some(length) = cell.get().map(|text| trim_trailing_spaces(text).len()) {
+                cell.truncate(length);
+            }
+        }
+    }
+
     /// Fills the column with NULL, between From and To
     pub fn fill_null(&mut self, from: usize, to: usize) {
         for index in from..to {
@@ -289,6 +347,7 @@ impl WCharColumn {
     /// can not guarantee the accessed element to be valid and in a defined state. It also can not
     /// panic on accessing an undefined element. It will panic however if `row_index` is larger or
     /// equal to the maximum number of elements in the buffer.
+    #[deprecated = "Use the safe TextColumnView::ustr_at instead"]
     pub unsafe fn ustr_at(&self, row_index: usize) -> Option<&U16Str> {
         self.value_at(row_index).map(U16Str::from_slice)
     }
@@ -304,6 +363,7 @@ where
         TextColumnView {
             num_rows: valid_rows,
             col: self,
+            trim: untrimmed,
         }
     }
 
@@ -315,6 +375,18 @@ where
     fn capacity(&self) -> usize {
         self.indicators.len()
     }
+
+    fn memory_usage(&self) -> usize {
+        TextColumn::memory_usage(self)
+    }
+
+    fn record_statistics(&self, valid_rows: usize, statistics: &mut FetchStatistics) {
+        // Indicators are in bytes, but the maximum string length is in characters.
+        let max_len_in_bytes = self.max_str_len * size_of::<C>();
+        for &indicator in &self.indicators[..valid_rows] {
+            statistics.record_cell(Indicator::from_isize(indicator), max_len_in_bytes);
+        }
+    }
 }
 
 /// Allows read only access to the valid part of a text column.
@@ -329,6 +401,8 @@ where
 pub struct TextColumnView<'c, C> {
     num_rows: usize,
     col: &'c TextColumn<C>,
+    /// Applied to each element before it is returned. See [`Self::trim_trailing_spaces`].
+    trim: fn(&[C]) -> &[C],
 }
 
 impl<'c, C> TextColumnView<'c, C> {
@@ -342,9 +416,17 @@ impl<'c, C> TextColumnView<'c, C> {
         self.num_rows == 0
     }
 
-    /// Slice of text at the specified row index without terminating zero.
+    /// Slice of text at the specified row index without terminating zero. `None` if the value is
+    /// `NULL`.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is not smaller than [`Self::len`].
     pub fn get(&self, index: usize) -> Option<&'c [C]> {
-        self.col.value_at(index)
+        if index >= self.num_rows {
+            panic!("Row index points beyond the range of valid values.")
+        }
+        self.col.value_at(index).map(self.trim)
     }
 
     /// Iterator over the valid elements of the text buffer
@@ -353,6 +435,50 @@ impl<'c, C> TextColumnView<'c, C> {
             pos: 0,
             num_rows: self.num_rows,
             col: self.col,
+            trim: self.trim,
+        }
+    }
+
+    /// Iterator over the valid elements of the text buffer, decoded as `str`. Narrow values are
+    /// borrowed from the buffer, as long as they are valid UTF-8. Only values which need to be
+    /// converted are allocated, i.e. all wide values and narrow values containing invalid UTF-8.
+    /// Invalid sequences are replaced with `U+FFFD`. Combine it with
+    /// [`Self::trim_trailing_spaces`] to strip padding without allocating.
+    ///
+    /// ```
+    /// use odbc_api::buffers::TextColumnView;
+    ///
+    /// fn count_greetings(view: TextColumnView<'_, u8>) -> usize {
+    ///     view.iter_str()
+    ///         .filter(|text| text.as_deref() == Some("Hello"))
+    ///         .count()
+    /// }
+    /// ```
+    pub fn iter_str(&self) -> TextColumnStrIt<'c, C> {
+        TextColumnStrIt { inner: self.iter() }
+    }
+
+    /// A view which omits trailing spaces of elements returned by [`Self::get`] and
+    /// [`Self::iter`]. Data sources pad values of fixed width `CHAR(n)` columns up to their
+    /// length, which is rarely of interest to the application. Methods giving access to the
+    /// underlying buffer, like [`Self::content_length_at`] or [`Self::raw_value_buffer`], are not
+    /// affected.
+    ///
+    /// ```
+    /// use odbc_api::buffers::TextColumnView;
+    ///
+    /// fn codes(view: TextColumnView<'_, u8>) -> Vec<Option<&[u8]>> {
+    ///     // `CHAR(5)` column holding 'ab' would otherwise return 'ab   '.
+    ///     view.trim_trailing_spaces().iter().collect()
+    /// }
+    /// ```
+    pub fn trim_trailing_spaces(self) -> Self
+    where
+        C: From<u8> + PartialEq,
+    {
+        Self {
+            trim: trim_trailing_spaces::<C>,
+            ..self
         }
     }
 
@@ -383,6 +509,95 @@ impl<'c, C> TextColumnView<'c, C> {
     }
 }
 
+impl<'c> TextColumnView<'c, u16> {
+    /// Appends the UTF-8 representation of the value at `index` to `out`. Returns `false` if the
+    /// value is `NULL`, in which case nothing is appended. Unpaired surrogates are replaced with
+    /// `U+FFFD`.
+    ///
+    /// Faster than decoding the values returned by [`Self::iter`] character by character, since
+    /// runs of ASCII characters are converted a chunk at a time. Reusing `out` for many values
+    /// also avoids an allocation per value.
+    ///
+    /// ```
+    /// use odbc_api::buffers::TextColumnView;
+    ///
+    /// fn to_utf8(view: TextColumnView<'_, u16>) -> Vec<Option<String>> {
+    ///     let mut text = String::new();
+    ///     (0..view.len())
+    ///         .map(|index| {
+    ///             text.clear();
+    ///             view.push_utf8(index, &mut text).then(|| text.clone())
+    ///         })
+    ///         .collect()
+    /// }
+    /// ```
+    pub fn push_utf8(&self, index: usize, out: &mut String) -> bool {
+        if let Some(text) = self.get(index) {
+            slice_to_utf8_lossy_into(text, out);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The value at `index` as `U16Str`. Includes interior nuls, but excludes the terminating nul.
+    /// `None` if the value is `NULL`.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is not smaller than [`Self::len`].
+    pub fn ustr_at(&self, index: usize) -> Option<&'c U16Str> {
+        assert!(
+            index < self.num_rows,
+            "Index {index} is out of bounds for a view with {} rows",
+            self.num_rows
+        );
+        self.get(index).map(U16Str::from_slice)
+    }
+
+    /// Decodes the value at `index` into a `String`. `Ok(None)` if the value is `NULL`. An error
+    /// is returned if the value is not valid UTF-16, e.g. because it contains unpaired surrogates.
+    ///
+    /// ```
+    /// use odbc_api::buffers::TextColumnView;
+    ///
+    /// fn first_name(view: TextColumnView<'_, u16>) -> Option<String> {
+    ///     view.string_at(0).expect("Names must be valid UTF-16")
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `index` is not smaller than [`Self::len`].
+    pub fn string_at(&self, index: usize) -> Result<Option<String>, FromUtf16Error> {
+        self.ustr_at(index)
+            .map(|text| String::from_utf16(text.as_slice()))
+            .transpose()
+    }
+
+    /// Decodes the value at `index` into a `String`. `None` if the value is `NULL`. Unpaired
+    /// surrogates are replaced with `U+FFFD`.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is not smaller than [`Self::len`].
+    pub fn string_at_lossy(&self, index: usize) -> Option<String> {
+        self.ustr_at(index).map(|text| {
+            let mut out = String::new();
+            slice_to_utf8_lossy_into(text.as_slice(), &mut out);
+            out
+        })
+    }
+
+    /// Decodes all values of the view into `String`s. `NULL`s are represented as `None`. Unpaired
+    /// surrogates are replaced with `U+FFFD`.
+    pub fn to_strings_lossy(&self) -> Vec<Option<String>> {
+        (0..self.num_rows)
+            .map(|index| self.string_at_lossy(index))
+            .collect()
+    }
+}
+
 unsafe impl<'a, C: 'static> BoundInputSlice<'a> for TextColumn<C> {
     type SliceMut = TextColumnSliceMut<'a, C>;
 
@@ -420,6 +635,11 @@ where
         self.column.set_value(row_index, element)
     }
 
+    /// Maximum length of elements, which can be held without resizing the buffer.
+    pub fn max_len(&self) -> usize {
+        self.column.max_len()
+    }
+
     /// Ensures that the buffer is large enough to hold elements of `element_length`. Does nothing
     /// if the buffer is already large enough. Otherwise it will reallocate and rebind the buffer.
     /// The first `num_rows_to_copy_elements` will be copied from the old value buffer to the new
@@ -429,6 +649,21 @@ where
         element_length: usize,
         num_rows_to_copy: usize,
     ) -> Result<(), Error>
+    where
+        TextColumn<C>: HasDataType + CData,
+    {
+        self.grow_to_fit(element_length, num_rows_to_copy, GrowthPolicy::Exact)
+    }
+
+    /// Like [`Self::ensure_max_element_length`], but `policy` decides the new maximum element
+    /// length, should the buffer need to grow. Fails with [`Error::TooLargeValueForBuffer`] if the
+    /// policy does not allow the buffer to grow.
+    pub fn grow_to_fit(
+        &mut self,
+        element_length: usize,
+        num_rows_to_copy: usize,
+        policy: GrowthPolicy,
+    ) -> Result<(), Error>
     where
         TextColumn<C>: HasDataType + CData,
     {
@@ -436,7 +671,9 @@ where
         // in order to hold it. This invalidates the pointers previously bound to the statement. So
         // we rebind them.
         if element_length > self.column.max_len() {
-            let new_max_str_len = element_length;
+            let new_max_str_len = policy
+                .new_max_len(self.column.max_len(), element_length)
+                .ok_or(Error::TooLargeValueForBuffer)?;
             self.column
                 .resize_max_str(new_max_str_len, num_rows_to_copy);
             unsafe {
@@ -480,6 +717,52 @@ where
     pub fn set_mut(&mut self, index: usize, length: usize) -> &mut [C] {
         self.column.set_mut(index, length)
     }
+
+    /// Mutable access to the first `num_rows` elements. See [`TextColumn::iter_mut`].
+    pub fn iter_mut(&mut self, num_rows: usize) -> CellsMut<'_, C> {
+        self.column.iter_mut(num_rows)
+    }
+}
+
+/// Default for [`TextColumnView::trim`], which returns elements as they are.
+fn untrimmed<C>(text: &[C]) -> &[C] {
+    text
+}
+
+fn trim_trailing_spaces<C>(text: &[C]) -> &[C]
+where
+    C: From<u8> + PartialEq,
+{
+    let space = C::from(b' ');
+    let length = text
+        .iter()
+        .rposition(|c| *c != space)
+        .map_or(0, |last| last + 1);
+    &text[..length]
+}
+
+impl<'c, C> IntoIterator for TextColumnView<'c, C>
+where
+    TextColumnIt<'c, C>: Iterator,
+{
+    type Item = <TextColumnIt<'c, C> as Iterator>::Item;
+    type IntoIter = TextColumnIt<'c, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'c, C> IntoIterator for &TextColumnView<'c, C>
+where
+    TextColumnIt<'c, C>: Iterator,
+{
+    type Item = <TextColumnIt<'c, C> as Iterator>::Item;
+    type IntoIter = TextColumnIt<'c, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
 
 /// Iterator over a text column. See [`TextColumnView::iter`]
@@ -488,6 +771,7 @@ pub struct TextColumnIt<'c, C> {
     pos: usize,
     num_rows: usize,
     col: &'c TextColumn<C>,
+    trim: fn(&[C]) -> &[C],
 }
 
 impl<'c, C> TextColumnIt<'c, C> {
@@ -495,7 +779,7 @@ impl<'c, C> TextColumnIt<'c, C> {
         if self.pos == self.num_rows {
             None
         } else {
-            let ret = Some(self.col.value_at(self.pos));
+            let ret = Some(self.col.value_at(self.pos).map(self.trim));
             self.pos += 1;
             ret
         }
@@ -532,6 +816,48 @@ impl<'c> Iterator for TextColumnIt<'c, u16> {
 
 impl<'c> ExactSizeIterator for TextColumnIt<'c, u16> {}
 
+/// Iterator over a text column yielding its values as `str`. See [`TextColumnView::iter_str`].
+#[derive(Debug)]
+pub struct TextColumnStrIt<'c, C> {
+    inner: TextColumnIt<'c, C>,
+}
+
+impl<'c> Iterator for TextColumnStrIt<'c, u8> {
+    type Item = Option<Cow<'c, str>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next_impl()
+            .map(|opt| opt.map(String::from_utf8_lossy))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'c> ExactSizeIterator for TextColumnStrIt<'c, u8> {}
+
+impl<'c> Iterator for TextColumnStrIt<'c, u16> {
+    type Item = Option<Cow<'c, str>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next_impl().map(|opt| {
+            opt.map(|text| {
+                let mut out = String::new();
+                slice_to_utf8_lossy_into(text, &mut out);
+                Cow::Owned(out)
+            })
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'c> ExactSizeIterator for TextColumnStrIt<'c, u16> {}
+
 unsafe impl CData for CharColumn {
     fn cdata_type(&self) -> CDataType {
         CDataType::Char
@@ -546,7 +872,7 @@ unsafe impl CData for CharColumn {
     }
 
     fn buffer_length(&self) -> isize {
-        (self.max_str_len + 1).try_into().unwrap()
+        isize::try_from(self.max_str_len + 1).expect("Element length must fit into an ODBC length.")
     }
 }
 
@@ -582,7 +908,10 @@ unsafe impl CData for WCharColumn {
     }
 
     fn buffer_length(&self) -> isize {
-        ((self.max_str_len + 1) * 2).try_into().unwrap()
+        (self.max_str_len + 1)
+            .checked_mul(2)
+            .and_then(|len| isize::try_from(len).ok())
+            .expect("Element length must fit into an ODBC length.")
     }
 }
 
@@ -603,3 +932,132 @@ impl HasDataType for WCharColumn {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use crate::buffers::{ColumnBuffer, TextColumn};
+
+    #[test]
+    fn view_trimming_trailing_spaces() {
+        let mut column = TextColumn::<u8>::new(3, 5);
+        column.set_value(0, Some(b"ab   "));
+        column.set_value(1, None);
+        column.set_value(2, Some(b"     "));
+
+        let view = column.view(3).trim_trailing_spaces();
+
+        assert_eq!(Some(&b"ab"[..]), view.get(0));
+        assert_eq!(
+            vec![Some(&b"ab"[..]), None, Some(&b""[..])],
+            view.iter().collect::<Vec<_>>()
+        );
+        // Underlying buffer is not changed
+        assert_eq!(Some(5), view.content_length_at(0));
+        assert_eq!(Some(&b"ab   "[..]), column.view(3).get(0));
+    }
+
+    #[test]
+    fn trim_trailing_spaces_in_place() {
+        let mut column = TextColumn::<u16>::new(2, 4);
+        let text: Vec<u16> = "a b ".encode_utf16().collect();
+        column.set_value(0, Some(&text));
+        column.set_value(1, Some(&text[..1]));
+
+        column.trim_trailing_spaces(2);
+
+        assert_eq!(Some(3), column.content_length_at(0));
+        assert_eq!(Some(1), column.content_length_at(1));
+    }
+
+    #[test]
+    fn wide_text_to_strings() {
+        let mut column = TextColumn::<u16>::new(3, 5);
+        let text: Vec<u16> = "Grüß".encode_utf16().collect();
+        column.set_value(0, Some(&text));
+        column.set_value(1, None);
+        // Unpaired surrogate
+        column.set_value(2, Some(&[0x61, 0xD800]));
+
+        let view = column.view(3);
+
+        assert_eq!(Some("Grüß".to_owned()), view.string_at(0).unwrap());
+        assert_eq!(None, view.string_at(1).unwrap());
+        assert!(view.string_at(2).is_err());
+        assert_eq!(
+            vec![Some("Grüß".to_owned()), None, Some("a\u{FFFD}".to_owned())],
+            view.to_strings_lossy()
+        );
+    }
+
+    #[test]
+    fn iter_str_borrows_valid_utf8() {
+        let mut column = TextColumn::<u8>::new(3, 5);
+        column.set_value(0, Some(b"ab   "));
+        column.set_value(1, None);
+        column.set_value(2, Some(&[b'a', 0xFF]));
+
+        let values: Vec<_> = column.view(3).trim_trailing_spaces().iter_str().collect();
+
+        assert!(matches!(&values[0], Some(Cow::Borrowed("ab"))));
+        assert!(values[1].is_none());
+        assert!(matches!(&values[2], Some(Cow::Owned(text)) if text == "a\u{FFFD}"));
+    }
+
+    #[test]
+    fn iter_str_decodes_wide_text() {
+        let mut column = TextColumn::<u16>::new(2, 5);
+        let text: Vec<u16> = "Grüß".encode_utf16().collect();
+        column.set_value(0, Some(&text));
+        column.set_value(1, None);
+
+        let values: Vec<_> = column.view(2).iter_str().collect();
+
+        assert_eq!(vec![Some(Cow::Borrowed("Grüß")), None], values);
+    }
+
+    #[test]
+    fn iterate_view_in_for_loop() {
+        let mut column = TextColumn::<u8>::new(3, 5);
+        column.set_value(0, Some(b"a"));
+        column.set_value(1, None);
+
+        let view = column.view(2);
+        let mut values = Vec::new();
+        for value in &view {
+            values.push(value);
+        }
+
+        assert_eq!(vec![Some(&b"a"[..]), None], values);
+        assert_eq!(2, view.into_iter().len());
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_beyond_valid_rows() {
+        let column = TextColumn::<u8>::new(3, 5);
+
+        column.view(2).get(2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn wide_text_index_out_of_bounds() {
+        let column = TextColumn::<u16>::new(3, 5);
+
+        column.view(2).ustr_at(2);
+    }
+
+    #[test]
+    fn element_size_exceeding_indicator_range_is_an_error() {
+        // Allocating would not even be attempted, since the length of a single element can not be
+        // described to ODBC.
+        let result = TextColumn::<u16>::try_new(1, usize::MAX / 2);
+        assert!(result.is_err());
+
+        let result = TextColumn::<u8>::try_new(usize::MAX / 2, 4);
+        let error = result.err().unwrap();
+        assert_eq!(5, error.element_size);
+    }
+}
@@ -0,0 +1,108 @@
+use odbc_sys::NULL_DATA;
+
+/// Packed bitmap telling which elements of a column are not `NULL`. Uses the same layout as the
+/// validity bitmaps of Apache Arrow: Bit `i % 8` (least significant bit first) of byte `i / 8` is
+/// set if, and only if element `i` holds a value.
+///
+/// Together with a dense slice of values (e.g. as returned by
+/// [`crate::buffers::NullableSlice::raw_values`]) this allows columnar consumers to process a
+/// nullable column without branching on an `Option` for each element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidityBitmap {
+    bits: Vec<u8>,
+    len: usize,
+    null_count: usize,
+}
+
+impl ValidityBitmap {
+    /// Computes the bitmap from indicators, as they are written by ODBC drivers. Any indicator
+    /// other than [`crate::sys::NULL_DATA`] marks a valid element.
+    pub fn from_indicators(indicators: &[isize]) -> Self {
+        let mut null_count = 0;
+        let bits = indicators
+            .chunks(8)
+            .map(|chunk| {
+                let mut byte = 0u8;
+                for (bit, &indicator) in chunk.iter().enumerate() {
+                    if indicator == NULL_DATA {
+                        null_count += 1;
+                    } else {
+                        byte |= 1 << bit;
+                    }
+                }
+                byte
+            })
+            .collect();
+        Self {
+            bits,
+            len: indicators.len(),
+            null_count,
+        }
+    }
+
+    /// Number of elements described by the bitmap.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// `true` if the bitmap describes no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of `NULL` elements. Consumers may skip the bitmap altogether, if this is zero.
+    pub fn null_count(&self) -> usize {
+        self.null_count
+    }
+
+    /// `true` if the element at `index` is not `NULL`.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is not smaller than [`Self::len`].
+    pub fn is_valid(&self, index: usize) -> bool {
+        assert!(index < self.len, "Index out of bounds of validity bitmap.");
+        self.bits[index / 8] & (1 << (index % 8)) != 0
+    }
+
+    /// The packed bits. Padding bits in the last byte are not set.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bits
+    }
+
+    /// Takes ownership of the packed bits, e.g. to hand them over to an Arrow buffer without
+    /// copying.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use odbc_sys::NULL_DATA;
+
+    use super::ValidityBitmap;
+
+    #[test]
+    fn bitmap_from_indicators() {
+        let mut indicators = vec![4; 10];
+        indicators[1] = NULL_DATA;
+        indicators[9] = NULL_DATA;
+
+        let bitmap = ValidityBitmap::from_indicators(&indicators);
+
+        assert_eq!(10, bitmap.len());
+        assert_eq!(2, bitmap.null_count());
+        assert_eq!(&[0b1111_1101, 0b0000_0001], bitmap.as_bytes());
+        assert!(bitmap.is_valid(0));
+        assert!(!bitmap.is_valid(1));
+        assert!(!bitmap.is_valid(9));
+    }
+
+    #[test]
+    fn empty_bitmap() {
+        let bitmap = ValidityBitmap::from_indicators(&[]);
+        assert!(bitmap.is_empty());
+        assert!(bitmap.as_bytes().is_empty());
+    }
+}
@@ -0,0 +1,123 @@
+//! Conversions of ODBC dates and times into the integer representations used by columnar and
+//! row based file formats, as well as into text.
+
+use std::fmt::Write;
+
+use crate::sys::{Date, Time, Timestamp};
+
+/// Formats `date` as `YYYY-MM-DD`.
+pub fn format_date(date: &Date) -> String {
+    format!("{:04}-{:02}-{:02}", date.year, date.month, date.day)
+}
+
+/// Formats `time` as `hh:mm:ss`.
+pub fn format_time(time: &Time) -> String {
+    format!("{:02}:{:02}:{:02}", time.hour, time.minute, time.second)
+}
+
+/// Formats `ts` as `YYYY-MM-DD hh:mm:ss.fffffffff`, with `separator` between date and time. Only
+/// significant digits of the fraction are printed, and none at all if it is zero.
+pub fn format_timestamp(ts: &Timestamp, separator: char) -> String {
+    let mut text = format!(
+        "{:04}-{:02}-{:02}{separator}{:02}:{:02}:{:02}",
+        ts.year, ts.month, ts.day, ts.hour, ts.minute, ts.second
+    );
+    // Fraction is in nanoseconds.
+    if ts.fraction != 0 {
+        write!(text, ".{:09}", ts.fraction).unwrap();
+        let significant_len = text.trim_end_matches('0').len();
+        text.truncate(significant_len);
+    }
+    text
+}
+
+#[cfg(any(feature = "avro", feature = "parquet"))]
+/// Days since 1970-01-01.
+pub fn days_since_epoch(date: &Date) -> i64 {
+    civil_days_since_epoch(date.year, date.month, date.day)
+}
+
+#[cfg(any(feature = "avro", feature = "parquet"))]
+/// Milliseconds since midnight.
+pub fn millis_since_midnight(time: &Time) -> i64 {
+    let seconds =
+        i64::from(time.hour) * 3600 + i64::from(time.minute) * 60 + i64::from(time.second);
+    seconds * 1000
+}
+
+#[cfg(any(feature = "avro", feature = "parquet"))]
+/// Microseconds since 1970-01-01 00:00:00. ODBC timestamps do not carry a time zone, so neither
+/// does the result.
+pub fn micros_since_epoch(ts: &Timestamp) -> i64 {
+    let days = civil_days_since_epoch(ts.year, ts.month, ts.day);
+    let seconds = days * 86_400
+        + i64::from(ts.hour) * 3600
+        + i64::from(ts.minute) * 60
+        + i64::from(ts.second);
+    // Fraction is in nanoseconds.
+    seconds * 1_000_000 + i64::from(ts.fraction) / 1000
+}
+
+#[cfg(any(feature = "avro", feature = "parquet"))]
+/// Number of days between 1970-01-01 and the date in the proleptic gregorian calendar. Negative
+/// for earlier dates.
+fn civil_days_since_epoch(year: i16, month: u16, day: u16) -> i64 {
+    // Shift the year to start in march, so the leap day is the last day of the year.
+    let year = i64::from(year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sys::Timestamp;
+
+    use super::format_timestamp;
+    #[cfg(any(feature = "avro", feature = "parquet"))]
+    use super::{civil_days_since_epoch, micros_since_epoch};
+
+    #[cfg(any(feature = "avro", feature = "parquet"))]
+    #[test]
+    fn days_relative_to_epoch() {
+        assert_eq!(0, civil_days_since_epoch(1970, 1, 1));
+        assert_eq!(-1, civil_days_since_epoch(1969, 12, 31));
+        assert_eq!(19_358, civil_days_since_epoch(2023, 1, 1));
+        assert_eq!(11_016, civil_days_since_epoch(2000, 2, 29));
+    }
+
+    #[cfg(any(feature = "avro", feature = "parquet"))]
+    #[test]
+    fn timestamp_in_micros() {
+        let ts = Timestamp {
+            year: 1970,
+            month: 1,
+            day: 2,
+            hour: 0,
+            minute: 0,
+            second: 1,
+            fraction: 500_000_000,
+        };
+        assert_eq!(86_401_500_000, micros_since_epoch(&ts));
+    }
+
+    #[test]
+    fn timestamp_as_text() {
+        let mut ts = Timestamp {
+            year: 2023,
+            month: 1,
+            day: 31,
+            hour: 13,
+            minute: 45,
+            second: 7,
+            fraction: 0,
+        };
+        assert_eq!("2023-01-31 13:45:07", format_timestamp(&ts, ' '));
+        ts.fraction = 123_000_000;
+        assert_eq!("2023-01-31T13:45:07.123", format_timestamp(&ts, 'T'));
+    }
+}
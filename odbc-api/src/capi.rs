@@ -0,0 +1,391 @@
+//! C API for building bindings for other languages (e.g. Python or Node) on top of this crate,
+//! without having to wrap `odbc-sys` again. Enabled with the `capi` feature.
+//!
+//! The functions are exported unmangled, so linking this crate into a `cdylib` makes them
+//! available to any language with a C foreign function interface. All functions catch panics,
+//! rather than unwinding into the caller. They return a [`Status`] and report their results using
+//! out parameters. If a function fails, the message describing the error is available using
+//! [`odbc_api_last_error`].
+//!
+//! Result sets are fetched as text in batches. This is the one representation every language
+//! binding is able to consume and convert further.
+//!
+//! ```c
+//! OdbcApiConnection* conn;
+//! // `0` indicates success.
+//! if (odbc_api_connect("DSN=YourDatabase;", &conn) != 0) {
+//!     fprintf(stderr, "%s\n", odbc_api_last_error());
+//!     return 1;
+//! }
+//! OdbcApiCursor* cursor;
+//! odbc_api_execute(conn, "SELECT title FROM Movies", 100, 4096, &cursor);
+//! size_t num_rows;
+//! while (odbc_api_cursor_fetch(cursor, &num_rows) == 0 && num_rows != 0) {
+//!     for (size_t row = 0; row != num_rows; ++row) {
+//!         const uint8_t* text;
+//!         size_t len;
+//!         odbc_api_cursor_text(cursor, 0, row, &text, &len);
+//!         printf("%.*s\n", (int) len, text);
+//!     }
+//! }
+//! odbc_api_cursor_free(cursor);
+//! odbc_api_connection_free(conn);
+//! ```
+//!
+//! # Safety
+//!
+//! Pointers passed to these functions must either be null or valid. Handles must only be freed
+//! once, and a cursor must be freed before the connection it has been created with.
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    ffi::{c_char, CStr, CString},
+    panic::{catch_unwind, AssertUnwindSafe},
+    ptr::{null, null_mut},
+};
+
+use crate::{
+    buffers::TextRowSet, handles::StatementImpl, BlockCursor, Connection, Cursor, CursorImpl,
+    Environment, ResultSetMetadata,
+};
+
+/// Outcome of a call to a function of the C API.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// The function succeeded.
+    Success = 0,
+    /// The function failed. See [`odbc_api_last_error`].
+    Error = 1,
+    /// The function panicked. The panic has been caught, see [`odbc_api_last_error`] for its
+    /// message.
+    Panic = 2,
+}
+
+/// Connection to a data source. Created by [`odbc_api_connect`].
+pub struct OdbcApiConnection {
+    connection: Connection<'static>,
+}
+
+/// Result set of a query, fetched in batches of text. Created by [`odbc_api_execute`].
+pub struct OdbcApiCursor {
+    /// The statement borrows a connection, which is owned by the caller. We rely on the caller to
+    /// free the cursor before the connection.
+    block_cursor: BlockCursor<CursorImpl<StatementImpl<'static>>, TextRowSet>,
+    column_names: Vec<CString>,
+    /// Number of rows in the last batch fetched.
+    num_rows: usize,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+/// Message describing the error of the last function of the C API which failed on this thread.
+/// Empty if none failed so far. The returned string is owned by this library and valid until the
+/// next call of a function of the C API on this thread.
+#[no_mangle]
+pub extern "C" fn odbc_api_last_error() -> *const c_char {
+    LAST_ERROR.with(|last_error| last_error.borrow().as_ptr())
+}
+
+/// Connects to a data source using the process wide environment.
+///
+/// # Safety
+///
+/// `connection_string` must be a valid, nul terminated string. `connection` must be valid for
+/// writes.
+#[no_mangle]
+pub unsafe extern "C" fn odbc_api_connect(
+    connection_string: *const c_char,
+    connection: *mut *mut OdbcApiConnection,
+) -> Status {
+    guard(|| {
+        let connection_string = to_str(connection_string, "connection_string")?;
+        let out = out_param(connection, "connection")?;
+        let env = Environment::singleton().map_err(|e| e.to_string())?;
+        let inner = env
+            .connect_with_connection_string(connection_string)
+            .map_err(|e| e.to_string())?;
+        *out = Box::into_raw(Box::new(OdbcApiConnection { connection: inner }));
+        Ok(())
+    })
+}
+
+/// Closes the connection and frees it. Does nothing if `connection` is null.
+///
+/// # Safety
+///
+/// `connection` must have been created by [`odbc_api_connect`] and not been freed before. All
+/// cursors created with it must have been freed.
+#[no_mangle]
+pub unsafe extern "C" fn odbc_api_connection_free(connection: *mut OdbcApiConnection) {
+    if !connection.is_null() {
+        // Dropping the connection may panic, if rolling back an open transaction fails. Only the
+        // message is of interest in that case.
+        let _ = guard(|| {
+            drop(Box::from_raw(connection));
+            Ok(())
+        });
+    }
+}
+
+/// Executes `query`. If it creates a result set a cursor fetching `batch_size` rows at once is
+/// written into `cursor`, otherwise null. Text longer than `max_str_len` is truncated, `0` means
+/// no limit beyond what the driver reports.
+///
+/// # Safety
+///
+/// `connection` must be a valid connection, `query` a valid nul terminated string and `cursor`
+/// valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn odbc_api_execute(
+    connection: *mut OdbcApiConnection,
+    query: *const c_char,
+    batch_size: usize,
+    max_str_len: usize,
+    cursor: *mut *mut OdbcApiCursor,
+) -> Status {
+    guard(|| {
+        let connection = connection
+            .as_ref()
+            .ok_or("Argument `connection` must not be null.")?;
+        let query = to_str(query, "query")?;
+        let out = out_param(cursor, "cursor")?;
+        *out = null_mut();
+        if batch_size == 0 {
+            return Err("Argument `batch_size` must be at least one.".to_owned());
+        }
+        let Some(mut inner) = connection
+            .connection
+            .execute(query, ())
+            .map_err(|e| e.to_string())?
+        else {
+            return Ok(());
+        };
+        let column_names = inner
+            .column_names()
+            .and_then(|names| names.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            // Column names with interior nul can not be represented in C, truncate them.
+            .map(|name| {
+                let end = name.find('\0').unwrap_or(name.len());
+                CString::new(&name[..end]).unwrap()
+            })
+            .collect();
+        let max_str_limit = (max_str_len != 0).then_some(max_str_len);
+        let buffer = TextRowSet::for_cursor(batch_size, &mut inner, max_str_limit)
+            .map_err(|e| e.to_string())?;
+        let block_cursor = inner.bind_buffer(buffer).map_err(|e| e.to_string())?;
+        *out = Box::into_raw(Box::new(OdbcApiCursor {
+            block_cursor,
+            column_names,
+            num_rows: 0,
+        }));
+        Ok(())
+    })
+}
+
+/// Number of columns of the result set.
+///
+/// # Safety
+///
+/// `cursor` must be a valid cursor and `num_cols` valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn odbc_api_cursor_num_cols(
+    cursor: *const OdbcApiCursor,
+    num_cols: *mut usize,
+) -> Status {
+    guard(|| {
+        let cursor = cursor
+            .as_ref()
+            .ok_or("Argument `cursor` must not be null.")?;
+        *out_param(num_cols, "num_cols")? = cursor.column_names.len();
+        Ok(())
+    })
+}
+
+/// Name of the column at the zero based `col_index`. The string is owned by the cursor and valid
+/// until it is freed.
+///
+/// # Safety
+///
+/// `cursor` must be a valid cursor and `name` valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn odbc_api_cursor_col_name(
+    cursor: *const OdbcApiCursor,
+    col_index: usize,
+    name: *mut *const c_char,
+) -> Status {
+    guard(|| {
+        let cursor = cursor
+            .as_ref()
+            .ok_or("Argument `cursor` must not be null.")?;
+        let out = out_param(name, "name")?;
+        let column_name = cursor.column_names.get(col_index).ok_or_else(|| {
+            format!(
+                "Column index {col_index} is out of bounds. The result set has {} columns.",
+                cursor.column_names.len()
+            )
+        })?;
+        *out = column_name.as_ptr();
+        Ok(())
+    })
+}
+
+/// Fetches the next batch of rows. The number of rows fetched is written into `num_rows`. `0`
+/// indicates the end of the result set. Fetching invalidates the text of the previous batch.
+///
+/// # Safety
+///
+/// `cursor` must be a valid cursor and `num_rows` valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn odbc_api_cursor_fetch(
+    cursor: *mut OdbcApiCursor,
+    num_rows: *mut usize,
+) -> Status {
+    guard(|| {
+        let cursor = cursor
+            .as_mut()
+            .ok_or("Argument `cursor` must not be null.")?;
+        let out = out_param(num_rows, "num_rows")?;
+        cursor.num_rows = cursor
+            .block_cursor
+            .fetch()
+            .map_err(|e| e.to_string())?
+            .map_or(0, |batch| batch.num_rows());
+        *out = cursor.num_rows;
+        Ok(())
+    })
+}
+
+/// Text of the cell at the zero based `col_index` and `row_index` within the current batch. For
+/// `NULL` null is written into `text`. The text is not nul terminated, its length in bytes is
+/// written into `len`. It is owned by the cursor and valid until the next fetch.
+///
+/// # Safety
+///
+/// `cursor` must be a valid cursor. `text` and `len` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn odbc_api_cursor_text(
+    cursor: *const OdbcApiCursor,
+    col_index: usize,
+    row_index: usize,
+    text: *mut *const u8,
+    len: *mut usize,
+) -> Status {
+    guard(|| {
+        let cursor = cursor
+            .as_ref()
+            .ok_or("Argument `cursor` must not be null.")?;
+        let out_text = out_param(text, "text")?;
+        let out_len = out_param(len, "len")?;
+        if col_index >= cursor.column_names.len() || row_index >= cursor.num_rows {
+            return Err(format!(
+                "Cell ({col_index}, {row_index}) is out of bounds. The batch has {} columns and \
+                {} rows.",
+                cursor.column_names.len(),
+                cursor.num_rows
+            ));
+        }
+        let cell = cursor.block_cursor.buffer().at(col_index, row_index);
+        *out_text = cell.map_or(null(), <[u8]>::as_ptr);
+        *out_len = cell.map_or(0, <[u8]>::len);
+        Ok(())
+    })
+}
+
+/// Closes the cursor and frees it. Does nothing if `cursor` is null.
+///
+/// # Safety
+///
+/// `cursor` must have been created by [`odbc_api_execute`] and not been freed before.
+#[no_mangle]
+pub unsafe extern "C" fn odbc_api_cursor_free(cursor: *mut OdbcApiCursor) {
+    if !cursor.is_null() {
+        let _ = guard(|| {
+            drop(Box::from_raw(cursor));
+            Ok(())
+        });
+    }
+}
+
+/// Invokes `f`, catching panics and recording errors, so they can be retrieved using
+/// [`odbc_api_last_error`].
+fn guard(f: impl FnOnce() -> Result<(), String>) -> Status {
+    let (status, message) = match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => return Status::Success,
+        Ok(Err(message)) => (Status::Error, message),
+        Err(payload) => (Status::Panic, panic_message(payload)),
+    };
+    let message = message.replace('\0', " ");
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = CString::new(message).unwrap());
+    status
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_owned()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Unknown panic".to_owned()
+    }
+}
+
+unsafe fn to_str<'a>(text: *const c_char, argument: &str) -> Result<&'a str, String> {
+    if text.is_null() {
+        return Err(format!("Argument `{argument}` must not be null."));
+    }
+    CStr::from_ptr(text)
+        .to_str()
+        .map_err(|_| format!("Argument `{argument}` must be valid UTF-8."))
+}
+
+unsafe fn out_param<'a, T>(out: *mut T, argument: &str) -> Result<&'a mut T, String> {
+    out.as_mut()
+        .ok_or_else(|| format!("Argument `{argument}` must not be null."))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ffi::CStr, ptr::null_mut};
+
+    use super::{guard, odbc_api_connect, odbc_api_cursor_fetch, odbc_api_last_error, Status};
+
+    fn last_error() -> String {
+        unsafe { CStr::from_ptr(odbc_api_last_error()) }
+            .to_str()
+            .unwrap()
+            .to_owned()
+    }
+
+    #[test]
+    fn null_arguments_are_errors() {
+        let mut connection = null_mut();
+
+        let status = unsafe { odbc_api_connect(std::ptr::null(), &mut connection) };
+
+        assert_eq!(Status::Error, status);
+        assert_eq!(
+            "Argument `connection_string` must not be null.",
+            last_error()
+        );
+
+        let mut num_rows = 0;
+        let status = unsafe { odbc_api_cursor_fetch(null_mut(), &mut num_rows) };
+
+        assert_eq!(Status::Error, status);
+        assert_eq!("Argument `cursor` must not be null.", last_error());
+    }
+
+    #[test]
+    fn panics_are_caught() {
+        let status = guard(|| panic!("Boom"));
+
+        assert_eq!(Status::Panic, status);
+        assert_eq!("Boom", last_error());
+    }
+}
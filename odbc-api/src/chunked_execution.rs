@@ -0,0 +1,211 @@
+use std::ops::ControlFlow;
+
+use thiserror::Error as ThisError;
+
+use crate::{handles::AsStatementRef, ArrayRow, ChunkError, Error, Prepared};
+
+/// Signature of the hook called after each chunk, if none has been set explicitly.
+type StopOnError = fn(&ChunkReport<'_>) -> ControlFlow<()>;
+
+/// Executes a prepared statement for each row of an iterator. Rows are tuples like
+/// `(i32, Option<f64>, &str)`. They are copied into parameter arrays owned by this crate, which are
+/// sent to the data source once they hold [`Self::chunk_size`] rows. The arrays are reused for the
+/// next chunk. Created by [`Prepared::chunked_execution`].
+///
+/// ```no_run
+/// use std::ops::ControlFlow;
+/// use odbc_api::{ChunkedExecutionError, ChunkedSummary, Connection};
+///
+/// fn insert_measurements(
+///     conn: &Connection<'_>,
+///     measurements: impl Iterator<Item = (i32, Option<f64>, String)>,
+/// ) -> Result<ChunkedSummary, Box<dyn std::error::Error>> {
+///     let mut prepared =
+///         conn.prepare("INSERT INTO Measurements (sensor_id, value, unit) VALUES (?, ?, ?)")?;
+///     let summary = prepared
+///         .chunked_execution()
+///         .chunk_size(500)
+///         .on_chunk(|report| {
+///             match report.result {
+///                 Ok(_) => eprintln!("Inserted {} rows.", report.num_rows),
+///                 Err(error) => eprintln!("Skipped rows starting at {}: {error}", report.first_row),
+///             }
+///             ControlFlow::Continue(())
+///         })
+///         .execute(measurements)?;
+///     Ok(summary)
+/// }
+/// ```
+pub struct ChunkedExecution<'p, S, H = StopOnError> {
+    prepared: &'p mut Prepared<S>,
+    chunk_size: usize,
+    on_chunk: H,
+}
+
+/// Passed to the hook set with [`ChunkedExecution::on_chunk`] after a chunk has been executed.
+#[derive(Debug, Clone, Copy)]
+pub struct ChunkReport<'a> {
+    /// Index of the first row of the chunk, within all rows passed to
+    /// [`ChunkedExecution::execute`].
+    pub first_row: usize,
+    /// Number of rows in the chunk.
+    pub num_rows: usize,
+    /// Number of rows affected by the chunk, if reported by the driver, or the error which caused
+    /// the chunk to fail.
+    pub result: Result<Option<usize>, &'a Error>,
+}
+
+/// Returned by [`ChunkedExecution::execute`].
+#[derive(Debug)]
+pub struct ChunkedSummary {
+    /// Number of rows in chunks which have been executed successfully.
+    pub rows_executed: usize,
+    /// Sum of the rows affected by each successful chunk. `None` if the driver did not report the
+    /// row count for at least one of them.
+    pub row_count: Option<usize>,
+    /// Chunks which failed, but have been skipped by the hook, in order.
+    pub failed_chunks: Vec<ChunkError>,
+}
+
+impl Default for ChunkedSummary {
+    fn default() -> Self {
+        Self {
+            rows_executed: 0,
+            row_count: Some(0),
+            failed_chunks: Vec::new(),
+        }
+    }
+}
+
+impl ChunkedSummary {
+    /// `true` if every chunk has been executed successfully.
+    pub fn is_complete(&self) -> bool {
+        self.failed_chunks.is_empty()
+    }
+}
+
+/// Emitted by [`ChunkedExecution::execute`], if a chunk failed and the hook decided to stop.
+#[derive(Debug, ThisError)]
+#[error(
+    "Executing the chunk starting at row {first_row} failed. {rows_executed} rows have been \
+    executed successfully before. Cause:\n{source}"
+)]
+pub struct ChunkedExecutionError {
+    /// Number of rows in chunks which have been executed successfully.
+    pub rows_executed: usize,
+    /// Index of the first row of the failed chunk. Resume with the row at this index.
+    pub first_row: usize,
+    /// The error reported by the data source for the failed chunk.
+    pub source: Error,
+}
+
+impl<'p, S> ChunkedExecution<'p, S> {
+    /// Executes chunks of `1000` rows, stopping at the first chunk which fails.
+    pub fn new(prepared: &'p mut Prepared<S>) -> Self {
+        Self {
+            prepared,
+            chunk_size: 1000,
+            on_chunk: stop_on_error,
+        }
+    }
+}
+
+impl<'p, S, H> ChunkedExecution<'p, S, H>
+where
+    S: AsStatementRef,
+    H: FnMut(&ChunkReport<'_>) -> ControlFlow<()>,
+{
+    /// Maximum number of rows sent to the data source at once. Default is `1000`.
+    ///
+    /// # Panics
+    ///
+    /// If `chunk_size` is `0`.
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "Chunk size must be at least one row.");
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Called after each chunk, whether it succeeded or not. Returning [`ControlFlow::Break`]
+    /// stops the execution. Then a failed chunk is reported as [`ChunkedExecutionError`], while
+    /// the remaining rows are not consumed. Returning [`ControlFlow::Continue`] for a failed chunk
+    /// skips it and records it in [`ChunkedSummary::failed_chunks`]. The default hook stops at the
+    /// first failed chunk.
+    pub fn on_chunk<H2>(self, on_chunk: H2) -> ChunkedExecution<'p, S, H2>
+    where
+        H2: FnMut(&ChunkReport<'_>) -> ControlFlow<()>,
+    {
+        ChunkedExecution {
+            prepared: self.prepared,
+            chunk_size: self.chunk_size,
+            on_chunk,
+        }
+    }
+
+    /// Consumes `rows` chunk by chunk, executing the statement once for each chunk.
+    pub fn execute<R>(
+        mut self,
+        rows: impl IntoIterator<Item = R>,
+    ) -> Result<ChunkedSummary, ChunkedExecutionError>
+    where
+        R: ArrayRow,
+    {
+        let mut rows = rows.into_iter();
+        let mut arrays = R::Arrays::default();
+        let mut summary = ChunkedSummary::default();
+        let mut first_row = 0;
+        loop {
+            R::clear(&mut arrays);
+            let mut num_rows = 0;
+            for row in rows.by_ref().take(self.chunk_size) {
+                row.push_to(&mut arrays);
+                num_rows += 1;
+            }
+            if num_rows == 0 {
+                break;
+            }
+            // The cursor is dropped right away, the statement is not expected to return a result
+            // set.
+            let executed = self.prepared.execute(&arrays).map(|_cursor| ());
+            let result = executed.and_then(|()| self.prepared.row_count());
+            let flow = (self.on_chunk)(&ChunkReport {
+                first_row,
+                num_rows,
+                result: result.as_ref().copied(),
+            });
+            match result {
+                Ok(row_count) => {
+                    summary.rows_executed += num_rows;
+                    summary.row_count = summary
+                        .row_count
+                        .zip(row_count)
+                        .map(|(total, row_count)| total + row_count);
+                }
+                Err(source) if flow.is_break() => {
+                    return Err(ChunkedExecutionError {
+                        rows_executed: summary.rows_executed,
+                        first_row,
+                        source,
+                    })
+                }
+                Err(source) => summary.failed_chunks.push(ChunkError {
+                    first_row,
+                    num_rows,
+                    source,
+                }),
+            }
+            if flow.is_break() {
+                break;
+            }
+            first_row += num_rows;
+        }
+        Ok(summary)
+    }
+}
+
+fn stop_on_error(report: &ChunkReport<'_>) -> ControlFlow<()> {
+    match report.result {
+        Ok(_) => ControlFlow::Continue(()),
+        Err(_) => ControlFlow::Break(()),
+    }
+}
@@ -0,0 +1,152 @@
+use std::borrow::Cow;
+
+/// Decides whether two column names refer to the same column. Data sources differ in how they
+/// report column names, e.g. PostgreSQL folds unquoted identifiers to lower case, while Oracle
+/// folds them to upper case. Name based lookups like [`crate::ResultSetMetadata::col_index`] use
+/// the matcher of the connection, which can be configured with
+/// [`crate::Connection::set_column_name_matcher`].
+///
+/// By default names are matched ignoring case and surrounding whitespace.
+///
+/// ```
+/// use odbc_api::ColumnNameMatcher;
+///
+/// let matcher = ColumnNameMatcher::default();
+/// assert!(matcher.matches("CUSTOMER_ID", "customer_id "));
+/// assert_eq!(Some(1), matcher.position(["id", "Name"], "NAME"));
+///
+/// let exact = ColumnNameMatcher::exact();
+/// assert!(!exact.matches("CUSTOMER_ID", "customer_id"));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ColumnNameMatcher {
+    case_insensitive: bool,
+    trim: bool,
+    normalization: Option<fn(&str) -> String>,
+}
+
+impl ColumnNameMatcher {
+    /// Matches only names which are identical.
+    pub fn exact() -> Self {
+        Self {
+            case_insensitive: false,
+            trim: false,
+            normalization: None,
+        }
+    }
+
+    /// Whether to compare names ignoring their case. Uses Unicode case folding.
+    pub fn case_insensitive(mut self, enabled: bool) -> Self {
+        self.case_insensitive = enabled;
+        self
+    }
+
+    /// Whether to ignore leading and trailing whitespace. Some drivers pad names of fixed width.
+    pub fn trim(mut self, enabled: bool) -> Self {
+        self.trim = enabled;
+        self
+    }
+
+    /// Applies `normalization` to each name before comparing it. Intended for Unicode
+    /// normalization, e.g. using the `unicode-normalization` crate, so composed and decomposed
+    /// forms of the same character match. Applied after trimming, but before case folding.
+    ///
+    /// ```
+    /// use odbc_api::ColumnNameMatcher;
+    ///
+    /// // Ignore underscores, so `customer_id` matches `CustomerId`.
+    /// let matcher = ColumnNameMatcher::default().normalization(Some(|name| name.replace('_', "")));
+    /// assert!(matcher.matches("customer_id", "CustomerId"));
+    /// ```
+    pub fn normalization(mut self, normalization: Option<fn(&str) -> String>) -> Self {
+        self.normalization = normalization;
+        self
+    }
+
+    /// The form of `name` used for comparisons. Two names match, if their keys are equal. Useful
+    /// to build maps indexed by column name.
+    pub fn key<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        let mut key = Cow::Borrowed(if self.trim { name.trim() } else { name });
+        if let Some(normalization) = self.normalization {
+            key = Cow::Owned(normalization(&key));
+        }
+        if self.case_insensitive && key.chars().any(char::is_uppercase) {
+            key = Cow::Owned(key.to_lowercase());
+        }
+        key
+    }
+
+    /// `true` if `left` and `right` refer to the same column.
+    pub fn matches(&self, left: &str, right: &str) -> bool {
+        self.key(left) == self.key(right)
+    }
+
+    /// Index of the first name in `names` matching `name`.
+    pub fn position<I>(&self, names: I, name: &str) -> Option<usize>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let key = self.key(name);
+        names
+            .into_iter()
+            .position(|candidate| self.key(candidate.as_ref()) == key)
+    }
+}
+
+impl Default for ColumnNameMatcher {
+    fn default() -> Self {
+        Self {
+            case_insensitive: true,
+            trim: true,
+            normalization: None,
+        }
+    }
+}
+
+impl PartialEq for ColumnNameMatcher {
+    fn eq(&self, other: &Self) -> bool {
+        // Function pointers are compared by address.
+        let address = |normalization: Option<fn(&str) -> String>| normalization.map(|f| f as usize);
+        self.case_insensitive == other.case_insensitive
+            && self.trim == other.trim
+            && address(self.normalization) == address(other.normalization)
+    }
+}
+
+impl Eq for ColumnNameMatcher {}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::ColumnNameMatcher;
+
+    #[test]
+    fn key_borrows_if_nothing_changes() {
+        let matcher = ColumnNameMatcher::default();
+
+        assert!(matches!(matcher.key(" name "), Cow::Borrowed("name")));
+        assert_eq!("name", matcher.key("NAME"));
+    }
+
+    #[test]
+    fn fold_unicode_case() {
+        let matcher = ColumnNameMatcher::default();
+
+        assert!(matcher.matches("GRÖSSE", "grösse"));
+        assert!(!ColumnNameMatcher::exact().matches("GRÖSSE", "grösse"));
+    }
+
+    #[test]
+    fn trimming_is_optional() {
+        let matcher = ColumnNameMatcher::default().trim(false);
+
+        assert!(!matcher.matches("name ", "name"));
+        assert_eq!(None, matcher.position(["id", "name "], "name"));
+        assert_eq!(
+            Some(1),
+            ColumnNameMatcher::default().position(["id", "name "], "name")
+        );
+    }
+}
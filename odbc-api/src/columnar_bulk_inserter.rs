@@ -1,7 +1,7 @@
 use crate::{
-    buffers::{ColumnBuffer, TextColumn},
-    execute::execute,
-    handles::{AsStatementRef, HasDataType, Statement, StatementRef},
+    buffers::{BinColumn, ColumnBuffer, GrowableColumn, GrowthPolicy, TextColumn},
+    execute::{execute, row_count},
+    handles::{AsStatementRef, CData, HasDataType, Statement, StatementRef},
     CursorImpl, Error,
 };
 
@@ -21,6 +21,8 @@ pub struct ColumnarBulkInserter<S, C> {
     parameter_set_size: usize,
     capacity: usize,
     parameters: Vec<C>,
+    /// Used by `append` to resize text and binary columns too small for a value.
+    growth_policy: GrowthPolicy,
 }
 
 impl<S, C> ColumnarBulkInserter<S, C>
@@ -69,6 +71,7 @@ where
             parameter_set_size: 0,
             capacity,
             parameters,
+            growth_policy: GrowthPolicy::default(),
         })
     }
 
@@ -89,6 +92,37 @@ where
         }
     }
 
+    /// Number of rows affected by the last call to [`Self::execute`]. For bulk inserts this is the
+    /// total number of rows inserted over all parameter sets of the batch. May return `None` if row
+    /// count is not available.
+    pub fn row_count(&mut self) -> Result<Option<usize>, Error> {
+        row_count(&self.statement.as_stmt_ref())
+    }
+
+    /// Decides how text and binary column buffers grow, should `append` encounter a value too
+    /// large for them. Default is [`GrowthPolicy::Exact`]. Since each growth reallocates and
+    /// rebinds the entire column, a more generous policy speeds up inserting values of steadily
+    /// increasing length. [`GrowthPolicy::Fail`] rejects such values instead, and leaves the
+    /// buffers as they are.
+    ///
+    /// ```
+    /// use odbc_api::{buffers::GrowthPolicy, Connection, Error};
+    ///
+    /// fn insert_names(conn: &Connection<'_>, names: &[&str]) -> Result<(), Error> {
+    ///     let prepared = conn.prepare("INSERT INTO Names (name) VALUES (?)")?;
+    ///     let mut inserter = prepared.into_text_inserter(names.len(), [50])?;
+    ///     inserter.set_growth_policy(GrowthPolicy::Exponential);
+    ///     for name in names {
+    ///         inserter.append([Some(name.as_bytes())].into_iter())?;
+    ///     }
+    ///     inserter.execute()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_growth_policy(&mut self, policy: GrowthPolicy) {
+        self.growth_policy = policy;
+    }
+
     /// Sets the number of rows in the buffer to zero.
     pub fn clear(&mut self) {
         self.parameter_set_size = 0;
@@ -199,6 +233,11 @@ where
     pub fn capacity(&self) -> usize {
         self.capacity
     }
+
+    /// Number of parameter buffers bound to the statement.
+    pub fn num_columns(&self) -> usize {
+        self.parameters.len()
+    }
 }
 
 /// You can obtain a mutable slice of a column buffer which allows you to change its contents.
@@ -227,18 +266,93 @@ pub unsafe trait BoundInputSlice<'a> {
     ) -> Self::SliceMut;
 }
 
+impl<S, C> ColumnarBulkInserter<S, C>
+where
+    S: AsStatementRef,
+    C: GrowableColumn + HasDataType + CData,
+{
+    /// Grows the text and binary columns, so they can hold elements of the estimated sizes without
+    /// reallocating during subsequent appends. Call this upfront, if the lengths of the values to
+    /// insert are known, or can be estimated from a sample. All columns which need to grow are
+    /// reallocated and rebound in a single pass, preserving the values already in the buffer.
+    /// Columns which are already large enough, or hold elements of fixed size, are left untouched.
+    /// The number of rows the buffer can hold is determined upon construction and does not change.
+    ///
+    /// # Parameters
+    ///
+    /// * `estimated_sizes`: Maximum element length for each column, in the order the columns are
+    ///   bound. `None` leaves the column as it is. For text columns the length is in characters,
+    ///   excluding the terminating zero, for binary columns in bytes.
+    ///
+    /// ```
+    /// use odbc_api::{Connection, Error};
+    ///
+    /// fn insert_articles(conn: &Connection<'_>, articles: &[(&str, &str)]) -> Result<(), Error> {
+    ///     let prepared = conn.prepare("INSERT INTO Articles (title, body) VALUES (?, ?)")?;
+    ///     let mut inserter = prepared.into_text_inserter(articles.len(), [50, 50])?;
+    ///     let max_title = articles.iter().map(|(title, _)| title.len()).max();
+    ///     let max_body = articles.iter().map(|(_, body)| body.len()).max();
+    ///     inserter.reserve_for([max_title, max_body])?;
+    ///     for (title, body) in articles {
+    ///         inserter.append([Some(title.as_bytes()), Some(body.as_bytes())].into_iter())?;
+    ///     }
+    ///     inserter.execute()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn reserve_for(
+        &mut self,
+        estimated_sizes: impl IntoIterator<Item = Option<usize>>,
+    ) -> Result<(), Error> {
+        self.grow_columns(estimated_sizes, GrowthPolicy::Exact)
+    }
+
+    /// Grows each column to hold an element of the required length, according to `policy`. The
+    /// new lengths of all columns are determined first, so the buffer stays untouched should the
+    /// policy forbid any of the columns to grow.
+    fn grow_columns(
+        &mut self,
+        required_lengths: impl IntoIterator<Item = Option<usize>>,
+        policy: GrowthPolicy,
+    ) -> Result<(), Error> {
+        let mut new_max_lens = Vec::new();
+        for (index, (column, required)) in self.parameters.iter().zip(required_lengths).enumerate()
+        {
+            let (Some(max_len), Some(required)) = (column.max_element_len(), required) else {
+                continue;
+            };
+            if required > max_len {
+                let new_max_len = policy
+                    .new_max_len(max_len, required)
+                    .ok_or(Error::TooLargeValueForBuffer)?;
+                new_max_lens.push((index, new_max_len));
+            }
+        }
+        // Growing invalidates the pointers bound to the statement, so we rebind the columns.
+        let mut stmt = self.statement.as_stmt_ref();
+        for (index, new_max_len) in new_max_lens {
+            let column = &mut self.parameters[index];
+            column.resize_max_element_len(new_max_len, self.parameter_set_size);
+            let parameter_number = (index + 1).try_into().unwrap();
+            unsafe {
+                stmt.bind_input_parameter(parameter_number, column)
+                    .into_result(&stmt)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 impl<S> ColumnarBulkInserter<S, TextColumn<u8>> {
     /// Takes one element from the iterator for each internal column buffer and appends it to the
-    /// end of the buffer. Should a cell of the row be too large for the associated column buffer,
-    /// the column buffer will be reallocated with `1.2` times its size, and rebound to the
-    /// statement.
+    /// end of the buffer. Should cells of the row be too large for their column buffers, these
+    /// column buffers will be reallocated according to the growth policy (see
+    /// [`Self::set_growth_policy`]), and rebound to the statement. Should the policy forbid
+    /// growing, the row is not appended.
     ///
     /// This method panics if it is tried to insert elements beyond batch size. It will also panic
     /// if row does not contain at least one item for each internal column buffer.
-    pub fn append<'b>(
-        &mut self,
-        mut row: impl Iterator<Item = Option<&'b [u8]>>,
-    ) -> Result<(), Error>
+    pub fn append<'b>(&mut self, row: impl Iterator<Item = Option<&'b [u8]>>) -> Result<(), Error>
     where
         S: AsStatementRef,
     {
@@ -246,22 +360,54 @@ impl<S> ColumnarBulkInserter<S, TextColumn<u8>> {
             panic!("Trying to insert elements into TextRowSet beyond batch size.")
         }
 
-        let mut col_index = 1;
-        for column in &mut self.parameters {
-            let text = row.next().expect(
-                "Row passed to TextRowSet::append must contain one element for each column.",
-            );
-            if let Some(text) = text {
-                unsafe {
-                    column
-                        .as_view_mut(col_index, self.statement.as_stmt_ref())
-                        .ensure_max_element_length(text.len(), self.parameter_set_size)?;
-                }
-                column.set_value(self.parameter_set_size, Some(text));
-            } else {
-                column.set_value(self.parameter_set_size, None);
-            }
-            col_index += 1;
+        let row: Vec<_> = row.take(self.parameters.len()).collect();
+        if row.len() < self.parameters.len() {
+            panic!("Row passed to TextRowSet::append must contain one element for each column.")
+        }
+        self.grow_columns(
+            row.iter().map(|text| text.map(<[u8]>::len)),
+            self.growth_policy,
+        )?;
+        for (column, text) in self.parameters.iter_mut().zip(row) {
+            column.set_value(self.parameter_set_size, text);
+        }
+
+        self.parameter_set_size += 1;
+
+        Ok(())
+    }
+}
+
+impl<S> ColumnarBulkInserter<S, BinColumn> {
+    /// Takes one element from the iterator for each internal column buffer and appends it to the
+    /// end of the buffer. Should cells of the row be too large for their column buffers, these
+    /// column buffers will be reallocated according to the growth policy (see
+    /// [`Self::set_growth_policy`]), and rebound to the statement. Should the policy forbid
+    /// growing, the row is not appended.
+    ///
+    /// This method panics if it is tried to insert elements beyond batch size. It will also panic
+    /// if row does not contain at least one item for each internal column buffer.
+    pub fn append<'b>(&mut self, row: impl Iterator<Item = Option<&'b [u8]>>) -> Result<(), Error>
+    where
+        S: AsStatementRef,
+    {
+        if self.capacity == self.parameter_set_size {
+            panic!("Trying to insert elements into binary buffers beyond batch size.")
+        }
+
+        let row: Vec<_> = row.take(self.parameters.len()).collect();
+        if row.len() < self.parameters.len() {
+            panic!(
+                "Row passed to ColumnarBulkInserter::append must contain one element for each \
+                column."
+            )
+        }
+        self.grow_columns(
+            row.iter().map(|bytes| bytes.map(<[u8]>::len)),
+            self.growth_policy,
+        )?;
+        for (column, bytes) in self.parameters.iter_mut().zip(row) {
+            column.set_value(self.parameter_set_size, bytes);
         }
 
         self.parameter_set_size += 1;
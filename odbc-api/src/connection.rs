@@ -1,20 +1,34 @@
 use crate::{
+    batch::{execute_and_collect, execute_batch, execute_script},
     buffers::BufferDesc,
     execute::{
-        execute_columns, execute_tables, execute_with_parameters, execute_with_parameters_polling,
+        execute_columns, execute_primary_keys, execute_tables, execute_with_parameters,
+        execute_with_parameters_polling,
     },
     handles::{self, slice_to_utf8, SqlText, State, Statement, StatementImpl},
+    insert_rows::{insert_rows, upsert_many},
+    preview::preview,
+    procedure::{call, ProcedureCall},
+    query_log::{QueryLog, SharedQueryLogger},
+    sql_check::check_sql,
     statement_connection::StatementConnection,
-    CursorImpl, CursorPolling, Error, ParameterCollectionRef, Preallocated, Prepared, Sleep,
+    statement_pool::{PooledStatement, StatementPool},
+    BatchMode, ColumnNameMatcher, CursorImpl, CursorPolling, DbmsDialect, Error, Extensions,
+    GetDataExtensions, InsertRowsError, InsertRowsOptions, Odbc38Features, ParameterCollectionRef,
+    Preallocated, Prepared, Preview, QueryLogger, Quirks, ScriptDelimiter, ScriptError, Sleep,
+    SqlCheck, SqlDialect, StatementOutcome, TextRecord, Upsert, UpsertSummary, UpsertSyntax,
 };
+use log::debug;
 use odbc_sys::HDbc;
-use std::{borrow::Cow, mem::ManuallyDrop, str, thread::panicking};
+use std::{borrow::Cow, cell::Cell, mem::ManuallyDrop, str, sync::Arc, thread::panicking};
 
 #[allow(deprecated)]
 use crate::buffers::{BufferDescription, BufferKind};
 
 impl<'conn> Drop for Connection<'conn> {
     fn drop(&mut self) {
+        // Free pooled statement handles before disconnecting, in order to not free them twice.
+        self.statement_pool.clear();
         match self.connection.disconnect().into_result(&self.connection) {
             Ok(()) => (),
             Err(Error::Diagnostics {
@@ -60,15 +74,31 @@ impl<'conn> Drop for Connection<'conn> {
 /// look at [`crate::Environment::set_connection_pooling`].
 pub struct Connection<'c> {
     connection: handles::Connection<'c>,
+    /// Idle statement handles which can be reused by [`Self::checkout_statement`].
+    statement_pool: StatementPool,
+    /// Quirks of the driver. `None` until determined by the first call to [`Self::quirks`].
+    quirks: Cell<Option<Quirks>>,
+    /// Informed about each statement executed on this connection. Shared with the statements
+    /// allocated on it.
+    query_logger: Option<SharedQueryLogger>,
+    /// State attached by layered libraries. See [`Self::extensions`].
+    extensions: Extensions,
 }
 
 impl<'c> Connection<'c> {
     pub(crate) fn new(connection: handles::Connection<'c>) -> Self {
-        Self { connection }
+        Self {
+            connection,
+            statement_pool: StatementPool::default(),
+            quirks: Cell::new(None),
+            query_logger: None,
+            extensions: Extensions::new(),
+        }
     }
 
     /// Transfers ownership of the handle to this open connection to the raw ODBC pointer.
     pub fn into_sys(self) -> HDbc {
+        self.statement_pool.clear();
         // We do not want to run the drop handler, but transfer ownership instead.
         ManuallyDrop::new(self).connection.as_sys()
     }
@@ -81,6 +111,7 @@ impl<'c> Connection<'c> {
     /// but, in case it is not, this may help you to break out of the type structure which might be
     /// to rigid for you, while simultaniously abondoning its safeguards.
     pub fn into_handle(self) -> handles::Connection<'c> {
+        self.statement_pool.clear();
         unsafe { handles::Connection::new(ManuallyDrop::new(self).connection.as_sys()) }
     }
 
@@ -118,9 +149,484 @@ impl<'c> Connection<'c> {
         query: &str,
         params: impl ParameterCollectionRef,
     ) -> Result<Option<CursorImpl<StatementImpl<'_>>>, Error> {
-        let query = SqlText::new(query);
+        let sql = SqlText::new(query);
         let lazy_statement = move || self.allocate_statement();
-        execute_with_parameters(lazy_statement, Some(&query), params)
+        let log = QueryLog::new(self.query_logger.as_ref(), query);
+        let cursor = execute_with_parameters(lazy_statement, Some(&sql), params, log)?;
+        Ok(cursor.map(|cursor| cursor.with_quirks(self.quirks_or_default())))
+    }
+
+    /// Executes an SQL statement and returns the number of rows affected by it. This is intended
+    /// for `INSERT`, `UPDATE` or `DELETE` statements, there [`Self::execute`] would return `None`
+    /// and discard the statement handle, which would be required to ask for the row count. Should
+    /// the statement create a result set it is closed without being fetched.
+    ///
+    /// # Parameters
+    ///
+    /// * `query`: The text representation of the SQL statement. E.g. "DELETE FROM my_table;".
+    /// * `params`: `?` may be used as a placeholder in the statement text. You can use `()` to
+    ///   represent no parameters. See the [`crate::parameter`] module level documentation for more
+    ///   information on how to pass parameters.
+    ///
+    /// # Return
+    ///
+    /// Number of rows affected by the statement. `None` if the row count is not available.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use odbc_api::Environment;
+    ///
+    /// let env = Environment::new()?;
+    ///
+    /// let conn = env.connect("YourDatabase", "SA", "My@Test@Password1")?;
+    /// let num_deleted = conn.execute_row_count("DELETE FROM Birthdays WHERE year < ?;", &1900)?;
+    /// if let Some(num_deleted) = num_deleted {
+    ///     println!("Deleted {num_deleted} birthdays.");
+    /// }
+    /// # Ok::<(), odbc_api::Error>(())
+    /// ```
+    pub fn execute_row_count(
+        &self,
+        query: &str,
+        params: impl ParameterCollectionRef,
+    ) -> Result<Option<usize>, Error> {
+        let mut statement = self.preallocate()?;
+        // A cursor, should one have been created, is dropped and closed immediately.
+        statement.execute(query, params)?;
+        statement.row_count()
+    }
+
+    /// Executes an SQL statement and passes the cursor to `f`, rather than returning it. The
+    /// statement, and with it any cursor, is freed before this method returns, so the parameters
+    /// are only required to outlive this call. Some drivers read parameters not only when the
+    /// statement is executed, but also while results are fetched, e.g. if parameter arrays produce
+    /// more than one result set. Since the cursor can not escape `f`, borrowed parameters like
+    /// `&str` or `&[u8]` are safe to use in that case, too, and large values need not be copied
+    /// into owned parameters.
+    ///
+    /// ```no_run
+    /// use odbc_api::{Connection, Cursor, Error, IntoParameter};
+    ///
+    /// fn count_matches(conn: &Connection<'_>, document: &str) -> Result<usize, Error> {
+    ///     conn.execute_scoped(
+    ///         "SELECT id FROM Documents WHERE content = ?",
+    ///         &document.into_parameter(),
+    ///         |cursor| {
+    ///             let mut num_matches = 0;
+    ///             if let Some(cursor) = cursor {
+    ///                 while cursor.next_row()?.is_some() {
+    ///                     num_matches += 1;
+    ///                 }
+    ///             }
+    ///             Ok(num_matches)
+    ///         },
+    ///     )
+    /// }
+    /// ```
+    pub fn execute_scoped<R>(
+        &self,
+        query: &str,
+        params: impl ParameterCollectionRef,
+        f: impl FnOnce(Option<&mut CursorImpl<StatementImpl<'_>>>) -> Result<R, Error>,
+    ) -> Result<R, Error> {
+        let mut cursor = self.execute(query, params)?;
+        f(cursor.as_mut())
+    }
+
+    /// Installs a hook, which is informed about every statement executed on this connection,
+    /// together with a preview of its parameters and the time it took to execute it. This includes
+    /// statements executed with [`Self::execute`], [`Self::execute_polling`], as well as
+    /// [`Prepared`] and [`Preallocated`] statements allocated after the logger has been installed.
+    /// Wrap sensitive parameters into [`crate::Redacted`] to keep their values out of the log. Pass
+    /// `None` to remove the logger.
+    ///
+    /// ```no_run
+    /// use odbc_api::{Environment, QueryLogEntry};
+    ///
+    /// let env = Environment::new()?;
+    /// let mut conn = env.connect("YourDatabase", "SA", "My@Test@Password1")?;
+    /// conn.set_query_logger(Some(Box::new(|entry: &QueryLogEntry| log::info!("{entry}"))));
+    /// # Ok::<(), odbc_api::Error>(())
+    /// ```
+    pub fn set_query_logger(&mut self, logger: Option<Box<dyn QueryLogger>>) {
+        self.query_logger = logger.map(Arc::from);
+    }
+
+    /// Executes a script containing multiple SQL statements, separated by `;`. In contrast to
+    /// [`Self::execute`] this reports the outcome of every statement in the script, rather than
+    /// only the first one. Any result sets created by statements in the script are discarded
+    /// without fetching them.
+    ///
+    /// # Parameters
+    ///
+    /// * `script`: The text of the SQL statements. E.g. "DELETE FROM a; INSERT INTO a VALUES (1);".
+    /// * `mode`: Wether to let the driver execute the script as one batch, or to split it into
+    ///   individual statements on the client side. See [`BatchMode`].
+    ///
+    /// # Return
+    ///
+    /// One outcome per result reported by the data source. Using [`BatchMode::Split`] this is
+    /// usually one per statement. Using [`BatchMode::Driver`], the mapping of statements to
+    /// results is up to the driver.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use odbc_api::{BatchMode, Environment, StatementOutcome};
+    ///
+    /// let env = Environment::new()?;
+    ///
+    /// let conn = env.connect("YourDatabase", "SA", "My@Test@Password1")?;
+    /// let outcomes = conn.execute_batch(
+    ///     "DELETE FROM Birthdays; INSERT INTO Birthdays (name, year) VALUES ('Bernd', 1980);",
+    ///     BatchMode::Split,
+    /// )?;
+    /// for outcome in outcomes {
+    ///     if let StatementOutcome::RowCount(Some(count)) = outcome {
+    ///         println!("{count} rows affected.");
+    ///     }
+    /// }
+    /// # Ok::<(), odbc_api::Error>(())
+    /// ```
+    pub fn execute_batch(
+        &self,
+        script: &str,
+        mode: BatchMode,
+    ) -> Result<Vec<StatementOutcome>, Error> {
+        let mut statement = self.allocate_statement()?;
+        execute_batch(&mut statement, script, mode)
+    }
+
+    /// Executes the statements of a script one after another, e.g. to apply a migration. The
+    /// script is split at each `delimiter` using [`crate::split_sql_script`], which keeps string
+    /// literals, quoted identifiers and comments intact. Any result sets created by statements in
+    /// the script are discarded without fetching them.
+    ///
+    /// Execution stops at the first statement which fails. The returned [`ScriptError`] tells which
+    /// one and in which line of the script it starts. Should no statement handle be allocated in
+    /// the first place, the error points to the first line of the script.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use odbc_api::{Environment, ScriptDelimiter};
+    ///
+    /// let env = Environment::new()?;
+    ///
+    /// let conn = env.connect("YourDatabase", "SA", "My@Test@Password1")?;
+    /// let script = "CREATE TABLE Birthdays (name VARCHAR(50), year INT)\n\
+    ///     GO\n\
+    ///     INSERT INTO Birthdays (name, year) VALUES ('Bernd', 1980)\n\
+    ///     GO\n";
+    /// if let Err(error) = conn.execute_script(script, &ScriptDelimiter::Line("GO".to_owned())) {
+    ///     eprintln!("Migration failed in line {}: {}", error.line, error.source);
+    /// }
+    /// # Ok::<(), odbc_api::Error>(())
+    /// ```
+    pub fn execute_script(
+        &self,
+        script: &str,
+        delimiter: &ScriptDelimiter,
+    ) -> Result<Vec<StatementOutcome>, ScriptError> {
+        let mut statement = self.allocate_statement().map_err(|source| ScriptError {
+            statement_index: 0,
+            line: 1,
+            statement: String::new(),
+            outcomes: Vec::new(),
+            source,
+        })?;
+        execute_script(&mut statement, script, delimiter)
+    }
+
+    /// Executes a single DDL statement, like `CREATE TABLE` or `ALTER INDEX`. Intended for
+    /// migrations, which should behave the same regardless of the driver:
+    ///
+    /// * The statement is always executed directly (`SQLExecDirect`) and never prepared, since some
+    ///   drivers can not prepare DDL, or execute it already during preparation.
+    /// * Drivers report the outcome of DDL inconsistently. Some return `SQL_NO_DATA` (e.g. for
+    ///   `DROP TABLE IF EXISTS` on a missing table), some a row count of `0` and others `-1`, some
+    ///   even create empty result sets. All of these are treated as success and any result sets
+    ///   are discarded, so `Ok(())` is returned in each case.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use odbc_api::Environment;
+    ///
+    /// let env = Environment::new()?;
+    ///
+    /// let conn = env.connect("YourDatabase", "SA", "My@Test@Password1")?;
+    /// conn.execute_ddl("DROP TABLE IF EXISTS Birthdays")?;
+    /// conn.execute_ddl("CREATE TABLE Birthdays (name VARCHAR(50), year INT)")?;
+    /// # Ok::<(), odbc_api::Error>(())
+    /// ```
+    pub fn execute_ddl(&self, sql: &str) -> Result<(), Error> {
+        let mut statement = self.allocate_statement()?;
+        // We are only interested in whether the statement succeeded, not in its outcome.
+        let mut outcomes = Vec::new();
+        execute_and_collect(&mut statement, sql, &mut outcomes)
+    }
+
+    /// Inserts rows of text into `table_name`. Prepares the `INSERT` statement (see
+    /// [`crate::insert_statement`]) and sends the rows in chunks using array parameters. Chunks are
+    /// bounded both by number of rows and by the memory required to hold them (see
+    /// [`InsertRowsOptions`]). The parameter buffers grow to fit the longest value of each column.
+    ///
+    /// # Parameters
+    ///
+    /// * `table_name`: Name of the target table, optionally qualified with a schema.
+    /// * `column_names`: Columns of the target table the values of each row are inserted into.
+    /// * `rows`: Each row holds one value for each column. `None` is inserted as `NULL`. The values
+    ///   are converted by the data source, so they must be formatted in a way it understands.
+    /// * `dialect`: Used to quote the column names.
+    ///
+    /// # Return
+    ///
+    /// The number of inserted rows. In case of an error, the returned [`InsertRowsError`] tells how
+    /// many rows have been sent and committed before, so the insertion can be resumed with the
+    /// remaining rows.
+    ///
+    /// # Panics
+    ///
+    /// If `column_names` is empty, or a row does not hold exactly one value for each column.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use odbc_api::{AnsiDialect, Connection, InsertRowsError, InsertRowsOptions};
+    ///
+    /// fn insert_birthdays(conn: &Connection<'_>) -> Result<usize, InsertRowsError> {
+    ///     let rows = [
+    ///         [Some("Keanu Reeves"), Some("1964-09-02")],
+    ///         [Some("Brad Pitt"), None],
+    ///     ];
+    ///     conn.insert_rows(
+    ///         "Birthdays",
+    ///         &["name", "birthday"],
+    ///         rows,
+    ///         &AnsiDialect,
+    ///         &InsertRowsOptions::default(),
+    ///     )
+    /// }
+    /// ```
+    pub fn insert_rows<R, V>(
+        &self,
+        table_name: &str,
+        column_names: &[&str],
+        rows: impl IntoIterator<Item = R>,
+        dialect: &impl SqlDialect,
+        options: &InsertRowsOptions,
+    ) -> Result<usize, InsertRowsError>
+    where
+        R: IntoIterator<Item = Option<V>>,
+        V: AsRef<[u8]>,
+    {
+        insert_rows(self, table_name, column_names, rows, dialect, options)
+    }
+
+    /// Prepares an upsert statement, using the syntax supported by the connected data source (see
+    /// [`UpsertSyntax::from_dbms_name`]). Bind a columnar buffer holding the key columns, followed
+    /// by the value columns to insert the rows.
+    ///
+    /// ```no_run
+    /// use odbc_api::{buffers::BufferDesc, AnsiDialect, Connection, Error, Upsert};
+    ///
+    /// fn upsert_birth_years(
+    ///     conn: &Connection<'_>,
+    ///     names: &[&str],
+    ///     years: &[i16],
+    /// ) -> Result<(), Error> {
+    ///     let upsert = Upsert::new("Birthdays")
+    ///         .key_columns(&["name"])
+    ///         .value_columns(&["year"]);
+    ///     let prepared = conn.prepare_upsert(&upsert, &AnsiDialect)?;
+    ///     let descs = [
+    ///         BufferDesc::Text { max_str_len: 255 },
+    ///         BufferDesc::I16 { nullable: false },
+    ///     ];
+    ///     let mut inserter = prepared.into_column_inserter(names.len(), descs)?;
+    ///     inserter.set_num_rows(names.len());
+    ///     let mut name_col = inserter.column_mut(0).as_text_view().unwrap();
+    ///     for (index, name) in names.iter().enumerate() {
+    ///         name_col.set_cell(index, Some(name.as_bytes()));
+    ///     }
+    ///     let year_col = inserter.column_mut(1).as_slice::<i16>().unwrap();
+    ///     year_col.copy_from_slice(years);
+    ///     inserter.execute()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn prepare_upsert(
+        &self,
+        upsert: &Upsert,
+        dialect: &impl SqlDialect,
+    ) -> Result<Prepared<StatementImpl<'_>>, Error> {
+        let syntax = UpsertSyntax::from_dbms_name(&self.database_management_system_name()?);
+        self.prepare(&upsert.to_sql(syntax, dialect))
+    }
+
+    /// Inserts `records` into `table_name`, or updates the rows with the same key, if they already
+    /// exist. Each field of the record is a column. The records are sent as text in chunks using
+    /// array parameters, just like [`Self::insert_rows`] does.
+    ///
+    /// A failing chunk does not stop the upsert. Since upserts can be repeated safely, the failed
+    /// chunks are reported in the returned [`UpsertSummary`], so they can be retried. Errors which
+    /// prevent sending any further chunk, e.g. failing to prepare the statement or to commit, are
+    /// reported as [`InsertRowsError`].
+    ///
+    /// # Parameters
+    ///
+    /// * `table_name`: Name of the target table, optionally qualified with a schema.
+    /// * `key_columns`: Columns identifying the row to update. Must be fields of the record.
+    /// * `records`: Rows to upsert. See [`TextRecord`], which can be derived using the `derive`
+    ///   feature.
+    /// * `dialect`: Used to quote the column names.
+    /// * `options`: Bounds the size of the chunks and controls periodic commits.
+    ///
+    /// # Panics
+    ///
+    /// If a key column is not a field of the record.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use odbc_api::{
+    ///     AnsiDialect, Connection, InsertRowsError, InsertRowsOptions, TextRecord, ToSqlText,
+    /// };
+    ///
+    /// struct Birthday {
+    ///     name: String,
+    ///     year: i16,
+    /// }
+    ///
+    /// impl TextRecord for Birthday {
+    ///     fn column_names() -> &'static [&'static str] {
+    ///         &["name", "year"]
+    ///     }
+    ///
+    ///     fn text_values(&self) -> Vec<Option<String>> {
+    ///         vec![self.name.to_sql_text(), self.year.to_sql_text()]
+    ///     }
+    /// }
+    ///
+    /// fn save(conn: &Connection<'_>, birthdays: &[Birthday]) -> Result<(), InsertRowsError> {
+    ///     let summary = conn.upsert_many(
+    ///         "Birthdays",
+    ///         &["name"],
+    ///         birthdays,
+    ///         &AnsiDialect,
+    ///         &InsertRowsOptions::default(),
+    ///     )?;
+    ///     for chunk in summary.failed_chunks {
+    ///         let end = chunk.first_row + chunk.num_rows;
+    ///         eprintln!("Rows {} to {end} failed: {}", chunk.first_row, chunk.source);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn upsert_many<T>(
+        &self,
+        table_name: &str,
+        key_columns: &[&str],
+        records: impl IntoIterator<Item = T>,
+        dialect: &impl SqlDialect,
+        options: &InsertRowsOptions,
+    ) -> Result<UpsertSummary, InsertRowsError>
+    where
+        T: TextRecord,
+    {
+        upsert_many(self, table_name, key_columns, records, dialect, options)
+    }
+
+    /// Executes a query and fetches up to `max_rows` rows of its result set as text. Useful to
+    /// show the first rows of a table or query, e.g. in command line or notebook tools. Returns
+    /// `None` if the statement does not produce a result set.
+    ///
+    /// The number of rows is limited using `SQL_ATTR_MAX_ROWS`, so drivers may avoid
+    /// materializing the entire result set. Values longer than 4096 bytes are truncated.
+    ///
+    /// ```no_run
+    /// use odbc_api::{Connection, Error};
+    ///
+    /// fn head(conn: &Connection<'_>, table: &str) -> Result<(), Error> {
+    ///     if let Some(preview) = conn.preview(&format!("SELECT * FROM {table}"), 10)? {
+    ///         println!("{}", preview.column_names.join(","));
+    ///         for row in preview.rows {
+    ///             let row: Vec<_> = row.iter().map(|v| v.as_deref().unwrap_or("NULL")).collect();
+    ///             println!("{}", row.join(","));
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn preview(&self, query: &str, max_rows: usize) -> Result<Option<Preview>, Error> {
+        preview(self, query, max_rows)
+    }
+
+    /// Calls a stored procedure. Assembles the ODBC call escape sequence (e.g.
+    /// `{call my_schema.my_proc(?, ?)}`) with one placeholder for each parameter in `params`.
+    ///
+    /// # Parameters
+    ///
+    /// * `procedure`: Name of the procedure, optionally qualified with schema and catalog. E.g.
+    ///   `"my_schema.my_proc"`.
+    /// * `params`: Arguments of the procedure. Use [`crate::Out`] and [`crate::InOut`] to bind
+    ///   output parameters. You can use `()` to represent no parameters.
+    ///
+    /// # Return
+    ///
+    /// Gives access to the result sets returned by the procedure. Output parameters are only valid
+    /// after calling [`ProcedureCall::finish`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use odbc_api::{Connection, Cursor, Error, Nullable, Out};
+    ///
+    /// fn count_birthdays(conn: &Connection<'_>, year: i32) -> Result<Option<i32>, Error> {
+    ///     let mut count = Nullable::<i32>::null();
+    ///     let mut call = conn.call("count_birthdays", (&year, Out(&mut count)))?;
+    ///     while let Some(mut result_set) = call.next_result_set()? {
+    ///         while let Some(_row) = result_set.next_row()? {
+    ///             // Process the rows returned by the procedure
+    ///         }
+    ///     }
+    ///     call.finish()?;
+    ///     Ok(count.into_opt())
+    /// }
+    /// ```
+    pub fn call<P>(&self, procedure: &str, params: P) -> Result<ProcedureCall<'_, P>, Error>
+    where
+        P: ParameterCollectionRef,
+    {
+        let statement = self.allocate_statement()?;
+        call(statement, procedure, false, params)
+    }
+
+    /// Same as [`Self::call`], but binds the first element of `params` to the return value of the
+    /// procedure, using the `{? = call ...}` escape sequence. The first parameter should therefore
+    /// be an [`crate::Out`] parameter.
+    ///
+    /// ```no_run
+    /// use odbc_api::{Connection, Error, Nullable, Out};
+    ///
+    /// fn status(conn: &Connection<'_>) -> Result<Option<i32>, Error> {
+    ///     let mut status = Nullable::<i32>::null();
+    ///     conn.call_with_return_value("check_health", (Out(&mut status),))?.finish()?;
+    ///     Ok(status.into_opt())
+    /// }
+    /// ```
+    pub fn call_with_return_value<P>(
+        &self,
+        procedure: &str,
+        params: P,
+    ) -> Result<ProcedureCall<'_, P>, Error>
+    where
+        P: ParameterCollectionRef,
+    {
+        let statement = self.allocate_statement()?;
+        call(statement, procedure, true, params)
     }
 
     /// Asynchronous sibling of [`Self::execute`]. Uses polling mode to be asynchronous. `sleep`
@@ -152,9 +658,10 @@ impl<'c> Connection<'c> {
         params: impl ParameterCollectionRef,
         sleep: impl Sleep,
     ) -> Result<Option<CursorPolling<StatementImpl<'_>>>, Error> {
-        let query = SqlText::new(query);
+        let sql = SqlText::new(query);
         let lazy_statement = move || self.allocate_statement();
-        execute_with_parameters_polling(lazy_statement, Some(&query), params, sleep).await
+        let log = QueryLog::new(self.query_logger.as_ref(), query);
+        execute_with_parameters_polling(lazy_statement, Some(&sql), params, sleep, log).await
     }
 
     /// In some use cases there you only execute a single statement, or the time to open a
@@ -240,10 +747,34 @@ impl<'c> Connection<'c> {
     ///   may be used as a placeholder in the statement text, to be replaced with parameters during
     ///   execution.
     pub fn prepare(&self, query: &str) -> Result<Prepared<StatementImpl<'_>>, Error> {
-        let query = SqlText::new(query);
+        let sql = SqlText::new(query);
         let mut stmt = self.allocate_statement()?;
-        stmt.prepare(&query).into_result(&stmt)?;
-        Ok(Prepared::new(stmt))
+        stmt.prepare(&sql).into_result(&stmt)?;
+        Ok(Prepared::new(stmt)
+            .with_quirks(self.quirks_or_default())
+            .with_query_logger(self.query_logger.clone(), query))
+    }
+
+    /// Validates `sql` against the data source without executing it. The statement is prepared and
+    /// the number of its parameters, as well as a description of its result set, is returned.
+    /// Syntax errors, or references to tables and columns which do not exist, are reported as
+    /// errors, if the driver checks them during preparation. Useful for linting or migration tools
+    /// validating generated SQL against a live schema.
+    ///
+    /// ```no_run
+    /// use odbc_api::{Connection, Error};
+    ///
+    /// fn lint(conn: &Connection<'_>, sql: &str) -> Result<(), Error> {
+    ///     let check = conn.check_sql(sql)?;
+    ///     println!("Parameters: {}", check.num_params);
+    ///     for column in check.columns {
+    ///         println!("{}: {:?}", column.name_to_string().unwrap(), column.data_type);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn check_sql(&self, sql: &str) -> Result<SqlCheck, Error> {
+        check_sql(self, sql)
     }
 
     /// Prepares an SQL statement which takes ownership of the connection. The advantage over
@@ -297,12 +828,16 @@ impl<'c> Connection<'c> {
     /// }
     /// ```
     pub fn into_prepared(self, query: &str) -> Result<Prepared<StatementConnection<'c>>, Error> {
-        let query = SqlText::new(query);
+        let sql = SqlText::new(query);
         let mut stmt = self.allocate_statement()?;
-        stmt.prepare(&query).into_result(&stmt)?;
+        stmt.prepare(&sql).into_result(&stmt)?;
+        let quirks = self.quirks_or_default();
+        let query_logger = self.query_logger.clone();
         // Safe: `handle` is a valid statement, and we are giving up ownership of `self`.
         let stmt = unsafe { StatementConnection::new(stmt.into_sys(), self) };
-        Ok(Prepared::new(stmt))
+        Ok(Prepared::new(stmt)
+            .with_quirks(quirks)
+            .with_query_logger(query_logger, query))
     }
 
     /// Allocates an SQL statement handle. This is recommended if you want to sequentially execute
@@ -337,7 +872,97 @@ impl<'c> Connection<'c> {
     /// ```
     pub fn preallocate(&self) -> Result<Preallocated<'_>, Error> {
         let stmt = self.allocate_statement()?;
-        Ok(Preallocated::new(stmt))
+        Ok(Preallocated::new(stmt).with_query_logger(self.query_logger.clone()))
+    }
+
+    /// Allocates `num_statements` statements, which may hold active result sets at the same time.
+    /// This allows interleaving the use of several cursors on a single connection, e.g. to fetch
+    /// the details for each row of a parent query.
+    ///
+    /// Many drivers only support one active statement per connection. Others, like the ones for
+    /// Microsoft SQL Server, support Multiple Active Result Sets (MARS) only if enabled in the
+    /// connection string, e.g. with `MARS_Connection=Yes`. Rather than failing with a busy
+    /// connection, once the second cursor is opened, this function fails upfront with
+    /// [`Error::TooManyConcurrentStatements`] if the driver reports to support fewer active
+    /// statements than requested. See [`Self::max_concurrent_activities`].
+    ///
+    /// ```no_run
+    /// use odbc_api::{Connection, Cursor, Error};
+    ///
+    /// fn interleave(conn: &Connection<'_>) -> Result<(), Error> {
+    ///     let mut statements = conn.preallocate_concurrent(2)?;
+    ///     let (orders, items) = statements.split_at_mut(1);
+    ///     let mut orders = orders[0].execute("SELECT id FROM Orders", ())?.unwrap();
+    ///     while let Some(mut order) = orders.next_row()? {
+    ///         let mut id = 0i32;
+    ///         order.get_data(1, &mut id)?;
+    ///         // The cursor over the orders stays open, while we query the items.
+    ///         let _items = items[0].execute("SELECT * FROM Items WHERE order_id = ?", &id)?;
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn preallocate_concurrent(
+        &self,
+        num_statements: usize,
+    ) -> Result<Vec<Preallocated<'_>>, Error> {
+        if num_statements > 1 {
+            if let Some(max_concurrent_activities) = self.max_concurrent_activities()? {
+                if usize::from(max_concurrent_activities) < num_statements {
+                    return Err(Error::TooManyConcurrentStatements {
+                        requested: num_statements,
+                        max_concurrent_activities,
+                    });
+                }
+            }
+        }
+        (0..num_statements).map(|_| self.preallocate()).collect()
+    }
+
+    /// Checks out a statement handle from the statement pool of this connection, or allocates a
+    /// new one if the pool is empty. The returned statement can be used like a [`Preallocated`]
+    /// statement. Once it is dropped, any buffers and parameters bound to the handle are released
+    /// and the handle is returned to the pool, ready to be reused by the next checkout.
+    ///
+    /// This is useful for workloads interleaving many short statements, as it avoids allocating
+    /// and freeing a statement handle for each of them, without having to pass around a
+    /// [`Preallocated`] statement explicitly. Statement attributes changed on the handle are not
+    /// reset on return. Use [`PooledStatement::discard`] to free the handle instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use odbc_api::{Connection, Error, IntoParameter};
+    ///
+    /// fn delete_user(conn: &Connection<'_>, user: &str) -> Result<(), Error> {
+    ///     // After the first call, no statement handle needs to be allocated anymore.
+    ///     let mut statement = conn.checkout_statement()?;
+    ///     statement.execute("DELETE FROM Comments WHERE user=?", &user.into_parameter())?;
+    ///     statement.execute("DELETE FROM Users WHERE name=?", &user.into_parameter())?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn checkout_statement(&self) -> Result<PooledStatement<'_>, Error> {
+        let statement = if let Some(handle) = self.statement_pool.take() {
+            // Safe due to the invariant of the statement pool.
+            unsafe { StatementImpl::new(handle) }
+        } else {
+            self.allocate_statement()?
+        };
+        let statement = Preallocated::new(statement).with_query_logger(self.query_logger.clone());
+        Ok(PooledStatement::new(statement, &self.statement_pool))
+    }
+
+    /// Number of idle statement handles currently held by the statement pool. See
+    /// [`Self::checkout_statement`].
+    pub fn num_pooled_statements(&self) -> usize {
+        self.statement_pool.len()
+    }
+
+    /// Frees all idle statement handles held by the statement pool. See
+    /// [`Self::checkout_statement`].
+    pub fn clear_statement_pool(&self) {
+        self.statement_pool.clear()
     }
 
     /// Specify the transaction mode. By default, ODBC transactions are in auto-commit mode.
@@ -356,6 +981,37 @@ impl<'c> Connection<'c> {
             .into_result(&self.connection)
     }
 
+    /// Enables or disables tracing of all ODBC function calls on this connection by the driver
+    /// manager. Useful to debug a single problematic connection without enabling tracing for the
+    /// entire system. Use [`Self::set_trace_file`] to control where the trace is written to. To
+    /// also capture the calls made while connecting use
+    /// [`crate::Environment::connect_with_connection_string_and_trace`].
+    ///
+    /// ```no_run
+    /// use odbc_api::Connection;
+    ///
+    /// fn debug_query(conn: &Connection<'_>) -> Result<(), odbc_api::Error> {
+    ///     conn.set_trace_file("/tmp/odbc_trace.log")?;
+    ///     conn.set_trace(true)?;
+    ///     conn.execute("SELECT 42", ())?;
+    ///     conn.set_trace(false)
+    /// }
+    /// ```
+    pub fn set_trace(&self, enabled: bool) -> Result<(), Error> {
+        self.connection
+            .set_trace(enabled)
+            .into_result(&self.connection)
+    }
+
+    /// Sets the path of the file the driver manager writes the trace to, once tracing has been
+    /// enabled using [`Self::set_trace`].
+    pub fn set_trace_file(&self, path: &str) -> Result<(), Error> {
+        let path = SqlText::new(path);
+        self.connection
+            .set_trace_file(&path)
+            .into_result(&self.connection)
+    }
+
     /// To commit a transaction in manual-commit mode.
     pub fn commit(&self) -> Result<(), Error> {
         self.connection.commit().into_result(&self.connection)
@@ -427,6 +1083,90 @@ impl<'c> Connection<'c> {
         force_send_sync::Send::new(self)
     }
 
+    /// Known quirks of the driver this connection uses. Determined from the file name of the
+    /// driver the first time this method is called, and cached for the lifetime of the connection.
+    /// See [`Quirks::from_driver_name`].
+    pub fn quirks(&self) -> Result<Quirks, Error> {
+        if let Some(quirks) = self.quirks.get() {
+            return Ok(quirks);
+        }
+        let quirks = Quirks::from_driver_name(&self.driver_name()?);
+        self.quirks.set(Some(quirks));
+        Ok(quirks)
+    }
+
+    /// Replaces the quirks applied to cursors and prepared statements created after this call.
+    /// Intended for drivers missing from the registry of [`Quirks::from_driver_name`].
+    ///
+    /// ```no_run
+    /// use odbc_api::{Connection, Quirks};
+    ///
+    /// fn bind_text_as_narrow(conn: &Connection<'_>) {
+    ///     let quirks = Quirks {
+    ///         no_wide_text: true,
+    ///         ..conn.quirks().unwrap_or_default()
+    ///     };
+    ///     conn.set_quirks(quirks);
+    /// }
+    /// ```
+    pub fn set_quirks(&self, quirks: Quirks) {
+        self.quirks.set(Some(quirks));
+    }
+
+    /// Dialect of the data source this connection is connected to. Pass it to functions generating
+    /// SQL, like [`crate::create_table_statement`] or [`Self::prepare_upsert`], so the statements
+    /// fit the data source. See [`DbmsDialect::from_dbms_name`].
+    ///
+    /// ```no_run
+    /// use odbc_api::{Connection, Error, SqlDialect};
+    ///
+    /// fn first_rows(conn: &Connection<'_>, table: &str) -> Result<(), Error> {
+    ///     let dialect = conn.dialect()?;
+    ///     let query = dialect.limit_query(&format!("SELECT * FROM {table}"), 10);
+    ///     conn.execute(&query, ())?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn dialect(&self) -> Result<DbmsDialect, Error> {
+        Ok(DbmsDialect::from_dbms_name(
+            &self.database_management_system_name()?,
+        ))
+    }
+
+    /// Changes how column names are matched by name based lookups on cursors and prepared
+    /// statements created after this call, e.g. [`crate::ResultSetMetadata::col_index`].
+    ///
+    /// ```no_run
+    /// use odbc_api::{ColumnNameMatcher, Connection};
+    ///
+    /// fn match_names_exactly(conn: &Connection<'_>) {
+    ///     conn.set_column_name_matcher(ColumnNameMatcher::exact());
+    /// }
+    /// ```
+    pub fn set_column_name_matcher(&self, matcher: ColumnNameMatcher) {
+        let mut quirks = self.quirks_or_default();
+        quirks.column_name_matcher = matcher;
+        self.quirks.set(Some(quirks));
+    }
+
+    /// Quirks are a best effort to work around driver issues. Failing to determine them should
+    /// not fail the operation they are required for. The outcome is cached either way, so only the
+    /// first statement of a connection asks the driver.
+    fn quirks_or_default(&self) -> Quirks {
+        self.quirks().unwrap_or_else(|error| {
+            debug!("Could not determine driver quirks: {error}");
+            let quirks = Quirks::new();
+            self.quirks.set(Some(quirks));
+            quirks
+        })
+    }
+
+    /// State attached to this connection by layered libraries, e.g. pools or ORMs. Dropped
+    /// together with the connection. See [`Extensions`].
+    pub fn extensions(&self) -> &Extensions {
+        &self.extensions
+    }
+
     /// Get the name of the database management system used by the connection.
     pub fn database_management_system_name(&self) -> Result<String, Error> {
         let mut buf = Vec::new();
@@ -437,6 +1177,72 @@ impl<'c> Connection<'c> {
         Ok(name)
     }
 
+    /// Get the file name of the driver library used by the connection (`SQL_DRIVER_NAME`), e.g.
+    /// `msodbcsql18.dll` or `libsqlite3odbc.so`.
+    pub fn driver_name(&self) -> Result<String, Error> {
+        let mut buf = Vec::new();
+        self.connection
+            .fetch_driver_name(&mut buf)
+            .into_result(&self.connection)?;
+        let name = slice_to_utf8(&buf).unwrap();
+        Ok(name)
+    }
+
+    /// Get the data source specific name of the server the connection is established with
+    /// (`SQL_SERVER_NAME`).
+    pub fn server_name(&self) -> Result<String, Error> {
+        let mut buf = Vec::new();
+        self.connection
+            .fetch_server_name(&mut buf)
+            .into_result(&self.connection)?;
+        let name = slice_to_utf8(&buf).unwrap();
+        Ok(name)
+    }
+
+    /// Which of the features introduced with ODBC 3.8 are supported by the driver. Features
+    /// unknown to drivers predating ODBC 3.8 are reported as not supported.
+    ///
+    /// ```no_run
+    /// use odbc_api::Environment;
+    ///
+    /// let env = Environment::new()?;
+    /// let conn = env.connect("YourDatabase", "SA", "My@Test@Password1")?;
+    /// if conn.odbc_3_8_features()?.async_statements {
+    ///     // Execute statements asynchronously
+    /// } else {
+    ///     // Fall back to blocking execution
+    /// }
+    /// # Ok::<(), odbc_api::Error>(())
+    /// ```
+    pub fn odbc_3_8_features(&self) -> Result<Odbc38Features, Error> {
+        Odbc38Features::fetch(&self.connection)
+    }
+
+    /// Extensions to `SQLGetData` supported by the driver. Consult them to decide which columns of
+    /// a result set can be left unbound and be fetched using
+    /// [`crate::BlockCursor::fetch_with_unbound`] instead.
+    pub fn get_data_extensions(&self) -> Result<GetDataExtensions, Error> {
+        GetDataExtensions::fetch(&self.connection)
+    }
+
+    /// Maximum number of statements, which can hold active result sets on this connection at the
+    /// same time. `None` if the driver does not report a limit.
+    pub fn max_concurrent_activities(&self) -> Result<Option<u16>, Error> {
+        let max = self
+            .connection
+            .max_concurrent_activities()
+            .into_result(&self.connection)?;
+        Ok((max != 0).then_some(max))
+    }
+
+    /// `true` if several statements can hold active result sets on this connection at the same
+    /// time, e.g. because Multiple Active Result Sets (MARS) are enabled for a connection to
+    /// Microsoft SQL Server. See [`Self::preallocate_concurrent`].
+    pub fn supports_multiple_active_result_sets(&self) -> Result<bool, Error> {
+        // A limit of zero is reported as `None`, so any limit other than one allows for several.
+        Ok(self.max_concurrent_activities()? != Some(1))
+    }
+
     /// Maximum length of catalog names.
     pub fn max_catalog_name_len(&self) -> Result<u16, Error> {
         self.connection
@@ -575,6 +1381,24 @@ impl<'c> Connection<'c> {
         )
     }
 
+    /// A cursor describing the columns making up the primary key of a table. Contrary to
+    /// [`Self::tables`] and [`Self::columns`] the arguments are not search patterns, but must name
+    /// the table exactly. The returned cursor has the columns:
+    /// `TABLE_CAT`, `TABLE_SCHEM`, `TABLE_NAME`, `COLUMN_NAME`, `KEY_SEQ`, `PK_NAME`.
+    pub fn primary_keys(
+        &self,
+        catalog_name: &str,
+        schema_name: &str,
+        table_name: &str,
+    ) -> Result<CursorImpl<StatementImpl<'_>>, Error> {
+        execute_primary_keys(
+            self.allocate_statement()?,
+            &SqlText::new(catalog_name),
+            &SqlText::new(schema_name),
+            &SqlText::new(table_name),
+        )
+    }
+
     /// The buffer descriptions for all standard buffers (not including extensions) returned in the
     /// columns query (e.g. [`Connection::columns`]).
     ///
@@ -0,0 +1,510 @@
+use thiserror::Error as ThisError;
+
+use crate::{
+    buffers::{
+        cell_to_text, AnyBuffer, AnySliceMut, CellValue, ColumnarAnyBuffer, InvalidCellConversion,
+    },
+    handles::AsStatementRef,
+    Bit, BlockCursor, ColumnarBulkInserter, Cursor, Error,
+};
+
+/// Controls how [`copy`] deals with values which do not fit into the parameter buffers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CopyOptions {
+    /// What to do with text or binary values, which are longer than the parameter buffer they are
+    /// copied into. Default is [`Truncation::Error`].
+    pub truncation: Truncation,
+}
+
+/// Policy for text and binary values exceeding the maximum element length of a parameter buffer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Truncation {
+    /// Stop copying and report [`CopyError::Truncation`].
+    #[default]
+    Error,
+    /// Cut the value to the maximum element length of the buffer. Text valid in UTF-8 or UTF-16
+    /// is cut at a character boundary. Truncated values are counted in
+    /// [`CopySummary::num_truncated`].
+    Truncate,
+    /// Reallocate and rebind the parameter buffer, so it can hold the value. This is expensive and
+    /// the buffer never shrinks again, so this is best suited for rare outliers.
+    Grow,
+}
+
+/// Returned by [`copy`] on success.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CopySummary {
+    /// Number of rows sent to the target statement.
+    pub num_rows: usize,
+    /// Number of values cut due to [`Truncation::Truncate`].
+    pub num_truncated: usize,
+}
+
+/// Emitted by [`copy`]. Rows are sent to the target in chunks, so every row before the chunk in
+/// which the error occurred has been executed already.
+#[derive(Debug, ThisError)]
+pub enum CopyError {
+    #[error(
+        "Copying rows failed after {rows_copied} rows have been sent to the target. Cause:\n\
+        {source}"
+    )]
+    Odbc {
+        /// Number of leading rows which have been sent to the target successfully.
+        rows_copied: usize,
+        source: Error,
+    },
+    #[error(
+        "The source result set has {num_source_columns} columns, but the target statement has \
+        {num_target_columns} parameter buffers. Expected the numbers to be equal."
+    )]
+    ColumnCountMismatch {
+        num_source_columns: usize,
+        num_target_columns: usize,
+    },
+    #[error(
+        "Value in row {row}, column {column} can not be copied into the target buffer: {source}"
+    )]
+    Conversion {
+        /// Zero based index of the row in the source result set.
+        row: usize,
+        /// Zero based index of the column.
+        column: usize,
+        source: InvalidCellConversion,
+    },
+    #[error(
+        "Value in row {row}, column {column} has a length of {length}, which exceeds the maximum \
+        element length {max_len} of the target buffer."
+    )]
+    Truncation {
+        /// Zero based index of the row in the source result set.
+        row: usize,
+        /// Zero based index of the column.
+        column: usize,
+        length: usize,
+        max_len: usize,
+    },
+}
+
+/// Copies all remaining rows of a result set into the parameter buffers of a prepared statement
+/// and executes it for each batch. Intended for replicating tables between data sources. The
+/// `n`-th column of the source is copied into the `n`-th parameter buffer of the target.
+///
+/// The types of the source and target buffers need not be the same. Values are converted between
+/// them, if this is possible without loss of information, e.g. integers are copied into integer
+/// buffers of a different width, as long as they are in range, and all values can be copied into
+/// text buffers. Conversions which would lose information, like fractional numbers into integer
+/// buffers, or `NULL` into a non nullable buffer, are reported as [`CopyError::Conversion`].
+///
+/// The batch sizes of source and target are independent. Each fetched row set is sent in as many
+/// chunks as required by the capacity of the inserter.
+///
+/// ```no_run
+/// use odbc_api::{
+///     buffers::{BufferDesc, ColumnarAnyBuffer}, copy, Connection, CopyError, CopyOptions,
+///     Cursor, Truncation,
+/// };
+///
+/// fn replicate(source: &Connection, target: &Connection) -> Result<usize, CopyError> {
+///     let descs = [
+///         BufferDesc::I32 { nullable: false },
+///         BufferDesc::Text { max_str_len: 255 },
+///     ];
+///     let rows_copied = |source| CopyError::Odbc { rows_copied: 0, source };
+///     let cursor = source
+///         .execute("SELECT id, name FROM Customers", ())
+///         .map_err(rows_copied)?
+///         .expect("SELECT statement must yield a result set");
+///     let buffer = ColumnarAnyBuffer::try_from_descs(5000, descs).map_err(rows_copied)?;
+///     let mut block_cursor = cursor.bind_buffer(buffer).map_err(rows_copied)?;
+///     let mut inserter = target
+///         .prepare("INSERT INTO Customers (id, name) VALUES (?, ?)")
+///         .and_then(|prepared| prepared.into_column_inserter(1000, descs))
+///         .map_err(rows_copied)?;
+///     let options = CopyOptions { truncation: Truncation::Grow };
+///     let summary = copy(&mut block_cursor, &mut inserter, &options)?;
+///     Ok(summary.num_rows)
+/// }
+/// ```
+///
+/// # Panics
+///
+/// If the capacity of `inserter` is zero.
+pub fn copy<C, S>(
+    cursor: &mut BlockCursor<C, ColumnarAnyBuffer>,
+    inserter: &mut ColumnarBulkInserter<S, AnyBuffer>,
+    options: &CopyOptions,
+) -> Result<CopySummary, CopyError>
+where
+    C: Cursor,
+    S: AsStatementRef,
+{
+    assert!(
+        inserter.capacity() != 0,
+        "Inserter used as target for copy must have a capacity of at least one row."
+    );
+    let mut summary = CopySummary::default();
+    let num_columns = inserter.num_columns();
+    while let Some(batch) = cursor
+        .fetch()
+        .map_err(|source| odbc_error(&summary, source))?
+    {
+        if batch.num_cols() != num_columns {
+            return Err(CopyError::ColumnCountMismatch {
+                num_source_columns: batch.num_cols(),
+                num_target_columns: num_columns,
+            });
+        }
+        let mut offset = 0;
+        while offset < batch.num_rows() {
+            let chunk_size = inserter.capacity().min(batch.num_rows() - offset);
+            inserter.set_num_rows(chunk_size);
+            for column in 0..num_columns {
+                let source = batch.column(column);
+                let mut target = inserter.column_mut(column);
+                for index in 0..chunk_size {
+                    let cell = source.cell(offset + index);
+                    let row = summary.num_rows + index;
+                    match write_cell(&mut target, index, cell, options.truncation) {
+                        Ok(truncated) => summary.num_truncated += truncated as usize,
+                        Err(CellError::Conversion(source)) => {
+                            return Err(CopyError::Conversion {
+                                row,
+                                column,
+                                source,
+                            })
+                        }
+                        Err(CellError::Truncation { length, max_len }) => {
+                            return Err(CopyError::Truncation {
+                                row,
+                                column,
+                                length,
+                                max_len,
+                            })
+                        }
+                        Err(CellError::Odbc(source)) => return Err(odbc_error(&summary, source)),
+                    }
+                }
+            }
+            inserter
+                .execute()
+                .map_err(|source| odbc_error(&summary, source))?;
+            summary.num_rows += chunk_size;
+            offset += chunk_size;
+        }
+    }
+    Ok(summary)
+}
+
+fn odbc_error(summary: &CopySummary, source: Error) -> CopyError {
+    CopyError::Odbc {
+        rows_copied: summary.num_rows,
+        source,
+    }
+}
+
+/// Reason a single cell could not be written. Lacks the position, which is known to the caller.
+enum CellError {
+    Conversion(InvalidCellConversion),
+    Truncation { length: usize, max_len: usize },
+    Odbc(Error),
+}
+
+impl From<InvalidCellConversion> for CellError {
+    fn from(source: InvalidCellConversion) -> Self {
+        CellError::Conversion(source)
+    }
+}
+
+/// Converts `cell` and writes it into `target` at `index`. The first `index` rows of `target` are
+/// preserved, should the buffer have to grow. `true` if the value has been truncated.
+fn write_cell(
+    target: &mut AnySliceMut<'_>,
+    index: usize,
+    cell: CellValue,
+    truncation: Truncation,
+) -> Result<bool, CellError> {
+    let mut truncated = false;
+    match target {
+        AnySliceMut::Text(view) => {
+            let text = match cell {
+                // Copied as is, since it may not be UTF-8.
+                CellValue::Text(bytes) => Some(bytes),
+                other => cell_to_text(other, "text")?.map(String::into_bytes),
+            };
+            let max_len = view.max_len();
+            let text = fit(text, max_len, truncation, &mut truncated, utf8_prefix)?;
+            if let Some(text) = &text {
+                view.ensure_max_element_length(text.len(), index)
+                    .map_err(CellError::Odbc)?;
+            }
+            view.set_cell(index, text.as_deref());
+        }
+        AnySliceMut::WText(view) => {
+            let text = match cell {
+                CellValue::WText(units) => Some(units),
+                other => {
+                    cell_to_text(other, "wide text")?.map(|text| text.encode_utf16().collect())
+                }
+            };
+            let max_len = view.max_len();
+            let text = fit(text, max_len, truncation, &mut truncated, utf16_prefix)?;
+            if let Some(text) = &text {
+                view.ensure_max_element_length(text.len(), index)
+                    .map_err(CellError::Odbc)?;
+            }
+            view.set_cell(index, text.as_deref());
+        }
+        AnySliceMut::Binary(view) => {
+            let bytes = match cell {
+                CellValue::Null => None,
+                CellValue::Binary(bytes) | CellValue::Text(bytes) => Some(bytes),
+                other => return Err(invalid(other, "binary").into()),
+            };
+            let max_len = view.max_len();
+            let bytes = fit(bytes, max_len, truncation, &mut truncated, |_, len| len)?;
+            if let Some(bytes) = &bytes {
+                view.ensure_max_element_length(bytes.len(), index)
+                    .map_err(CellError::Odbc)?;
+            }
+            view.set_cell(index, bytes.as_deref());
+        }
+        AnySliceMut::Date(values) => values[index] = required(cell.try_into(), "Date")?,
+        AnySliceMut::Time(values) => values[index] = required(cell.try_into(), "Time")?,
+        AnySliceMut::Timestamp(values) => values[index] = required(cell.try_into(), "Timestamp")?,
+        AnySliceMut::F64(values) => values[index] = required(to_f64(cell, "f64"), "f64")?,
+        AnySliceMut::F32(values) => values[index] = required(to_f64(cell, "f32"), "f32")? as f32,
+        AnySliceMut::I8(values) => values[index] = required(to_integer(cell, "i8"), "i8")?,
+        AnySliceMut::I16(values) => values[index] = required(to_integer(cell, "i16"), "i16")?,
+        AnySliceMut::I32(values) => values[index] = required(to_integer(cell, "i32"), "i32")?,
+        AnySliceMut::I64(values) => values[index] = required(to_integer(cell, "i64"), "i64")?,
+        AnySliceMut::U8(values) => values[index] = required(to_integer(cell, "u8"), "u8")?,
+        AnySliceMut::Bit(values) => values[index] = Bit::from_bool(required(to_bool(cell), "bit")?),
+        AnySliceMut::NullableDate(values) => values.set_cell(index, cell.try_into()?),
+        AnySliceMut::NullableTime(values) => values.set_cell(index, cell.try_into()?),
+        AnySliceMut::NullableTimestamp(values) => values.set_cell(index, cell.try_into()?),
+        AnySliceMut::NullableF64(values) => values.set_cell(index, to_f64(cell, "f64")?),
+        AnySliceMut::NullableF32(values) => {
+            values.set_cell(index, to_f64(cell, "f32")?.map(|f| f as f32))
+        }
+        AnySliceMut::NullableI8(values) => values.set_cell(index, to_integer(cell, "i8")?),
+        AnySliceMut::NullableI16(values) => values.set_cell(index, to_integer(cell, "i16")?),
+        AnySliceMut::NullableI32(values) => values.set_cell(index, to_integer(cell, "i32")?),
+        AnySliceMut::NullableI64(values) => values.set_cell(index, to_integer(cell, "i64")?),
+        AnySliceMut::NullableU8(values) => values.set_cell(index, to_integer(cell, "u8")?),
+        AnySliceMut::NullableBit(values) => {
+            values.set_cell(index, to_bool(cell)?.map(Bit::from_bool))
+        }
+    }
+    Ok(truncated)
+}
+
+/// Applies the truncation policy to a value longer than `max_len`. `prefix_len` yields the length
+/// the value is cut to. Growing the buffer is left to the caller.
+fn fit<T>(
+    value: Option<Vec<T>>,
+    max_len: usize,
+    truncation: Truncation,
+    truncated: &mut bool,
+    prefix_len: impl FnOnce(&[T], usize) -> usize,
+) -> Result<Option<Vec<T>>, CellError> {
+    match value {
+        Some(mut value) if value.len() > max_len => match truncation {
+            Truncation::Error => Err(CellError::Truncation {
+                length: value.len(),
+                max_len,
+            }),
+            Truncation::Truncate => {
+                let len = prefix_len(&value, max_len);
+                value.truncate(len);
+                *truncated = true;
+                Ok(Some(value))
+            }
+            Truncation::Grow => Ok(Some(value)),
+        },
+        other => Ok(other),
+    }
+}
+
+/// Largest length not exceeding `max_len`, which does not split a multi byte character. Text in
+/// other encodings is cut at `max_len`.
+fn utf8_prefix(text: &[u8], max_len: usize) -> usize {
+    if std::str::from_utf8(text).is_err() {
+        return max_len;
+    }
+    // Continuation bytes have the bit pattern `10xxxxxx`.
+    (0..=max_len)
+        .rev()
+        .find(|&len| len == text.len() || text[len] & 0b1100_0000 != 0b1000_0000)
+        .unwrap_or(0)
+}
+
+/// Largest length not exceeding `max_len`, which does not split a surrogate pair.
+fn utf16_prefix(text: &[u16], max_len: usize) -> usize {
+    // High surrogates are in the range `D800` to `DBFF`.
+    if max_len > 0 && (0xD800..0xDC00).contains(&text[max_len - 1]) {
+        max_len - 1
+    } else {
+        max_len
+    }
+}
+
+fn invalid(value: CellValue, target: &'static str) -> InvalidCellConversion {
+    InvalidCellConversion { target, value }
+}
+
+/// Reports `NULL` as a conversion error, for buffers without indicators.
+fn required<T>(
+    value: Result<Option<T>, InvalidCellConversion>,
+    target: &'static str,
+) -> Result<T, InvalidCellConversion> {
+    value?.ok_or_else(|| invalid(CellValue::Null, target))
+}
+
+/// Integral numbers, exact decimals, bits and text which parses as an integer.
+fn to_integer<T>(cell: CellValue, target: &'static str) -> Result<Option<T>, InvalidCellConversion>
+where
+    T: TryFrom<i64>,
+{
+    let integer = match &cell {
+        CellValue::Null => return Ok(None),
+        &CellValue::I64(i) => Some(i),
+        &CellValue::Bit(b) => Some(b as i64),
+        &CellValue::Decimal { value, scale } => decimal_to_integer(value, scale),
+        CellValue::Text(_) | CellValue::WText(_) => String::try_from(cell.clone())
+            .ok()
+            .and_then(|text| text.trim().parse().ok()),
+        _ => None,
+    };
+    match integer.and_then(|i| T::try_from(i).ok()) {
+        Some(integer) => Ok(Some(integer)),
+        None => Err(invalid(cell, target)),
+    }
+}
+
+/// `None` if the decimal has fractional digits, or is out of range.
+fn decimal_to_integer(value: i64, scale: i16) -> Option<i64> {
+    let factor = 10i64.checked_pow(scale.unsigned_abs().into())?;
+    if scale >= 0 {
+        (value % factor == 0).then_some(value / factor)
+    } else {
+        value.checked_mul(factor)
+    }
+}
+
+/// Any number, or text which parses as a number.
+fn to_f64(cell: CellValue, target: &'static str) -> Result<Option<f64>, InvalidCellConversion> {
+    let float = match &cell {
+        CellValue::Null => return Ok(None),
+        &CellValue::F64(f) => Some(f),
+        &CellValue::I64(i) => Some(i as f64),
+        &CellValue::Decimal { value, scale } => Some(value as f64 / 10f64.powi(scale.into())),
+        CellValue::Text(_) | CellValue::WText(_) => String::try_from(cell.clone())
+            .ok()
+            .and_then(|text| text.trim().parse().ok()),
+        _ => None,
+    };
+    float.map(Some).ok_or_else(|| invalid(cell, target))
+}
+
+/// Bits, the integers `0` and `1` and text holding either of them.
+fn to_bool(cell: CellValue) -> Result<Option<bool>, InvalidCellConversion> {
+    if let CellValue::Bit(b) = cell {
+        return Ok(Some(b));
+    }
+    match to_integer::<u8>(cell, "bit")? {
+        None => Ok(None),
+        Some(0) => Ok(Some(false)),
+        Some(1) => Ok(Some(true)),
+        Some(i) => Err(invalid(CellValue::I64(i.into()), "bit")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::buffers::CellValue;
+
+    use super::{
+        fit, to_bool, to_f64, to_integer, utf16_prefix, utf8_prefix, CellError, Truncation,
+    };
+
+    #[test]
+    fn integers_are_range_checked() {
+        assert_eq!(Ok(Some(100i8)), to_integer(CellValue::I64(100), "i8"));
+        assert!(to_integer::<i8>(CellValue::I64(300), "i8").is_err());
+        assert_eq!(Ok(None), to_integer::<i8>(CellValue::Null, "i8"));
+    }
+
+    #[test]
+    fn only_exact_decimals_convert_into_integers() {
+        let exact = CellValue::Decimal {
+            value: 4200,
+            scale: 2,
+        };
+        let fractional = CellValue::Decimal {
+            value: 4250,
+            scale: 2,
+        };
+
+        assert_eq!(Ok(Some(42i32)), to_integer(exact, "i32"));
+        assert!(to_integer::<i32>(fractional, "i32").is_err());
+    }
+
+    #[test]
+    fn parse_numbers_from_text() {
+        assert_eq!(
+            Ok(Some(42i64)),
+            to_integer(CellValue::Text(b" 42 ".to_vec()), "i64")
+        );
+        assert_eq!(
+            Ok(Some(1.5)),
+            to_f64(CellValue::WText("1.5".encode_utf16().collect()), "f64")
+        );
+        assert!(to_integer::<i64>(CellValue::Text(b"1.5".to_vec()), "i64").is_err());
+    }
+
+    #[test]
+    fn bits_from_integers() {
+        assert_eq!(Ok(Some(true)), to_bool(CellValue::I64(1)));
+        assert!(to_bool(CellValue::I64(2)).is_err());
+    }
+
+    #[test]
+    fn truncate_at_character_boundaries() {
+        let utf8 = "aü".as_bytes();
+        let utf16: Vec<u16> = "a😀".encode_utf16().collect();
+
+        assert_eq!(1, utf8_prefix(utf8, 2));
+        assert_eq!(3, utf8_prefix(utf8, 3));
+        // Latin-1 encoded "aü" is not valid UTF-8.
+        assert_eq!(1, utf8_prefix(&[b'a', 0xfc], 1));
+        assert_eq!(1, utf16_prefix(&utf16, 2));
+        assert_eq!(0, utf16_prefix(&utf16, 0));
+    }
+
+    #[test]
+    fn truncation_policy() {
+        let value = || Some(b"Hello".to_vec());
+        let mut truncated = false;
+
+        let result = fit(value(), 3, Truncation::Error, &mut truncated, utf8_prefix);
+        assert!(matches!(
+            result,
+            Err(CellError::Truncation {
+                length: 5,
+                max_len: 3
+            })
+        ));
+        let result = fit(value(), 3, Truncation::Grow, &mut truncated, utf8_prefix);
+        assert!(matches!(result, Ok(Some(v)) if v == b"Hello"));
+        assert!(!truncated);
+        let result = fit(
+            value(),
+            3,
+            Truncation::Truncate,
+            &mut truncated,
+            utf8_prefix,
+        );
+        assert!(matches!(result, Ok(Some(v)) if v == b"Hel"));
+        assert!(truncated);
+    }
+}
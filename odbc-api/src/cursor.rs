@@ -1,15 +1,29 @@
-use odbc_sys::HStmt;
+use odbc_sys::{FetchOrientation, HStmt};
 
 use crate::{
     buffers::Indicator,
     error::ExtendResult,
+    execute::row_count,
+    fetch_limits::{FetchLimits, LimitExceeded, LimitTracker},
+    fetch_progress::{FetchProgress, ProgressTracker},
+    fetch_statistics::FetchStatistics,
+    fixed_sized::Pod,
     handles::{AsStatementRef, CDataMut, SqlResult, State, Statement, StatementRef},
     parameter::{CElement, VarBinarySliceMut, VarCharSliceMut},
-    sleep::{wait_for, Sleep},
-    Error, ResultSetMetadata,
+    sampling::{Sampler, Sampling},
+    sleep::{cancel, wait_for, Sleep},
+    Bit, Error, Nullable, Quirks, ResultSetMetadata,
 };
 
-use std::{cmp::max, thread::panicking};
+use std::{
+    cmp::max,
+    fmt,
+    mem::{self, ManuallyDrop},
+    ops::ControlFlow,
+    ptr,
+    task::Poll,
+    thread::panicking,
+};
 
 /// Cursors are used to process and iterate the result sets returned by executing queries.
 ///
@@ -82,8 +96,9 @@ pub struct CursorRow<'s> {
 impl<'s> CursorRow<'s> {
     /// # Safety
     ///
-    /// `statement` must be in a cursor state.
-    unsafe fn new(statement: StatementRef<'s>) -> Self {
+    /// `statement` must be in a cursor state, or have the value of a streamed output parameter
+    /// available.
+    pub(crate) unsafe fn new(statement: StatementRef<'s>) -> Self {
         CursorRow { statement }
     }
 }
@@ -93,23 +108,70 @@ impl<'s> CursorRow<'s> {
     /// method drains the data from the field. It can be called repeatedly to if not all the data
     /// fit in the output buffer at once. It should not called repeatedly to fetch the same value
     /// twice. Column index starts at `1`.
+    ///
+    /// If the driver fails to convert the value into the C type of `target`, an
+    /// [`Error::InvalidDataConversion`] naming the column and the C type is returned.
     pub fn get_data(
         &mut self,
         col_or_param_num: u16,
         target: &mut (impl CElement + CDataMut),
     ) -> Result<(), Error> {
+        let c_type = target.cdata_type();
         self.statement
             .get_data(col_or_param_num, target)
             .into_result(&self.statement)
-            .provide_context_for_diagnostic(|record, function| {
-                if record.state == State::INDICATOR_VARIABLE_REQUIRED_BUT_NOT_SUPPLIED {
+            .provide_context_for_diagnostic(|record, function| match record.state {
+                State::INDICATOR_VARIABLE_REQUIRED_BUT_NOT_SUPPLIED => {
                     Error::UnableToRepresentNull(record)
-                } else {
-                    Error::Diagnostics { record, function }
                 }
+                State::RESTRICTED_DATA_TYPE_ATTRIBUTE_VIOLATION
+                | State::NUMERIC_VALUE_OUT_OF_RANGE
+                | State::INVALID_DATETIME_FORMAT
+                | State::INVALID_CHARACTER_VALUE_FOR_CAST_SPECIFICATION => {
+                    Error::InvalidDataConversion {
+                        column_number: col_or_param_num,
+                        c_type,
+                        record,
+                    }
+                }
+                _ => Error::Diagnostics { record, function },
             })
     }
 
+    /// Fetches a fixed sized value from the current row, e.g. an integer, a float, a
+    /// [`crate::sys::Date`] or a [`crate::sys::Timestamp`]. The driver converts the value of the
+    /// column into the requested type. `None` if the value is `NULL`. Column index starts at `1`.
+    ///
+    /// ```
+    /// use odbc_api::{sys::Date, Cursor, Error};
+    ///
+    /// fn print_birthdays(cursor: &mut impl Cursor) -> Result<(), Error> {
+    ///     while let Some(mut row) = cursor.next_row()? {
+    ///         let id: Option<u64> = row.get_value(1)?;
+    ///         let birthday: Option<Date> = row.get_value(2)?;
+    ///         println!("{id:?}: {birthday:?}");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn get_value<T>(&mut self, col_or_param_num: u16) -> Result<Option<T>, Error>
+    where
+        T: Pod,
+    {
+        let mut target = Nullable::<T>::null();
+        self.get_data(col_or_param_num, &mut target)?;
+        Ok(target.into_opt())
+    }
+
+    /// Fetches a boolean value from the current row. The value is requested as [`crate::Bit`], so
+    /// the driver converts it, e.g. from an integer or from text. `None` if the value is `NULL`.
+    /// Column index starts at `1`.
+    pub fn get_bool(&mut self, col_or_param_num: u16) -> Result<Option<bool>, Error> {
+        Ok(self
+            .get_value::<Bit>(col_or_param_num)?
+            .map(|bit| bit.as_bool()))
+    }
+
     /// Retrieves arbitrary large character data from the row and stores it in the buffer. Column
     /// index starts at `1`.
     ///
@@ -237,6 +299,8 @@ impl<'s> CursorRow<'s> {
 pub struct CursorImpl<Stmt: AsStatementRef> {
     /// A statement handle in cursor mode.
     statement: Stmt,
+    /// Quirks of the driver, which created the result set. Used to adjust buffer sizes.
+    quirks: Quirks,
 }
 
 impl<S> Drop for CursorImpl<S>
@@ -264,7 +328,14 @@ where
     }
 }
 
-impl<S> ResultSetMetadata for CursorImpl<S> where S: AsStatementRef {}
+impl<S> ResultSetMetadata for CursorImpl<S>
+where
+    S: AsStatementRef,
+{
+    fn quirks(&self) -> Quirks {
+        self.quirks
+    }
+}
 
 impl<S> Cursor for CursorImpl<S>
 where
@@ -297,12 +368,29 @@ where
     ///
     /// `statement` must be in Cursor state, for the invariants of this type to hold.
     pub unsafe fn new(statement: S) -> Self {
-        Self { statement }
+        Self {
+            statement,
+            quirks: Quirks::new(),
+        }
+    }
+
+    /// Quirks of the driver reported by [`ResultSetMetadata::quirks`].
+    pub(crate) fn with_quirks(mut self, quirks: Quirks) -> Self {
+        self.quirks = quirks;
+        self
     }
 
     pub(crate) fn as_sys(&mut self) -> HStmt {
         self.as_stmt_ref().as_sys()
     }
+
+    /// Number of rows affected by the statement which created this cursor. May return `None` if row
+    /// count is not available. Some drivers may use this to report the number of rows in the result
+    /// set. Most drivers however only know how many rows have been fetched after they have been
+    /// fetched.
+    pub fn row_count(&mut self) -> Result<Option<usize>, Error> {
+        row_count(&self.statement.as_stmt_ref())
+    }
 }
 
 /// A Row set buffer binds row, or column wise buffers to a cursor in order to fill them with row
@@ -337,6 +425,12 @@ pub unsafe trait RowSetBuffer {
     /// It's the implementations responsibility to ensure that all bound buffers are valid until
     /// unbound or the statement handle is deleted.
     unsafe fn bind_colmuns_to_cursor(&mut self, cursor: StatementRef<'_>) -> Result<(), Error>;
+
+    /// Accounts for the values of the last fetched row set in `statistics`. Row and batch counts
+    /// are taken care of by the cursor. The default implementation records nothing.
+    fn record_statistics(&self, statistics: &mut FetchStatistics) {
+        let _ = statistics;
+    }
 }
 
 unsafe impl<T: RowSetBuffer> RowSetBuffer for &mut T {
@@ -355,6 +449,10 @@ unsafe impl<T: RowSetBuffer> RowSetBuffer for &mut T {
     unsafe fn bind_colmuns_to_cursor(&mut self, cursor: StatementRef<'_>) -> Result<(), Error> {
         (*self).bind_colmuns_to_cursor(cursor)
     }
+
+    fn record_statistics(&self, statistics: &mut FetchStatistics) {
+        (**self).record_statistics(statistics)
+    }
 }
 
 #[deprecated = "Use new name BlockCursor instead"]
@@ -382,14 +480,262 @@ pub type RowSetCursor<C, B> = BlockCursor<C, B>;
 pub struct BlockCursor<C: AsStatementRef, B> {
     buffer: B,
     cursor: C,
+    progress: Option<ProgressTracker>,
+    statistics: FetchStatistics,
+    sampling: Option<Sampler>,
+    limits: Option<LimitTracker>,
+    /// Number of rows fetched at once. May be smaller than the capacity of the buffer. See
+    /// [`Self::set_row_array_size`].
+    row_array_size: usize,
+    /// Zero based index of the first row of the next row set, if fetched without scrolling.
+    position: usize,
+    /// `false` once the driver told us that it can not scroll the cursor.
+    try_scrolling: bool,
 }
 
 impl<C, B> BlockCursor<C, B>
 where
     C: Cursor,
+    B: RowSetBuffer,
 {
-    fn new(buffer: B, cursor: C) -> Self {
-        Self { buffer, cursor }
+    pub(crate) fn new(buffer: B, cursor: C) -> Self {
+        let row_array_size = buffer.row_array_size();
+        Self {
+            buffer,
+            cursor,
+            progress: None,
+            statistics: FetchStatistics::default(),
+            sampling: None,
+            limits: None,
+            row_array_size,
+            position: 0,
+            try_scrolling: true,
+        }
+    }
+
+    /// Registers a callback which is invoked after each row set fetched, with the total number of
+    /// rows and row sets fetched so far, as well as the time elapsed since registering the
+    /// callback. This allows e.g. for rendering progress bars. Should the callback return
+    /// [`ControlFlow::Break`], any subsequent call to fetch returns `None`, as if the result set
+    /// had been consumed. This can be used to enforce soft limits. Registering a new callback
+    /// replaces the previous one and resets the progress.
+    ///
+    /// ```
+    /// use odbc_api::{buffers::TextRowSet, Cursor, FetchProgress};
+    /// use std::{ops::ControlFlow, time::Duration};
+    ///
+    /// fn fetch_for_at_most_ten_seconds(mut cursor: impl Cursor) {
+    ///     let buffer = TextRowSet::for_cursor(100, &mut cursor, Some(4000)).unwrap();
+    ///     let mut cursor = cursor.bind_buffer(buffer).unwrap();
+    ///     cursor.set_progress_callback(|progress: &FetchProgress| {
+    ///         eprintln!("Fetched {} rows", progress.num_rows);
+    ///         if progress.elapsed > Duration::from_secs(10) {
+    ///             ControlFlow::Break(())
+    ///         } else {
+    ///             ControlFlow::Continue(())
+    ///         }
+    ///     });
+    ///     while let Some(batch) = cursor.fetch().unwrap() {
+    ///         // ... process values in batch ...
+    ///     }
+    /// }
+    /// ```
+    pub fn set_progress_callback(
+        &mut self,
+        callback: impl FnMut(&FetchProgress) -> ControlFlow<()> + Send + 'static,
+    ) {
+        self.progress = Some(ProgressTracker::new(Box::new(callback)));
+    }
+
+    /// Statistics about all row sets fetched so far, like the number of rows and the number of
+    /// truncated values. Remains accessible after the result set has been consumed.
+    ///
+    /// ```
+    /// use odbc_api::{buffers::TextRowSet, Cursor};
+    ///
+    /// fn fetch_all_and_assert_no_truncation(mut cursor: impl Cursor) {
+    ///     let buffer = TextRowSet::for_cursor(100, &mut cursor, Some(4000)).unwrap();
+    ///     let mut cursor = cursor.bind_buffer(buffer).unwrap();
+    ///     while let Some(batch) = cursor.fetch().unwrap() {
+    ///         // ... process values in batch ...
+    ///     }
+    ///     let statistics = cursor.statistics();
+    ///     assert!(
+    ///         !statistics.has_truncated_values(),
+    ///         "{} of {} rows fetched contained truncated values",
+    ///         statistics.num_truncated,
+    ///         statistics.num_rows
+    ///     );
+    /// }
+    /// ```
+    pub fn statistics(&self) -> &FetchStatistics {
+        &self.statistics
+    }
+
+    /// The bound buffer, holding the row set fetched last.
+    #[cfg(feature = "capi")]
+    pub(crate) fn buffer(&self) -> &B {
+        &self.buffer
+    }
+
+    /// Only return a sample of the row sets in the result set to subsequent calls to
+    /// [`Self::fetch`]. Progress and statistics only account for the row sets returned. Setting a
+    /// new sampling restarts the sample at the current position in the result set.
+    ///
+    /// ```
+    /// use odbc_api::{buffers::TextRowSet, Cursor, Sampling};
+    ///
+    /// fn profile_every_tenth_batch(mut cursor: impl Cursor) {
+    ///     let buffer = TextRowSet::for_cursor(1000, &mut cursor, Some(4000)).unwrap();
+    ///     let mut cursor = cursor.bind_buffer(buffer).unwrap();
+    ///     cursor.set_sampling(Sampling::EveryNthBatch(10));
+    ///     while let Some(batch) = cursor.fetch().unwrap() {
+    ///         // ... analyse sampled rows ...
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `sampling` is [`Sampling::EveryNthBatch`] with `0`, or [`Sampling::Random`] with a
+    /// probability outside of `(0, 1]`.
+    pub fn set_sampling(&mut self, sampling: Sampling) {
+        self.sampling = Some(Sampler::new(sampling));
+    }
+
+    /// Enforces limits on the number of rows and the time spent fetching the result set. Once a
+    /// limit is exceeded, subsequent calls to fetch return `None`, as if the result set had been
+    /// consumed. Use [`Self::limit_exceeded`] afterwards to tell a partial result from a complete
+    /// one. Setting new limits restarts counting rows and time.
+    ///
+    /// ```
+    /// use odbc_api::{buffers::TextRowSet, Cursor, FetchLimits};
+    /// use std::time::Duration;
+    ///
+    /// fn fetch_contained(mut cursor: impl Cursor) {
+    ///     let buffer = TextRowSet::for_cursor(1000, &mut cursor, Some(4000)).unwrap();
+    ///     let mut cursor = cursor.bind_buffer(buffer).unwrap();
+    ///     cursor.set_fetch_limits(FetchLimits {
+    ///         max_duration: Some(Duration::from_secs(10)),
+    ///         cancel_after: Some(Duration::from_secs(30)),
+    ///         max_rows: Some(100_000),
+    ///     });
+    ///     while let Some(batch) = cursor.fetch().unwrap() {
+    ///         // ... process values in batch ...
+    ///     }
+    ///     if let Some(limit) = cursor.limit_exceeded() {
+    ///         eprintln!("Partial result. Exceeded limit: {limit:?}");
+    ///     }
+    /// }
+    /// ```
+    pub fn set_fetch_limits(&mut self, limits: FetchLimits) {
+        // Stop the watchdog of the previous limits, before starting a new one.
+        self.limits = None;
+        let statement = self.cursor.as_stmt_ref().as_sys();
+        // Safe: The tracker is dropped before the cursor, see the drop implementation.
+        self.limits = Some(unsafe { LimitTracker::new(limits, statement) });
+    }
+
+    /// The limit set with [`Self::set_fetch_limits`] which stopped fetching, if any. `Some`
+    /// indicates that the rows returned so far are only a partial result.
+    pub fn limit_exceeded(&self) -> Option<LimitExceeded> {
+        self.limits.as_ref().and_then(LimitTracker::exceeded)
+    }
+
+    /// Maximum number of rows fetched by the next call to [`Self::fetch`]. Initially the capacity
+    /// of the bound buffer.
+    pub fn row_array_size(&self) -> usize {
+        self.row_array_size
+    }
+
+    /// Changes the number of rows fetched at once by subsequent calls to [`Self::fetch`]. The
+    /// bound buffer stays in place, so `row_array_size` must not exceed its capacity. E.g. fetch a
+    /// small first row set, to show results quickly, and larger ones afterwards, to reduce the
+    /// number of roundtrips. To fetch more rows than the buffer can hold, use [`Self::unbind`] and
+    /// bind a larger buffer to the cursor.
+    ///
+    /// ```
+    /// use odbc_api::{buffers::TextRowSet, Cursor, Error};
+    ///
+    /// fn show_first_rows_quickly(mut cursor: impl Cursor) -> Result<(), Error> {
+    ///     let buffer = TextRowSet::for_cursor(5000, &mut cursor, Some(4000))?;
+    ///     let mut cursor = cursor.bind_buffer(buffer)?;
+    ///     cursor.set_row_array_size(50)?;
+    ///     let mut first = true;
+    ///     while let Some(batch) = cursor.fetch()? {
+    ///         // ... process rows in batch ...
+    ///         if first {
+    ///             cursor.set_row_array_size(5000)?;
+    ///             first = false;
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `row_array_size` is `0` or larger than the capacity of the bound buffer.
+    pub fn set_row_array_size(&mut self, row_array_size: usize) -> Result<(), Error> {
+        if row_array_size == 0 || row_array_size > self.buffer.row_array_size() {
+            panic!(
+                "Row array size must be at least one and must not exceed the capacity of the \
+                bound buffer."
+            );
+        }
+        let mut stmt = self.cursor.as_stmt_ref();
+        unsafe { stmt.set_row_array_size(row_array_size) }.into_result(&stmt)?;
+        self.row_array_size = row_array_size;
+        Ok(())
+    }
+
+    /// Unbinds the buffer from the cursor and returns both, so the cursor can e.g. be bound to a
+    /// buffer of different size and continue fetching the result set where this one stopped, or the
+    /// buffer can be bound to the cursor of the next query, without allocating it again. Progress
+    /// callbacks, statistics, sampling and fetch limits are discarded.
+    ///
+    /// ```
+    /// use odbc_api::{buffers::TextRowSet, Connection, Cursor, Error};
+    ///
+    /// fn print_tables(conn: &Connection<'_>, tables: &[&str]) -> Result<(), Error> {
+    ///     let mut buffer = TextRowSet::from_max_str_lens(1000, [255, 255])?;
+    ///     for table in tables {
+    ///         let query = format!("SELECT a, b FROM {table}");
+    ///         let cursor = conn.execute(&query, ())?.expect("SELECT must yield a result set");
+    ///         let mut block_cursor = cursor.bind_buffer(buffer)?;
+    ///         while let Some(batch) = block_cursor.fetch()? {
+    ///             println!("{} rows of {table}", batch.num_rows());
+    ///         }
+    ///         // Reuse the buffer for the next table
+    ///         let (_, unbound) = block_cursor.unbind().map_err(|e| e.source)?;
+    ///         buffer = unbound;
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// Should unbinding fail, the buffer is leaked, since the driver may still write into it. The
+    /// cursor is returned as part of the error, so the application can still close it, or fetch
+    /// the remainder of the result set row by row.
+    pub fn unbind(self) -> Result<(C, B), UnbindError<C>> {
+        let mut this = ManuallyDrop::new(self);
+        // Stop the watchdog, before the cursor is handed out.
+        this.limits = None;
+        let result = unbind_buffer_from_statement(this.cursor.as_stmt_ref());
+        // Safe: Fields are moved out exactly once and `Drop` is not executed for `this`.
+        let (cursor, buffer) = unsafe {
+            ptr::drop_in_place(&mut this.progress);
+            ptr::drop_in_place(&mut this.statistics);
+            ptr::drop_in_place(&mut this.sampling);
+            (ptr::read(&this.cursor), ptr::read(&this.buffer))
+        };
+        match result {
+            Ok(()) => Ok((cursor, buffer)),
+            Err(source) => {
+                mem::forget(buffer);
+                Err(UnbindError { cursor, source })
+            }
+        }
     }
 
     /// Fills the bound buffer with the next row set.
@@ -446,12 +792,167 @@ where
         &mut self,
         error_for_truncation: bool,
     ) -> Result<Option<&B>, Error> {
+        if matches!(&self.progress, Some(progress) if progress.is_stopped()) {
+            return Ok(None);
+        }
+        if let Some(limits) = &mut self.limits {
+            if limits.check_before_fetch().is_some() {
+                return Ok(None);
+            }
+        }
+        let batches_to_skip = match &mut self.sampling {
+            Some(sampler) if sampler.remaining_rows() == Some(0) => return Ok(None),
+            Some(sampler) => sampler.batches_to_skip(),
+            None => 0,
+        };
+        let result = if batches_to_skip == 0 {
+            self.fetch_next(error_for_truncation)
+        } else {
+            self.skip_and_fetch(batches_to_skip, error_for_truncation)
+        };
+        let has_row = match result {
+            Ok(has_row) => has_row,
+            Err(error) => {
+                if let Some(limits) = &mut self.limits {
+                    // The error is caused by the watchdog canceling the fetch.
+                    if limits.canceled_by_watchdog() {
+                        return Ok(None);
+                    }
+                }
+                return Err(error);
+            }
+        };
+        if has_row {
+            let mut num_rows = *self.buffer.mut_num_fetch_rows();
+            if let Some(limits) = &mut self.limits {
+                let allowed = limits.record_rows(num_rows);
+                if allowed < num_rows {
+                    num_rows = allowed;
+                    *self.buffer.mut_num_fetch_rows() = allowed;
+                }
+            }
+            if let Some(sampler) = &mut self.sampling {
+                if let Some(remaining) = sampler.remaining_rows() {
+                    if num_rows > remaining {
+                        num_rows = remaining;
+                        *self.buffer.mut_num_fetch_rows() = remaining;
+                    }
+                }
+                sampler.record_rows(num_rows);
+            }
+            self.statistics.num_batches += 1;
+            self.statistics.num_rows += num_rows;
+            #[cfg(feature = "metrics")]
+            let num_truncated = self.statistics.num_truncated;
+            self.buffer.record_statistics(&mut self.statistics);
+            #[cfg(feature = "metrics")]
+            crate::metrics::record_batch(num_rows, self.statistics.num_truncated - num_truncated);
+            if let Some(progress) = &mut self.progress {
+                progress.record_batch(num_rows);
+            }
+        }
+        Ok(has_row.then_some(&self.buffer))
+    }
+
+    /// Like [`Self::fetch`], but also returns the current row of the cursor. Values of columns not
+    /// bound to the buffer can be fetched from it using [`CursorRow::get_data`] and its siblings.
+    /// This allows binding only some columns of a result set, e.g. using
+    /// [`crate::buffers::ColumnarAnyBuffer::try_from_descs_and_indices`], while fetching large or
+    /// variadic values of the remaining ones individually.
+    ///
+    /// Which columns may remain unbound depends on the driver. Most drivers require unbound
+    /// columns to follow the last bound one. See [`crate::Connection::get_data_extensions`].
+    ///
+    /// ```
+    /// use odbc_api::{
+    ///     buffers::{BufferDesc, ColumnarAnyBuffer},
+    ///     Cursor, Error,
+    /// };
+    ///
+    /// /// Binds the id in the first column, but fetches the text in the second one individually.
+    /// fn print_documents(cursor: impl Cursor) -> Result<(), Error> {
+    ///     let buffer = ColumnarAnyBuffer::try_from_descs_and_indices(
+    ///         1,
+    ///         [(1, BufferDesc::I32 { nullable: false })].into_iter(),
+    ///     )?;
+    ///     let mut cursor = cursor.bind_buffer(buffer)?;
+    ///     let mut text = Vec::new();
+    ///     while let Some((batch, mut row)) = cursor.fetch_with_unbound()? {
+    ///         let id = batch.column(0).as_slice::<i32>().unwrap()[0];
+    ///         row.get_text(2, &mut text)?;
+    ///         println!("{id}: {}", String::from_utf8_lossy(&text));
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the row array size is not `1`. The cursor can not be positioned on an individual row of
+    /// a larger row set. See [`Self::set_row_array_size`].
+    pub fn fetch_with_unbound(&mut self) -> Result<Option<(&B, CursorRow<'_>)>, Error> {
+        assert_eq!(
+            1, self.row_array_size,
+            "Fetching unbound columns requires a row array size of 1."
+        );
+        if self.fetch_with_truncation_check(false)?.is_none() {
+            return Ok(None);
+        }
+        // Safe: The cursor is positioned on the row we just fetched.
+        let row = unsafe { CursorRow::new(self.cursor.as_stmt_ref()) };
+        Ok(Some((&self.buffer, row)))
+    }
+
+    /// Fetches the row set following the current one.
+    fn fetch_next(&mut self, error_for_truncation: bool) -> Result<bool, Error> {
         let mut stmt = self.cursor.as_stmt_ref();
-        unsafe {
+        let has_row = unsafe {
             let result = stmt.fetch();
-            let has_row = error_handling_for_fetch(result, stmt, error_for_truncation)?;
-            Ok(has_row.then_some(&self.buffer))
+            error_handling_for_fetch(result, stmt, error_for_truncation)?
+        };
+        self.position += self.row_array_size;
+        Ok(has_row)
+    }
+
+    /// Skips `num_batches` row sets and fetches the one after them. Scrolls the cursor if the
+    /// driver supports it, otherwise the skipped row sets are fetched and discarded.
+    fn skip_and_fetch(
+        &mut self,
+        num_batches: usize,
+        error_for_truncation: bool,
+    ) -> Result<bool, Error> {
+        let batch_size = self.row_array_size;
+        if self.try_scrolling {
+            let start = self.position + num_batches * batch_size;
+            let mut stmt = self.cursor.as_stmt_ref();
+            let result = unsafe {
+                // Absolute positions are one based.
+                let result = stmt.fetch_scroll(FetchOrientation::Absolute, (start + 1) as isize);
+                error_handling_for_fetch(result, stmt, error_for_truncation)
+            };
+            match result {
+                Ok(has_row) => {
+                    self.position = start + batch_size;
+                    return Ok(has_row);
+                }
+                // Forward only cursor or no support for scrolling in the driver. Fall back to
+                // fetching the row sets we want to skip.
+                Err(Error::Diagnostics { record, .. })
+                    if record.state == State::FETCH_TYPE_OUT_OF_RANGE
+                        || record.state == State::OPTIONAL_FEATURE_NOT_IMPLEMENTED =>
+                {
+                    self.try_scrolling = false;
+                }
+                Err(error) => return Err(error),
+            }
         }
+        for _ in 0..num_batches {
+            // Truncation in skipped row sets is of no concern to the application.
+            if !self.fetch_next(false)? {
+                return Ok(false);
+            }
+        }
+        self.fetch_next(error_for_truncation)
     }
 }
 
@@ -460,23 +961,64 @@ where
     C: AsStatementRef,
 {
     fn drop(&mut self) {
-        unsafe {
-            let mut stmt = self.cursor.as_stmt_ref();
-            if let Err(e) = stmt
-                .unbind_cols()
-                .into_result(&stmt)
-                .and_then(|()| stmt.set_num_rows_fetched(None).into_result(&stmt))
-            {
-                // Avoid panicking, if we already have a panic. We don't want to mask the original
-                // error.
-                if !panicking() {
-                    panic!("Unexpected error unbinding columns: {:?}", e)
-                }
+        // Stop the watchdog, so it never cancels a statement which has already been freed.
+        self.limits = None;
+        if let Err(e) = unbind_buffer_from_statement(self.cursor.as_stmt_ref()) {
+            // Avoid panicking, if we already have a panic. We don't want to mask the original
+            // error.
+            if !panicking() {
+                panic!("Unexpected error unbinding columns: {:?}", e)
             }
         }
     }
 }
 
+/// Emitted by [`BlockCursor::unbind`] and [`BlockCursorPolling::unbind`] if the driver fails to
+/// unbind the buffer. The buffer has been leaked, since the driver may still hold pointers into
+/// it, but the cursor is handed back to the application.
+pub struct UnbindError<C> {
+    /// The cursor the buffer had been bound to. Its columns may still be bound, so it should only
+    /// be used to fetch rows without a block cursor, or be dropped.
+    pub cursor: C,
+    /// The error reported by the driver.
+    pub source: Error,
+}
+
+impl<C> fmt::Debug for UnbindError<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Cursors do not implement `Debug`.
+        f.debug_struct("UnbindError")
+            .field("source", &self.source)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C> fmt::Display for UnbindError<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Unbinding the buffer from the cursor failed. Cause:\n{}",
+            self.source
+        )
+    }
+}
+
+impl<C> std::error::Error for UnbindError<C> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Unbinds all columns and the rows fetched counter from `stmt`. Both are attempted, even if the
+/// first one fails, so as few pointers into the buffer as possible remain with the driver. The
+/// first error is reported.
+fn unbind_buffer_from_statement(mut stmt: StatementRef<'_>) -> Result<(), Error> {
+    let unbind_cols = stmt.unbind_cols().into_result(&stmt);
+    // Safe: Resets the pointer, so the driver no longer writes into the buffer.
+    let unbind_num_rows = unsafe { stmt.set_num_rows_fetched(None) }.into_result(&stmt);
+    unbind_cols.and(unbind_num_rows)
+}
+
 /// The asynchronous sibiling of [`CursorImpl`]. Use this to fetch results in asynchronous code.
 ///
 /// Like [`CursorImpl`] this is an ODBC statement handle in cursor state. However unlike its
@@ -557,14 +1099,41 @@ where
 {
     buffer: B,
     cursor: C,
+    progress: Option<ProgressTracker>,
+    statistics: FetchStatistics,
+    /// `true` if [`Self::poll_fetch`] left a fetch in progress. It must be completed or canceled
+    /// before the buffer is unbound.
+    fetch_still_executing: bool,
 }
 
 impl<C, B> BlockCursorPolling<C, B>
 where
     C: AsStatementRef,
+    B: RowSetBuffer,
 {
     fn new(buffer: B, cursor: C) -> Self {
-        Self { buffer, cursor }
+        Self {
+            buffer,
+            cursor,
+            progress: None,
+            statistics: FetchStatistics::default(),
+            fetch_still_executing: false,
+        }
+    }
+
+    /// Registers a callback which is invoked after each row set fetched. Asynchronous sibling of
+    /// [`BlockCursor::set_progress_callback`].
+    pub fn set_progress_callback(
+        &mut self,
+        callback: impl FnMut(&FetchProgress) -> ControlFlow<()> + Send + 'static,
+    ) {
+        self.progress = Some(ProgressTracker::new(Box::new(callback)));
+    }
+
+    /// Statistics about all row sets fetched so far. Asynchronous sibling of
+    /// [`BlockCursor::statistics`].
+    pub fn statistics(&self) -> &FetchStatistics {
+        &self.statistics
     }
 
     /// Fills the bound buffer with the next row set.
@@ -592,18 +1161,87 @@ where
         error_for_truncation: bool,
         mut sleep: impl Sleep,
     ) -> Result<Option<&B>, Error> {
+        if matches!(&self.progress, Some(progress) if progress.is_stopped()) {
+            return Ok(None);
+        }
         let mut stmt = self.cursor.as_stmt_ref();
-        unsafe {
-            let result = wait_for(|| stmt.fetch(), &mut sleep).await;
-            let has_row = error_handling_for_fetch(result, stmt, error_for_truncation)?;
-            Ok(has_row.then_some(&self.buffer))
+        let has_row = unsafe {
+            let result = wait_for(&mut stmt, |stmt| stmt.fetch(), &mut sleep).await;
+            // Completes a fetch left in progress by `poll_fetch`, too.
+            self.fetch_still_executing = false;
+            error_handling_for_fetch(result, stmt, error_for_truncation)?
+        };
+        if has_row {
+            self.record_row_set();
+        }
+        Ok(has_row.then_some(&self.buffer))
+    }
+
+    /// Fills the bound buffer with the next row set, without waiting for the driver.
+    /// [`Poll::Pending`] if the fetch is still executing. Call again, e.g. after the next poll
+    /// interval, to continue it. Unlike the future returned by [`Self::fetch`], this does not
+    /// borrow the cursor while the fetch is in progress. Should the cursor be dropped before the
+    /// fetch completed, it is canceled.
+    pub(crate) fn poll_fetch(
+        &mut self,
+        error_for_truncation: bool,
+    ) -> Result<Poll<Option<&B>>, Error> {
+        if matches!(&self.progress, Some(progress) if progress.is_stopped()) {
+            return Ok(Poll::Ready(None));
+        }
+        let mut stmt = self.cursor.as_stmt_ref();
+        let result = unsafe { stmt.fetch() };
+        self.fetch_still_executing = matches!(result, SqlResult::StillExecuting);
+        if self.fetch_still_executing {
+            return Ok(Poll::Pending);
+        }
+        let has_row = error_handling_for_fetch(result, stmt, error_for_truncation)?;
+        if has_row {
+            self.record_row_set();
+        }
+        Ok(Poll::Ready(has_row.then_some(&self.buffer)))
+    }
+
+    /// Updates statistics and progress after a row set has been fetched.
+    fn record_row_set(&mut self) {
+        let num_rows = *self.buffer.mut_num_fetch_rows();
+        self.statistics.num_batches += 1;
+        self.statistics.num_rows += num_rows;
+        #[cfg(feature = "metrics")]
+        let num_truncated = self.statistics.num_truncated;
+        self.buffer.record_statistics(&mut self.statistics);
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_batch(num_rows, self.statistics.num_truncated - num_truncated);
+        if let Some(progress) = &mut self.progress {
+            progress.record_batch(num_rows);
+        }
+    }
+
+    /// Unbinds the buffer from the cursor and returns both. Asynchronous sibling of
+    /// [`BlockCursor::unbind`]. Unbinding does not involve a roundtrip, so it is not polled.
+    pub fn unbind(self) -> Result<(C, B), UnbindError<C>> {
+        let mut this = ManuallyDrop::new(self);
+        this.cancel_fetch();
+        let result = unbind_buffer_from_statement(this.cursor.as_stmt_ref());
+        // Safe: Fields are moved out exactly once and `Drop` is not executed for `this`.
+        let (cursor, buffer) = unsafe {
+            ptr::drop_in_place(&mut this.progress);
+            ptr::drop_in_place(&mut this.statistics);
+            (ptr::read(&this.cursor), ptr::read(&this.buffer))
+        };
+        match result {
+            Ok(()) => Ok((cursor, buffer)),
+            Err(source) => {
+                mem::forget(buffer);
+                Err(UnbindError { cursor, source })
+            }
         }
     }
 }
 
 /// Binds a row set buffer to a statment. Implementation is shared between synchronous and
 /// asynchronous cursors.
-unsafe fn bind_row_set_buffer_to_statement(
+pub(crate) unsafe fn bind_row_set_buffer_to_statement(
     mut stmt: StatementRef<'_>,
     row_set_buffer: &mut impl RowSetBuffer,
 ) -> Result<(), Error> {
@@ -651,23 +1289,33 @@ fn error_handling_for_fetch(
     Ok(has_row)
 }
 
+impl<C, B> BlockCursorPolling<C, B>
+where
+    C: AsStatementRef,
+{
+    /// Cancels a fetch left in progress by [`Self::poll_fetch`], so the driver no longer writes
+    /// into the buffer.
+    fn cancel_fetch(&mut self) {
+        if self.fetch_still_executing {
+            self.fetch_still_executing = false;
+            cancel(&mut self.cursor.as_stmt_ref(), |stmt| unsafe {
+                stmt.fetch()
+            });
+        }
+    }
+}
+
 impl<C, B> Drop for BlockCursorPolling<C, B>
 where
     C: AsStatementRef,
 {
     fn drop(&mut self) {
-        unsafe {
-            let mut stmt = self.cursor.as_stmt_ref();
-            if let Err(e) = stmt
-                .unbind_cols()
-                .into_result(&stmt)
-                .and_then(|()| stmt.set_num_rows_fetched(None).into_result(&stmt))
-            {
-                // Avoid panicking, if we already have a panic. We don't want to mask the original
-                // error.
-                if !panicking() {
-                    panic!("Unexpected error unbinding columns: {:?}", e)
-                }
+        self.cancel_fetch();
+        if let Err(e) = unbind_buffer_from_statement(self.cursor.as_stmt_ref()) {
+            // Avoid panicking, if we already have a panic. We don't want to mask the original
+            // error.
+            if !panicking() {
+                panic!("Unexpected error unbinding columns: {:?}", e)
             }
         }
     }
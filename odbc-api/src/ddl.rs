@@ -0,0 +1,240 @@
+use crate::{ColumnDescription, DataType, Error, Nullability, ResultSetMetadata};
+
+/// Controls how SQL generated by this crate, e.g. by [`create_table_statement`] or
+/// [`crate::insert_statement`], spells identifiers, literals and types. The default
+/// implementations follow the SQL standard. [`crate::DbmsDialect`] covers some popular data
+/// sources. Implement this trait and override individual methods to target a data source
+/// deviating from it.
+///
+/// ```
+/// use odbc_api::{AnsiDialect, DataType, SqlDialect};
+///
+/// /// PostgreSQL names binary types `BYTEA`.
+/// struct Postgres;
+///
+/// impl SqlDialect for Postgres {
+///     fn type_name(&self, data_type: DataType) -> Option<String> {
+///         match data_type {
+///             DataType::Binary { .. }
+///             | DataType::Varbinary { .. }
+///             | DataType::LongVarbinary { .. } => Some("BYTEA".to_owned()),
+///             other => AnsiDialect.type_name(other),
+///         }
+///     }
+/// }
+/// ```
+pub trait SqlDialect {
+    /// Quotes a column name, so it may contain whitespace, reserved words or special characters.
+    /// Default implementation encloses the identifier in double quotes (`"`) and escapes double
+    /// quotes within it by doubling them.
+    fn quote_identifier(&self, identifier: &str) -> String {
+        format!("\"{}\"", identifier.replace('"', "\"\""))
+    }
+
+    /// Character string literal holding `text`. Default implementation encloses the text in single
+    /// quotes (`'`) and escapes single quotes within it by doubling them.
+    fn quote_literal(&self, text: &str) -> String {
+        format!("'{}'", text.replace('\'', "''"))
+    }
+
+    /// Changes `query` to return at most `max_rows` rows. Default implementation appends a
+    /// `FETCH FIRST` clause, as specified by the SQL standard.
+    fn limit_query(&self, query: &str, max_rows: usize) -> String {
+        format!("{query} FETCH FIRST {max_rows} ROWS ONLY")
+    }
+
+    /// Expression evaluating to the current date and time. Default is `CURRENT_TIMESTAMP`.
+    fn current_timestamp(&self) -> &'static str {
+        "CURRENT_TIMESTAMP"
+    }
+
+    /// Name of the SQL type used to declare a column of type `data_type`. `None` if there is no
+    /// suitable type.
+    fn type_name(&self, data_type: DataType) -> Option<String> {
+        let with_length = |name: &str, length: usize| {
+            if length == 0 {
+                // Length is unknown. Leave it to the data source to pick a default.
+                name.to_owned()
+            } else {
+                format!("{name}({length})")
+            }
+        };
+        let with_precision = |name: &str, precision: i16| {
+            if precision == 0 {
+                name.to_owned()
+            } else {
+                format!("{name}({precision})")
+            }
+        };
+        let name = match data_type {
+            DataType::Char { length } => with_length("CHAR", length),
+            DataType::WChar { length } => with_length("NCHAR", length),
+            DataType::Varchar { length } => with_length("VARCHAR", length),
+            DataType::WVarchar { length } => with_length("NVARCHAR", length),
+            DataType::LongVarchar { .. } => "CLOB".to_owned(),
+            DataType::WLongVarchar { .. } => "NCLOB".to_owned(),
+            DataType::Xml { .. } => "XML".to_owned(),
+            DataType::Binary { length } => with_length("BINARY", length),
+            DataType::Varbinary { length } => with_length("VARBINARY", length),
+            DataType::LongVarbinary { .. } => "BLOB".to_owned(),
+            DataType::Numeric { precision, scale } => format!("NUMERIC({precision},{scale})"),
+            DataType::Decimal { precision, scale } => format!("DECIMAL({precision},{scale})"),
+            DataType::TinyInt => "TINYINT".to_owned(),
+            DataType::SmallInt => "SMALLINT".to_owned(),
+            DataType::Integer => "INTEGER".to_owned(),
+            DataType::BigInt => "BIGINT".to_owned(),
+            DataType::Float { precision } => with_length("FLOAT", precision),
+            DataType::Real => "REAL".to_owned(),
+            DataType::Double => "DOUBLE PRECISION".to_owned(),
+            DataType::Bit => "BIT".to_owned(),
+            DataType::Date => "DATE".to_owned(),
+            DataType::Time { precision } => with_precision("TIME", precision),
+            DataType::Timestamp { precision } => with_precision("TIMESTAMP", precision),
+            DataType::Unknown | DataType::Other { .. } => return None,
+        };
+        Some(name)
+    }
+}
+
+/// Uses the default implementations of [`SqlDialect`], which follow the SQL standard.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AnsiDialect;
+
+impl SqlDialect for AnsiDialect {}
+
+/// Generates a `CREATE TABLE` statement for a table able to hold the result set described by
+/// `metadata`. Useful for landing the result of a query in a new table on another data source.
+///
+/// Column names are quoted using [`SqlDialect::quote_identifier`]. `table_name` is inserted as is,
+/// so it may be qualified with a schema. Columns which are known not to hold `NULL` are declared
+/// `NOT NULL`.
+///
+/// ```no_run
+/// use odbc_api::{create_table_statement, AnsiDialect, Connection, Error};
+///
+/// fn copy_schema(source: &Connection<'_>, target: &Connection<'_>) -> Result<(), Error> {
+///     let mut cursor = source.execute("SELECT * FROM Birthdays", ())?.unwrap();
+///     let ddl = create_table_statement("BirthdaysCopy", &mut cursor, &AnsiDialect)?;
+///     target.execute(&ddl, ())?;
+///     Ok(())
+/// }
+/// ```
+pub fn create_table_statement(
+    table_name: &str,
+    metadata: &mut impl ResultSetMetadata,
+    dialect: &impl SqlDialect,
+) -> Result<String, Error> {
+    let num_cols: u16 = metadata.num_result_cols()?.try_into().unwrap();
+    let mut columns = Vec::with_capacity(num_cols as usize);
+    for column_number in 1..(num_cols + 1) {
+        let mut desc = ColumnDescription::default();
+        metadata.describe_col(column_number, &mut desc)?;
+        columns.push(desc);
+    }
+    create_table_statement_from_descriptions(table_name, &columns, dialect)
+}
+
+fn create_table_statement_from_descriptions(
+    table_name: &str,
+    columns: &[ColumnDescription],
+    dialect: &impl SqlDialect,
+) -> Result<String, Error> {
+    let mut column_definitions = Vec::with_capacity(columns.len());
+    for (index, column) in columns.iter().enumerate() {
+        let type_name =
+            dialect
+                .type_name(column.data_type)
+                .ok_or(Error::UnsupportedColumnType {
+                    column_number: (index + 1) as u16,
+                    data_type: column.data_type,
+                })?;
+        let name = dialect.quote_identifier(&column.name_to_string().unwrap());
+        let not_null = if column.nullability == Nullability::NoNulls {
+            " NOT NULL"
+        } else {
+            ""
+        };
+        column_definitions.push(format!("{name} {type_name}{not_null}"));
+    }
+    Ok(format!(
+        "CREATE TABLE {table_name} ({})",
+        column_definitions.join(", ")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ColumnDescription, DataType, Error, Nullability};
+
+    use super::{create_table_statement_from_descriptions, AnsiDialect, SqlDialect};
+
+    #[test]
+    fn create_table_with_ansi_dialect() {
+        let columns = [
+            ColumnDescription::new("id", DataType::Integer, Nullability::NoNulls),
+            ColumnDescription::new(
+                "first \"name\"",
+                DataType::Varchar { length: 20 },
+                Nullability::Nullable,
+            ),
+            ColumnDescription::new(
+                "amount",
+                DataType::Decimal {
+                    precision: 10,
+                    scale: 2,
+                },
+                Nullability::Unknown,
+            ),
+            ColumnDescription::new(
+                "created",
+                DataType::Timestamp { precision: 3 },
+                Nullability::Nullable,
+            ),
+        ];
+
+        let ddl = create_table_statement_from_descriptions("test", &columns, &AnsiDialect).unwrap();
+
+        assert_eq!(
+            "CREATE TABLE test (\"id\" INTEGER NOT NULL, \"first \"\"name\"\"\" VARCHAR(20), \
+            \"amount\" DECIMAL(10,2), \"created\" TIMESTAMP(3))",
+            ddl
+        );
+    }
+
+    #[test]
+    fn custom_quoting() {
+        struct Brackets;
+
+        impl SqlDialect for Brackets {
+            fn quote_identifier(&self, identifier: &str) -> String {
+                format!("[{identifier}]")
+            }
+        }
+
+        let columns = [ColumnDescription::new(
+            "a",
+            DataType::Date,
+            Nullability::Nullable,
+        )];
+        let ddl = create_table_statement_from_descriptions("test", &columns, &Brackets).unwrap();
+
+        assert_eq!("CREATE TABLE test ([a] DATE)", ddl);
+    }
+
+    #[test]
+    fn unknown_type_is_an_error() {
+        let columns = [
+            ColumnDescription::new("a", DataType::Integer, Nullability::Nullable),
+            ColumnDescription::new("b", DataType::Unknown, Nullability::Nullable),
+        ];
+        let result = create_table_statement_from_descriptions("test", &columns, &AnsiDialect);
+
+        assert!(matches!(
+            result,
+            Err(Error::UnsupportedColumnType {
+                column_number: 2,
+                data_type: DataType::Unknown
+            })
+        ));
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{AnsiDialect, DataType, SqlDialect, UpsertSyntax};
+
+/// [`SqlDialect`] of a popular database management system. Use [`Self::from_dbms_name`], or
+/// [`crate::Connection::dialect`], to pick the one fitting the connected data source, so SQL
+/// generated by this crate is portable between them.
+///
+/// ```
+/// use odbc_api::{DbmsDialect, SqlDialect};
+///
+/// let dialect = DbmsDialect::from_dbms_name("Microsoft SQL Server");
+/// assert_eq!(
+///     "SELECT TOP 10 a FROM t ORDER BY a",
+///     dialect.limit_query("SELECT a FROM t ORDER BY a", 10)
+/// );
+///
+/// let dialect = DbmsDialect::from_dbms_name("MariaDB");
+/// assert_eq!("`a``b`", dialect.quote_identifier("a`b"));
+/// assert_eq!(r"'it''s \\'", dialect.quote_literal(r"it's \"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DbmsDialect {
+    /// Follows the SQL standard, see [`AnsiDialect`]. Used for unknown data sources.
+    #[default]
+    Ansi,
+    /// Microsoft SQL Server.
+    MsSql,
+    /// MySQL or MariaDB.
+    MySql,
+    /// PostgreSQL.
+    PostgreSql,
+    /// SQLite.
+    Sqlite,
+    /// IBM Db2.
+    Db2,
+}
+
+impl DbmsDialect {
+    /// Picks the dialect of a database management system, as reported by
+    /// [`crate::Connection::database_management_system_name`]. Unknown names yield
+    /// [`DbmsDialect::Ansi`].
+    pub fn from_dbms_name(dbms_name: &str) -> Self {
+        match dbms_name {
+            "Microsoft SQL Server" => DbmsDialect::MsSql,
+            "MySQL" | "MariaDB" => DbmsDialect::MySql,
+            "PostgreSQL" => DbmsDialect::PostgreSql,
+            "SQLite" => DbmsDialect::Sqlite,
+            // DB2 appends the platform to its name, e.g. `DB2/LINUXX8664`.
+            name if name.starts_with("DB2") => DbmsDialect::Db2,
+            _ => DbmsDialect::Ansi,
+        }
+    }
+
+    /// Syntax used for statements generated by [`crate::Upsert`].
+    pub fn upsert_syntax(self) -> UpsertSyntax {
+        match self {
+            DbmsDialect::PostgreSql | DbmsDialect::Sqlite => UpsertSyntax::OnConflict,
+            DbmsDialect::MySql => UpsertSyntax::OnDuplicateKey,
+            DbmsDialect::Ansi | DbmsDialect::MsSql | DbmsDialect::Db2 => UpsertSyntax::Merge,
+        }
+    }
+}
+
+impl SqlDialect for DbmsDialect {
+    fn quote_identifier(&self, identifier: &str) -> String {
+        match self {
+            // Double quotes only delimit identifiers in MySQL if `ANSI_QUOTES` is set.
+            DbmsDialect::MySql => format!("`{}`", identifier.replace('`', "``")),
+            _ => AnsiDialect.quote_identifier(identifier),
+        }
+    }
+
+    fn quote_literal(&self, text: &str) -> String {
+        match self {
+            // Backslashes start escape sequences in MySQL, unless `NO_BACKSLASH_ESCAPES` is set.
+            DbmsDialect::MySql => format!("'{}'", text.replace('\\', "\\\\").replace('\'', "''")),
+            _ => AnsiDialect.quote_literal(text),
+        }
+    }
+
+    fn limit_query(&self, query: &str, max_rows: usize) -> String {
+        match self {
+            // `FETCH FIRST` requires an `ORDER BY` clause in Microsoft SQL Server.
+            DbmsDialect::MsSql => insert_top(query, max_rows),
+            DbmsDialect::MySql | DbmsDialect::PostgreSql | DbmsDialect::Sqlite => {
+                format!("{query} LIMIT {max_rows}")
+            }
+            DbmsDialect::Ansi | DbmsDialect::Db2 => AnsiDialect.limit_query(query, max_rows),
+        }
+    }
+
+    fn current_timestamp(&self) -> &'static str {
+        match self {
+            // `CURRENT_TIMESTAMP` is a `DATETIME` with a resolution of only about 3 milliseconds.
+            DbmsDialect::MsSql => "SYSDATETIME()",
+            DbmsDialect::Db2 => "CURRENT TIMESTAMP",
+            _ => AnsiDialect.current_timestamp(),
+        }
+    }
+
+    fn type_name(&self, data_type: DataType) -> Option<String> {
+        let name = match (self, data_type) {
+            (DbmsDialect::MsSql, DataType::LongVarchar { .. }) => "VARCHAR(MAX)".to_owned(),
+            (DbmsDialect::MsSql, DataType::WLongVarchar { .. }) => "NVARCHAR(MAX)".to_owned(),
+            (DbmsDialect::MsSql, DataType::LongVarbinary { .. }) => "VARBINARY(MAX)".to_owned(),
+            (DbmsDialect::MsSql, DataType::Timestamp { precision }) => {
+                format!("DATETIME2({})", precision.clamp(0, 7))
+            }
+            (DbmsDialect::MySql, DataType::LongVarchar { .. })
+            | (DbmsDialect::MySql, DataType::WLongVarchar { .. }) => "LONGTEXT".to_owned(),
+            (DbmsDialect::MySql, DataType::LongVarbinary { .. }) => "LONGBLOB".to_owned(),
+            (DbmsDialect::MySql, DataType::Timestamp { precision }) => {
+                format!("DATETIME({})", precision.clamp(0, 6))
+            }
+            (DbmsDialect::PostgreSql, DataType::LongVarchar { .. })
+            | (DbmsDialect::PostgreSql, DataType::WLongVarchar { .. }) => "TEXT".to_owned(),
+            (
+                DbmsDialect::PostgreSql,
+                DataType::Binary { .. }
+                | DataType::Varbinary { .. }
+                | DataType::LongVarbinary { .. },
+            ) => "BYTEA".to_owned(),
+            (_, other) => return AnsiDialect.type_name(other),
+        };
+        Some(name)
+    }
+}
+
+/// Inserts a `TOP` clause after the leading `SELECT` or `SELECT DISTINCT` of `query`. Other
+/// queries, e.g. unions, are wrapped into a derived table. Wrapping is avoided if possible, because
+/// `ORDER BY` is not allowed in derived tables.
+fn insert_top(query: &str, max_rows: usize) -> String {
+    let trimmed = query.trim_start();
+    let starts_with_keyword = |text: &str, keyword: &str| {
+        text.len() > keyword.len()
+            && text.is_char_boundary(keyword.len())
+            && text[..keyword.len()].eq_ignore_ascii_case(keyword)
+            && text[keyword.len()..].starts_with(char::is_whitespace)
+    };
+    if !starts_with_keyword(trimmed, "SELECT") {
+        return format!("SELECT TOP {max_rows} * FROM ({query}) AS limited");
+    }
+    let mut split = "SELECT".len();
+    let rest = trimmed[split..].trim_start();
+    if starts_with_keyword(rest, "DISTINCT") {
+        split = trimmed.len() - rest.len() + "DISTINCT".len();
+    }
+    format!("{} TOP {max_rows}{}", &trimmed[..split], &trimmed[split..])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DataType, SqlDialect, UpsertSyntax};
+
+    use super::DbmsDialect;
+
+    #[test]
+    fn unknown_dbms_follows_standard() {
+        let dialect = DbmsDialect::from_dbms_name("Some DBMS");
+
+        assert_eq!(DbmsDialect::Ansi, dialect);
+        assert_eq!("'it''s'", dialect.quote_literal("it's"));
+        assert_eq!(
+            "SELECT a FROM t FETCH FIRST 5 ROWS ONLY",
+            dialect.limit_query("SELECT a FROM t", 5)
+        );
+        assert_eq!(UpsertSyntax::Merge, dialect.upsert_syntax());
+    }
+
+    #[test]
+    fn limit_query_with_top() {
+        let limit = |query| DbmsDialect::MsSql.limit_query(query, 3);
+
+        assert_eq!("select TOP 3 a FROM t", limit("select a FROM t"));
+        assert_eq!(
+            "SELECT DISTINCT TOP 3 a FROM t",
+            limit(" SELECT DISTINCT a FROM t")
+        );
+        assert_eq!(
+            "SELECT TOP 3 * FROM ((SELECT a FROM t) UNION (SELECT a FROM u)) AS limited",
+            limit("(SELECT a FROM t) UNION (SELECT a FROM u)")
+        );
+    }
+
+    #[test]
+    fn detect_db2_on_any_platform() {
+        let dialect = DbmsDialect::from_dbms_name("DB2/LINUXX8664");
+
+        assert_eq!(DbmsDialect::Db2, dialect);
+        assert_eq!("CURRENT TIMESTAMP", dialect.current_timestamp());
+    }
+
+    #[test]
+    fn type_names_per_dialect() {
+        let blob = DataType::LongVarbinary { length: 0 };
+        let timestamp = DataType::Timestamp { precision: 9 };
+
+        assert_eq!(
+            Some("VARBINARY(MAX)".to_owned()),
+            DbmsDialect::MsSql.type_name(blob)
+        );
+        assert_eq!(
+            Some("DATETIME2(7)".to_owned()),
+            DbmsDialect::MsSql.type_name(timestamp)
+        );
+        assert_eq!(
+            Some("BYTEA".to_owned()),
+            DbmsDialect::PostgreSql.type_name(blob)
+        );
+        assert_eq!(
+            Some("DATETIME(6)".to_owned()),
+            DbmsDialect::MySql.type_name(timestamp)
+        );
+        assert_eq!(Some("BLOB".to_owned()), DbmsDialect::Sqlite.type_name(blob));
+    }
+
+    #[test]
+    fn upsert_syntax_matches_dbms_name() {
+        for name in ["Microsoft SQL Server", "MariaDB", "PostgreSQL", "SQLite"] {
+            assert_eq!(
+                UpsertSyntax::from_dbms_name(name),
+                DbmsDialect::from_dbms_name(name).upsert_syntax()
+            );
+        }
+    }
+}
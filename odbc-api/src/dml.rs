@@ -0,0 +1,74 @@
+use crate::SqlDialect;
+
+/// Generates a parameterized `INSERT` statement with one placeholder for each column, e.g.
+/// `INSERT INTO Birthdays ("name", "birthday") VALUES (?, ?)`. The placeholders are in the same
+/// order as the column names, so the statement fits a buffer binding one column for each name,
+/// e.g. a [`crate::buffers::ColumnarAnyBuffer`] used with [`crate::Prepared::into_column_inserter`].
+///
+/// Column names are quoted using [`SqlDialect::quote_identifier`]. `table_name` is inserted as is,
+/// so it may be qualified with a schema.
+///
+/// ```
+/// use odbc_api::{insert_statement, AnsiDialect};
+///
+/// let sql = insert_statement("Birthdays", &["name", "birthday"], &AnsiDialect);
+/// assert_eq!(r#"INSERT INTO Birthdays ("name", "birthday") VALUES (?, ?)"#, sql);
+/// ```
+///
+/// # Panics
+///
+/// If `column_names` is empty.
+pub fn insert_statement(
+    table_name: &str,
+    column_names: &[impl AsRef<str>],
+    dialect: &impl SqlDialect,
+) -> String {
+    assert!(
+        !column_names.is_empty(),
+        "An INSERT statement requires at least one column."
+    );
+    let columns: Vec<_> = column_names
+        .iter()
+        .map(|name| dialect.quote_identifier(name.as_ref()))
+        .collect();
+    let placeholders = vec!["?"; column_names.len()];
+    format!(
+        "INSERT INTO {table_name} ({}) VALUES ({})",
+        columns.join(", "),
+        placeholders.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AnsiDialect, SqlDialect};
+
+    use super::insert_statement;
+
+    #[test]
+    fn quote_column_names() {
+        let sql = insert_statement("s.t", &["a b".to_owned(), "c\"d".to_owned()], &AnsiDialect);
+        assert_eq!(r#"INSERT INTO s.t ("a b", "c""d") VALUES (?, ?)"#, sql);
+    }
+
+    #[test]
+    fn dialect_specific_quoting() {
+        struct Backticks;
+
+        impl SqlDialect for Backticks {
+            fn quote_identifier(&self, identifier: &str) -> String {
+                format!("`{identifier}`")
+            }
+        }
+
+        let sql = insert_statement("t", &["a"], &Backticks);
+        assert_eq!("INSERT INTO t (`a`) VALUES (?)", sql);
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_columns() {
+        let no_columns: [&str; 0] = [];
+        insert_statement("t", &no_columns, &AnsiDialect);
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{
+    handles::{self, SqlResult, State},
+    Error,
+};
+
+/// `SQL_AM_NONE`. Asynchronous execution is not supported at all.
+const ASYNC_MODE_NONE: u32 = 0;
+/// `SQL_ASYNC_DBC_CAPABLE`
+const ASYNC_DBC_CAPABLE: u32 = 1;
+/// `SQL_ASYNC_NOTIFICATION_CAPABLE`
+const ASYNC_NOTIFICATION_CAPABLE: u32 = 1;
+/// `SQL_GD_ANY_COLUMN` bit of `SQL_GETDATA_EXTENSIONS`.
+const GETDATA_ANY_COLUMN: u32 = 0x1;
+/// `SQL_GD_ANY_ORDER` bit of `SQL_GETDATA_EXTENSIONS`.
+const GETDATA_ANY_ORDER: u32 = 0x2;
+/// `SQL_GD_BLOCK` bit of `SQL_GETDATA_EXTENSIONS`.
+const GETDATA_BLOCK: u32 = 0x4;
+/// `SQL_GD_BOUND` bit of `SQL_GETDATA_EXTENSIONS`.
+const GETDATA_BOUND: u32 = 0x8;
+/// `SQL_GD_OUTPUT_PARAMS` bit of `SQL_GETDATA_EXTENSIONS`.
+const GETDATA_OUTPUT_PARAMS: u32 = 0x10;
+
+/// Features introduced with ODBC 3.8, which may or may not be supported by the driver. Returned
+/// by [`crate::Connection::odbc_3_8_features`]. Allows higher level code to fall back to a
+/// different strategy, rather than failing at runtime.
+///
+/// Note that the application must also declare ODBC 3.8 on the environment to use any of these
+/// (see [`crate::Environment::odbc_version`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Odbc38Features {
+    /// Statements can be executed asynchronously, e.g. with
+    /// [`crate::Connection::preallocate`] and [`crate::Preallocated::into_polling`].
+    pub async_statements: bool,
+    /// Connection level functions like connecting, committing or disconnecting can be executed
+    /// asynchronously.
+    pub async_connection_functions: bool,
+    /// The driver is able to notify the application about the completion of an asynchronous
+    /// operation, rather than the application having to poll.
+    pub async_notification: bool,
+    /// Output parameters can be retrieved in parts using `SQLGetData`, after the statement has
+    /// been executed. Allows for fetching large output values without knowing their size upfront.
+    pub streamed_output_parameters: bool,
+}
+
+impl Odbc38Features {
+    pub(crate) fn fetch(connection: &handles::Connection<'_>) -> Result<Self, Error> {
+        let async_mode = info_or(connection, connection.async_mode(), ASYNC_MODE_NONE)?;
+        let async_dbc = info_or(connection, connection.async_dbc_functions(), 0)?;
+        let async_notification = info_or(connection, connection.async_notification(), 0)?;
+        let get_data_extensions = info_or(connection, connection.get_data_extensions(), 0)?;
+        Ok(Self {
+            async_statements: async_mode != ASYNC_MODE_NONE,
+            async_connection_functions: async_dbc == ASYNC_DBC_CAPABLE,
+            async_notification: async_notification == ASYNC_NOTIFICATION_CAPABLE,
+            streamed_output_parameters: get_data_extensions & GETDATA_OUTPUT_PARAMS != 0,
+        })
+    }
+}
+
+/// Extensions to `SQLGetData` supported by the driver. Returned by
+/// [`crate::Connection::get_data_extensions`]. Tells the application which columns it may leave
+/// unbound, if it binds only some columns of a result set to a buffer and fetches the others with
+/// [`crate::BlockCursor::fetch_with_unbound`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GetDataExtensions {
+    /// Unbound columns may precede bound ones. Otherwise only columns after the last bound column
+    /// can be fetched using `SQLGetData`.
+    pub any_column: bool,
+    /// Unbound columns can be fetched in any order. Otherwise they must be fetched in ascending
+    /// order of their column index.
+    pub any_order: bool,
+    /// `SQLGetData` can be called for rows of a row set with more than one row.
+    pub block: bool,
+    /// `SQLGetData` can be called for bound columns, too.
+    pub bound: bool,
+}
+
+impl GetDataExtensions {
+    pub(crate) fn fetch(connection: &handles::Connection<'_>) -> Result<Self, Error> {
+        let bits = connection.get_data_extensions().into_result(connection)?;
+        Ok(Self::from_bits(bits))
+    }
+
+    fn from_bits(bits: u32) -> Self {
+        Self {
+            any_column: bits & GETDATA_ANY_COLUMN != 0,
+            any_order: bits & GETDATA_ANY_ORDER != 0,
+            block: bits & GETDATA_BLOCK != 0,
+            bound: bits & GETDATA_BOUND != 0,
+        }
+    }
+
+    /// `true` if the unbound column `column` can be fetched using `SQLGetData`, given the column
+    /// indices bound to a buffer. Column indices start at `1`.
+    pub fn can_fetch_unbound(&self, bound_columns: &[u16], column: u16) -> bool {
+        if bound_columns.contains(&column) {
+            return self.bound;
+        }
+        self.any_column || bound_columns.iter().all(|&bound| bound < column)
+    }
+}
+
+/// Drivers predating ODBC 3.8 do not know about the information types introduced with it. We
+/// treat them as if the feature in question is not supported, rather than failing.
+fn info_or(
+    connection: &handles::Connection<'_>,
+    result: SqlResult<u32>,
+    default: u32,
+) -> Result<u32, Error> {
+    match result.into_result(connection) {
+        Err(Error::Diagnostics { record, .. })
+            if record.state == State::INVALID_INFORMATION_TYPE =>
+        {
+            Ok(default)
+        }
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GetDataExtensions;
+
+    #[test]
+    fn unbound_columns_must_follow_bound_ones_by_default() {
+        let minimal = GetDataExtensions::from_bits(0);
+        assert!(minimal.can_fetch_unbound(&[1, 3], 4));
+        assert!(!minimal.can_fetch_unbound(&[1, 3], 2));
+        assert!(!minimal.can_fetch_unbound(&[1, 3], 3));
+
+        let any_column = GetDataExtensions::from_bits(0x1);
+        assert!(any_column.can_fetch_unbound(&[1, 3], 2));
+        assert!(!any_column.can_fetch_unbound(&[1, 3], 3));
+    }
+}
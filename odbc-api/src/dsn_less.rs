@@ -0,0 +1,306 @@
+use std::borrow::Cow;
+
+use crate::{escape_attribute_value, DbmsDialect, DriverInfo};
+
+/// How to authenticate, if connecting without a data source name using e.g.
+/// [`crate::Environment::connect_sql_server`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Authentication<'a> {
+    /// User name and password, passed as `UID` and `PWD`.
+    Password { user: &'a str, password: &'a str },
+    /// Credentials are not part of the connection string. Microsoft SQL Server uses the Windows
+    /// account of the process (`Trusted_Connection=yes`), other drivers fall back to their own
+    /// defaults, e.g. a password file or Kerberos.
+    Integrated,
+}
+
+/// Picks the installed driver best suited to connect to `dbms`, i.e. the latest version of the
+/// driver of the vendor, preferring Unicode over ANSI drivers. `None` if none is installed, or if
+/// connecting to `dbms` by host name is not supported.
+pub(crate) fn select_driver(dbms: DbmsDialect, drivers: &[DriverInfo]) -> Option<&str> {
+    drivers
+        .iter()
+        .map(|driver| driver.description.as_str())
+        .filter_map(|name| driver_preference(dbms, name).map(|preference| (preference, name)))
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, name)| name)
+}
+
+/// Connection string for `dbms` without a data source name. `host` may carry a port, separated by
+/// a colon, e.g. `localhost:1433`. IPv6 addresses must be enclosed in brackets if followed by a
+/// port, e.g. `[::1]:1433`.
+pub(crate) fn connection_string(
+    dbms: DbmsDialect,
+    driver: &str,
+    host: &str,
+    database: &str,
+    authentication: Authentication<'_>,
+) -> String {
+    let (address, port) = split_host(host);
+    // IPv6 addresses are bracketed, so their colons are not mistaken for a port separator.
+    let server = if address.contains(':') {
+        Cow::Owned(format!("[{address}]"))
+    } else {
+        Cow::Borrowed(address)
+    };
+    let server = escape_attribute_value(&server);
+    // The driver name is always enclosed in braces, so only closing braces need to be escaped.
+    let mut connection_string = format!("Driver={{{}}};", driver.replace('}', "}}"));
+    match (dbms, port) {
+        // SQL Server expects the port as part of the server, separated by a comma.
+        (DbmsDialect::MsSql, Some(port)) => {
+            connection_string += &format!("Server={server},{port};")
+        }
+        (_, Some(port)) => connection_string += &format!("Server={server};Port={port};"),
+        (_, None) => connection_string += &format!("Server={server};"),
+    }
+    connection_string += &format!("Database={};", escape_attribute_value(database));
+    match authentication {
+        Authentication::Password { user, password } => {
+            connection_string += &format!(
+                "UID={};PWD={};",
+                escape_attribute_value(user),
+                escape_attribute_value(password)
+            )
+        }
+        Authentication::Integrated if dbms == DbmsDialect::MsSql => {
+            connection_string += "Trusted_Connection=yes;"
+        }
+        Authentication::Integrated => (),
+    }
+    connection_string
+}
+
+/// Splits `host` into address and port. IPv6 addresses are recognized with brackets, e.g.
+/// `[::1]:1433`, or bare without a port, e.g. `::1`. Brackets are stripped from the address.
+fn split_host(host: &str) -> (&str, Option<&str>) {
+    let is_port = |port: &&str| !port.is_empty() && port.bytes().all(|b| b.is_ascii_digit());
+    if let Some((address, rest)) = host
+        .strip_prefix('[')
+        .and_then(|bracketed| bracketed.split_once(']'))
+    {
+        return (address, rest.strip_prefix(':').filter(is_port));
+    }
+    match host.split_once(':') {
+        // More than one colon is a bare IPv6 address, which can not carry a port.
+        Some((address, port)) if !port.contains(':') && is_port(&port) => (address, Some(port)),
+        _ => (host, None),
+    }
+}
+
+/// Ranks a driver by how well it fits `dbms`. Higher is better, `None` if it does not fit at all.
+/// The first element distinguishes kinds of drivers, the second compares versions of the same kind.
+fn driver_preference(dbms: DbmsDialect, name: &str) -> Option<(u8, Vec<u32>)> {
+    // Driver managers do not agree on the case, e.g. `PostgreSQL UNICODE` on Linux.
+    let lower = name.to_ascii_lowercase();
+    let kind = match dbms {
+        DbmsDialect::MsSql => {
+            if lower.starts_with("odbc driver") && lower.ends_with("for sql server") {
+                3
+            } else if lower.starts_with("sql server native client") {
+                2
+            } else if lower == "sql server" {
+                1
+            } else if lower == "freetds" {
+                0
+            } else {
+                return None;
+            }
+        }
+        DbmsDialect::PostgreSql => {
+            if lower.starts_with("postgresql unicode") {
+                1
+            } else if lower.starts_with("postgresql") {
+                0
+            } else {
+                return None;
+            }
+        }
+        DbmsDialect::MySql => {
+            if lower.starts_with("mariadb") {
+                2
+            } else if lower.starts_with("mysql") && lower.contains("unicode") {
+                1
+            } else if lower.starts_with("mysql") {
+                0
+            } else {
+                return None;
+            }
+        }
+        DbmsDialect::Ansi | DbmsDialect::Sqlite | DbmsDialect::Db2 => return None,
+    };
+    Some((kind, version(name)))
+}
+
+/// All numbers within the name of a driver, e.g. `[3, 1]` for `MariaDB ODBC 3.1 Driver`.
+fn version(name: &str) -> Vec<u32> {
+    name.split(|c: char| !c.is_ascii_digit())
+        .filter_map(|digits| digits.parse().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{DbmsDialect, DriverInfo};
+
+    use super::{connection_string, select_driver, split_host, Authentication};
+
+    fn drivers(names: &[&str]) -> Vec<DriverInfo> {
+        names
+            .iter()
+            .map(|name| DriverInfo {
+                description: name.to_string(),
+                attributes: HashMap::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn prefer_latest_sql_server_driver() {
+        let drivers = drivers(&[
+            "SQL Server",
+            "ODBC Driver 18 for SQL Server",
+            "PostgreSQL Unicode",
+            "ODBC Driver 17 for SQL Server",
+        ]);
+
+        assert_eq!(
+            Some("ODBC Driver 18 for SQL Server"),
+            select_driver(DbmsDialect::MsSql, &drivers)
+        );
+        assert_eq!(
+            Some("PostgreSQL Unicode"),
+            select_driver(DbmsDialect::PostgreSql, &drivers)
+        );
+        assert_eq!(None, select_driver(DbmsDialect::MySql, &drivers));
+    }
+
+    #[test]
+    fn prefer_unicode_driver() {
+        let drivers = drivers(&[
+            "PostgreSQL ANSI",
+            "PostgreSQL UNICODE",
+            "MySQL ODBC 8.0 ANSI Driver",
+            "MySQL ODBC 8.0 Unicode Driver",
+        ]);
+
+        assert_eq!(
+            Some("PostgreSQL UNICODE"),
+            select_driver(DbmsDialect::PostgreSql, &drivers)
+        );
+        assert_eq!(
+            Some("MySQL ODBC 8.0 Unicode Driver"),
+            select_driver(DbmsDialect::MySql, &drivers)
+        );
+    }
+
+    #[test]
+    fn sql_server_connection_string() {
+        let password = Authentication::Password {
+            user: "SA",
+            password: "My@Test;Password1",
+        };
+
+        let with_port = connection_string(
+            DbmsDialect::MsSql,
+            "ODBC Driver 18 for SQL Server",
+            "localhost:1433",
+            "master",
+            password,
+        );
+        let integrated = connection_string(
+            DbmsDialect::MsSql,
+            "ODBC Driver 18 for SQL Server",
+            "localhost",
+            "master",
+            Authentication::Integrated,
+        );
+
+        assert_eq!(
+            "Driver={ODBC Driver 18 for SQL Server};Server=localhost,1433;Database=master;\
+            UID=SA;PWD={My@Test;Password1};",
+            with_port
+        );
+        assert_eq!(
+            "Driver={ODBC Driver 18 for SQL Server};Server=localhost;Database=master;\
+            Trusted_Connection=yes;",
+            integrated
+        );
+    }
+
+    #[test]
+    fn postgres_connection_string() {
+        let connection_string = connection_string(
+            DbmsDialect::PostgreSql,
+            "PostgreSQL Unicode",
+            "db.example.com:5432",
+            "test",
+            Authentication::Password {
+                user: "postgres",
+                password: "password",
+            },
+        );
+
+        assert_eq!(
+            "Driver={PostgreSQL Unicode};Server=db.example.com;Port=5432;Database=test;\
+            UID=postgres;PWD=password;",
+            connection_string
+        );
+    }
+
+    #[test]
+    fn split_host_and_port() {
+        assert_eq!(("localhost", Some("1433")), split_host("localhost:1433"));
+        assert_eq!(("localhost", None), split_host("localhost"));
+        assert_eq!(("::1", Some("1433")), split_host("[::1]:1433"));
+        assert_eq!(("::1", None), split_host("[::1]"));
+        assert_eq!(("::1", None), split_host("::1"));
+        assert_eq!(("fe80::1:1433", None), split_host("fe80::1:1433"));
+    }
+
+    #[test]
+    fn ipv6_connection_string() {
+        let with_port = connection_string(
+            DbmsDialect::MsSql,
+            "ODBC Driver 18 for SQL Server",
+            "[::1]:1433",
+            "master",
+            Authentication::Integrated,
+        );
+        let without_port = connection_string(
+            DbmsDialect::PostgreSql,
+            "PostgreSQL Unicode",
+            "::1",
+            "test",
+            Authentication::Integrated,
+        );
+
+        assert_eq!(
+            "Driver={ODBC Driver 18 for SQL Server};Server=[::1],1433;Database=master;\
+            Trusted_Connection=yes;",
+            with_port
+        );
+        assert_eq!(
+            "Driver={PostgreSQL Unicode};Server=[::1];Database=test;",
+            without_port
+        );
+    }
+
+    #[test]
+    fn escape_closing_brace_in_driver_name() {
+        let connection_string = connection_string(
+            DbmsDialect::MySql,
+            "MySQL {Unicode} Driver",
+            "localhost",
+            "test",
+            Authentication::Integrated,
+        );
+
+        assert_eq!(
+            "Driver={MySQL {Unicode}} Driver};Server=localhost;Database=test;",
+            connection_string
+        );
+    }
+}
@@ -1,9 +1,10 @@
-use std::{cmp::max, collections::HashMap, ptr::null_mut, sync::Mutex};
+use std::{cmp::max, collections::HashMap, ptr::null_mut, sync::Mutex, time::Duration};
 
 use crate::{
+    dsn_less::{self, select_driver},
     error::ExtendResult,
     handles::{self, log_diagnostics, OutputStringBuffer, SqlResult, SqlText, State, SzBuffer},
-    Connection, DriverCompleteOption, Error,
+    AccessToken, Authentication, Connection, DbmsDialect, DriverCompleteOption, Error, Failover,
 };
 use log::debug;
 use odbc_sys::{AttrCpMatch, AttrOdbcVersion, FetchOrientation, HWnd};
@@ -18,6 +19,20 @@ const ODBC_API_VERSION: AttrOdbcVersion = AttrOdbcVersion::Odbc3_80;
 #[cfg(feature = "odbc_version_3_5")]
 const ODBC_API_VERSION: AttrOdbcVersion = AttrOdbcVersion::Odbc3;
 
+/// State of the process wide environment returned by [`Environment::singleton`].
+struct Singleton {
+    /// ODBC version declared then the environment is created.
+    version: AttrOdbcVersion,
+    /// `None` until the first call to [`Environment::singleton`]. The environment is leaked
+    /// intentionally, it lives until the process terminates.
+    environment: Option<&'static Environment>,
+}
+
+static SINGLETON: Mutex<Singleton> = Mutex::new(Singleton {
+    version: ODBC_API_VERSION,
+    environment: None,
+});
+
 /// An ODBC 3.8 environment.
 ///
 /// Associated with an `Environment` is any information that is global in nature, such as:
@@ -40,6 +55,8 @@ pub struct Environment {
     /// If multiple fallible operations are executed in parallel, we need the mutex to ensure the
     /// errors are fetched by the correct thread.
     internal_state: Mutex<()>,
+    /// ODBC version declared to the driver manager.
+    version: AttrOdbcVersion,
 }
 
 unsafe impl Sync for Environment {}
@@ -94,6 +111,8 @@ impl Environment {
     pub unsafe fn set_connection_pooling(
         scheme: odbc_sys::AttrConnectionPooling,
     ) -> Result<(), Error> {
+        #[cfg(feature = "dynamic_loading")]
+        handles::ensure_driver_manager()?;
         match handles::Environment::set_connection_pooling(scheme) {
             SqlResult::Error { .. } => Err(Error::FailedSettingConnectionPooling),
             SqlResult::Success(()) | SqlResult::SuccessWithInfo(()) => Ok(()),
@@ -136,6 +155,14 @@ impl Environment {
     ///
     /// Creating one environment in your binary is safe however.
     pub fn new() -> Result<Self, Error> {
+        Self::with_odbc_version(ODBC_API_VERSION)
+    }
+
+    /// Like [`Self::new`], but declares `version` to the driver manager, rather than ODBC 3.8 (or
+    /// ODBC 3.0 if the `odbc_version_3_5` feature is active).
+    pub fn with_odbc_version(version: AttrOdbcVersion) -> Result<Self, Error> {
+        #[cfg(feature = "dynamic_loading")]
+        handles::ensure_driver_manager()?;
         let result = handles::Environment::new();
 
         let environment = match result {
@@ -151,7 +178,7 @@ impl Environment {
         debug!("ODBC Environment created.");
 
         let result = environment
-            .declare_version(ODBC_API_VERSION)
+            .declare_version(version)
             .into_result(&environment);
 
         // Translate invalid attribute into a more meaningful error, provided the additional
@@ -171,9 +198,63 @@ impl Environment {
         Ok(Self {
             environment,
             internal_state: Mutex::new(()),
+            version,
         })
     }
 
+    /// ODBC version declared to the driver manager then creating the environment. Features
+    /// introduced with ODBC 3.8 (see [`crate::Connection::odbc_3_8_features`]) are only available
+    /// if this is [`AttrOdbcVersion::Odbc3_80`].
+    pub fn odbc_version(&self) -> AttrOdbcVersion {
+        self.version
+    }
+
+    /// Process wide ODBC environment. Created on first use and never dropped. ODBC recommends
+    /// using only one environment per process, so binaries (as opposed to libraries) may prefer
+    /// this over creating their own environment using [`Self::new`] and storing it in a `static`.
+    ///
+    /// Creating the environment is only attempted again in subsequent calls, if it failed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use odbc_api::Environment;
+    ///
+    /// let env = Environment::singleton()?;
+    /// let conn = env.connect("YourDatabase", "SA", "My@Test@Password1")?;
+    /// # Ok::<(), odbc_api::Error>(())
+    /// ```
+    pub fn singleton() -> Result<&'static Environment, Error> {
+        let mut singleton = SINGLETON.lock().unwrap();
+        if let Some(environment) = singleton.environment {
+            return Ok(environment);
+        }
+        let environment: &'static Environment =
+            Box::leak(Box::new(Self::with_odbc_version(singleton.version)?));
+        singleton.environment = Some(environment);
+        Ok(environment)
+    }
+
+    /// Sets the ODBC version declared by the environment returned from [`Self::singleton`]. Must
+    /// be called before the first call to [`Self::singleton`], otherwise
+    /// [`Error::SingletonAlreadyInitialized`] is returned.
+    ///
+    /// ```no_run
+    /// use odbc_api::{Environment, sys::AttrOdbcVersion};
+    ///
+    /// Environment::set_singleton_odbc_version(AttrOdbcVersion::Odbc3)?;
+    /// let env = Environment::singleton()?;
+    /// # Ok::<(), odbc_api::Error>(())
+    /// ```
+    pub fn set_singleton_odbc_version(version: AttrOdbcVersion) -> Result<(), Error> {
+        let mut singleton = SINGLETON.lock().unwrap();
+        if singleton.environment.is_some() {
+            return Err(Error::SingletonAlreadyInitialized);
+        }
+        singleton.version = version;
+        Ok(())
+    }
+
     /// Allocates a connection handle and establishes connections to a driver and a data source.
     ///
     /// * See [Connecting with SQLConnect][1]
@@ -253,6 +334,158 @@ impl Environment {
         Ok(Connection::new(connection))
     }
 
+    /// Same as [`Self::connect_with_connection_string`], but tries again up to `retries` times,
+    /// waiting `delay` before each new attempt, e.g. while a database started alongside the
+    /// application is not ready yet. Failed attempts are logged as warnings. If all attempts fail,
+    /// the error of the last one is returned. See [`crate::Failover`] to try several data sources.
+    ///
+    /// ```no_run
+    /// use odbc_api::Environment;
+    /// use std::time::Duration;
+    ///
+    /// let env = Environment::new()?;
+    /// let conn = env.connect_with_retry(
+    ///     "Driver={ODBC Driver 18 for SQL Server};Server=localhost;UID=SA;PWD=My@Test@Password1;",
+    ///     5,
+    ///     Duration::from_secs(2),
+    /// )?;
+    /// # Ok::<(), odbc_api::Error>(())
+    /// ```
+    pub fn connect_with_retry(
+        &self,
+        connection_string: &str,
+        retries: usize,
+        delay: Duration,
+    ) -> Result<Connection<'_>, Error> {
+        Failover::new([connection_string])
+            .with_retries(retries, delay)
+            .connect(self)
+            .map(|(_index, connection)| connection)
+    }
+
+    /// Connects to a Microsoft SQL Server without a data source name. The latest installed
+    /// Microsoft driver is picked from [`Self::drivers`]. `host` may carry a port, separated by a
+    /// colon, e.g. `localhost:1433`.
+    ///
+    /// ```no_run
+    /// use odbc_api::{Authentication, Environment};
+    ///
+    /// let env = Environment::new()?;
+    /// let auth = Authentication::Password { user: "SA", password: "My@Test@Password1" };
+    /// let conn = env.connect_sql_server("localhost:1433", "master", auth)?;
+    /// # Ok::<(), odbc_api::Error>(())
+    /// ```
+    pub fn connect_sql_server(
+        &self,
+        host: &str,
+        database: &str,
+        authentication: Authentication<'_>,
+    ) -> Result<Connection<'_>, Error> {
+        self.connect_dbms(DbmsDialect::MsSql, host, database, authentication)
+    }
+
+    /// Connects to a PostgreSQL server without a data source name, using the installed
+    /// `psqlODBC` driver. Unicode drivers are preferred over ANSI ones. `host` may carry a port,
+    /// separated by a colon, e.g. `localhost:5432`.
+    pub fn connect_postgres(
+        &self,
+        host: &str,
+        database: &str,
+        authentication: Authentication<'_>,
+    ) -> Result<Connection<'_>, Error> {
+        self.connect_dbms(DbmsDialect::PostgreSql, host, database, authentication)
+    }
+
+    /// Connects to a database server without a data source name. The connection string is
+    /// assembled for the driver of the vendor found in [`Self::drivers`]. Supports
+    /// [`DbmsDialect::MsSql`], [`DbmsDialect::PostgreSql`] and [`DbmsDialect::MySql`]. Other
+    /// database management systems yield [`Error::NoSuitableDriver`], just like a missing driver.
+    /// `host` may carry a port, separated by a colon. IPv6 addresses followed by a port must be
+    /// enclosed in brackets, e.g. `[::1]:1433`.
+    pub fn connect_dbms(
+        &self,
+        dbms: DbmsDialect,
+        host: &str,
+        database: &str,
+        authentication: Authentication<'_>,
+    ) -> Result<Connection<'_>, Error> {
+        let drivers = self.drivers()?;
+        let driver = select_driver(dbms, &drivers).ok_or(Error::NoSuitableDriver { dbms })?;
+        let connection_string =
+            dsn_less::connection_string(dbms, driver, host, database, authentication);
+        self.connect_with_connection_string(&connection_string)
+    }
+
+    /// Same as [`Self::connect_with_connection_string`], but enables tracing of ODBC function
+    /// calls by the driver manager for this connection only, before connecting to the data
+    /// source. This way the trace written to `trace_file` also captures the calls made while
+    /// establishing the connection. Tracing can be deactivated later using
+    /// [`Connection::set_trace`].
+    ///
+    /// ```no_run
+    /// use odbc_api::Environment;
+    ///
+    /// let env = Environment::new()?;
+    ///
+    /// let conn = env.connect_with_connection_string_and_trace(
+    ///     "DSN=ProblematicDataSource;",
+    ///     "/tmp/odbc_trace.log",
+    /// )?;
+    /// # Ok::<(), odbc_api::Error>(())
+    /// ```
+    pub fn connect_with_connection_string_and_trace(
+        &self,
+        connection_string: &str,
+        trace_file: &str,
+    ) -> Result<Connection<'_>, Error> {
+        let connection_string = SqlText::new(connection_string);
+        let trace_file = SqlText::new(trace_file);
+        let mut connection = self.allocate_connection()?;
+        connection
+            .set_trace_file(&trace_file)
+            .into_result(&connection)?;
+        connection.set_trace(true).into_result(&connection)?;
+        connection
+            .connect_with_connection_string(&connection_string)
+            .into_result(&connection)?;
+        Ok(Connection::new(connection))
+    }
+
+    /// Same as [`Self::connect_with_connection_string`], but authenticates using an access token
+    /// instead of user and password. E.g. a token obtained for the managed identity of an Azure
+    /// deployment. Supported by the Microsoft ODBC Driver for SQL Server. The connection string
+    /// must not specify `UID`, `PWD`, `Authentication` or `Trusted_Connection`.
+    ///
+    /// ```no_run
+    /// use odbc_api::{AccessToken, Environment};
+    ///
+    /// let env = Environment::new()?;
+    ///
+    /// // Obtained e.g. from the Azure instance metadata service
+    /// let token = AccessToken::new("eyJ0eXAiOi...")?;
+    /// let conn = env.connect_with_connection_string_and_access_token(
+    ///     "Driver={ODBC Driver 18 for SQL Server};Server=myserver.database.windows.net;\
+    ///     Database=mydb;",
+    ///     &token,
+    /// )?;
+    /// # Ok::<(), odbc_api::Error>(())
+    /// ```
+    pub fn connect_with_connection_string_and_access_token(
+        &self,
+        connection_string: &str,
+        access_token: &AccessToken,
+    ) -> Result<Connection<'_>, Error> {
+        let connection_string = SqlText::new(connection_string);
+        let mut connection = self.allocate_connection()?;
+        // Safe: `AccessToken` holds a properly aligned `ACCESSTOKEN` structure, which outlives
+        // the call to connect.
+        unsafe { connection.set_access_token(access_token.as_bytes()) }.into_result(&connection)?;
+        connection
+            .connect_with_connection_string(&connection_string)
+            .into_result(&connection)?;
+        Ok(Connection::new(connection))
+    }
+
     /// Allocates a connection handle and establishes connections to a driver and a data source.
     ///
     /// An alternative to `connect` and `connect_with_connection_string`. This method can be
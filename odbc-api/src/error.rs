@@ -1,8 +1,12 @@
-use std::io;
+use std::{io, mem::size_of};
 
+use odbc_sys::CDataType;
 use thiserror::Error as ThisError;
 
-use crate::handles::{log_diagnostics, Diagnostics, Record as DiagnosticRecord, SqlResult, State};
+use crate::{
+    handles::{log_diagnostics, Diagnostics, Record as DiagnosticRecord, SqlResult, State},
+    DataType, DbmsDialect,
+};
 
 /// Error indicating a failed allocation for a column buffer
 #[derive(Debug)]
@@ -14,6 +18,24 @@ pub struct TooLargeBufferSize {
 }
 
 impl TooLargeBufferSize {
+    /// Number of values of type `T` in a buffer holding `num_elements` elements of `element_len`
+    /// values each. Fails if the size of the buffer in bytes overflows, or if the size of a single
+    /// element does not fit into the signed length ODBC uses to describe buffers and indicators.
+    pub(crate) fn checked_len<T>(num_elements: usize, element_len: usize) -> Result<usize, Self> {
+        let element_size = element_len.saturating_mul(size_of::<T>());
+        let error = TooLargeBufferSize {
+            num_elements,
+            element_size,
+        };
+        if element_size > isize::MAX as usize {
+            return Err(error);
+        }
+        match element_size.checked_mul(num_elements) {
+            Some(num_bytes) if num_bytes <= isize::MAX as usize => Ok(element_len * num_elements),
+            _ => Err(error),
+        }
+    }
+
     /// Map the column allocation error to an [`crate::Error`] adding the context of which
     /// column caused the allocation error.
     pub fn add_context(self, buffer_index: u16) -> Error {
@@ -37,6 +59,15 @@ pub enum Error {
     /// [`crate::Environment::new`].
     #[error("Failed to allocate ODBC Environment.")]
     FailedAllocatingEnvironment,
+    /// The ODBC driver manager could not be loaded at runtime, e.g. because unixODBC is not
+    /// installed. Only emitted with the `dynamic_loading` feature by [`crate::Environment::new`],
+    /// [`crate::Environment::set_connection_pooling`] and [`crate::load_driver_manager`].
+    #[cfg(feature = "dynamic_loading")]
+    #[error("Failed to load the ODBC driver manager:\n{reason}")]
+    FailedLoadingDriverManager {
+        /// Why loading the driver manager failed, for each of the libraries which have been tried.
+        reason: String,
+    },
     /// This should never happen, given that ODBC driver manager and ODBC driver do not have any
     /// Bugs. Since we may link vs a bunch of these, better to be on the safe side.
     #[error(
@@ -129,9 +160,97 @@ pub enum Error {
         truncation."
     )]
     TooLargeValueForBuffer,
+    /// A batch holds more rows than ODBC can count with the signed integer it uses as indicator.
+    /// Emitted e.g. by binding a [`crate::parameter::TableValuedParameter`].
+    #[error(
+        "A batch holds {num_rows} rows, which is more than can be represented by an ODBC \
+        indicator. Send the rows in smaller batches."
+    )]
+    TooManyRowsInBatch { num_rows: usize },
+    /// Emitted by binding [`crate::ParameterArrays`], if the arrays do not have the same length.
+    #[error(
+        "Parameter array {parameter_number} holds {len} elements, but the first one holds \
+        {expected}. All parameter arrays must have the same length."
+    )]
+    ParameterArraysOfDifferentLength {
+        /// One based index of the first array whose length differs from the first array.
+        parameter_number: usize,
+        len: usize,
+        expected: usize,
+    },
+    /// Emitted by [`crate::Connection::preallocate_concurrent`] if the driver does not support as
+    /// many active statements on a single connection as requested.
+    #[error(
+        "Requested {requested} statements to be active at the same time, but the driver supports \
+        only {max_concurrent_activities} active statements per connection. For Microsoft SQL \
+        Server, enable Multiple Active Result Sets (MARS) by adding `MARS_Connection=Yes` to the \
+        connection string. Otherwise use a separate connection for each statement."
+    )]
+    TooManyConcurrentStatements {
+        requested: usize,
+        max_concurrent_activities: u16,
+    },
+    /// Emitted by [`crate::create_table_statement`] if the dialect does not know how to name the
+    /// type of a column.
+    #[error("The SQL dialect has no type name for column {column_number} of type {data_type:?}.")]
+    UnsupportedColumnType {
+        /// One based column index
+        column_number: u16,
+        data_type: DataType,
+    },
+    /// The driver could not convert the value of a column into the C data type requested with
+    /// [`crate::CursorRow::get_data`], e.g. text which is not a number into an integer.
+    #[error(
+        "The value of column {column_number} could not be converted into the requested C data \
+        type {c_type:?}. Diagnostic record returned by the driver:\n{record}"
+    )]
+    InvalidDataConversion {
+        /// One based column index
+        column_number: u16,
+        /// C data type requested by the application.
+        c_type: CDataType,
+        /// Diagnostic record returned by the driver.
+        record: DiagnosticRecord,
+    },
+    /// Emitted by [`crate::Environment::set_singleton_odbc_version`] if the process wide
+    /// environment has already been created.
+    #[error(
+        "The ODBC version of the process wide environment can not be changed after the \
+        environment has been created."
+    )]
+    SingletonAlreadyInitialized,
+    /// Emitted by [`crate::Environment::connect_dbms`] and its siblings, if no installed driver is
+    /// known to connect to the database management system without a data source name.
+    #[error(
+        "None of the installed ODBC drivers is known to connect to {dbms:?} without a data source \
+        name. Install the ODBC driver of the vendor, or connect using a connection string."
+    )]
+    NoSuitableDriver { dbms: DbmsDialect },
+    /// Emitted by [`crate::AccessToken::new`] if the encoded token is too large for its length to
+    /// be stored in the 32 Bit length field of the `ACCESSTOKEN` structure.
+    #[error(
+        "The access token is {len_bytes} bytes long encoded as UTF-16, which is more than the \
+        driver can accept."
+    )]
+    TooLargeAccessToken { len_bytes: usize },
 }
 
 impl Error {
+    /// Diagnostic record returned by the driver or driver manager, if the error has been caused
+    /// by one. Its SQLSTATE tells the class of the error, e.g. syntax errors or constraint
+    /// violations.
+    pub fn diagnostic_record(&self) -> Option<&DiagnosticRecord> {
+        match self {
+            Error::Diagnostics { record, .. }
+            | Error::UnsupportedOdbcApiVersion(record)
+            | Error::InvalidRowArraySize { record, .. }
+            | Error::UnableToRepresentNull(record)
+            | Error::OracleOdbcDriverDoesNotSupport64Bit(record)
+            | Error::InvalidDataConversion { record, .. } => Some(record),
+            _ => None,
+        }
+    }
+
     /// Allows for mapping the error variant from the "catch all" diagnostic to a more specific one
     /// offering the oppertunity to provide context in the error message.
     fn provide_context_for_diagnostic<F>(self, f: F) -> Self
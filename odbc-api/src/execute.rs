@@ -1,8 +1,15 @@
 use std::intrinsics::transmute;
+#[cfg(feature = "metrics")]
+use std::time::Instant;
 
+use odbc_sys::Pointer;
+
+#[cfg(feature = "odbc_version_3_80")]
+use crate::{handles::ParamData, StreamedOutputs, StreamingParameterCollection};
 use crate::{
-    handles::{AsStatementRef, SqlText, Statement},
+    handles::{AsStatementRef, SqlText, Statement, StatementRef},
     parameter::Blob,
+    query_log::QueryLog,
     sleep::wait_for,
     CursorImpl, CursorPolling, Error, ParameterCollectionRef, Sleep,
 };
@@ -18,21 +25,32 @@ use crate::{
 /// * `query`: SQL query to be executed. If `None` it is a assumed a prepared query is to be
 ///   executed.
 /// * `params`: The parameters bound to the statement before query execution.
+/// * `log`: Informed about the execution, if a query logger is installed on the connection.
 pub fn execute_with_parameters<S>(
     lazy_statement: impl FnOnce() -> Result<S, Error>,
     query: Option<&SqlText<'_>>,
     params: impl ParameterCollectionRef,
+    log: Option<QueryLog<'_>>,
 ) -> Result<Option<CursorImpl<S>>, Error>
 where
     S: AsStatementRef,
 {
-    unsafe {
-        if let Some(statement) = bind_parameters(lazy_statement, params)? {
-            execute(statement, query)
-        } else {
-            Ok(None)
+    let log = log.map(|log| log.start(&params));
+    #[cfg(feature = "metrics")]
+    let start = Instant::now();
+    let result = unsafe {
+        match bind_parameters(lazy_statement, params) {
+            Ok(Some(statement)) => execute(statement, query),
+            Ok(None) => Ok(None),
+            Err(error) => Err(error),
         }
+    };
+    #[cfg(feature = "metrics")]
+    crate::metrics::record_execution(start.elapsed(), result.as_ref().err());
+    if let Some(log) = log {
+        log.finish(result.as_ref().err());
     }
+    result
 }
 
 /// Asynchronous sibiling of [`execute_with_parameters`]
@@ -41,17 +59,27 @@ pub async fn execute_with_parameters_polling<S>(
     query: Option<&SqlText<'_>>,
     params: impl ParameterCollectionRef,
     sleep: impl Sleep,
+    log: Option<QueryLog<'_>>,
 ) -> Result<Option<CursorPolling<S>>, Error>
 where
     S: AsStatementRef,
 {
-    unsafe {
-        if let Some(statement) = bind_parameters(lazy_statement, params)? {
-            execute_polling(statement, query, sleep).await
-        } else {
-            Ok(None)
+    let log = log.map(|log| log.start(&params));
+    #[cfg(feature = "metrics")]
+    let start = Instant::now();
+    let result = unsafe {
+        match bind_parameters(lazy_statement, params) {
+            Ok(Some(statement)) => execute_polling(statement, query, sleep).await,
+            Ok(None) => Ok(None),
+            Err(error) => Err(error),
         }
+    };
+    #[cfg(feature = "metrics")]
+    crate::metrics::record_execution(start.elapsed(), result.as_ref().err());
+    if let Some(log) = log {
+        log.finish(result.as_ref().err());
     }
+    result
 }
 
 unsafe fn bind_parameters<S>(
@@ -92,6 +120,28 @@ where
     S: AsStatementRef,
 {
     let mut stmt = statement.as_stmt_ref();
+    execute_and_put_data(&mut stmt, query)?;
+
+    // Check if a result set has been created.
+    if stmt.num_result_cols().into_result(&stmt)? == 0 {
+        Ok(None)
+    } else {
+        // Safe: `statement` is in cursor state.
+        let cursor = CursorImpl::new(statement);
+        Ok(Some(cursor))
+    }
+}
+
+/// Executes the statement and streams the values of any delayed parameters to the data source.
+/// Leaves the statement in the state after execution, without looking at its results.
+///
+/// # Safety
+///
+/// Same as [`execute`].
+pub unsafe fn execute_and_put_data(
+    stmt: &mut StatementRef<'_>,
+    query: Option<&SqlText<'_>>,
+) -> Result<(), Error> {
     let result = if let Some(sql) = query {
         // We execute an unprepared "one shot query"
         stmt.exec_direct(sql)
@@ -105,30 +155,68 @@ where
     let need_data =
         result
             .on_success(|| false)
-            .into_result_with(&stmt, false, Some(false), Some(true))?;
+            .into_result_with(&*stmt, false, Some(false), Some(true))?;
 
     if need_data {
         // Check if any delayed parameters have been bound which stream data to the database at
         // statement execution time. Loops over each bound stream.
-        while let Some(blob_ptr) = stmt.param_data().into_result(&stmt)? {
-            // The safe interfaces currently exclusively bind pointers to `Blob` trait objects
-            let blob_ptr: *mut &mut dyn Blob = transmute(blob_ptr);
-            let blob_ref = &mut *blob_ptr;
-            // Loop over all batches within each blob
-            while let Some(batch) = blob_ref.next_batch().map_err(Error::FailedReadingInput)? {
-                stmt.put_binary_batch(batch).into_result(&stmt)?;
-            }
+        while let Some(blob_ptr) = stmt.param_data().into_result(&*stmt)? {
+            put_blob(stmt, blob_ptr)?;
         }
     }
+    Ok(())
+}
 
-    // Check if a result set has been created.
-    if stmt.num_result_cols().into_result(&stmt)? == 0 {
-        Ok(None)
-    } else {
-        // Safe: `statement` is in cursor state.
-        let cursor = CursorImpl::new(statement);
-        Ok(Some(cursor))
+/// Sends all batches of the blob identified by `blob_ptr` to the data source.
+///
+/// # Safety
+///
+/// `blob_ptr` must have been returned by `SQLParamData` and point to a `&mut dyn Blob`.
+unsafe fn put_blob(stmt: &mut StatementRef<'_>, blob_ptr: Pointer) -> Result<(), Error> {
+    // The safe interfaces currently exclusively bind pointers to `Blob` trait objects
+    let blob_ptr: *mut &mut dyn Blob = transmute(blob_ptr);
+    let blob_ref = &mut *blob_ptr;
+    // Loop over all batches within each blob
+    while let Some(batch) = blob_ref.next_batch().map_err(Error::FailedReadingInput)? {
+        stmt.put_binary_batch(batch).into_result(&*stmt)?;
     }
+    Ok(())
+}
+
+/// Executes a statement with streamed output parameters bound. See
+/// [`crate::Preallocated::execute_with_output_streams`].
+#[cfg(feature = "odbc_version_3_80")]
+pub fn execute_with_output_streams<S>(
+    mut statement: S,
+    query: &SqlText<'_>,
+    mut params: impl StreamingParameterCollection,
+) -> Result<StreamedOutputs<S>, Error>
+where
+    S: AsStatementRef,
+{
+    let available = unsafe {
+        let mut stmt = statement.as_stmt_ref();
+        // Reset parameters so we do not dereference stale once by mistake.
+        stmt.reset_parameters().into_result(&stmt)?;
+        stmt.set_paramset_size(1).into_result(&stmt)?;
+        params.bind_parameters_to(&mut stmt)?;
+        // `None` as long as delayed input parameters need data.
+        let available = stmt
+            .exec_direct_with_output_streams(query)
+            .map(Some)
+            .into_result_with(&stmt, false, Some(Some(false)), Some(None))?;
+        match available {
+            Some(available) => available,
+            None => loop {
+                match stmt.param_data_with_output_streams().into_result(&stmt)? {
+                    ParamData::NeedData(blob_ptr) => put_blob(&mut stmt, blob_ptr)?,
+                    ParamData::OutputAvailable => break true,
+                    ParamData::Done => break false,
+                }
+            },
+        }
+    };
+    Ok(StreamedOutputs::new(statement, available))
 }
 
 /// # Safety
@@ -147,10 +235,10 @@ where
     let mut stmt = statement.as_stmt_ref();
     let result = if let Some(sql) = query {
         // We execute an unprepared "one shot query"
-        wait_for(|| stmt.exec_direct(sql), &mut sleep).await
+        wait_for(&mut stmt, |stmt| stmt.exec_direct(sql), &mut sleep).await
     } else {
         // We execute a prepared query
-        wait_for(|| stmt.execute(), &mut sleep).await
+        wait_for(&mut stmt, |stmt| stmt.execute(), &mut sleep).await
     };
 
     // If delayed parameters (e.g. input streams) are bound we might need to put data in order to
@@ -169,14 +257,15 @@ where
             let blob_ref = &mut *blob_ptr;
             // Loop over all batches within each blob
             while let Some(batch) = blob_ref.next_batch().map_err(Error::FailedReadingInput)? {
-                let result = wait_for(|| stmt.put_binary_batch(batch), &mut sleep).await;
+                let result =
+                    wait_for(&mut stmt, |stmt| stmt.put_binary_batch(batch), &mut sleep).await;
                 result.into_result(&stmt)?;
             }
         }
     }
 
     // Check if a result set has been created.
-    let num_result_cols = wait_for(|| stmt.num_result_cols(), &mut sleep)
+    let num_result_cols = wait_for(&mut stmt, |stmt| stmt.num_result_cols(), &mut sleep)
         .await
         .into_result(&stmt)?;
     if num_result_cols == 0 {
@@ -238,3 +327,36 @@ where
 
     Ok(cursor)
 }
+
+/// Shared implementation for executing a primary keys query between [`crate::Connection`] and
+/// [`crate::Preallocated`].
+pub fn execute_primary_keys<S>(
+    mut statement: S,
+    catalog_name: &SqlText,
+    schema_name: &SqlText,
+    table_name: &SqlText,
+) -> Result<CursorImpl<S>, Error>
+where
+    S: AsStatementRef,
+{
+    let mut stmt = statement.as_stmt_ref();
+
+    stmt.primary_keys(catalog_name, schema_name, table_name)
+        .into_result(&stmt)?;
+
+    // We assume primary keys always creates a result set, since it works like a SELECT statement.
+    debug_assert_ne!(stmt.num_result_cols().unwrap(), 0);
+
+    // Safe: `statement` is in Cursor state.
+    let cursor = unsafe { CursorImpl::new(statement) };
+
+    Ok(cursor)
+}
+
+/// Number of rows affected by the last statement executed on `stmt`. `None` if the driver does not
+/// report it, which ODBC signals with `-1`. Other negative counts are reported as `None`, too.
+pub fn row_count(stmt: &impl Statement) -> Result<Option<usize>, Error> {
+    stmt.row_count()
+        .into_result(stmt)
+        .map(|count| usize::try_from(count).ok())
+}
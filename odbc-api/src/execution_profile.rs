@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+/// Picks defaults for fetching result sets, depending on whether the application cares more about
+/// the time until the first row arrives, or about the time it takes to transfer the entire result
+/// set. All knobs are derived from the profile in one place, so applications can switch between
+/// them without tuning each value individually.
+///
+/// ```
+/// use odbc_api::{buffers::TextRowSet, Cursor, Error, ExecutionProfile};
+///
+/// fn print_rows(mut cursor: impl Cursor, profile: ExecutionProfile) -> Result<(), Error> {
+///     let buffer = TextRowSet::for_cursor(profile.batch_size(), &mut cursor, Some(4096))?;
+///     let mut block_cursor = cursor.bind_buffer(buffer)?;
+///     while let Some(batch) = block_cursor.fetch()? {
+///         for row_index in 0..batch.num_rows() {
+///             let first = batch.at_as_str(0, row_index).unwrap_or(None);
+///             println!("{}", first.unwrap_or("NULL"));
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+///
+/// In async code, use [`Self::poll_interval`] to construct the [`crate::Sleep`] passed to polling
+/// functions:
+///
+/// ```
+/// use odbc_api::{Connection, Error, ExecutionProfile};
+///
+/// async fn refresh(connection: &Connection<'_>, profile: ExecutionProfile) -> Result<(), Error> {
+///     let sleep = || tokio::time::sleep(profile.poll_interval());
+///     connection.execute_polling("REFRESH MATERIALIZED VIEW v", (), sleep).await?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExecutionProfile {
+    /// Minimize the total time to transfer the result set, e.g. for ETL jobs. Fetches large
+    /// batches, to reduce the number of roundtrips, and polls asynchronous operations rarely, to
+    /// not waste CPU cycles.
+    #[default]
+    Throughput,
+    /// Minimize the time until the first rows are available to the application, e.g. for
+    /// interactive dashboards. Fetches small batches, so the first one is filled quickly, and polls
+    /// asynchronous operations often, to notice their completion without delay.
+    Latency,
+}
+
+impl ExecutionProfile {
+    /// Number of rows to fetch in one roundtrip. Use it as capacity of the buffers bound to a
+    /// cursor, e.g. with [`crate::buffers::TextRowSet::for_cursor`].
+    pub fn batch_size(self) -> usize {
+        match self {
+            ExecutionProfile::Throughput => 5000,
+            ExecutionProfile::Latency => 50,
+        }
+    }
+
+    /// Number of batches an application should fetch ahead, while it is still busy processing the
+    /// current one, e.g. by fetching in a separate thread. Prefetching hides roundtrips, but it
+    /// delays the first batch, since additional buffers need to be allocated and filled.
+    pub fn prefetch_batches(self) -> usize {
+        match self {
+            ExecutionProfile::Throughput => 1,
+            ExecutionProfile::Latency => 0,
+        }
+    }
+
+    /// Time to wait between polls of an asynchronous operation. See [`crate::Sleep`].
+    pub fn poll_interval(self) -> Duration {
+        match self {
+            ExecutionProfile::Throughput => Duration::from_millis(50),
+            ExecutionProfile::Latency => Duration::from_millis(1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ExecutionProfile;
+
+    #[test]
+    fn latency_profile_favours_small_batches_and_frequent_polls() {
+        let latency = ExecutionProfile::Latency;
+        let throughput = ExecutionProfile::default();
+
+        assert_eq!(ExecutionProfile::Throughput, throughput);
+        assert!(latency.batch_size() < throughput.batch_size());
+        assert!(latency.prefetch_batches() < throughput.prefetch_batches());
+        assert!(latency.poll_interval() < throughput.poll_interval());
+    }
+}
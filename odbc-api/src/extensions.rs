@@ -0,0 +1,180 @@
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    collections::HashMap,
+    fmt,
+    sync::Arc,
+};
+
+/// Typed state attached to a [`crate::Connection`] by layered libraries, like pools, ORMs or
+/// metrics collectors. Each type can be stored at most once, so libraries should use a type of
+/// their own as key, rather than e.g. `String`. Obtain it using [`crate::Connection::extensions`].
+///
+/// This allows to associate state with a connection without wrapping it, so code expecting a
+/// `&Connection` keeps working. Values are shared using [`Arc`]. State which changes during the
+/// lifetime of the connection has to provide interior mutability, e.g. by using atomics or a
+/// [`std::sync::Mutex`].
+///
+/// ```no_run
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use odbc_api::Connection;
+///
+/// /// Counts the queries a library executes per connection.
+/// #[derive(Default)]
+/// struct QueryCount(AtomicUsize);
+///
+/// fn count_query(conn: &Connection<'_>) -> usize {
+///     let count = conn.extensions().get_or_insert_with(QueryCount::default);
+///     count.0.fetch_add(1, Ordering::Relaxed) + 1
+/// }
+/// ```
+#[derive(Default)]
+pub struct Extensions {
+    values: RefCell<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>,
+}
+
+impl Extensions {
+    /// Empty set of extensions, not holding a value of any type.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores `value`, replacing the previous value of the same type, which is returned.
+    pub fn insert<T>(&self, value: T) -> Option<Arc<T>>
+    where
+        T: Any + Send + Sync,
+    {
+        self.values
+            .borrow_mut()
+            .insert(TypeId::of::<T>(), Arc::new(value))
+            .map(downcast)
+    }
+
+    /// The value of type `T`, if one has been stored.
+    pub fn get<T>(&self) -> Option<Arc<T>>
+    where
+        T: Any + Send + Sync,
+    {
+        self.values
+            .borrow()
+            .get(&TypeId::of::<T>())
+            .cloned()
+            .map(downcast)
+    }
+
+    /// The value of type `T`. Stores the result of `init` first, if there is none yet. `init` may
+    /// use the extensions itself.
+    pub fn get_or_insert_with<T>(&self, init: impl FnOnce() -> T) -> Arc<T>
+    where
+        T: Any + Send + Sync,
+    {
+        if let Some(value) = self.get() {
+            return value;
+        }
+        // Not borrowed while calling `init`, so it may access the extensions.
+        let value = Arc::new(init());
+        let stored = self
+            .values
+            .borrow_mut()
+            .entry(TypeId::of::<T>())
+            // `init` may have inserted a value of the same type. It takes precedence.
+            .or_insert(value)
+            .clone();
+        downcast(stored)
+    }
+
+    /// Removes the value of type `T` and returns it.
+    pub fn remove<T>(&self) -> Option<Arc<T>>
+    where
+        T: Any + Send + Sync,
+    {
+        self.values
+            .borrow_mut()
+            .remove(&TypeId::of::<T>())
+            .map(downcast)
+    }
+
+    /// Number of values stored.
+    pub fn len(&self) -> usize {
+        self.values.borrow().len()
+    }
+
+    /// `true` if no values are stored.
+    pub fn is_empty(&self) -> bool {
+        self.values.borrow().is_empty()
+    }
+}
+
+impl fmt::Debug for Extensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Values are opaque, so only their number is shown.
+        f.debug_struct("Extensions")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// Values are keyed by their type id, so the downcast can not fail.
+fn downcast<T>(value: Arc<dyn Any + Send + Sync>) -> Arc<T>
+where
+    T: Any + Send + Sync,
+{
+    value
+        .downcast()
+        .unwrap_or_else(|_| unreachable!("Extension must be stored under its own type id."))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use super::Extensions;
+
+    #[derive(Debug, PartialEq)]
+    struct PoolId(u32);
+
+    #[test]
+    fn values_are_keyed_by_type() {
+        let extensions = Extensions::new();
+
+        assert_eq!(None, extensions.insert(PoolId(1)));
+        extensions.insert(String::from("other"));
+
+        assert_eq!(Some(Arc::new(PoolId(1))), extensions.get::<PoolId>());
+        assert_eq!(Some(Arc::new(PoolId(1))), extensions.insert(PoolId(2)));
+        assert_eq!(Some(Arc::new(PoolId(2))), extensions.remove::<PoolId>());
+        assert_eq!(None, extensions.get::<PoolId>());
+        assert_eq!(1, extensions.len());
+    }
+
+    #[test]
+    fn initialize_value_once() {
+        let extensions = Extensions::new();
+        let calls = AtomicUsize::new(0);
+        let init = || {
+            calls.fetch_add(1, Ordering::Relaxed);
+            PoolId(7)
+        };
+
+        extensions.get_or_insert_with(init);
+        let value = extensions.get_or_insert_with(init);
+
+        assert_eq!(PoolId(7), *value);
+        assert_eq!(1, calls.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn init_may_access_extensions() {
+        let extensions = Extensions::new();
+
+        let value = extensions.get_or_insert_with(|| {
+            extensions.insert(PoolId(1));
+            PoolId(2)
+        });
+
+        assert_eq!(PoolId(1), *value);
+    }
+}
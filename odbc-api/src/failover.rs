@@ -0,0 +1,160 @@
+use std::{
+    fmt,
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use log::warn;
+
+use crate::{Connection, Environment, Error};
+
+/// Connects to the first reachable of several data sources, e.g. the replicas of a database
+/// deployed in multiple regions. Connection strings are tried in the order they have been passed,
+/// so the preferred data source should come first.
+///
+/// # Example
+///
+/// ```no_run
+/// use odbc_api::{Environment, Failover};
+/// use std::time::Duration;
+///
+/// let env = Environment::new()?;
+/// let failover = Failover::new([
+///     "Driver={ODBC Driver 17 for SQL Server};Server=primary;UID=SA;PWD=My@Test@Password1;",
+///     "Driver={ODBC Driver 17 for SQL Server};Server=secondary;UID=SA;PWD=My@Test@Password1;",
+/// ])
+/// .with_retries(3, Duration::from_secs(5));
+/// let (index, conn) = failover.connect(&env)?;
+/// println!("Connected to data source {index}.");
+/// # Ok::<(), odbc_api::Error>(())
+/// ```
+#[derive(Clone)]
+pub struct Failover {
+    connection_strings: Vec<String>,
+    /// Number of additional rounds over all connection strings, in case none is reachable.
+    retries: usize,
+    /// Time to wait between two rounds.
+    retry_delay: Duration,
+}
+
+// Connection strings usually contain credentials, so only their number is printed.
+impl fmt::Debug for Failover {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Failover")
+            .field("num_connection_strings", &self.connection_strings.len())
+            .field("retries", &self.retries)
+            .field("retry_delay", &self.retry_delay)
+            .finish()
+    }
+}
+
+impl Failover {
+    /// Connection strings in order of preference. By default each one is tried once.
+    pub fn new<S>(connection_strings: impl IntoIterator<Item = S>) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            connection_strings: connection_strings.into_iter().map(Into::into).collect(),
+            retries: 0,
+            retry_delay: Duration::ZERO,
+        }
+    }
+
+    /// If none of the data sources is reachable, try all of them again up to `retries` times,
+    /// waiting `delay` before each new round.
+    pub fn with_retries(mut self, retries: usize, delay: Duration) -> Self {
+        self.retries = retries;
+        self.retry_delay = delay;
+        self
+    }
+
+    /// Connects to the first reachable data source. Returns its index in the list of connection
+    /// strings together with the connection. Failed attempts are logged as warnings. If no data
+    /// source could be reached, the error of the last attempt is returned.
+    ///
+    /// # Panics
+    ///
+    /// If no connection strings have been specified.
+    pub fn connect<'env>(
+        &self,
+        env: &'env Environment,
+    ) -> Result<(usize, Connection<'env>), Error> {
+        assert!(
+            !self.connection_strings.is_empty(),
+            "Failover requires at least one connection string."
+        );
+        let mut round = 0;
+        loop {
+            let mut last_error = None;
+            for (index, connection_string) in self.connection_strings.iter().enumerate() {
+                match env.connect_with_connection_string(connection_string) {
+                    Ok(connection) => return Ok((index, connection)),
+                    Err(error) => {
+                        warn!("Failed to connect to data source {index}: {error}");
+                        last_error = Some(error);
+                    }
+                }
+            }
+            if round == self.retries {
+                return Err(last_error.unwrap());
+            }
+            round += 1;
+            thread::sleep(self.retry_delay);
+        }
+    }
+
+    /// Same as [`Self::connect`], but connects in a background thread, so the application can go
+    /// on with other work while the data sources are tried and retried.
+    ///
+    /// # Safety
+    ///
+    /// The connection is created on another thread, than the one it is used on. See
+    /// [`Connection::promote_to_send`].
+    pub unsafe fn connect_in_background(
+        self,
+        env: &'static Environment,
+    ) -> JoinHandle<Result<BackgroundConnection, Error>> {
+        thread::spawn(move || {
+            let (index, connection) = self.connect(env)?;
+            Ok(BackgroundConnection { index, connection })
+        })
+    }
+}
+
+/// Connection established by [`Failover::connect_in_background`].
+pub struct BackgroundConnection {
+    index: usize,
+    connection: Connection<'static>,
+}
+
+/// Safe, because the caller of [`Failover::connect_in_background`] vouches for the driver
+/// supporting connections to be used on threads other than the one they have been created on.
+unsafe impl Send for BackgroundConnection {}
+
+impl BackgroundConnection {
+    /// Index of the connection string the connection has been established with.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The established connection.
+    pub fn into_connection(self) -> Connection<'static> {
+        self.connection
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Failover;
+
+    #[test]
+    fn debug_does_not_print_connection_strings() {
+        let failover = Failover::new(["Driver={SQLite3};PWD=secret;", "Server=other;PWD=secret;"]);
+
+        let debug = format!("{failover:?}");
+
+        assert!(!debug.contains("secret"));
+        assert!(debug.contains("num_connection_strings: 2"));
+    }
+}
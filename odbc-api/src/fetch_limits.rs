@@ -0,0 +1,277 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Condvar, Mutex, PoisonError,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use odbc_sys::HStmt;
+
+use crate::handles::{Statement, StatementRef};
+
+/// Limits enforced while fetching a result set with a block cursor. Intended for services which
+/// must contain runaway queries. Set them using [`crate::BlockCursor::set_fetch_limits`]. Once a
+/// limit is exceeded, fetch returns `None` as if the result set had been consumed, and
+/// [`crate::BlockCursor::limit_exceeded`] tells the application that the rows fetched so far are
+/// only a partial result.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FetchLimits {
+    /// Soft limit for the wall clock time spent fetching. Checked before each row set is fetched.
+    /// Once exceeded no further row sets are fetched. A single fetch blocking for a long time is
+    /// not interrupted, see [`Self::cancel_after`] for that.
+    pub max_duration: Option<Duration>,
+    /// Hard limit for the wall clock time spent fetching. A watchdog thread cancels the statement
+    /// once this duration has passed, even if the application is blocked waiting for the driver
+    /// to return the next row set.
+    pub cancel_after: Option<Duration>,
+    /// Maximum number of rows returned. The last row set is truncated to not exceed it.
+    pub max_rows: Option<usize>,
+}
+
+/// The limit which has ended fetching a result set early. See
+/// [`crate::BlockCursor::limit_exceeded`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitExceeded {
+    /// [`FetchLimits::max_rows`] rows have been returned. The result set may hold more rows.
+    Rows,
+    /// [`FetchLimits::max_duration`] passed between two fetches.
+    Duration,
+    /// A fetch has been canceled, because [`FetchLimits::cancel_after`] passed.
+    Canceled,
+}
+
+/// Keeps track of the limits of a single block cursor.
+pub(crate) struct LimitTracker {
+    limits: FetchLimits,
+    start: Instant,
+    num_rows: usize,
+    exceeded: Option<LimitExceeded>,
+    watchdog: Option<Watchdog>,
+}
+
+impl LimitTracker {
+    /// # Safety
+    ///
+    /// `statement` must outlive the tracker.
+    pub unsafe fn new(limits: FetchLimits, statement: HStmt) -> Self {
+        // Safe: ODBC explicitly allows calling `SQLCancel` from another thread, in order to cancel
+        // a function executing synchronously on the statement. The watchdog is stopped before the
+        // tracker is dropped, so the statement is still valid once the cancellation runs.
+        let statement = force_send_sync::Send::new(statement);
+        Self::with_cancel(limits, move || {
+            // If no function is executing on the statement this has no effect.
+            let _ = StatementRef::new(*statement).cancel();
+        })
+    }
+
+    /// Like [`Self::new`], but `cancel` is invoked by the watchdog once
+    /// [`FetchLimits::cancel_after`] has passed, instead of canceling a statement.
+    pub fn with_cancel(limits: FetchLimits, cancel: impl FnOnce() + Send + 'static) -> Self {
+        let watchdog = limits
+            .cancel_after
+            .map(|timeout| Watchdog::new(timeout, cancel));
+        Self {
+            limits,
+            start: Instant::now(),
+            num_rows: 0,
+            exceeded: None,
+            watchdog,
+        }
+    }
+
+    pub fn exceeded(&self) -> Option<LimitExceeded> {
+        self.exceeded
+    }
+
+    /// Checks the limits before fetching the next row set. `Some` if no further row sets must be
+    /// fetched.
+    pub fn check_before_fetch(&mut self) -> Option<LimitExceeded> {
+        if self.exceeded.is_none() {
+            // `SQLCancel` has no effect if it is called between two fetches, so the hard limit must
+            // be enforced here as well.
+            if self.canceled_by_watchdog() {
+                return self.exceeded;
+            }
+            if self.limits.max_rows == Some(self.num_rows) {
+                self.exceeded = Some(LimitExceeded::Rows);
+            } else if matches!(self.limits.max_duration, Some(max) if self.start.elapsed() >= max) {
+                self.exceeded = Some(LimitExceeded::Duration);
+            }
+        }
+        self.exceeded
+    }
+
+    /// Account for a fetched row set with `num_rows` rows. Returns the number of rows the row set
+    /// must be truncated to.
+    pub fn record_rows(&mut self, num_rows: usize) -> usize {
+        let num_rows = match self.limits.max_rows {
+            Some(max) if self.num_rows + num_rows > max => {
+                self.exceeded = Some(LimitExceeded::Rows);
+                max - self.num_rows
+            }
+            _ => num_rows,
+        };
+        self.num_rows += num_rows;
+        num_rows
+    }
+
+    /// `true` if the watchdog canceled the statement. Any error returned by the driver after that
+    /// is expected to be the result of the cancellation.
+    pub fn canceled_by_watchdog(&mut self) -> bool {
+        let canceled = matches!(&self.watchdog, Some(watchdog) if watchdog.has_canceled());
+        if canceled {
+            self.exceeded = Some(LimitExceeded::Canceled);
+        }
+        canceled
+    }
+}
+
+/// Cancels a statement from a background thread once a timeout has passed, unless it is stopped
+/// before. Stopped on drop. Cancellation is performed by a closure, so it can be tested without a
+/// statement.
+struct Watchdog {
+    shared: Arc<WatchdogState>,
+    thread: Option<JoinHandle<()>>,
+}
+
+struct WatchdogState {
+    /// `true` once the watchdog is stopped.
+    stopped: Mutex<bool>,
+    /// Notified then the watchdog is stopped, in order to wake the thread.
+    wake: Condvar,
+    /// Set before the statement is canceled.
+    canceled: AtomicBool,
+}
+
+impl Watchdog {
+    fn new(timeout: Duration, cancel: impl FnOnce() + Send + 'static) -> Self {
+        let shared = Arc::new(WatchdogState {
+            stopped: Mutex::new(false),
+            wake: Condvar::new(),
+            canceled: AtomicBool::new(false),
+        });
+        let thread_shared = shared.clone();
+        let thread = thread::spawn(move || {
+            let state = &*thread_shared;
+            let (stopped, _) = state
+                .wake
+                .wait_timeout_while(state.stopped.lock().unwrap(), timeout, |stopped| !*stopped)
+                .unwrap();
+            if *stopped {
+                return;
+            }
+            state.canceled.store(true, Ordering::SeqCst);
+            // Cancel while still holding the lock, so the statement can not be freed in between.
+            cancel();
+        });
+        Self {
+            shared,
+            thread: Some(thread),
+        }
+    }
+
+    /// `true` if the watchdog has canceled the statement.
+    fn has_canceled(&self) -> bool {
+        self.shared.canceled.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        // Do not panic in drop, if `cancel` panicked in the watchdog thread.
+        *self
+            .shared
+            .stopped
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = true;
+        self.shared.wake.notify_one();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        thread::sleep,
+        time::Duration,
+    };
+
+    use super::{FetchLimits, LimitExceeded, LimitTracker};
+
+    #[test]
+    fn truncate_last_row_set_to_max_rows() {
+        let limits = FetchLimits {
+            max_rows: Some(25),
+            ..Default::default()
+        };
+        let mut tracker = LimitTracker::with_cancel(limits, || ());
+
+        assert_eq!(None, tracker.check_before_fetch());
+        assert_eq!(10, tracker.record_rows(10));
+        assert_eq!(None, tracker.check_before_fetch());
+        assert_eq!(10, tracker.record_rows(10));
+        assert_eq!(None, tracker.check_before_fetch());
+        assert_eq!(5, tracker.record_rows(10));
+        assert_eq!(Some(LimitExceeded::Rows), tracker.check_before_fetch());
+    }
+
+    #[test]
+    fn stop_after_max_duration() {
+        let limits = FetchLimits {
+            max_duration: Some(Duration::from_millis(10)),
+            ..Default::default()
+        };
+        let mut tracker = LimitTracker::with_cancel(limits, || ());
+
+        assert_eq!(None, tracker.check_before_fetch());
+        sleep(Duration::from_millis(20));
+        assert_eq!(Some(LimitExceeded::Duration), tracker.check_before_fetch());
+    }
+
+    #[test]
+    fn watchdog_cancels_only_after_timeout() {
+        let limits = FetchLimits {
+            cancel_after: Some(Duration::from_millis(10)),
+            ..Default::default()
+        };
+        let num_cancellations = Arc::new(AtomicUsize::new(0));
+        let counter = num_cancellations.clone();
+        let mut tracker = LimitTracker::with_cancel(limits, move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        assert_eq!(None, tracker.check_before_fetch());
+        assert_eq!(0, num_cancellations.load(Ordering::SeqCst));
+        sleep(Duration::from_millis(100));
+        assert_eq!(1, num_cancellations.load(Ordering::SeqCst));
+        // Canceling between two fetches has no effect on the statement, so the next fetch must not
+        // happen.
+        assert_eq!(Some(LimitExceeded::Canceled), tracker.check_before_fetch());
+        assert_eq!(Some(LimitExceeded::Canceled), tracker.exceeded());
+    }
+
+    #[test]
+    fn watchdog_stopped_before_timeout_does_not_cancel() {
+        let limits = FetchLimits {
+            cancel_after: Some(Duration::from_secs(60)),
+            ..Default::default()
+        };
+        let num_cancellations = Arc::new(AtomicUsize::new(0));
+        let counter = num_cancellations.clone();
+        let tracker = LimitTracker::with_cancel(limits, move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        drop(tracker);
+
+        assert_eq!(0, num_cancellations.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,106 @@
+use std::{
+    ops::ControlFlow,
+    time::{Duration, Instant},
+};
+
+/// Progress of fetching a result set using a block cursor. Passed to the callback registered with
+/// [`crate::BlockCursor::set_progress_callback`] after each row set has been fetched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FetchProgress {
+    /// Total number of rows fetched so far, including the current row set.
+    pub num_rows: usize,
+    /// Total number of row sets (batches) fetched so far, including the current one.
+    pub num_batches: usize,
+    /// Time passed since the callback has been registered.
+    pub elapsed: Duration,
+}
+
+/// Callback invoked after each block fetch. Returning [`ControlFlow::Break`] ends the iteration
+/// over the result set. It must be `Send`, so a cursor reporting its progress can still be moved to
+/// another thread.
+pub type ProgressCallback = Box<dyn FnMut(&FetchProgress) -> ControlFlow<()> + Send>;
+
+/// Accumulates the fetch progress of a block cursor and invokes the callback with it.
+pub(crate) struct ProgressTracker {
+    start: Instant,
+    num_rows: usize,
+    num_batches: usize,
+    /// `true` once the callback requested to stop fetching.
+    stopped: bool,
+    callback: ProgressCallback,
+}
+
+impl ProgressTracker {
+    pub fn new(callback: ProgressCallback) -> Self {
+        Self {
+            start: Instant::now(),
+            num_rows: 0,
+            num_batches: 0,
+            stopped: false,
+            callback,
+        }
+    }
+
+    /// `true` if the callback returned [`ControlFlow::Break`] for any previous row set.
+    pub fn is_stopped(&self) -> bool {
+        self.stopped
+    }
+
+    /// Account for a newly fetched row set and report the progress to the callback.
+    pub fn record_batch(&mut self, num_rows: usize) {
+        self.num_rows += num_rows;
+        self.num_batches += 1;
+        let progress = FetchProgress {
+            num_rows: self.num_rows,
+            num_batches: self.num_batches,
+            elapsed: self.start.elapsed(),
+        };
+        if (self.callback)(&progress).is_break() {
+            self.stopped = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        ops::ControlFlow,
+        sync::{Arc, Mutex},
+    };
+
+    use super::{FetchProgress, ProgressTracker};
+
+    #[test]
+    fn accumulate_rows_and_batches() {
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let sink = reported.clone();
+        let mut tracker = ProgressTracker::new(Box::new(move |progress: &FetchProgress| {
+            sink.lock()
+                .unwrap()
+                .push((progress.num_rows, progress.num_batches));
+            ControlFlow::Continue(())
+        }));
+
+        tracker.record_batch(10);
+        tracker.record_batch(3);
+
+        assert_eq!(vec![(10, 1), (13, 2)], *reported.lock().unwrap());
+        assert!(!tracker.is_stopped());
+    }
+
+    #[test]
+    fn stop_once_callback_breaks() {
+        let mut tracker = ProgressTracker::new(Box::new(|progress: &FetchProgress| {
+            if progress.num_rows >= 20 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        }));
+
+        tracker.record_batch(10);
+        assert!(!tracker.is_stopped());
+        tracker.record_batch(10);
+        assert!(tracker.is_stopped());
+    }
+}
@@ -0,0 +1,85 @@
+use crate::buffers::Indicator;
+
+/// Statistics about the row sets fetched with a block cursor. See
+/// [`crate::BlockCursor::statistics`]. Intended for data pipelines, which want to assert data
+/// quality invariants, e.g. that no value has been silently truncated, after the result set has
+/// been consumed.
+///
+/// Cell level statistics (bytes, truncation, `NoTotal`) are only collected for buffers which
+/// implement [`crate::buffers::ColumnBuffer::record_statistics`], like the ones shipped with
+/// this crate. Row and batch counts are always accurate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FetchStatistics {
+    /// Number of row sets (batches) fetched.
+    pub num_batches: usize,
+    /// Total number of rows fetched.
+    pub num_rows: usize,
+    /// Total number of bytes written by the driver into the value buffers. Does not include
+    /// indicators or terminating zeroes.
+    pub num_bytes: usize,
+    /// Number of values which did not fit into their column buffer and have been truncated. This
+    /// includes the values with an indicator of [`Indicator::NoTotal`].
+    pub num_truncated: usize,
+    /// Number of values for which the driver could not tell their total length
+    /// ([`Indicator::NoTotal`]). These are truncated, yet we can not know by how much.
+    pub num_no_total: usize,
+}
+
+impl FetchStatistics {
+    /// `true` if any fetched value has been truncated to fit into its buffer.
+    pub fn has_truncated_values(&self) -> bool {
+        self.num_truncated != 0
+    }
+
+    /// Accounts for a single variable sized value, e.g. a text or binary cell, based on its
+    /// indicator and the maximum length in bytes its buffer can hold.
+    pub fn record_cell(&mut self, indicator: Indicator, max_len: usize) {
+        match indicator {
+            Indicator::Null => (),
+            Indicator::NoTotal => {
+                self.num_no_total += 1;
+                self.num_truncated += 1;
+                self.num_bytes += max_len;
+            }
+            Indicator::Length(length) => {
+                if length > max_len {
+                    self.num_truncated += 1;
+                    self.num_bytes += max_len;
+                } else {
+                    self.num_bytes += length;
+                }
+            }
+        }
+    }
+
+    /// Accounts for a fixed sized value of `size` bytes. `NULL`s do not count towards the bytes.
+    pub fn record_fixed_sized_cell(&mut self, indicator: Indicator, size: usize) {
+        if indicator != Indicator::Null {
+            self.num_bytes += size;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::buffers::Indicator;
+
+    use super::FetchStatistics;
+
+    #[test]
+    fn count_truncated_and_no_total_cells() {
+        let mut statistics = FetchStatistics::default();
+
+        statistics.record_cell(Indicator::Length(3), 5);
+        statistics.record_cell(Indicator::Null, 5);
+        statistics.record_cell(Indicator::Length(7), 5);
+        statistics.record_cell(Indicator::NoTotal, 5);
+        statistics.record_fixed_sized_cell(Indicator::Length(4), 4);
+        statistics.record_fixed_sized_cell(Indicator::Null, 4);
+
+        assert_eq!(3 + 5 + 5 + 4, statistics.num_bytes);
+        assert_eq!(2, statistics.num_truncated);
+        assert_eq!(1, statistics.num_no_total);
+        assert!(statistics.has_truncated_values());
+    }
+}
@@ -10,9 +10,14 @@ mod bind;
 mod buffer;
 mod column_description;
 mod connection;
+mod cursor_type;
 mod data_type;
 mod diagnostics;
+mod driver_attributes;
+#[cfg(feature = "dynamic_loading")]
+mod dynamic;
 mod environment;
+mod leak_tracking;
 mod logging;
 mod sql_char;
 mod sql_result;
@@ -23,16 +28,38 @@ pub use {
     bind::{CData, CDataMut, DelayedInput, HasDataType},
     column_description::{ColumnDescription, Nullability},
     connection::Connection,
-    data_type::DataType,
+    cursor_type::{CursorSensitivity, CursorType},
+    data_type::{DataType, OtherDataType},
     diagnostics::{Diagnostics, Record, State},
     environment::Environment,
+    leak_tracking::{live_handles, log_leaked_handles, LiveHandles},
     logging::log_diagnostics,
-    sql_char::{slice_to_cow_utf8, slice_to_utf8, OutputStringBuffer, SqlChar, SqlText, SzBuffer},
+    sql_char::{
+        slice_to_cow_utf8, slice_to_utf8, slice_to_utf8_into, slice_to_utf8_lossy_into, utf8_to_sz,
+        OutputStringBuffer, SqlChar, SqlText, SzBuffer,
+    },
     sql_result::SqlResult,
     statement::{AsStatementRef, ParameterDescription, Statement, StatementImpl, StatementRef},
 };
 
-use odbc_sys::{Handle, HandleType, SQLFreeHandle, SqlReturn};
+#[cfg(feature = "odbc_version_3_80")]
+pub use statement::ParamData;
+
+#[cfg(feature = "dynamic_loading")]
+pub use dynamic::load_driver_manager;
+
+#[cfg(feature = "dynamic_loading")]
+pub(crate) use dynamic::ensure_driver_manager;
+
+// ODBC functions are either linked against, or loaded together with the driver manager at runtime.
+#[cfg(not(feature = "dynamic_loading"))]
+use odbc_sys as ffi;
+
+#[cfg(feature = "dynamic_loading")]
+use dynamic as ffi;
+
+use self::ffi::SQLFreeHandle;
+use odbc_sys::{Handle, HandleType, SqlReturn};
 use std::thread::panicking;
 
 /// Helper function freeing a handle and panicking on errors. Yet if the drop is triggered during
@@ -42,6 +69,7 @@ use std::thread::panicking;
 ///
 /// `handle` Must be a valid ODBC handle and `handle_type` must match its type.
 pub unsafe fn drop_handle(handle: Handle, handle_type: HandleType) {
+    leak_tracking::untrack(handle);
     match SQLFreeHandle(handle_type, handle) {
         SqlReturn::SUCCESS => (),
         other => {
@@ -1,7 +1,9 @@
 use super::{
     as_handle::AsHandle,
     buffer::mut_buf_ptr,
-    drop_handle,
+    driver_attributes, drop_handle,
+    ffi::{SQLAllocHandle, SQLDisconnect, SQLEndTran},
+    leak_tracking::track,
     sql_char::{
         binary_length, is_truncated_bin, resize_to_fit_with_tz, resize_to_fit_without_tz, SqlChar,
         SqlText,
@@ -12,19 +14,19 @@ use super::{
 };
 use odbc_sys::{
     CompletionType, ConnectionAttribute, DriverConnectOption, HDbc, HEnv, HStmt, HWnd, Handle,
-    HandleType, InfoType, Pointer, SQLAllocHandle, SQLDisconnect, SQLEndTran, IS_UINTEGER,
+    HandleType, InfoType, Pointer, IS_POINTER, IS_UINTEGER,
 };
 use std::{ffi::c_void, marker::PhantomData, mem::size_of, ptr::null_mut};
 
 #[cfg(feature = "narrow")]
-use odbc_sys::{
+use super::ffi::{
     SQLConnect as sql_connect, SQLDriverConnect as sql_driver_connect,
     SQLGetConnectAttr as sql_get_connect_attr, SQLGetInfo as sql_get_info,
     SQLSetConnectAttr as sql_set_connect_attr,
 };
 
 #[cfg(not(feature = "narrow"))]
-use odbc_sys::{
+use super::ffi::{
     SQLConnectW as sql_connect, SQLDriverConnectW as sql_driver_connect,
     SQLGetConnectAttrW as sql_get_connect_attr, SQLGetInfoW as sql_get_info,
     SQLSetConnectAttrW as sql_set_connect_attr,
@@ -165,7 +167,10 @@ impl<'c> Connection<'c> {
         unsafe {
             SQLAllocHandle(HandleType::Stmt, self.as_handle(), &mut out)
                 .into_sql_result("SQLAllocHandle")
-                .on_success(|| StatementImpl::new(out as HStmt))
+                .on_success(|| {
+                    track(out, HandleType::Stmt);
+                    StatementImpl::new(out as HStmt)
+                })
         }
     }
 
@@ -186,6 +191,56 @@ impl<'c> Connection<'c> {
         }
     }
 
+    /// Enables or disables tracing of ODBC function calls by the driver manager. Trace output
+    /// is written to the file set with [`Self::set_trace_file`], or to the driver manager's
+    /// default trace file.
+    pub fn set_trace(&self, enabled: bool) -> SqlResult<()> {
+        // SQL_OPT_TRACE_ON is 1, SQL_OPT_TRACE_OFF is 0
+        let val = enabled as u32;
+        unsafe {
+            sql_set_connect_attr(
+                self.handle,
+                ConnectionAttribute::Trace,
+                val as Pointer,
+                0, // will be ignored according to ODBC spec
+            )
+            .into_sql_result("SQLSetConnectAttr")
+        }
+    }
+
+    /// Sets the path of the file the driver manager writes its trace to, once tracing is enabled.
+    pub fn set_trace_file(&self, path: &SqlText) -> SqlResult<()> {
+        // Length of string attributes is always specified in bytes, even for the wide function.
+        let len_bytes = path.len_char() * size_of::<SqlChar>();
+        unsafe {
+            sql_set_connect_attr(
+                self.handle,
+                ConnectionAttribute::TraceFile,
+                path.ptr() as Pointer,
+                len_bytes.try_into().unwrap(),
+            )
+            .into_sql_result("SQLSetConnectAttr")
+        }
+    }
+
+    /// Authenticate using an access token rather than user and password. Must be called before
+    /// connecting. Specific to the Microsoft ODBC Driver for SQL Server.
+    ///
+    /// # Safety
+    ///
+    /// `access_token` must hold an `ACCESSTOKEN` structure, i.e. a 32 Bit length in bytes,
+    /// followed by that many bytes of the token encoded as UTF-16LE. It must be aligned to four
+    /// bytes and stay valid until the connection is established.
+    pub unsafe fn set_access_token(&self, access_token: &[u8]) -> SqlResult<()> {
+        driver_attributes::set_connect_attr(
+            self.handle,
+            driver_attributes::SS_ACCESS_TOKEN,
+            access_token.as_ptr() as Pointer,
+            IS_POINTER,
+        )
+        .into_sql_result("SQLSetConnectAttr")
+    }
+
     /// To commit a transaction in manual-commit mode.
     pub fn commit(&self) -> SqlResult<()> {
         unsafe {
@@ -205,15 +260,32 @@ impl<'c> Connection<'c> {
     /// Fetch the name of the database management system used by the connection and store it into
     /// the provided `buf`.
     pub fn fetch_database_management_system_name(&self, buf: &mut Vec<SqlChar>) -> SqlResult<()> {
+        self.fetch_info_string(InfoType::DbmsName as u16, buf)
+    }
+
+    /// Fetch the file name of the driver library used by the connection (`SQL_DRIVER_NAME`), e.g.
+    /// `libmsodbcsql-18.0.so.1.1`, and store it into the provided `buf`.
+    pub fn fetch_driver_name(&self, buf: &mut Vec<SqlChar>) -> SqlResult<()> {
+        self.fetch_info_string(driver_attributes::DRIVER_NAME, buf)
+    }
+
+    /// Fetch the name of the server the connection is established with (`SQL_SERVER_NAME`) and
+    /// store it into the provided `buf`.
+    pub fn fetch_server_name(&self, buf: &mut Vec<SqlChar>) -> SqlResult<()> {
+        self.fetch_info_string(InfoType::ServerName as u16, buf)
+    }
+
+    /// Fetch the character string information identified by `info_type` into `buf`.
+    fn fetch_info_string(&self, info_type: u16, buf: &mut Vec<SqlChar>) -> SqlResult<()> {
         // String length in bytes, not characters. Terminating zero is excluded.
         let mut string_length_in_bytes: i16 = 0;
         // Let's utilize all of `buf`s capacity.
         buf.resize(buf.capacity(), 0);
 
         unsafe {
-            let mut res = sql_get_info(
+            let mut res = driver_attributes::get_info(
                 self.handle,
-                InfoType::DbmsName,
+                info_type,
                 mut_buf_ptr(buf) as Pointer,
                 binary_length(buf).try_into().unwrap(),
                 &mut string_length_in_bytes as *mut i16,
@@ -228,9 +300,9 @@ impl<'c> Connection<'c> {
             if is_truncated_bin(buf, string_length_in_bytes.try_into().unwrap()) {
                 // It seems we must try again with a large enough buffer.
                 resize_to_fit_with_tz(buf, string_length_in_bytes.try_into().unwrap());
-                res = sql_get_info(
+                res = driver_attributes::get_info(
                     self.handle,
-                    InfoType::DbmsName,
+                    info_type,
                     mut_buf_ptr(buf) as Pointer,
                     binary_length(buf).try_into().unwrap(),
                     &mut string_length_in_bytes as *mut i16,
@@ -267,6 +339,52 @@ impl<'c> Connection<'c> {
         }
     }
 
+    fn info_u32(&self, info_type: InfoType) -> SqlResult<u32> {
+        unsafe {
+            let mut value = 0u32;
+            sql_get_info(
+                self.handle,
+                info_type,
+                &mut value as *mut u32 as Pointer,
+                // See `info_u16` for why we specify the buffer length.
+                size_of::<u32>() as i16,
+                null_mut(),
+            )
+            .into_sql_result("SQLGetInfo")
+            .on_success(|| value)
+        }
+    }
+
+    /// Level of asynchronous execution supported by the driver (`SQL_ASYNC_MODE`). `0` if not
+    /// supported, `1` for connection level and `2` for statement level.
+    pub fn async_mode(&self) -> SqlResult<u32> {
+        self.info_u32(InfoType::AsyncMode)
+    }
+
+    /// `1` if the driver can execute connection functions asynchronously
+    /// (`SQL_ASYNC_DBC_FUNCTIONS`). Introduced with ODBC 3.8.
+    pub fn async_dbc_functions(&self) -> SqlResult<u32> {
+        self.info_u32(InfoType::AsyncDbcFunctions)
+    }
+
+    /// `1` if the driver supports notification on completion of asynchronous operations
+    /// (`SQL_ASYNC_NOTIFICATION`). Introduced with ODBC 3.8.
+    pub fn async_notification(&self) -> SqlResult<u32> {
+        self.info_u32(InfoType::AsyncNotification)
+    }
+
+    /// Bitmask enumerating extensions to `SQLGetData` supported by the driver
+    /// (`SQL_GETDATA_EXTENSIONS`).
+    pub fn get_data_extensions(&self) -> SqlResult<u32> {
+        self.info_u32(InfoType::GetDataExtensions)
+    }
+
+    /// Maximum number of statements, which can be active on the connection at the same time
+    /// (`SQL_MAX_CONCURRENT_ACTIVITIES`). `0` if there is no limit, or the limit is unknown.
+    pub fn max_concurrent_activities(&self) -> SqlResult<u16> {
+        self.info_u16(InfoType::MaxConcurrentActivities)
+    }
+
     /// Maximum length of catalog names.
     pub fn max_catalog_name_len(&self) -> SqlResult<u16> {
         self.info_u16(InfoType::MaxCatalogNameLen)
@@ -0,0 +1,58 @@
+/// Kind of cursor created by executing a statement. Determines whether, and how changes made to
+/// the underlying data by others are visible while scrolling through a result set. Drivers are
+/// free to substitute a cursor type they do not support with a different one. In that case they
+/// emit a warning (`01S02`). See
+/// <https://learn.microsoft.com/sql/odbc/reference/develop-app/cursor-types>.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CursorType {
+    /// `SQL_CURSOR_FORWARD_ONLY`. The cursor only scrolls forward. This is the ODBC default and
+    /// usually the fastest option.
+    #[default]
+    ForwardOnly,
+    /// `SQL_CURSOR_STATIC`. The result set is a snapshot taken then the statement is executed.
+    /// Changes made by others are not visible.
+    Static,
+    /// `SQL_CURSOR_KEYSET_DRIVEN`. Membership and order of the rows in the result set are fixed
+    /// then the statement is executed, but updates and deletes of these rows by others are
+    /// visible.
+    KeysetDriven,
+    /// `SQL_CURSOR_DYNAMIC`. Membership, order and values of the rows may change while scrolling
+    /// through the result set. All changes made by others are visible.
+    Dynamic,
+}
+
+impl CursorType {
+    /// Value of the `SQL_ATTR_CURSOR_TYPE` statement attribute.
+    pub fn as_attribute_value(self) -> usize {
+        match self {
+            CursorType::ForwardOnly => 0,
+            CursorType::KeysetDriven => 1,
+            CursorType::Dynamic => 2,
+            CursorType::Static => 3,
+        }
+    }
+}
+
+/// Whether cursors make changes to the result set, made by other cursors, visible. See
+/// <https://learn.microsoft.com/sql/odbc/reference/develop-app/specifying-cursor-types>.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CursorSensitivity {
+    /// `SQL_UNSPECIFIED`. Up to the driver. This is the ODBC default.
+    #[default]
+    Unspecified,
+    /// `SQL_INSENSITIVE`. Changes made by others are not visible to the cursor.
+    Insensitive,
+    /// `SQL_SENSITIVE`. Changes made by others are visible to the cursor.
+    Sensitive,
+}
+
+impl CursorSensitivity {
+    /// Value of the `SQL_ATTR_CURSOR_SENSITIVITY` statement attribute.
+    pub fn as_attribute_value(self) -> usize {
+        match self {
+            CursorSensitivity::Unspecified => 0,
+            CursorSensitivity::Insensitive => 1,
+            CursorSensitivity::Sensitive => 2,
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use odbc_sys::SqlDataType;
 
+use crate::buffers::BufferDesc;
+
 /// The relational type of the column. Think of it as the type used in the `CREATE TABLE` statement
 /// then creating the database.
 ///
@@ -80,6 +82,19 @@ pub enum DataType {
         /// depends on the capabilities of the driver and datasource. E.g. its 2^31 - 1 for MSSQL.
         length: usize,
     },
+    /// `NTEXT`. Variable length wide character string for long text objects.
+    WLongVarchar {
+        /// Maximum length of the character string (excluding terminating zero). Maximum size
+        /// depends on the capabilities of the driver and datasource. E.g. its 2^30 - 1 for MSSQL.
+        length: usize,
+    },
+    /// `XML`. An XML document, e.g. the `XML` type of Microsoft SQL Server. Drivers transfer it
+    /// like long variable length character data, so it is usually fetched as text.
+    Xml {
+        /// Maximum length of the document in characters as reported by the driver. Often `0` or
+        /// very large for documents without an upper bound.
+        length: usize,
+    },
     /// `BLOB`. Variable length data for long binary objects.
     LongVarbinary {
         /// Maximum length of the binary data. Maximum size depends on the capabilities of the
@@ -132,6 +147,10 @@ pub enum DataType {
     },
 }
 
+/// Type code of the `XML` type of Microsoft SQL Server (`SQL_SS_XML`). ODBC itself does not
+/// define a type code for XML.
+const SQL_SS_XML: SqlDataType = SqlDataType(-152);
+
 impl DataType {
     /// This constructor is useful to create an instance of the enumeration using values returned by
     /// ODBC Api calls like `SQLDescribeCol`, rather than just initializing a variant directly.
@@ -187,6 +206,12 @@ impl DataType {
             SqlDataType::EXT_W_CHAR => DataType::WChar {
                 length: column_size,
             },
+            SqlDataType::EXT_W_LONG_VARCHAR => DataType::WLongVarchar {
+                length: column_size,
+            },
+            SQL_SS_XML => DataType::Xml {
+                length: column_size,
+            },
             other => DataType::Other {
                 data_type: other,
                 column_size,
@@ -220,6 +245,8 @@ impl DataType {
             DataType::Bit => SqlDataType::EXT_BIT,
             DataType::WVarchar { .. } => SqlDataType::EXT_W_VARCHAR,
             DataType::WChar { .. } => SqlDataType::EXT_W_CHAR,
+            DataType::WLongVarchar { .. } => SqlDataType::EXT_W_LONG_VARCHAR,
+            DataType::Xml { .. } => SQL_SS_XML,
             DataType::Other { data_type, .. } => *data_type,
         }
     }
@@ -246,7 +273,9 @@ impl DataType {
             | DataType::Binary { length }
             | DataType::WChar { length }
             | DataType::WVarchar { length }
-            | DataType::LongVarchar { length } => *length,
+            | DataType::LongVarchar { length }
+            | DataType::WLongVarchar { length }
+            | DataType::Xml { length } => *length,
             DataType::Float { precision, .. }
             | DataType::Numeric { precision, .. }
             | DataType::Decimal { precision, .. } => *precision,
@@ -271,6 +300,8 @@ impl DataType {
             | DataType::LongVarbinary { .. }
             | DataType::Binary { .. }
             | DataType::LongVarchar { .. }
+            | DataType::WLongVarchar { .. }
+            | DataType::Xml { .. }
             | DataType::Date
             | DataType::BigInt
             | DataType::TinyInt
@@ -302,7 +333,9 @@ impl DataType {
             | DataType::WVarchar { length }
             | DataType::WChar { length }
             | DataType::Char { length }
-            | DataType::LongVarchar { length } => Some(*length),
+            | DataType::LongVarchar { length }
+            | DataType::WLongVarchar { length }
+            | DataType::Xml { length } => Some(*length),
             // The precision of the column plus 2 (a sign, precision digits, and a decimal point).
             // For example, the display size of a column defined as NUMERIC(10,3) is 12.
             DataType::Numeric {
@@ -350,6 +383,75 @@ impl DataType {
         }
     }
 
+    /// The maximum length of a value in bytes, if it is transferred in its default C
+    /// representation. For character data this assumes a single byte character set for narrow and
+    /// UTF-16 for wide types. Drivers report the actual octet length of a column with
+    /// [`crate::ResultSetMetadata::col_octet_length`].
+    ///
+    /// See: <https://docs.microsoft.com/en-us/sql/odbc/reference/appendixes/octet-length>
+    ///
+    /// ```
+    /// use odbc_api::DataType;
+    ///
+    /// assert_eq!(DataType::Varchar { length: 10 }.octet_len(), Some(10));
+    /// assert_eq!(DataType::WVarchar { length: 10 }.octet_len(), Some(20));
+    /// assert_eq!(DataType::Integer.octet_len(), Some(4));
+    /// assert_eq!(DataType::Timestamp { precision: 3 }.octet_len(), Some(16));
+    /// ```
+    pub fn octet_len(&self) -> Option<usize> {
+        match self {
+            DataType::Unknown | DataType::Other { .. } => None,
+            DataType::Char { length }
+            | DataType::Varchar { length }
+            | DataType::LongVarchar { length }
+            | DataType::Binary { length }
+            | DataType::Varbinary { length }
+            | DataType::LongVarbinary { length } => Some(*length),
+            // Two bytes for each UTF-16 code unit.
+            DataType::WChar { length }
+            | DataType::WVarchar { length }
+            | DataType::WLongVarchar { length }
+            | DataType::Xml { length } => Some(*length * 2),
+            // Same as display size, since the C representation is character data.
+            DataType::Numeric { precision, .. } | DataType::Decimal { precision, .. } => {
+                Some(precision + 2)
+            }
+            DataType::Bit | DataType::TinyInt => Some(1),
+            DataType::SmallInt => Some(2),
+            DataType::Integer | DataType::Real => Some(4),
+            DataType::Float { precision } => Some(if *precision <= 24 { 4 } else { 8 }),
+            DataType::BigInt | DataType::Double => Some(8),
+            // Size of `SQL_DATE_STRUCT` and `SQL_TIME_STRUCT`.
+            DataType::Date | DataType::Time { .. } => Some(6),
+            // Size of `SQL_TIMESTAMP_STRUCT`.
+            DataType::Timestamp { .. } => Some(16),
+        }
+    }
+
+    /// `true` for types holding long character data, which usually is not bounded by a sensible
+    /// maximum length. These are `TEXT` (`LongVarchar`), `NTEXT` (`WLongVarchar`) and `XML`, as
+    /// well as `VARCHAR` and `NVARCHAR` columns with a reported length of `0`, which e.g. Microsoft
+    /// SQL Server uses for `VARCHAR(max)`. Fetching such columns in bulk requires an upper bound
+    /// for the buffer size, or streaming the values individually.
+    ///
+    /// ```
+    /// use odbc_api::DataType;
+    ///
+    /// assert!(DataType::Xml { length: 0 }.is_long_text());
+    /// assert!(DataType::Varchar { length: 0 }.is_long_text());
+    /// assert!(!DataType::Varchar { length: 255 }.is_long_text());
+    /// assert!(!DataType::LongVarbinary { length: 0 }.is_long_text());
+    /// ```
+    pub fn is_long_text(&self) -> bool {
+        match self {
+            DataType::LongVarchar { .. } | DataType::WLongVarchar { .. } | DataType::Xml { .. } => {
+                true
+            }
+            DataType::Varchar { length } | DataType::WVarchar { length } => *length == 0,
+            _ => false,
+        }
+    }
+
     /// The maximum length of the UTF-8 representation in bytes.
     ///
     /// ```
@@ -404,3 +506,126 @@ impl Default for DataType {
         DataType::Unknown
     }
 }
+
+/// Detailed description of a column, whose type is specific to the driver or data source, i.e. of
+/// type [`DataType::Other`]. E.g. `geometry`, `hierarchyid` or interval types. Obtained with
+/// [`crate::ResultSetMetadata::col_other_data_type`].
+///
+/// Such columns can still be fetched as text, which the driver converts them to. Use
+/// [`Self::utf8_len`] to find a suitable buffer length.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OtherDataType {
+    /// Type code reported by the driver.
+    pub type_code: SqlDataType,
+    /// Size of the column element. Exact meaning if any depends on `type_code`.
+    pub size: usize,
+    /// Decimal digits of the column element. Exact meaning if any depends on `type_code`.
+    pub decimal_digits: i16,
+    /// Data source dependent name of the type, e.g. `geometry`.
+    pub name: String,
+    /// Maximum number of characters required to display values of the column, as reported by the
+    /// driver. `None` if unknown.
+    pub display_size: Option<usize>,
+    /// Maximum length of column elements in bytes, as reported by the driver. `None` if unknown.
+    pub octet_length: Option<usize>,
+}
+
+impl OtherDataType {
+    /// The [`DataType::Other`] variant described by this type.
+    pub fn data_type(&self) -> DataType {
+        DataType::Other {
+            data_type: self.type_code,
+            column_size: self.size,
+            decimal_digits: self.decimal_digits,
+        }
+    }
+
+    /// Length of a text buffer, able to hold the UTF-8 representation of the values in bytes.
+    /// Prefers the display size reported by the driver. Otherwise assumes the driver converts the
+    /// values from their binary representation into hexadecimal digits, which takes two characters
+    /// per byte. `None` if the driver reported neither.
+    ///
+    /// ```
+    /// use odbc_api::{sys::SqlDataType, OtherDataType};
+    ///
+    /// let geometry = OtherDataType {
+    ///     type_code: SqlDataType(-151),
+    ///     size: 0,
+    ///     decimal_digits: 0,
+    ///     name: "geometry".to_owned(),
+    ///     display_size: None,
+    ///     octet_length: Some(4000),
+    /// };
+    /// assert_eq!(Some(8000), geometry.utf8_len());
+    /// ```
+    pub fn utf8_len(&self) -> Option<usize> {
+        self.display_size
+            .or_else(|| self.octet_length.map(|octets| octets * 2))
+    }
+
+    /// Length of a text buffer, able to hold the UTF-16 representation of the values in 2-Byte
+    /// characters. See [`Self::utf8_len`].
+    pub fn utf16_len(&self) -> Option<usize> {
+        self.utf8_len()
+    }
+
+    /// Text buffer able to hold the values of the column. `None` if no sensible length is known,
+    /// in which case values should be streamed, or bound with an explicit upper bound.
+    pub fn buffer_desc(&self) -> Option<BufferDesc> {
+        self.utf8_len()
+            .map(|max_str_len| BufferDesc::Text { max_str_len })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DataType;
+
+    #[test]
+    fn buffer_lengths_of_text() {
+        let varchar = DataType::Varchar { length: 10 };
+        assert_eq!(Some(10), varchar.display_size());
+        assert_eq!(Some(40), varchar.utf8_len());
+        assert_eq!(Some(20), varchar.utf16_len());
+        assert_eq!(Some(10), varchar.octet_len());
+
+        let ntext = DataType::WLongVarchar { length: 100 };
+        assert_eq!(Some(100), ntext.display_size());
+        assert_eq!(Some(100), ntext.utf8_len());
+        assert_eq!(Some(200), ntext.octet_len());
+    }
+
+    #[test]
+    fn buffer_lengths_of_fixed_sized_types() {
+        let time = DataType::Time { precision: 3 };
+        assert_eq!(Some(12), time.display_size());
+        assert_eq!(Some(12), time.utf8_len());
+        assert_eq!(Some(6), time.octet_len());
+
+        let decimal = DataType::Decimal {
+            precision: 10,
+            scale: 3,
+        };
+        assert_eq!(Some(12), decimal.display_size());
+        assert_eq!(Some(12), decimal.octet_len());
+
+        assert_eq!(Some(24), DataType::Float { precision: 53 }.display_size());
+        assert_eq!(Some(8), DataType::Float { precision: 53 }.octet_len());
+        assert_eq!(Some(4), DataType::Float { precision: 24 }.octet_len());
+    }
+
+    #[test]
+    fn binary_displayed_as_hex() {
+        let binary = DataType::Varbinary { length: 16 };
+        assert_eq!(Some(32), binary.display_size());
+        assert_eq!(Some(32), binary.utf8_len());
+        assert_eq!(Some(16), binary.octet_len());
+    }
+
+    #[test]
+    fn no_buffer_lengths_for_unknown_types() {
+        assert_eq!(None, DataType::Unknown.display_size());
+        assert_eq!(None, DataType::Unknown.utf8_len());
+        assert_eq!(None, DataType::Unknown.octet_len());
+    }
+}
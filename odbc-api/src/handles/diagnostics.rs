@@ -11,10 +11,10 @@ use std::fmt;
 // Starting with odbc 5 we may be able to specify utf8 encoding. until then, we may need to fall
 // back on the 'W' wide function calls.
 #[cfg(not(feature = "narrow"))]
-use odbc_sys::SQLGetDiagRecW as sql_get_diag_rec;
+use super::ffi::SQLGetDiagRecW as sql_get_diag_rec;
 
 #[cfg(feature = "narrow")]
-use odbc_sys::SQLGetDiagRec as sql_get_diag_rec;
+use super::ffi::SQLGetDiagRec as sql_get_diag_rec;
 
 /// A buffer large enough to hold an `SOLState` for diagnostics
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -33,6 +33,23 @@ impl State {
     pub const STRING_DATA_RIGHT_TRUNCATION: State = State(*b"01004");
     /// StrLen_or_IndPtr was a null pointer and NULL data was retrieved.
     pub const INDICATOR_VARIABLE_REQUIRED_BUT_NOT_SUPPLIED: State = State(*b"22002");
+    /// The information type passed to `SQLGetInfo` is not known to the driver.
+    pub const INVALID_INFORMATION_TYPE: State = State(*b"HY096");
+    /// The fetch orientation passed to `SQLFetchScroll` is not supported by the cursor, e.g.
+    /// because it is forward only.
+    pub const FETCH_TYPE_OUT_OF_RANGE: State = State(*b"HY106");
+    /// The driver does not support the requested optional feature.
+    pub const OPTIONAL_FEATURE_NOT_IMPLEMENTED: State = State(*b"HYC00");
+    /// The data value of a column can not be converted to the requested C data type.
+    pub const RESTRICTED_DATA_TYPE_ATTRIBUTE_VIOLATION: State = State(*b"07006");
+    /// Returning the numeric value would have caused the whole part of the number to be
+    /// truncated.
+    pub const NUMERIC_VALUE_OUT_OF_RANGE: State = State(*b"22003");
+    /// A character value could not be converted into a date, time or timestamp.
+    pub const INVALID_DATETIME_FORMAT: State = State(*b"22007");
+    /// A character value could not be converted into the requested C data type, e.g. `abc` into
+    /// an integer.
+    pub const INVALID_CHARACTER_VALUE_FOR_CAST_SPECIFICATION: State = State(*b"22018");
 
     /// Drops terminating zero and changes char type, if required
     pub fn from_chars_with_nul(code: &[SqlChar; SQLSTATE_SIZE + 1]) -> Self {
@@ -0,0 +1,120 @@
+//! Some drivers define statement and connection attributes of their own, which are not part of
+//! the attribute enumerations in `odbc-sys`. Likewise `odbc_sys::InfoType` lacks some of the
+//! information types defined by the ODBC standard. Constructing one of these enumerations with an
+//! unknown discriminant would be undefined behaviour, so we declare the functions with plain
+//! integer identifiers instead. The symbols are provided by the driver manager `odbc-sys` links
+//! against, or the one loaded at runtime with the `dynamic_loading` feature.
+
+use odbc_sys::{HDbc, HStmt, Integer, Pointer, SmallInt, SqlReturn, USmallInt};
+
+/// `SQL_SOPT_SS_PARAM_FOCUS`. Microsoft SQL Server specific statement attribute. Selects the
+/// table valued parameter, the following calls to `SQLBindParameter` bind the columns of. `0`
+/// focuses the statement parameters again.
+pub const SS_PARAM_FOCUS: Integer = 1236;
+
+/// `SQL_DRIVER_NAME`. File name of the driver library, e.g. `msodbcsql18.dll`.
+pub const DRIVER_NAME: USmallInt = 6;
+
+/// `SQL_COPT_SS_ACCESS_TOKEN`. Microsoft SQL Server specific connection attribute. Points to an
+/// `ACCESSTOKEN` structure used to authenticate, instead of user and password. Must be set before
+/// connecting.
+pub const SS_ACCESS_TOKEN: Integer = 1256;
+
+#[cfg(not(feature = "dynamic_loading"))]
+extern "system" {
+    #[cfg(not(feature = "narrow"))]
+    #[link_name = "SQLSetStmtAttrW"]
+    fn sql_set_stmt_attr(hstmt: HStmt, attr: Integer, value: Pointer, length: Integer)
+        -> SqlReturn;
+
+    #[cfg(feature = "narrow")]
+    #[link_name = "SQLSetStmtAttr"]
+    fn sql_set_stmt_attr(hstmt: HStmt, attr: Integer, value: Pointer, length: Integer)
+        -> SqlReturn;
+
+    #[cfg(not(feature = "narrow"))]
+    #[link_name = "SQLSetConnectAttrW"]
+    fn sql_set_connect_attr(
+        hdbc: HDbc,
+        attr: Integer,
+        value: Pointer,
+        length: Integer,
+    ) -> SqlReturn;
+
+    #[cfg(feature = "narrow")]
+    #[link_name = "SQLSetConnectAttr"]
+    fn sql_set_connect_attr(
+        hdbc: HDbc,
+        attr: Integer,
+        value: Pointer,
+        length: Integer,
+    ) -> SqlReturn;
+
+    #[cfg(not(feature = "narrow"))]
+    #[link_name = "SQLGetInfoW"]
+    fn sql_get_info(
+        hdbc: HDbc,
+        info_type: USmallInt,
+        value: Pointer,
+        buffer_length: SmallInt,
+        string_length: *mut SmallInt,
+    ) -> SqlReturn;
+
+    #[cfg(feature = "narrow")]
+    #[link_name = "SQLGetInfo"]
+    fn sql_get_info(
+        hdbc: HDbc,
+        info_type: USmallInt,
+        value: Pointer,
+        buffer_length: SmallInt,
+        string_length: *mut SmallInt,
+    ) -> SqlReturn;
+}
+
+#[cfg(feature = "dynamic_loading")]
+use super::ffi::{sql_get_info, sql_set_connect_attr, sql_set_stmt_attr};
+
+/// Sets a statement attribute identified by its integer constant.
+///
+/// # Safety
+///
+/// `value` and `length` must be valid for `attribute` according to the driver documentation.
+pub unsafe fn set_stmt_attr(
+    statement: HStmt,
+    attribute: Integer,
+    value: Pointer,
+    length: Integer,
+) -> SqlReturn {
+    sql_set_stmt_attr(statement, attribute, value, length)
+}
+
+/// Sets a connection attribute identified by its integer constant.
+///
+/// # Safety
+///
+/// `value` and `length` must be valid for `attribute` according to the driver documentation.
+pub unsafe fn set_connect_attr(
+    connection: HDbc,
+    attribute: Integer,
+    value: Pointer,
+    length: Integer,
+) -> SqlReturn {
+    sql_set_connect_attr(connection, attribute, value, length)
+}
+
+/// Returns general information about the driver and data source, identified by its integer
+/// constant.
+///
+/// # Safety
+///
+/// `value` must point to a buffer of `buffer_length` bytes, suitable for `info_type` according to
+/// the ODBC documentation.
+pub unsafe fn get_info(
+    connection: HDbc,
+    info_type: USmallInt,
+    value: Pointer,
+    buffer_length: SmallInt,
+    string_length: *mut SmallInt,
+) -> SqlReturn {
+    sql_get_info(connection, info_type, value, buffer_length, string_length)
+}
@@ -0,0 +1,609 @@
+//! Loads the ODBC driver manager at runtime, rather than linking against it. Each ODBC function is
+//! resolved once the driver manager is loaded and called through a function pointer. The functions
+//! in this module mirror the signatures of their counterparts in `odbc-sys`, so the rest of the
+//! handles module does not care which of the two it calls.
+
+use std::{
+    ffi::{OsStr, OsString},
+    sync::OnceLock,
+};
+
+use libloading::Library;
+use odbc_sys::{
+    CDataType, CompletionType, ConnectionAttribute, Desc, DriverConnectOption,
+    EnvironmentAttribute, FetchOrientation, FreeStmtOption, HDbc, HEnv, HStmt, HWnd, Handle,
+    HandleType, InfoType, Integer, Len, Nullability, ParamType, Pointer, SmallInt, SqlDataType,
+    SqlReturn, StatementAttribute, ULen, USmallInt,
+};
+
+#[cfg(feature = "odbc_version_3_80")]
+use odbc_sys::RetCode;
+
+#[cfg(feature = "narrow")]
+use odbc_sys::Char;
+
+#[cfg(not(feature = "narrow"))]
+use odbc_sys::WChar;
+
+use crate::{handles::SqlChar, Error};
+
+/// File names of the driver manager, tried in order if none has been loaded explicitly using
+/// [`load_driver_manager`].
+#[cfg(windows)]
+const DEFAULT_LIBRARIES: &[&str] = &["odbc32.dll"];
+#[cfg(all(target_os = "macos", feature = "iodbc"))]
+const DEFAULT_LIBRARIES: &[&str] = &["libiodbc.2.dylib", "libiodbc.dylib"];
+#[cfg(all(target_os = "macos", not(feature = "iodbc")))]
+const DEFAULT_LIBRARIES: &[&str] = &["libodbc.2.dylib", "libodbc.dylib"];
+#[cfg(all(not(windows), not(target_os = "macos"), feature = "iodbc"))]
+const DEFAULT_LIBRARIES: &[&str] = &["libiodbc.so.2", "libiodbc.so"];
+#[cfg(all(not(windows), not(target_os = "macos"), not(feature = "iodbc")))]
+const DEFAULT_LIBRARIES: &[&str] = &["libodbc.so.2", "libodbc.so"];
+
+/// The loaded driver manager, or the reason why loading it failed.
+static DRIVER_MANAGER: OnceLock<Result<DriverManager, String>> = OnceLock::new();
+
+struct DriverManager {
+    functions: Functions,
+    // Must outlive the function pointers in `functions`. Since it is stored in a static, the
+    // library is never unloaded.
+    _library: Library,
+}
+
+impl DriverManager {
+    fn load(file_name: &OsStr) -> Result<Self, String> {
+        let describe =
+            |error: libloading::Error| format!("{}: {error}", file_name.to_string_lossy());
+        // Safety: Loading the driver manager runs its initialization routines, which we have to
+        // trust, just like we would if we linked against it.
+        let library = unsafe { Library::new(file_name) }.map_err(describe)?;
+        let functions = unsafe { Functions::load(&library) }.map_err(describe)?;
+        Ok(Self {
+            functions,
+            _library: library,
+        })
+    }
+}
+
+/// Loads the ODBC driver manager from `file_name` at runtime. `file_name` is either a path or the
+/// name of a library in the search path of the platform, e.g. `libodbc.so.2`. Must be called
+/// before the first [`crate::Environment`] is created. If it is not called at all, the driver
+/// manager is loaded from the default location of the platform once the first environment is
+/// created.
+///
+/// Only available with the `dynamic_loading` feature.
+pub fn load_driver_manager(file_name: impl AsRef<OsStr>) -> Result<(), Error> {
+    let file_name = file_name.as_ref();
+    let mut loaded_now = false;
+    let result = DRIVER_MANAGER.get_or_init(|| {
+        loaded_now = true;
+        DriverManager::load(file_name)
+    });
+    match result {
+        Ok(_) if loaded_now => Ok(()),
+        Err(reason) if loaded_now => Err(Error::FailedLoadingDriverManager {
+            reason: reason.clone(),
+        }),
+        _ => Err(Error::FailedLoadingDriverManager {
+            reason: format!(
+                "Can not load {}, because a driver manager has already been loaded.",
+                file_name.to_string_lossy()
+            ),
+        }),
+    }
+}
+
+/// Makes sure a driver manager is loaded, loading it from its default location if need be. Called
+/// before the first call into ODBC, so a missing driver manager is reported with a meaningful
+/// error.
+pub(crate) fn ensure_driver_manager() -> Result<(), Error> {
+    match DRIVER_MANAGER.get_or_init(load_default) {
+        Ok(_) => Ok(()),
+        Err(reason) => Err(Error::FailedLoadingDriverManager {
+            reason: reason.clone(),
+        }),
+    }
+}
+
+fn load_default() -> Result<DriverManager, String> {
+    let mut reasons = Vec::new();
+    for file_name in DEFAULT_LIBRARIES {
+        match DriverManager::load(&OsString::from(file_name)) {
+            Ok(driver_manager) => return Ok(driver_manager),
+            Err(reason) => reasons.push(reason),
+        }
+    }
+    Err(reasons.join("\n"))
+}
+
+fn functions() -> Option<&'static Functions> {
+    DRIVER_MANAGER
+        .get_or_init(load_default)
+        .as_ref()
+        .ok()
+        .map(|driver_manager| &driver_manager.functions)
+}
+
+/// Declares a table of function pointers, which are resolved by symbol name, and a function for
+/// each entry calling through the table. Without a driver manager the functions return
+/// `SQL_ERROR`.
+macro_rules! functions {
+    ($($(#[$attr:meta])* fn $name:ident = $symbol:literal ($($arg:ident: $ty:ty),* $(,)?);)*) => {
+        #[allow(non_snake_case)]
+        struct Functions {
+            $($(#[$attr])* $name: unsafe extern "system" fn($($ty),*) -> SqlReturn,)*
+        }
+
+        impl Functions {
+            /// # Safety
+            ///
+            /// The symbols in `library` must match the signatures of the table.
+            unsafe fn load(library: &Library) -> Result<Self, libloading::Error> {
+                Ok(Self {
+                    $($(#[$attr])* $name: *library.get(concat!($symbol, "\0").as_bytes())?,)*
+                })
+            }
+        }
+
+        $(
+            $(#[$attr])*
+            #[allow(non_snake_case, clippy::too_many_arguments)]
+            pub unsafe fn $name($($arg: $ty),*) -> SqlReturn {
+                match functions() {
+                    Some(functions) => (functions.$name)($($arg),*),
+                    None => SqlReturn::ERROR,
+                }
+            }
+        )*
+    };
+}
+
+functions! {
+    fn SQLAllocHandle = "SQLAllocHandle"(
+        handle_type: HandleType,
+        input_handle: Handle,
+        output_handle: *mut Handle,
+    );
+    fn SQLFreeHandle = "SQLFreeHandle"(handle_type: HandleType, handle: Handle);
+    fn SQLSetEnvAttr = "SQLSetEnvAttr"(
+        environment_handle: HEnv,
+        attribute: EnvironmentAttribute,
+        value: Pointer,
+        string_length: Integer,
+    );
+    fn SQLDisconnect = "SQLDisconnect"(connection_handle: HDbc);
+    fn SQLEndTran = "SQLEndTran"(
+        handle_type: HandleType,
+        handle: Handle,
+        completion_type: CompletionType,
+    );
+    fn SQLBindCol = "SQLBindCol"(
+        hstmt: HStmt,
+        col_number: USmallInt,
+        target_type: CDataType,
+        target_value: Pointer,
+        buffer_length: Len,
+        length_or_indicator: *mut Len,
+    );
+    fn SQLBindParameter = "SQLBindParameter"(
+        hstmt: HStmt,
+        parameter_number: USmallInt,
+        input_output_type: ParamType,
+        value_type: CDataType,
+        parameter_type: SqlDataType,
+        column_size: ULen,
+        decimal_digits: SmallInt,
+        parameter_value_ptr: Pointer,
+        buffer_length: Len,
+        str_len_or_ind_ptr: *mut Len,
+    );
+    fn SQLCancel = "SQLCancel"(statement_handle: HStmt);
+    fn SQLCloseCursor = "SQLCloseCursor"(hstmt: HStmt);
+    #[cfg(feature = "odbc_version_3_80")]
+    fn SQLCompleteAsync = "SQLCompleteAsync"(
+        handle_type: HandleType,
+        handle: Handle,
+        async_ret_code_ptr: *mut RetCode,
+    );
+    fn SQLDescribeParam = "SQLDescribeParam"(
+        statement_handle: HStmt,
+        parameter_number: USmallInt,
+        data_type_ptr: *mut SqlDataType,
+        parameter_size_ptr: *mut ULen,
+        decimal_digits_ptr: *mut SmallInt,
+        nullable_ptr: *mut Nullability,
+    );
+    fn SQLExecute = "SQLExecute"(hstmt: HStmt);
+    fn SQLFetch = "SQLFetch"(statement_handle: HStmt);
+    fn SQLFetchScroll = "SQLFetchScroll"(
+        statement_handle: HStmt,
+        fetch_orientation: FetchOrientation,
+        fetch_offset: Len,
+    );
+    fn SQLFreeStmt = "SQLFreeStmt"(hstmt: HStmt, option: FreeStmtOption);
+    fn SQLGetData = "SQLGetData"(
+        statement_handle: HStmt,
+        col_or_param_num: USmallInt,
+        target_type: CDataType,
+        target_value_ptr: Pointer,
+        buffer_length: Len,
+        str_len_or_ind_ptr: *mut Len,
+    );
+    fn SQLMoreResults = "SQLMoreResults"(statement_handle: HStmt);
+    fn SQLNumParams = "SQLNumParams"(statement_handle: HStmt, parameter_count_ptr: *mut SmallInt);
+    fn SQLNumResultCols = "SQLNumResultCols"(
+        statement_handle: HStmt,
+        column_count_ptr: *mut SmallInt,
+    );
+    fn SQLParamData = "SQLParamData"(hstmt: HStmt, value_out: *mut Pointer);
+    fn SQLPutData = "SQLPutData"(hstmt: HStmt, data: Pointer, str_len_or_ind: Len);
+    fn SQLRowCount = "SQLRowCount"(hstmt: HStmt, row_count: *mut Len);
+
+    // Narrow functions
+
+    #[cfg(feature = "narrow")]
+    fn SQLColAttribute = "SQLColAttribute"(
+        statement_handle: HStmt,
+        column_number: USmallInt,
+        field_identifier: Desc,
+        character_attribute_ptr: Pointer,
+        buffer_length: SmallInt,
+        string_length_ptr: *mut SmallInt,
+        numeric_attribute_ptr: *mut Len,
+    );
+    #[cfg(feature = "narrow")]
+    fn SQLColumns = "SQLColumns"(
+        statement_handle: HStmt,
+        catalog_name: *const Char,
+        catalog_name_length: SmallInt,
+        schema_name: *const Char,
+        schema_name_length: SmallInt,
+        table_name: *const Char,
+        table_name_length: SmallInt,
+        column_name: *const Char,
+        column_name_length: SmallInt,
+    );
+    #[cfg(feature = "narrow")]
+    fn SQLConnect = "SQLConnect"(
+        connection_handle: HDbc,
+        server_name: *const Char,
+        name_length_1: SmallInt,
+        user_name: *const Char,
+        name_length_2: SmallInt,
+        authentication: *const Char,
+        name_length_3: SmallInt,
+    );
+    #[cfg(feature = "narrow")]
+    fn SQLDataSources = "SQLDataSources"(
+        environment_handle: HEnv,
+        direction: FetchOrientation,
+        server_name: *mut Char,
+        buffer_length_1: SmallInt,
+        name_length_1: *mut SmallInt,
+        description: *mut Char,
+        buffer_length_2: SmallInt,
+        name_length_2: *mut SmallInt,
+    );
+    #[cfg(feature = "narrow")]
+    fn SQLDescribeCol = "SQLDescribeCol"(
+        hstmt: HStmt,
+        col_number: USmallInt,
+        col_name: *mut Char,
+        buffer_length: SmallInt,
+        name_length: *mut SmallInt,
+        data_type: *mut SqlDataType,
+        col_size: *mut ULen,
+        decimal_digits: *mut SmallInt,
+        nullable: *mut Nullability,
+    );
+    #[cfg(feature = "narrow")]
+    fn SQLDriverConnect = "SQLDriverConnect"(
+        connection_handle: HDbc,
+        window_handle: HWnd,
+        in_connection_string: *const Char,
+        string_length_1: SmallInt,
+        out_connection_string: *mut Char,
+        buffer_length: SmallInt,
+        string_length_2: *mut SmallInt,
+        driver_completion: DriverConnectOption,
+    );
+    #[cfg(feature = "narrow")]
+    fn SQLDrivers = "SQLDrivers"(
+        henv: HEnv,
+        direction: FetchOrientation,
+        driver_desc: *mut Char,
+        driver_desc_max: SmallInt,
+        out_driver_desc: *mut SmallInt,
+        driver_attributes: *mut Char,
+        drvr_attr_max: SmallInt,
+        out_drvr_attr: *mut SmallInt,
+    );
+    #[cfg(feature = "narrow")]
+    fn SQLExecDirect = "SQLExecDirect"(
+        statement_handle: HStmt,
+        statement_text: *const Char,
+        text_length: Integer,
+    );
+    #[cfg(feature = "narrow")]
+    fn SQLGetConnectAttr = "SQLGetConnectAttr"(
+        connection_handle: HDbc,
+        attribute: ConnectionAttribute,
+        value_ptr: Pointer,
+        buffer_length: Integer,
+        string_length_ptr: *mut Integer,
+    );
+    #[cfg(feature = "narrow")]
+    fn SQLGetDiagRec = "SQLGetDiagRec"(
+        handle_type: HandleType,
+        handle: Handle,
+        record_number: SmallInt,
+        state: *mut Char,
+        native_error_ptr: *mut Integer,
+        message_text: *mut Char,
+        buffer_length: SmallInt,
+        text_length_ptr: *mut SmallInt,
+    );
+    #[cfg(feature = "narrow")]
+    fn SQLGetInfo = "SQLGetInfo"(
+        connection_handle: HDbc,
+        info_type: InfoType,
+        info_value_ptr: Pointer,
+        buffer_length: SmallInt,
+        string_length_ptr: *mut SmallInt,
+    );
+    #[cfg(feature = "narrow")]
+    fn SQLPrepare = "SQLPrepare"(hstmt: HStmt, statement_text: *const Char, text_length: Integer);
+    #[cfg(feature = "narrow")]
+    fn SQLSetConnectAttr = "SQLSetConnectAttr"(
+        hdbc: HDbc,
+        attr: ConnectionAttribute,
+        value: Pointer,
+        str_length: Integer,
+    );
+    #[cfg(feature = "narrow")]
+    fn SQLSetStmtAttr = "SQLSetStmtAttr"(
+        hstmt: HStmt,
+        attr: StatementAttribute,
+        value: Pointer,
+        str_length: Integer,
+    );
+    #[cfg(feature = "narrow")]
+    fn SQLTables = "SQLTables"(
+        statement_handle: HStmt,
+        catalog_name: *const Char,
+        name_length_1: SmallInt,
+        schema_name: *const Char,
+        name_length_2: SmallInt,
+        table_name: *const Char,
+        name_length_3: SmallInt,
+        table_type: *const Char,
+        name_length_4: SmallInt,
+    );
+
+    // Wide functions
+
+    #[cfg(not(feature = "narrow"))]
+    fn SQLColAttributeW = "SQLColAttributeW"(
+        statement_handle: HStmt,
+        column_number: USmallInt,
+        field_identifier: Desc,
+        character_attribute_ptr: Pointer,
+        buffer_length: SmallInt,
+        string_length_ptr: *mut SmallInt,
+        numeric_attribute_ptr: *mut Len,
+    );
+    #[cfg(not(feature = "narrow"))]
+    fn SQLColumnsW = "SQLColumnsW"(
+        statement_handle: HStmt,
+        catalog_name: *const WChar,
+        catalog_name_length: SmallInt,
+        schema_name: *const WChar,
+        schema_name_length: SmallInt,
+        table_name: *const WChar,
+        table_name_length: SmallInt,
+        column_name: *const WChar,
+        column_name_length: SmallInt,
+    );
+    #[cfg(not(feature = "narrow"))]
+    fn SQLConnectW = "SQLConnectW"(
+        connection_handle: HDbc,
+        server_name: *const WChar,
+        name_length_1: SmallInt,
+        user_name: *const WChar,
+        name_length_2: SmallInt,
+        authentication: *const WChar,
+        name_length_3: SmallInt,
+    );
+    #[cfg(not(feature = "narrow"))]
+    fn SQLDataSourcesW = "SQLDataSourcesW"(
+        environment_handle: HEnv,
+        direction: FetchOrientation,
+        server_name: *mut WChar,
+        buffer_length_1: SmallInt,
+        name_length_1: *mut SmallInt,
+        description: *mut WChar,
+        buffer_length_2: SmallInt,
+        name_length_2: *mut SmallInt,
+    );
+    #[cfg(not(feature = "narrow"))]
+    fn SQLDescribeColW = "SQLDescribeColW"(
+        hstmt: HStmt,
+        col_number: USmallInt,
+        col_name: *mut WChar,
+        buffer_length: SmallInt,
+        name_length: *mut SmallInt,
+        data_type: *mut SqlDataType,
+        col_size: *mut ULen,
+        decimal_digits: *mut SmallInt,
+        nullable: *mut Nullability,
+    );
+    #[cfg(not(feature = "narrow"))]
+    fn SQLDriverConnectW = "SQLDriverConnectW"(
+        connection_handle: HDbc,
+        window_handle: HWnd,
+        in_connection_string: *const WChar,
+        string_length_1: SmallInt,
+        out_connection_string: *mut WChar,
+        buffer_length: SmallInt,
+        string_length_2: *mut SmallInt,
+        driver_completion: DriverConnectOption,
+    );
+    #[cfg(not(feature = "narrow"))]
+    fn SQLDriversW = "SQLDriversW"(
+        henv: HEnv,
+        direction: FetchOrientation,
+        driver_desc: *mut WChar,
+        driver_desc_max: SmallInt,
+        out_driver_desc: *mut SmallInt,
+        driver_attributes: *mut WChar,
+        drvr_attr_max: SmallInt,
+        out_drvr_attr: *mut SmallInt,
+    );
+    #[cfg(not(feature = "narrow"))]
+    fn SQLExecDirectW = "SQLExecDirectW"(
+        statement_handle: HStmt,
+        statement_text: *const WChar,
+        text_length: Integer,
+    );
+    #[cfg(not(feature = "narrow"))]
+    fn SQLGetConnectAttrW = "SQLGetConnectAttrW"(
+        connection_handle: HDbc,
+        attribute: ConnectionAttribute,
+        value_ptr: Pointer,
+        buffer_length: Integer,
+        string_length_ptr: *mut Integer,
+    );
+    #[cfg(not(feature = "narrow"))]
+    fn SQLGetDiagRecW = "SQLGetDiagRecW"(
+        handle_type: HandleType,
+        handle: Handle,
+        record_number: SmallInt,
+        state: *mut WChar,
+        native_error_ptr: *mut Integer,
+        message_text: *mut WChar,
+        buffer_length: SmallInt,
+        text_length_ptr: *mut SmallInt,
+    );
+    #[cfg(not(feature = "narrow"))]
+    fn SQLGetInfoW = "SQLGetInfoW"(
+        connection_handle: HDbc,
+        info_type: InfoType,
+        info_value_ptr: Pointer,
+        buffer_length: SmallInt,
+        string_length_ptr: *mut SmallInt,
+    );
+    #[cfg(not(feature = "narrow"))]
+    fn SQLPrepareW = "SQLPrepareW"(
+        hstmt: HStmt,
+        statement_text: *const WChar,
+        text_length: Integer,
+    );
+    #[cfg(not(feature = "narrow"))]
+    fn SQLSetConnectAttrW = "SQLSetConnectAttrW"(
+        hdbc: HDbc,
+        attr: ConnectionAttribute,
+        value: Pointer,
+        str_length: Integer,
+    );
+    #[cfg(not(feature = "narrow"))]
+    fn SQLSetStmtAttrW = "SQLSetStmtAttrW"(
+        hstmt: HStmt,
+        attr: StatementAttribute,
+        value: Pointer,
+        str_length: Integer,
+    );
+    #[cfg(not(feature = "narrow"))]
+    fn SQLTablesW = "SQLTablesW"(
+        statement_handle: HStmt,
+        catalog_name: *const WChar,
+        name_length_1: SmallInt,
+        schema_name: *const WChar,
+        name_length_2: SmallInt,
+        table_name: *const WChar,
+        name_length_3: SmallInt,
+        table_type: *const WChar,
+        name_length_4: SmallInt,
+    );
+
+    // Functions `odbc-sys` does not declare, or declares with enumerations, which can not represent
+    // driver specific attributes. See `driver_attributes` and `statement`.
+
+    #[cfg(feature = "narrow")]
+    fn sql_primary_keys = "SQLPrimaryKeys"(
+        hstmt: HStmt,
+        catalog_name: *const SqlChar,
+        catalog_name_length: SmallInt,
+        schema_name: *const SqlChar,
+        schema_name_length: SmallInt,
+        table_name: *const SqlChar,
+        table_name_length: SmallInt,
+    );
+    #[cfg(not(feature = "narrow"))]
+    fn sql_primary_keys = "SQLPrimaryKeysW"(
+        hstmt: HStmt,
+        catalog_name: *const SqlChar,
+        catalog_name_length: SmallInt,
+        schema_name: *const SqlChar,
+        schema_name_length: SmallInt,
+        table_name: *const SqlChar,
+        table_name_length: SmallInt,
+    );
+    #[cfg(feature = "narrow")]
+    fn sql_set_stmt_attr = "SQLSetStmtAttr"(
+        hstmt: HStmt,
+        attr: Integer,
+        value: Pointer,
+        length: Integer,
+    );
+    #[cfg(not(feature = "narrow"))]
+    fn sql_set_stmt_attr = "SQLSetStmtAttrW"(
+        hstmt: HStmt,
+        attr: Integer,
+        value: Pointer,
+        length: Integer,
+    );
+    #[cfg(feature = "narrow")]
+    fn sql_set_connect_attr = "SQLSetConnectAttr"(
+        hdbc: HDbc,
+        attr: Integer,
+        value: Pointer,
+        length: Integer,
+    );
+    #[cfg(not(feature = "narrow"))]
+    fn sql_set_connect_attr = "SQLSetConnectAttrW"(
+        hdbc: HDbc,
+        attr: Integer,
+        value: Pointer,
+        length: Integer,
+    );
+    #[cfg(feature = "narrow")]
+    fn sql_get_info = "SQLGetInfo"(
+        hdbc: HDbc,
+        info_type: USmallInt,
+        value: Pointer,
+        buffer_length: SmallInt,
+        string_length: *mut SmallInt,
+    );
+    #[cfg(not(feature = "narrow"))]
+    fn sql_get_info = "SQLGetInfoW"(
+        hdbc: HDbc,
+        info_type: USmallInt,
+        value: Pointer,
+        buffer_length: SmallInt,
+        string_length: *mut SmallInt,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DriverManager;
+
+    #[test]
+    fn missing_library_names_file() {
+        let reason = DriverManager::load("libdoes_not_exist.so".as_ref())
+            .err()
+            .unwrap();
+
+        assert!(reason.starts_with("libdoes_not_exist.so: "));
+    }
+}
@@ -1,21 +1,23 @@
 use super::{
     as_handle::AsHandle,
     drop_handle,
+    ffi::{SQLAllocHandle, SQLSetEnvAttr},
+    leak_tracking::track,
     sql_char::SqlChar,
     sql_result::{ExtSqlReturn, SqlResult},
     Connection,
 };
 use odbc_sys::{
     AttrCpMatch, AttrOdbcVersion, EnvironmentAttribute, FetchOrientation, HDbc, HEnv, Handle,
-    HandleType, SQLAllocHandle, SQLSetEnvAttr,
+    HandleType,
 };
 use std::ptr::null_mut;
 
 #[cfg(feature = "narrow")]
-use odbc_sys::{SQLDataSources as sql_data_sources, SQLDrivers as sql_drivers};
+use super::ffi::{SQLDataSources as sql_data_sources, SQLDrivers as sql_drivers};
 
 #[cfg(not(feature = "narrow"))]
-use odbc_sys::{SQLDataSourcesW as sql_data_sources, SQLDriversW as sql_drivers};
+use super::ffi::{SQLDataSourcesW as sql_data_sources, SQLDriversW as sql_drivers};
 
 /// An `Environment` is a global context, in which to access data.
 ///
@@ -103,8 +105,11 @@ impl Environment {
             let mut handle = null_mut();
             let result: SqlResult<()> = SQLAllocHandle(HandleType::Env, null_mut(), &mut handle)
                 .into_sql_result("SQLAllocHandle");
-            result.on_success(|| Environment {
-                handle: handle as HEnv,
+            result.on_success(|| {
+                track(handle, HandleType::Env);
+                Environment {
+                    handle: handle as HEnv,
+                }
             })
         }
     }
@@ -129,7 +134,10 @@ impl Environment {
         unsafe {
             SQLAllocHandle(HandleType::Dbc, self.as_handle(), &mut handle)
                 .into_sql_result("SQLAllocHandle")
-                .on_success(|| Connection::new(handle as HDbc))
+                .on_success(|| {
+                    track(handle, HandleType::Dbc);
+                    Connection::new(handle as HDbc)
+                })
         }
     }
 
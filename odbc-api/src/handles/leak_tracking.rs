@@ -0,0 +1,186 @@
+//! Bookkeeping of live ODBC handles in debug builds. Helps long running applications to find
+//! handles which are never freed. In release builds (without `debug_assertions`) all functions in
+//! this module are no-ops and no bookkeeping happens.
+//!
+//! Enabling the `handle_backtraces` feature additionally captures a backtrace each time a handle
+//! is allocated, so leaks can be traced back to the code which allocated the handle.
+
+use odbc_sys::{Handle, HandleType};
+
+/// Number of handles allocated by this crate, which have not been freed yet. See [`live_handles`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LiveHandles {
+    /// Number of live environment handles.
+    pub environments: usize,
+    /// Number of live connection handles.
+    pub connections: usize,
+    /// Number of live statement handles.
+    pub statements: usize,
+}
+
+/// Number of handles allocated and not yet freed by this crate. `None` if handle tracking is not
+/// available, because the crate has been compiled without `debug_assertions`.
+///
+/// Handles are counted between their allocation and the call to [`crate::handles::drop_handle`].
+/// This means handles passed to application code using e.g. `into_sys` are still counted as live,
+/// unless they are handed back to this crate to free them.
+///
+/// ```no_run
+/// use odbc_api::{handles::live_handles, Environment};
+///
+/// let env = Environment::new()?;
+/// let conn = env.connect_with_connection_string("DSN=MyDataSource;")?;
+/// if let Some(live) = live_handles() {
+///     assert_eq!(1, live.connections);
+/// }
+/// # Ok::<(), odbc_api::Error>(())
+/// ```
+pub fn live_handles() -> Option<LiveHandles> {
+    imp::live_handles()
+}
+
+/// Logs a warning for each handle which has been allocated, but not freed yet. If the
+/// `handle_backtraces` feature is active, the warnings include a backtrace of the allocation.
+/// Intended to be called before shutting down an application, after all connections and
+/// statements should have been dropped. Returns the number of handles reported. Always `0` if
+/// handle tracking is not available. See [`live_handles`].
+pub fn log_leaked_handles() -> usize {
+    imp::log_leaked_handles()
+}
+
+/// Register a freshly allocated handle.
+pub(super) fn track(handle: Handle, handle_type: HandleType) {
+    imp::track(handle, handle_type)
+}
+
+/// Remove a handle from the bookkeeping, once it is freed. Handles which have not been allocated
+/// by this crate are ignored.
+pub(super) fn untrack(handle: Handle) {
+    imp::untrack(handle)
+}
+
+#[cfg(debug_assertions)]
+mod imp {
+    use std::sync::Mutex;
+
+    #[cfg(feature = "handle_backtraces")]
+    use std::backtrace::Backtrace;
+
+    use log::warn;
+    use odbc_sys::{Handle, HandleType};
+
+    use super::LiveHandles;
+
+    struct Entry {
+        handle: usize,
+        handle_type: HandleType,
+        #[cfg(feature = "handle_backtraces")]
+        backtrace: Backtrace,
+    }
+
+    /// We expect only a moderate number of handles to be alive at the same time, so a linear
+    /// search for the entry to remove is fine.
+    static REGISTRY: Mutex<Vec<Entry>> = Mutex::new(Vec::new());
+
+    fn registry() -> std::sync::MutexGuard<'static, Vec<Entry>> {
+        // The registry is consistent, even if another thread panicked while holding the lock.
+        REGISTRY
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn live_handles() -> Option<LiveHandles> {
+        let mut live = LiveHandles::default();
+        for entry in registry().iter() {
+            match entry.handle_type {
+                HandleType::Env => live.environments += 1,
+                HandleType::Dbc => live.connections += 1,
+                HandleType::Stmt => live.statements += 1,
+                HandleType::Desc => (),
+            }
+        }
+        Some(live)
+    }
+
+    pub fn log_leaked_handles() -> usize {
+        let registry = registry();
+        for entry in registry.iter() {
+            #[cfg(feature = "handle_backtraces")]
+            warn!(
+                "Leaked ODBC handle {:#x} of type {:?}. Allocated at:\n{}",
+                entry.handle, entry.handle_type, entry.backtrace
+            );
+            #[cfg(not(feature = "handle_backtraces"))]
+            warn!(
+                "Leaked ODBC handle {:#x} of type {:?}.",
+                entry.handle, entry.handle_type
+            );
+        }
+        registry.len()
+    }
+
+    pub fn track(handle: Handle, handle_type: HandleType) {
+        registry().push(Entry {
+            handle: handle as usize,
+            handle_type,
+            #[cfg(feature = "handle_backtraces")]
+            backtrace: Backtrace::force_capture(),
+        });
+    }
+
+    pub fn untrack(handle: Handle) {
+        let mut registry = registry();
+        if let Some(index) = registry
+            .iter()
+            .position(|entry| entry.handle == handle as usize)
+        {
+            registry.swap_remove(index);
+        }
+    }
+}
+
+#[cfg(not(debug_assertions))]
+mod imp {
+    use odbc_sys::{Handle, HandleType};
+
+    use super::LiveHandles;
+
+    pub fn live_handles() -> Option<LiveHandles> {
+        None
+    }
+
+    pub fn log_leaked_handles() -> usize {
+        0
+    }
+
+    pub fn track(_handle: Handle, _handle_type: HandleType) {}
+
+    pub fn untrack(_handle: Handle) {}
+}
+
+#[cfg(all(test, debug_assertions))]
+mod tests {
+    use odbc_sys::{Handle, HandleType};
+
+    use super::{live_handles, track, untrack};
+
+    #[test]
+    fn count_tracked_handles() {
+        // Fake handles, which are never passed to ODBC.
+        let env = 0x10 as Handle;
+        let stmt = 0x20 as Handle;
+
+        track(env, HandleType::Env);
+        track(stmt, HandleType::Stmt);
+        let live = live_handles().unwrap();
+        assert_eq!(1, live.environments);
+        assert_eq!(1, live.statements);
+
+        untrack(stmt);
+        // Untracking an unknown handle is ignored
+        untrack(0x30 as Handle);
+        assert_eq!(0, live_handles().unwrap().statements);
+        untrack(env);
+        assert_eq!(0, live_handles().unwrap().environments);
+    }
+}
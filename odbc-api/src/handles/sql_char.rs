@@ -2,16 +2,17 @@
 //! in this module, so the rest of the crate doesn't have to.
 
 use super::buffer::{buf_ptr, mut_buf_ptr};
-use std::{borrow::Cow, mem::size_of};
+use std::{
+    borrow::Cow,
+    char::{decode_utf16, DecodeUtf16Error, REPLACEMENT_CHARACTER},
+    mem::size_of,
+};
 
 #[cfg(feature = "narrow")]
-use std::{ffi::CStr, string::FromUtf8Error};
+use std::{ffi::CStr, str::Utf8Error, string::FromUtf8Error};
 
 #[cfg(not(feature = "narrow"))]
-use std::{
-    char::{decode_utf16, DecodeUtf16Error},
-    marker::PhantomData,
-};
+use std::marker::PhantomData;
 
 #[cfg(not(feature = "narrow"))]
 use widestring::{U16CStr, U16String};
@@ -32,7 +33,141 @@ pub fn slice_to_utf8(text: &[u8]) -> Result<String, FromUtf8Error> {
 }
 #[cfg(not(feature = "narrow"))]
 pub fn slice_to_utf8(text: &[u16]) -> Result<String, DecodeUtf16Error> {
-    decode_utf16(text.iter().copied()).collect()
+    let mut out = String::with_capacity(text.len());
+    slice_to_utf8_into(text, &mut out)?;
+    Ok(out)
+}
+
+/// Same as [`slice_to_utf8`], but appends the text to `out` instead of allocating a new `String`.
+/// Reusing `out` avoids allocations then converting many values in a row. On error `out` is left
+/// unchanged.
+#[cfg(feature = "narrow")]
+pub fn slice_to_utf8_into(text: &[u8], out: &mut String) -> Result<(), Utf8Error> {
+    out.push_str(std::str::from_utf8(text)?);
+    Ok(())
+}
+/// Same as [`slice_to_utf8`], but appends the text to `out` instead of allocating a new `String`.
+/// Reusing `out` avoids allocations then converting many values in a row. On error `out` is left
+/// unchanged.
+#[cfg(not(feature = "narrow"))]
+pub fn slice_to_utf8_into(text: &[u16], out: &mut String) -> Result<(), DecodeUtf16Error> {
+    utf16_to_utf8_into(text, out, false)
+}
+
+/// Appends the UTF-8 representation of UTF-16 `text` to `out`. Unpaired surrogates are replaced
+/// with [`REPLACEMENT_CHARACTER`].
+pub fn slice_to_utf8_lossy_into(text: &[u16], out: &mut String) {
+    // Replacing invalid characters never fails.
+    let _ = utf16_to_utf8_into(text, out, true);
+}
+
+/// Appends the UTF-8 representation of UTF-16 `text` to `out`. Unpaired surrogates are replaced
+/// with `U+FFFD` if `replace_invalid` is `true`. Otherwise an error is returned and `out` is left
+/// unchanged.
+///
+/// Text fetched from databases is often mostly ASCII. Runs of ASCII characters are therefore
+/// checked and converted a block at a time with SIMD instructions, rather than decoding each
+/// character individually.
+fn utf16_to_utf8_into(
+    text: &[u16],
+    out: &mut String,
+    replace_invalid: bool,
+) -> Result<(), DecodeUtf16Error> {
+    let len_before = out.len();
+    out.reserve(text.len());
+    let mut rest = text;
+    while !rest.is_empty() {
+        let ascii_len = push_ascii_prefix(rest, out);
+        // Surrogate pairs never contain ASCII code units, so splitting the text at the next ASCII
+        // character never splits a pair.
+        let tail = &rest[ascii_len..];
+        let non_ascii_len = tail
+            .iter()
+            .position(|&unit| unit < 0x80)
+            .unwrap_or(tail.len());
+        let (non_ascii, tail) = tail.split_at(non_ascii_len);
+        for c in decode_utf16(non_ascii.iter().copied()) {
+            match c {
+                Ok(c) => out.push(c),
+                Err(_) if replace_invalid => out.push(REPLACEMENT_CHARACTER),
+                Err(error) => {
+                    out.truncate(len_before);
+                    return Err(error);
+                }
+            }
+        }
+        rest = tail;
+    }
+    Ok(())
+}
+
+/// Appends the leading ASCII characters of `text` to `out` and returns their number.
+fn push_ascii_prefix(text: &[u16], out: &mut String) -> usize {
+    // Safe: Only ASCII characters are appended, which are valid UTF-8 and represented by their
+    // value.
+    let bytes = unsafe { out.as_mut_vec() };
+    let mut len = push_ascii_blocks(text, bytes);
+    for &unit in &text[len..] {
+        if unit >= 0x80 {
+            break;
+        }
+        bytes.push(unit as u8);
+        len += 1;
+    }
+    len
+}
+
+/// Number of UTF-16 code units converted at once by [`push_ascii_blocks`].
+const ASCII_BLOCK_LEN: usize = 16;
+
+/// Appends leading blocks of [`ASCII_BLOCK_LEN`] ASCII characters to `bytes`, and returns the
+/// number of characters appended. Stops at the first block containing a non ASCII character.
+#[cfg(target_arch = "x86_64")]
+fn push_ascii_blocks(text: &[u16], bytes: &mut Vec<u8>) -> usize {
+    use std::arch::x86_64::{
+        __m128i, _mm_and_si128, _mm_cmpeq_epi8, _mm_loadu_si128, _mm_movemask_epi8, _mm_or_si128,
+        _mm_packus_epi16, _mm_set1_epi16, _mm_setzero_si128, _mm_storeu_si128,
+    };
+
+    let mut len = 0;
+    for block in text.chunks_exact(ASCII_BLOCK_LEN) {
+        // Safe: SSE2 is part of the x86_64 baseline. Loads are unaligned and read the 32 bytes of
+        // `block`. The store writes 16 bytes into capacity reserved right before.
+        unsafe {
+            let low = _mm_loadu_si128(block.as_ptr() as *const __m128i);
+            let high = _mm_loadu_si128(block.as_ptr().add(8) as *const __m128i);
+            // Any bit above the lowest seven set, in any of the code units?
+            let non_ascii_bits = _mm_and_si128(_mm_or_si128(low, high), _mm_set1_epi16(!0x7f));
+            let is_zero = _mm_cmpeq_epi8(non_ascii_bits, _mm_setzero_si128());
+            if _mm_movemask_epi8(is_zero) != 0xffff {
+                break;
+            }
+            // All code units are smaller than 128, so packing them into bytes is lossless.
+            let packed = _mm_packus_epi16(low, high);
+            bytes.reserve(ASCII_BLOCK_LEN);
+            let end = bytes.as_mut_ptr().add(bytes.len());
+            _mm_storeu_si128(end as *mut __m128i, packed);
+            bytes.set_len(bytes.len() + ASCII_BLOCK_LEN);
+        }
+        len += ASCII_BLOCK_LEN;
+    }
+    len
+}
+
+/// Appends leading blocks of [`ASCII_BLOCK_LEN`] ASCII characters to `bytes`, and returns the
+/// number of characters appended. Stops at the first block containing a non ASCII character.
+#[cfg(not(target_arch = "x86_64"))]
+fn push_ascii_blocks(text: &[u16], bytes: &mut Vec<u8>) -> usize {
+    let mut len = 0;
+    for block in text.chunks_exact(ASCII_BLOCK_LEN) {
+        // Written branch free, so the compiler can vectorize the check.
+        if block.iter().fold(0, |acc, &unit| acc | unit) >= 0x80 {
+            break;
+        }
+        bytes.extend(block.iter().map(|&unit| unit as u8));
+        len += ASCII_BLOCK_LEN;
+    }
+    len
 }
 
 #[cfg(feature = "narrow")]
@@ -63,6 +198,18 @@ fn sz_to_utf8(buffer: &[u8]) -> String {
     c_str.to_string_lossy().into_owned()
 }
 
+/// Zero terminated representation of `text` in the character encoding used for ODBC function
+/// calls. Used for arguments, then ODBC requires a pointer to a zero terminated string, which must
+/// stay valid beyond the function call.
+#[cfg(not(feature = "narrow"))]
+pub fn utf8_to_sz(text: &str) -> Vec<u16> {
+    text.encode_utf16().chain(Some(0)).collect()
+}
+#[cfg(feature = "narrow")]
+pub fn utf8_to_sz(text: &str) -> Vec<u8> {
+    text.bytes().chain(Some(0)).collect()
+}
+
 /// Buffer length in bytes, not characters
 pub fn binary_length(buffer: &[SqlChar]) -> usize {
     buffer.len() * size_of::<SqlChar>()
@@ -238,3 +385,56 @@ impl OutputStringBuffer {
         self.actual_length >= self.buffer.len().try_into().unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::utf16_to_utf8_into;
+
+    fn convert(text: &str) -> String {
+        let utf16: Vec<u16> = text.encode_utf16().collect();
+        let mut out = String::new();
+        utf16_to_utf8_into(&utf16, &mut out, false).unwrap();
+        out
+    }
+
+    #[test]
+    fn ascii_text() {
+        let text = "Hello, World! This text is longer than a couple of blocks of ASCII.";
+        assert_eq!(text, convert(text));
+    }
+
+    #[test]
+    fn mixed_text() {
+        let text = "Grüße aus Köln, 東京 and 🦀 crabs, all around the world™. Ende, aber länger.";
+        assert_eq!(text, convert(text));
+    }
+
+    #[test]
+    fn replace_unpaired_surrogate() {
+        let utf16 = [b'a' as u16, 0xD800, b'b' as u16];
+        let mut out = "Prefix ".to_owned();
+        utf16_to_utf8_into(&utf16, &mut out, true).unwrap();
+        assert_eq!("Prefix a\u{FFFD}b", out);
+    }
+}
+
+#[cfg(all(test, not(feature = "narrow")))]
+mod wide_tests {
+    use super::slice_to_utf8_into;
+
+    #[test]
+    fn append_to_existing_text() {
+        let mut out = "Hello, ".to_owned();
+        let text: Vec<u16> = "Wörld".encode_utf16().collect();
+        slice_to_utf8_into(&text, &mut out).unwrap();
+        assert_eq!("Hello, Wörld", out);
+    }
+
+    #[test]
+    fn leave_text_unchanged_on_error() {
+        let mut out = "Hello".to_owned();
+        let text = [b'a' as u16, 0xD800];
+        assert!(slice_to_utf8_into(&text, &mut out).is_err());
+        assert_eq!("Hello", out);
+    }
+}
@@ -3,34 +3,89 @@ use super::{
     bind::{CDataMut, DelayedInput, HasDataType},
     buffer::{clamp_small_int, mut_buf_ptr},
     column_description::{ColumnDescription, Nullability},
+    cursor_type::{CursorSensitivity, CursorType},
     data_type::DataType,
+    driver_attributes::{set_stmt_attr, SS_PARAM_FOCUS},
     drop_handle,
+    ffi::{
+        SQLBindCol, SQLBindParameter, SQLCancel, SQLCloseCursor, SQLCompleteAsync,
+        SQLDescribeParam, SQLExecute, SQLFetch, SQLFetchScroll, SQLFreeStmt, SQLGetData,
+        SQLMoreResults, SQLNumParams, SQLNumResultCols, SQLParamData, SQLPutData, SQLRowCount,
+    },
     sql_char::{binary_length, is_truncated_bin, resize_to_fit_without_tz},
     sql_result::ExtSqlReturn,
     CData, SqlChar, SqlResult, SqlText,
 };
 use odbc_sys::{
-    Desc, FreeStmtOption, HDbc, HStmt, Handle, HandleType, Len, ParamType, Pointer, SQLBindCol,
-    SQLBindParameter, SQLCloseCursor, SQLCompleteAsync, SQLDescribeParam, SQLExecute, SQLFetch,
-    SQLFreeStmt, SQLGetData, SQLNumParams, SQLNumResultCols, SQLParamData, SQLPutData, SQLRowCount,
-    SqlDataType, SqlReturn, StatementAttribute, IS_POINTER,
+    CDataType, Desc, FetchOrientation, FreeStmtOption, HDbc, HStmt, Handle, HandleType, Len,
+    ParamType, Pointer, SqlDataType, SqlReturn, StatementAttribute, IS_INTEGER, IS_POINTER, NTS,
 };
 use std::{ffi::c_void, marker::PhantomData, mem::ManuallyDrop, ptr::null_mut};
 
+/// Returned by [`Statement::param_data_with_output_streams`].
+#[cfg(feature = "odbc_version_3_80")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamData {
+    /// The value of the delayed input parameter, identified by the token passed to bind parameter
+    /// as value pointer, is required.
+    NeedData(Pointer),
+    /// All delayed input parameters have been sent. Values of streamed output parameters are
+    /// available.
+    OutputAvailable,
+    /// All delayed input parameters have been sent. No values of streamed output parameters are
+    /// available.
+    Done,
+}
+
+/// `SQL_SS_TABLE`. Microsoft SQL Server specific data type of table valued parameters.
+const SS_TABLE: SqlDataType = SqlDataType(-153);
+
 #[cfg(feature = "narrow")]
-use odbc_sys::{
+use super::ffi::{
     SQLColAttribute as sql_col_attribute, SQLColumns as sql_columns,
     SQLDescribeCol as sql_describe_col, SQLExecDirect as sql_exec_direc, SQLPrepare as sql_prepare,
     SQLSetStmtAttr as sql_set_stmt_attr, SQLTables as sql_tables,
 };
 
 #[cfg(not(feature = "narrow"))]
-use odbc_sys::{
+use super::ffi::{
     SQLColAttributeW as sql_col_attribute, SQLColumnsW as sql_columns,
     SQLDescribeColW as sql_describe_col, SQLExecDirectW as sql_exec_direc,
     SQLPrepareW as sql_prepare, SQLSetStmtAttrW as sql_set_stmt_attr, SQLTablesW as sql_tables,
 };
 
+// `odbc-sys` does not declare `SQLPrimaryKeys`, yet driver managers export it like any other
+// catalog function.
+#[cfg(not(feature = "dynamic_loading"))]
+extern "system" {
+    #[cfg(not(feature = "narrow"))]
+    #[link_name = "SQLPrimaryKeysW"]
+    fn sql_primary_keys(
+        hstmt: HStmt,
+        catalog_name: *const SqlChar,
+        catalog_name_length: i16,
+        schema_name: *const SqlChar,
+        schema_name_length: i16,
+        table_name: *const SqlChar,
+        table_name_length: i16,
+    ) -> SqlReturn;
+
+    #[cfg(feature = "narrow")]
+    #[link_name = "SQLPrimaryKeys"]
+    fn sql_primary_keys(
+        hstmt: HStmt,
+        catalog_name: *const SqlChar,
+        catalog_name_length: i16,
+        schema_name: *const SqlChar,
+        schema_name_length: i16,
+        table_name: *const SqlChar,
+        table_name_length: i16,
+    ) -> SqlReturn;
+}
+
+#[cfg(feature = "dynamic_loading")]
+use super::ffi::sql_primary_keys;
+
 /// An owned valid (i.e. successfully allocated) ODBC statement handle.
 pub struct StatementImpl<'s> {
     parent: PhantomData<&'s HDbc>,
@@ -200,6 +255,22 @@ pub trait Statement: AsHandle {
         SQLFetch(self.as_sys()).into_sql_result("SQLFetch")
     }
 
+    /// Fetches the row set specified by `orientation` and `offset`. E.g. with
+    /// [`FetchOrientation::Absolute`] the row set starts with the row at the one based position
+    /// `offset`. Requires a scrollable cursor for any other orientation than
+    /// [`FetchOrientation::Next`].
+    ///
+    /// # Safety
+    ///
+    /// Fetch dereferences bound column pointers.
+    unsafe fn fetch_scroll(
+        &mut self,
+        orientation: FetchOrientation,
+        offset: isize,
+    ) -> SqlResult<()> {
+        SQLFetchScroll(self.as_sys(), orientation, offset).into_sql_result("SQLFetchScroll")
+    }
+
     /// Retrieves data for a single column in the result set or for a single parameter.
     fn get_data(&mut self, col_or_param_num: u16, target: &mut impl CDataMut) -> SqlResult<()> {
         unsafe {
@@ -324,6 +395,12 @@ pub trait Statement: AsHandle {
         unsafe { SQLCloseCursor(self.as_sys()) }.into_sql_result("SQLCloseCursor")
     }
 
+    /// Close the cursor associated with the statement, if any, and discard pending results. Unlike
+    /// [`Self::close_cursor`] this does not fail if no cursor is open.
+    fn close_cursor_if_open(&mut self) -> SqlResult<()> {
+        unsafe { SQLFreeStmt(self.as_sys(), FreeStmtOption::Close) }.into_sql_result("SQLFreeStmt")
+    }
+
     /// Send an SQL statement to the data source for preparation. The application can include one or
     /// more parameter markers in the SQL statement. To include a parameter marker, the application
     /// embeds a question mark (?) into the SQL string at the appropriate position.
@@ -443,6 +520,49 @@ pub trait Statement: AsHandle {
         }
     }
 
+    /// Sets the kind of cursor created by executing the statement. Must be called before the
+    /// statement is executed.
+    fn set_cursor_type(&mut self, cursor_type: CursorType) -> SqlResult<()> {
+        unsafe {
+            sql_set_stmt_attr(
+                self.as_sys(),
+                StatementAttribute::CursorType,
+                cursor_type.as_attribute_value() as Pointer,
+                0,
+            )
+            .into_sql_result("SQLSetStmtAttr")
+        }
+    }
+
+    /// Sets whether the cursor created by executing the statement makes changes made by others
+    /// visible. Must be called before the statement is executed.
+    fn set_cursor_sensitivity(&mut self, sensitivity: CursorSensitivity) -> SqlResult<()> {
+        unsafe {
+            sql_set_stmt_attr(
+                self.as_sys(),
+                StatementAttribute::CursorSensitivity,
+                sensitivity.as_attribute_value() as Pointer,
+                0,
+            )
+            .into_sql_result("SQLSetStmtAttr")
+        }
+    }
+
+    /// Limits the number of rows returned by the result set of subsequently executed queries to
+    /// `max_rows`. `0` means no limit, which is the default. Drivers may use this to optimize the
+    /// query. This is equivalent to setting `SQL_ATTR_MAX_ROWS` in the bare C API.
+    fn set_max_rows(&mut self, max_rows: usize) -> SqlResult<()> {
+        unsafe {
+            sql_set_stmt_attr(
+                self.as_sys(),
+                StatementAttribute::MaxRows,
+                max_rows as Pointer,
+                0,
+            )
+            .into_sql_result("SQLSetStmtAttr")
+        }
+    }
+
     /// Enables or disables asynchronous execution for this statement handle. If asynchronous
     /// execution is not enabled on connection level it is disabled by default and everything is
     /// executed synchronously.
@@ -560,6 +680,97 @@ pub trait Statement: AsHandle {
         .into_sql_result("SQLBindParameter")
     }
 
+    /// Binds a parameter marker as streamed output parameter. Rather than being written into a
+    /// buffer, its value is retrieved in parts using [`Statement::get_data`] after execution. Use
+    /// [`Statement::output_param_data`] to find out which parameters are available.
+    ///
+    /// * `token`: Passed back by [`Statement::output_param_data`] to identify the parameter.
+    ///
+    /// See <https://learn.microsoft.com/sql/odbc/reference/develop-app/retrieving-output-parameters-using-sqlgetdata>.
+    ///
+    /// # Safety
+    ///
+    /// * It is up to the caller to ensure the lifetimes of the bound indicator.
+    /// * Calling this function may influence other statements that share the APD.
+    #[cfg(feature = "odbc_version_3_80")]
+    unsafe fn bind_output_stream_parameter(
+        &mut self,
+        parameter_number: u16,
+        cdata_type: CDataType,
+        data_type: DataType,
+        token: Pointer,
+        indicator: *mut isize,
+    ) -> SqlResult<()> {
+        SQLBindParameter(
+            self.as_sys(),
+            parameter_number,
+            ParamType::OutputStream,
+            cdata_type,
+            data_type.data_type(),
+            data_type.column_size(),
+            data_type.decimal_digits(),
+            token,
+            0,
+            indicator,
+        )
+        .into_sql_result("SQLBindParameter")
+    }
+
+    /// Binds a table valued parameter to a parameter marker. Table valued parameters are specific
+    /// to Microsoft SQL Server. Use [`Statement::set_parameter_focus`] afterwards to bind the
+    /// columns of the table.
+    ///
+    /// * `type_name`: Zero terminated name of the table type.
+    /// * `capacity`: Maximum number of rows the bound column buffers can hold.
+    /// * `num_rows`: Points to the number of rows actually passed.
+    ///
+    /// See <https://learn.microsoft.com/sql/relational-databases/native-client-odbc-table-valued-parameters/binding-and-data-transfer-of-table-valued-parameters-and-column-values>.
+    ///
+    /// # Safety
+    ///
+    /// * It is up to the caller to ensure the lifetimes of the bound parameters.
+    /// * `type_name` must be zero terminated.
+    unsafe fn bind_table_valued_parameter(
+        &mut self,
+        parameter_number: u16,
+        type_name: &[SqlChar],
+        capacity: usize,
+        num_rows: *const isize,
+    ) -> SqlResult<()> {
+        SQLBindParameter(
+            self.as_sys(),
+            parameter_number,
+            ParamType::Input,
+            CDataType::Default,
+            SS_TABLE,
+            capacity,
+            0,
+            // We cast const to mut here, but we specify the input_output_type as input.
+            type_name.as_ptr() as *mut c_void,
+            NTS,
+            // We cast const to mut here, but we specify the input_output_type as input.
+            num_rows as *mut isize,
+        )
+        .into_sql_result("SQLBindParameter")
+    }
+
+    /// Subsequent calls to [`Statement::bind_input_parameter`] bind the columns of the table
+    /// valued parameter with number `parameter_number`. Pass `0` to bind the parameters of the
+    /// statement itself again.
+    ///
+    /// # Safety
+    ///
+    /// Only supported by Microsoft SQL Server.
+    unsafe fn set_parameter_focus(&mut self, parameter_number: u16) -> SqlResult<()> {
+        set_stmt_attr(
+            self.as_sys(),
+            SS_PARAM_FOCUS,
+            parameter_number as Pointer,
+            IS_INTEGER,
+        )
+        .into_sql_result("SQLSetStmtAttr")
+    }
+
     /// `true` if a given column in a result set is unsigned or not a numeric type, `false`
     /// otherwise.
     ///
@@ -622,50 +833,65 @@ pub trait Statement: AsHandle {
     /// The column alias, if it applies. If the column alias does not apply, the column name is
     /// returned. If there is no column name or a column alias, an empty string is returned.
     fn col_name(&self, column_number: u16, buffer: &mut Vec<SqlChar>) -> SqlResult<()> {
+        unsafe { self.string_col_attribute(Desc::Name, column_number, buffer) }
+    }
+
+    /// Data source dependent name of the type of the column, e.g. `geometry` or `hierarchyid`.
+    fn col_type_name(&self, column_number: u16, buffer: &mut Vec<SqlChar>) -> SqlResult<()> {
+        unsafe { self.string_col_attribute(Desc::TypeName, column_number, buffer) }
+    }
+
+    /// # Safety
+    ///
+    /// It is the callers responsibility to ensure that `attribute` refers to a string attribute.
+    unsafe fn string_col_attribute(
+        &self,
+        attribute: Desc,
+        column_number: u16,
+        buffer: &mut Vec<SqlChar>,
+    ) -> SqlResult<()> {
         // String length in bytes, not characters. Terminating zero is excluded.
         let mut string_length_in_bytes: i16 = 0;
         // Let's utilize all of `buf`s capacity.
         buffer.resize(buffer.capacity(), 0);
-        unsafe {
-            let mut res = sql_col_attribute(
+        let mut res = sql_col_attribute(
+            self.as_sys(),
+            column_number,
+            attribute,
+            mut_buf_ptr(buffer) as Pointer,
+            binary_length(buffer).try_into().unwrap(),
+            &mut string_length_in_bytes as *mut i16,
+            null_mut(),
+        )
+        .into_sql_result("SQLColAttribute");
+
+        if res.is_err() {
+            return res;
+        }
+
+        if is_truncated_bin(buffer, string_length_in_bytes.try_into().unwrap()) {
+            // If we could rely on every ODBC driver sticking to the specifcation it would
+            // probably best to resize by `string_length_in_bytes / 2 + 1`. Yet e.g. SQLite
+            // seems to report the length in characters, so to work with a wide range of DB
+            // systems, and since buffers for names are not expected to become super large we
+            // ommit the division by two here.
+            buffer.resize((string_length_in_bytes + 1).try_into().unwrap(), 0);
+
+            res = sql_col_attribute(
                 self.as_sys(),
                 column_number,
-                Desc::Name,
+                attribute,
                 mut_buf_ptr(buffer) as Pointer,
                 binary_length(buffer).try_into().unwrap(),
                 &mut string_length_in_bytes as *mut i16,
                 null_mut(),
             )
             .into_sql_result("SQLColAttribute");
-
-            if res.is_err() {
-                return res;
-            }
-
-            if is_truncated_bin(buffer, string_length_in_bytes.try_into().unwrap()) {
-                // If we could rely on every ODBC driver sticking to the specifcation it would
-                // probably best to resize by `string_length_in_bytes / 2 + 1`. Yet e.g. SQLite
-                // seems to report the length in characters, so to work with a wide range of DB
-                // systems, and since buffers for names are not expected to become super large we
-                // ommit the division by two here.
-                buffer.resize((string_length_in_bytes + 1).try_into().unwrap(), 0);
-
-                res = sql_col_attribute(
-                    self.as_sys(),
-                    column_number,
-                    Desc::Name,
-                    mut_buf_ptr(buffer) as Pointer,
-                    binary_length(buffer).try_into().unwrap(),
-                    &mut string_length_in_bytes as *mut i16,
-                    null_mut(),
-                )
-                .into_sql_result("SQLColAttribute");
-            }
-            // Resize buffer to exact string length without terminal zero
-            resize_to_fit_without_tz(buffer, string_length_in_bytes.try_into().unwrap());
-
-            res
         }
+        // Resize buffer to exact string length without terminal zero
+        resize_to_fit_without_tz(buffer, string_length_in_bytes.try_into().unwrap());
+
+        res
     }
 
     /// # Safety
@@ -738,6 +964,58 @@ pub trait Statement: AsHandle {
         }
     }
 
+    /// Like [`Self::exec_direct`], but for statements with streamed output parameters bound (see
+    /// [`Statement::bind_output_stream_parameter`]). Reports whether their values are available.
+    /// [`SqlResult::NeedData`] is returned if delayed input parameters need data first.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`Self::exec_direct`].
+    #[cfg(feature = "odbc_version_3_80")]
+    unsafe fn exec_direct_with_output_streams(&mut self, statement: &SqlText) -> SqlResult<bool> {
+        match sql_exec_direc(
+            self.as_sys(),
+            statement.ptr(),
+            statement.len_char().try_into().unwrap(),
+        ) {
+            SqlReturn::PARAM_DATA_AVAILABLE => SqlResult::Success(true),
+            other => other.into_sql_result("SQLExecDirect").on_success(|| false),
+        }
+    }
+
+    /// Like [`Self::param_data`], but for statements with streamed output parameters bound. Once
+    /// all delayed input parameters have been sent, it reports whether the values of streamed
+    /// output parameters are available.
+    #[cfg(feature = "odbc_version_3_80")]
+    fn param_data_with_output_streams(&mut self) -> SqlResult<ParamData> {
+        unsafe {
+            let mut param_id: Pointer = null_mut();
+            match SQLParamData(self.as_sys(), &mut param_id as *mut Pointer) {
+                SqlReturn::NEED_DATA => SqlResult::Success(ParamData::NeedData(param_id)),
+                SqlReturn::PARAM_DATA_AVAILABLE => SqlResult::Success(ParamData::OutputAvailable),
+                other => other
+                    .into_sql_result("SQLParamData")
+                    .on_success(|| ParamData::Done),
+            }
+        }
+    }
+
+    /// Use after [`Self::exec_direct_with_output_streams`] or
+    /// [`Self::param_data_with_output_streams`] reported values of streamed output parameters to
+    /// be available, to learn which one can be retrieved next. Returns the token passed to
+    /// [`Statement::bind_output_stream_parameter`], or `None` if all streamed output parameters
+    /// have been handled.
+    #[cfg(feature = "odbc_version_3_80")]
+    fn output_param_data(&mut self) -> SqlResult<Option<Pointer>> {
+        unsafe {
+            let mut param_id: Pointer = null_mut();
+            match SQLParamData(self.as_sys(), &mut param_id as *mut Pointer) {
+                SqlReturn::PARAM_DATA_AVAILABLE => SqlResult::Success(Some(param_id)),
+                other => other.into_sql_result("SQLParamData").on_success(|| None),
+            }
+        }
+    }
+
     /// Executes a columns query using this statement handle.
     fn columns(
         &mut self,
@@ -791,6 +1069,29 @@ pub trait Statement: AsHandle {
         }
     }
 
+    /// Returns the column names that make up the primary key for a table. The driver returns the
+    /// information as a result set. Contrary to [`Self::tables`] and [`Self::columns`] the names
+    /// are not interpreted as search patterns.
+    fn primary_keys(
+        &mut self,
+        catalog_name: &SqlText,
+        schema_name: &SqlText,
+        table_name: &SqlText,
+    ) -> SqlResult<()> {
+        unsafe {
+            sql_primary_keys(
+                self.as_sys(),
+                catalog_name.ptr(),
+                catalog_name.len_char().try_into().unwrap(),
+                schema_name.ptr(),
+                schema_name.len_char().try_into().unwrap(),
+                table_name.ptr(),
+                table_name.len_char().try_into().unwrap(),
+            )
+            .into_sql_result("SQLPrimaryKeys")
+        }
+    }
+
     /// To put a batch of binary data into the data source at statement execution time. May return
     /// [`SqlResult::NeedData`]
     ///
@@ -827,6 +1128,29 @@ pub trait Statement: AsHandle {
         }
     }
 
+    /// Determines whether more results are available on a statement containing `SELECT`,
+    /// `UPDATE`, `INSERT`, or `DELETE` statements and, if so, initializes processing for those
+    /// results. Any rows of the current result set are discarded. [`SqlResult::NoData`] is
+    /// returned to indicate that there are no more results.
+    ///
+    /// See: <https://learn.microsoft.com/en-us/sql/odbc/reference/syntax/sqlmoreresults-function>
+    ///
+    /// # Safety
+    ///
+    /// Processing the next result may cause the driver to access bound parameters of the next
+    /// parameter set. These must still be valid.
+    unsafe fn more_results(&mut self) -> SqlResult<()> {
+        SQLMoreResults(self.as_sys()).into_sql_result("SQLMoreResults")
+    }
+
+    /// Cancels the processing on the statement. In asynchronous mode, the canceled function must
+    /// be called again until it no longer returns [`SqlResult::StillExecuting`].
+    ///
+    /// See: <https://learn.microsoft.com/en-us/sql/odbc/reference/syntax/sqlcancel-function>
+    fn cancel(&self) -> SqlResult<()> {
+        unsafe { SQLCancel(self.as_sys()) }.into_sql_result("SQLCancel")
+    }
+
     /// In polling mode can be used instead of repeating the function call. In notification mode
     /// this completes the asynchronous operation. This method panics, in case asynchronous mode is
     /// not enabled. [`SqlResult::NoData`] if no asynchronous operation is in progress, or (specific
@@ -0,0 +1,326 @@
+use std::mem::size_of;
+
+use thiserror::Error as ThisError;
+
+use crate::{
+    buffers::TextColumn, handles::StatementRef, insert_statement, ColumnarBulkInserter, Connection,
+    Error, SqlDialect, TextRecord, Upsert, UpsertSyntax,
+};
+
+/// Controls how [`crate::Connection::insert_rows`] splits the rows into chunks, which are sent to
+/// the data source one at a time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InsertRowsOptions {
+    /// Upper bound for the number of rows sent to the data source at once. Default is `1000`.
+    pub max_rows_per_chunk: usize,
+    /// Upper bound for the memory in bytes allocated for the parameter buffers, including
+    /// indicators. Since every row reserves space for the longest value of each column inserted so
+    /// far, chunks get smaller than [`Self::max_rows_per_chunk`] if values are long. A single row
+    /// is always sent, even if it exceeds the budget on its own. Default is 16 MiB.
+    pub max_bytes_per_chunk: usize,
+    /// Call [`crate::Connection::commit`] after every `n` chunks, and once all rows are inserted.
+    /// This is only meaningful if autocommit has been disabled using
+    /// [`crate::Connection::set_autocommit`]. `None` leaves it to the application to commit the
+    /// transaction. Default is `None`.
+    pub commit_every_n_chunks: Option<usize>,
+}
+
+impl Default for InsertRowsOptions {
+    fn default() -> Self {
+        Self {
+            max_rows_per_chunk: 1000,
+            max_bytes_per_chunk: 16 * 1024 * 1024,
+            commit_every_n_chunks: None,
+        }
+    }
+}
+
+/// Emitted by [`crate::Connection::insert_rows`]. Tells how many rows have been inserted before
+/// the error occurred, so the insertion can be resumed.
+#[derive(Debug, ThisError)]
+#[error(
+    "Inserting rows failed after {rows_executed} rows have been sent to the data source. \
+    {rows_committed} of them have been committed. Cause:\n{source}"
+)]
+pub struct InsertRowsError {
+    /// Number of leading rows which have been successfully sent to the data source. With
+    /// autocommit enabled these are persisted.
+    pub rows_executed: usize,
+    /// Number of leading rows covered by a commit issued due to
+    /// [`InsertRowsOptions::commit_every_n_chunks`]. Resume with the row at this index.
+    pub rows_committed: usize,
+    /// The error which caused the insertion to stop.
+    pub source: Error,
+}
+
+/// A chunk of rows which could not be upserted by [`crate::Connection::upsert_many`], or has been
+/// skipped by [`crate::ChunkedExecution`].
+#[derive(Debug)]
+pub struct ChunkError {
+    /// Index of the first row of the chunk, within all rows passed to `upsert_many` or
+    /// [`crate::ChunkedExecution::execute`].
+    pub first_row: usize,
+    /// Number of rows in the chunk.
+    pub num_rows: usize,
+    /// The error reported by the data source for this chunk.
+    pub source: Error,
+}
+
+/// Returned by [`crate::Connection::upsert_many`].
+#[derive(Debug, Default)]
+pub struct UpsertSummary {
+    /// Number of rows in chunks which have been executed successfully.
+    pub rows_upserted: usize,
+    /// Chunks which failed, in order. Rows within these chunks may or may not have been upserted,
+    /// depending on the driver.
+    pub failed_chunks: Vec<ChunkError>,
+}
+
+impl UpsertSummary {
+    /// `true` if every chunk has been executed successfully.
+    pub fn is_complete(&self) -> bool {
+        self.failed_chunks.is_empty()
+    }
+}
+
+/// Number of rows sent, or committed so far.
+#[derive(Default)]
+struct Progress {
+    /// Rows sent to the data source, including the ones in failed chunks.
+    rows_sent: usize,
+    rows_executed: usize,
+    rows_committed: usize,
+    chunks_since_commit: usize,
+    /// Only collected if chunks failing should not stop the insertion.
+    failed_chunks: Option<Vec<ChunkError>>,
+}
+
+pub(crate) fn insert_rows<R, V>(
+    conn: &Connection<'_>,
+    table_name: &str,
+    column_names: &[&str],
+    rows: impl IntoIterator<Item = R>,
+    dialect: &impl SqlDialect,
+    options: &InsertRowsOptions,
+) -> Result<usize, InsertRowsError>
+where
+    R: IntoIterator<Item = Option<V>>,
+    V: AsRef<[u8]>,
+{
+    let mut progress = Progress::default();
+    let sql = insert_statement(table_name, column_names, dialect);
+    match insert_chunks(conn, &sql, column_names.len(), rows, options, &mut progress) {
+        Ok(()) => Ok(progress.rows_executed),
+        Err(source) => Err(InsertRowsError {
+            rows_executed: progress.rows_executed,
+            rows_committed: progress.rows_committed,
+            source,
+        }),
+    }
+}
+
+pub(crate) fn upsert_many<T>(
+    conn: &Connection<'_>,
+    table_name: &str,
+    key_columns: &[&str],
+    records: impl IntoIterator<Item = T>,
+    dialect: &impl SqlDialect,
+    options: &InsertRowsOptions,
+) -> Result<UpsertSummary, InsertRowsError>
+where
+    T: TextRecord,
+{
+    let column_names = T::column_names();
+    // The upsert statement expects the keys first, followed by the values. This maps each of its
+    // placeholders to the index of the value in the record.
+    let key_indices: Vec<usize> = key_columns
+        .iter()
+        .map(|key| {
+            column_names
+                .iter()
+                .position(|name| name == key)
+                .unwrap_or_else(|| panic!("Key column '{key}' is not a field of the record."))
+        })
+        .collect();
+    let value_indices: Vec<usize> = (0..column_names.len())
+        .filter(|index| !key_indices.contains(index))
+        .collect();
+    let value_columns: Vec<&str> = value_indices
+        .iter()
+        .map(|&index| column_names[index])
+        .collect();
+    let upsert = Upsert::new(table_name)
+        .key_columns(key_columns)
+        .value_columns(&value_columns);
+    let order: Vec<usize> = key_indices.into_iter().chain(value_indices).collect();
+    let rows = records.into_iter().map(|record| {
+        let mut values = record.text_values();
+        order
+            .iter()
+            .map(|&index| values[index].take())
+            .collect::<Vec<_>>()
+    });
+
+    let mut progress = Progress {
+        failed_chunks: Some(Vec::new()),
+        ..Progress::default()
+    };
+    let result = conn
+        .database_management_system_name()
+        .map(|dbms_name| upsert.to_sql(UpsertSyntax::from_dbms_name(&dbms_name), dialect))
+        .and_then(|sql| insert_chunks(conn, &sql, order.len(), rows, options, &mut progress));
+    match result {
+        Ok(()) => Ok(UpsertSummary {
+            rows_upserted: progress.rows_executed,
+            failed_chunks: progress.failed_chunks.unwrap_or_default(),
+        }),
+        Err(source) => Err(InsertRowsError {
+            rows_executed: progress.rows_executed,
+            rows_committed: progress.rows_committed,
+            source,
+        }),
+    }
+}
+
+fn insert_chunks<R, V>(
+    conn: &Connection<'_>,
+    sql: &str,
+    num_cols: usize,
+    rows: impl IntoIterator<Item = R>,
+    options: &InsertRowsOptions,
+    progress: &mut Progress,
+) -> Result<(), Error>
+where
+    R: IntoIterator<Item = Option<V>>,
+    V: AsRef<[u8]>,
+{
+    let mut prepared = conn.prepare(sql)?;
+    let mut rows = rows.into_iter().map(|row| {
+        let row: Vec<_> = row.into_iter().collect();
+        assert_eq!(
+            num_cols,
+            row.len(),
+            "Each row passed to insert_rows must contain one value for each column."
+        );
+        row
+    });
+
+    let mut max_str_lens = vec![0; num_cols];
+    let mut capacity = rows_per_chunk(&max_str_lens, options);
+    // Row which did not fit into the buffers of the previous chunk.
+    let mut pending = None;
+    loop {
+        // A new inserter is bound each time the chunk size needs to shrink, in order to stay
+        // within the memory budget.
+        let mut inserter = prepared.text_inserter(capacity, max_str_lens.iter().copied())?;
+        loop {
+            let row = if let Some(row) = pending.take().or_else(|| rows.next()) {
+                row
+            } else {
+                execute_chunk(conn, &mut inserter, options, progress)?;
+                if options.commit_every_n_chunks.is_some() {
+                    conn.commit()?;
+                    progress.rows_committed = progress.rows_executed;
+                }
+                return Ok(());
+            };
+
+            for (max_str_len, cell) in max_str_lens.iter_mut().zip(&row) {
+                let len = cell.as_ref().map(|text| text.as_ref().len()).unwrap_or(0);
+                *max_str_len = (*max_str_len).max(len);
+            }
+            let required_capacity = rows_per_chunk(&max_str_lens, options);
+            if required_capacity < capacity {
+                execute_chunk(conn, &mut inserter, options, progress)?;
+                capacity = required_capacity;
+                pending = Some(row);
+                break;
+            }
+            if inserter.num_rows() == inserter.capacity() {
+                execute_chunk(conn, &mut inserter, options, progress)?;
+            }
+            inserter.append(row.iter().map(|cell| cell.as_ref().map(AsRef::as_ref)))?;
+        }
+    }
+}
+
+/// Number of rows fitting into the memory budget, if each text column holds values of up to
+/// `max_str_lens` bytes.
+fn rows_per_chunk(max_str_lens: &[usize], options: &InsertRowsOptions) -> usize {
+    let bytes_per_row: usize = max_str_lens
+        .iter()
+        // Terminating zero and indicator
+        .map(|len| len + 1 + size_of::<isize>())
+        .sum();
+    (options.max_bytes_per_chunk / bytes_per_row.max(1)).clamp(1, options.max_rows_per_chunk.max(1))
+}
+
+/// Sends the rows in the buffer to the data source and clears it. Commits if required. Failing
+/// chunks are recorded, rather than reported as error, if `progress` collects them.
+fn execute_chunk(
+    conn: &Connection<'_>,
+    inserter: &mut ColumnarBulkInserter<StatementRef<'_>, TextColumn<u8>>,
+    options: &InsertRowsOptions,
+    progress: &mut Progress,
+) -> Result<(), Error> {
+    let num_rows = inserter.num_rows();
+    if num_rows == 0 {
+        return Ok(());
+    }
+    let first_row = progress.rows_sent;
+    progress.rows_sent += num_rows;
+    // Any cursor is discarded, so the buffer can be cleared.
+    let result = inserter.execute().map(|_cursor| ());
+    inserter.clear();
+    if let Err(source) = result {
+        return match &mut progress.failed_chunks {
+            Some(failed_chunks) => {
+                failed_chunks.push(ChunkError {
+                    first_row,
+                    num_rows,
+                    source,
+                });
+                Ok(())
+            }
+            None => Err(source),
+        };
+    }
+    progress.rows_executed += num_rows;
+    progress.chunks_since_commit += 1;
+    if let Some(n) = options.commit_every_n_chunks {
+        if progress.chunks_since_commit >= n {
+            conn.commit()?;
+            progress.rows_committed = progress.rows_executed;
+            progress.chunks_since_commit = 0;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{rows_per_chunk, InsertRowsOptions};
+
+    #[test]
+    fn chunk_size_limited_by_row_count() {
+        let options = InsertRowsOptions {
+            max_rows_per_chunk: 100,
+            ..InsertRowsOptions::default()
+        };
+        assert_eq!(100, rows_per_chunk(&[10, 20], &options));
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")] // Indicator size is platform dependent.
+    fn chunk_size_limited_by_memory() {
+        let options = InsertRowsOptions {
+            max_rows_per_chunk: 100,
+            max_bytes_per_chunk: 1000,
+            commit_every_n_chunks: None,
+        };
+        // (91 + 1 + 8) bytes per row
+        assert_eq!(10, rows_per_chunk(&[91], &options));
+        // Always send at least one row
+        assert_eq!(1, rows_per_chunk(&[5000], &options));
+    }
+}
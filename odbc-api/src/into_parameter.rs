@@ -1,7 +1,11 @@
 use crate::{
+    buffers::{cell_to_text, CellValue},
     fixed_sized::Pod,
-    parameter::{InputParameter, VarBinaryBox, VarBinarySlice, VarCharBox, VarCharSlice},
-    Nullable,
+    parameter::{
+        Binary, InputParameter, VarBinaryBox, VarBinarySlice, VarCharBox, VarCharSlice,
+        VarWCharBox, VarWCharSlice, Wide, WithDataType,
+    },
+    Bit, DataType, Nullable, U16Str, U16String,
 };
 
 /// An instance can be consumed and to create a parameter which can be bound to a statement during
@@ -67,6 +71,71 @@ impl IntoParameter for Option<String> {
     }
 }
 
+impl<'a> IntoParameter for &'a U16Str {
+    type Parameter = VarWCharSlice<'a>;
+
+    fn into_parameter(self) -> Self::Parameter {
+        VarWCharSlice::new(self.as_slice())
+    }
+}
+
+impl<'a> IntoParameter for Option<&'a U16Str> {
+    type Parameter = VarWCharSlice<'a>;
+
+    fn into_parameter(self) -> Self::Parameter {
+        match self {
+            Some(text) => text.into_parameter(),
+            None => VarWCharSlice::NULL,
+        }
+    }
+}
+
+impl IntoParameter for U16String {
+    type Parameter = VarWCharBox;
+
+    fn into_parameter(self) -> Self::Parameter {
+        self.into()
+    }
+}
+
+impl IntoParameter for Option<U16String> {
+    type Parameter = VarWCharBox;
+
+    fn into_parameter(self) -> Self::Parameter {
+        match self {
+            Some(text) => text.into_parameter(),
+            None => VarWCharBox::null(),
+        }
+    }
+}
+
+impl IntoParameter for Wide<&str> {
+    type Parameter = VarWCharBox;
+
+    fn into_parameter(self) -> Self::Parameter {
+        VarWCharBox::from_str_slice(self.0)
+    }
+}
+
+impl IntoParameter for Wide<Option<&str>> {
+    type Parameter = VarWCharBox;
+
+    fn into_parameter(self) -> Self::Parameter {
+        match self.0 {
+            Some(text) => Wide(text).into_parameter(),
+            None => VarWCharBox::null(),
+        }
+    }
+}
+
+impl IntoParameter for Wide<String> {
+    type Parameter = VarWCharBox;
+
+    fn into_parameter(self) -> Self::Parameter {
+        VarWCharBox::from_str_slice(&self.0)
+    }
+}
+
 impl<'a> IntoParameter for &'a [u8] {
     type Parameter = VarBinarySlice<'a>;
 
@@ -105,6 +174,22 @@ impl IntoParameter for Option<Vec<u8>> {
     }
 }
 
+impl<const N: usize> IntoParameter for [u8; N] {
+    type Parameter = Binary<N>;
+
+    fn into_parameter(self) -> Self::Parameter {
+        Binary::new(self)
+    }
+}
+
+impl<const N: usize> IntoParameter for Option<[u8; N]> {
+    type Parameter = Binary<N>;
+
+    fn into_parameter(self) -> Self::Parameter {
+        self.into()
+    }
+}
+
 impl<T> IntoParameter for Option<T>
 where
     T: Pod + InputParameter,
@@ -118,3 +203,60 @@ where
         }
     }
 }
+
+impl IntoParameter for CellValue {
+    type Parameter = Box<dyn InputParameter>;
+
+    /// Values without a matching parameter type, like decimals or GUIDs, are bound as text.
+    fn into_parameter(self) -> Self::Parameter {
+        match self {
+            CellValue::Null => Box::new(VarCharBox::null()),
+            CellValue::Text(bytes) => Box::new(VarCharBox::from_vec(bytes)),
+            CellValue::WText(units) => Box::new(VarWCharBox::from_vec(units)),
+            CellValue::Binary(bytes) => Box::new(VarBinaryBox::from_vec(bytes)),
+            CellValue::I64(value) => Box::new(value),
+            CellValue::F64(value) => Box::new(value),
+            CellValue::Date(value) => Box::new(value),
+            CellValue::Time(value) => Box::new(WithDataType {
+                value,
+                data_type: DataType::Time { precision: 0 },
+            }),
+            CellValue::Timestamp(value) => {
+                // Declare only as many fractional digits as are required, since some drivers reject
+                // a precision higher than the one supported by the data source.
+                let mut precision = 9;
+                let mut fraction = value.fraction;
+                while precision > 0 && fraction % 10 == 0 {
+                    precision -= 1;
+                    fraction /= 10;
+                }
+                Box::new(WithDataType {
+                    value,
+                    data_type: DataType::Timestamp { precision },
+                })
+            }
+            CellValue::Bit(value) => Box::new(Bit::from_bool(value)),
+            other @ (CellValue::Decimal { .. } | CellValue::Guid(_)) => {
+                let text = cell_to_text(other, "text")
+                    .expect("Decimals and GUIDs must have a text representation.")
+                    .unwrap();
+                Box::new(VarCharBox::from_string(text))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{buffers::CellValue, handles::HasDataType, DataType};
+
+    use super::IntoParameter;
+
+    #[test]
+    fn wide_text_cell_is_bound_as_wide_text() {
+        // Unpaired surrogate followed by an 'a'. Must not be replaced by converting to UTF-8.
+        let parameter = CellValue::WText(vec![0xD800, 0x61]).into_parameter();
+
+        assert_eq!(DataType::WVarchar { length: 2 }, parameter.data_type());
+    }
+}
@@ -0,0 +1,320 @@
+//! Fetch the rows of a result set as [`serde_json`] values. Requires the `json` feature.
+//!
+//! ```no_run
+//! use odbc_api::{json::JsonRows, Connection, Error};
+//!
+//! fn birthdays_as_json(conn: &Connection<'_>) -> Result<String, Error> {
+//!     let cursor = conn.execute("SELECT name, birthday FROM Birthdays", ())?.unwrap();
+//!     let batch_size = 1000;
+//!     let max_str_len = Some(4096);
+//!     let rows = JsonRows::new(cursor, batch_size, max_str_len)?
+//!         .collect::<Result<Vec<_>, _>>()?;
+//!     Ok(serde_json::to_string(&rows).unwrap())
+//! }
+//! ```
+
+use serde_json::{Map, Number, Value};
+
+use crate::{
+    buffers::{
+        max_str_len_with_limit, AnySlice, BufferDesc, ColumnarAnyBuffer, NullableSlice, TextBinding,
+    },
+    calendar::{format_date, format_time, format_timestamp},
+    sys::{Date, Time, Timestamp},
+    BlockCursor, Cursor, DataType, Error,
+};
+
+/// Iterates over the rows of a cursor, yielding each of them as a JSON object mapping column names
+/// to values. Rows are fetched in batches, so choose a batch size which balances memory usage and
+/// the number of roundtrips to the data source. Use [`Self::next_batch`] to get a whole batch as a
+/// single JSON array instead.
+///
+/// Values are mapped to JSON as follows:
+///
+/// * `NULL` is mapped to `null`.
+/// * Integers and floating point numbers are mapped to numbers. `NaN` and infinity are mapped to
+///   `null`.
+/// * `DECIMAL` and `NUMERIC` are mapped to numbers. Should the value exceed the range of a 64 Bit
+///   integer, its precision is limited to that of a 64 Bit floating point.
+/// * `BIT` is mapped to `true` or `false`.
+/// * Dates, times and timestamps are mapped to strings in ISO 8601 format, e.g. `2023-01-31`,
+///   `13:45:00` and `2023-01-31T13:45:00.123`.
+/// * Binary data is mapped to a string of lowercase hexadecimal digits.
+/// * Everything else is fetched as text and mapped to a string.
+pub struct JsonRows<C: Cursor> {
+    block_cursor: BlockCursor<C, ColumnarAnyBuffer>,
+    /// One entry for each column
+    columns: Vec<JsonColumn>,
+    /// Rows of the current batch, which have not been yielded yet.
+    pending: std::vec::IntoIter<Map<String, Value>>,
+}
+
+/// How to convert the values of a single column
+struct JsonColumn {
+    name: String,
+    /// `true` for numeric columns fetched as text, whose values are parsed into numbers.
+    parse_number: bool,
+}
+
+impl<C> JsonRows<C>
+where
+    C: Cursor,
+{
+    /// Binds a buffer fitting the column types of `cursor`, holding up to `batch_size` rows.
+    ///
+    /// Text is fetched as UTF-16 and converted to UTF-8 regardless of the system encoding, unless
+    /// the driver has the [`crate::Quirks::no_wide_text`] quirk.
+    /// `max_str_len` limits the number of UTF-16 code units reserved for each text value. It is
+    /// also used for columns whose maximum length is not reported by the driver (e.g.
+    /// `VARCHAR(max)`). Without a limit values of such columns can not be fetched. Should any
+    /// value not fit into the buffer, fetching fails rather than yielding truncated text.
+    pub fn new(
+        mut cursor: C,
+        batch_size: usize,
+        max_str_len: Option<usize>,
+    ) -> Result<Self, Error> {
+        let quirks = cursor.quirks();
+        let num_cols: u16 = cursor.num_result_cols()?.try_into().unwrap();
+        let mut columns = Vec::new();
+        let mut descs = Vec::new();
+        for col_index in 1..(num_cols + 1) {
+            let data_type = cursor.col_data_type(col_index)?;
+            // We always bind nullable buffers, since drivers are not reliable in reporting
+            // nullability.
+            let desc = match quirks.buffer_desc(data_type, true) {
+                Some(BufferDesc::Text { .. }) | None => {
+                    match quirks.text_binding(TextBinding::Wide) {
+                        TextBinding::Wide => BufferDesc::WText {
+                            max_str_len: max_str_len_with_limit(
+                                data_type.utf16_len().unwrap_or(0),
+                                max_str_len,
+                            ),
+                        },
+                        _ => BufferDesc::Text {
+                            max_str_len: max_str_len_with_limit(
+                                data_type.utf8_len().unwrap_or(0),
+                                max_str_len,
+                            ),
+                        },
+                    }
+                }
+                Some(desc) => desc,
+            };
+            let parse_number = matches!(desc, BufferDesc::WText { .. } | BufferDesc::Text { .. })
+                && matches!(
+                    data_type,
+                    DataType::Numeric { .. } | DataType::Decimal { .. }
+                );
+            columns.push(JsonColumn {
+                name: cursor.col_name(col_index)?,
+                parse_number,
+            });
+            descs.push(desc);
+        }
+        let buffer = ColumnarAnyBuffer::try_from_descs(batch_size, descs)?;
+        let block_cursor = cursor.bind_buffer(buffer)?;
+        Ok(Self {
+            block_cursor,
+            columns,
+            pending: Vec::new().into_iter(),
+        })
+    }
+
+    /// Fetches the next batch and returns its rows as a JSON array of objects. `None` if the
+    /// result set is consumed. Rows fetched, but not yet yielded by the iterator are discarded.
+    pub fn next_batch(&mut self) -> Result<Option<Value>, Error> {
+        self.pending = Vec::new().into_iter();
+        let rows = self.fetch_rows()?;
+        Ok(rows.map(|rows| Value::Array(rows.into_iter().map(Value::Object).collect())))
+    }
+
+    fn fetch_rows(&mut self) -> Result<Option<Vec<Map<String, Value>>>, Error> {
+        let batch = if let Some(batch) = self.block_cursor.fetch_with_truncation_check(true)? {
+            batch
+        } else {
+            return Ok(None);
+        };
+        let mut rows = vec![Map::new(); batch.num_rows()];
+        for (buffer_index, column) in self.columns.iter().enumerate() {
+            let values = to_json_values(batch.column(buffer_index), column.parse_number);
+            for (row, value) in rows.iter_mut().zip(values) {
+                row.insert(column.name.clone(), value);
+            }
+        }
+        Ok(Some(rows))
+    }
+}
+
+impl<C> Iterator for JsonRows<C>
+where
+    C: Cursor,
+{
+    type Item = Result<Map<String, Value>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(row) = self.pending.next() {
+                return Some(Ok(row));
+            }
+            match self.fetch_rows() {
+                Ok(Some(rows)) => self.pending = rows.into_iter(),
+                Ok(None) => return None,
+                Err(error) => return Some(Err(error)),
+            }
+        }
+    }
+}
+
+/// One JSON value for each row of the fetched column, with `NULL` mapped to [`Value::Null`]. Binary
+/// data is hex encoded. If `parse_number` is set, text which parses as a number becomes a number.
+fn to_json_values(column: AnySlice<'_>, parse_number: bool) -> Vec<Value> {
+    match column {
+        AnySlice::Text(view) => (0..view.len())
+            .map(|index| {
+                view.get(index)
+                    .map(|text| {
+                        text_to_json(String::from_utf8_lossy(text).into_owned(), parse_number)
+                    })
+                    .unwrap_or(Value::Null)
+            })
+            .collect(),
+        AnySlice::WText(view) => (0..view.len())
+            .map(|index| {
+                let mut text = String::new();
+                if view.push_utf8(index, &mut text) {
+                    text_to_json(text, parse_number)
+                } else {
+                    Value::Null
+                }
+            })
+            .collect(),
+        AnySlice::Binary(view) => (0..view.len())
+            .map(|index| {
+                let mut text = String::new();
+                if view.push_hex(index, &mut text) {
+                    Value::String(text)
+                } else {
+                    Value::Null
+                }
+            })
+            .collect(),
+        AnySlice::Date(values) => values.iter().map(date).collect(),
+        AnySlice::Time(values) => values.iter().map(time).collect(),
+        AnySlice::Timestamp(values) => values.iter().map(timestamp).collect(),
+        AnySlice::F64(values) => values.iter().map(|&v| Value::from(v)).collect(),
+        AnySlice::F32(values) => values.iter().map(|&v| Value::from(v)).collect(),
+        AnySlice::I8(values) => values.iter().map(|&v| Value::from(v)).collect(),
+        AnySlice::I16(values) => values.iter().map(|&v| Value::from(v)).collect(),
+        AnySlice::I32(values) => values.iter().map(|&v| Value::from(v)).collect(),
+        AnySlice::I64(values) => values.iter().map(|&v| Value::from(v)).collect(),
+        AnySlice::U8(values) => values.iter().map(|&v| Value::from(v)).collect(),
+        AnySlice::Bit(values) => values.iter().map(|v| Value::Bool(v.as_bool())).collect(),
+        AnySlice::NullableDate(values) => nullable(values, date),
+        AnySlice::NullableTime(values) => nullable(values, time),
+        AnySlice::NullableTimestamp(values) => nullable(values, timestamp),
+        AnySlice::NullableF64(values) => nullable(values, |&v| Value::from(v)),
+        AnySlice::NullableF32(values) => nullable(values, |&v| Value::from(v)),
+        AnySlice::NullableI8(values) => nullable(values, |&v| Value::from(v)),
+        AnySlice::NullableI16(values) => nullable(values, |&v| Value::from(v)),
+        AnySlice::NullableI32(values) => nullable(values, |&v| Value::from(v)),
+        AnySlice::NullableI64(values) => nullable(values, |&v| Value::from(v)),
+        AnySlice::NullableU8(values) => nullable(values, |&v| Value::from(v)),
+        AnySlice::NullableBit(values) => nullable(values, |v| Value::Bool(v.as_bool())),
+    }
+}
+
+fn nullable<T>(values: NullableSlice<'_, T>, to_json: impl Fn(&T) -> Value) -> Vec<Value> {
+    values
+        .map(|value| value.map(&to_json).unwrap_or(Value::Null))
+        .collect()
+}
+
+fn text_to_json(text: String, parse_number: bool) -> Value {
+    if parse_number {
+        if let Some(number) = parse_decimal(&text) {
+            return Value::Number(number);
+        }
+    }
+    Value::String(text)
+}
+
+/// Parses the text representation of a decimal. Some drivers omit the leading zero, e.g. `.5`.
+fn parse_decimal(text: &str) -> Option<Number> {
+    let text = text.trim();
+    if let Ok(n) = text.parse::<i64>() {
+        Some(n.into())
+    } else if let Ok(n) = text.parse::<u64>() {
+        Some(n.into())
+    } else {
+        text.parse::<f64>().ok().and_then(Number::from_f64)
+    }
+}
+
+fn date(date: &Date) -> Value {
+    Value::String(format_date(date))
+}
+
+fn time(time: &Time) -> Value {
+    Value::String(format_time(time))
+}
+
+fn timestamp(ts: &Timestamp) -> Value {
+    Value::String(format_timestamp(ts, 'T'))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use crate::{
+        buffers::{AnySlice, BinColumn},
+        sys::{Date, Timestamp},
+    };
+
+    use super::{date, parse_decimal, timestamp, to_json_values};
+
+    #[test]
+    fn decimal_without_leading_zero() {
+        assert_eq!(Some(0.5), parse_decimal(".50").unwrap().as_f64());
+        assert_eq!(Some(-12), parse_decimal("-12").unwrap().as_i64());
+        assert_eq!(
+            Some(u64::MAX),
+            parse_decimal("18446744073709551615").unwrap().as_u64()
+        );
+        assert!(parse_decimal("not a number").is_none());
+    }
+
+    #[test]
+    fn temporal_values_in_iso_8601() {
+        let d = Date {
+            year: 2023,
+            month: 1,
+            day: 31,
+        };
+        assert_eq!(Value::from("2023-01-31"), date(&d));
+
+        let mut ts = Timestamp {
+            year: 2023,
+            month: 1,
+            day: 31,
+            hour: 13,
+            minute: 45,
+            second: 7,
+            fraction: 0,
+        };
+        assert_eq!(Value::from("2023-01-31T13:45:07"), timestamp(&ts));
+        ts.fraction = 123_000_000;
+        assert_eq!(Value::from("2023-01-31T13:45:07.123"), timestamp(&ts));
+    }
+
+    #[test]
+    fn binary_as_hex() {
+        let mut column = BinColumn::new(2, 3);
+        column.set_value(0, Some(&[0, 255, 26]));
+        column.set_value(1, None);
+        assert_eq!(
+            vec![Value::from("00ff1a"), Value::Null],
+            to_json_values(AnySlice::Binary(column.view(2)), false)
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use std::{
+    sync::{Arc, Condvar, Mutex},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use log::{debug, warn};
+
+use crate::{Connection, Error, SharedConnection};
+
+/// How [`KeepAlive`] checks on an idle connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeepAliveProbe {
+    /// Executes the statement and discards any result set. E.g. `SELECT 1`. This causes a round
+    /// trip to the data source and is therefore suitable to keep firewalls from dropping the
+    /// session.
+    Query(String),
+    /// Asks the driver wether the connection is dead (`SQL_ATTR_CONNECTION_DEAD`). Cheaper, but
+    /// many drivers answer from the state of the last operation, rather than sending anything to
+    /// the data source.
+    ConnectionDead,
+}
+
+impl KeepAliveProbe {
+    fn probe(&self, conn: &Connection<'_>) -> Result<(), Error> {
+        match self {
+            KeepAliveProbe::Query(query) => {
+                // The cursor is dropped and closed immediately.
+                conn.execute(query, ())?;
+            }
+            KeepAliveProbe::ConnectionDead => {
+                if conn.is_dead()? {
+                    warn!("Keepalive found connection to be dead.");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Background thread probing a [`SharedConnection`] whenever it has been idle for a while. Keeps
+/// firewalls from silently dropping sessions, which would otherwise fail on first use after an
+/// idle period, and reveals dead connections early in the logs. Dropping it stops the thread.
+///
+/// # Example
+///
+/// ```no_run
+/// use odbc_api::{Environment, KeepAlive, KeepAliveProbe, SharedConnection};
+/// use std::{sync::Arc, time::Duration};
+///
+/// let env = Environment::singleton()?;
+/// let conn = env.connect("YourDatabase", "SA", "My@Test@Password1")?;
+/// let shared = Arc::new(unsafe { SharedConnection::new(conn) });
+/// let _keep_alive = KeepAlive::new(
+///     shared.clone(),
+///     Duration::from_secs(60),
+///     KeepAliveProbe::Query("SELECT 1".to_owned()),
+/// );
+/// // Use `shared` ...
+/// # Ok::<(), odbc_api::Error>(())
+/// ```
+pub struct KeepAlive {
+    /// Set to `true` to signal the thread to stop.
+    stop: Arc<(Mutex<bool>, Condvar)>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl KeepAlive {
+    /// Starts a thread, which executes `probe` each time the connection has not been used for
+    /// `interval`. Probes are skipped while the connection is checked out by someone else.
+    pub fn new(
+        connection: Arc<SharedConnection<'static>>,
+        interval: Duration,
+        probe: KeepAliveProbe,
+    ) -> Self {
+        let stop = Arc::new((Mutex::new(false), Condvar::new()));
+        let thread_stop = stop.clone();
+        let thread = thread::spawn(move || {
+            let (stopped, signal) = &*thread_stop;
+            loop {
+                // Only hold the lock while waiting, so dropping `KeepAlive` does not have to wait
+                // for a probe to finish executing.
+                let stop = {
+                    let stopped = stopped.lock().unwrap();
+                    let (stopped, _timeout) = signal
+                        .wait_timeout_while(stopped, interval, |stopped| !*stopped)
+                        .unwrap();
+                    *stopped
+                };
+                if stop {
+                    break;
+                }
+                // Connections in use do not need to be kept alive, and we do not want to wait for
+                // them.
+                if let Some(conn) = connection.try_lock_idle(interval) {
+                    match probe.probe(&conn) {
+                        Ok(()) => debug!("Keepalive probe succeeded."),
+                        Err(error) => warn!("Keepalive probe failed: {error}"),
+                    }
+                }
+            }
+        });
+        Self {
+            stop,
+            thread: Some(thread),
+        }
+    }
+}
+
+impl Drop for KeepAlive {
+    fn drop(&mut self) {
+        let (stopped, signal) = &*self.stop;
+        *stopped.lock().unwrap() = true;
+        signal.notify_one();
+        if let Some(thread) = self.thread.take() {
+            // A panic in the keepalive thread has already been reported, no need to panic again.
+            let _ = thread.join();
+        }
+    }
+}
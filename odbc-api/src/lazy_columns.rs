@@ -0,0 +1,164 @@
+use crate::{
+    buffers::{max_str_len_with_limit, BufferDesc, CellValue, ColumnarAnyBuffer},
+    BlockCursor, Cursor, CursorRow, DataType, Error,
+};
+
+/// Fetches a result set row by row, binding all columns except the ones marked as lazy. Lazy
+/// columns are described, but not transferred, unless their value is accessed using
+/// [`LazyRow::get`]. This saves bandwidth for wide tables with large columns, which are rarely
+/// read by the consumer.
+///
+/// Values of lazy columns are fetched individually using `SQLGetData`. Binary columns are fetched
+/// as [`CellValue::Binary`], all other columns as [`CellValue::Text`]. Most drivers require
+/// columns fetched this way to follow the last bound column, and to be accessed in ascending
+/// order. Marking the last columns of the result set as lazy and accessing them in order works
+/// with every driver. See [`crate::Connection::get_data_extensions`] for what else your driver
+/// supports.
+///
+/// ```no_run
+/// use odbc_api::{buffers::CellValue, Cursor, Error, LazyColumnCursor};
+///
+/// /// Prints the names of all documents, but the body only of the ones marked as important.
+/// fn print_important(cursor: impl Cursor) -> Result<(), Error> {
+///     // Columns: 1 name, 2 is_important, 3 body
+///     let mut cursor = LazyColumnCursor::new(cursor, &[3], Some(255))?;
+///     while let Some(mut row) = cursor.next_row()? {
+///         println!("{:?}", row.get(1)?);
+///         if row.get(2)? == CellValue::Bit(true) {
+///             println!("{:?}", row.get(3)?);
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct LazyColumnCursor<C: Cursor> {
+    block_cursor: BlockCursor<C, ColumnarAnyBuffer>,
+    /// One based numbers of the bound columns, in the order of their buffers.
+    bound_columns: Vec<u16>,
+    /// One based numbers of the lazy columns, ascending, together with their types.
+    lazy_columns: Vec<(u16, DataType)>,
+}
+
+impl<C> LazyColumnCursor<C>
+where
+    C: Cursor,
+{
+    /// Binds every column of `cursor`, which is not contained in `lazy_columns`. Column numbers
+    /// are one based. Text of bound columns is limited to `max_str_len` bytes, should the driver
+    /// report a larger or no maximum length.
+    pub fn new(
+        mut cursor: C,
+        lazy_columns: &[u16],
+        max_str_len: Option<usize>,
+    ) -> Result<Self, Error> {
+        let quirks = cursor.quirks();
+        let num_cols: u16 = cursor.num_result_cols()?.try_into().unwrap();
+        let mut bound = Vec::new();
+        let mut lazy = Vec::new();
+        for col_index in 1..(num_cols + 1) {
+            let data_type = cursor.col_data_type(col_index)?;
+            if lazy_columns.contains(&col_index) {
+                lazy.push((col_index, data_type));
+                continue;
+            }
+            let desc = match quirks.buffer_desc(data_type, true) {
+                Some(BufferDesc::Text { .. }) | None => BufferDesc::Text {
+                    max_str_len: max_str_len_with_limit(
+                        data_type.utf8_len().unwrap_or(0),
+                        max_str_len,
+                    ),
+                },
+                Some(desc) => desc,
+            };
+            bound.push((col_index, desc));
+        }
+        let bound_columns = bound.iter().map(|&(col_index, _)| col_index).collect();
+        // We need to position the cursor on an individual row to fetch unbound columns.
+        let buffer = ColumnarAnyBuffer::try_from_descs_and_indices(1, bound.into_iter())?;
+        let block_cursor = cursor.bind_buffer(buffer)?;
+        Ok(Self {
+            block_cursor,
+            bound_columns,
+            lazy_columns: lazy,
+        })
+    }
+
+    /// One based numbers of the columns which are fetched lazily, ascending.
+    pub fn lazy_columns(&self) -> impl Iterator<Item = u16> + '_ {
+        self.lazy_columns.iter().map(|&(col_index, _)| col_index)
+    }
+
+    /// Advances the cursor to the next row. `None` if the result set is consumed.
+    pub fn next_row(&mut self) -> Result<Option<LazyRow<'_>>, Error> {
+        let Some((batch, row)) = self.block_cursor.fetch_with_unbound()? else {
+            return Ok(None);
+        };
+        Ok(Some(LazyRow {
+            batch,
+            row,
+            bound_columns: &self.bound_columns,
+            lazy_columns: &self.lazy_columns,
+            fetched: vec![None; self.lazy_columns.len()],
+        }))
+    }
+}
+
+/// Current row of a [`LazyColumnCursor`].
+pub struct LazyRow<'a> {
+    batch: &'a ColumnarAnyBuffer,
+    row: CursorRow<'a>,
+    bound_columns: &'a [u16],
+    lazy_columns: &'a [(u16, DataType)],
+    /// Values of lazy columns, once they have been fetched. Same order as `lazy_columns`. ODBC
+    /// allows fetching each value only once, so we keep them around for repeated access.
+    fetched: Vec<Option<CellValue>>,
+}
+
+impl LazyRow<'_> {
+    /// Value of the column with the one based number `column`. Values of lazy columns are fetched
+    /// from the data source the first time they are accessed.
+    ///
+    /// # Panics
+    ///
+    /// If `column` is not part of the result set.
+    pub fn get(&mut self, column: u16) -> Result<CellValue, Error> {
+        if let Some(buffer_index) = self.bound_columns.iter().position(|&c| c == column) {
+            return Ok(self.batch.column(buffer_index).cell(0));
+        }
+        let lazy_index = self
+            .lazy_columns
+            .iter()
+            .position(|&(c, _)| c == column)
+            .unwrap_or_else(|| panic!("Column {column} is not part of the result set."));
+        if let Some(value) = &self.fetched[lazy_index] {
+            return Ok(value.clone());
+        }
+        let (_, data_type) = self.lazy_columns[lazy_index];
+        let mut buf = Vec::new();
+        let value = if is_binary(data_type) {
+            let is_some = self.row.get_binary(column, &mut buf)?;
+            is_some.then_some(CellValue::Binary(buf))
+        } else {
+            let is_some = self.row.get_text(column, &mut buf)?;
+            is_some.then_some(CellValue::Text(buf))
+        }
+        .unwrap_or(CellValue::Null);
+        self.fetched[lazy_index] = Some(value.clone());
+        Ok(value)
+    }
+
+    /// `true` if `column` is lazy and its value has already been fetched.
+    pub fn is_fetched(&self, column: u16) -> bool {
+        self.lazy_columns
+            .iter()
+            .position(|&(c, _)| c == column)
+            .is_some_and(|lazy_index| self.fetched[lazy_index].is_some())
+    }
+}
+
+fn is_binary(data_type: DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Binary { .. } | DataType::Varbinary { .. } | DataType::LongVarbinary { .. }
+    )
+}
@@ -4,49 +4,159 @@
 //! standard to access databases. See the [`guide`] for more information and code
 //! examples.
 
+mod access_token;
+mod batch;
+mod calendar;
+mod chunked_execution;
+mod column_name_matcher;
 mod columnar_bulk_inserter;
 mod connection;
+mod copy;
 mod cursor;
+mod ddl;
+mod dialect;
+mod dml;
 mod driver_complete_option;
+mod driver_features;
+mod dsn_less;
 mod environment;
 mod error;
 mod execute;
+mod execution_profile;
+mod extensions;
+mod failover;
+mod fetch_limits;
+mod fetch_progress;
+mod fetch_statistics;
 mod fixed_sized;
+mod insert_rows;
 mod into_parameter;
+mod keep_alive;
+mod lazy_columns;
+mod matrix;
+mod nul_policy;
 mod nullable;
 mod parameter_collection;
 mod preallocated;
 mod prepared;
+mod preview;
+mod procedure;
+mod query_log;
+mod quirks;
+#[cfg(feature = "result_cache")]
+mod result_cache;
 mod result_set_metadata;
+mod resume;
+mod row_stream;
+mod sampling;
+mod shared_connection;
 mod sleep;
+mod sql_check;
 mod statement_connection;
+mod statement_pool;
+#[cfg(feature = "odbc_version_3_80")]
+mod streamed_output;
+mod text_record;
+mod upsert;
 
+#[cfg(feature = "avro")]
+pub mod avro;
 pub mod buffers;
+#[cfg(feature = "capi")]
+pub mod capi;
 pub mod guide;
 pub mod handles;
+#[cfg(feature = "json")]
+pub mod json;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod parameter;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+pub mod schema;
+#[cfg(feature = "testing")]
+pub mod testing;
 
+#[cfg(feature = "result_cache")]
+pub use self::result_cache::{CachedResult, ResultCache};
 pub use self::{
+    access_token::AccessToken,
+    batch::{
+        split_sql_script, split_sql_statements, BatchMode, ScriptDelimiter, ScriptError,
+        ScriptStatement, StatementOutcome,
+    },
+    chunked_execution::{ChunkReport, ChunkedExecution, ChunkedExecutionError, ChunkedSummary},
+    column_name_matcher::ColumnNameMatcher,
     columnar_bulk_inserter::{BoundInputSlice, ColumnarBulkInserter},
     connection::{escape_attribute_value, Connection},
+    copy::{copy, CopyError, CopyOptions, CopySummary, Truncation},
     cursor::{
-        BlockCursor, BlockCursorPolling, Cursor, CursorImpl, CursorPolling, CursorRow, RowSetBuffer,
+        BlockCursor, BlockCursorPolling, Cursor, CursorImpl, CursorPolling, CursorRow,
+        RowSetBuffer, UnbindError,
     },
+    ddl::{create_table_statement, AnsiDialect, SqlDialect},
+    dialect::DbmsDialect,
+    dml::insert_statement,
     driver_complete_option::DriverCompleteOption,
+    driver_features::{GetDataExtensions, Odbc38Features},
+    dsn_less::Authentication,
     environment::{DataSourceInfo, DriverInfo, Environment},
     error::{Error, TooLargeBufferSize},
+    execution_profile::ExecutionProfile,
+    extensions::Extensions,
+    failover::{BackgroundConnection, Failover},
+    fetch_limits::{FetchLimits, LimitExceeded},
+    fetch_progress::{FetchProgress, ProgressCallback},
+    fetch_statistics::FetchStatistics,
     fixed_sized::Bit,
-    handles::{ColumnDescription, DataType, Nullability},
+    handles::{
+        ColumnDescription, CursorSensitivity, CursorType, DataType, Nullability, OtherDataType,
+    },
+    insert_rows::{ChunkError, InsertRowsError, InsertRowsOptions, UpsertSummary},
     into_parameter::IntoParameter,
+    keep_alive::{KeepAlive, KeepAliveProbe},
+    lazy_columns::{LazyColumnCursor, LazyRow},
+    matrix::{Matrix, MatrixError, NullPolicy},
+    nul_policy::{InteriorNulError, NulPolicy},
     nullable::Nullable,
     parameter::{InOut, Out, OutputParameter},
-    parameter_collection::{ParameterCollection, ParameterCollectionRef, ParameterTupleElement},
+    parameter_collection::{
+        ArrayElement, ArrayRow, InputParameterCollection, IntoParameterArray, NullableArray,
+        ParameterArray, ParameterArrays, ParameterCollection, ParameterCollectionRef,
+        ParameterTupleElement, TextArray,
+    },
     preallocated::{Preallocated, PreallocatedPolling},
     prepared::Prepared,
+    preview::Preview,
+    procedure::{ProcedureCall, ProcedureResultSet},
+    query_log::{QueryLogEntry, QueryLogger, Redacted},
+    quirks::Quirks,
     result_set_metadata::ResultSetMetadata,
+    resume::{InvalidResumeToken, ResumableExtraction, ResumeToken},
+    row_stream::RowStream,
+    sampling::Sampling,
+    shared_connection::{LockSharedConnection, SharedConnection, SharedConnectionGuard},
     sleep::Sleep,
+    sql_check::SqlCheck,
     statement_connection::StatementConnection,
+    statement_pool::PooledStatement,
+    text_record::{TextRecord, ToSqlText},
+    upsert::{Upsert, UpsertSyntax},
 };
+#[cfg(feature = "dynamic_loading")]
+pub use handles::load_driver_manager;
+/// Derives [`InputParameterCollection`] for structs, binding one parameter per field. Requires the
+/// `derive` feature.
+#[cfg(feature = "derive")]
+pub use odbc_api_derive::InputParameterCollection;
+/// Derives [`TextRecord`] for structs with named fields. Requires the `derive` feature.
+#[cfg(feature = "derive")]
+pub use odbc_api_derive::TextRecord;
+#[cfg(feature = "odbc_version_3_80")]
+pub use streamed_output::{
+    StreamedOutput, StreamedOutputs, StreamingParameterCollection, StreamingTupleElement,
+};
+
 // Reexports
 pub use force_send_sync;
 /// Reexports `odbc-sys` as sys to enable applications to always use the same version as this
@@ -0,0 +1,234 @@
+use thiserror::Error as ThisError;
+
+use crate::{
+    buffers::{BufferDesc, ColumnarAnyBuffer},
+    handles::DataType,
+    Cursor, Error,
+};
+
+/// Decides how [`Matrix::fetch`] treats `NULL` values.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum NullPolicy {
+    /// Fail with [`MatrixError::UnexpectedNull`].
+    #[default]
+    Fail,
+    /// Represent `NULL` as `f64::NAN`.
+    Nan,
+    /// Represent `NULL` with the value given.
+    Fill(f64),
+    /// Omit rows containing at least one `NULL` from the matrix.
+    SkipRow,
+}
+
+/// Error returned by [`Matrix::fetch`].
+#[derive(Debug, ThisError)]
+pub enum MatrixError {
+    #[error("Fetching numeric result set into a matrix failed:\n{0}")]
+    Odbc(#[from] Error),
+    #[error(
+        "Column {column} of the result set has type {data_type:?}. Only numeric columns can be \
+        fetched into a matrix."
+    )]
+    NonNumericColumn {
+        /// One based index of the column.
+        column: u16,
+        data_type: DataType,
+    },
+    #[error("Value in row {row}, column {column} is NULL.")]
+    UnexpectedNull {
+        /// Zero based index of the row in the result set.
+        row: usize,
+        /// One based index of the column.
+        column: u16,
+    },
+}
+
+/// All rows of a numeric result set as a dense matrix of `f64`. Intended for pulling feature
+/// matrices out of a database for numeric or scientific computing.
+///
+/// Values are stored in row major order, so the matrix can be converted into an `ndarray` without
+/// copying:
+///
+/// ```ignore
+/// let array = ndarray::Array2::from_shape_vec(matrix.shape(), matrix.values)?;
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix {
+    /// Values of the result set in row major order. Element `(row, column)` is at index
+    /// `row * num_cols + column`.
+    pub values: Vec<f64>,
+    /// Number of rows in the matrix. Rows skipped due to [`NullPolicy::SkipRow`] are not counted.
+    pub num_rows: usize,
+    /// Number of columns in the result set.
+    pub num_cols: usize,
+}
+
+impl Matrix {
+    /// Fetches all rows of `cursor` in batches of `batch_size` rows. Every column must be
+    /// numeric, i.e. an integer, floating point, decimal or bit column. Values are converted to
+    /// `f64` by the driver, so decimals and large integers may lose precision.
+    ///
+    /// ```no_run
+    /// use odbc_api::{Connection, Matrix, MatrixError, NullPolicy};
+    ///
+    /// fn features(conn: &Connection<'_>) -> Result<Matrix, MatrixError> {
+    ///     let cursor = conn
+    ///         .execute("SELECT height, weight FROM Measurements", ())?
+    ///         .expect("SELECT statement must yield a result set.");
+    ///     Matrix::fetch(cursor, 10_000, NullPolicy::Nan)
+    /// }
+    /// ```
+    pub fn fetch(
+        mut cursor: impl Cursor,
+        batch_size: usize,
+        null_policy: NullPolicy,
+    ) -> Result<Self, MatrixError> {
+        let num_cols: u16 = cursor.num_result_cols()?.try_into().unwrap();
+        for column in 1..(num_cols + 1) {
+            let data_type = cursor.col_data_type(column)?;
+            if !is_numeric(data_type) {
+                return Err(MatrixError::NonNumericColumn { column, data_type });
+            }
+        }
+        let descs = (0..num_cols).map(|_| BufferDesc::F64 { nullable: true });
+        let buffer = ColumnarAnyBuffer::try_from_descs(batch_size, descs)?;
+        let mut block_cursor = cursor.bind_buffer(buffer)?;
+
+        let mut matrix = Matrix {
+            values: Vec::new(),
+            num_rows: 0,
+            num_cols: num_cols.into(),
+        };
+        // Index of the current row in the result set, including skipped ones.
+        let mut row_index = 0;
+        let mut row = Vec::with_capacity(matrix.num_cols);
+        while let Some(batch) = block_cursor.fetch()? {
+            matrix.values.reserve(batch.num_rows() * matrix.num_cols);
+            let mut columns: Vec<_> = (0..batch.num_cols())
+                .map(|buffer_index| {
+                    batch
+                        .column(buffer_index)
+                        .as_nullable_slice::<f64>()
+                        .unwrap()
+                })
+                .collect();
+            for _ in 0..batch.num_rows() {
+                row.clear();
+                row.extend(
+                    columns
+                        .iter_mut()
+                        .map(|column| column.next().unwrap().copied()),
+                );
+                matrix.push_row(&row, null_policy, row_index)?;
+                row_index += 1;
+            }
+        }
+        Ok(matrix)
+    }
+
+    /// Number of rows and columns.
+    pub fn shape(&self) -> (usize, usize) {
+        (self.num_rows, self.num_cols)
+    }
+
+    /// Element in the given zero based row and column.
+    ///
+    /// # Panics
+    ///
+    /// If the row or column is out of bounds.
+    pub fn get(&self, row: usize, column: usize) -> f64 {
+        assert!(column < self.num_cols, "Column index out of bounds.");
+        self.values[row * self.num_cols + column]
+    }
+
+    /// Elements of the given zero based row.
+    pub fn row(&self, row: usize) -> &[f64] {
+        &self.values[row * self.num_cols..(row + 1) * self.num_cols]
+    }
+
+    /// Appends a row, replacing or skipping nulls according to `null_policy`.
+    fn push_row(
+        &mut self,
+        row: &[Option<f64>],
+        null_policy: NullPolicy,
+        row_index: usize,
+    ) -> Result<(), MatrixError> {
+        let replacement = match null_policy {
+            NullPolicy::Fail => {
+                if let Some(position) = row.iter().position(Option::is_none) {
+                    return Err(MatrixError::UnexpectedNull {
+                        row: row_index,
+                        column: (position + 1).try_into().unwrap(),
+                    });
+                }
+                f64::NAN
+            }
+            NullPolicy::Nan => f64::NAN,
+            NullPolicy::Fill(value) => value,
+            NullPolicy::SkipRow => {
+                if row.iter().any(Option::is_none) {
+                    return Ok(());
+                }
+                f64::NAN
+            }
+        };
+        self.values
+            .extend(row.iter().map(|value| value.unwrap_or(replacement)));
+        self.num_rows += 1;
+        Ok(())
+    }
+}
+
+/// `true` for data types the driver can convert to `f64`.
+fn is_numeric(data_type: DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Numeric { .. }
+            | DataType::Decimal { .. }
+            | DataType::Integer
+            | DataType::SmallInt
+            | DataType::TinyInt
+            | DataType::BigInt
+            | DataType::Float { .. }
+            | DataType::Real
+            | DataType::Double
+            | DataType::Bit
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Matrix, MatrixError, NullPolicy};
+
+    fn fill(null_policy: NullPolicy) -> Result<Matrix, MatrixError> {
+        let mut matrix = Matrix {
+            values: Vec::new(),
+            num_rows: 0,
+            num_cols: 2,
+        };
+        matrix.push_row(&[Some(1.), Some(2.)], null_policy, 0)?;
+        matrix.push_row(&[Some(3.), None], null_policy, 1)?;
+        matrix.push_row(&[Some(5.), Some(6.)], null_policy, 2)?;
+        Ok(matrix)
+    }
+
+    #[test]
+    fn null_policies() {
+        let matrix = fill(NullPolicy::Fill(-1.)).unwrap();
+        assert_eq!((3, 2), matrix.shape());
+        assert_eq!(vec![1., 2., 3., -1., 5., 6.], matrix.values);
+        assert_eq!(&[3., -1.], matrix.row(1));
+
+        let matrix = fill(NullPolicy::Nan).unwrap();
+        assert!(matrix.get(1, 1).is_nan());
+
+        let matrix = fill(NullPolicy::SkipRow).unwrap();
+        assert_eq!((2, 2), matrix.shape());
+        assert_eq!(vec![1., 2., 5., 6.], matrix.values);
+
+        assert!(matches!(
+            fill(NullPolicy::Fail),
+            Err(MatrixError::UnexpectedNull { row: 1, column: 2 })
+        ));
+    }
+}
@@ -0,0 +1,131 @@
+//! Names of the counters and histograms emitted about executed statements and fetched rows.
+//! Requires the `metrics` feature.
+//!
+//! Metrics are emitted with the macros of the `metrics` facade crate, i.e. to whatever recorder the
+//! application installed, e.g. a Prometheus exporter. They are independent of the query logger,
+//! so no previews of parameters are rendered. Without a recorder installed, emitting is a no-op.
+
+use std::time::Duration;
+
+use ::metrics::{counter, histogram};
+
+use crate::Error;
+
+/// Counter incremented for each executed statement. Labeled with `status`, which is either `ok`
+/// or `error`.
+pub const QUERIES_TOTAL: &str = "odbc_queries_total";
+/// Histogram of the time it took to execute statements, in seconds. Does not include fetching
+/// result sets.
+pub const QUERY_DURATION_SECONDS: &str = "odbc_query_duration_seconds";
+/// Counter incremented for each statement which failed. Labeled with `sqlstate_class`, the first
+/// two characters of the SQLSTATE, e.g. `42` for syntax errors or access violations. `none` if
+/// the error has not been reported with a diagnostic record.
+pub const QUERY_ERRORS_TOTAL: &str = "odbc_query_errors_total";
+/// Counter of rows fetched from result sets.
+pub const ROWS_FETCHED_TOTAL: &str = "odbc_rows_fetched_total";
+/// Counter of row sets (batches) fetched from result sets.
+pub const BATCHES_FETCHED_TOTAL: &str = "odbc_batches_fetched_total";
+/// Counter of fetched values, which have been truncated to fit into their buffer.
+pub const TRUNCATED_VALUES_TOTAL: &str = "odbc_truncated_values_total";
+
+/// Emits the metrics about a statement, once it has been executed.
+pub(crate) fn record_execution(duration: Duration, error: Option<&Error>) {
+    let status = if error.is_some() { "error" } else { "ok" };
+    counter!(QUERIES_TOTAL, "status" => status).increment(1);
+    histogram!(QUERY_DURATION_SECONDS).record(duration.as_secs_f64());
+    if let Some(error) = error {
+        let class = error
+            .diagnostic_record()
+            .map(|record| String::from_utf8_lossy(&record.state.0[..2]).into_owned())
+            .unwrap_or_else(|| "none".to_owned());
+        counter!(QUERY_ERRORS_TOTAL, "sqlstate_class" => class).increment(1);
+    }
+}
+
+/// Emits the metrics about a row set, once it has been fetched.
+pub(crate) fn record_batch(num_rows: usize, num_truncated: usize) {
+    counter!(ROWS_FETCHED_TOTAL).increment(num_rows as u64);
+    counter!(BATCHES_FETCHED_TOTAL).increment(1);
+    counter!(TRUNCATED_VALUES_TOTAL).increment(num_truncated as u64);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+    use crate::{
+        handles::{Record, State},
+        Error,
+    };
+
+    use super::{record_batch, record_execution};
+
+    /// Names and labels of the emitted metrics, with the value of counters.
+    fn emitted(f: impl FnOnce()) -> Vec<String> {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        metrics::with_local_recorder(&recorder, f);
+        let mut emitted: Vec<_> = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .map(|(key, _unit, _description, value)| {
+                let (_kind, key) = key.into_parts();
+                let labels: Vec<_> = key
+                    .labels()
+                    .map(|label| format!("{}={}", label.key(), label.value()))
+                    .collect();
+                let value = match value {
+                    DebugValue::Counter(value) => value.to_string(),
+                    DebugValue::Histogram(values) => format!("{} samples", values.len()),
+                    DebugValue::Gauge(value) => value.to_string(),
+                };
+                format!("{}{labels:?} {value}", key.name())
+            })
+            .collect();
+        emitted.sort();
+        emitted
+    }
+
+    #[test]
+    fn failed_query_by_sql_state_class() {
+        let error = Error::Diagnostics {
+            record: Record {
+                state: State(*b"42S02"),
+                native_error: 208,
+                message: Vec::new(),
+            },
+            function: "SQLExecDirect",
+        };
+
+        let emitted = emitted(|| record_execution(Duration::from_millis(250), Some(&error)));
+
+        assert_eq!(
+            vec![
+                r#"odbc_queries_total["status=error"] 1"#,
+                r#"odbc_query_duration_seconds[] 1 samples"#,
+                r#"odbc_query_errors_total["sqlstate_class=42"] 1"#,
+            ],
+            emitted
+        );
+    }
+
+    #[test]
+    fn fetched_rows_and_truncated_values() {
+        let emitted = emitted(|| {
+            record_batch(100, 0);
+            record_batch(42, 2);
+        });
+
+        assert_eq!(
+            vec![
+                r#"odbc_batches_fetched_total[] 2"#,
+                r#"odbc_rows_fetched_total[] 142"#,
+                r#"odbc_truncated_values_total[] 2"#,
+            ],
+            emitted
+        );
+    }
+}
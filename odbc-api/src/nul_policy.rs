@@ -0,0 +1,190 @@
+use std::borrow::Cow;
+
+use thiserror::Error as ThisError;
+
+use crate::{
+    buffers::{Indicator, TextColumn},
+    parameter::VarCharBox,
+};
+
+/// Decides how `NUL` characters within text are handled. Text is bound and fetched together with
+/// its length, so `NUL` is transferred like any other character. Yet drivers and data sources
+/// disagree on what to make of it: some store it, some cut the value off at the first `NUL` and
+/// some reject it. Use this policy to decide explicitly, before values are bound as parameters or
+/// after they are fetched.
+///
+/// Note that a text parameter created with [`crate::IntoParameter`] or
+/// [`crate::parameter::VarCharSlice::new`], whose last character is `NUL`, is interpreted as
+/// truncated with the `NUL` being the terminating zero. [`Self::text_parameter`] does not have
+/// this caveat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NulPolicy {
+    /// Keep `NUL` characters as part of the value.
+    #[default]
+    PassThrough,
+    /// Remove every `NUL` character from the value.
+    Strip,
+    /// Fail with [`InteriorNulError`].
+    Error,
+}
+
+/// Text contains a `NUL` character and the [`NulPolicy`] is [`NulPolicy::Error`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ThisError)]
+#[error("Text contains a NUL character at position {position}.")]
+pub struct InteriorNulError {
+    /// Zero based index of the row, if the value is part of a column buffer.
+    pub row: Option<usize>,
+    /// Offset of the first `NUL` in characters, i.e. in bytes for narrow and in UTF-16 code units
+    /// for wide text.
+    pub position: usize,
+}
+
+impl NulPolicy {
+    /// Applies the policy to a single narrow or wide text value. Only allocates, if `NUL`
+    /// characters are stripped.
+    ///
+    /// ```
+    /// use odbc_api::NulPolicy;
+    ///
+    /// assert_eq!(b"ab", &NulPolicy::Strip.apply(b"a\0b").unwrap()[..]);
+    /// assert_eq!(1, NulPolicy::Error.apply(b"a\0b").unwrap_err().position);
+    /// ```
+    pub fn apply<C>(self, text: &[C]) -> Result<Cow<'_, [C]>, InteriorNulError>
+    where
+        C: Copy + Default + PartialEq,
+    {
+        let Some(position) = text.iter().position(|c| *c == C::default()) else {
+            return Ok(Cow::Borrowed(text));
+        };
+        match self {
+            NulPolicy::PassThrough => Ok(Cow::Borrowed(text)),
+            NulPolicy::Strip => Ok(Cow::Owned(
+                text.iter()
+                    .copied()
+                    .filter(|c| *c != C::default())
+                    .collect(),
+            )),
+            NulPolicy::Error => Err(InteriorNulError {
+                row: None,
+                position,
+            }),
+        }
+    }
+
+    /// Creates a text parameter according to the policy. Other than [`VarCharBox::from_vec`],
+    /// a `NUL` at the end of `text` is passed through as part of the value, rather than being
+    /// interpreted as a terminating zero.
+    pub fn text_parameter(self, text: &[u8]) -> Result<VarCharBox, InteriorNulError> {
+        let mut buffer = self.apply(text)?.into_owned();
+        let len = buffer.len();
+        // Reserve room for a terminating zero, so the indicator is smaller than the buffer and the
+        // value is not mistaken for being truncated.
+        buffer.push(0);
+        Ok(VarCharBox::from_buffer(
+            buffer.into_boxed_slice(),
+            Indicator::Length(len),
+        ))
+    }
+
+    /// Applies the policy in place to the first `num_rows` values of a fetched text column. With
+    /// [`Self::Error`] the first value containing `NUL` is reported, values are not modified.
+    ///
+    /// # Panics
+    ///
+    /// If `num_rows` is larger than the capacity of the buffer.
+    pub fn apply_to_column<C>(
+        self,
+        column: &mut TextColumn<C>,
+        num_rows: usize,
+    ) -> Result<(), InteriorNulError>
+    where
+        C: Copy + Default + PartialEq,
+    {
+        match self {
+            NulPolicy::PassThrough => Ok(()),
+            NulPolicy::Strip => {
+                for mut cell in column.iter_mut(num_rows) {
+                    let Some(value) = cell.get_mut() else {
+                        continue;
+                    };
+                    let mut len = 0;
+                    for index in 0..value.len() {
+                        if value[index] != C::default() {
+                            value[len] = value[index];
+                            len += 1;
+                        }
+                    }
+                    cell.truncate(len);
+                }
+                Ok(())
+            }
+            NulPolicy::Error => {
+                for (row, cell) in column.iter_mut(num_rows).enumerate() {
+                    let position = cell
+                        .get()
+                        .and_then(|value| value.iter().position(|c| *c == C::default()));
+                    if let Some(position) = position {
+                        return Err(InteriorNulError {
+                            row: Some(row),
+                            position,
+                        });
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::buffers::{CharColumn, WCharColumn};
+
+    use super::{InteriorNulError, NulPolicy};
+
+    #[test]
+    fn trailing_nul_is_part_of_text_parameter() {
+        let parameter = NulPolicy::PassThrough.text_parameter(b"ab\0").unwrap();
+
+        assert!(parameter.is_complete());
+        assert_eq!(Some(&b"ab\0"[..]), parameter.as_bytes());
+    }
+
+    #[test]
+    fn strip_nul_from_text_parameter() {
+        let parameter = NulPolicy::Strip.text_parameter(b"\0a\0b").unwrap();
+
+        assert_eq!(Some(&b"ab"[..]), parameter.as_bytes());
+    }
+
+    #[test]
+    fn strip_nul_in_column() {
+        let mut column = CharColumn::new(3, 5);
+        column.set_value(0, Some(b"a\0b\0c"));
+        column.set_value(1, None);
+        column.set_value(2, Some(b"abc"));
+
+        NulPolicy::Strip.apply_to_column(&mut column, 3).unwrap();
+
+        assert_eq!(Some(&b"abc"[..]), column.value_at(0));
+        assert_eq!(None, column.value_at(1));
+        assert_eq!(Some(&b"abc"[..]), column.value_at(2));
+    }
+
+    #[test]
+    fn report_nul_in_wide_column() {
+        let mut column = WCharColumn::new(2, 5);
+        column.set_value(0, Some(&[0x61]));
+        column.set_value(1, Some(&[0x61, 0x62, 0]));
+
+        let result = NulPolicy::Error.apply_to_column(&mut column, 2);
+
+        assert_eq!(
+            Err(InteriorNulError {
+                row: Some(1),
+                position: 2
+            }),
+            result
+        );
+    }
+}
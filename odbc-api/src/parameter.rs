@@ -11,7 +11,11 @@
 //! * `&mut BlobParam` -> Stream long input parameters.
 //! * `Box<dyn InputParameter>` -> Aribtrary input parameter
 //! * `&[Box<dyn InputParameter>]` -> Aribtrary number of arbitrary input parameters
+//! * `&[&dyn InputParameter]` -> Arbitrary number of borrowed arbitrary input parameters
+//! * `&Vec<a>` -> Arbitrary number of parameters, e.g. `&Vec<Box<dyn InputParameter>>`
+//! * `&s` -> Struct deriving `InputParameterCollection`, binding one parameter per field.
 //! * `a.into_parameter()` -> Convert idiomatic Rust type into something bindable by ODBC.
+//! * `Wide(a).into_parameter()` -> Bind text as UTF-16.
 //!
 //! ## Passing a single parameter
 //!
@@ -132,7 +136,8 @@
 //! ```
 //!
 //! Should you have more type information the type available, but only at runtime can also bind an
-//! array of `[Box<dyn InputParameter]`.
+//! array of `[Box<dyn InputParameter]`, or of `[&dyn InputParameter]` if you do not want to give
+//! up ownership of the parameters.
 //!
 //! ## Output and Input/Output parameters
 //!
@@ -313,15 +318,26 @@
 //! work? Well, in that case please open an issue or a pull request. [`crate::IntoParameter`] can usually be
 //! implemented entirely in safe code, and is a suitable spot to enable support for your custom
 //! types.
+mod binary;
 mod blob;
 mod c_string;
+#[cfg(feature = "odbc_version_3_80")]
+mod output_stream;
+mod table_valued;
 mod varbin;
 mod varchar;
+mod varwchar;
+
+#[cfg(feature = "odbc_version_3_80")]
+pub use self::output_stream::OutputStream;
 
 pub use self::{
+    binary::Binary,
     blob::{Blob, BlobParam, BlobRead, BlobSlice},
+    table_valued::TableValuedParameter,
     varbin::{VarBinary, VarBinaryArray, VarBinaryBox, VarBinarySlice, VarBinarySliceMut},
     varchar::{VarChar, VarCharArray, VarCharBox, VarCharSlice, VarCharSliceMut},
+    varwchar::{VarWChar, VarWCharBox, VarWCharSlice, Wide},
 };
 
 use std::ffi::c_void;
@@ -495,28 +511,38 @@ impl<T> HasDataType for WithDataType<T> {
 unsafe impl<T> CElement for WithDataType<T> where T: CElement {}
 unsafe impl<T> OutputParameter for WithDataType<T> where T: Pod {}
 
-// Allow for input parameters whose type is only known at runtime.
-unsafe impl CData for Box<dyn InputParameter> {
-    fn cdata_type(&self) -> CDataType {
-        self.as_ref().cdata_type()
-    }
+macro_rules! impl_input_parameter_for_trait_object {
+    (<$($lt:lifetime),*> $t:ty) => {
+        unsafe impl<$($lt),*> CData for $t {
+            fn cdata_type(&self) -> CDataType {
+                (**self).cdata_type()
+            }
 
-    fn indicator_ptr(&self) -> *const isize {
-        self.as_ref().indicator_ptr()
-    }
+            fn indicator_ptr(&self) -> *const isize {
+                (**self).indicator_ptr()
+            }
 
-    fn value_ptr(&self) -> *const c_void {
-        self.as_ref().value_ptr()
-    }
+            fn value_ptr(&self) -> *const c_void {
+                (**self).value_ptr()
+            }
 
-    fn buffer_length(&self) -> isize {
-        self.as_ref().buffer_length()
-    }
-}
+            fn buffer_length(&self) -> isize {
+                (**self).buffer_length()
+            }
+        }
 
-impl HasDataType for Box<dyn InputParameter> {
-    fn data_type(&self) -> DataType {
-        self.as_ref().data_type()
-    }
+        impl<$($lt),*> HasDataType for $t {
+            fn data_type(&self) -> DataType {
+                (**self).data_type()
+            }
+        }
+
+        unsafe impl<$($lt),*> CElement for $t {}
+    };
 }
-unsafe impl CElement for Box<dyn InputParameter> {}
+
+// Allow for input parameters whose type is only known at runtime, owned or borrowed. Boxed
+// parameters may borrow their values, e.g. `Box::new(text.into_parameter())` for a `&str`, so text
+// does not need to be copied to be part of a dynamic list of parameters.
+impl_input_parameter_for_trait_object!(<'a> Box<dyn InputParameter + 'a>);
+impl_input_parameter_for_trait_object!(<'a, 'b> &'a (dyn InputParameter + 'b));
@@ -0,0 +1,171 @@
+use std::ffi::c_void;
+
+use odbc_sys::{CDataType, NULL_DATA};
+
+use crate::{
+    buffers::Indicator,
+    handles::{CData, CDataMut, HasDataType},
+    DataType, OutputParameter,
+};
+
+use super::CElement;
+
+/// Fixed size binary data of exactly `N` bytes, allocated on the stack. Bound as `BINARY(N)`. Use
+/// it for hashes, UUIDs and other values of known length, as input or output parameter or with
+/// [`crate::CursorRow::get_data`], without allocating a `Vec` for each value.
+///
+/// ```no_run
+/// use odbc_api::{Connection, Cursor, Error, IntoParameter, parameter::Binary};
+///
+/// fn insert_hash(conn: &Connection<'_>, sha256: [u8; 32]) -> Result<(), Error> {
+///     conn.execute("INSERT INTO Files (sha256) VALUES (?)", &sha256.into_parameter())?;
+///     Ok(())
+/// }
+///
+/// fn uuid_of_user(conn: &Connection<'_>, name: &str) -> Result<Option<[u8; 16]>, Error> {
+///     let mut uuid = Binary::<16>::NULL;
+///     let mut cursor = conn
+///         .execute("SELECT uuid FROM Users WHERE name = ?", &name.into_parameter())?
+///         .expect("SELECT statement must yield a result set.");
+///     if let Some(mut row) = cursor.next_row()? {
+///         row.get_data(1, &mut uuid)?;
+///     }
+///     Ok(uuid.value().copied())
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Binary<const N: usize> {
+    value: [u8; N],
+    /// Length of the value in bytes, or `NULL_DATA`. Smaller than `N` only if fewer bytes have
+    /// been written into an output parameter.
+    indicator: isize,
+}
+
+impl<const N: usize> Binary<N> {
+    /// Indicates a missing value.
+    pub const NULL: Self = Self {
+        value: [0; N],
+        indicator: NULL_DATA,
+    };
+
+    /// Binds all `N` bytes of `value`.
+    pub fn new(value: [u8; N]) -> Self {
+        Self {
+            value,
+            indicator: N.try_into().unwrap(),
+        }
+    }
+
+    /// The value, or `None` if it is `NULL` or has not been completely written by the driver.
+    pub fn value(&self) -> Option<&[u8; N]> {
+        match self.indicator() {
+            Indicator::Length(len) if len == N => Some(&self.value),
+            _ => None,
+        }
+    }
+
+    /// Valid bytes of the value, or `None` if it is `NULL`. Shorter than `N`, if the driver wrote
+    /// fewer bytes. Truncated to `N` bytes, if the value written by the driver has been longer.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self.indicator() {
+            Indicator::Null => None,
+            Indicator::NoTotal => Some(&self.value),
+            Indicator::Length(len) => Some(&self.value[..len.min(N)]),
+        }
+    }
+
+    /// `false` if the value written by the driver did not fit into `N` bytes.
+    pub fn is_complete(&self) -> bool {
+        match self.indicator() {
+            Indicator::Null => true,
+            Indicator::NoTotal => false,
+            Indicator::Length(len) => len <= N,
+        }
+    }
+
+    /// Read access to the underlying ODBC indicator.
+    pub fn indicator(&self) -> Indicator {
+        Indicator::from_isize(self.indicator)
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for Binary<N> {
+    fn from(value: [u8; N]) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<const N: usize> From<Option<[u8; N]>> for Binary<N> {
+    fn from(value: Option<[u8; N]>) -> Self {
+        value.map_or(Self::NULL, Self::new)
+    }
+}
+
+unsafe impl<const N: usize> CData for Binary<N> {
+    fn cdata_type(&self) -> CDataType {
+        CDataType::Binary
+    }
+
+    fn indicator_ptr(&self) -> *const isize {
+        &self.indicator as *const isize
+    }
+
+    fn value_ptr(&self) -> *const c_void {
+        self.value.as_ptr() as *const c_void
+    }
+
+    fn buffer_length(&self) -> isize {
+        N.try_into().unwrap()
+    }
+}
+
+impl<const N: usize> HasDataType for Binary<N> {
+    fn data_type(&self) -> DataType {
+        DataType::Binary { length: N }
+    }
+}
+
+unsafe impl<const N: usize> CDataMut for Binary<N> {
+    fn mut_indicator_ptr(&mut self) -> *mut isize {
+        &mut self.indicator as *mut isize
+    }
+
+    fn mut_value_ptr(&mut self) -> *mut c_void {
+        self.value.as_mut_ptr() as *mut c_void
+    }
+}
+
+unsafe impl<const N: usize> CElement for Binary<N> {}
+unsafe impl<const N: usize> OutputParameter for Binary<N> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{buffers::Indicator, handles::HasDataType, DataType};
+
+    use super::Binary;
+
+    #[test]
+    fn value_of_fixed_size() {
+        let uuid = Binary::new([7; 16]);
+
+        assert_eq!(Some(&[7; 16]), uuid.value());
+        assert_eq!(DataType::Binary { length: 16 }, uuid.data_type());
+        assert_eq!(None, Binary::<16>::NULL.value());
+        assert_eq!(Binary::<4>::NULL, None.into());
+    }
+
+    #[test]
+    fn incomplete_output() {
+        let mut short = Binary::new([1, 2, 3, 4]);
+        short.indicator = 2;
+        let mut long = Binary::new([1, 2, 3, 4]);
+        long.indicator = 6;
+
+        assert_eq!(None, short.value());
+        assert_eq!(Some(&[1, 2][..]), short.as_bytes());
+        assert!(short.is_complete());
+        assert_eq!(Some(&[1, 2, 3, 4][..]), long.as_bytes());
+        assert!(!long.is_complete());
+        assert_eq!(Indicator::Length(6), long.indicator());
+    }
+}
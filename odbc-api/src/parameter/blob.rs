@@ -1,4 +1,4 @@
-use odbc_sys::{len_data_at_exec, CDataType, DATA_AT_EXEC};
+use odbc_sys::{CDataType, DATA_AT_EXEC};
 
 use crate::{
     handles::{DelayedInput, HasDataType, Statement},
@@ -55,15 +55,22 @@ pub struct BlobParam<'a> {
 
 impl<'a> BlobParam<'a> {
     pub fn new(blob: &'a mut impl Blob) -> Self {
-        let indicator = if let Some(size) = blob.size_hint() {
-            len_data_at_exec(size.try_into().unwrap())
-        } else {
-            DATA_AT_EXEC
-        };
+        let indicator = data_at_exec_indicator(blob.size_hint());
         Self { indicator, blob }
     }
 }
 
+/// Indicator for a parameter streamed at execution time. Same as [`crate::sys::len_data_at_exec`]
+/// if a size hint is given. `SQLLEN` has the width of a pointer, so on 32 Bit platforms sizes
+/// beyond 2 GiB can not be represented. In that case we omit the size hint, rather than to panic.
+fn data_at_exec_indicator(size_hint: Option<usize>) -> isize {
+    const SQL_LEN_DATA_AT_EXEC_OFFSET: isize = -100;
+    size_hint
+        .and_then(|size| isize::try_from(size).ok())
+        .and_then(|size| SQL_LEN_DATA_AT_EXEC_OFFSET.checked_sub(size))
+        .unwrap_or(DATA_AT_EXEC)
+}
+
 unsafe impl DelayedInput for BlobParam<'_> {
     fn cdata_type(&self) -> CDataType {
         self.blob.c_data_type()
@@ -334,14 +341,20 @@ impl BlobRead<BufReader<File>> {
     /// ```
     pub fn from_path(path: &Path) -> io::Result<Self> {
         let file = File::open(path)?;
-        let size = file.metadata()?.len().try_into().unwrap();
+        let len = file.metadata()?.len();
         let buf_read = BufReader::new(file);
-        Ok(Self {
-            consume: 0,
-            exact: true,
-            size,
-            buf_read,
-        })
+        // Files may be larger than `usize::MAX` on 32 Bit platforms. We can still stream them, just
+        // not announce their exact size to the driver.
+        let blob = match usize::try_from(len) {
+            Ok(size) => Self {
+                consume: 0,
+                exact: true,
+                size,
+                buf_read,
+            },
+            Err(_) => Self::with_upper_bound(buf_read, usize::MAX),
+        };
+        Ok(blob)
     }
 }
 
@@ -383,3 +396,25 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use odbc_sys::{len_data_at_exec, DATA_AT_EXEC};
+
+    use super::data_at_exec_indicator;
+
+    #[test]
+    fn indicator_with_size_hint() {
+        assert_eq!(len_data_at_exec(42), data_at_exec_indicator(Some(42)));
+        assert_eq!(DATA_AT_EXEC, data_at_exec_indicator(None));
+    }
+
+    #[test]
+    fn omit_size_hint_exceeding_sqllen() {
+        assert_eq!(DATA_AT_EXEC, data_at_exec_indicator(Some(usize::MAX)));
+        assert_eq!(
+            DATA_AT_EXEC,
+            data_at_exec_indicator(Some(isize::MAX as usize))
+        );
+    }
+}
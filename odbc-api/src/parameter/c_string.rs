@@ -26,7 +26,7 @@ unsafe impl CData for CStr {
     }
 
     fn buffer_length(&self) -> isize {
-        0
+        self.to_bytes_with_nul().len().try_into().unwrap()
     }
 }
 
@@ -54,7 +54,7 @@ unsafe impl CData for CString {
     }
 
     fn buffer_length(&self) -> isize {
-        0
+        self.to_bytes_with_nul().len().try_into().unwrap()
     }
 }
 
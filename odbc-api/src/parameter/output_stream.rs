@@ -0,0 +1,88 @@
+use odbc_sys::{CDataType, NULL_DATA};
+
+use crate::{handles::Statement, DataType, Error, StreamingTupleElement};
+
+/// Binds a parameter as streamed output parameter. Rather than being written into a buffer large
+/// enough to hold the largest possible value, the value is retrieved in parts after the statement
+/// has been executed. Use it with [`crate::Preallocated::execute_with_output_streams`], e.g. to
+/// retrieve large documents returned by stored procedures.
+///
+/// Requires the driver to support ODBC 3.8 (see [`crate::Odbc38Features`]).
+///
+/// # Example
+///
+/// ```no_run
+/// use odbc_api::{Connection, DataType, Error, parameter::OutputStream};
+///
+/// fn fetch_document(conn: &Connection<'_>, id: i32) -> Result<Option<String>, Error> {
+///     let mut document = OutputStream::new(DataType::LongVarchar { length: 0 });
+///     let mut statement = conn.preallocate()?;
+///     let mut outputs =
+///         statement.execute_with_output_streams("{call GetDocument(?, ?)}", (&id, &mut document))?;
+///     let mut text = Vec::new();
+///     while let Some(mut output) = outputs.next_output()? {
+///         if output.parameter_number() == 2 && !output.get_text(&mut text)? {
+///             return Ok(None);
+///         }
+///     }
+///     Ok(Some(String::from_utf8(text).unwrap()))
+/// }
+/// ```
+///
+/// Other ways to execute a statement do not accept streamed output parameters, since nothing would
+/// retrieve their values:
+///
+/// ```compile_fail
+/// use odbc_api::{Connection, DataType, parameter::OutputStream};
+///
+/// fn fetch_document(conn: &Connection<'_>) {
+///     let mut document = OutputStream::new(DataType::LongVarchar { length: 0 });
+///     conn.execute("{call GetDocument(?)}", (&mut document,));
+/// }
+/// ```
+#[derive(Debug)]
+pub struct OutputStream {
+    data_type: DataType,
+    cdata_type: CDataType,
+    /// Ignored by the driver, but it still must be able to point somewhere.
+    indicator: isize,
+}
+
+impl OutputStream {
+    /// `data_type` is the SQL type of the parameter. Values of binary types are retrieved as
+    /// [`CDataType::Binary`], all others as [`CDataType::Char`].
+    pub fn new(data_type: DataType) -> Self {
+        let cdata_type = match data_type {
+            DataType::Binary { .. }
+            | DataType::Varbinary { .. }
+            | DataType::LongVarbinary { .. } => CDataType::Binary,
+            _ => CDataType::Char,
+        };
+        Self {
+            data_type,
+            cdata_type,
+            indicator: NULL_DATA,
+        }
+    }
+}
+
+/// Only accepted by [`crate::Preallocated::execute_with_output_streams`], since the values must be
+/// retrieved from the statement after execution.
+unsafe impl StreamingTupleElement for &mut OutputStream {
+    unsafe fn bind_to(
+        &mut self,
+        parameter_number: u16,
+        stmt: &mut impl Statement,
+    ) -> Result<(), Error> {
+        // The parameter number is used as token, to identify the parameter once its value is made
+        // available.
+        stmt.bind_output_stream_parameter(
+            parameter_number,
+            self.cdata_type,
+            self.data_type,
+            parameter_number as usize as *mut _,
+            &mut self.indicator,
+        )
+        .into_result(stmt)
+    }
+}
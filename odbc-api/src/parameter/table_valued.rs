@@ -0,0 +1,113 @@
+use crate::{
+    buffers::{ColumnBuffer, ColumnarBuffer},
+    handles::{utf8_to_sz, HasDataType, SqlChar, Statement},
+    Error, ParameterCollection, ParameterTupleElement, RowSetBuffer,
+};
+
+/// Passes the rows of a [`ColumnarBuffer`] (e.g. a [`crate::buffers::ColumnarAnyBuffer`]) as a
+/// single table valued parameter. This is the fastest way to send many rows to a stored procedure
+/// in Microsoft SQL Server. Table valued parameters are specific to Microsoft SQL Server and not
+/// supported by other drivers.
+///
+/// The columns of the buffer are bound in order to the columns of the table type. All valid rows
+/// of the buffer are sent.
+///
+/// # Example
+///
+/// ```no_run
+/// use odbc_api::{
+///     buffers::ColumnarAnyBuffer, parameter::TableValuedParameter, Connection, Error,
+/// };
+///
+/// /// Assumes: `CREATE TYPE dbo.Birthday AS TABLE (name VARCHAR(50), year INT)` and a procedure
+/// /// `insert_birthdays` accepting a `@birthdays dbo.Birthday READONLY` argument.
+/// fn send_birthdays(conn: &Connection<'_>, birthdays: &ColumnarAnyBuffer) -> Result<(), Error> {
+///     let mut tvp = TableValuedParameter::new("dbo.Birthday", birthdays);
+///     conn.execute("{call insert_birthdays(?)}", (&mut tvp,))?;
+///     Ok(())
+/// }
+/// ```
+pub struct TableValuedParameter<'a, C> {
+    /// Zero terminated name of the table type.
+    type_name: Vec<SqlChar>,
+    rows: &'a ColumnarBuffer<C>,
+    /// Bound as indicator of the table valued parameter. Tells the driver how many rows are valid.
+    /// Set upon binding, since the number of rows may not fit into an indicator.
+    num_rows: isize,
+}
+
+impl<'a, C> TableValuedParameter<'a, C>
+where
+    C: ColumnBuffer,
+{
+    /// # Parameters
+    ///
+    /// * `type_name`: Name of the table type as declared with `CREATE TYPE`. E.g.
+    ///   `"dbo.Birthday"`.
+    /// * `rows`: Buffer holding the rows of the table. Its columns must match the columns of the
+    ///   table type in number and order.
+    pub fn new(type_name: &str, rows: &'a ColumnarBuffer<C>) -> Self {
+        Self {
+            type_name: utf8_to_sz(type_name),
+            rows,
+            num_rows: 0,
+        }
+    }
+}
+
+impl<'a, C> TableValuedParameter<'a, C>
+where
+    C: ColumnBuffer + HasDataType,
+{
+    unsafe fn bind(
+        &mut self,
+        parameter_number: u16,
+        stmt: &mut impl Statement,
+    ) -> Result<(), Error> {
+        let num_rows = self.rows.num_rows();
+        self.num_rows =
+            isize::try_from(num_rows).map_err(|_| Error::TooManyRowsInBatch { num_rows })?;
+        stmt.bind_table_valued_parameter(
+            parameter_number,
+            &self.type_name,
+            self.rows.row_array_size(),
+            &self.num_rows,
+        )
+        .into_result(stmt)?;
+        // Bind the columns of the table type, then focus the parameters of the statement again.
+        stmt.set_parameter_focus(parameter_number)
+            .into_result(stmt)?;
+        for (index, column) in self.rows.column_buffers().enumerate() {
+            let column_number = (index + 1).try_into().unwrap();
+            stmt.bind_input_parameter(column_number, column)
+                .into_result(stmt)?;
+        }
+        stmt.set_parameter_focus(0).into_result(stmt)
+    }
+}
+
+unsafe impl<C> ParameterCollection for TableValuedParameter<'_, C>
+where
+    C: ColumnBuffer + HasDataType,
+{
+    fn parameter_set_size(&self) -> usize {
+        1
+    }
+
+    unsafe fn bind_parameters_to(&mut self, stmt: &mut impl Statement) -> Result<(), Error> {
+        self.bind(1, stmt)
+    }
+}
+
+unsafe impl<C> ParameterTupleElement for &mut TableValuedParameter<'_, C>
+where
+    C: ColumnBuffer + HasDataType,
+{
+    unsafe fn bind_to(
+        &mut self,
+        parameter_number: u16,
+        stmt: &mut impl Statement,
+    ) -> Result<(), Error> {
+        self.bind(parameter_number, stmt)
+    }
+}
@@ -261,7 +261,7 @@ impl<'a> VarCharSlice<'a> {
     /// Caveat: This constructor is going to create a truncated value in case the input slice ends
     /// with `nul`. Should you want to insert an actual string those payload ends with `nul` into
     /// the database you need a buffer one byte longer than the string. You can instantiate such a
-    /// value using [`Self::from_buffer`].
+    /// value using [`Self::from_buffer`] or [`crate::NulPolicy::text_parameter`].
     pub fn new(value: &'a [u8]) -> Self {
         Self::from_buffer(value, Indicator::Length(value.len()))
     }
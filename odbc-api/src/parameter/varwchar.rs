@@ -0,0 +1,208 @@
+use std::{
+    borrow::Borrow,
+    ffi::c_void,
+    mem::{size_of, size_of_val},
+};
+
+use odbc_sys::{CDataType, NULL_DATA};
+use widestring::{U16Str, U16String};
+
+use crate::{
+    buffers::Indicator,
+    handles::{CData, HasDataType},
+    DataType,
+};
+
+use super::CElement;
+
+/// Binds a slice of UTF-16 code units as variadic sized wide character data (`SQL_C_WCHAR`). Some
+/// drivers require parameters compared with `NVARCHAR` columns to be bound as wide text, e.g. to
+/// make use of an index with a specific collation, or to not depend on the system encoding.
+///
+/// Meaningful instantiations of this type are:
+///
+/// * [`self::VarWCharSlice`] - immutable borrowed parameter.
+/// * [`self::VarWCharBox`] - heap allocated owned parameter.
+///
+/// Use [`crate::IntoParameter`] on [`U16Str`], [`U16String`], or on text wrapped in [`Wide`] to
+/// create them.
+#[derive(Debug, Clone, Copy)]
+pub struct VarWChar<B> {
+    /// Contains the value. Characters must be valid up to the index indicated by `indicator`.
+    buffer: B,
+    /// Length of the value stored in `buffer` in bytes, or `NULL_DATA`.
+    indicator: isize,
+}
+
+/// Parameter type for owned, variable sized wide character data.
+pub type VarWCharBox = VarWChar<Box<[u16]>>;
+
+impl VarWCharBox {
+    /// Constructs a 'missing' value.
+    pub fn null() -> Self {
+        // Avoid binding an empty buffer as `NVARCHAR(0)`, see `VarCharBox::null`.
+        Self::from_buffer(Box::new([0]), Indicator::Null)
+    }
+
+    /// Create an owned parameter containing the text of `val`, encoded as UTF-16.
+    pub fn from_str_slice(val: &str) -> Self {
+        Self::from_vec(val.encode_utf16().collect())
+    }
+
+    /// Create an owned parameter from UTF-16 code units.
+    pub fn from_vec(val: Vec<u16>) -> Self {
+        let indicator = Indicator::Length(val.len() * size_of::<u16>());
+        Self::from_buffer(val.into_boxed_slice(), indicator)
+    }
+}
+
+/// Binds borrowed UTF-16 text as an input parameter. Created if `into_parameter` of the
+/// [`crate::IntoParameter`] trait is called on a `&U16Str`.
+///
+/// ```no_run
+/// use odbc_api::{Connection, Error, IntoParameter, U16String};
+///
+/// fn find(conn: &Connection<'_>, name: &U16String) -> Result<(), Error> {
+///     conn.execute("SELECT id FROM Users WHERE name=?", &name.as_ustr().into_parameter())?;
+///     Ok(())
+/// }
+/// ```
+pub type VarWCharSlice<'a> = VarWChar<&'a [u16]>;
+
+impl<'a> VarWCharSlice<'a> {
+    /// Indicates missing data
+    pub const NULL: Self = Self {
+        buffer: &[0],
+        indicator: NULL_DATA,
+    };
+
+    /// Constructs a new parameter containing the text in the specified buffer.
+    pub fn new(value: &'a [u16]) -> Self {
+        Self::from_buffer(value, Indicator::Length(size_of_val(value)))
+    }
+}
+
+impl<B> VarWChar<B>
+where
+    B: Borrow<[u16]>,
+{
+    /// Creates a new instance from an existing buffer. `indicator` is the length of the value in
+    /// bytes, not in characters.
+    ///
+    /// # Panics
+    ///
+    /// If the indicator is `NoTotal`, or larger than the buffer.
+    pub fn from_buffer(buffer: B, indicator: Indicator) -> Self {
+        match indicator {
+            Indicator::Null => (),
+            Indicator::NoTotal => panic!("Length of wide text parameters must be known."),
+            Indicator::Length(len) => {
+                assert!(
+                    len <= size_of_val(buffer.borrow()),
+                    "Indicator must not exceed the buffer length."
+                )
+            }
+        }
+        Self {
+            buffer,
+            indicator: indicator.to_isize(),
+        }
+    }
+
+    /// The text as UTF-16 code units, or `None` if the value is `NULL`.
+    pub fn as_slice(&self) -> Option<&[u16]> {
+        match self.indicator() {
+            Indicator::Length(len) => Some(&self.buffer.borrow()[..len / size_of::<u16>()]),
+            Indicator::Null | Indicator::NoTotal => None,
+        }
+    }
+
+    /// The text, or `None` if the value is `NULL`.
+    pub fn as_ustr(&self) -> Option<&U16Str> {
+        self.as_slice().map(U16Str::from_slice)
+    }
+
+    /// Read access to the underlying ODBC indicator, i.e. the length of the value in bytes.
+    pub fn indicator(&self) -> Indicator {
+        Indicator::from_isize(self.indicator)
+    }
+}
+
+unsafe impl<B> CData for VarWChar<B>
+where
+    B: Borrow<[u16]>,
+{
+    fn cdata_type(&self) -> CDataType {
+        CDataType::WChar
+    }
+
+    fn indicator_ptr(&self) -> *const isize {
+        &self.indicator as *const isize
+    }
+
+    fn value_ptr(&self) -> *const c_void {
+        self.buffer.borrow().as_ptr() as *const c_void
+    }
+
+    fn buffer_length(&self) -> isize {
+        size_of_val(self.buffer.borrow()).try_into().unwrap()
+    }
+}
+
+impl<B> HasDataType for VarWChar<B>
+where
+    B: Borrow<[u16]>,
+{
+    fn data_type(&self) -> DataType {
+        // Column size of wide character types is in characters, not in bytes.
+        DataType::WVarchar {
+            length: self.buffer.borrow().len(),
+        }
+    }
+}
+
+unsafe impl CElement for VarWCharSlice<'_> {}
+unsafe impl CElement for VarWCharBox {}
+
+/// Wraps text, so [`crate::IntoParameter`] encodes it as UTF-16 and binds it as wide character
+/// data, rather than narrow text in the system encoding. The conversion happens when the
+/// parameter is created, i.e. directly before binding.
+///
+/// ```no_run
+/// use odbc_api::{Connection, Error, IntoParameter, parameter::Wide};
+///
+/// fn find(conn: &Connection<'_>, name: &str) -> Result<(), Error> {
+///     conn.execute("SELECT id FROM Users WHERE name=?", &Wide(name).into_parameter())?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Wide<T>(pub T);
+
+impl From<U16String> for VarWCharBox {
+    fn from(text: U16String) -> Self {
+        Self::from_vec(text.into_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{buffers::Indicator, handles::HasDataType, DataType};
+
+    use super::{VarWCharBox, VarWCharSlice};
+
+    #[test]
+    fn indicator_is_length_in_bytes() {
+        let parameter = VarWCharBox::from_str_slice("Grüße");
+
+        assert_eq!(Indicator::Length(10), parameter.indicator());
+        assert_eq!(DataType::WVarchar { length: 5 }, parameter.data_type());
+        assert_eq!("Grüße", parameter.as_ustr().unwrap().to_string_lossy());
+    }
+
+    #[test]
+    fn null() {
+        assert_eq!(None, VarWCharSlice::NULL.as_slice());
+        assert_eq!(None, VarWCharBox::null().as_slice());
+    }
+}
@@ -1,11 +1,39 @@
 use crate::{handles::Statement, parameter::InputParameter, Error};
 
+mod arrays;
 mod tuple;
 
-pub use tuple::ParameterTupleElement;
+pub use self::{
+    arrays::{
+        ArrayElement, ArrayRow, IntoParameterArray, NullableArray, ParameterArray, ParameterArrays,
+        TextArray,
+    },
+    tuple::ParameterTupleElement,
+};
 
 /// A collection of input parameters. They can be bound to a statement using a shared reference.
 ///
+/// With the `derive` feature enabled, it can be derived for structs, whose fields implement
+/// [`InputParameter`]. Each field is bound to a placeholder, in the order of declaration.
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # mod example {
+/// use odbc_api::{parameter::VarCharBox, Connection, Error, InputParameterCollection};
+///
+/// #[derive(InputParameterCollection)]
+/// struct Birthday {
+///     name: VarCharBox,
+///     year: i32,
+/// }
+///
+/// fn insert_birthday(conn: &Connection<'_>, birthday: &Birthday) -> Result<(), Error> {
+///     conn.execute("INSERT INTO Birthdays (name, year) VALUES (?, ?)", birthday)?;
+///     Ok(())
+/// }
+/// # }
+/// ```
+///
 /// # Safety
 ///
 /// Must only bind pointers to statement which are valid for the lifetime of the collection. The
@@ -23,6 +51,12 @@ pub unsafe trait InputParameterCollection {
     /// responsibility that by then the buffers are either unbound from the statement or still
     /// valild.
     unsafe fn bind_input_parameters_to(&self, stmt: &mut impl Statement) -> Result<(), Error>;
+
+    /// Textual representation of the parameters, used for logging. See
+    /// [`ParameterCollectionRef::parameter_previews`].
+    fn parameter_previews(&self) -> Vec<String> {
+        Vec::new()
+    }
 }
 
 unsafe impl<T> ParameterCollectionRef for &T
@@ -36,6 +70,10 @@ where
     unsafe fn bind_parameters_to(&mut self, stmt: &mut impl Statement) -> Result<(), Error> {
         self.bind_input_parameters_to(stmt)
     }
+
+    fn parameter_previews(&self) -> Vec<String> {
+        (**self).parameter_previews()
+    }
 }
 
 unsafe impl<T> InputParameterCollection for T
@@ -47,7 +85,12 @@ where
     }
 
     unsafe fn bind_input_parameters_to(&self, stmt: &mut impl Statement) -> Result<(), Error> {
-        stmt.bind_input_parameter(1, self).into_result(stmt)
+        let mut parameter = self;
+        parameter.bind_to(1, stmt)
+    }
+
+    fn parameter_previews(&self) -> Vec<String> {
+        vec![ParameterTupleElement::preview(&self)]
     }
 }
 
@@ -60,12 +103,34 @@ where
     }
 
     unsafe fn bind_input_parameters_to(&self, stmt: &mut impl Statement) -> Result<(), Error> {
-        for (index, parameter) in self.iter().enumerate() {
-            stmt.bind_input_parameter(index as u16 + 1, parameter)
-                .into_result(stmt)?;
+        for (index, mut parameter) in self.iter().enumerate() {
+            parameter.bind_to(index as u16 + 1, stmt)?;
         }
         Ok(())
     }
+
+    fn parameter_previews(&self) -> Vec<String> {
+        self.iter()
+            .map(|parameter| ParameterTupleElement::preview(&parameter))
+            .collect()
+    }
+}
+
+unsafe impl<T> InputParameterCollection for Vec<T>
+where
+    T: InputParameter,
+{
+    fn parameter_set_size(&self) -> usize {
+        1
+    }
+
+    unsafe fn bind_input_parameters_to(&self, stmt: &mut impl Statement) -> Result<(), Error> {
+        self.as_slice().bind_input_parameters_to(stmt)
+    }
+
+    fn parameter_previews(&self) -> Vec<String> {
+        InputParameterCollection::parameter_previews(self.as_slice())
+    }
 }
 
 /// SQL Parameters used to execute a query.
@@ -127,6 +192,22 @@ where
 /// # Ok::<(), odbc_api::Error>(())
 /// ```
 ///
+/// The elements of a slice or `Vec` may also be trait objects, so parameters of different types
+/// can be bound, whose number is only known at runtime. Tuples, slices, vectors and structs
+/// deriving [`InputParameterCollection`] all bind their elements using [`ParameterTupleElement`],
+/// so functions accepting `impl ParameterCollectionRef` work with any of them.
+///
+/// ```no_run
+/// use odbc_api::{Connection, Error, IntoParameter, parameter::InputParameter};
+///
+/// fn find_birthdays(conn: &Connection<'_>, name: &str, year: i32) -> Result<(), Error> {
+///     let name = name.into_parameter();
+///     let params: Vec<&dyn InputParameter> = vec![&name, &year];
+///     conn.execute("SELECT * FROM Birthdays WHERE name = ? AND year = ?;", &params)?;
+///     Ok(())
+/// }
+/// ```
+///
 /// # Safety
 ///
 /// Instances of this type are passed by value, so this type can be implemented by both constant and
@@ -145,6 +226,13 @@ pub unsafe trait ParameterCollectionRef {
     /// responsibility that by then the buffers are either unbound from the statement or still
     /// valild.
     unsafe fn bind_parameters_to(&mut self, stmt: &mut impl Statement) -> Result<(), Error>;
+
+    /// Textual representation of each parameter in the collection, e.g. `'Hello'`, `42` or `NULL`.
+    /// Passed to a [`crate::QueryLogger`] as part of a [`crate::QueryLogEntry`]. The default
+    /// implementation returns an empty `Vec`, i.e. the parameters are not logged.
+    fn parameter_previews(&self) -> Vec<String> {
+        Vec::new()
+    }
 }
 
 unsafe impl<T> ParameterCollectionRef for &mut T
@@ -158,6 +246,10 @@ where
     unsafe fn bind_parameters_to(&mut self, stmt: &mut impl Statement) -> Result<(), Error> {
         (**self).bind_parameters_to(stmt)
     }
+
+    fn parameter_previews(&self) -> Vec<String> {
+        (**self).parameter_previews()
+    }
 }
 
 /// Implementers of this trait can be bound to a statement through a
@@ -180,6 +272,12 @@ pub unsafe trait ParameterCollection {
     /// the parameter remains valid while it is used. If the parameter is bound as an output
     /// parameter it must also be ensured that it is exclusively referenced by statement.
     unsafe fn bind_parameters_to(&mut self, stmt: &mut impl Statement) -> Result<(), Error>;
+
+    /// Textual representation of the parameters, used for logging. See
+    /// [`ParameterCollectionRef::parameter_previews`].
+    fn parameter_previews(&self) -> Vec<String> {
+        Vec::new()
+    }
 }
 
 unsafe impl<T> ParameterCollection for T
@@ -193,4 +291,8 @@ where
     unsafe fn bind_parameters_to(&mut self, stmt: &mut impl Statement) -> Result<(), Error> {
         self.bind_input_parameters_to(stmt)
     }
+
+    fn parameter_previews(&self) -> Vec<String> {
+        InputParameterCollection::parameter_previews(self)
+    }
 }
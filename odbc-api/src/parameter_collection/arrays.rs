@@ -0,0 +1,626 @@
+//! Bind slices of fixed sized values as parameter arrays, executing a statement once for each
+//! element.
+
+use std::{ffi::c_void, mem::size_of, ptr::null};
+
+use odbc_sys::{CDataType, NULL_DATA};
+
+use super::{InputParameterCollection, ParameterCollectionRef};
+use crate::{
+    fixed_sized::Pod,
+    handles::{CData, HasDataType, Statement},
+    DataType, Error,
+};
+
+/// A column of values, bound to a single placeholder as a parameter array. See
+/// [`ParameterArrays`].
+///
+/// # Safety
+///
+/// `bind_array_to` must bind pointers to at least [`Self::len`] elements, which stay valid for the
+/// lifetime of the instance.
+pub unsafe trait ParameterArray {
+    /// Number of elements in the array.
+    fn len(&self) -> usize;
+
+    /// `true` if the array has no elements.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Binds the elements to the placeholder `parameter_number`.
+    ///
+    /// # Safety
+    ///
+    /// The array must remain valid while it is bound to the statement.
+    unsafe fn bind_array_to(
+        &self,
+        parameter_number: u16,
+        stmt: &mut impl Statement,
+    ) -> Result<(), Error>;
+}
+
+/// Conversion into a [`ParameterArray`]. Slices of fixed sized values are bound as they are,
+/// slices of optional values are copied into a [`NullableArray`].
+pub trait IntoParameterArray {
+    type Array: ParameterArray;
+
+    fn into_parameter_array(self) -> Self::Array;
+}
+
+/// Slices of fixed sized values are bound directly, without copying them.
+unsafe impl<T> ParameterArray for &[T]
+where
+    T: Pod + HasDataType,
+{
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+
+    unsafe fn bind_array_to(
+        &self,
+        parameter_number: u16,
+        stmt: &mut impl Statement,
+    ) -> Result<(), Error> {
+        let column = RawColumn {
+            cdata_type: T::C_DATA_TYPE,
+            data_type: T::default().data_type(),
+            values: self.as_ptr() as *const c_void,
+            indicators: null(),
+            element_size: size_of::<T>(),
+        };
+        stmt.bind_input_parameter(parameter_number, &column)
+            .into_result(stmt)
+    }
+}
+
+unsafe impl<A> ParameterArray for &A
+where
+    A: ParameterArray,
+{
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+
+    unsafe fn bind_array_to(
+        &self,
+        parameter_number: u16,
+        stmt: &mut impl Statement,
+    ) -> Result<(), Error> {
+        (**self).bind_array_to(parameter_number, stmt)
+    }
+}
+
+unsafe impl<T> ParameterArray for Vec<T>
+where
+    T: Pod + HasDataType,
+{
+    fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    unsafe fn bind_array_to(
+        &self,
+        parameter_number: u16,
+        stmt: &mut impl Statement,
+    ) -> Result<(), Error> {
+        self.as_slice().bind_array_to(parameter_number, stmt)
+    }
+}
+
+impl<'a, T> IntoParameterArray for &'a [T]
+where
+    T: Pod + HasDataType,
+{
+    type Array = &'a [T];
+
+    fn into_parameter_array(self) -> Self::Array {
+        self
+    }
+}
+
+impl<'a, A> IntoParameterArray for &'a A
+where
+    A: ParameterArray,
+{
+    type Array = &'a A;
+
+    fn into_parameter_array(self) -> Self::Array {
+        self
+    }
+}
+
+impl<T> IntoParameterArray for &[Option<T>]
+where
+    T: Pod + HasDataType,
+{
+    type Array = NullableArray<T>;
+
+    fn into_parameter_array(self) -> Self::Array {
+        NullableArray::new(self)
+    }
+}
+
+/// Values of a slice of options together with an indicator array, marking `None` as `NULL`. The
+/// memory layout of `Option<T>` can not be bound directly, so the values are copied once.
+#[derive(Debug, Clone, Default)]
+pub struct NullableArray<T> {
+    values: Vec<T>,
+    indicators: Vec<isize>,
+}
+
+impl<T> NullableArray<T>
+where
+    T: Pod,
+{
+    /// Copies `values` into the array, with `None` bound as `NULL`.
+    pub fn new(values: &[Option<T>]) -> Self {
+        let indicators = values
+            .iter()
+            .map(|value| match value {
+                Some(_) => size_of::<T>().try_into().unwrap(),
+                None => NULL_DATA,
+            })
+            .collect();
+        let values = values
+            .iter()
+            .map(|value| value.unwrap_or_default())
+            .collect();
+        Self { values, indicators }
+    }
+
+    /// Appends a value. `None` is bound as `NULL`.
+    pub fn push(&mut self, value: Option<T>) {
+        self.indicators.push(match value {
+            Some(_) => size_of::<T>().try_into().unwrap(),
+            None => NULL_DATA,
+        });
+        self.values.push(value.unwrap_or_default());
+    }
+
+    /// Removes all values, keeping the allocated memory.
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.indicators.clear();
+    }
+}
+
+unsafe impl<T> ParameterArray for NullableArray<T>
+where
+    T: Pod + HasDataType,
+{
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    unsafe fn bind_array_to(
+        &self,
+        parameter_number: u16,
+        stmt: &mut impl Statement,
+    ) -> Result<(), Error> {
+        let column = RawColumn {
+            cdata_type: T::C_DATA_TYPE,
+            data_type: T::default().data_type(),
+            values: self.values.as_ptr() as *const c_void,
+            indicators: self.indicators.as_ptr(),
+            element_size: size_of::<T>(),
+        };
+        stmt.bind_input_parameter(parameter_number, &column)
+            .into_result(stmt)
+    }
+}
+
+/// Narrow text values of varying length, bound as a parameter array of `VARCHAR`. All values are
+/// stored with the same stride, which is the length of the longest value pushed so far. Pushing a
+/// longer value copies the existing ones once.
+#[derive(Debug, Clone, Default)]
+pub struct TextArray {
+    values: Vec<u8>,
+    indicators: Vec<isize>,
+    /// Length of the longest value in bytes.
+    max_len: usize,
+}
+
+impl TextArray {
+    /// Appends a value. `None` is bound as `NULL`.
+    pub fn push(&mut self, value: Option<&[u8]>) {
+        if let Some(value) = value {
+            if value.len() > self.stride() {
+                self.restride(value.len());
+            }
+        }
+        let start = self.values.len();
+        self.values.resize(start + self.stride(), 0);
+        match value {
+            Some(value) => {
+                self.values[start..start + value.len()].copy_from_slice(value);
+                self.indicators.push(value.len().try_into().unwrap());
+            }
+            None => self.indicators.push(NULL_DATA),
+        }
+    }
+
+    /// Value at `index`, or `None` if it is `NULL`.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn value_at(&self, index: usize) -> Option<&[u8]> {
+        let len = usize::try_from(self.indicators[index]).ok()?;
+        let start = index * self.stride();
+        Some(&self.values[start..start + len])
+    }
+
+    /// Removes all values. Keeps the allocated memory and the stride, so buffers filled again with
+    /// values of similar length are not copied.
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.indicators.clear();
+    }
+
+    /// Distance between two values in bytes. At least one, so the buffer bound for an array of
+    /// empty strings is not empty.
+    fn stride(&self) -> usize {
+        self.max_len.max(1)
+    }
+
+    fn restride(&mut self, max_len: usize) {
+        let old_stride = self.stride();
+        let mut values = vec![0; max_len * self.indicators.len()];
+        for (index, value) in self.values.chunks(old_stride).enumerate() {
+            values[index * max_len..index * max_len + old_stride].copy_from_slice(value);
+        }
+        self.values = values;
+        self.max_len = max_len;
+    }
+}
+
+unsafe impl ParameterArray for TextArray {
+    fn len(&self) -> usize {
+        self.indicators.len()
+    }
+
+    unsafe fn bind_array_to(
+        &self,
+        parameter_number: u16,
+        stmt: &mut impl Statement,
+    ) -> Result<(), Error> {
+        let column = RawColumn {
+            cdata_type: CDataType::Char,
+            data_type: DataType::Varchar {
+                length: self.stride(),
+            },
+            values: self.values.as_ptr() as *const c_void,
+            indicators: self.indicators.as_ptr(),
+            element_size: self.stride(),
+        };
+        stmt.bind_input_parameter(parameter_number, &column)
+            .into_result(stmt)
+    }
+}
+
+/// A value which can be appended to a parameter array owned by this crate. Used by
+/// [`crate::ChunkedExecution`] to fill one array per element of a row.
+pub trait ArrayElement: Sized {
+    /// The array holding the values of one placeholder.
+    type Array: ParameterArray + Default;
+
+    fn push_to(self, array: &mut Self::Array);
+
+    /// Removes all values from `array`, so it can be filled again.
+    fn clear(array: &mut Self::Array);
+}
+
+impl<T> ArrayElement for T
+where
+    T: Pod + HasDataType,
+{
+    type Array = Vec<T>;
+
+    fn push_to(self, array: &mut Self::Array) {
+        array.push(self)
+    }
+
+    fn clear(array: &mut Self::Array) {
+        array.clear()
+    }
+}
+
+impl<T> ArrayElement for Option<T>
+where
+    T: Pod + HasDataType,
+{
+    type Array = NullableArray<T>;
+
+    fn push_to(self, array: &mut Self::Array) {
+        array.push(self)
+    }
+
+    fn clear(array: &mut Self::Array) {
+        array.clear()
+    }
+}
+
+macro_rules! impl_array_element_for_text {
+    ($t:ty, $text:ident => $bytes:expr) => {
+        impl ArrayElement for $t {
+            type Array = TextArray;
+
+            fn push_to(self, array: &mut Self::Array) {
+                let $text = self;
+                array.push($bytes)
+            }
+
+            fn clear(array: &mut Self::Array) {
+                array.clear()
+            }
+        }
+    };
+}
+
+impl_array_element_for_text!(&str, text => Some(text.as_bytes()));
+impl_array_element_for_text!(String, text => Some(text.as_bytes()));
+impl_array_element_for_text!(Option<&str>, text => text.map(str::as_bytes));
+impl_array_element_for_text!(Option<String>, text => text.as_deref().map(str::as_bytes));
+
+/// A tuple of [`ArrayElement`]s, i.e. the values for all placeholders of one execution of a
+/// statement. See [`crate::ChunkedExecution`].
+pub trait ArrayRow: Sized {
+    /// One array for each element of the row.
+    type Arrays: InputParameterCollection + Default;
+
+    fn push_to(self, arrays: &mut Self::Arrays);
+
+    /// Removes all rows from `arrays`, so they can be filled again.
+    fn clear(arrays: &mut Self::Arrays);
+}
+
+macro_rules! impl_array_row_for_tuple {
+    ($($t:ident $index:tt)+) => {
+        impl<$($t: ArrayElement,)+> ArrayRow for ($($t,)+) {
+            type Arrays = ParameterArrays<($($t::Array,)+)>;
+
+            fn push_to(self, arrays: &mut Self::Arrays) {
+                $(self.$index.push_to(&mut (arrays.0).$index);)+
+            }
+
+            fn clear(arrays: &mut Self::Arrays) {
+                $($t::clear(&mut (arrays.0).$index);)+
+            }
+        }
+    };
+}
+
+impl_array_row_for_tuple! { A 0 }
+impl_array_row_for_tuple! { A 0 B 1 }
+impl_array_row_for_tuple! { A 0 B 1 C 2 }
+impl_array_row_for_tuple! { A 0 B 1 C 2 D 3 }
+impl_array_row_for_tuple! { A 0 B 1 C 2 D 3 E 4 }
+impl_array_row_for_tuple! { A 0 B 1 C 2 D 3 E 4 F 5 }
+impl_array_row_for_tuple! { A 0 B 1 C 2 D 3 E 4 F 5 G 6 }
+impl_array_row_for_tuple! { A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 }
+impl_array_row_for_tuple! { A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8 }
+impl_array_row_for_tuple! { A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8 J 9 }
+
+/// A tuple of [`ParameterArray`]s, one for each placeholder. The statement is executed once for
+/// each index of the arrays, e.g. to insert many rows with a single roundtrip. Contrary to
+/// [`crate::ColumnarBulkInserter`] the values do not need to be copied into buffers owned by this
+/// crate first.
+///
+/// ```no_run
+/// use odbc_api::{Connection, Error, ParameterArrays};
+///
+/// fn insert_measurements(
+///     conn: &Connection<'_>,
+///     sensor_ids: &[i32],
+///     values: &[Option<f64>],
+/// ) -> Result<(), Error> {
+///     let params = ParameterArrays::from((sensor_ids, values));
+///     conn.execute("INSERT INTO Measurements (sensor_id, value) VALUES (?, ?)", params)?;
+///     Ok(())
+/// }
+/// ```
+///
+/// Should the arrays not have the same length, binding them fails with
+/// [`Error::ParameterArraysOfDifferentLength`] and the statement is not executed.
+#[derive(Debug, Clone, Default)]
+pub struct ParameterArrays<T>(T);
+
+/// Ensures all arrays have the same length. `lens` holds the length of the array bound to each
+/// placeholder, in order.
+fn check_lengths(lens: &[usize]) -> Result<(), Error> {
+    let expected = lens[0];
+    match lens.iter().position(|&len| len != expected) {
+        Some(index) => Err(Error::ParameterArraysOfDifferentLength {
+            parameter_number: index + 1,
+            len: lens[index],
+            expected,
+        }),
+        None => Ok(()),
+    }
+}
+
+macro_rules! bind_arrays {
+    ($offset:expr, $stmt:ident) => (
+        Ok(())
+    );
+    ($offset:expr, $stmt:ident $head:ident $($tail:ident)*) => (
+        {
+            $head.bind_array_to($offset + 1, $stmt)?;
+            bind_arrays!($offset + 1, $stmt $($tail)*)
+        }
+    );
+}
+
+macro_rules! impl_parameter_arrays_for_tuple {
+    ($first:ident $($t:ident)*) => {
+        impl<$first: IntoParameterArray, $($t: IntoParameterArray,)*> From<($first, $($t,)*)>
+            for ParameterArrays<($first::Array, $($t::Array,)*)>
+        {
+            #[allow(non_snake_case)]
+            fn from(($first, $($t,)*): ($first, $($t,)*)) -> Self {
+                ParameterArrays(($first.into_parameter_array(), $($t.into_parameter_array(),)*))
+            }
+        }
+
+        #[allow(non_snake_case)]
+        unsafe impl<$first: ParameterArray, $($t: ParameterArray,)*> InputParameterCollection
+            for ParameterArrays<($first, $($t,)*)>
+        {
+            /// Length of the shortest array, so the driver never reads past the end of an array.
+            fn parameter_set_size(&self) -> usize {
+                let ($first, $($t,)*) = &self.0;
+                [$first.len(), $($t.len(),)*].into_iter().min().unwrap()
+            }
+
+            unsafe fn bind_input_parameters_to(
+                &self,
+                stmt: &mut impl Statement,
+            ) -> Result<(), Error> {
+                let ($first, $($t,)*) = &self.0;
+                check_lengths(&[$first.len(), $($t.len(),)*])?;
+                bind_arrays!(0, stmt $first $($t)*)
+            }
+        }
+
+        unsafe impl<$first: ParameterArray, $($t: ParameterArray,)*> ParameterCollectionRef
+            for ParameterArrays<($first, $($t,)*)>
+        {
+            fn parameter_set_size(&self) -> usize {
+                InputParameterCollection::parameter_set_size(self)
+            }
+
+            unsafe fn bind_parameters_to(
+                &mut self,
+                stmt: &mut impl Statement,
+            ) -> Result<(), Error> {
+                self.bind_input_parameters_to(stmt)
+            }
+        }
+    };
+}
+
+impl_parameter_arrays_for_tuple! { A }
+impl_parameter_arrays_for_tuple! { A B }
+impl_parameter_arrays_for_tuple! { A B C }
+impl_parameter_arrays_for_tuple! { A B C D }
+impl_parameter_arrays_for_tuple! { A B C D E }
+impl_parameter_arrays_for_tuple! { A B C D E F }
+impl_parameter_arrays_for_tuple! { A B C D E F G }
+impl_parameter_arrays_for_tuple! { A B C D E F G H }
+impl_parameter_arrays_for_tuple! { A B C D E F G H I }
+impl_parameter_arrays_for_tuple! { A B C D E F G H I J }
+
+/// Describes a column of values in memory owned by someone else, so it can be passed to
+/// [`Statement::bind_input_parameter`]. Only the pointers are bound, not the instance itself.
+struct RawColumn {
+    cdata_type: CDataType,
+    data_type: DataType,
+    values: *const c_void,
+    indicators: *const isize,
+    element_size: usize,
+}
+
+unsafe impl CData for RawColumn {
+    fn cdata_type(&self) -> CDataType {
+        self.cdata_type
+    }
+
+    fn indicator_ptr(&self) -> *const isize {
+        self.indicators
+    }
+
+    fn value_ptr(&self) -> *const c_void {
+        self.values
+    }
+
+    fn buffer_length(&self) -> isize {
+        self.element_size.try_into().unwrap()
+    }
+}
+
+impl HasDataType for RawColumn {
+    fn data_type(&self) -> DataType {
+        self.data_type
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use odbc_sys::NULL_DATA;
+
+    use crate::{Error, ParameterCollectionRef};
+
+    use super::{
+        check_lengths, ArrayRow, NullableArray, ParameterArray, ParameterArrays, TextArray,
+    };
+
+    #[test]
+    fn indicators_for_options() {
+        let array = NullableArray::new(&[Some(1i64), None, Some(3)]);
+
+        assert_eq!(vec![1, 0, 3], array.values);
+        assert_eq!(vec![8, NULL_DATA, 8], array.indicators);
+    }
+
+    #[test]
+    fn parameter_set_size_is_array_length() {
+        let ids = [1i32, 2, 3];
+        let amounts = [Some(1.5f64), None, Some(2.5)];
+
+        let params = ParameterArrays::from((&ids[..], &amounts[..]));
+
+        assert_eq!(3, params.parameter_set_size());
+    }
+
+    #[test]
+    fn arrays_of_different_length() {
+        let ids = [1i32, 2, 3];
+        let amounts = [1.5f64];
+
+        let params = ParameterArrays::from((&ids[..], &amounts[..]));
+
+        assert_eq!(1, params.parameter_set_size());
+        assert!(matches!(
+            check_lengths(&[3, 3, 1]),
+            Err(Error::ParameterArraysOfDifferentLength {
+                parameter_number: 3,
+                len: 1,
+                expected: 3
+            })
+        ));
+        assert!(check_lengths(&[3, 3]).is_ok());
+    }
+
+    #[test]
+    fn longer_text_restrides_existing_values() {
+        let mut array = TextArray::default();
+        array.push(Some(b"ab"));
+        array.push(None);
+        array.push(Some(b"abcd"));
+        array.push(Some(b""));
+
+        assert_eq!(4, array.len());
+        assert_eq!(Some(&b"ab"[..]), array.value_at(0));
+        assert_eq!(None, array.value_at(1));
+        assert_eq!(Some(&b"abcd"[..]), array.value_at(2));
+        assert_eq!(Some(&b""[..]), array.value_at(3));
+    }
+
+    #[test]
+    fn push_rows_to_arrays() {
+        let mut arrays = <(i32, Option<f64>, &str) as ArrayRow>::Arrays::default();
+        (1, Some(1.5), "one").push_to(&mut arrays);
+        (2, None::<f64>, "two").push_to(&mut arrays);
+
+        assert_eq!(2, arrays.parameter_set_size());
+        let (ids, amounts, names) = &arrays.0;
+        assert_eq!(vec![1, 2], *ids);
+        assert_eq!(vec![8, NULL_DATA], amounts.indicators);
+        assert_eq!(Some(&b"two"[..]), names.value_at(1));
+
+        <(i32, Option<f64>, &str)>::clear(&mut arrays);
+
+        assert_eq!(0, arrays.parameter_set_size());
+    }
+}
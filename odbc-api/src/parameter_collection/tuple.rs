@@ -2,7 +2,10 @@
 //! trait.
 
 use super::ParameterCollectionRef;
-use crate::{handles::Statement, parameter::InputParameter, Error, InOut, Out, OutputParameter};
+use crate::{
+    handles::Statement, parameter::InputParameter, query_log::preview_cdata, Error, InOut, Out,
+    OutputParameter,
+};
 
 macro_rules! impl_bind_parameters {
     ($offset:expr, $stmt:ident) => (
@@ -31,6 +34,11 @@ macro_rules! impl_parameters_for_tuple{
                 let ($($t,)*) = self;
                 impl_bind_parameters!(0, stmt $($t)*)
             }
+
+            fn parameter_previews(&self) -> Vec<String> {
+                let ($($t,)*) = self;
+                vec![$($t.preview(),)*]
+            }
         }
     );
 }
@@ -67,6 +75,12 @@ pub unsafe trait ParameterTupleElement {
         parameter_number: u16,
         stmt: &mut impl Statement,
     ) -> Result<(), Error>;
+
+    /// Textual representation of the parameter, used for logging. See
+    /// [`crate::ParameterCollectionRef::parameter_previews`]. Defaults to `?`.
+    fn preview(&self) -> String {
+        "?".to_owned()
+    }
 }
 
 /// Bind immutable references as input parameters.
@@ -82,6 +96,10 @@ where
         stmt.bind_input_parameter(parameter_number, *self)
             .into_result(stmt)
     }
+
+    fn preview(&self) -> String {
+        preview_cdata(*self)
+    }
 }
 
 /// Bind mutable references as input/output parameter.
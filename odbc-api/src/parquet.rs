@@ -0,0 +1,357 @@
+//! Export result sets as Apache Parquet files. Requires the `parquet` feature.
+//!
+//! The Parquet schema is derived from the result set metadata. Each fetched batch is converted into
+//! an Arrow record batch and handed to the `ArrowWriter` of the `parquet` crate. Rows are buffered
+//! until a row group is complete, so memory usage is bound by the size of a row group, not by the
+//! size of the result set.
+//!
+//! ```no_run
+//! use odbc_api::{parquet::{write_parquet, ParquetOptions}, Connection};
+//!
+//! fn export_birthdays(conn: &Connection<'_>) -> Result<(), Box<dyn std::error::Error>> {
+//!     let cursor = conn.execute("SELECT name, birthday FROM Birthdays", ())?.unwrap();
+//!     let num_rows = write_parquet(cursor, "birthdays.parquet", &ParquetOptions::default())?;
+//!     println!("Exported {num_rows} rows.");
+//!     Ok(())
+//! }
+//! ```
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    sync::Arc,
+};
+
+use ::parquet::{
+    arrow::ArrowWriter, basic::Compression, errors::ParquetError as WriterError,
+    file::properties::WriterProperties,
+};
+use arrow_array::{
+    builder::StringBuilder, ArrayRef, BinaryArray, BooleanArray, Date32Array, Float32Array,
+    Float64Array, Int16Array, Int32Array, Int64Array, Int8Array, RecordBatch,
+    Time32MillisecondArray, TimestampMicrosecondArray, UInt8Array,
+};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef, TimeUnit};
+use thiserror::Error as ThisError;
+
+use crate::{
+    buffers::{max_str_len_with_limit, AnySlice, BufferDesc, ColumnarAnyBuffer},
+    calendar::{days_since_epoch, micros_since_epoch, millis_since_midnight},
+    sys::{Date, Time},
+    Cursor, Error,
+};
+
+/// Error emitted by [`write_parquet`] and [`write_parquet_to`].
+#[derive(Debug, ThisError)]
+pub enum ParquetError {
+    /// Fetching a batch from the data source failed.
+    #[error("Fetching rows for Parquet export failed:\n{0}")]
+    Odbc(#[from] Error),
+    /// Creating the Parquet file failed.
+    #[error("Creating Parquet file failed:\n{0}")]
+    Io(#[from] io::Error),
+    /// Converting a fetched batch into an Arrow record batch failed.
+    #[error("Converting rows into Arrow arrays failed:\n{0}")]
+    Arrow(#[from] ArrowError),
+    /// Encoding or writing the Parquet file failed.
+    #[error("Writing Parquet file failed:\n{0}")]
+    Parquet(#[from] WriterError),
+}
+
+/// Compression codec applied to the data pages of a Parquet file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParquetCompression {
+    /// Store pages as they are.
+    Uncompressed,
+    /// Compress pages with Snappy. Fast and supported by virtually every Parquet reader.
+    #[default]
+    Snappy,
+}
+
+impl ParquetCompression {
+    fn codec(self) -> Compression {
+        match self {
+            ParquetCompression::Uncompressed => Compression::UNCOMPRESSED,
+            ParquetCompression::Snappy => Compression::SNAPPY,
+        }
+    }
+}
+
+/// Options for [`write_parquet`] and [`write_parquet_to`].
+#[derive(Debug, Clone, Copy)]
+pub struct ParquetOptions {
+    /// Maximum number of rows fetched with one roundtrip to the data source.
+    pub batch_size: usize,
+    /// Text is fetched as UTF-16 and converted to UTF-8 regardless of the system encoding. Limits
+    /// the number of UTF-16 code units reserved for each text value. It is also used for columns
+    /// whose maximum length is not reported by the driver. Should any value not fit into the
+    /// buffer, fetching fails rather than exporting truncated text.
+    pub max_str_len: Option<usize>,
+    /// Maximum number of rows in a row group. Larger row groups allow for better compression and
+    /// faster scans, but must be held in memory until they are written. Must not be `0`.
+    pub row_group_size: usize,
+    /// Codec used to compress the data pages.
+    pub compression: ParquetCompression,
+}
+
+impl Default for ParquetOptions {
+    fn default() -> Self {
+        Self {
+            batch_size: 1000,
+            max_str_len: Some(4096),
+            row_group_size: 100_000,
+            compression: ParquetCompression::default(),
+        }
+    }
+}
+
+/// Fetches the rows of a cursor and writes them into a Parquet file at `path`, creating or
+/// truncating it. Returns the number of rows written. See [`write_parquet_to`] for details.
+pub fn write_parquet(
+    cursor: impl Cursor,
+    path: impl AsRef<Path>,
+    options: &ParquetOptions,
+) -> Result<usize, ParquetError> {
+    let file = File::create(path)?;
+    write_parquet_to(cursor, BufWriter::new(file), options)
+}
+
+/// Fetches the rows of a cursor and writes them as a Parquet file into `writer`. Returns the
+/// number of rows written. Column names are taken from the result set. Should several columns
+/// share a name, a suffix `_1`, `_2`, ... is appended to the later ones.
+///
+/// All columns are optional, since drivers are not reliable in reporting nullability. Types are
+/// mapped as follows:
+///
+/// * Integers are mapped to `INT32` or `INT64`, annotated with their width if less than 32 Bit.
+/// * `REAL` and `FLOAT` are mapped to `FLOAT` or `DOUBLE`, depending on their precision.
+/// * `BIT` is mapped to `BOOLEAN`.
+/// * Dates are mapped to `INT32` annotated as `DATE`.
+/// * Times are mapped to `INT32` annotated as `TIME_MILLIS`.
+/// * Timestamps are mapped to `INT64` with logical type `TIMESTAMP(isAdjustedToUTC=false,
+///   unit=MICROS)`, since ODBC timestamps do not carry a time zone.
+/// * Binary data is mapped to `BYTE_ARRAY`.
+/// * Everything else, including `DECIMAL` and `NUMERIC`, is fetched as text and mapped to
+///   `BYTE_ARRAY` annotated as `UTF8`.
+///
+/// # Panics
+///
+/// If [`ParquetOptions::row_group_size`] is `0`.
+pub fn write_parquet_to(
+    mut cursor: impl Cursor,
+    writer: impl Write + Send,
+    options: &ParquetOptions,
+) -> Result<usize, ParquetError> {
+    let quirks = cursor.quirks();
+    let num_cols: u16 = cursor.num_result_cols()?.try_into().unwrap();
+    let mut fields: Vec<Field> = Vec::new();
+    let mut descs = Vec::new();
+    for col_index in 1..(num_cols + 1) {
+        let data_type = cursor.col_data_type(col_index)?;
+        let desc = match quirks.buffer_desc(data_type, true) {
+            Some(BufferDesc::Text { .. }) | None => BufferDesc::WText {
+                max_str_len: max_str_len_with_limit(
+                    data_type.utf16_len().unwrap_or(0),
+                    options.max_str_len,
+                ),
+            },
+            Some(desc) => desc,
+        };
+        let mut name = cursor.col_name(col_index)?;
+        if fields.iter().any(|field| field.name() == &name) {
+            let mut suffix = 1;
+            while fields
+                .iter()
+                .any(|field| field.name() == &format!("{name}_{suffix}"))
+            {
+                suffix += 1;
+            }
+            name = format!("{name}_{suffix}");
+        }
+        fields.push(arrow_field(name, &desc));
+        descs.push(desc);
+    }
+    let schema = Arc::new(Schema::new(fields));
+    let buffer = ColumnarAnyBuffer::try_from_descs(options.batch_size, descs)?;
+    let mut block_cursor = cursor.bind_buffer(buffer)?;
+
+    let properties = WriterProperties::builder()
+        .set_compression(options.compression.codec())
+        .set_max_row_group_size(options.row_group_size)
+        .set_created_by(concat!("odbc-api version ", env!("CARGO_PKG_VERSION")).to_owned())
+        .build();
+    let mut file = ArrowWriter::try_new(writer, schema.clone(), Some(properties))?;
+    let mut num_rows = 0;
+    while let Some(batch) = block_cursor.fetch_with_truncation_check(true)? {
+        file.write(&record_batch(&schema, batch)?)?;
+        num_rows += batch.num_rows();
+    }
+    // Writes the last row group and the footer and flushes `writer`.
+    file.close()?;
+    Ok(num_rows)
+}
+
+/// Nullable Arrow field holding the values of a buffer. The Arrow types are chosen so the
+/// `ArrowWriter` maps them to the Parquet types listed in [`write_parquet_to`].
+fn arrow_field(name: String, desc: &BufferDesc) -> Field {
+    let data_type = match desc {
+        BufferDesc::I8 { .. } => DataType::Int8,
+        BufferDesc::I16 { .. } => DataType::Int16,
+        BufferDesc::I32 { .. } => DataType::Int32,
+        BufferDesc::U8 { .. } => DataType::UInt8,
+        BufferDesc::I64 { .. } => DataType::Int64,
+        BufferDesc::F32 { .. } => DataType::Float32,
+        BufferDesc::F64 { .. } => DataType::Float64,
+        BufferDesc::Bit { .. } => DataType::Boolean,
+        BufferDesc::Date { .. } => DataType::Date32,
+        BufferDesc::Time { .. } => DataType::Time32(TimeUnit::Millisecond),
+        BufferDesc::Timestamp { .. } => DataType::Timestamp(TimeUnit::Microsecond, None),
+        BufferDesc::Binary { .. } => DataType::Binary,
+        _ => DataType::Utf8,
+    };
+    Field::new(name, data_type, true)
+}
+
+/// Copies the valid rows of a fetched batch into Arrow arrays.
+fn record_batch(schema: &SchemaRef, batch: &ColumnarAnyBuffer) -> Result<RecordBatch, ArrowError> {
+    let columns = (0..batch.num_cols())
+        .map(|buffer_index| arrow_array(batch.column(buffer_index)))
+        .collect();
+    RecordBatch::try_new(schema.clone(), columns)
+}
+
+fn arrow_array(column: AnySlice<'_>) -> ArrayRef {
+    match column {
+        AnySlice::Text(view) => {
+            let mut builder = StringBuilder::new();
+            for text in view.iter() {
+                builder.append_option(text.map(String::from_utf8_lossy));
+            }
+            Arc::new(builder.finish())
+        }
+        AnySlice::WText(view) => {
+            let mut builder = StringBuilder::new();
+            let mut text = String::new();
+            for index in 0..view.len() {
+                text.clear();
+                let is_some = view.push_utf8(index, &mut text);
+                builder.append_option(is_some.then_some(&text));
+            }
+            Arc::new(builder.finish())
+        }
+        AnySlice::Binary(view) => Arc::new(view.iter().collect::<BinaryArray>()),
+        AnySlice::Date(v) => Arc::new(Date32Array::from_iter_values(v.iter().map(days))),
+        AnySlice::Time(v) => Arc::new(Time32MillisecondArray::from_iter_values(
+            v.iter().map(millis),
+        )),
+        AnySlice::Timestamp(v) => Arc::new(TimestampMicrosecondArray::from_iter_values(
+            v.iter().map(micros_since_epoch),
+        )),
+        AnySlice::F64(v) => Arc::new(Float64Array::from_iter_values(v.iter().copied())),
+        AnySlice::F32(v) => Arc::new(Float32Array::from_iter_values(v.iter().copied())),
+        AnySlice::I8(v) => Arc::new(Int8Array::from_iter_values(v.iter().copied())),
+        AnySlice::I16(v) => Arc::new(Int16Array::from_iter_values(v.iter().copied())),
+        AnySlice::I32(v) => Arc::new(Int32Array::from_iter_values(v.iter().copied())),
+        AnySlice::I64(v) => Arc::new(Int64Array::from_iter_values(v.iter().copied())),
+        AnySlice::U8(v) => Arc::new(UInt8Array::from_iter_values(v.iter().copied())),
+        AnySlice::Bit(v) => Arc::new(
+            v.iter()
+                .map(|bit| Some(bit.as_bool()))
+                .collect::<BooleanArray>(),
+        ),
+        AnySlice::NullableDate(v) => Arc::new(v.map(|d| d.map(days)).collect::<Date32Array>()),
+        AnySlice::NullableTime(v) => {
+            Arc::new(v.map(|t| t.map(millis)).collect::<Time32MillisecondArray>())
+        }
+        AnySlice::NullableTimestamp(v) => Arc::new(
+            v.map(|ts| ts.map(micros_since_epoch))
+                .collect::<TimestampMicrosecondArray>(),
+        ),
+        AnySlice::NullableF64(v) => Arc::new(v.map(Option::<&_>::copied).collect::<Float64Array>()),
+        AnySlice::NullableF32(v) => Arc::new(v.map(Option::<&_>::copied).collect::<Float32Array>()),
+        AnySlice::NullableI8(v) => Arc::new(v.map(Option::<&_>::copied).collect::<Int8Array>()),
+        AnySlice::NullableI16(v) => Arc::new(v.map(Option::<&_>::copied).collect::<Int16Array>()),
+        AnySlice::NullableI32(v) => Arc::new(v.map(Option::<&_>::copied).collect::<Int32Array>()),
+        AnySlice::NullableI64(v) => Arc::new(v.map(Option::<&_>::copied).collect::<Int64Array>()),
+        AnySlice::NullableU8(v) => Arc::new(v.map(Option::<&_>::copied).collect::<UInt8Array>()),
+        AnySlice::NullableBit(v) => Arc::new(
+            v.map(|bit| bit.map(|bit| bit.as_bool()))
+                .collect::<BooleanArray>(),
+        ),
+    }
+}
+
+/// Days since epoch. Dates are stored as 32 Bit integers, which covers all valid ODBC dates.
+fn days(date: &Date) -> i32 {
+    days_since_epoch(date) as i32
+}
+
+/// Milliseconds since midnight, which always fits into 32 Bit.
+fn millis(time: &Time) -> i32 {
+    millis_since_midnight(time) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow_array::{Array, Int32Array, StringArray};
+    use arrow_schema::Schema;
+    use parquet::arrow::{arrow_reader::ParquetRecordBatchReaderBuilder, ArrowWriter};
+
+    use crate::buffers::{AnyBuffer, BufferDesc, ColumnarAnyBuffer};
+
+    use super::{arrow_field, record_batch};
+
+    #[test]
+    fn read_back_written_batch() {
+        let int_desc = BufferDesc::I32 { nullable: true };
+        let text_desc = BufferDesc::Text { max_str_len: 10 };
+        let mut numbers = AnyBuffer::try_from_desc(2, int_desc).unwrap();
+        let mut text = AnyBuffer::try_from_desc(2, text_desc).unwrap();
+        if let AnyBuffer::NullableI32(column) = &mut numbers {
+            column.writer_n(2).write([Some(42), None].into_iter());
+        }
+        if let AnyBuffer::Text(column) = &mut text {
+            column.set_value(0, Some(b"Hello"));
+            column.set_value(1, None);
+        }
+        let mut batch =
+            unsafe { ColumnarAnyBuffer::new_unchecked(2, vec![(1, numbers), (2, text)]) };
+        batch.set_num_rows(2);
+        let schema = Arc::new(Schema::new(vec![
+            arrow_field("a".to_owned(), &int_desc),
+            arrow_field("b".to_owned(), &text_desc),
+        ]));
+
+        let mut file = tempfile::tempfile().unwrap();
+        let mut writer = ArrowWriter::try_new(&mut file, schema.clone(), None).unwrap();
+        writer
+            .write(&record_batch(&schema, &batch).unwrap())
+            .unwrap();
+        writer.close().unwrap();
+
+        let mut reader = ParquetRecordBatchReaderBuilder::try_new(file)
+            .unwrap()
+            .build()
+            .unwrap();
+        let read = reader.next().unwrap().unwrap();
+        assert_eq!(schema, read.schema());
+        assert_eq!(
+            &Int32Array::from(vec![Some(42), None]),
+            read.column(0)
+                .as_any()
+                .downcast_ref::<Int32Array>()
+                .unwrap()
+        );
+        assert_eq!(
+            &StringArray::from(vec![Some("Hello"), None]),
+            read.column(1)
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .unwrap()
+        );
+        assert!(reader.next().is_none());
+    }
+}
@@ -1,9 +1,13 @@
+#[cfg(feature = "odbc_version_3_80")]
+use crate::{execute::execute_with_output_streams, StreamedOutputs, StreamingParameterCollection};
 use crate::{
     execute::{
-        execute_columns, execute_tables, execute_with_parameters, execute_with_parameters_polling,
+        execute_columns, execute_primary_keys, execute_tables, execute_with_parameters,
+        execute_with_parameters_polling, row_count,
     },
     handles::{AsStatementRef, SqlText, Statement, StatementImpl, StatementRef},
-    CursorImpl, CursorPolling, Error, ParameterCollectionRef, Sleep,
+    query_log::{QueryLog, SharedQueryLogger},
+    CursorImpl, CursorPolling, CursorSensitivity, CursorType, Error, ParameterCollectionRef, Sleep,
 };
 
 /// A preallocated SQL statement handle intended for sequential execution of different queries. See
@@ -35,11 +39,25 @@ use crate::{
 pub struct Preallocated<'open_connection> {
     /// A valid statement handle.
     statement: StatementImpl<'open_connection>,
+    /// Query logger of the connection.
+    query_logger: Option<SharedQueryLogger>,
+    /// `true` once cursor type or cursor sensitivity may have been changed from their defaults.
+    cursor_attributes_changed: bool,
 }
 
 impl<'o> Preallocated<'o> {
     pub(crate) fn new(statement: StatementImpl<'o>) -> Self {
-        Self { statement }
+        Self {
+            statement,
+            query_logger: None,
+            cursor_attributes_changed: false,
+        }
+    }
+
+    /// Informs `logger` about each statement executed.
+    pub(crate) fn with_query_logger(mut self, logger: Option<SharedQueryLogger>) -> Self {
+        self.query_logger = logger;
+        self
     }
 
     /// Executes a statement. This is the fastest way to sequentially execute different SQL
@@ -87,8 +105,67 @@ impl<'o> Preallocated<'o> {
         query: &str,
         params: impl ParameterCollectionRef,
     ) -> Result<Option<CursorImpl<&mut StatementImpl<'o>>>, Error> {
+        let sql = SqlText::new(query);
+        let log = QueryLog::new(self.query_logger.as_ref(), query);
+        let statement = &mut self.statement;
+        execute_with_parameters(move || Ok(statement), Some(&sql), params, log)
+    }
+
+    /// Kind of cursor created by subsequent calls to [`Self::execute`]. By default cursors are
+    /// forward only. Applications which need to scroll and see changes made by others while doing
+    /// so, can request e.g. a keyset driven or dynamic cursor. The driver may substitute a cursor
+    /// type it does not support with a different one, in which case a warning is logged.
+    ///
+    /// ```no_run
+    /// use odbc_api::{Connection, CursorSensitivity, CursorType, Error};
+    ///
+    /// fn watch_orders(conn: &Connection<'_>) -> Result<(), Error> {
+    ///     let mut statement = conn.preallocate()?;
+    ///     statement.set_cursor_type(CursorType::KeysetDriven)?;
+    ///     statement.set_cursor_sensitivity(CursorSensitivity::Sensitive)?;
+    ///     if let Some(cursor) = statement.execute("SELECT id, status FROM Orders", ())? {
+    ///         // ... scroll through orders ...
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_cursor_type(&mut self, cursor_type: CursorType) -> Result<(), Error> {
+        self.cursor_attributes_changed = true;
+        self.statement
+            .set_cursor_type(cursor_type)
+            .into_result(&self.statement)
+    }
+
+    /// Whether cursors created by subsequent calls to [`Self::execute`] make changes made by
+    /// others visible. See [`Self::set_cursor_type`].
+    pub fn set_cursor_sensitivity(&mut self, sensitivity: CursorSensitivity) -> Result<(), Error> {
+        self.cursor_attributes_changed = true;
+        self.statement
+            .set_cursor_sensitivity(sensitivity)
+            .into_result(&self.statement)
+    }
+
+    /// Executes a statement with streamed output parameters (see
+    /// [`crate::parameter::OutputStream`]) bound. Their values are retrieved using the returned
+    /// [`StreamedOutputs`], after execution. Only once all of them have been retrieved (or the
+    /// returned value is dropped), the statement can be used again.
+    #[cfg(feature = "odbc_version_3_80")]
+    pub fn execute_with_output_streams(
+        &mut self,
+        query: &str,
+        params: impl StreamingParameterCollection,
+    ) -> Result<StreamedOutputs<StatementRef<'_>>, Error> {
         let query = SqlText::new(query);
-        execute_with_parameters(move || Ok(&mut self.statement), Some(&query), params)
+        execute_with_output_streams(self.statement.as_stmt_ref(), &query, params)
+    }
+
+    /// Limits the number of rows in result sets created by subsequent calls to [`Self::execute`].
+    /// `0` means no limit, which is the default. Not every driver supports this, some substitute
+    /// the value and emit a warning instead.
+    pub fn set_max_rows(&mut self, max_rows: usize) -> Result<(), Error> {
+        self.statement
+            .set_max_rows(max_rows)
+            .into_result(&self.statement)
     }
 
     /// Transfer ownership to the underlying statement handle.
@@ -103,6 +180,11 @@ impl<'o> Preallocated<'o> {
         self.statement
     }
 
+    /// `true` if [`Self::set_cursor_type`] or [`Self::set_cursor_sensitivity`] have been called.
+    pub(crate) fn cursor_attributes_changed(&self) -> bool {
+        self.cursor_attributes_changed
+    }
+
     /// List tables, schemas, views and catalogs of a datasource.
     ///
     /// # Parameters
@@ -157,6 +239,22 @@ impl<'o> Preallocated<'o> {
         )
     }
 
+    /// A cursor describing the columns making up the primary key of a table. See
+    /// [`crate::Connection::primary_keys`].
+    pub fn primary_keys(
+        &mut self,
+        catalog_name: &str,
+        schema_name: &str,
+        table_name: &str,
+    ) -> Result<CursorImpl<&mut StatementImpl<'o>>, Error> {
+        execute_primary_keys(
+            &mut self.statement,
+            &SqlText::new(catalog_name),
+            &SqlText::new(schema_name),
+            &SqlText::new(table_name),
+        )
+    }
+
     /// Number of rows affected by the last `INSERT`, `UPDATE` or `DELETE` statment. May return
     /// `None` if row count is not available. Some drivers may also allow to use this to determine
     /// how many rows have been fetched using `SELECT`. Most drivers however only know how many rows
@@ -184,17 +282,7 @@ impl<'o> Preallocated<'o> {
     /// }
     /// ```
     pub fn row_count(&mut self) -> Result<Option<usize>, Error> {
-        self.statement
-            .row_count()
-            .into_result(&self.statement)
-            .map(|count| {
-                // ODBC returns -1 in case a row count is not available
-                if count == -1 {
-                    None
-                } else {
-                    Some(count.try_into().unwrap())
-                }
-            })
+        row_count(&self.statement)
     }
 
     /// Call this method to enable asynchronous polling mode on the statement
@@ -202,7 +290,7 @@ impl<'o> Preallocated<'o> {
         self.statement
             .set_async_enable(true)
             .into_result(&self.statement)?;
-        Ok(PreallocatedPolling::new(self.statement))
+        Ok(PreallocatedPolling::new(self.statement, self.query_logger))
     }
 }
 
@@ -217,11 +305,16 @@ impl<'o> AsStatementRef for Preallocated<'o> {
 pub struct PreallocatedPolling<'open_connection> {
     /// A valid statement handle in polling mode
     statement: StatementImpl<'open_connection>,
+    /// Query logger of the connection.
+    query_logger: Option<SharedQueryLogger>,
 }
 
 impl<'o> PreallocatedPolling<'o> {
-    fn new(statement: StatementImpl<'o>) -> Self {
-        Self { statement }
+    fn new(statement: StatementImpl<'o>, query_logger: Option<SharedQueryLogger>) -> Self {
+        Self {
+            statement,
+            query_logger,
+        }
     }
 
     /// Executes a statement. This is the fastest way to sequentially execute different SQL
@@ -271,14 +364,10 @@ impl<'o> PreallocatedPolling<'o> {
         params: impl ParameterCollectionRef,
         sleep: impl Sleep,
     ) -> Result<Option<CursorPolling<&mut StatementImpl<'o>>>, Error> {
-        let query = SqlText::new(query);
-        execute_with_parameters_polling(
-            move || Ok(&mut self.statement),
-            Some(&query),
-            params,
-            sleep,
-        )
-        .await
+        let sql = SqlText::new(query);
+        let log = QueryLog::new(self.query_logger.as_ref(), query);
+        let statement = &mut self.statement;
+        execute_with_parameters_polling(move || Ok(statement), Some(&sql), params, sleep, log).await
     }
 }
 
@@ -1,8 +1,12 @@
+use std::ops::ControlFlow;
+
 use crate::{
     buffers::{AnyBuffer, BufferDesc, ColumnBuffer, TextColumn},
-    execute::execute_with_parameters,
+    execute::{execute_with_parameters, row_count},
     handles::{AsStatementRef, HasDataType, ParameterDescription, Statement, StatementRef},
-    ColumnarBulkInserter, CursorImpl, Error, ParameterCollectionRef, ResultSetMetadata,
+    query_log::{QueryLog, SharedQueryLogger},
+    ChunkedExecution, ColumnarBulkInserter, Cursor, CursorImpl, Error, ParameterCollectionRef,
+    Quirks, ResultSetMetadata, RowSetBuffer,
 };
 
 #[allow(deprecated)]
@@ -12,11 +16,35 @@ use crate::buffers::BufferDescription;
 /// once. See [`crate::Connection::prepare`].
 pub struct Prepared<S> {
     statement: S,
+    /// Quirks of the driver. Passed on to cursors created by executing the statement.
+    quirks: Quirks,
+    /// Query logger of the connection, together with the text of the statement.
+    query_log: Option<(SharedQueryLogger, String)>,
 }
 
 impl<S> Prepared<S> {
     pub(crate) fn new(statement: S) -> Self {
-        Self { statement }
+        Self {
+            statement,
+            quirks: Quirks::new(),
+            query_log: None,
+        }
+    }
+
+    /// Quirks of the driver reported by [`ResultSetMetadata::quirks`].
+    pub(crate) fn with_quirks(mut self, quirks: Quirks) -> Self {
+        self.quirks = quirks;
+        self
+    }
+
+    /// Informs `logger` about each execution of the statement prepared from `sql`.
+    pub(crate) fn with_query_logger(
+        mut self,
+        logger: Option<SharedQueryLogger>,
+        sql: &str,
+    ) -> Self {
+        self.query_log = logger.map(|logger| (logger, sql.to_owned()));
+        self
     }
 
     /// Transfer ownership to the underlying statement handle.
@@ -47,8 +75,14 @@ where
         &mut self,
         params: impl ParameterCollectionRef,
     ) -> Result<Option<CursorImpl<StatementRef<'_>>>, Error> {
+        let quirks = self.quirks;
+        let log = self
+            .query_log
+            .as_ref()
+            .and_then(|(logger, sql)| QueryLog::new(Some(logger), sql));
         let stmt = self.statement.as_stmt_ref();
-        execute_with_parameters(move || Ok(stmt), None, params)
+        let cursor = execute_with_parameters(move || Ok(stmt), None, params, log)?;
+        Ok(cursor.map(|cursor| cursor.with_quirks(quirks)))
     }
 
     /// Describes parameter marker associated with a prepared SQL statement.
@@ -290,8 +324,12 @@ where
     ) -> Result<ColumnarBulkInserter<S, AnyBuffer>, Error> {
         let parameter_buffers = descriptions
             .into_iter()
-            .map(|desc| AnyBuffer::from_desc(capacity, desc))
-            .collect();
+            .enumerate()
+            .map(|(buffer_index, desc)| {
+                AnyBuffer::try_from_desc(capacity, desc)
+                    .map_err(|source| source.add_context(buffer_index.try_into().unwrap()))
+            })
+            .collect::<Result<_, _>>()?;
         unsafe { self.unchecked_bind_columnar_array_parameters(parameter_buffers) }
     }
 
@@ -326,11 +364,122 @@ where
 
         let parameter_buffers = descriptions
             .into_iter()
-            .map(|desc| AnyBuffer::from_desc(capacity, desc))
+            .enumerate()
+            .map(|(buffer_index, desc)| {
+                AnyBuffer::try_from_desc(capacity, desc)
+                    .map_err(|source| source.add_context(buffer_index.try_into().unwrap()))
+            })
+            .collect::<Result<_, _>>()?;
+        unsafe { ColumnarBulkInserter::new(stmt, parameter_buffers) }
+    }
+
+    /// A [`crate::ColumnarBulkInserter`] binding text buffers, which borrows the statement. Like
+    /// [`Self::into_text_inserter`], but the prepared statement can be reused with a different set
+    /// of parameter buffers afterwards. E.g. to change the capacity between chunks.
+    ///
+    /// ```no_run
+    /// use odbc_api::{Connection, Error};
+    ///
+    /// fn insert_drinks(conn: &Connection<'_>, drinks: &[&str]) -> Result<(), Error> {
+    ///     let mut prepared = conn.prepare("INSERT INTO Drinks (name) VALUES (?)")?;
+    ///     for chunk in drinks.chunks(100) {
+    ///         let max_str_len = chunk.iter().map(|drink| drink.len()).max().unwrap_or(0);
+    ///         let mut inserter = prepared.text_inserter(chunk.len(), [max_str_len])?;
+    ///         for drink in chunk {
+    ///             inserter.append([Some(drink.as_bytes())].into_iter())?;
+    ///         }
+    ///         inserter.execute()?;
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn text_inserter(
+        &mut self,
+        capacity: usize,
+        max_str_len: impl IntoIterator<Item = usize>,
+    ) -> Result<ColumnarBulkInserter<StatementRef<'_>, TextColumn<u8>>, Error> {
+        let stmt = self.statement.as_stmt_ref();
+        let parameter_buffers = max_str_len
+            .into_iter()
+            .map(|max_str_len| TextColumn::new(capacity, max_str_len))
             .collect();
+        // Text Columns are created with NULL as default, which is valid for insertion.
         unsafe { ColumnarBulkInserter::new(stmt, parameter_buffers) }
     }
 
+    /// Executes the statement for each row of an iterator, sending the rows to the data source in
+    /// chunks of parameter arrays. See [`ChunkedExecution`].
+    pub fn chunked_execution(&mut self) -> ChunkedExecution<'_, S> {
+        ChunkedExecution::new(self)
+    }
+
+    /// Executes the prepared statement and passes the cursor to `f`, rather than returning it.
+    /// Afterwards the cursor is closed and the parameters are unbound using [`Self::reset`], so
+    /// they are only required to outlive this call and no stale pointers stay bound to the
+    /// statement. See [`crate::Connection::execute_scoped`].
+    pub fn execute_scoped<R>(
+        &mut self,
+        params: impl ParameterCollectionRef,
+        f: impl FnOnce(Option<&mut CursorImpl<StatementRef<'_>>>) -> Result<R, Error>,
+    ) -> Result<R, Error> {
+        let result = self
+            .execute(params)
+            .and_then(|mut cursor| f(cursor.as_mut()));
+        let reset = self.reset();
+        let value = result?;
+        reset?;
+        Ok(value)
+    }
+
+    /// Executes the prepared statement, binds `buffer` to the result set and fetches it batch by
+    /// batch, passing each batch to `on_batch`. Afterwards the buffer is unbound, the cursor closed
+    /// and the parameters are released, so the buffer can be reused for the next execution.
+    /// Returning [`ControlFlow::Break`] from `on_batch` stops fetching and discards the remaining
+    /// rows.
+    ///
+    /// Returns `false` if the statement did not create a result set. `on_batch` is not called in
+    /// this case.
+    ///
+    /// ```no_run
+    /// use std::ops::ControlFlow;
+    /// use odbc_api::{buffers::{BufferDesc, ColumnarAnyBuffer}, Connection, Error};
+    ///
+    /// fn sum_amounts(conn: &Connection<'_>, customers: &[i32]) -> Result<Vec<i64>, Error> {
+    ///     let mut prepared = conn.prepare("SELECT amount FROM Orders WHERE customer = ?")?;
+    ///     let mut buffer =
+    ///         ColumnarAnyBuffer::try_from_descs(1000, [BufferDesc::I64 { nullable: false }])?;
+    ///     let mut sums = Vec::new();
+    ///     for customer in customers {
+    ///         let mut sum = 0;
+    ///         prepared.execute_into(customer, &mut buffer, |batch| {
+    ///             sum += batch.column(0).as_slice::<i64>().unwrap().iter().sum::<i64>();
+    ///             ControlFlow::Continue(())
+    ///         })?;
+    ///         sums.push(sum);
+    ///     }
+    ///     Ok(sums)
+    /// }
+    /// ```
+    pub fn execute_into<B>(
+        &mut self,
+        params: impl ParameterCollectionRef,
+        buffer: &mut B,
+        on_batch: impl FnMut(&B) -> ControlFlow<()>,
+    ) -> Result<bool, Error>
+    where
+        B: RowSetBuffer,
+    {
+        let result = match self.execute(params) {
+            Ok(Some(cursor)) => fetch_batches(cursor, buffer, on_batch).map(|()| true),
+            Ok(None) => Ok(false),
+            Err(error) => Err(error),
+        };
+        let reset = self.reset();
+        let has_result_set = result?;
+        reset?;
+        Ok(has_result_set)
+    }
+
     /// Number of rows affected by the last `INSERT`, `UPDATE` or `DELETE` statment. May return
     /// `None` if row count is not available. Some drivers may also allow to use this to determine
     /// how many rows have been fetched using `SELECT`. Most drivers however only know how many rows
@@ -359,19 +508,55 @@ where
     /// }
     /// ```
     pub fn row_count(&mut self) -> Result<Option<usize>, Error> {
-        let stmt = self.statement.as_stmt_ref();
-        stmt.row_count().into_result(&stmt).map(|count| {
-            // ODBC returns -1 in case a row count is not available
-            if count == -1 {
-                None
-            } else {
-                Some(count.try_into().unwrap())
-            }
-        })
+        row_count(&self.statement.as_stmt_ref())
     }
 }
 
-impl<S> ResultSetMetadata for Prepared<S> where S: AsStatementRef {}
+impl<S> Prepared<S>
+where
+    S: AsStatementRef,
+{
+    /// Closes any result set still open and releases all parameters bound to the statement. The
+    /// statement stays prepared, so it can be executed again with a different set of parameters,
+    /// without the need to prepare it again. Dropping a cursor already closes it, so this is
+    /// mostly useful for statements which have been modified using
+    /// [`crate::handles::AsStatementRef::as_stmt_ref`].
+    ///
+    /// ```
+    /// use odbc_api::{Connection, Error, IntoParameter};
+    ///
+    /// fn delete_users(conn: &Connection<'_>, names: &[&str]) -> Result<(), Error> {
+    ///     let mut prepared = conn.prepare("DELETE FROM Users WHERE name=?")?;
+    ///     for &name in names {
+    ///         prepared.execute(&name.into_parameter())?;
+    ///         prepared.reset()?;
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn reset(&mut self) -> Result<(), Error> {
+        let mut stmt = self.statement.as_stmt_ref();
+        stmt.close_cursor_if_open().into_result(&stmt)?;
+        stmt.reset_parameters().into_result(&stmt)
+    }
+
+    /// Releases all column buffers bound to the statement. Block cursors unbind their buffer then
+    /// they are dropped, so this is only required for buffers bound using
+    /// [`crate::handles::AsStatementRef::as_stmt_ref`].
+    pub fn unbind(&mut self) -> Result<(), Error> {
+        let mut stmt = self.statement.as_stmt_ref();
+        stmt.unbind_cols().into_result(&stmt)
+    }
+}
+
+impl<S> ResultSetMetadata for Prepared<S>
+where
+    S: AsStatementRef,
+{
+    fn quirks(&self) -> Quirks {
+        self.quirks
+    }
+}
 
 impl<S> AsStatementRef for Prepared<S>
 where
@@ -381,3 +566,23 @@ where
         self.statement.as_stmt_ref()
     }
 }
+
+/// Fetches all batches of `cursor` into `buffer`, until the result set is consumed or `on_batch`
+/// breaks.
+fn fetch_batches<B>(
+    cursor: impl Cursor,
+    buffer: &mut B,
+    mut on_batch: impl FnMut(&B) -> ControlFlow<()>,
+) -> Result<(), Error>
+where
+    B: RowSetBuffer,
+{
+    let mut block_cursor = cursor.bind_buffer(buffer)?;
+    while let Some(batch) = block_cursor.fetch()? {
+        if on_batch(batch).is_break() {
+            break;
+        }
+    }
+    block_cursor.unbind().map_err(|error| error.source)?;
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use crate::{buffers::TextRowSet, Connection, Cursor, Error, ResultSetMetadata};
+
+/// Upper bound for the length of individual values fetched by [`Connection::preview`]. Longer
+/// values are truncated.
+const MAX_STR_LEN: usize = 4096;
+
+/// The first rows of a result set as text, together with the names of its columns. Returned by
+/// [`Connection::preview`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preview {
+    /// Names of the columns in the result set, in order.
+    pub column_names: Vec<String>,
+    /// Rows of the result set. Each row holds one element for each column. `NULL` is represented
+    /// as `None`. Bytes which are not valid UTF-8 are replaced with `U+FFFD`.
+    pub rows: Vec<Vec<Option<String>>>,
+}
+
+pub(crate) fn preview(
+    conn: &Connection<'_>,
+    query: &str,
+    max_rows: usize,
+) -> Result<Option<Preview>, Error> {
+    let mut statement = conn.preallocate()?;
+    // Zero would mean no limit. In that case we still execute the query, in order to learn the
+    // column names, but do not fetch any rows.
+    if max_rows != 0 {
+        statement.set_max_rows(max_rows)?;
+    }
+    let mut cursor = if let Some(cursor) = statement.execute(query, ())? {
+        cursor
+    } else {
+        return Ok(None);
+    };
+    let column_names = cursor.column_names()?.collect::<Result<Vec<_>, _>>()?;
+    let mut rows = Vec::new();
+    if max_rows != 0 {
+        // Drivers are free to ignore the maximum number of rows, so we limit the batch size as well
+        // and only fetch a single batch.
+        let buffer = TextRowSet::for_cursor(max_rows, &mut cursor, Some(MAX_STR_LEN))?;
+        let mut block_cursor = cursor.bind_buffer(buffer)?;
+        if let Some(batch) = block_cursor.fetch()? {
+            for row_index in 0..batch.num_rows() {
+                let row = (0..batch.num_cols())
+                    .map(|col_index| {
+                        batch
+                            .at(col_index, row_index)
+                            .map(|text| String::from_utf8_lossy(text).into_owned())
+                    })
+                    .collect();
+                rows.push(row);
+            }
+        }
+    }
+    Ok(Some(Preview { column_names, rows }))
+}
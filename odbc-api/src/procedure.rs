@@ -0,0 +1,246 @@
+use odbc_sys::{HStmt, Handle, HandleType, ParamType};
+
+use crate::{
+    cursor::bind_row_set_buffer_to_statement,
+    execute::execute_and_put_data,
+    handles::{
+        AsHandle, AsStatementRef, CData, CDataMut, DelayedInput, HasDataType, SqlResult, SqlText,
+        Statement, StatementImpl, StatementRef,
+    },
+    BlockCursor, Cursor, Error, ParameterCollectionRef, ResultSetMetadata, RowSetBuffer,
+};
+
+/// Results of a stored procedure invoked with [`crate::Connection::call`] or
+/// [`crate::Connection::call_with_return_value`].
+///
+/// A procedure may return any number of result sets, which can be iterated using
+/// [`Self::next_result_set`]. Many drivers (e.g. Microsoft SQL Server) only write the values of
+/// output parameters and the return value once all result sets have been consumed. Therefore the
+/// bound parameters are held by this type until [`Self::finish`] is called, which discards any
+/// result sets left and hands the parameters back to the application.
+pub struct ProcedureCall<'c, P> {
+    statement: StatementImpl<'c>,
+    /// Parameters bound to the statement. Output parameters may be written by the driver until all
+    /// results of the statement have been consumed, so we must keep them borrowed until then.
+    params: P,
+    state: State,
+}
+
+/// Position of a [`ProcedureCall`] within the results of the statement.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// The statement is positioned on a result set, which has not been handed out yet.
+    Unconsumed,
+    /// The current result (if any) has been handed out or has no columns. We need to advance to
+    /// the next result first, before handing out another result set.
+    Consumed,
+    /// All results have been consumed. Output parameters have been written.
+    Done,
+}
+
+impl<'c, P> ProcedureCall<'c, P> {
+    /// The next result set returned by the procedure. `None` once all result sets have been
+    /// consumed. Rows of the previous result set which have not been fetched are discarded.
+    /// Results without columns (e.g. row counts of `INSERT` statements within the procedure) are
+    /// skipped.
+    pub fn next_result_set(&mut self) -> Result<Option<ProcedureResultSet<'_>>, Error> {
+        loop {
+            match self.state {
+                State::Done => return Ok(None),
+                State::Unconsumed => {
+                    self.state = State::Consumed;
+                    return Ok(Some(ProcedureResultSet {
+                        statement: self.statement.as_stmt_ref(),
+                    }));
+                }
+                State::Consumed => self.advance()?,
+            }
+        }
+    }
+
+    /// Discards all remaining result sets, so the driver writes the values of output parameters.
+    /// Returns the parameters passed to the call, so values of output parameters can be extracted
+    /// from it.
+    pub fn finish(mut self) -> Result<P, Error> {
+        while self.state != State::Done {
+            self.advance()?;
+        }
+        Ok(self.params)
+    }
+
+    /// Move to the next result of the statement.
+    fn advance(&mut self) -> Result<(), Error> {
+        let stmt = &mut self.statement;
+        if unsafe { stmt.more_results() }.into_result_bool(&*stmt)? {
+            self.update_state()
+        } else {
+            self.state = State::Done;
+            Ok(())
+        }
+    }
+
+    /// Inspect the current result of the statement to find out if it is a result set.
+    fn update_state(&mut self) -> Result<(), Error> {
+        let stmt = &mut self.statement;
+        let num_cols = stmt.num_result_cols().into_result(&*stmt)?;
+        self.state = if num_cols > 0 {
+            State::Unconsumed
+        } else {
+            State::Consumed
+        };
+        Ok(())
+    }
+}
+
+/// A result set returned by a stored procedure. See [`ProcedureCall::next_result_set`].
+///
+/// Other than [`crate::CursorImpl`] the cursor is not closed then this type is dropped, since
+/// closing the cursor would also discard all remaining result sets of the procedure.
+pub struct ProcedureResultSet<'s> {
+    statement: StatementRef<'s>,
+}
+
+impl<'s> AsStatementRef for ProcedureResultSet<'s> {
+    fn as_stmt_ref(&mut self) -> StatementRef<'_> {
+        self.statement.as_stmt_ref()
+    }
+}
+
+impl<'s> ResultSetMetadata for ProcedureResultSet<'s> {}
+
+impl<'s> Cursor for ProcedureResultSet<'s> {
+    fn bind_buffer<B>(mut self, mut row_set_buffer: B) -> Result<BlockCursor<Self, B>, Error>
+    where
+        B: RowSetBuffer,
+    {
+        let stmt = self.statement.as_stmt_ref();
+        unsafe {
+            bind_row_set_buffer_to_statement(stmt, &mut row_set_buffer)?;
+        }
+        Ok(BlockCursor::new(row_set_buffer, self))
+    }
+}
+
+/// Shared implementation of [`crate::Connection::call`] and
+/// [`crate::Connection::call_with_return_value`].
+pub(crate) fn call<'c, P>(
+    mut statement: StatementImpl<'c>,
+    procedure: &str,
+    with_return_value: bool,
+    mut params: P,
+) -> Result<ProcedureCall<'c, P>, Error>
+where
+    P: ParameterCollectionRef,
+{
+    let num_params = unsafe {
+        // Reset parameters so we do not dereference stale once by mistake if we call
+        // `exec_direct`.
+        statement.reset_parameters().into_result(&statement)?;
+        statement
+            .set_paramset_size(params.parameter_set_size())
+            .into_result(&statement)?;
+        let mut counter = CountParameters {
+            statement: &mut statement,
+            num_params: 0,
+        };
+        params.bind_parameters_to(&mut counter)?;
+        counter.num_params
+    };
+    let query = call_escape(procedure, num_params, with_return_value);
+    let query = SqlText::new(&query);
+    unsafe {
+        execute_and_put_data(&mut statement.as_stmt_ref(), Some(&query))?;
+    }
+    let mut call = ProcedureCall {
+        statement,
+        params,
+        state: State::Consumed,
+    };
+    call.update_state()?;
+    Ok(call)
+}
+
+/// Assembles the ODBC escape sequence for calling a procedure. E.g. `{? = call my_proc(?, ?)}`.
+/// If `with_return_value` is `true`, the first parameter binds the return value, and is not part
+/// of the argument list.
+fn call_escape(procedure: &str, num_params: u16, with_return_value: bool) -> String {
+    let num_args = if with_return_value {
+        num_params.saturating_sub(1)
+    } else {
+        num_params
+    };
+    let args = vec!["?"; num_args.into()].join(", ");
+    let return_value = if with_return_value { "? = " } else { "" };
+    if num_args == 0 {
+        format!("{{{return_value}call {procedure}}}")
+    } else {
+        format!("{{{return_value}call {procedure}({args})}}")
+    }
+}
+
+/// Forwards parameter bindings to the wrapped statement, remembering the highest parameter number
+/// bound. This way we learn the number of placeholders required for the call escape sequence.
+struct CountParameters<'a, 'c> {
+    statement: &'a mut StatementImpl<'c>,
+    num_params: u16,
+}
+
+unsafe impl<'a, 'c> AsHandle for CountParameters<'a, 'c> {
+    fn as_handle(&self) -> Handle {
+        self.statement.as_handle()
+    }
+
+    fn handle_type(&self) -> HandleType {
+        HandleType::Stmt
+    }
+}
+
+impl<'a, 'c> Statement for CountParameters<'a, 'c> {
+    fn as_sys(&self) -> HStmt {
+        self.statement.as_sys()
+    }
+
+    unsafe fn bind_input_parameter(
+        &mut self,
+        parameter_number: u16,
+        parameter: &(impl HasDataType + CData + ?Sized),
+    ) -> SqlResult<()> {
+        self.num_params = self.num_params.max(parameter_number);
+        self.statement
+            .bind_input_parameter(parameter_number, parameter)
+    }
+
+    unsafe fn bind_parameter(
+        &mut self,
+        parameter_number: u16,
+        input_output_type: ParamType,
+        parameter: &mut (impl CDataMut + HasDataType),
+    ) -> SqlResult<()> {
+        self.num_params = self.num_params.max(parameter_number);
+        self.statement
+            .bind_parameter(parameter_number, input_output_type, parameter)
+    }
+
+    unsafe fn bind_delayed_input_parameter(
+        &mut self,
+        parameter_number: u16,
+        parameter: &mut (impl DelayedInput + HasDataType),
+    ) -> SqlResult<()> {
+        self.num_params = self.num_params.max(parameter_number);
+        self.statement
+            .bind_delayed_input_parameter(parameter_number, parameter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::call_escape;
+
+    #[test]
+    fn call_escape_sequences() {
+        assert_eq!("{call my_proc}", call_escape("my_proc", 0, false));
+        assert_eq!("{call s.my_proc(?, ?)}", call_escape("s.my_proc", 2, false));
+        assert_eq!("{? = call my_proc}", call_escape("my_proc", 1, true));
+        assert_eq!("{? = call my_proc(?)}", call_escape("my_proc", 2, true));
+    }
+}
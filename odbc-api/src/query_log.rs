@@ -0,0 +1,335 @@
+use std::{
+    fmt::{self, Display},
+    slice,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use odbc_sys::{CDataType, Date, Time, Timestamp, NTS, NULL_DATA};
+
+use crate::{
+    calendar::{format_date, format_time, format_timestamp},
+    handles::{CData, Statement},
+    parameter_collection::InputParameterCollection,
+    Error, ParameterCollectionRef, ParameterTupleElement,
+};
+
+/// Text values in parameter previews are cut off after this many characters.
+const MAX_PREVIEW_LEN: usize = 64;
+
+/// Placeholder shown in previews of parameters wrapped in [`Redacted`].
+const REDACTED: &str = "<redacted>";
+
+/// Receives a [`QueryLogEntry`] for each statement executed on a connection, be it directly, using
+/// a [`crate::Prepared`] or [`crate::Preallocated`] statement, or in polling mode. Install it with
+/// [`crate::Connection::set_query_logger`]. Implemented for closures taking a `&QueryLogEntry`.
+///
+/// Loggers must be `Send` and `Sync`, since they are shared between the connection and the
+/// statements allocated on it, which may be sent to another thread (see
+/// [`crate::Connection::promote_to_send`]).
+pub trait QueryLogger: Send + Sync {
+    /// Called after the statement has been executed, whether it succeeded or not.
+    fn log(&self, entry: &QueryLogEntry<'_>);
+}
+
+impl<F> QueryLogger for F
+where
+    F: Fn(&QueryLogEntry<'_>) + Send + Sync,
+{
+    fn log(&self, entry: &QueryLogEntry<'_>) {
+        self(entry)
+    }
+}
+
+/// Describes an executed statement. The [`Display`] implementation renders it in a single line,
+/// suitable for log files.
+#[derive(Debug)]
+pub struct QueryLogEntry<'a> {
+    /// Text of the executed statement.
+    pub sql: &'a str,
+    /// Preview of each parameter bound to the statement, in order. Long text values are cut off,
+    /// parameters wrapped in [`Redacted`] are replaced with `<redacted>`. `NULL` is rendered as
+    /// `NULL`, strings are quoted. Parameters whose value can not be rendered, e.g. streamed
+    /// blobs or output parameters, are represented with `?`.
+    pub parameters: &'a [String],
+    /// Time it took to execute the statement. The time required to fetch result sets is not
+    /// included.
+    pub duration: Duration,
+    /// The error, should the execution have failed.
+    pub error: Option<&'a Error>,
+}
+
+impl Display for QueryLogEntry<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Executed '{}'", self.sql)?;
+        if !self.parameters.is_empty() {
+            write!(f, " with parameters ({})", self.parameters.join(", "))?;
+        }
+        write!(f, " in {:.3}ms", self.duration.as_secs_f64() * 1000.)?;
+        if let Some(error) = self.error {
+            write!(f, ". Failed: {error}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Marks parameters, whose values must not show up in the [`QueryLogEntry`] passed to a
+/// [`QueryLogger`], e.g. passwords. Binds the wrapped parameters exactly like the unwrapped ones.
+///
+/// ```
+/// use odbc_api::{Connection, Error, IntoParameter, Redacted};
+///
+/// fn add_user(conn: &Connection<'_>, name: &str, password_hash: &str) -> Result<(), Error> {
+///     conn.execute(
+///         "INSERT INTO Users (name, password_hash) VALUES (?, ?)",
+///         (&name.into_parameter(), Redacted(&password_hash.into_parameter())),
+///     )?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Redacted<T>(pub T);
+
+unsafe impl<T> ParameterTupleElement for Redacted<T>
+where
+    T: ParameterTupleElement,
+{
+    unsafe fn bind_to(
+        &mut self,
+        parameter_number: u16,
+        stmt: &mut impl Statement,
+    ) -> Result<(), Error> {
+        self.0.bind_to(parameter_number, stmt)
+    }
+
+    fn preview(&self) -> String {
+        REDACTED.to_owned()
+    }
+}
+
+unsafe impl<T> ParameterCollectionRef for Redacted<&T>
+where
+    T: InputParameterCollection + ?Sized,
+{
+    fn parameter_set_size(&self) -> usize {
+        self.0.parameter_set_size()
+    }
+
+    unsafe fn bind_parameters_to(&mut self, stmt: &mut impl Statement) -> Result<(), Error> {
+        self.0.bind_input_parameters_to(stmt)
+    }
+
+    fn parameter_previews(&self) -> Vec<String> {
+        vec![REDACTED.to_owned(); self.0.parameter_previews().len()]
+    }
+}
+
+/// Logger installed on a connection. Shared with the statements allocated on it.
+pub(crate) type SharedQueryLogger = Arc<dyn QueryLogger>;
+
+/// Where to report the execution of a statement to, if a logger is installed.
+#[derive(Clone, Copy)]
+pub(crate) struct QueryLog<'a> {
+    logger: &'a dyn QueryLogger,
+    sql: &'a str,
+}
+
+impl<'a> QueryLog<'a> {
+    pub fn new(logger: Option<&'a SharedQueryLogger>, sql: &'a str) -> Option<Self> {
+        logger.map(|logger| Self {
+            logger: logger.as_ref(),
+            sql,
+        })
+    }
+
+    /// Captures the start time and previews of the parameters. Call this before the parameters are
+    /// bound.
+    pub fn start(self, params: &impl ParameterCollectionRef) -> StartedQueryLog<'a> {
+        StartedQueryLog {
+            log: self,
+            start: Instant::now(),
+            parameters: params.parameter_previews(),
+        }
+    }
+}
+
+/// Returned by [`QueryLog::start`].
+pub(crate) struct StartedQueryLog<'a> {
+    log: QueryLog<'a>,
+    start: Instant,
+    parameters: Vec<String>,
+}
+
+impl StartedQueryLog<'_> {
+    /// Passes the entry to the logger, once the statement has been executed.
+    pub fn finish(self, error: Option<&Error>) {
+        self.log.logger.log(&QueryLogEntry {
+            sql: self.log.sql,
+            parameters: &self.parameters,
+            duration: self.start.elapsed(),
+            error,
+        });
+    }
+}
+
+/// Renders the value bound by `parameter` for a [`QueryLogEntry`].
+pub(crate) fn preview_cdata(parameter: &(impl CData + ?Sized)) -> String {
+    let indicator_ptr = parameter.indicator_ptr();
+    // Safe: Implementers of `CData` guarantee the pointers to be valid and to describe a value of
+    // the reported C type.
+    unsafe {
+        let indicator = if indicator_ptr.is_null() {
+            None
+        } else {
+            Some(*indicator_ptr)
+        };
+        if indicator == Some(NULL_DATA) {
+            return "NULL".to_owned();
+        }
+        let value = parameter.value_ptr();
+        if value.is_null() {
+            return "?".to_owned();
+        }
+        // Length of the buffer in bytes. Terminating zeroes are only searched within it, so we do
+        // not read past its end, should there be none.
+        let buffer_length = usize::try_from(parameter.buffer_length()).unwrap_or(0);
+        match parameter.cdata_type() {
+            CDataType::Char => {
+                let bytes = match indicator {
+                    Some(length) if length >= 0 => {
+                        slice::from_raw_parts(value as *const u8, length as usize)
+                    }
+                    Some(NTS) | None if buffer_length > 0 => {
+                        let buffer = slice::from_raw_parts(value as *const u8, buffer_length);
+                        let length = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+                        &buffer[..length]
+                    }
+                    Some(_) | None => return "?".to_owned(),
+                };
+                quote(&String::from_utf8_lossy(bytes))
+            }
+            CDataType::WChar => {
+                let text = match indicator {
+                    Some(length) if length >= 0 => {
+                        slice::from_raw_parts(value as *const u16, length as usize / 2)
+                    }
+                    Some(NTS) | None if buffer_length > 1 => {
+                        let buffer = slice::from_raw_parts(value as *const u16, buffer_length / 2);
+                        let length = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+                        &buffer[..length]
+                    }
+                    Some(_) | None => return "?".to_owned(),
+                };
+                quote(&String::from_utf16_lossy(text))
+            }
+            CDataType::Binary => match indicator {
+                Some(length) if length >= 0 => format!("<{length} bytes>"),
+                _ => "?".to_owned(),
+            },
+            CDataType::Bit | CDataType::UTinyInt => (*(value as *const u8)).to_string(),
+            CDataType::STinyInt => (*(value as *const i8)).to_string(),
+            CDataType::SShort => (*(value as *const i16)).to_string(),
+            CDataType::UShort => (*(value as *const u16)).to_string(),
+            CDataType::SLong => (*(value as *const i32)).to_string(),
+            CDataType::ULong => (*(value as *const u32)).to_string(),
+            CDataType::SBigInt => (*(value as *const i64)).to_string(),
+            CDataType::UBigInt => (*(value as *const u64)).to_string(),
+            CDataType::Float => (*(value as *const f32)).to_string(),
+            CDataType::Double => (*(value as *const f64)).to_string(),
+            CDataType::TypeDate => format_date(&*(value as *const Date)),
+            CDataType::TypeTime => format_time(&*(value as *const Time)),
+            CDataType::TypeTimestamp => format_timestamp(&*(value as *const Timestamp), ' '),
+            _ => "?".to_owned(),
+        }
+    }
+}
+
+/// Quotes text, cutting it off after [`MAX_PREVIEW_LEN`] characters.
+fn quote(text: &str) -> String {
+    let mut chars = text.chars();
+    let mut quoted: String = chars.by_ref().take(MAX_PREVIEW_LEN).collect();
+    if chars.next().is_some() {
+        quoted.push_str("...");
+    }
+    format!("'{quoted}'")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ffi::c_void, time::Duration};
+
+    use odbc_sys::{CDataType, NTS};
+
+    use crate::{handles::CData, IntoParameter, ParameterCollectionRef};
+
+    use super::{preview_cdata, QueryLogEntry, Redacted};
+
+    /// Text claiming to be zero terminated, without a terminating zero.
+    struct Unterminated([u8; 3]);
+
+    unsafe impl CData for Unterminated {
+        fn cdata_type(&self) -> CDataType {
+            CDataType::Char
+        }
+
+        fn indicator_ptr(&self) -> *const isize {
+            &NTS as *const isize
+        }
+
+        fn value_ptr(&self) -> *const c_void {
+            self.0.as_ptr() as *const c_void
+        }
+
+        fn buffer_length(&self) -> isize {
+            self.0.len() as isize
+        }
+    }
+
+    #[test]
+    fn preview_parameters() {
+        let text = "Hello".into_parameter();
+        let missing: Option<i32> = None;
+        let params = (&42i32, &text, &missing.into_parameter(), &1.5f64);
+        assert_eq!(
+            vec!["42", "'Hello'", "NULL", "1.5"],
+            params.parameter_previews()
+        );
+    }
+
+    #[test]
+    fn redact_parameters() {
+        let password = "secret".into_parameter();
+        let params = (&"alice".into_parameter(), Redacted(&password));
+        assert_eq!(vec!["'alice'", "<redacted>"], params.parameter_previews());
+        assert_eq!(vec!["<redacted>"], Redacted(&password).parameter_previews());
+    }
+
+    #[test]
+    fn zero_terminated_text_ends_with_buffer() {
+        assert_eq!("'abc'", preview_cdata(&Unterminated(*b"abc")));
+        assert_eq!("'ab'", preview_cdata(&Unterminated(*b"ab\0")));
+        assert_eq!("'Hello'", preview_cdata(c"Hello"));
+    }
+
+    #[test]
+    fn cut_off_long_text() {
+        let text = "a".repeat(100).into_parameter();
+        let previews = (&text,).parameter_previews();
+        assert_eq!(format!("'{}...'", "a".repeat(64)), previews[0]);
+    }
+
+    #[test]
+    fn display_entry() {
+        let parameters = ["1".to_owned(), "<redacted>".to_owned()];
+        let entry = QueryLogEntry {
+            sql: "SELECT ?, ?",
+            parameters: &parameters,
+            duration: Duration::from_micros(1500),
+            error: None,
+        };
+        assert_eq!(
+            "Executed 'SELECT ?, ?' with parameters (1, <redacted>) in 1.500ms",
+            entry.to_string()
+        );
+    }
+}
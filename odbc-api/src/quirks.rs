@@ -0,0 +1,153 @@
+use crate::{
+    buffers::{BufferDesc, TextBinding, TextRowSet},
+    ColumnNameMatcher, DataType,
+};
+
+/// Known deviations of ODBC drivers from the behaviour this crate expects. Rather than handling
+/// each of these at the call site, applications and this crate can look up the quirks of the driver
+/// a [`crate::Connection`] uses with [`crate::Connection::quirks`]. Quirks are a property of the
+/// driver, not of the database management system, so they are looked up by the file name of the
+/// driver library.
+///
+/// Cursors created by [`crate::Connection::execute`] or statements prepared with
+/// [`crate::Connection::prepare`] know the quirks of their connection, so buffers chosen for their
+/// columns account for them automatically.
+///
+/// ```
+/// use odbc_api::{buffers::BufferDesc, DataType, Quirks};
+///
+/// let quirks = Quirks::from_driver_name("libsqora.so.19.1");
+/// // The Oracle driver does not support 64 Bit integers, so they are fetched as text.
+/// assert_eq!(
+///     Some(BufferDesc::Text { max_str_len: 20 }),
+///     quirks.buffer_desc(DataType::BigInt, true)
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Quirks {
+    /// The driver does not support 64 bit integer C types. Such columns are fetched as text
+    /// instead. E.g. the Oracle ODBC driver, which reports `HY004` once the first row is fetched.
+    pub no_64_bit_integers: bool,
+    /// The driver does not implement the wide (UTF-16) flavour of ODBC functions and character
+    /// data correctly. Text columns are bound as narrow buffers, even where this crate would
+    /// otherwise prefer wide buffers to be independent of the system locale. See
+    /// [`Self::text_binding`].
+    pub no_wide_text: bool,
+    /// The driver reports a column size of `0` for columns of unbounded length, like
+    /// `VARCHAR(MAX)`, and `SQL_NO_TOTAL` rather than a length as indicator for their values. A
+    /// buffer derived from the reported size could not hold any value, so such text columns are
+    /// bound with [`TextRowSet::LONG_TEXT_LIMIT`] characters instead. E.g. Microsoft SQL Server.
+    pub no_total_for_unbounded_columns: bool,
+    /// Used by name based lookups of columns, like [`crate::ResultSetMetadata::col_index`]. By
+    /// default names are matched ignoring case and surrounding whitespace.
+    pub column_name_matcher: ColumnNameMatcher,
+}
+
+impl Quirks {
+    /// Assume the driver behaves like the ODBC standard specifies.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Looks up the known quirks of a driver by the file name of its library, as reported by
+    /// [`crate::Connection::driver_name`] (`SQL_DRIVER_NAME`). The name is matched ignoring case
+    /// and platform specific prefixes or suffixes, e.g. `SQORA32.DLL` and `libsqora.so.19.1` both
+    /// refer to the Oracle driver. Unknown names yield a driver without any quirks.
+    pub fn from_driver_name(driver_name: &str) -> Self {
+        let name = driver_name.to_ascii_lowercase();
+        let mut quirks = Self::new();
+        if name.contains("sqora") {
+            quirks.no_64_bit_integers = true;
+        }
+        if name.contains("msodbcsql") || name.contains("sqlncli") || name.contains("sqlsrv32") {
+            quirks.no_total_for_unbounded_columns = true;
+        }
+        quirks
+    }
+
+    /// Binding to use for text columns, if `preferred` would be chosen for a driver without
+    /// quirks. Accounts for [`Self::no_wide_text`].
+    pub fn text_binding(&self, preferred: TextBinding) -> TextBinding {
+        if self.no_wide_text {
+            TextBinding::Narrow
+        } else {
+            preferred
+        }
+    }
+
+    /// Same as [`BufferDesc::from_data_type`], but adjusts the description to the quirks of the
+    /// driver.
+    pub fn buffer_desc(&self, data_type: DataType, nullable: bool) -> Option<BufferDesc> {
+        let desc = BufferDesc::from_data_type(data_type, nullable)?;
+        let desc = match desc {
+            // Large enough for `-9223372036854775808`
+            BufferDesc::I64 { .. } if self.no_64_bit_integers => {
+                BufferDesc::Text { max_str_len: 20 }
+            }
+            BufferDesc::Text { max_str_len: 0 } if self.no_total_for_unbounded_columns => {
+                BufferDesc::Text {
+                    max_str_len: TextRowSet::LONG_TEXT_LIMIT,
+                }
+            }
+            _ => desc,
+        };
+        Some(desc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        buffers::{BufferDesc, TextBinding, TextRowSet},
+        DataType,
+    };
+
+    use super::Quirks;
+
+    #[test]
+    fn unknown_driver_has_no_quirks() {
+        assert_eq!(Quirks::new(), Quirks::from_driver_name("psqlodbcw.so"));
+        assert_eq!(Quirks::new(), Quirks::from_driver_name("libsqlite3odbc.so"));
+    }
+
+    #[test]
+    fn fetch_big_int_as_text_without_64_bit_support() {
+        let quirks = Quirks::from_driver_name("SQORA32.DLL");
+        assert_eq!(
+            Some(BufferDesc::Text { max_str_len: 20 }),
+            quirks.buffer_desc(DataType::BigInt, true)
+        );
+        assert_eq!(
+            Some(BufferDesc::I64 { nullable: true }),
+            Quirks::new().buffer_desc(DataType::BigInt, true)
+        );
+    }
+
+    #[test]
+    fn unbounded_sql_server_text() {
+        let quirks = Quirks::from_driver_name("libmsodbcsql-18.0.so.1.1");
+        assert_eq!(
+            Some(BufferDesc::Text {
+                max_str_len: TextRowSet::LONG_TEXT_LIMIT
+            }),
+            quirks.buffer_desc(DataType::Varchar { length: 0 }, true)
+        );
+        assert_eq!(
+            Some(BufferDesc::Text { max_str_len: 10 }),
+            quirks.buffer_desc(DataType::Varchar { length: 10 }, true)
+        );
+    }
+
+    #[test]
+    fn narrow_text_without_wide_support() {
+        let quirks = Quirks {
+            no_wide_text: true,
+            ..Quirks::new()
+        };
+        assert_eq!(TextBinding::Narrow, quirks.text_binding(TextBinding::Wide));
+        assert_eq!(
+            TextBinding::Wide,
+            Quirks::new().text_binding(TextBinding::Wide)
+        );
+    }
+}
@@ -0,0 +1,299 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    buffers::TextRowSet, parameter::VarCharSlice, Connection, Cursor, Error, IntoParameter,
+};
+
+/// Upper bound for the length of individual values fetched by [`ResultCache::fetch`]. Longer
+/// values cause an error.
+const MAX_STR_LEN: usize = 4096;
+
+/// Caches small, materialized result sets of read only queries, keyed by the server and database
+/// queried, the statement text and its parameters. Intended for lookup tables, which are queried
+/// over and over again, e.g. by reporting services. Requires the `result_cache` feature.
+///
+/// Entries expire after a fixed time to live, so changes to the underlying tables become visible
+/// eventually. The cache does not know which tables a query reads, so it must only be used for
+/// data which may be stale for that long. Result sets with more than [`ResultCache::max_rows`]
+/// rows are not cached. Only their first rows are returned, see [`CachedResult::complete`].
+///
+/// The cache is not bound to a connection and can be shared between threads, e.g. behind an
+/// [`Arc`].
+///
+/// ```no_run
+/// use odbc_api::{Connection, Error, ResultCache};
+/// use std::time::Duration;
+///
+/// fn country_name(
+///     cache: &ResultCache,
+///     conn: &Connection<'_>,
+///     code: &str,
+/// ) -> Result<Option<String>, Error> {
+///     let result = cache
+///         .fetch(conn, "SELECT name FROM Countries WHERE code = ?", &[Some(code)])?
+///         .expect("SELECT statement must return a result set.");
+///     Ok(result.rows.first().and_then(|row| row[0].clone()))
+/// }
+///
+/// let cache = ResultCache::new(Duration::from_secs(300));
+/// ```
+#[derive(Debug)]
+pub struct ResultCache {
+    time_to_live: Duration,
+    max_rows: usize,
+    entries: Mutex<HashMap<CacheKey, Entry>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    /// Name of the server, as reported by the driver.
+    server: String,
+    /// Catalog (i.e. database) the connection is using.
+    catalog: String,
+    sql: String,
+    params: Vec<Option<String>>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    fetched_at: Instant,
+    result: Arc<CachedResult>,
+}
+
+/// A result set materialized as text by [`ResultCache::fetch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedResult {
+    /// Names of the columns in the result set, in order.
+    pub column_names: Vec<String>,
+    /// Rows of the result set. Each row holds one element for each column. `NULL` is represented
+    /// as `None`. Bytes which are not valid UTF-8 are replaced with `U+FFFD`.
+    pub rows: Vec<Vec<Option<String>>>,
+    /// `false` if the result set had more than [`ResultCache::max_rows`] rows. In this case `rows`
+    /// only holds the first `max_rows` rows and the result has not been cached.
+    pub complete: bool,
+}
+
+impl ResultCache {
+    /// Entries expire `time_to_live` after they have been fetched. Caches result sets with up to
+    /// `1000` rows. Use [`Self::with_max_rows`] to change this.
+    pub fn new(time_to_live: Duration) -> Self {
+        ResultCache {
+            time_to_live,
+            max_rows: 1000,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Result sets with more rows than `max_rows` are not cached.
+    pub fn with_max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = max_rows;
+        self
+    }
+
+    /// Maximum number of rows in a result set, for it to be cached.
+    pub fn max_rows(&self) -> usize {
+        self.max_rows
+    }
+
+    /// Returns the cached result set of `sql` executed with `params`, unless it is missing or
+    /// expired. In that case `sql` is executed on `conn` and the result set fetched as text.
+    ///
+    /// Parameters are bound as text and are part of the key, so identical statements with
+    /// different parameters are cached separately. `None` is bound as `NULL`. The server name and
+    /// the current catalog of `conn` are part of the key as well, so connections to different
+    /// databases do not share entries.
+    ///
+    /// # Return
+    ///
+    /// `None` if the statement did not create a result set. These outcomes are not cached. Values
+    /// longer than 4096 bytes are not truncated, but cause an error.
+    pub fn fetch(
+        &self,
+        conn: &Connection<'_>,
+        sql: &str,
+        params: &[Option<&str>],
+    ) -> Result<Option<Arc<CachedResult>>, Error> {
+        let key = CacheKey {
+            server: conn.server_name()?,
+            catalog: conn.current_catalog()?,
+            sql: sql.to_owned(),
+            params: params
+                .iter()
+                .map(|param| param.map(str::to_owned))
+                .collect(),
+        };
+        if let Some(result) = self.lookup(&key, Instant::now()) {
+            return Ok(Some(result));
+        }
+        let parameters: Vec<VarCharSlice<'_>> =
+            params.iter().map(|&param| param.into_parameter()).collect();
+        let cursor = if let Some(cursor) = conn.execute(sql, &parameters[..])? {
+            cursor
+        } else {
+            return Ok(None);
+        };
+        let result = Arc::new(materialize(cursor, self.max_rows)?);
+        if result.complete {
+            self.insert(key, result.clone(), Instant::now());
+        }
+        Ok(Some(result))
+    }
+
+    /// Removes all entries, e.g. after the cached tables have been modified.
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    /// Number of cached result sets, including expired ones, which have not been removed yet.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// `true` if no result set is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lookup(&self, key: &CacheKey, now: Instant) -> Option<Arc<CachedResult>> {
+        let entries = self.entries.lock().unwrap();
+        let entry = entries.get(key)?;
+        (now.duration_since(entry.fetched_at) < self.time_to_live).then(|| entry.result.clone())
+    }
+
+    fn insert(&self, key: CacheKey, result: Arc<CachedResult>, now: Instant) {
+        let mut entries = self.entries.lock().unwrap();
+        // Expired entries would be replaced on their next lookup anyway. Dropping them here keeps
+        // the cache from growing with keys which are never looked up again.
+        entries.retain(|_, entry| now.duration_since(entry.fetched_at) < self.time_to_live);
+        entries.insert(
+            key,
+            Entry {
+                fetched_at: now,
+                result,
+            },
+        );
+    }
+}
+
+/// Fetches up to `max_rows` rows of `cursor` as text.
+fn materialize(mut cursor: impl Cursor, max_rows: usize) -> Result<CachedResult, Error> {
+    let column_names = cursor.column_names()?.collect::<Result<Vec<_>, _>>()?;
+    // One additional row tells us whether the result set is larger than `max_rows`.
+    let batch_size = max_rows.saturating_add(1).min(1000);
+    let buffer = TextRowSet::for_cursor(batch_size, &mut cursor, Some(MAX_STR_LEN))?;
+    let mut block_cursor = cursor.bind_buffer(buffer)?;
+    let mut rows = Vec::new();
+    while let Some(batch) = block_cursor.fetch_with_truncation_check(true)? {
+        for row_index in 0..batch.num_rows() {
+            if rows.len() == max_rows {
+                return Ok(CachedResult {
+                    column_names,
+                    rows,
+                    complete: false,
+                });
+            }
+            let row = (0..batch.num_cols())
+                .map(|col_index| {
+                    batch
+                        .at(col_index, row_index)
+                        .map(|text| String::from_utf8_lossy(text).into_owned())
+                })
+                .collect();
+            rows.push(row);
+        }
+    }
+    Ok(CachedResult {
+        column_names,
+        rows,
+        complete: true,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::Arc,
+        time::{Duration, Instant},
+    };
+
+    use super::{CacheKey, CachedResult, ResultCache};
+
+    fn result(value: &str) -> Arc<CachedResult> {
+        Arc::new(CachedResult {
+            column_names: vec!["a".to_owned()],
+            rows: vec![vec![Some(value.to_owned())]],
+            complete: true,
+        })
+    }
+
+    fn key(catalog: &str, sql: &str, params: &[Option<&str>]) -> CacheKey {
+        CacheKey {
+            server: "localhost".to_owned(),
+            catalog: catalog.to_owned(),
+            sql: sql.to_owned(),
+            params: params
+                .iter()
+                .map(|param| param.map(str::to_owned))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn entries_expire_after_time_to_live() {
+        let cache = ResultCache::new(Duration::from_secs(10));
+        let key = key("test", "SELECT a FROM t WHERE b = ?", &[Some("1")]);
+        let start = Instant::now();
+
+        cache.insert(key.clone(), result("x"), start);
+
+        assert_eq!(
+            Some(result("x")),
+            cache.lookup(&key, start + Duration::from_secs(9))
+        );
+        assert_eq!(None, cache.lookup(&key, start + Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn parameters_are_part_of_the_key() {
+        let cache = ResultCache::new(Duration::from_secs(10));
+        let sql = "SELECT a FROM t WHERE b = ?";
+        let now = Instant::now();
+
+        cache.insert(key("test", sql, &[Some("1")]), result("x"), now);
+
+        assert_eq!(None, cache.lookup(&key("test", sql, &[None]), now));
+        assert_eq!(None, cache.lookup(&key("test", sql, &[Some("2")]), now));
+    }
+
+    #[test]
+    fn catalog_is_part_of_the_key() {
+        let cache = ResultCache::new(Duration::from_secs(10));
+        let now = Instant::now();
+
+        cache.insert(key("test", "SELECT a FROM t", &[]), result("x"), now);
+
+        assert_eq!(
+            None,
+            cache.lookup(&key("other", "SELECT a FROM t", &[]), now)
+        );
+    }
+
+    #[test]
+    fn inserting_drops_expired_entries() {
+        let cache = ResultCache::new(Duration::from_secs(10));
+        let start = Instant::now();
+
+        cache.insert(key("test", "SELECT 1", &[]), result("1"), start);
+        cache.insert(
+            key("test", "SELECT 2", &[]),
+            result("2"),
+            start + Duration::from_secs(20),
+        );
+
+        assert_eq!(1, cache.len());
+    }
+}
@@ -2,7 +2,7 @@ use odbc_sys::SqlDataType;
 
 use crate::{
     handles::{slice_to_utf8, AsStatementRef, SqlChar, Statement},
-    ColumnDescription, DataType, Error,
+    ColumnDescription, DataType, Error, OtherDataType, Quirks,
 };
 
 /// Provides Metadata of the resulting the result set. Implemented by `Cursor` types and prepared
@@ -12,6 +12,14 @@ use crate::{
 /// See also:
 /// <https://docs.microsoft.com/en-us/sql/odbc/reference/develop-app/result-set-metadata>
 pub trait ResultSetMetadata: AsStatementRef {
+    /// Known quirks of the driver which created the result set. Buffer sizes derived from the
+    /// metadata (e.g. by [`crate::buffers::TextRowSet::for_cursor`]) are adjusted to them. Types
+    /// created by a [`crate::Connection`] report the quirks of that connection, see
+    /// [`crate::Connection::quirks`]. The default implementation assumes no quirks.
+    fn quirks(&self) -> Quirks {
+        Quirks::new()
+    }
+
     /// Fetch a column description using the column index.
     ///
     /// # Parameters
@@ -94,6 +102,17 @@ pub trait ResultSetMetadata: AsStatementRef {
         Ok(slice_to_utf8(&buf).unwrap())
     }
 
+    /// Data source dependent name of the type of the column, e.g. `geometry` or `NVARCHAR`.
+    ///
+    /// `column_number`: Index of the column, starting at 1.
+    fn col_type_name(&mut self, column_number: u16) -> Result<String, Error> {
+        let stmt = self.as_stmt_ref();
+        let mut buf = vec![0; 128];
+        stmt.col_type_name(column_number, &mut buf)
+            .into_result(&stmt)?;
+        Ok(slice_to_utf8(&buf).unwrap())
+    }
+
     /// Use this if you want to iterate over all column names and allocate a `String` for each one.
     ///
     /// This is a wrapper around `col_name` introduced for convenience.
@@ -101,6 +120,32 @@ pub trait ResultSetMetadata: AsStatementRef {
         ColumnNamesIt::new(self)
     }
 
+    /// Index (starting at 1) of the first column named `name`. Names are compared using the
+    /// [`crate::ColumnNameMatcher`] of the quirks of the result set, so by default case and
+    /// surrounding whitespace are ignored. `None` if no column matches.
+    ///
+    /// ```
+    /// use odbc_api::{Cursor, Error, ResultSetMetadata};
+    ///
+    /// fn customer_ids(cursor: &mut impl Cursor) -> Result<u16, Error> {
+    ///     let index = cursor.col_index("customer_id")?.expect("Column customer_id is missing.");
+    ///     Ok(index)
+    /// }
+    /// ```
+    fn col_index(&mut self, name: &str) -> Result<Option<u16>, Error>
+    where
+        Self: Sized,
+    {
+        let matcher = self.quirks().column_name_matcher;
+        let key = matcher.key(name);
+        for (index, col_name) in self.column_names()?.enumerate() {
+            if matcher.key(&col_name?) == key {
+                return Ok(Some((index + 1).try_into().unwrap()));
+            }
+        }
+        Ok(None)
+    }
+
     /// Data type of the specified column.
     ///
     /// `column_number`: Index of the column, starting at 1.
@@ -127,6 +172,9 @@ pub trait ResultSetMetadata: AsStatementRef {
             SqlDataType::EXT_LONG_VARCHAR => DataType::LongVarchar {
                 length: self.col_display_size(column_number)?.try_into().unwrap(),
             },
+            SqlDataType::EXT_W_LONG_VARCHAR => DataType::WLongVarchar {
+                length: self.col_display_size(column_number)?.try_into().unwrap(),
+            },
             SqlDataType::CHAR => DataType::Char {
                 length: self.col_display_size(column_number)?.try_into().unwrap(),
             },
@@ -158,18 +206,42 @@ pub trait ResultSetMetadata: AsStatementRef {
             SqlDataType::EXT_BIG_INT => DataType::BigInt,
             SqlDataType::EXT_TINY_INT => DataType::TinyInt,
             SqlDataType::EXT_BIT => DataType::Bit,
-            other => {
+            // Types without a dedicated attribute to query their size, like `XML`, or unknown to
+            // this crate. `describe_col` reports column size and decimal digits for them.
+            _ => {
                 let mut column_description = ColumnDescription::default();
                 self.describe_col(column_number, &mut column_description)?;
-                DataType::Other {
-                    data_type: other,
-                    column_size: column_description.data_type.column_size(),
-                    decimal_digits: column_description.data_type.decimal_digits(),
-                }
+                column_description.data_type
             }
         };
         Ok(dt)
     }
+
+    /// Detailed description of a column with a driver specific type ([`DataType::Other`]),
+    /// including the name of the type and its size in characters and bytes. `None` if the column
+    /// has a type known to this crate.
+    ///
+    /// `column_number`: Index of the column, starting at 1.
+    fn col_other_data_type(&mut self, column_number: u16) -> Result<Option<OtherDataType>, Error> {
+        let (type_code, size, decimal_digits) = match self.col_data_type(column_number)? {
+            DataType::Other {
+                data_type,
+                column_size,
+                decimal_digits,
+            } => (data_type, column_size, decimal_digits),
+            _ => return Ok(None),
+        };
+        // Drivers report non positive values if the size is unknown.
+        let positive = |size: isize| usize::try_from(size).ok().filter(|&size| size != 0);
+        Ok(Some(OtherDataType {
+            type_code,
+            size,
+            decimal_digits,
+            name: self.col_type_name(column_number)?,
+            display_size: positive(self.col_display_size(column_number)?),
+            octet_length: positive(self.col_octet_length(column_number)?),
+        }))
+    }
 }
 
 /// Buffer sizes able to hold the display size of each column in utf-8 encoding. You may call this
@@ -0,0 +1,490 @@
+use std::{fmt, str::FromStr};
+
+use odbc_sys::{Date, Guid, Time, Timestamp};
+use thiserror::Error as ThisError;
+
+use crate::{
+    buffers::{CellValue, ColumnarAnyBuffer},
+    parameter::InputParameter,
+    IntoParameter, SqlDialect,
+};
+
+/// Extracts a table ordered by a unique key, in a way which allows to resume the extraction after
+/// an interruption, rather than restarting it. After each processed batch the application obtains
+/// a [`ResumeToken`] holding the key values of the last row. Should the extraction be interrupted,
+/// the query is continued from the row following the one described by the last persisted token.
+///
+/// Key columns must identify each row uniquely and must not be `NULL`, otherwise rows may be
+/// skipped.
+///
+/// ```no_run
+/// use odbc_api::{
+///     buffers::{BufferDesc, ColumnarAnyBuffer}, AnsiDialect, Connection, Cursor, Error,
+///     ResumableExtraction, ResumeToken,
+/// };
+///
+/// fn extract(
+///     conn: &Connection<'_>,
+///     checkpoint: Option<String>,
+///     mut save_checkpoint: impl FnMut(String),
+/// ) -> Result<(), Error> {
+///     let extraction = ResumableExtraction::new("Orders", &["id", "amount"], &["id"]);
+///     let token: Option<ResumeToken> =
+///         checkpoint.map(|text| text.parse().expect("Checkpoint must be a valid token"));
+///     let sql = extraction.sql(token.as_ref(), &AnsiDialect);
+///     let parameters = extraction.parameters(token.as_ref());
+///     let cursor = conn.execute(&sql, parameters.as_slice())?.unwrap();
+///     let buffer = ColumnarAnyBuffer::try_from_descs(
+///         1000,
+///         [BufferDesc::I64 { nullable: false }, BufferDesc::F64 { nullable: true }],
+///     )?;
+///     let mut block_cursor = cursor.bind_buffer(buffer)?;
+///     while let Some(batch) = block_cursor.fetch()? {
+///         // ... Process batch
+///         if let Some(token) = extraction.resume_token(batch) {
+///             save_checkpoint(token.to_string());
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResumableExtraction {
+    table_name: String,
+    column_names: Vec<String>,
+    /// Positions of the key columns within `column_names`.
+    key_indices: Vec<usize>,
+}
+
+impl ResumableExtraction {
+    /// Extraction of the columns `column_names` of the table `table_name`, ordered by the columns
+    /// `key_columns`. Earlier key columns take precedence in the ordering. Every key column must
+    /// also be one of the extracted columns, so the key values can be taken from the fetched
+    /// batches. `table_name` is inserted into the query as is, so it may be qualified with a
+    /// schema.
+    ///
+    /// # Panics
+    ///
+    /// If `key_columns` is empty, or holds a name which is not one of the `column_names`.
+    pub fn new(
+        table_name: &str,
+        column_names: &[impl AsRef<str>],
+        key_columns: &[impl AsRef<str>],
+    ) -> Self {
+        assert!(
+            !key_columns.is_empty(),
+            "A resumable extraction requires at least one key column."
+        );
+        let column_names: Vec<String> = column_names
+            .iter()
+            .map(|name| name.as_ref().to_owned())
+            .collect();
+        let key_indices = key_columns
+            .iter()
+            .map(|key| {
+                let key = key.as_ref();
+                column_names
+                    .iter()
+                    .position(|name| name == key)
+                    .unwrap_or_else(|| {
+                        panic!("Key column {key} must be one of the extracted columns.")
+                    })
+            })
+            .collect();
+        Self {
+            table_name: table_name.to_owned(),
+            column_names,
+            key_indices,
+        }
+    }
+
+    /// Query extracting the rows following the one described by `resume`, or all rows if `resume`
+    /// is `None`. Bind the parameters returned by [`Self::parameters`] to execute it. Columns of
+    /// the result set are in the order passed to [`Self::new`].
+    ///
+    /// The condition is spelled out, rather than using a row value comparison, since not every
+    /// data source supports the latter. E.g. for the keys `a` and `b`:
+    ///
+    /// ```
+    /// use odbc_api::{AnsiDialect, ResumableExtraction, ResumeToken, buffers::CellValue};
+    ///
+    /// let extraction = ResumableExtraction::new("t", &["a", "b", "c"], &["a", "b"]);
+    /// let token = ResumeToken::new(vec![CellValue::I64(1), CellValue::I64(2)]);
+    ///
+    /// assert_eq!(
+    ///     r#"SELECT "a", "b", "c" FROM t WHERE ("a" > ?) OR ("a" = ? AND "b" > ?) ORDER BY "a", "b""#,
+    ///     extraction.sql(Some(&token), &AnsiDialect)
+    /// );
+    /// ```
+    pub fn sql(&self, resume: Option<&ResumeToken>, dialect: &impl SqlDialect) -> String {
+        let quote = |index: &usize| dialect.quote_identifier(&self.column_names[*index]);
+        let columns: Vec<_> = self
+            .column_names
+            .iter()
+            .map(|name| dialect.quote_identifier(name))
+            .collect();
+        let keys: Vec<_> = self.key_indices.iter().map(quote).collect();
+        let mut sql = format!("SELECT {} FROM {}", columns.join(", "), self.table_name);
+        if resume.is_some() {
+            let terms: Vec<_> = (0..keys.len())
+                .map(|term| {
+                    let mut comparisons: Vec<_> = keys[..term]
+                        .iter()
+                        .map(|key| format!("{key} = ?"))
+                        .collect();
+                    comparisons.push(format!("{} > ?", keys[term]));
+                    format!("({})", comparisons.join(" AND "))
+                })
+                .collect();
+            sql.push_str(" WHERE ");
+            sql.push_str(&terms.join(" OR "));
+        }
+        sql.push_str(" ORDER BY ");
+        sql.push_str(&keys.join(", "));
+        sql
+    }
+
+    /// Parameters for the query returned by [`Self::sql`]. Empty if `resume` is `None`.
+    ///
+    /// # Panics
+    ///
+    /// If the number of key values in `resume` does not match the number of key columns.
+    pub fn parameters(&self, resume: Option<&ResumeToken>) -> Vec<Box<dyn InputParameter>> {
+        let Some(resume) = resume else {
+            return Vec::new();
+        };
+        assert_eq!(
+            self.key_indices.len(),
+            resume.key_values.len(),
+            "Resume token must hold one value for each key column."
+        );
+        (0..resume.key_values.len())
+            .flat_map(|term| resume.key_values[..=term].iter())
+            .map(|value| value.clone().into_parameter())
+            .collect()
+    }
+
+    /// Token describing the last row of `batch`. `None` if `batch` is empty. The columns of
+    /// `batch` are expected to be in the order passed to [`Self::new`].
+    pub fn resume_token(&self, batch: &ColumnarAnyBuffer) -> Option<ResumeToken> {
+        let last = batch.num_rows().checked_sub(1)?;
+        let key_values = self
+            .key_indices
+            .iter()
+            .map(|&index| batch.column(index).cell(last))
+            .collect();
+        Some(ResumeToken { key_values })
+    }
+}
+
+/// Key values of the last row processed by a [`ResumableExtraction`]. Its text representation
+/// (see [`fmt::Display`] and [`FromStr`]) is intended to be persisted as a checkpoint. It holds
+/// only ASCII characters without whitespace, so it can be stored in most places.
+///
+/// ```
+/// use odbc_api::{ResumeToken, buffers::CellValue};
+///
+/// let token = ResumeToken::new(vec![CellValue::I64(42), CellValue::Text(b"abc".to_vec())]);
+/// let text = token.to_string();
+/// assert_eq!(token, text.parse().unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResumeToken {
+    key_values: Vec<CellValue>,
+}
+
+impl ResumeToken {
+    /// Token for the row with `key_values`, in the order of the key columns.
+    pub fn new(key_values: Vec<CellValue>) -> Self {
+        Self { key_values }
+    }
+
+    /// Values of the key columns, in the order of the key columns.
+    pub fn key_values(&self) -> &[CellValue] {
+        &self.key_values
+    }
+}
+
+/// Each value is written as a tag, followed by a colon and the payload. Values are separated by
+/// commas. Variable length data is hex encoded, so it can not be mistaken for a separator.
+impl fmt::Display for ResumeToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, value) in self.key_values.iter().enumerate() {
+            if index != 0 {
+                f.write_str(",")?;
+            }
+            match value {
+                CellValue::Null => f.write_str("null")?,
+                CellValue::Text(bytes) => write_hex(f, "text", bytes)?,
+                CellValue::WText(units) => {
+                    let bytes: Vec<u8> = units.iter().flat_map(|u| u.to_be_bytes()).collect();
+                    write_hex(f, "wtext", &bytes)?
+                }
+                CellValue::Binary(bytes) => write_hex(f, "bin", bytes)?,
+                CellValue::I64(i) => write!(f, "int:{i}")?,
+                // Bit pattern, so the value survives the round trip exactly.
+                CellValue::F64(float) => write!(f, "float:{:x}", float.to_bits())?,
+                CellValue::Decimal { value, scale } => write!(f, "dec:{value}.{scale}")?,
+                CellValue::Date(d) => write!(f, "date:{}.{}.{}", d.year, d.month, d.day)?,
+                CellValue::Time(t) => write!(f, "time:{}.{}.{}", t.hour, t.minute, t.second)?,
+                CellValue::Timestamp(ts) => write!(
+                    f,
+                    "ts:{}.{}.{}.{}.{}.{}.{}",
+                    ts.year, ts.month, ts.day, ts.hour, ts.minute, ts.second, ts.fraction
+                )?,
+                CellValue::Bit(b) => write!(f, "bit:{}", *b as u8)?,
+                CellValue::Guid(g) => {
+                    let mut bytes = Vec::with_capacity(16);
+                    bytes.extend(g.d1.to_be_bytes());
+                    bytes.extend(g.d2.to_be_bytes());
+                    bytes.extend(g.d3.to_be_bytes());
+                    bytes.extend(g.d4);
+                    write_hex(f, "guid", &bytes)?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The text could not be parsed into a [`ResumeToken`].
+#[derive(Debug, ThisError, Clone, PartialEq, Eq)]
+#[error("Invalid resume token. Can not parse key value: '{value}'")]
+pub struct InvalidResumeToken {
+    /// Text representation of the first key value, which could not be parsed.
+    pub value: String,
+}
+
+impl FromStr for ResumeToken {
+    type Err = InvalidResumeToken;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let key_values = s
+            .split(',')
+            .map(|value| {
+                parse_value(value).ok_or_else(|| InvalidResumeToken {
+                    value: value.to_owned(),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { key_values })
+    }
+}
+
+fn write_hex(f: &mut fmt::Formatter<'_>, tag: &str, bytes: &[u8]) -> fmt::Result {
+    write!(f, "{tag}:")?;
+    for byte in bytes {
+        write!(f, "{byte:02x}")?;
+    }
+    Ok(())
+}
+
+fn parse_hex(hex: &str) -> Option<Vec<u8>> {
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| match pair {
+            &[high, low] => Some(hex_digit(high)? << 4 | hex_digit(low)?),
+            _ => None,
+        })
+        .collect()
+}
+
+fn hex_digit(digit: u8) -> Option<u8> {
+    (digit as char).to_digit(16).map(|d| d as u8)
+}
+
+/// Parses dot separated numbers, e.g. `2021.3.20`, into an array of `N` numbers.
+fn parse_fields<T: FromStr + Default + Copy, const N: usize>(text: &str) -> Option<[T; N]> {
+    let mut fields = [T::default(); N];
+    let mut parts = text.split('.');
+    for field in &mut fields {
+        *field = parts.next()?.parse().ok()?;
+    }
+    parts.next().is_none().then_some(fields)
+}
+
+fn parse_value(text: &str) -> Option<CellValue> {
+    if text == "null" {
+        return Some(CellValue::Null);
+    }
+    let (tag, payload) = text.split_once(':')?;
+    let value = match tag {
+        "text" => CellValue::Text(parse_hex(payload)?),
+        "wtext" => {
+            let bytes = parse_hex(payload)?;
+            let pairs = bytes.chunks_exact(2);
+            if !pairs.remainder().is_empty() {
+                return None;
+            }
+            let units = pairs
+                .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                .collect();
+            CellValue::WText(units)
+        }
+        "bin" => CellValue::Binary(parse_hex(payload)?),
+        "int" => CellValue::I64(payload.parse().ok()?),
+        "float" => CellValue::F64(f64::from_bits(u64::from_str_radix(payload, 16).ok()?)),
+        "dec" => {
+            let (value, scale) = payload.rsplit_once('.')?;
+            CellValue::Decimal {
+                value: value.parse().ok()?,
+                scale: scale.parse().ok()?,
+            }
+        }
+        "date" => {
+            let [year, month, day] = parse_fields::<i16, 3>(payload)?;
+            CellValue::Date(Date {
+                year,
+                month: month.try_into().ok()?,
+                day: day.try_into().ok()?,
+            })
+        }
+        "time" => {
+            let [hour, minute, second] = parse_fields::<u16, 3>(payload)?;
+            CellValue::Time(Time {
+                hour,
+                minute,
+                second,
+            })
+        }
+        "ts" => {
+            let [year, month, day, hour, minute, second, fraction] =
+                parse_fields::<i64, 7>(payload)?;
+            CellValue::Timestamp(Timestamp {
+                year: year.try_into().ok()?,
+                month: month.try_into().ok()?,
+                day: day.try_into().ok()?,
+                hour: hour.try_into().ok()?,
+                minute: minute.try_into().ok()?,
+                second: second.try_into().ok()?,
+                fraction: fraction.try_into().ok()?,
+            })
+        }
+        "bit" => match payload {
+            "0" => CellValue::Bit(false),
+            "1" => CellValue::Bit(true),
+            _ => return None,
+        },
+        "guid" => {
+            let bytes = parse_hex(payload)?;
+            if bytes.len() != 16 {
+                return None;
+            }
+            CellValue::Guid(Guid {
+                d1: u32::from_be_bytes(bytes[0..4].try_into().unwrap()),
+                d2: u16::from_be_bytes([bytes[4], bytes[5]]),
+                d3: u16::from_be_bytes([bytes[6], bytes[7]]),
+                d4: bytes[8..16].try_into().unwrap(),
+            })
+        }
+        _ => return None,
+    };
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use odbc_sys::{Date, Guid, Time, Timestamp};
+
+    use crate::{
+        buffers::{AnyBuffer, CellValue, ColumnarAnyBuffer},
+        AnsiDialect,
+    };
+
+    use super::{InvalidResumeToken, ResumableExtraction, ResumeToken};
+
+    #[test]
+    fn initial_query_has_no_condition() {
+        let extraction = ResumableExtraction::new("t", &["a", "b"], &["b"]);
+
+        assert_eq!(
+            r#"SELECT "a", "b" FROM t ORDER BY "b""#,
+            extraction.sql(None, &AnsiDialect)
+        );
+        assert!(extraction.parameters(None).is_empty());
+    }
+
+    #[test]
+    fn one_parameter_for_each_comparison() {
+        let extraction = ResumableExtraction::new("t", &["a", "b"], &["a", "b"]);
+        let token = ResumeToken::new(vec![CellValue::I64(1), CellValue::I64(2)]);
+
+        assert_eq!(3, extraction.parameters(Some(&token)).len());
+    }
+
+    #[test]
+    #[should_panic]
+    fn key_must_be_extracted() {
+        ResumableExtraction::new("t", &["a"], &["b"]);
+    }
+
+    #[test]
+    fn token_from_last_row_of_batch() {
+        let mut batch = ColumnarAnyBuffer::new(vec![(1, AnyBuffer::I64(vec![10, 11, 12]))]);
+        let extraction = ResumableExtraction::new("t", &["id"], &["id"]);
+
+        assert_eq!(None, extraction.resume_token(&batch));
+        batch.set_num_rows(3);
+        assert_eq!(
+            Some(ResumeToken::new(vec![CellValue::I64(12)])),
+            extraction.resume_token(&batch)
+        );
+    }
+
+    #[test]
+    fn token_text_round_trip() {
+        let token = ResumeToken::new(vec![
+            CellValue::Null,
+            CellValue::Text(b"a,b:c".to_vec()),
+            CellValue::WText("Grüße".encode_utf16().collect()),
+            CellValue::Binary(vec![0, 255]),
+            CellValue::I64(-42),
+            CellValue::F64(0.1),
+            CellValue::Decimal {
+                value: -1234,
+                scale: 2,
+            },
+            CellValue::Date(Date {
+                year: 2021,
+                month: 3,
+                day: 20,
+            }),
+            CellValue::Time(Time {
+                hour: 15,
+                minute: 24,
+                second: 12,
+            }),
+            CellValue::Timestamp(Timestamp {
+                year: 2021,
+                month: 3,
+                day: 20,
+                hour: 15,
+                minute: 24,
+                second: 12,
+                fraction: 123,
+            }),
+            CellValue::Bit(true),
+            CellValue::Guid(Guid {
+                d1: 1,
+                d2: 2,
+                d3: 3,
+                d4: [4, 5, 6, 7, 8, 9, 10, 11],
+            }),
+        ]);
+
+        let text = token.to_string();
+
+        assert!(text.is_ascii() && !text.contains(char::is_whitespace));
+        assert_eq!(token, text.parse().unwrap());
+    }
+
+    #[test]
+    fn reject_invalid_token() {
+        assert_eq!(
+            Err(InvalidResumeToken {
+                value: "int:x".to_owned()
+            }),
+            "int:1,int:x".parse::<ResumeToken>()
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use std::{
+    collections::VecDeque,
+    future::{poll_fn, Future},
+    marker::PhantomData,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use crate::{
+    buffers::{ColumnarAnyBuffer, Row, RowTransform},
+    handles::AsStatementRef,
+    BlockCursorPolling, Error, Sleep,
+};
+
+/// Asynchronous stream of the rows of a result set, created with
+/// [`BlockCursorPolling::into_stream`]. Each row is copied out of the bound buffer and converted
+/// into `T`.
+///
+/// Rows are fetched in batches, but the next batch is only fetched once every row of the previous
+/// one has been taken from the stream. A slow consumer therefore causes the stream to stop fetching,
+/// rather than buffering the result set in memory.
+///
+/// With the `stream` feature, `RowStream` implements `futures_core::Stream`, so stream combinators
+/// and sinks of the `futures` crate can be used. Otherwise use [`Self::next`], which mirrors
+/// `StreamExt::next`.
+pub struct RowStream<C, S, T>
+where
+    C: AsStatementRef,
+    S: Sleep,
+{
+    block_cursor: BlockCursorPolling<C, ColumnarAnyBuffer>,
+    sleep: S,
+    /// Awaited before polling a fetch which has still been executing.
+    sleeping: Option<Pin<Box<S::Poll>>>,
+    /// Rows of the last batch, which have not been taken from the stream yet.
+    rows: VecDeque<Row>,
+    /// `true` once the result set is consumed, or fetching failed.
+    is_done: bool,
+    /// Rows for which the filter returns `false` are dropped, before they are copied out of the
+    /// bound buffer.
+    filter: Option<Box<RowFilter>>,
+    /// Applied to each row yielded, e.g. to mask sensitive values.
+    transform: RowTransform,
+    item: PhantomData<fn() -> T>,
+}
+
+/// Predicate deciding which rows of a batch are yielded by a [`RowStream`].
+type RowFilter = dyn FnMut(&ColumnarAnyBuffer, usize) -> bool + Send;
+
+impl<C, S, T> RowStream<C, S, T>
+where
+    C: AsStatementRef,
+    S: Sleep,
+    T: From<Row>,
+{
+    /// Next row of the result set. Fetches the next batch, if all rows of the previous one have
+    /// been returned. `None` once the result set is consumed. After an error has been returned,
+    /// the stream ends.
+    ///
+    /// The returned future is cancellation safe. Should it be dropped while a fetch is still in
+    /// progress, the next call continues it, so no rows are lost.
+    pub async fn next(&mut self) -> Option<Result<T, Error>> {
+        poll_fn(|cx| self.poll_next_row(cx)).await
+    }
+
+    fn poll_next_row(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<T, Error>>> {
+        // Every row of a batch may be dropped by the filter, so we may need to fetch several.
+        while self.rows.is_empty() && !self.is_done {
+            if let Some(sleeping) = &mut self.sleeping {
+                ready!(sleeping.as_mut().poll(cx));
+                self.sleeping = None;
+            }
+            match self.block_cursor.poll_fetch(false) {
+                Ok(Poll::Pending) => self.sleeping = Some(Box::pin(self.sleep.next_poll())),
+                Ok(Poll::Ready(Some(batch))) => match &mut self.filter {
+                    Some(filter) => self.rows.extend(batch.rows_where(filter)),
+                    None => self.rows.extend(batch.rows()),
+                },
+                Ok(Poll::Ready(None)) => self.is_done = true,
+                Err(error) => {
+                    self.is_done = true;
+                    return Poll::Ready(Some(Err(error)));
+                }
+            }
+        }
+        Poll::Ready(
+            self.rows
+                .pop_front()
+                .map(|row| Ok(self.transform.apply(row).into())),
+        )
+    }
+
+    /// Only yields rows for which `filter` returns `true`. `filter` is called with the bound
+    /// buffer and the zero based index of the row within the current batch, see
+    /// [`ColumnarAnyBuffer::rows_where`]. Replaces any previous filter.
+    pub fn with_filter(
+        mut self,
+        filter: impl FnMut(&ColumnarAnyBuffer, usize) -> bool + Send + 'static,
+    ) -> Self {
+        self.filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Applies `transform` to each row, before it is converted into `T`. Obtain it from
+    /// [`crate::buffers::CellTransforms::for_result_set`]. Rows dropped by the filter are not
+    /// transformed. Replaces any previous transformation.
+    pub fn with_transform(mut self, transform: RowTransform) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// Stops streaming and returns the block cursor. Rows of the current batch which have not
+    /// been taken from the stream yet are discarded.
+    pub fn into_block_cursor(self) -> BlockCursorPolling<C, ColumnarAnyBuffer> {
+        self.block_cursor
+    }
+}
+
+#[cfg(feature = "stream")]
+impl<C, S, T> futures_core::Stream for RowStream<C, S, T>
+where
+    C: AsStatementRef + Unpin,
+    S: Sleep + Unpin,
+    T: From<Row>,
+{
+    type Item = Result<T, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_next_row(cx)
+    }
+}
+
+impl<C> BlockCursorPolling<C, ColumnarAnyBuffer>
+where
+    C: AsStatementRef,
+{
+    /// Turns the cursor into an asynchronous stream of rows, each converted into `T`. Use
+    /// [`Row`] itself for `T` to obtain the values of the cells without conversion.
+    ///
+    /// ```no_run
+    /// use odbc_api::{buffers::{BufferDesc, ColumnarAnyBuffer, Row}, Connection, Error};
+    /// use std::time::Duration;
+    ///
+    /// async fn count_rows(conn: &Connection<'_>) -> Result<usize, Error> {
+    ///     let sleep = || tokio::time::sleep(Duration::from_millis(10));
+    ///     let cursor = conn
+    ///         .execute_polling("SELECT id, name FROM Users", (), sleep)
+    ///         .await?
+    ///         .expect("SELECT statement must yield a result set.");
+    ///     let buffer = ColumnarAnyBuffer::try_from_descs(100, [
+    ///         BufferDesc::I32 { nullable: false },
+    ///         BufferDesc::Text { max_str_len: 50 },
+    ///     ])?;
+    ///     let mut rows = cursor.bind_buffer(buffer)?.into_stream::<Row>(sleep);
+    ///     let mut num_rows = 0;
+    ///     while let Some(row) = rows.next().await {
+    ///         let _row = row?;
+    ///         num_rows += 1;
+    ///     }
+    ///     Ok(num_rows)
+    /// }
+    /// ```
+    pub fn into_stream<T>(self, sleep: impl Sleep) -> RowStream<C, impl Sleep, T>
+    where
+        T: From<Row>,
+    {
+        RowStream {
+            block_cursor: self,
+            sleep,
+            sleeping: None,
+            rows: VecDeque::new(),
+            is_done: false,
+            filter: None,
+            transform: RowTransform::default(),
+            item: PhantomData,
+        }
+    }
+}
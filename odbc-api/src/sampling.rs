@@ -0,0 +1,166 @@
+/// Fetch only a subset of the row sets of a result set. Intended for profiling and statistics
+/// tools, which need a representative sample of a huge table, rather than all of its rows. Set it
+/// using [`crate::BlockCursor::set_sampling`].
+///
+/// Skipped row sets are jumped over using a scrollable cursor, if the statement has been executed
+/// with one (see [`crate::Preallocated::set_cursor_type`]). This saves transferring the skipped
+/// rows. Forward only cursors still fetch the skipped row sets, but do not return them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Sampling {
+    /// Returns the first row set and every `n`th after that. `EveryNthBatch(1)` returns all row
+    /// sets.
+    EveryNthBatch(usize),
+    /// Returns each row set with a probability of `probability`, until `max_rows` rows have been
+    /// returned. The last row set returned is truncated to not exceed `max_rows`. Identical seeds
+    /// yield identical samples for identical result sets.
+    Random {
+        /// Probability with which each row set is part of the sample. Must be greater than zero
+        /// and at most one.
+        probability: f64,
+        /// Upper bound for the number of rows in the sample.
+        max_rows: usize,
+        /// Seed of the pseudo random number generator.
+        seed: u64,
+    },
+}
+
+/// Decides which row sets are part of the sample.
+pub(crate) struct Sampler {
+    sampling: Sampling,
+    /// State of the xorshift pseudo random number generator.
+    rng_state: u64,
+    /// Rows returned as part of the sample so far.
+    num_rows: usize,
+    /// `true` then the next row set would be the first one of the result set.
+    at_start: bool,
+}
+
+impl Sampler {
+    /// # Panics
+    ///
+    /// If the sampling parameters are out of range.
+    pub fn new(sampling: Sampling) -> Self {
+        let rng_state = match sampling {
+            Sampling::EveryNthBatch(n) => {
+                assert!(n != 0, "Sampling every 0th row set is not possible.");
+                0
+            }
+            Sampling::Random {
+                probability, seed, ..
+            } => {
+                assert!(
+                    probability > 0. && probability <= 1.,
+                    "Sampling probability must be within (0, 1], but is {probability}."
+                );
+                // Xorshift must not be seeded with zero, it would only ever yield zeroes.
+                if seed == 0 {
+                    0x9E37_79B9_7F4A_7C15
+                } else {
+                    seed
+                }
+            }
+        };
+        Self {
+            sampling,
+            rng_state,
+            num_rows: 0,
+            at_start: true,
+        }
+    }
+
+    /// Maximum number of rows which may still be returned as part of the sample. `None` if
+    /// unbounded.
+    pub fn remaining_rows(&self) -> Option<usize> {
+        match self.sampling {
+            Sampling::EveryNthBatch(_) => None,
+            Sampling::Random { max_rows, .. } => Some(max_rows - self.num_rows),
+        }
+    }
+
+    /// Number of row sets to skip, before fetching the next one, which is part of the sample.
+    pub fn batches_to_skip(&mut self) -> usize {
+        let at_start = self.at_start;
+        self.at_start = false;
+        match self.sampling {
+            Sampling::EveryNthBatch(n) => {
+                if at_start {
+                    0
+                } else {
+                    n - 1
+                }
+            }
+            Sampling::Random { probability, .. } => {
+                if probability == 1. {
+                    return 0;
+                }
+                // The number of row sets skipped before the next one is sampled follows a
+                // geometric distribution.
+                let uniform = self.next_uniform();
+                (uniform.ln() / (1. - probability).ln()) as usize
+            }
+        }
+    }
+
+    /// Account for `num_rows` rows returned as part of the sample.
+    pub fn record_rows(&mut self, num_rows: usize) {
+        self.num_rows += num_rows;
+    }
+
+    /// Uniformly distributed pseudo random number within (0, 1].
+    fn next_uniform(&mut self) -> f64 {
+        // xorshift64*
+        self.rng_state ^= self.rng_state >> 12;
+        self.rng_state ^= self.rng_state << 25;
+        self.rng_state ^= self.rng_state >> 27;
+        let random = self.rng_state.wrapping_mul(0x2545_F491_4F6C_DD1D);
+        // Use the upper 53 bits, which fit into the mantissa of an f64.
+        ((random >> 11) + 1) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Sampler, Sampling};
+
+    #[test]
+    fn every_nth_batch_starts_with_first() {
+        let mut sampler = Sampler::new(Sampling::EveryNthBatch(3));
+
+        assert_eq!(0, sampler.batches_to_skip());
+        assert_eq!(2, sampler.batches_to_skip());
+        assert_eq!(2, sampler.batches_to_skip());
+        assert_eq!(None, sampler.remaining_rows());
+    }
+
+    #[test]
+    fn random_sampling_is_reproducible_and_bounded() {
+        let sampling = Sampling::Random {
+            probability: 0.1,
+            max_rows: 100,
+            seed: 42,
+        };
+        let mut first = Sampler::new(sampling);
+        let mut second = Sampler::new(sampling);
+        let skips: Vec<_> = (0..10).map(|_| first.batches_to_skip()).collect();
+        let same_skips: Vec<_> = (0..10).map(|_| second.batches_to_skip()).collect();
+        assert_eq!(skips, same_skips);
+        // With a probability of 10%, we expect about 9 skipped row sets for each sampled one.
+        let total: usize = skips.iter().sum();
+        assert!(total > 20 && total < 500, "Total skipped: {total}");
+
+        first.record_rows(60);
+        assert_eq!(Some(40), first.remaining_rows());
+    }
+
+    #[test]
+    fn probability_of_one_samples_everything() {
+        let mut sampler = Sampler::new(Sampling::Random {
+            probability: 1.,
+            max_rows: 10,
+            seed: 0,
+        });
+
+        assert_eq!(0, sampler.batches_to_skip());
+        assert_eq!(0, sampler.batches_to_skip());
+    }
+}
@@ -0,0 +1,569 @@
+//! Capture the tables, columns and primary keys of a data source using the catalog functions and
+//! compare them. Useful to verify that a migration produced the same schema in different
+//! environments.
+//!
+//! ```no_run
+//! use odbc_api::{schema::{schema_snapshot, SchemaFilter}, Environment};
+//!
+//! let env = Environment::new()?;
+//! let staging = env.connect("Staging", "SA", "My@Test@Password1")?;
+//! let production = env.connect("Production", "SA", "My@Test@Password1")?;
+//! let filter = SchemaFilter {
+//!     schema: "dbo".to_owned(),
+//!     ..SchemaFilter::default()
+//! };
+//! let expected = schema_snapshot(&staging, &filter)?;
+//! let actual = schema_snapshot(&production, &filter)?;
+//! for change in expected.diff(&actual) {
+//!     println!("{change}");
+//! }
+//! # Ok::<(), odbc_api::Error>(())
+//! ```
+
+use std::{collections::HashMap, fmt};
+
+use crate::{Connection, Cursor, CursorRow, Error, Nullability, Nullable};
+
+/// Selects the tables captured by [`schema_snapshot`]. Each field is a search pattern, as used by
+/// [`Connection::tables`]. `%` matches any number of characters, `_` exactly one. Empty strings
+/// (the default) do not restrict the selection.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaFilter {
+    pub catalog: String,
+    pub schema: String,
+    pub table: String,
+}
+
+/// Tables, columns and primary keys of a data source at a point in time. Created by
+/// [`schema_snapshot`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaSnapshot {
+    /// Ordered by schema and table name.
+    pub tables: Vec<TableSnapshot>,
+}
+
+/// A single table within a [`SchemaSnapshot`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableSnapshot {
+    /// `None` if the data source does not support catalogs.
+    pub catalog: Option<String>,
+    /// `None` if the data source does not support schemas.
+    pub schema: Option<String>,
+    pub name: String,
+    /// Ordered by their position within the table.
+    pub columns: Vec<ColumnSnapshot>,
+    /// Names of the columns making up the primary key, in key order. Empty if the table has no
+    /// primary key.
+    pub primary_key: Vec<String>,
+}
+
+impl TableSnapshot {
+    /// Table name qualified with the schema name, if any. E.g. `dbo.Birthdays`.
+    pub fn qualified_name(&self) -> String {
+        match &self.schema {
+            Some(schema) if !schema.is_empty() => format!("{schema}.{}", self.name),
+            _ => self.name.clone(),
+        }
+    }
+
+    /// Tables of different environments are matched by schema and name. Catalogs usually differ
+    /// between environments, as they often correspond to database names.
+    fn key(&self) -> (Option<&str>, &str) {
+        (self.schema.as_deref(), &self.name)
+    }
+}
+
+/// A single column within a [`TableSnapshot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnSnapshot {
+    pub name: String,
+    /// SQL data type as reported in the `DATA_TYPE` column of [`Connection::columns`].
+    pub data_type: i16,
+    /// Data source dependent name of the type, e.g. `VARCHAR` or `int identity`.
+    pub type_name: String,
+    /// Column size, e.g. the maximum length of strings or the precision of numbers. `None` if not
+    /// applicable.
+    pub column_size: Option<i32>,
+    /// Scale of numbers or precision of fractional seconds. `None` if not applicable.
+    pub decimal_digits: Option<i16>,
+    pub nullability: Nullability,
+}
+
+/// A difference between two [`SchemaSnapshot`]s. See [`SchemaSnapshot::diff`]. Tables are
+/// identified by their qualified name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaChange {
+    TableAdded {
+        table: String,
+    },
+    TableRemoved {
+        table: String,
+    },
+    ColumnAdded {
+        table: String,
+        column: ColumnSnapshot,
+    },
+    ColumnRemoved {
+        table: String,
+        column: ColumnSnapshot,
+    },
+    /// A column with the same name differs in type, size or nullability.
+    ColumnChanged {
+        table: String,
+        old: ColumnSnapshot,
+        new: ColumnSnapshot,
+    },
+    PrimaryKeyChanged {
+        table: String,
+        old: Vec<String>,
+        new: Vec<String>,
+    },
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaChange::TableAdded { table } => write!(f, "Table {table} has been added."),
+            SchemaChange::TableRemoved { table } => write!(f, "Table {table} has been removed."),
+            SchemaChange::ColumnAdded { table, column } => {
+                write!(f, "Column {table}.{} has been added.", column.name)
+            }
+            SchemaChange::ColumnRemoved { table, column } => {
+                write!(f, "Column {table}.{} has been removed.", column.name)
+            }
+            SchemaChange::ColumnChanged { table, old, new } => write!(
+                f,
+                "Column {table}.{} changed from {}({:?}, {:?}) {:?} to {}({:?}, {:?}) {:?}.",
+                old.name,
+                old.type_name,
+                old.column_size,
+                old.decimal_digits,
+                old.nullability,
+                new.type_name,
+                new.column_size,
+                new.decimal_digits,
+                new.nullability
+            ),
+            SchemaChange::PrimaryKeyChanged { table, old, new } => write!(
+                f,
+                "Primary key of {table} changed from ({}) to ({}).",
+                old.join(", "),
+                new.join(", ")
+            ),
+        }
+    }
+}
+
+impl SchemaSnapshot {
+    /// Lists everything which needs to change in `self` to obtain `newer`. Tables are matched by
+    /// schema and name, columns by name. Changes in column order are not reported.
+    pub fn diff(&self, newer: &SchemaSnapshot) -> Vec<SchemaChange> {
+        let old_tables: HashMap<_, _> = self.tables.iter().map(|t| (t.key(), t)).collect();
+        let new_tables: HashMap<_, _> = newer.tables.iter().map(|t| (t.key(), t)).collect();
+        let mut changes = Vec::new();
+        for old in &self.tables {
+            if !new_tables.contains_key(&old.key()) {
+                changes.push(SchemaChange::TableRemoved {
+                    table: old.qualified_name(),
+                });
+            }
+        }
+        for new in &newer.tables {
+            match old_tables.get(&new.key()) {
+                None => changes.push(SchemaChange::TableAdded {
+                    table: new.qualified_name(),
+                }),
+                Some(old) => diff_tables(old, new, &mut changes),
+            }
+        }
+        changes
+    }
+}
+
+fn diff_tables(old: &TableSnapshot, new: &TableSnapshot, changes: &mut Vec<SchemaChange>) {
+    let table = new.qualified_name();
+    let find = |columns: &'_ [ColumnSnapshot], name: &str| -> Option<ColumnSnapshot> {
+        columns.iter().find(|c| c.name == name).cloned()
+    };
+    for old_column in &old.columns {
+        match find(&new.columns, &old_column.name) {
+            None => changes.push(SchemaChange::ColumnRemoved {
+                table: table.clone(),
+                column: old_column.clone(),
+            }),
+            Some(new_column) if new_column != *old_column => {
+                changes.push(SchemaChange::ColumnChanged {
+                    table: table.clone(),
+                    old: old_column.clone(),
+                    new: new_column,
+                })
+            }
+            Some(_) => (),
+        }
+    }
+    for new_column in &new.columns {
+        if find(&old.columns, &new_column.name).is_none() {
+            changes.push(SchemaChange::ColumnAdded {
+                table: table.clone(),
+                column: new_column.clone(),
+            });
+        }
+    }
+    if old.primary_key != new.primary_key {
+        changes.push(SchemaChange::PrimaryKeyChanged {
+            table,
+            old: old.primary_key.clone(),
+            new: new.primary_key.clone(),
+        });
+    }
+}
+
+/// Captures tables, columns and primary keys of all tables (but not views) matching `filter`.
+/// Issues one catalog query for the tables, one for the columns and one per table for its primary
+/// key.
+pub fn schema_snapshot(
+    conn: &Connection<'_>,
+    filter: &SchemaFilter,
+) -> Result<SchemaSnapshot, Error> {
+    let mut buf = Vec::new();
+
+    // Tables. Columns of the result set: TABLE_CAT, TABLE_SCHEM, TABLE_NAME, TABLE_TYPE, REMARKS
+    let mut tables = Vec::new();
+    let mut cursor = conn.tables(&filter.catalog, &filter.schema, &filter.table, "TABLE")?;
+    while let Some(mut row) = cursor.next_row()? {
+        tables.push(TableSnapshot {
+            catalog: text(&mut row, 1, &mut buf)?,
+            schema: text(&mut row, 2, &mut buf)?,
+            name: text(&mut row, 3, &mut buf)?.unwrap_or_default(),
+            columns: Vec::new(),
+            primary_key: Vec::new(),
+        });
+    }
+    drop(cursor);
+
+    // Columns of all tables at once. Columns belonging to views are skipped, since we only listed
+    // tables.
+    let index_by_table: HashMap<_, _> = tables
+        .iter()
+        .enumerate()
+        .map(|(index, t)| ((t.catalog.clone(), t.schema.clone(), t.name.clone()), index))
+        .collect();
+    let mut positioned_columns = vec![Vec::new(); tables.len()];
+    let mut cursor = conn.columns(&filter.catalog, &filter.schema, &filter.table, "")?;
+    while let Some(mut row) = cursor.next_row()? {
+        let table_key = (
+            text(&mut row, 1, &mut buf)?,
+            text(&mut row, 2, &mut buf)?,
+            text(&mut row, 3, &mut buf)?.unwrap_or_default(),
+        );
+        let Some(&table_index) = index_by_table.get(&table_key) else {
+            continue;
+        };
+        let name = text(&mut row, 4, &mut buf)?.unwrap_or_default();
+        let data_type = small_int(&mut row, 5)?.unwrap_or_default();
+        let type_name = text(&mut row, 6, &mut buf)?.unwrap_or_default();
+        let column_size = int(&mut row, 7)?;
+        let decimal_digits = small_int(&mut row, 9)?;
+        let nullability = match small_int(&mut row, 11)? {
+            Some(0) => Nullability::NoNulls,
+            Some(1) => Nullability::Nullable,
+            _ => Nullability::Unknown,
+        };
+        let ordinal_position = int(&mut row, 17)?.unwrap_or_default();
+        positioned_columns[table_index].push((
+            ordinal_position,
+            ColumnSnapshot {
+                name,
+                data_type,
+                type_name,
+                column_size,
+                decimal_digits,
+                nullability,
+            },
+        ));
+    }
+    drop(cursor);
+    for (table, mut columns) in tables.iter_mut().zip(positioned_columns) {
+        columns.sort_by_key(|(position, _)| *position);
+        table.columns = columns.into_iter().map(|(_, column)| column).collect();
+    }
+
+    // Primary keys. Columns of the result set: TABLE_CAT, TABLE_SCHEM, TABLE_NAME, COLUMN_NAME,
+    // KEY_SEQ, PK_NAME
+    for table in &mut tables {
+        let mut key_columns = Vec::new();
+        let mut cursor = conn.primary_keys(
+            table.catalog.as_deref().unwrap_or_default(),
+            table.schema.as_deref().unwrap_or_default(),
+            &table.name,
+        )?;
+        while let Some(mut row) = cursor.next_row()? {
+            let name = text(&mut row, 4, &mut buf)?.unwrap_or_default();
+            let key_sequence = small_int(&mut row, 5)?.unwrap_or_default();
+            key_columns.push((key_sequence, name));
+        }
+        key_columns.sort();
+        table.primary_key = key_columns.into_iter().map(|(_, name)| name).collect();
+    }
+
+    tables.sort_by(|a, b| a.key().cmp(&b.key()));
+    Ok(SchemaSnapshot { tables })
+}
+
+/// Snapshots can be persisted as JSON, so a schema captured in one environment can be compared
+/// against another one later. Requires the `json` feature.
+#[cfg(feature = "json")]
+impl SchemaSnapshot {
+    /// Represents the snapshot as a JSON value, e.g. to write it into a file using
+    /// `serde_json::to_writer_pretty`.
+    pub fn to_json(&self) -> serde_json::Value {
+        use serde_json::json;
+
+        let tables: Vec<_> = self
+            .tables
+            .iter()
+            .map(|table| {
+                let columns: Vec<_> = table
+                    .columns
+                    .iter()
+                    .map(|column| {
+                        json!({
+                            "name": column.name,
+                            "data_type": column.data_type,
+                            "type_name": column.type_name,
+                            "column_size": column.column_size,
+                            "decimal_digits": column.decimal_digits,
+                            "nullability": format!("{:?}", column.nullability),
+                        })
+                    })
+                    .collect();
+                json!({
+                    "catalog": table.catalog,
+                    "schema": table.schema,
+                    "name": table.name,
+                    "columns": columns,
+                    "primary_key": table.primary_key,
+                })
+            })
+            .collect();
+        json!({ "tables": tables })
+    }
+
+    /// Inverse of [`Self::to_json`]. `None` if `value` does not have the structure produced by
+    /// [`Self::to_json`].
+    pub fn from_json(value: &serde_json::Value) -> Option<Self> {
+        use serde_json::Value;
+
+        fn opt_str(value: &Value) -> Option<Option<String>> {
+            match value {
+                Value::Null => Some(None),
+                Value::String(text) => Some(Some(text.clone())),
+                _ => None,
+            }
+        }
+
+        fn opt_int(value: &Value) -> Option<Option<i64>> {
+            match value {
+                Value::Null => Some(None),
+                other => other.as_i64().map(Some),
+            }
+        }
+
+        let mut tables = Vec::new();
+        for table in value.get("tables")?.as_array()? {
+            let mut columns = Vec::new();
+            for column in table.get("columns")?.as_array()? {
+                let nullability = match column.get("nullability")?.as_str()? {
+                    "Nullable" => Nullability::Nullable,
+                    "NoNulls" => Nullability::NoNulls,
+                    "Unknown" => Nullability::Unknown,
+                    _ => return None,
+                };
+                columns.push(ColumnSnapshot {
+                    name: column.get("name")?.as_str()?.to_owned(),
+                    data_type: column.get("data_type")?.as_i64()?.try_into().ok()?,
+                    type_name: column.get("type_name")?.as_str()?.to_owned(),
+                    column_size: opt_int(column.get("column_size")?)?
+                        .map(TryInto::try_into)
+                        .transpose()
+                        .ok()?,
+                    decimal_digits: opt_int(column.get("decimal_digits")?)?
+                        .map(TryInto::try_into)
+                        .transpose()
+                        .ok()?,
+                    nullability,
+                });
+            }
+            let primary_key = table
+                .get("primary_key")?
+                .as_array()?
+                .iter()
+                .map(|name| name.as_str().map(str::to_owned))
+                .collect::<Option<_>>()?;
+            tables.push(TableSnapshot {
+                catalog: opt_str(table.get("catalog")?)?,
+                schema: opt_str(table.get("schema")?)?,
+                name: table.get("name")?.as_str()?.to_owned(),
+                columns,
+                primary_key,
+            });
+        }
+        Some(SchemaSnapshot { tables })
+    }
+}
+
+fn text(row: &mut CursorRow<'_>, col: u16, buf: &mut Vec<u8>) -> Result<Option<String>, Error> {
+    let is_not_null = row.get_text(col, buf)?;
+    Ok(is_not_null.then(|| String::from_utf8_lossy(buf).into_owned()))
+}
+
+fn small_int(row: &mut CursorRow<'_>, col: u16) -> Result<Option<i16>, Error> {
+    let mut value = Nullable::<i16>::null();
+    row.get_data(col, &mut value)?;
+    Ok(value.into_opt())
+}
+
+fn int(row: &mut CursorRow<'_>, col: u16) -> Result<Option<i32>, Error> {
+    let mut value = Nullable::<i32>::null();
+    row.get_data(col, &mut value)?;
+    Ok(value.into_opt())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Nullability;
+
+    use super::{ColumnSnapshot, SchemaChange, SchemaSnapshot, TableSnapshot};
+
+    fn column(name: &str, type_name: &str, nullability: Nullability) -> ColumnSnapshot {
+        ColumnSnapshot {
+            name: name.to_owned(),
+            data_type: 12,
+            type_name: type_name.to_owned(),
+            column_size: Some(10),
+            decimal_digits: None,
+            nullability,
+        }
+    }
+
+    fn table(name: &str, columns: Vec<ColumnSnapshot>, primary_key: &[&str]) -> TableSnapshot {
+        TableSnapshot {
+            catalog: Some("dev".to_owned()),
+            schema: Some("dbo".to_owned()),
+            name: name.to_owned(),
+            columns,
+            primary_key: primary_key.iter().map(|&s| s.to_owned()).collect(),
+        }
+    }
+
+    #[test]
+    fn identical_snapshots_have_no_changes() {
+        let snapshot = SchemaSnapshot {
+            tables: vec![table(
+                "Birthdays",
+                vec![column("name", "VARCHAR", Nullability::NoNulls)],
+                &["name"],
+            )],
+        };
+
+        assert!(snapshot.diff(&snapshot.clone()).is_empty());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_round_trip() {
+        let snapshot = SchemaSnapshot {
+            tables: vec![TableSnapshot {
+                catalog: None,
+                ..table(
+                    "Birthdays",
+                    vec![column("name", "VARCHAR", Nullability::NoNulls)],
+                    &["name"],
+                )
+            }],
+        };
+
+        let json = snapshot.to_json();
+
+        assert_eq!(Some(snapshot), SchemaSnapshot::from_json(&json));
+    }
+
+    #[test]
+    fn catalogs_are_ignored() {
+        let old = SchemaSnapshot {
+            tables: vec![table("Birthdays", Vec::new(), &[])],
+        };
+        let mut new = old.clone();
+        new.tables[0].catalog = Some("prod".to_owned());
+
+        assert!(old.diff(&new).is_empty());
+    }
+
+    #[test]
+    fn report_changed_tables_columns_and_keys() {
+        let old = SchemaSnapshot {
+            tables: vec![
+                table(
+                    "Birthdays",
+                    vec![
+                        column("id", "INTEGER", Nullability::NoNulls),
+                        column("name", "VARCHAR", Nullability::Nullable),
+                        column("remarks", "VARCHAR", Nullability::Nullable),
+                    ],
+                    &["id"],
+                ),
+                table("Obsolete", Vec::new(), &[]),
+            ],
+        };
+        let new = SchemaSnapshot {
+            tables: vec![
+                table(
+                    "Birthdays",
+                    vec![
+                        column("id", "INTEGER", Nullability::NoNulls),
+                        column("name", "VARCHAR", Nullability::NoNulls),
+                        column("birthday", "DATE", Nullability::Nullable),
+                    ],
+                    &["id", "name"],
+                ),
+                table("Fresh", Vec::new(), &[]),
+            ],
+        };
+
+        let changes = old.diff(&new);
+
+        assert_eq!(
+            vec![
+                SchemaChange::TableRemoved {
+                    table: "dbo.Obsolete".to_owned()
+                },
+                SchemaChange::ColumnChanged {
+                    table: "dbo.Birthdays".to_owned(),
+                    old: column("name", "VARCHAR", Nullability::Nullable),
+                    new: column("name", "VARCHAR", Nullability::NoNulls),
+                },
+                SchemaChange::ColumnRemoved {
+                    table: "dbo.Birthdays".to_owned(),
+                    column: column("remarks", "VARCHAR", Nullability::Nullable),
+                },
+                SchemaChange::ColumnAdded {
+                    table: "dbo.Birthdays".to_owned(),
+                    column: column("birthday", "DATE", Nullability::Nullable),
+                },
+                SchemaChange::PrimaryKeyChanged {
+                    table: "dbo.Birthdays".to_owned(),
+                    old: vec!["id".to_owned()],
+                    new: vec!["id".to_owned(), "name".to_owned()],
+                },
+                SchemaChange::TableAdded {
+                    table: "dbo.Fresh".to_owned()
+                },
+            ],
+            changes
+        );
+        assert_eq!(
+            "Primary key of dbo.Birthdays changed from (id) to (id, name).",
+            changes[4].to_string()
+        );
+    }
+}
@@ -0,0 +1,250 @@
+use std::{
+    cell::UnsafeCell,
+    future::Future,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+    sync::{Condvar, Mutex, MutexGuard},
+    task::{Context, Poll, Waker},
+    time::{Duration, Instant},
+};
+
+use crate::Connection;
+
+/// A connection which can be shared between threads or asynchronous tasks (e.g. in an `Arc`).
+/// Access to the connection is serialized. A caller checks out the connection with [`Self::lock`]
+/// or [`Self::lock_async`] and has exclusive access to it, until the returned guard is dropped.
+/// Since cursors and prepared statements borrow the guard, no other thread can use the connection
+/// while they are alive. Use [`crate::KeepAlive`] to keep the connection from timing out, while it
+/// is idle.
+///
+/// Waiting for the connection with [`Self::lock_async`] does not block the thread, and the guard
+/// may be held across `.await` points, e.g. while executing a statement in polling mode.
+///
+/// # Example
+///
+/// ```no_run
+/// use lazy_static::lazy_static;
+/// use odbc_api::{Environment, SharedConnection};
+/// use std::{sync::Arc, thread};
+///
+/// lazy_static! {
+///     static ref ENV: Environment = Environment::new().unwrap();
+/// }
+///
+/// let conn = ENV.connect("YourDatabase", "SA", "My@Test@Password1")?;
+/// let shared = Arc::new(unsafe { SharedConnection::new(conn) });
+///
+/// let handles: Vec<_> = (1..=3).map(|batch| {
+///     let shared = shared.clone();
+///     thread::spawn(move || {
+///         let conn = shared.lock();
+///         conn.execute("INSERT INTO Batches (id) VALUES (?)", &batch)?;
+///         Ok::<(), odbc_api::Error>(())
+///     })
+/// }).collect();
+/// for handle in handles {
+///     handle.join().unwrap()?;
+/// }
+/// # Ok::<(), odbc_api::Error>(())
+/// ```
+///
+/// Sharing a connection between asynchronous tasks:
+///
+/// ```no_run
+/// use odbc_api::{Error, SharedConnection};
+/// use std::time::Duration;
+///
+/// async fn insert_batch(shared: &SharedConnection<'_>, batch: i32) -> Result<(), Error> {
+///     let conn = shared.lock_async().await;
+///     let sleep = || tokio::time::sleep(Duration::from_millis(20));
+///     conn.execute_polling("INSERT INTO Batches (id) VALUES (?)", &batch, sleep)
+///         .await?;
+///     Ok(())
+/// }
+/// ```
+pub struct SharedConnection<'env> {
+    /// Wether the connection is checked out, and who is waiting for it. The mutex is only held to
+    /// change the state, never while the connection is used.
+    state: Mutex<LockState>,
+    /// Signaled then the connection is checked in, to wake threads blocked in [`Self::lock`].
+    checked_in: Condvar,
+    /// Only accessed by the holder of a [`SharedConnectionGuard`].
+    inner: UnsafeCell<Inner<'env>>,
+}
+
+struct Inner<'env> {
+    connection: Connection<'env>,
+    /// Point in time then the connection has been checked in the last time.
+    last_used: Instant,
+}
+
+#[derive(Default)]
+struct LockState {
+    checked_out: bool,
+    /// Tasks waiting in [`SharedConnection::lock_async`].
+    waiting_tasks: Vec<Waker>,
+}
+
+/// Safe: The connection is only ever accessed by one thread at a time. The remaining concern is
+/// wether the driver allows using one connection from different threads at all. The caller of
+/// [`SharedConnection::new`] vouches for that.
+unsafe impl Send for SharedConnection<'_> {}
+
+unsafe impl Sync for SharedConnection<'_> {}
+
+impl<'env> SharedConnection<'env> {
+    /// Wraps a connection, so it can be shared between threads.
+    ///
+    /// # Safety
+    ///
+    /// The driver must tolerate the connection to be used from different threads. See
+    /// [`Connection::promote_to_send`].
+    pub unsafe fn new(connection: Connection<'env>) -> Self {
+        Self {
+            state: Mutex::new(LockState::default()),
+            checked_in: Condvar::new(),
+            inner: UnsafeCell::new(Inner {
+                connection,
+                last_used: Instant::now(),
+            }),
+        }
+    }
+
+    /// Checks out the connection, blocking until no other thread uses it. Use
+    /// [`Self::lock_async`] in asynchronous code instead, in order not to block the executor.
+    ///
+    /// A thread panicking while holding the guard does not render the connection unusable. Keep
+    /// in mind though, that any transaction it started may still be open.
+    pub fn lock(&self) -> SharedConnectionGuard<'_, 'env> {
+        let mut state = self.state();
+        while state.checked_out {
+            state = self
+                .checked_in
+                .wait(state)
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+        state.checked_out = true;
+        SharedConnectionGuard { shared: self }
+    }
+
+    /// Checks out the connection, once no one else uses it. Waiting does not block the thread.
+    pub fn lock_async(&self) -> LockSharedConnection<'_, 'env> {
+        LockSharedConnection { shared: self }
+    }
+
+    /// Checks out the connection, if no other thread uses it at the moment. `None` otherwise.
+    pub fn try_lock(&self) -> Option<SharedConnectionGuard<'_, 'env>> {
+        self.try_check_out()
+            .then_some(SharedConnectionGuard { shared: self })
+    }
+
+    /// Checks out the connection, if it has not been used by anyone for at least `min_idle`.
+    pub(crate) fn try_lock_idle(
+        &self,
+        min_idle: Duration,
+    ) -> Option<SharedConnectionGuard<'_, 'env>> {
+        if !self.try_check_out() {
+            return None;
+        }
+        // Safe: We checked out the connection. Check before creating the `SharedConnectionGuard`.
+        // Dropping it would count as use.
+        if unsafe { (*self.inner.get()).last_used }.elapsed() < min_idle {
+            self.check_in();
+            return None;
+        }
+        Some(SharedConnectionGuard { shared: self })
+    }
+
+    /// Unwraps the connection.
+    pub fn into_inner(self) -> Connection<'env> {
+        self.inner.into_inner().connection
+    }
+
+    fn state(&self) -> MutexGuard<'_, LockState> {
+        // The state is consistent at all times, so we can ignore poisoning.
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// `true` if the connection has been checked out.
+    fn try_check_out(&self) -> bool {
+        let mut state = self.state();
+        if state.checked_out {
+            false
+        } else {
+            state.checked_out = true;
+            true
+        }
+    }
+
+    fn check_in(&self) {
+        let waiting_tasks = {
+            let mut state = self.state();
+            state.checked_out = false;
+            std::mem::take(&mut state.waiting_tasks)
+        };
+        self.checked_in.notify_one();
+        // Whoever polls first checks the connection out, the others register themselves again.
+        for task in waiting_tasks {
+            task.wake();
+        }
+    }
+}
+
+/// Future returned by [`SharedConnection::lock_async`].
+pub struct LockSharedConnection<'a, 'env> {
+    shared: &'a SharedConnection<'env>,
+}
+
+impl<'a, 'env> Future for LockSharedConnection<'a, 'env> {
+    type Output = SharedConnectionGuard<'a, 'env>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let shared = self.shared;
+        let mut state = shared.state();
+        if state.checked_out {
+            if !state
+                .waiting_tasks
+                .iter()
+                .any(|task| task.will_wake(cx.waker()))
+            {
+                state.waiting_tasks.push(cx.waker().clone());
+            }
+            Poll::Pending
+        } else {
+            state.checked_out = true;
+            Poll::Ready(SharedConnectionGuard { shared })
+        }
+    }
+}
+
+/// Exclusive access to the connection of a [`SharedConnection`]. Dereferences to [`Connection`].
+/// Other threads may use the connection, once this is dropped.
+pub struct SharedConnectionGuard<'a, 'env> {
+    shared: &'a SharedConnection<'env>,
+}
+
+impl<'env> Deref for SharedConnectionGuard<'_, 'env> {
+    type Target = Connection<'env>;
+
+    fn deref(&self) -> &Connection<'env> {
+        // Safe: The connection is checked out by this guard.
+        unsafe { &(*self.shared.inner.get()).connection }
+    }
+}
+
+impl<'env> DerefMut for SharedConnectionGuard<'_, 'env> {
+    fn deref_mut(&mut self) -> &mut Connection<'env> {
+        // Safe: The connection is checked out by this guard.
+        unsafe { &mut (*self.shared.inner.get()).connection }
+    }
+}
+
+impl Drop for SharedConnectionGuard<'_, '_> {
+    fn drop(&mut self) {
+        // Safe: The connection is still checked out by this guard.
+        unsafe { (*self.shared.inner.get()).last_used = Instant::now() };
+        self.shared.check_in();
+    }
+}
@@ -1,12 +1,19 @@
-use std::future::Future;
+use std::{cmp::min, future::Future, thread, time::Duration};
 
-use crate::handles::SqlResult;
+use log::warn;
+
+use crate::handles::{SqlResult, Statement};
 
 /// Governs the behaviour of of polling in async functions.
 ///
 /// There is a generic implementation for any function retuning a future. This allows e.g. to pass
 /// `|| tokio::time::sleep(Duration::from_millis(50))` to functions expecting sleep. That is if
 /// you use `tokio` as your async runtime, of course.
+///
+/// All futures polling ODBC functions in this crate are cancellation safe. Should such a future be
+/// dropped while the operation is still in progress, e.g. due to a timeout, `SQLCancel` is called
+/// on the statement and the canceled function is completed. Afterwards the statement can be used to
+/// execute further queries.
 pub trait Sleep {
     type Poll: Future;
 
@@ -26,15 +33,88 @@ where
     }
 }
 
-pub async fn wait_for<F, O>(mut f: F, sleep: &mut impl Sleep) -> SqlResult<O>
+/// Repeats `f` until it no longer returns [`SqlResult::StillExecuting`], awaiting `sleep` in
+/// between.
+///
+/// The returned future is cancellation safe. Should it be dropped, before the asynchronous
+/// operation completed (e.g. because it has been wrapped in a timeout), `SQLCancel` is called on
+/// `statement` once, and the canceled function is called again in order to complete it. Drivers
+/// usually acknowledge the cancellation right away, so this does not block the executor. Should a
+/// driver still report the function as executing, dropping the future waits until it is done, so
+/// the driver does not access any bound buffers or parameters after the future has been dropped.
+pub async fn wait_for<S, F, O>(statement: &mut S, f: F, sleep: &mut impl Sleep) -> SqlResult<O>
 where
-    F: FnMut() -> SqlResult<O>,
+    S: Statement,
+    F: FnMut(&mut S) -> SqlResult<O>,
 {
-    let mut ret = (f)();
+    let mut polling = CancelOnDrop {
+        statement,
+        f,
+        still_executing: true,
+    };
+    let mut ret = polling.poll();
     // Wait for operation to finish, using polling method
     while matches!(ret, SqlResult::StillExecuting) {
         sleep.next_poll().await;
-        ret = (f)();
+        ret = polling.poll();
     }
+    polling.still_executing = false;
     ret
 }
+
+/// Keeps track of wether an asynchronous operation on a statement is still in progress, so it can
+/// be canceled if the future driving it is dropped.
+struct CancelOnDrop<'a, S, F, O>
+where
+    S: Statement,
+    F: FnMut(&mut S) -> SqlResult<O>,
+{
+    statement: &'a mut S,
+    f: F,
+    still_executing: bool,
+}
+
+impl<'a, S, F, O> CancelOnDrop<'a, S, F, O>
+where
+    S: Statement,
+    F: FnMut(&mut S) -> SqlResult<O>,
+{
+    fn poll(&mut self) -> SqlResult<O> {
+        (self.f)(self.statement)
+    }
+}
+
+impl<'a, S, F, O> Drop for CancelOnDrop<'a, S, F, O>
+where
+    S: Statement,
+    F: FnMut(&mut S) -> SqlResult<O>,
+{
+    fn drop(&mut self) {
+        if !self.still_executing {
+            return;
+        }
+        // The future has been dropped mid poll.
+        cancel(self.statement, &mut self.f)
+    }
+}
+
+/// Cancels an asynchronous operation on `statement` which is still in progress, and calls `f`
+/// until it completes. `SQLCancel` is synchronous, once it returned the canceled function usually
+/// completes with SQLSTATE HY008 (Operation canceled) the next time it is called.
+pub(crate) fn cancel<S, O>(statement: &mut S, mut f: impl FnMut(&mut S) -> SqlResult<O>)
+where
+    S: Statement,
+{
+    let _ = statement.cancel();
+    if !matches!(f(statement), SqlResult::StillExecuting) {
+        return;
+    }
+    // Bound buffers and parameters may be freed after the drop, so we must not return before
+    // the driver is done with them. Back off rather than spinning, to keep the thread idle.
+    warn!("Driver did not acknowledge canceling an asynchronous operation. Waiting for it.");
+    let mut backoff = Duration::from_millis(1);
+    while matches!(f(statement), SqlResult::StillExecuting) {
+        thread::sleep(backoff);
+        backoff = min(backoff * 2, Duration::from_millis(100));
+    }
+}
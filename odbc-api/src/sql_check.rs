@@ -0,0 +1,32 @@
+use crate::{ColumnDescription, Connection, Error, ResultSetMetadata};
+
+/// Parameters and result set of a statement, as reported by the data source after preparing it.
+/// Returned by [`Connection::check_sql`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqlCheck {
+    /// Number of parameter markers (`?`) in the statement.
+    pub num_params: u16,
+    /// Description of each column in the result set, in order. Empty if the statement would not
+    /// produce a result set.
+    pub columns: Vec<ColumnDescription>,
+}
+
+pub(crate) fn check_sql(conn: &Connection<'_>, sql: &str) -> Result<SqlCheck, Error> {
+    let mut prepared = conn.prepare(sql)?;
+    let num_params = prepared.num_params()?.try_into().unwrap();
+    // Some drivers (e.g. Microsoft SQL Server with deferred prepare) only send the statement to
+    // the data source once metadata is requested. Asking for the number of result columns makes
+    // sure errors in the statement surface here.
+    let num_cols: u16 = prepared.num_result_cols()?.try_into().unwrap();
+    let columns = (1..=num_cols)
+        .map(|column_number| {
+            let mut description = ColumnDescription::default();
+            prepared.describe_col(column_number, &mut description)?;
+            Ok(description)
+        })
+        .collect::<Result<_, Error>>()?;
+    Ok(SqlCheck {
+        num_params,
+        columns,
+    })
+}
@@ -0,0 +1,118 @@
+use std::{
+    cell::RefCell,
+    ops::{Deref, DerefMut},
+};
+
+use odbc_sys::{HStmt, Handle, HandleType};
+
+use crate::{
+    handles::{drop_handle, AsStatementRef, Statement, StatementRef},
+    CursorSensitivity, CursorType, Preallocated,
+};
+
+/// Idle statement handles owned by a [`crate::Connection`]. Handles are returned to the pool then
+/// a [`PooledStatement`] is dropped and reused by the next call to
+/// [`crate::Connection::checkout_statement`].
+#[derive(Default)]
+pub(crate) struct StatementPool {
+    /// Invariant: Each handle is a valid statement handle allocated on the connection owning the
+    /// pool, without any buffers bound to it, without an open cursor, without a limit on the
+    /// number of rows returned and with default cursor type and sensitivity.
+    idle: RefCell<Vec<HStmt>>,
+}
+
+impl StatementPool {
+    /// Take an idle statement handle out of the pool, if any.
+    pub fn take(&self) -> Option<HStmt> {
+        self.idle.borrow_mut().pop()
+    }
+
+    /// Number of idle statement handles currently held by the pool.
+    pub fn len(&self) -> usize {
+        self.idle.borrow().len()
+    }
+
+    /// Frees all idle statement handles. Must be called before the owning connection is
+    /// disconnected.
+    pub fn clear(&self) {
+        for handle in self.idle.borrow_mut().drain(..) {
+            unsafe {
+                drop_handle(handle as Handle, HandleType::Stmt);
+            }
+        }
+    }
+}
+
+/// A [`Preallocated`] statement checked out from the statement pool of a [`crate::Connection`].
+/// Upon drop the statement handle is reset and returned to the pool instead of being freed. See
+/// [`crate::Connection::checkout_statement`].
+pub struct PooledStatement<'c> {
+    /// Always `Some`, except during drop.
+    statement: Option<Preallocated<'c>>,
+    pool: &'c StatementPool,
+}
+
+impl<'c> PooledStatement<'c> {
+    pub(crate) fn new(statement: Preallocated<'c>, pool: &'c StatementPool) -> Self {
+        Self {
+            statement: Some(statement),
+            pool,
+        }
+    }
+
+    /// Frees the statement handle, rather than returning it to the pool. Useful if the state of the
+    /// statement has been changed in a way which should not leak into future checkouts. E.g. by
+    /// setting statement attributes using the raw handle.
+    pub fn discard(mut self) {
+        // Dropping the preallocated statement frees the handle.
+        self.statement.take();
+    }
+}
+
+impl<'c> Deref for PooledStatement<'c> {
+    type Target = Preallocated<'c>;
+
+    fn deref(&self) -> &Preallocated<'c> {
+        self.statement.as_ref().unwrap()
+    }
+}
+
+impl<'c> DerefMut for PooledStatement<'c> {
+    fn deref_mut(&mut self) -> &mut Preallocated<'c> {
+        self.statement.as_mut().unwrap()
+    }
+}
+
+impl<'c> AsStatementRef for PooledStatement<'c> {
+    fn as_stmt_ref(&mut self) -> StatementRef<'_> {
+        self.statement.as_mut().unwrap().as_stmt_ref()
+    }
+}
+
+impl<'c> Drop for PooledStatement<'c> {
+    fn drop(&mut self) {
+        if let Some(preallocated) = self.statement.take() {
+            let restore_cursor_attributes = preallocated.cursor_attributes_changed();
+            let mut statement = preallocated.into_statement();
+            // Any cursor has already been closed, since cursors borrow the preallocated statement
+            // and close themselves on drop. We still need to make sure no stale pointers to bound
+            // buffers or parameters are passed on to the next user of the handle. Neither should a
+            // limit set with `Preallocated::set_max_rows` cut off the result sets of the next user,
+            // nor should it get a scrollable cursor without asking for one. Cursor attributes are
+            // only restored if they have been changed, since not every driver supports setting
+            // them.
+            let failed_reset = statement.unbind_cols().is_err()
+                || statement.reset_parameters().is_err()
+                || statement.set_max_rows(0).is_err()
+                || (restore_cursor_attributes
+                    && (statement.set_cursor_type(CursorType::ForwardOnly).is_err()
+                        || statement
+                            .set_cursor_sensitivity(CursorSensitivity::Unspecified)
+                            .is_err()));
+            if !failed_reset {
+                self.pool.idle.borrow_mut().push(statement.into_sys());
+            }
+            // Otherwise the statement is dropped and its handle freed.
+        }
+    }
+}
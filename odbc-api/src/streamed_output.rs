@@ -0,0 +1,188 @@
+use crate::{
+    handles::{AsStatementRef, CDataMut, SqlResult, Statement},
+    parameter::CElement,
+    CursorRow, Error, ParameterTupleElement,
+};
+
+/// Parameters accepted by [`crate::Preallocated::execute_with_output_streams`]. Implemented for
+/// tuples of [`StreamingTupleElement`]s. Streamed output parameters like
+/// [`crate::parameter::OutputStream`] can only be passed this way, since they require the
+/// application to retrieve their values after execution. Other ways to execute statements do not
+/// accept them.
+///
+/// # Safety
+///
+/// Parameters bound to the statement must remain valid for the lifetime of the instance.
+pub unsafe trait StreamingParameterCollection {
+    /// Bind the parameters to a statement.
+    ///
+    /// # Safety
+    ///
+    /// Since the parameters are now bound to `stmt` callers must take care that it is ensured that
+    /// they remain valid while they are used.
+    unsafe fn bind_parameters_to(&mut self, stmt: &mut impl Statement) -> Result<(), Error>;
+}
+
+/// Element of a tuple passed to [`crate::Preallocated::execute_with_output_streams`]. Either a
+/// [`ParameterTupleElement`], or a streamed output parameter.
+///
+/// # Safety
+///
+/// Parameters bound to the statement must remain valid for the lifetime of the instance.
+pub unsafe trait StreamingTupleElement {
+    /// Bind the parameter in question to a specific `parameter_number`.
+    ///
+    /// # Safety
+    ///
+    /// See [`ParameterTupleElement::bind_to`].
+    unsafe fn bind_to(
+        &mut self,
+        parameter_number: u16,
+        stmt: &mut impl Statement,
+    ) -> Result<(), Error>;
+}
+
+unsafe impl<T> StreamingTupleElement for T
+where
+    T: ParameterTupleElement,
+{
+    unsafe fn bind_to(
+        &mut self,
+        parameter_number: u16,
+        stmt: &mut impl Statement,
+    ) -> Result<(), Error> {
+        ParameterTupleElement::bind_to(self, parameter_number, stmt)
+    }
+}
+
+macro_rules! impl_bind_streaming_parameters {
+    ($offset:expr, $stmt:ident) => (
+        Ok(())
+    );
+    ($offset:expr, $stmt:ident $head:ident $($tail:ident)*) => (
+        {
+            StreamingTupleElement::bind_to($head, $offset+1, $stmt)?;
+            impl_bind_streaming_parameters!($offset+1, $stmt $($tail)*)
+        }
+    );
+}
+
+macro_rules! impl_streaming_parameters_for_tuple{
+    ($($t:ident)*) => (
+        #[allow(unused_parens)]
+        #[allow(unused_variables)]
+        #[allow(non_snake_case)]
+        unsafe impl<$($t:StreamingTupleElement,)*> StreamingParameterCollection for ($($t,)*)
+        {
+            unsafe fn bind_parameters_to(&mut self, stmt: &mut impl Statement) -> Result<(), Error> {
+                let ($($t,)*) = self;
+                impl_bind_streaming_parameters!(0, stmt $($t)*)
+            }
+        }
+    );
+}
+
+impl_streaming_parameters_for_tuple! {}
+impl_streaming_parameters_for_tuple! { A }
+impl_streaming_parameters_for_tuple! { A B }
+impl_streaming_parameters_for_tuple! { A B C }
+impl_streaming_parameters_for_tuple! { A B C D }
+impl_streaming_parameters_for_tuple! { A B C D E }
+impl_streaming_parameters_for_tuple! { A B C D E F }
+impl_streaming_parameters_for_tuple! { A B C D E F G }
+impl_streaming_parameters_for_tuple! { A B C D E F G H }
+impl_streaming_parameters_for_tuple! { A B C D E F G H I }
+impl_streaming_parameters_for_tuple! { A B C D E F G H I J }
+
+/// Values of streamed output parameters, which became available after executing a statement with
+/// [`crate::Preallocated::execute_with_output_streams`]. The driver decides in which order the
+/// values are made available.
+///
+/// Any values not retrieved are discarded then this is dropped.
+pub struct StreamedOutputs<S: AsStatementRef> {
+    statement: S,
+    /// `true` as long as there may be more streamed output parameters to retrieve.
+    available: bool,
+}
+
+impl<S> StreamedOutputs<S>
+where
+    S: AsStatementRef,
+{
+    pub(crate) fn new(statement: S, available: bool) -> Self {
+        Self {
+            statement,
+            available,
+        }
+    }
+
+    /// Advances to the next streamed output parameter, whose value is available. `None` if the
+    /// values of all streamed output parameters have been made available.
+    pub fn next_output(&mut self) -> Result<Option<StreamedOutput<'_>>, Error> {
+        if !self.available {
+            return Ok(None);
+        }
+        let mut stmt = self.statement.as_stmt_ref();
+        let token = stmt.output_param_data().into_result(&stmt)?;
+        if let Some(token) = token {
+            // [`crate::parameter::OutputStream`] binds the parameter number as token.
+            let parameter_number = token as usize as u16;
+            // Safe: The value of a streamed output parameter is available.
+            let row = unsafe { CursorRow::new(stmt) };
+            Ok(Some(StreamedOutput {
+                parameter_number,
+                row,
+            }))
+        } else {
+            self.available = false;
+            Ok(None)
+        }
+    }
+}
+
+impl<S> Drop for StreamedOutputs<S>
+where
+    S: AsStatementRef,
+{
+    fn drop(&mut self) {
+        if self.available {
+            // Skip any values the application is not interested in, so the statement can be
+            // executed again. Errors are ignored, since we can not report them in drop.
+            let mut stmt = self.statement.as_stmt_ref();
+            while let SqlResult::Success(Some(_)) | SqlResult::SuccessWithInfo(Some(_)) =
+                stmt.output_param_data()
+            {}
+        }
+    }
+}
+
+/// Value of a single streamed output parameter. See [`StreamedOutputs::next_output`].
+pub struct StreamedOutput<'s> {
+    parameter_number: u16,
+    row: CursorRow<'s>,
+}
+
+impl<'s> StreamedOutput<'s> {
+    /// One based index of the parameter within the statement.
+    pub fn parameter_number(&self) -> u16 {
+        self.parameter_number
+    }
+
+    /// Fills `target` with the next part of the value. Call it repeatedly to stream large values
+    /// in chunks, until the indicator of `target` no longer signals truncation.
+    pub fn get_data(&mut self, target: &mut (impl CElement + CDataMut)) -> Result<(), Error> {
+        self.row.get_data(self.parameter_number, target)
+    }
+
+    /// Retrieves the entire value as text and stores it in `buf`. Returns `false` if the value is
+    /// `NULL`.
+    pub fn get_text(&mut self, buf: &mut Vec<u8>) -> Result<bool, Error> {
+        self.row.get_text(self.parameter_number, buf)
+    }
+
+    /// Retrieves the entire value as binary and stores it in `buf`. Returns `false` if the value is
+    /// `NULL`.
+    pub fn get_binary(&mut self, buf: &mut Vec<u8>) -> Result<bool, Error> {
+        self.row.get_binary(self.parameter_number, buf)
+    }
+}
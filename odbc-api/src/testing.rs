@@ -0,0 +1,246 @@
+//! Harness for behaviour tests of crates built on top of `odbc-api`. Requires the `testing`
+//! feature.
+//!
+//! Tests are written against the [`TestDataSource`] trait, so they can run against an in-memory
+//! SQLite database ([`SqliteInMemory`]) on any machine with the SQLite ODBC driver installed, as
+//! well as against other data sources, without starting containers.
+//!
+//! ```no_run
+//! use odbc_api::{
+//!     testing::{SqliteInMemory, TestDataSource},
+//!     Environment,
+//! };
+//!
+//! #[test]
+//! fn read_your_own_writes() {
+//!     let env = Environment::new().unwrap();
+//!     let Some(sqlite) = SqliteInMemory::new(&env).unwrap() else {
+//!         eprintln!("SQLite ODBC driver not installed. Skipping test.");
+//!         return;
+//!     };
+//!     let (conn, table) = sqlite.given("Birthdays", &["VARCHAR(50)", "DATE"]).unwrap();
+//!
+//!     table
+//!         .insert(&conn, [[Some("Keanu Reeves"), Some("1964-09-02")], [Some("Brad Pitt"), None]])
+//!         .unwrap();
+//!
+//!     assert_eq!(
+//!         vec![
+//!             vec![Some("Keanu Reeves".to_owned()), Some("1964-09-02".to_owned())],
+//!             vec![Some("Brad Pitt".to_owned()), None],
+//!         ],
+//!         table.content(&conn).unwrap()
+//!     );
+//! }
+//! ```
+
+#[cfg(feature = "proptest")]
+mod roundtrip;
+
+#[cfg(feature = "proptest")]
+pub use self::roundtrip::{CaseLimits, ColumnType, RoundtripCase};
+
+use crate::{buffers::TextRowSet, Connection, Cursor, Environment, Error, InsertRowsOptions};
+
+/// Name of the SQLite ODBC driver, as registered by its installer.
+#[cfg(target_os = "windows")]
+pub const SQLITE_DRIVER: &str = "SQLite3 ODBC Driver";
+/// Name of the SQLite ODBC driver, as registered by its installer.
+#[cfg(not(target_os = "windows"))]
+pub const SQLITE_DRIVER: &str = "SQLite3";
+
+/// A data source behaviour tests can run against.
+pub trait TestDataSource {
+    /// Opens a new connection to the data source.
+    fn connect(&self) -> Result<Connection<'_>, Error>;
+
+    /// Type of the autoincrementing `id` column, which is added to each table created by
+    /// [`Self::given`] in order to preserve the insertion order.
+    fn index_type(&self) -> &str;
+
+    /// Opens a new connection and (re)creates an empty table named `table_name` with it. The
+    /// columns are named `a`, `b`, `c`, ... and have the types in `column_types`.
+    ///
+    /// In-memory databases only exist for the lifetime of the connection, so read and write the
+    /// table using the returned connection.
+    fn given(
+        &self,
+        table_name: &str,
+        column_types: &[&str],
+    ) -> Result<(Connection<'_>, TestTable), Error> {
+        let conn = self.connect()?;
+        let table = TestTable::new(table_name, column_types);
+        conn.execute(&table.sql_drop_if_exists(), ())?;
+        conn.execute(&table.sql_create_table(self.index_type()), ())?;
+        Ok((conn, table))
+    }
+}
+
+/// An in-memory SQLite database, accessed through the SQLite ODBC driver. Each connection has a
+/// database of its own, which is discarded once the connection is closed, so tests are isolated
+/// from each other and leave nothing behind.
+#[derive(Clone, Copy)]
+pub struct SqliteInMemory<'env> {
+    environment: &'env Environment,
+}
+
+impl<'env> SqliteInMemory<'env> {
+    /// `None` if the [`SQLITE_DRIVER`] is not installed, so tests can skip themselves on machines
+    /// without it.
+    pub fn new(environment: &'env Environment) -> Result<Option<Self>, Error> {
+        let installed = environment
+            .drivers()?
+            .iter()
+            .any(|driver| driver.description == SQLITE_DRIVER);
+        Ok(installed.then_some(Self { environment }))
+    }
+}
+
+impl TestDataSource for SqliteInMemory<'_> {
+    fn connect(&self) -> Result<Connection<'_>, Error> {
+        self.environment.connect_with_connection_string(&format!(
+            "Driver={{{SQLITE_DRIVER}}};Database=:memory:"
+        ))
+    }
+
+    fn index_type(&self) -> &str {
+        "INTEGER PRIMARY KEY AUTOINCREMENT"
+    }
+}
+
+/// Table created by [`TestDataSource::given`]. Builds statements for it and reads and writes its
+/// content as text, so tests can compare it with their expectations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestTable {
+    pub name: String,
+    pub column_types: Vec<String>,
+    pub column_names: Vec<String>,
+}
+
+impl TestTable {
+    /// Columns are named `a`, `b`, `c`, ...
+    ///
+    /// # Panics
+    ///
+    /// If there are more than 26 columns.
+    pub fn new(name: &str, column_types: &[&str]) -> Self {
+        assert!(
+            column_types.len() <= 26,
+            "Test tables have at most 26 columns."
+        );
+        let column_names = (b'a'..=b'z')
+            .take(column_types.len())
+            .map(|letter| char::from(letter).to_string())
+            .collect();
+        TestTable {
+            name: name.to_owned(),
+            column_types: column_types.iter().map(|&ty| ty.to_owned()).collect(),
+            column_names,
+        }
+    }
+
+    /// SQL statement text dropping the table, if it exists.
+    pub fn sql_drop_if_exists(&self) -> String {
+        format!("DROP TABLE IF EXISTS {}", self.name)
+    }
+
+    /// SQL statement text creating the table, with an additional `id` column of type
+    /// `index_type`.
+    pub fn sql_create_table(&self, index_type: &str) -> String {
+        let cols = self
+            .column_names
+            .iter()
+            .zip(&self.column_types)
+            .map(|(name, ty)| format!(", {name} {ty}"))
+            .collect::<String>();
+        format!("CREATE TABLE {} (id {index_type}{cols})", self.name)
+    }
+
+    /// SQL statement text selecting all columns, but `id`, in insertion order.
+    pub fn sql_all_ordered_by_id(&self) -> String {
+        format!(
+            "SELECT {} FROM {} ORDER BY id",
+            self.column_names.join(", "),
+            self.name
+        )
+    }
+
+    /// Parameterized statement text inserting one row.
+    pub fn sql_insert(&self) -> String {
+        let placeholders = vec!["?"; self.column_names.len()].join(", ");
+        format!(
+            "INSERT INTO {} ({}) VALUES ({placeholders})",
+            self.name,
+            self.column_names.join(", ")
+        )
+    }
+
+    /// Inserts `rows` of text values using [`Connection::insert_rows`]. `None` is inserted as
+    /// `NULL`.
+    pub fn insert<R, V>(
+        &self,
+        conn: &Connection<'_>,
+        rows: impl IntoIterator<Item = R>,
+    ) -> Result<usize, Error>
+    where
+        R: IntoIterator<Item = Option<V>>,
+        V: AsRef<[u8]>,
+    {
+        let column_names: Vec<_> = self.column_names.iter().map(String::as_str).collect();
+        conn.insert_rows(
+            &self.name,
+            &column_names,
+            rows,
+            &conn.dialect()?,
+            &InsertRowsOptions::default(),
+        )
+        .map_err(|error| error.source)
+    }
+
+    /// All rows of the table in insertion order, with each value fetched as text. `NULL` is
+    /// represented as `None`.
+    pub fn content(&self, conn: &Connection<'_>) -> Result<Vec<Vec<Option<String>>>, Error> {
+        let mut cursor = conn
+            .execute(&self.sql_all_ordered_by_id(), ())?
+            .expect("SELECT statement must return a result set.");
+        let buffer = TextRowSet::for_cursor(100, &mut cursor, Some(4096))?;
+        let mut block_cursor = cursor.bind_buffer(buffer)?;
+        let mut rows = Vec::new();
+        while let Some(batch) = block_cursor.fetch()? {
+            for row_index in 0..batch.num_rows() {
+                let row = (0..batch.num_cols())
+                    .map(|col_index| {
+                        batch
+                            .at(col_index, row_index)
+                            .map(|bytes| String::from_utf8_lossy(bytes).into_owned())
+                    })
+                    .collect();
+                rows.push(row);
+            }
+        }
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TestTable;
+
+    #[test]
+    fn statements_for_test_table() {
+        let table = TestTable::new("People", &["VARCHAR(50)", "INTEGER"]);
+
+        assert_eq!(
+            "CREATE TABLE People (id INTEGER PRIMARY KEY, a VARCHAR(50), b INTEGER)",
+            table.sql_create_table("INTEGER PRIMARY KEY")
+        );
+        assert_eq!(
+            "SELECT a, b FROM People ORDER BY id",
+            table.sql_all_ordered_by_id()
+        );
+        assert_eq!(
+            "INSERT INTO People (a, b) VALUES (?, ?)",
+            table.sql_insert()
+        );
+    }
+}
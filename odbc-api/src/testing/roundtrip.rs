@@ -0,0 +1,207 @@
+use proptest::{
+    arbitrary::Arbitrary,
+    collection::vec,
+    option,
+    prelude::{prop_oneof, BoxedStrategy, Just, Strategy},
+    string::string_regex,
+};
+
+use crate::Error;
+
+use super::TestDataSource;
+
+/// Type of a column in a [`RoundtripCase`]. All types are supported by the common data sources
+/// and roundtrip losslessly as text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    SmallInt,
+    Integer,
+    BigInt,
+    /// Variadic text with up to `n` characters.
+    VarChar(usize),
+}
+
+impl ColumnType {
+    /// Name of the type in `CREATE TABLE` statements.
+    pub fn sql_type(&self) -> String {
+        match self {
+            ColumnType::SmallInt => "SMALLINT".to_owned(),
+            ColumnType::Integer => "INTEGER".to_owned(),
+            ColumnType::BigInt => "BIGINT".to_owned(),
+            ColumnType::VarChar(n) => format!("VARCHAR({n})"),
+        }
+    }
+}
+
+/// A table schema together with rows to insert into it. Values are represented as text, `None`
+/// represents `NULL`.
+///
+/// Implements [`Arbitrary`], so `proptest` generates random cases and shrinks failing ones to a
+/// minimal example. Use [`proptest::arbitrary::any_with`] together with [`CaseLimits`] to change
+/// the size of the generated cases.
+///
+/// ```no_run
+/// use odbc_api::{
+///     testing::{RoundtripCase, SqliteInMemory},
+///     Environment,
+/// };
+/// use proptest::{prelude::any, test_runner::TestRunner};
+///
+/// let env = Environment::new().unwrap();
+/// let sqlite = SqliteInMemory::new(&env).unwrap().unwrap();
+/// // Deterministic runner, so the same cases are generated each time.
+/// let mut runner = TestRunner::deterministic();
+/// runner
+///     .run(&any::<RoundtripCase>(), |case| {
+///         case.assert_roundtrip(&sqlite, "Roundtrip").unwrap();
+///         Ok(())
+///     })
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoundtripCase {
+    /// Types of the columns, in order.
+    pub column_types: Vec<ColumnType>,
+    /// Each row holds one value for each column.
+    pub rows: Vec<Vec<Option<String>>>,
+}
+
+impl RoundtripCase {
+    /// Creates a table named `table_name` using [`TestDataSource::given`], inserts the rows and
+    /// fetches them again.
+    ///
+    /// # Panics
+    ///
+    /// If the fetched rows differ from the inserted ones. The message contains the case, so it can
+    /// be reproduced.
+    pub fn assert_roundtrip(
+        &self,
+        source: &impl TestDataSource,
+        table_name: &str,
+    ) -> Result<(), Error> {
+        let column_types: Vec<_> = self.column_types.iter().map(ColumnType::sql_type).collect();
+        let column_types: Vec<_> = column_types.iter().map(String::as_str).collect();
+        let (conn, table) = source.given(table_name, &column_types)?;
+        table.insert(
+            &conn,
+            self.rows.iter().map(|row| row.iter().map(Option::as_deref)),
+        )?;
+        let fetched = table.content(&conn)?;
+        assert_eq!(
+            self.rows, fetched,
+            "Fetched rows differ from inserted ones for case: {self:?}"
+        );
+        Ok(())
+    }
+}
+
+/// Upper bounds for the size of [`RoundtripCase`]s generated by `proptest`. Pass them to
+/// [`proptest::arbitrary::any_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CaseLimits {
+    /// Upper bound for the number of columns. At least one column is generated.
+    pub max_columns: usize,
+    /// Upper bound for the number of rows.
+    pub max_rows: usize,
+    /// Upper bound for the length of `VARCHAR` columns.
+    pub max_str_len: usize,
+}
+
+impl Default for CaseLimits {
+    /// Up to 5 columns and 20 rows. Text columns hold up to 20 characters.
+    fn default() -> Self {
+        Self {
+            max_columns: 5,
+            max_rows: 20,
+            max_str_len: 20,
+        }
+    }
+}
+
+impl Arbitrary for ColumnType {
+    /// Upper bound for the length of `VARCHAR` columns.
+    type Parameters = usize;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(max_str_len: usize) -> Self::Strategy {
+        prop_oneof![
+            Just(ColumnType::SmallInt),
+            Just(ColumnType::Integer),
+            Just(ColumnType::BigInt),
+            (1..=max_str_len.max(1)).prop_map(ColumnType::VarChar),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for RoundtripCase {
+    type Parameters = CaseLimits;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(limits: CaseLimits) -> Self::Strategy {
+        vec(
+            ColumnType::arbitrary_with(limits.max_str_len),
+            1..=limits.max_columns.max(1),
+        )
+        .prop_flat_map(move |column_types| {
+            let row: Vec<_> = column_types.iter().map(|&ty| value(ty)).collect();
+            (Just(column_types), vec(row, 0..=limits.max_rows))
+        })
+        .prop_map(|(column_types, rows)| RoundtripCase { column_types, rows })
+        .boxed()
+    }
+}
+
+/// Random value of type `ty`. One in eight values is `NULL`. Extreme values are more likely than
+/// they would be if values were uniformly distributed, since they are more likely to reveal
+/// errors.
+fn value(ty: ColumnType) -> BoxedStrategy<Option<String>> {
+    let value = match ty {
+        ColumnType::SmallInt => integer(i16::MIN.into(), i16::MAX.into()),
+        ColumnType::Integer => integer(i32::MIN.into(), i32::MAX.into()),
+        ColumnType::BigInt => integer(i64::MIN, i64::MAX),
+        // Printable ASCII, including quotes and spaces.
+        ColumnType::VarChar(max_len) => string_regex(&format!("[ -~]{{0,{max_len}}}"))
+            .unwrap()
+            .boxed(),
+    };
+    option::weighted(0.875, value).boxed()
+}
+
+fn integer(min: i64, max: i64) -> BoxedStrategy<String> {
+    prop_oneof![
+        1 => Just(min),
+        1 => Just(max),
+        1 => Just(0),
+        5 => min..=max,
+    ]
+    .prop_map(|value| value.to_string())
+    .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{arbitrary::any_with, proptest};
+
+    use super::{CaseLimits, ColumnType, RoundtripCase};
+
+    proptest! {
+        #[test]
+        fn values_fit_into_their_columns(
+            case in any_with::<RoundtripCase>(CaseLimits { max_rows: 50, ..CaseLimits::default() })
+        ) {
+            assert!(!case.column_types.is_empty() && case.column_types.len() <= 5);
+            for row in &case.rows {
+                for (value, ty) in row.iter().zip(&case.column_types) {
+                    let Some(value) = value else { continue };
+                    match *ty {
+                        ColumnType::SmallInt => assert!(value.parse::<i16>().is_ok()),
+                        ColumnType::Integer => assert!(value.parse::<i32>().is_ok()),
+                        ColumnType::BigInt => assert!(value.parse::<i64>().is_ok()),
+                        ColumnType::VarChar(max_len) => assert!(value.len() <= max_len),
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+/// A struct, which can be sent to the data source as a row of text, e.g. using
+/// [`crate::Connection::upsert_many`]. Each field is a column. The data source converts the text
+/// into the type of the column, so values must be formatted in a way it understands.
+///
+/// With the `derive` feature enabled, it can be derived for structs with named fields, whose
+/// fields implement [`ToSqlText`]. Columns are named like the fields.
+///
+/// ```
+/// use odbc_api::{TextRecord, ToSqlText};
+///
+/// struct Birthday {
+///     name: String,
+///     year: Option<i16>,
+/// }
+///
+/// impl TextRecord for Birthday {
+///     fn column_names() -> &'static [&'static str] {
+///         &["name", "year"]
+///     }
+///
+///     fn text_values(&self) -> Vec<Option<String>> {
+///         vec![self.name.to_sql_text(), self.year.to_sql_text()]
+///     }
+/// }
+/// ```
+pub trait TextRecord {
+    /// Names of the columns, in the same order as the values returned by [`Self::text_values`].
+    fn column_names() -> &'static [&'static str];
+
+    /// One value for each column. `None` represents `NULL`.
+    fn text_values(&self) -> Vec<Option<String>>;
+}
+
+impl<T> TextRecord for &T
+where
+    T: TextRecord,
+{
+    fn column_names() -> &'static [&'static str] {
+        T::column_names()
+    }
+
+    fn text_values(&self) -> Vec<Option<String>> {
+        (**self).text_values()
+    }
+}
+
+/// Renders a field of a [`TextRecord`] as text. `None` represents `NULL`.
+pub trait ToSqlText {
+    /// Text representation understood by the data source.
+    fn to_sql_text(&self) -> Option<String>;
+}
+
+macro_rules! impl_to_sql_text_using_display {
+    ($($t:ty)*) => (
+        $(
+            impl ToSqlText for $t {
+                fn to_sql_text(&self) -> Option<String> {
+                    Some(self.to_string())
+                }
+            }
+        )*
+    );
+}
+
+impl_to_sql_text_using_display! { i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 String str char }
+
+impl ToSqlText for bool {
+    /// `1` or `0`, which is understood for both boolean and bit columns.
+    fn to_sql_text(&self) -> Option<String> {
+        Some(if *self { "1" } else { "0" }.to_owned())
+    }
+}
+
+impl<T> ToSqlText for Option<T>
+where
+    T: ToSqlText,
+{
+    fn to_sql_text(&self) -> Option<String> {
+        self.as_ref().and_then(ToSqlText::to_sql_text)
+    }
+}
+
+impl<T> ToSqlText for &T
+where
+    T: ToSqlText + ?Sized,
+{
+    fn to_sql_text(&self) -> Option<String> {
+        (**self).to_sql_text()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ToSqlText;
+
+    #[test]
+    fn render_fields() {
+        assert_eq!(Some("42".to_owned()), 42i32.to_sql_text());
+        assert_eq!(Some("1.5".to_owned()), 1.5f64.to_sql_text());
+        assert_eq!(Some("1".to_owned()), true.to_sql_text());
+        assert_eq!(Some("Hello".to_owned()), "Hello".to_sql_text());
+        assert_eq!(None, None::<String>.to_sql_text());
+        assert_eq!(Some("7".to_owned()), Some(7u8).to_sql_text());
+    }
+}
@@ -0,0 +1,265 @@
+use crate::SqlDialect;
+
+/// The dialect specific syntax used to insert a row, or update it if a row with the same key
+/// already exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpsertSyntax {
+    /// `MERGE INTO ... USING ... WHEN MATCHED ... WHEN NOT MATCHED ...` as specified by the SQL
+    /// standard, using a table value constructor as source. E.g. Microsoft SQL Server.
+    Merge,
+    /// `INSERT INTO ... ON CONFLICT (...) DO UPDATE SET ...`. E.g. PostgreSQL or SQLite.
+    OnConflict,
+    /// `INSERT INTO ... ON DUPLICATE KEY UPDATE ...`. E.g. MySQL or MariaDB. The conflicting key is
+    /// determined by the unique indices of the table, rather than by the key columns.
+    OnDuplicateKey,
+}
+
+impl UpsertSyntax {
+    /// Picks the syntax supported by a database management system, as reported by
+    /// [`crate::Connection::database_management_system_name`]. Falls back to
+    /// [`UpsertSyntax::Merge`] for unknown names, since it is part of the SQL standard.
+    pub fn from_dbms_name(dbms_name: &str) -> Self {
+        match dbms_name {
+            "PostgreSQL" | "SQLite" => UpsertSyntax::OnConflict,
+            "MySQL" | "MariaDB" => UpsertSyntax::OnDuplicateKey,
+            _ => UpsertSyntax::Merge,
+        }
+    }
+}
+
+/// Builds a parameterized statement, which inserts a row, or updates the existing row with the
+/// same key. Use [`crate::Connection::prepare_upsert`] to prepare it using the syntax of the
+/// connected data source.
+///
+/// The statement has one placeholder for each key column, followed by one for each value column.
+/// So it can be executed with a columnar buffer holding the keys in its first columns, and the
+/// values in the remaining ones. E.g. using [`crate::Prepared::into_column_inserter`].
+///
+/// ```
+/// use odbc_api::{AnsiDialect, Upsert, UpsertSyntax};
+///
+/// let upsert = Upsert::new("Birthdays")
+///     .key_columns(&["name"])
+///     .value_columns(&["birthday"]);
+/// assert_eq!(
+///     "INSERT INTO Birthdays (\"name\", \"birthday\") VALUES (?, ?) \
+///     ON CONFLICT (\"name\") DO UPDATE SET \"birthday\" = EXCLUDED.\"birthday\"",
+///     upsert.to_sql(UpsertSyntax::OnConflict, &AnsiDialect)
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Upsert {
+    table_name: String,
+    key_columns: Vec<String>,
+    value_columns: Vec<String>,
+}
+
+impl Upsert {
+    /// Upsert into `table_name`. The name is inserted into the statement as is, so it may be
+    /// qualified with a schema.
+    pub fn new(table_name: &str) -> Self {
+        Self {
+            table_name: table_name.to_owned(),
+            key_columns: Vec::new(),
+            value_columns: Vec::new(),
+        }
+    }
+
+    /// Columns identifying the row to update. Appended to the key columns specified so far.
+    pub fn key_columns(mut self, names: &[&str]) -> Self {
+        self.key_columns
+            .extend(names.iter().map(|&name| name.to_owned()));
+        self
+    }
+
+    /// Columns updated, should a row with the same key exist. Appended to the value columns
+    /// specified so far.
+    pub fn value_columns(mut self, names: &[&str]) -> Self {
+        self.value_columns
+            .extend(names.iter().map(|&name| name.to_owned()));
+        self
+    }
+
+    /// Number of placeholders in the statement.
+    pub fn num_params(&self) -> usize {
+        self.key_columns.len() + self.value_columns.len()
+    }
+
+    /// Text of the statement in the given syntax. Column names are quoted using `dialect`. Should
+    /// no value columns be specified, existing rows are left unchanged.
+    ///
+    /// # Panics
+    ///
+    /// If no key columns have been specified.
+    pub fn to_sql(&self, syntax: UpsertSyntax, dialect: &impl SqlDialect) -> String {
+        assert!(
+            !self.key_columns.is_empty(),
+            "An upsert requires at least one key column."
+        );
+        let quote = |names: &[String]| -> Vec<String> {
+            names
+                .iter()
+                .map(|name| dialect.quote_identifier(name))
+                .collect()
+        };
+        let keys = quote(&self.key_columns);
+        let values = quote(&self.value_columns);
+        let all: Vec<&str> = keys.iter().chain(&values).map(String::as_str).collect();
+        let placeholders = vec!["?"; all.len()].join(", ");
+        let table_name = &self.table_name;
+
+        match syntax {
+            UpsertSyntax::Merge => {
+                let on = keys
+                    .iter()
+                    .map(|key| format!("target.{key} = source.{key}"))
+                    .collect::<Vec<_>>()
+                    .join(" AND ");
+                let mut sql = format!(
+                    "MERGE INTO {table_name} AS target USING (VALUES ({placeholders})) \
+                    AS source ({}) ON {on}",
+                    all.join(", ")
+                );
+                if !values.is_empty() {
+                    let set = values
+                        .iter()
+                        .map(|value| format!("{value} = source.{value}"))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    sql.push_str(&format!(" WHEN MATCHED THEN UPDATE SET {set}"));
+                }
+                let source_values = all
+                    .iter()
+                    .map(|column| format!("source.{column}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                // Microsoft SQL Server requires `MERGE` statements to be terminated.
+                sql.push_str(&format!(
+                    " WHEN NOT MATCHED THEN INSERT ({}) VALUES ({source_values});",
+                    all.join(", ")
+                ));
+                sql
+            }
+            UpsertSyntax::OnConflict => {
+                let action = if values.is_empty() {
+                    "NOTHING".to_owned()
+                } else {
+                    let set = values
+                        .iter()
+                        .map(|value| format!("{value} = EXCLUDED.{value}"))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    format!("UPDATE SET {set}")
+                };
+                format!(
+                    "INSERT INTO {table_name} ({}) VALUES ({placeholders}) \
+                    ON CONFLICT ({}) DO {action}",
+                    all.join(", "),
+                    keys.join(", ")
+                )
+            }
+            UpsertSyntax::OnDuplicateKey => {
+                // Assigning a key to itself leaves the existing row unchanged.
+                let updated = if values.is_empty() {
+                    &keys[..1]
+                } else {
+                    &values[..]
+                };
+                let set = updated
+                    .iter()
+                    .map(|value| format!("{value} = VALUES({value})"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!(
+                    "INSERT INTO {table_name} ({}) VALUES ({placeholders}) \
+                    ON DUPLICATE KEY UPDATE {set}",
+                    all.join(", ")
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AnsiDialect, SqlDialect};
+
+    use super::{Upsert, UpsertSyntax};
+
+    /// Unquoted identifiers keep the expected statements readable.
+    struct NoQuotes;
+
+    impl SqlDialect for NoQuotes {
+        fn quote_identifier(&self, identifier: &str) -> String {
+            identifier.to_owned()
+        }
+    }
+
+    fn upsert() -> Upsert {
+        Upsert::new("t")
+            .key_columns(&["k"])
+            .value_columns(&["a", "b"])
+    }
+
+    #[test]
+    fn merge() {
+        assert_eq!(
+            "MERGE INTO t AS target USING (VALUES (?, ?, ?)) AS source (k, a, b) \
+            ON target.k = source.k WHEN MATCHED THEN UPDATE SET a = source.a, b = source.b \
+            WHEN NOT MATCHED THEN INSERT (k, a, b) VALUES (source.k, source.a, source.b);",
+            upsert().to_sql(UpsertSyntax::Merge, &NoQuotes)
+        );
+    }
+
+    #[test]
+    fn on_conflict() {
+        assert_eq!(
+            "INSERT INTO t (k, a, b) VALUES (?, ?, ?) ON CONFLICT (k) \
+            DO UPDATE SET a = EXCLUDED.a, b = EXCLUDED.b",
+            upsert().to_sql(UpsertSyntax::OnConflict, &NoQuotes)
+        );
+    }
+
+    #[test]
+    fn on_duplicate_key() {
+        assert_eq!(
+            "INSERT INTO t (k, a, b) VALUES (?, ?, ?) ON DUPLICATE KEY UPDATE a = VALUES(a), \
+            b = VALUES(b)",
+            upsert().to_sql(UpsertSyntax::OnDuplicateKey, &NoQuotes)
+        );
+    }
+
+    #[test]
+    fn keys_only() {
+        let upsert = Upsert::new("t").key_columns(&["k"]);
+        assert_eq!(
+            "INSERT INTO t (\"k\") VALUES (?) ON CONFLICT (\"k\") DO NOTHING",
+            upsert.to_sql(UpsertSyntax::OnConflict, &AnsiDialect)
+        );
+        assert_eq!(
+            "INSERT INTO t (k) VALUES (?) ON DUPLICATE KEY UPDATE k = VALUES(k)",
+            upsert.to_sql(UpsertSyntax::OnDuplicateKey, &NoQuotes)
+        );
+        assert_eq!(
+            "MERGE INTO t AS target USING (VALUES (?)) AS source (k) ON target.k = source.k \
+            WHEN NOT MATCHED THEN INSERT (k) VALUES (source.k);",
+            upsert.to_sql(UpsertSyntax::Merge, &NoQuotes)
+        );
+    }
+
+    #[test]
+    fn syntax_from_dbms_name() {
+        assert_eq!(
+            UpsertSyntax::OnConflict,
+            UpsertSyntax::from_dbms_name("PostgreSQL")
+        );
+        assert_eq!(
+            UpsertSyntax::OnDuplicateKey,
+            UpsertSyntax::from_dbms_name("MariaDB")
+        );
+        assert_eq!(
+            UpsertSyntax::Merge,
+            UpsertSyntax::from_dbms_name("Microsoft SQL Server")
+        );
+    }
+}
@@ -10,20 +10,37 @@ use common::{cursor_to_string, Profile, SingleColumnRowSetBuffer, ENV};
 
 use odbc_api::{
     buffers::{
-        BufferDesc, ColumnarAnyBuffer, ColumnarBuffer, Indicator, Item, TextColumn, TextRowSet,
+        BufferDesc, CallerOwnedBuffer, CellTransforms, CellValue, CharsetFinding, CharsetIssue,
+        CoerceTo, ColumnarAnyBuffer, ColumnarBuffer, DecimalColumn, GrowthPolicy, Indicator, Item,
+        ResultSetHasher, Row, RowOrder, TextBinding, TextColumn, TextRowSet, TypeClass,
+        TypeCoercion,
     },
-    handles::{OutputStringBuffer, Statement},
+    copy,
+    handles::{AsStatementRef, OutputStringBuffer, Statement},
     parameter::InputParameter,
     parameter::{
-        Blob, BlobRead, BlobSlice, VarBinaryArray, VarCharArray, VarCharSlice, WithDataType,
+        Binary, Blob, BlobRead, BlobSlice, OutputStream, TableValuedParameter, VarBinaryArray,
+        VarCharArray, VarCharSlice, Wide, WithDataType,
     },
-    sys, Bit, ColumnDescription, Cursor, DataType, Error, InOut, IntoParameter, Nullability,
-    Nullable, Out, ResultSetMetadata, U16Str, U16String,
+    schema::{schema_snapshot, SchemaFilter},
+    sys, AnsiDialect, Authentication, BatchMode, Bit, ColumnDescription, ColumnNameMatcher,
+    CopyOptions, Cursor, CursorSensitivity, CursorType, DataType, DbmsDialect, Environment, Error,
+    Failover, FetchLimits, InOut, InsertRowsOptions, IntoParameter, KeepAlive, KeepAliveProbe,
+    LazyColumnCursor, LimitExceeded, Matrix, MatrixError, NulPolicy, NullPolicy, Nullability,
+    Nullable, Out, ParameterArrays, Preview, ResultSetMetadata, ResumableExtraction, ResumeToken,
+    Sampling, ScriptDelimiter, SharedConnection, SqlDialect, StatementOutcome, Truncation, U16Str,
+    U16String, Upsert,
 };
 use std::{
+    collections::HashMap,
     ffi::CString,
     io::{self, Write},
-    iter, str, thread,
+    iter,
+    ops::ControlFlow,
+    ptr::null_mut,
+    str,
+    sync::{mpsc, Arc, Mutex},
+    thread,
     time::Duration,
 };
 
@@ -111,6 +128,20 @@ fn bogus_connection_string() {
     };
 }
 
+#[test]
+fn environment_singleton() {
+    // When
+    let first = Environment::singleton().unwrap();
+    let second = Environment::singleton().unwrap();
+
+    // Then
+    assert!(std::ptr::eq(first, second));
+    assert!(matches!(
+        Environment::set_singleton_odbc_version(sys::AttrOdbcVersion::Odbc3),
+        Err(Error::SingletonAlreadyInitialized)
+    ));
+}
+
 #[test_case(MSSQL; "Microsoft SQL Server")]
 #[test_case(MARIADB; "Maria DB")]
 #[test_case(SQLITE_3; "SQLite 3")]
@@ -120,6 +151,117 @@ fn connect_to_db(profile: &Profile) {
     assert!(!conn.is_dead().unwrap())
 }
 
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn failover_to_reachable_data_source(profile: &Profile) {
+    let failover = Failover::new(["foobar", profile.connection_string]);
+
+    let (index, conn) = failover.connect(&ENV).unwrap();
+
+    assert_eq!(1, index);
+    assert!(!conn.is_dead().unwrap())
+}
+
+#[test]
+fn failover_without_reachable_data_source() {
+    let failover = Failover::new(["foo", "bar"]).with_retries(1, Duration::from_millis(1));
+
+    let result = failover.connect(&ENV);
+
+    assert!(result.is_err());
+}
+
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn connect_with_retry(profile: &Profile) {
+    let conn = ENV
+        .connect_with_retry(profile.connection_string, 1, Duration::from_millis(1))
+        .unwrap();
+
+    assert!(!conn.is_dead().unwrap())
+}
+
+/// Assemble the connection string from host, database and credentials, detecting the driver.
+#[test_case(DbmsDialect::MsSql, "localhost", "master", "SA", "My@Test@Password1"; "Microsoft SQL Server")]
+#[test_case(DbmsDialect::MySql, "127.0.0.1:3306", "test_db", "root", "my-secret-pw"; "Maria DB")]
+#[test_case(DbmsDialect::PostgreSql, "localhost:5432", "test", "test", "test"; "PostgreSQL")]
+fn connect_without_data_source_name(
+    dbms: DbmsDialect,
+    host: &str,
+    database: &str,
+    user: &str,
+    password: &str,
+) {
+    let authentication = Authentication::Password { user, password };
+
+    let conn = ENV
+        .connect_dbms(dbms, host, database, authentication)
+        .unwrap();
+
+    assert_eq!(dbms, conn.dialect().unwrap());
+}
+
+/// Drivers predating ODBC 3.8 must not cause an error, but report the features as unsupported.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn odbc_3_8_features(profile: &Profile) {
+    let conn = profile.connection().unwrap();
+
+    let features = conn.odbc_3_8_features();
+
+    assert!(features.is_ok());
+}
+
+/// Bind only the first column and fetch the second one using `SQLGetData`.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn fetch_with_unbound_columns(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, table) = profile
+        .given(&table_name, &["INTEGER", "VARCHAR(50)"])
+        .unwrap();
+    conn.execute(
+        &format!("INSERT INTO {table_name} (a,b) VALUES (1, 'one'), (2, NULL)"),
+        (),
+    )
+    .unwrap();
+    assert!(conn
+        .get_data_extensions()
+        .unwrap()
+        .can_fetch_unbound(&[1], 2));
+
+    let cursor = conn
+        .execute(&table.sql_all_ordered_by_id(), ())
+        .unwrap()
+        .unwrap();
+    let buffer = ColumnarAnyBuffer::try_from_descs_and_indices(
+        1,
+        [(1, BufferDesc::I32 { nullable: false })].into_iter(),
+    )
+    .unwrap();
+    let mut cursor = cursor.bind_buffer(buffer).unwrap();
+    let mut actual = Vec::new();
+    let mut text = Vec::new();
+    while let Some((batch, mut row)) = cursor.fetch_with_unbound().unwrap() {
+        let id = batch.column(0).as_slice::<i32>().unwrap()[0];
+        let is_not_null = row.get_text(2, &mut text).unwrap();
+        actual.push((
+            id,
+            is_not_null.then(|| String::from_utf8(text.clone()).unwrap()),
+        ));
+    }
+
+    assert_eq!(vec![(1, Some("one".to_owned())), (2, None)], actual);
+}
+
 #[test_case(MSSQL; "Microsoft SQL Server")]
 fn describe_columns(profile: &Profile) {
     let table_name = table_name!();
@@ -347,6 +489,76 @@ fn bind_char(profile: &Profile) {
     assert_eq!(Some(&b"Hello"[..]), batch.column(0).get(0));
 }
 
+/// Fetch MONEY values as scaled integers, without precision loss of a round trip through floats.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+fn fetch_money_as_scaled_integer(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["MONEY"]).unwrap();
+    conn.execute(
+        &format!("INSERT INTO {table_name} (a) VALUES (922337203685477.5807), (NULL), (-0.0001)"),
+        (),
+    )
+    .unwrap();
+
+    let cursor = conn
+        .execute(&table.sql_all_ordered_by_id(), ())
+        .unwrap()
+        .unwrap();
+    let mut buf = ColumnarBuffer::new(vec![(1, DecimalColumn::money(3))]);
+    let mut row_set_cursor = cursor.bind_buffer(&mut buf).unwrap();
+    let batch = row_set_cursor.fetch().unwrap().unwrap();
+    let values: Vec<_> = batch.column(0).iter().map(Result::unwrap).collect();
+
+    assert_eq!(vec![Some(i64::MAX), None, Some(-1)], values);
+}
+
+/// Copy rows out of the buffer, so they can be processed on another thread.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn send_owned_rows_through_channel(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, table) = profile
+        .given(&table_name, &["INTEGER", "VARCHAR(10)"])
+        .unwrap();
+    conn.execute(
+        &format!("INSERT INTO {table_name} (a, b) VALUES (1, 'one'), (NULL, 'two'), (3, NULL)"),
+        (),
+    )
+    .unwrap();
+
+    let cursor = conn
+        .execute(&table.sql_all_ordered_by_id(), ())
+        .unwrap()
+        .unwrap();
+    let buffer = ColumnarAnyBuffer::try_from_descs(
+        2,
+        [
+            BufferDesc::I32 { nullable: true },
+            BufferDesc::Text { max_str_len: 10 },
+        ],
+    )
+    .unwrap();
+    let mut block_cursor = cursor.bind_buffer(buffer).unwrap();
+    let (sender, receiver) = mpsc::channel();
+    let consumer = thread::spawn(move || receiver.iter().collect::<Vec<Row>>());
+    while let Some(batch) = block_cursor.fetch().unwrap() {
+        for row in batch.rows() {
+            sender.send(row).unwrap();
+        }
+    }
+    drop(sender);
+    let rows = consumer.join().unwrap();
+
+    let expected: Vec<Row> = vec![
+        vec![CellValue::I64(1), CellValue::Text(b"one".to_vec())].into(),
+        vec![CellValue::Null, CellValue::Text(b"two".to_vec())].into(),
+        vec![CellValue::I64(3), CellValue::Null].into(),
+    ];
+    assert_eq!(expected, rows);
+}
+
 /// Bind a CHAR column to a wchar buffer
 #[test_case(MSSQL; "Microsoft SQL Server")]
 #[test_case(MARIADB; "Maria DB")]
@@ -534,6 +746,34 @@ fn bind_numeric_to_i64(profile: &Profile) {
     assert_eq!(1234567890, actual[0]);
 }
 
+/// Fetch values directly into memory owned by the application.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn fetch_into_caller_owned_memory(profile: &Profile) {
+    let table_name = table_name!();
+    let conn = profile.setup_empty_table(&table_name, &["INT"]).unwrap();
+    let insert_sql = format!("INSERT INTO {table_name} (a) VALUES (1), (NULL), (3)");
+    conn.execute(&insert_sql, ()).unwrap();
+
+    let mut values = [0i32; 5];
+    {
+        let sql = format!("SELECT a FROM {table_name} ORDER BY id");
+        let cursor = conn.execute(&sql, ()).unwrap().unwrap();
+        let buffer = unsafe { CallerOwnedBuffer::new(vec![(1, &mut values[..])]) };
+        let mut block_cursor = cursor.bind_buffer(buffer).unwrap();
+        let batch = block_cursor.fetch().unwrap().unwrap();
+        assert_eq!(3, batch.num_rows());
+        assert!(batch.is_valid(0, 0));
+        assert!(!batch.is_valid(0, 1));
+        assert!(batch.is_valid(0, 2));
+    }
+
+    assert_eq!(1, values[0]);
+    assert_eq!(3, values[2]);
+}
+
 /// Bind a columnar buffer to a VARBINARY(10) column and fetch data.
 #[test_case(MSSQL; "Microsoft SQL Server")]
 // #[test_case(MARIADB; "Maria DB")] // Convert syntax is different
@@ -1246,6 +1486,37 @@ fn preallocated(profile: &Profile) {
     }
 }
 
+/// Request a keyset driven cursor, to see updates made by other statements while fetching.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+fn keyset_driven_cursor(profile: &Profile) {
+    let table_name = table_name!();
+    let conn = profile
+        .setup_empty_table(&table_name, &["VARCHAR(10)"])
+        .unwrap();
+    conn.execute(
+        &format!("INSERT INTO {table_name} (a) VALUES ('Hello')"),
+        (),
+    )
+    .unwrap();
+
+    let mut prealloc = conn.preallocate().unwrap();
+    prealloc.set_cursor_type(CursorType::KeysetDriven).unwrap();
+    prealloc
+        .set_cursor_sensitivity(CursorSensitivity::Sensitive)
+        .unwrap();
+    let cursor = prealloc
+        .execute(&format!("SELECT a FROM {table_name} ORDER BY id"), ())
+        .unwrap()
+        .unwrap();
+
+    // Update the row after the keyset has been determined, but before it is fetched.
+    conn.execute(&format!("UPDATE {table_name} SET a = 'World'"), ())
+        .unwrap();
+
+    let actual = cursor_to_string(cursor);
+    assert_eq!("World", actual);
+}
+
 /// Reuse a preallocated handle. Verify that columns bound to the statement during a previous
 /// execution are not dereferenced during a second one.
 #[test_case(MSSQL; "Microsoft SQL Server")]
@@ -1711,6 +1982,95 @@ fn send_connection(profile: &Profile) {
     assert_eq!("1\n2\n3", actual)
 }
 
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn shared_connection(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["INTEGER"]).unwrap();
+    let shared = unsafe { SharedConnection::new(conn) };
+
+    // Insert from several threads, using the same connection.
+    let insert_sql = table.sql_insert();
+    thread::scope(|s| {
+        for value in 1..=3 {
+            let shared = &shared;
+            let insert_sql = &insert_sql;
+            s.spawn(move || {
+                shared.lock().execute(insert_sql, &value).unwrap();
+            });
+        }
+    });
+
+    let conn = shared.into_inner();
+    let cursor = conn
+        .execute(&format!("SELECT a FROM {table_name} ORDER BY a"), ())
+        .unwrap()
+        .unwrap();
+    assert_eq!("1\n2\n3", cursor_to_string(cursor));
+}
+
+/// Tasks waiting for the connection must not block the thread, or the task holding it could never
+/// finish on a single threaded runtime.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+#[tokio::test]
+async fn shared_connection_between_tasks(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["INTEGER"]).unwrap();
+    let shared = unsafe { SharedConnection::new(conn) };
+    let insert_sql = table.sql_insert();
+    let sleep = || tokio::time::sleep(Duration::from_millis(10));
+
+    let insert = |value: i32| {
+        let shared = &shared;
+        let insert_sql = &insert_sql;
+        async move {
+            let conn = shared.lock_async().await;
+            // Yield while holding the connection, so the other tasks have to wait for it.
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            conn.execute_polling(insert_sql, &value, sleep)
+                .await
+                .unwrap();
+        }
+    };
+    tokio::join!(insert(1), insert(2), insert(3));
+
+    let conn = shared.into_inner();
+    let cursor = conn
+        .execute(&format!("SELECT a FROM {table_name} ORDER BY a"), ())
+        .unwrap()
+        .unwrap();
+    assert_eq!("1\n2\n3", cursor_to_string(cursor));
+}
+
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn keep_alive_idle_connection(profile: &Profile) {
+    // Given a shared connection kept alive in the background
+    let conn = profile.connection().unwrap();
+    let shared = Arc::new(unsafe { SharedConnection::new(conn) });
+    let keep_alive = KeepAlive::new(
+        shared.clone(),
+        Duration::from_millis(10),
+        KeepAliveProbe::Query("SELECT 1".to_owned()),
+    );
+
+    // When the connection is idle, while probes are executed
+    thread::sleep(Duration::from_millis(100));
+    drop(keep_alive);
+
+    // Then it is still usable
+    let conn = shared.lock();
+    let cursor = conn.execute("SELECT 42", ()).unwrap().unwrap();
+    assert_eq!("42", cursor_to_string(cursor));
+}
+
 #[test_case(MSSQL; "Microsoft SQL Server")]
 #[test_case(MARIADB; "Maria DB")]
 #[test_case(SQLITE_3; "SQLite 3")]
@@ -1883,7 +2243,9 @@ fn ignore_output_column(profile: &Profile) {
         .unwrap();
 
     let bd = BufferDesc::I32 { nullable: true };
-    let buffer = ColumnarAnyBuffer::from_descs_and_indices(20, [(1, bd), (3, bd)].iter().copied());
+    let buffer =
+        ColumnarAnyBuffer::try_from_descs_and_indices(20, [(1, bd), (3, bd)].iter().copied())
+            .unwrap();
     let mut cursor = cursor.bind_buffer(buffer).unwrap();
 
     // Assert that there is no batch.
@@ -2873,71 +3235,1680 @@ fn database_management_system_name(profile: &Profile, expected_name: &'static st
     assert_eq!(expected_name, actual_name);
 }
 
-// Check the max name length for the catalogs, schemas, tables, and columns.
-#[test_case(MSSQL, 128, 128, 128, 128; "Microsoft SQL Server")]
-#[test_case(MARIADB, 256, 0, 256, 255; "Maria DB")]
-#[test_case(SQLITE_3, 255, 255, 255, 255; "SQLite 3")]
-#[test_case(POSTGRES, 0, 63, 63, 63; "PostgreSQL")]
-fn name_limits(
-    profile: &Profile,
-    expected_max_catalog_name_len: u16,
-    expected_max_schema_name_len: u16,
-    expected_max_table_name_len: u16,
-    expected_max_column_name_len: u16,
-) {
+/// Quirks are a property of the driver, so they are looked up by the file name of its library.
+#[test_case(MSSQL, true; "Microsoft SQL Server")]
+#[test_case(SQLITE_3, false; "SQLite 3")]
+fn quirks_from_driver_name(profile: &Profile, expected_no_total: bool) {
     let conn = profile.connection().unwrap();
 
-    assert_eq!(
-        conn.max_catalog_name_len().unwrap(),
-        expected_max_catalog_name_len
-    );
-    assert_eq!(
-        conn.max_schema_name_len().unwrap(),
-        expected_max_schema_name_len
-    );
-    assert_eq!(
-        conn.max_table_name_len().unwrap(),
-        expected_max_table_name_len
-    );
-    assert_eq!(
-        conn.max_column_name_len().unwrap(),
-        expected_max_column_name_len
-    );
+    let quirks = conn.quirks().unwrap();
+
+    assert_eq!(expected_no_total, quirks.no_total_for_unbounded_columns);
 }
 
-// Check the current catalog being used by the connection.
-#[test_case(MSSQL, "master"; "Microsoft SQL Server")]
-#[test_case(MARIADB, "test_db"; "Maria DB")]
-#[test_case(SQLITE_3, ""; "SQLite 3")]
-#[test_case(POSTGRES, "test"; "PostgreSQL")]
-fn current_catalog(profile: &Profile, expected_catalog: &str) {
-    let conn = profile.connection().unwrap();
+/// SQLite does not enforce the length of `VARCHAR` columns, so text buffers must still reserve
+/// space for multi byte characters in order to not truncate values.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(SQLITE_3; "SQLite 3")]
+fn text_buffer_holds_multi_byte_characters(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["VARCHAR(10)"]).unwrap();
+    conn.execute(&table.sql_insert(), &"ÄÖÜäöüßÄÖÜ".into_parameter())
+        .unwrap();
 
-    assert_eq!(conn.current_catalog().unwrap(), expected_catalog);
+    let mut cursor = conn
+        .execute(&table.sql_all_ordered_by_id(), ())
+        .unwrap()
+        .unwrap();
+    let buffer = TextRowSet::for_cursor(1, &mut cursor, None).unwrap();
+    let mut cursor = cursor.bind_buffer(buffer).unwrap();
+    let batch = cursor.fetch_with_truncation_check(true).unwrap().unwrap();
+
+    assert_eq!("ÄÖÜäöüßÄÖÜ", batch.at_as_str(0, 0).unwrap().unwrap());
 }
 
-#[test_case(MSSQL, "dbo"; "Microsoft SQL Server")]
-#[test_case(MARIADB, ""; "Maria DB")]
-#[test_case(SQLITE_3, "dbo"; "SQLite 3")]
-// #[test_case(POSTGRES, "test"; "PostgreSQL")] Errors out in linux
-fn columns_query(profile: &Profile, schema: &str) {
+/// `XML` and `NVARCHAR(max)` columns are identified as long text and capped to a default limit,
+/// rather than allocating buffers of the reported size.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+fn long_text_columns_capped_by_default(profile: &Profile) {
     let table_name = table_name!();
     let conn = profile
-        .setup_empty_table(&table_name, &["VARCHAR(10)"])
+        .setup_empty_table(&table_name, &["XML", "NVARCHAR(max)"])
         .unwrap();
 
-    let row_set_buffer =
-        ColumnarAnyBuffer::try_from_descs(2, conn.columns_buffer_descs(255, 255, 255).unwrap())
-            .unwrap();
-    // Mariadb does not support schemas
-    let columns = conn
-        .columns(&conn.current_catalog().unwrap(), schema, &table_name, "a")
+    let mut cursor = conn
+        .execute(&format!("SELECT a, b FROM {table_name}"), ())
+        .unwrap()
         .unwrap();
+    let xml = cursor.col_data_type(1).unwrap();
+    let nvarchar_max = cursor.col_data_type(2).unwrap();
+    let buffer = TextRowSet::for_cursor(1, &mut cursor, None).unwrap();
 
-    let mut cursor = columns.bind_buffer(row_set_buffer).unwrap();
-    let batch = cursor.fetch().unwrap().unwrap();
+    assert!(matches!(xml, DataType::Xml { .. }));
+    assert!(xml.is_long_text());
+    assert!(nvarchar_max.is_long_text());
+    assert_eq!(TextRowSet::LONG_TEXT_LIMIT, buffer.max_len(0));
+    assert_eq!(TextRowSet::LONG_TEXT_LIMIT, buffer.max_len(1));
+}
 
-    const COLUMN_NAME_INDEX: usize = 3;
+/// Driver specific types are described with their name and a length suitable for text buffers.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+fn describe_driver_specific_type(profile: &Profile) {
+    let table_name = table_name!();
+    let conn = profile
+        .setup_empty_table(&table_name, &["hierarchyid", "INTEGER"])
+        .unwrap();
+
+    let mut cursor = conn
+        .execute(&format!("SELECT a, b FROM {table_name}"), ())
+        .unwrap()
+        .unwrap();
+    let hierarchy_id = cursor.col_other_data_type(1).unwrap().unwrap();
+    let integer = cursor.col_other_data_type(2).unwrap();
+
+    assert_eq!("hierarchyid", hierarchy_id.name);
+    assert!(hierarchy_id.utf8_len().is_some());
+    assert!(integer.is_none());
+}
+
+/// Look up columns by name, regardless of the case the data source reports them in.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn column_index_by_name(profile: &Profile) {
+    let table_name = table_name!();
+    let conn = profile
+        .setup_empty_table(&table_name, &["INTEGER", "VARCHAR(10)"])
+        .unwrap();
+
+    let mut cursor = conn
+        .execute(&format!("SELECT a, b FROM {table_name}"), ())
+        .unwrap()
+        .unwrap();
+    let index_b = cursor.col_index("B").unwrap();
+    let index_c = cursor.col_index("c").unwrap();
+    drop(cursor);
+    conn.set_column_name_matcher(ColumnNameMatcher::exact());
+    let mut cursor = conn
+        .execute(&format!("SELECT a, b FROM {table_name}"), ())
+        .unwrap()
+        .unwrap();
+    let exact_index_b = cursor.col_index("B").unwrap();
+
+    assert_eq!(Some(2), index_b);
+    assert_eq!(None, index_c);
+    assert_eq!(None, exact_index_b);
+}
+
+/// Conversion errors in `get_data` name the column and the C type the value has been requested as.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+fn get_data_reports_invalid_conversion(profile: &Profile) {
+    let table_name = table_name!();
+    let conn = profile
+        .setup_empty_table(&table_name, &["VARCHAR(10)"])
+        .unwrap();
+    conn.execute(&format!("INSERT INTO {table_name} (a) VALUES ('abc')"), ())
+        .unwrap();
+
+    let mut cursor = conn
+        .execute(&format!("SELECT a FROM {table_name}"), ())
+        .unwrap()
+        .unwrap();
+    let mut row = cursor.next_row().unwrap().unwrap();
+    let result = row.get_value::<i32>(1);
+
+    assert!(matches!(
+        result,
+        Err(Error::InvalidDataConversion {
+            column_number: 1,
+            c_type: sys::CDataType::SLong,
+            ..
+        })
+    ));
+}
+
+/// Fetch fixed sized values of different types row by row.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn get_value_of_fixed_sized_types(profile: &Profile) {
+    let table_name = table_name!();
+    let conn = profile
+        .setup_empty_table(&table_name, &["INTEGER", "DATE", "INTEGER"])
+        .unwrap();
+    conn.execute(
+        &format!(
+            "INSERT INTO {table_name} (a, b, c) VALUES (42, '2021-03-20', 1), (NULL, NULL, 0)"
+        ),
+        (),
+    )
+    .unwrap();
+
+    let mut cursor = conn
+        .execute(&format!("SELECT a, b, c FROM {table_name} ORDER BY id"), ())
+        .unwrap()
+        .unwrap();
+    let mut actual = Vec::new();
+    while let Some(mut row) = cursor.next_row().unwrap() {
+        let a: Option<u64> = row.get_value(1).unwrap();
+        let b: Option<sys::Date> = row.get_value(2).unwrap();
+        let c = row.get_bool(3).unwrap();
+        actual.push((a, b.map(|d| (d.year, d.month, d.day)), c));
+    }
+
+    assert_eq!(
+        vec![
+            (Some(42), Some((2021, 3, 20)), Some(true)),
+            (None, None, Some(false))
+        ],
+        actual
+    );
+}
+
+/// Fetch a result set as text using the C API.
+#[cfg(feature = "capi")]
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn fetch_text_using_capi(profile: &Profile) {
+    use odbc_api::capi::{
+        odbc_api_connect, odbc_api_connection_free, odbc_api_cursor_col_name,
+        odbc_api_cursor_fetch, odbc_api_cursor_free, odbc_api_cursor_text, odbc_api_execute,
+        Status,
+    };
+    use std::{ffi::CStr, ptr::null_mut, slice};
+
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["VARCHAR(10)"]).unwrap();
+    conn.execute(
+        &format!("INSERT INTO {table_name} (a) VALUES ('Hello'), (NULL)"),
+        (),
+    )
+    .unwrap();
+
+    let connection_string = CString::new(profile.connection_string).unwrap();
+    let query = CString::new(table.sql_all_ordered_by_id()).unwrap();
+    let mut actual = Vec::new();
+    unsafe {
+        let mut capi_conn = null_mut();
+        assert_eq!(
+            Status::Success,
+            odbc_api_connect(connection_string.as_ptr(), &mut capi_conn)
+        );
+        let mut cursor = null_mut();
+        assert_eq!(
+            Status::Success,
+            odbc_api_execute(capi_conn, query.as_ptr(), 10, 0, &mut cursor)
+        );
+        let mut name = std::ptr::null();
+        assert_eq!(
+            Status::Success,
+            odbc_api_cursor_col_name(cursor, 0, &mut name)
+        );
+        let name = CStr::from_ptr(name).to_str().unwrap().to_lowercase();
+        let mut num_rows = 0;
+        assert_eq!(
+            Status::Success,
+            odbc_api_cursor_fetch(cursor, &mut num_rows)
+        );
+        for row_index in 0..num_rows {
+            let mut text = std::ptr::null();
+            let mut len = 0;
+            assert_eq!(
+                Status::Success,
+                odbc_api_cursor_text(cursor, 0, row_index, &mut text, &mut len)
+            );
+            actual.push((!text.is_null()).then(|| slice::from_raw_parts(text, len).to_vec()));
+        }
+        odbc_api_cursor_free(cursor);
+        odbc_api_connection_free(capi_conn);
+        assert_eq!("a", name);
+    }
+
+    assert_eq!(vec![Some(b"Hello".to_vec()), None], actual);
+}
+
+/// Reset a prepared statement between executions with different parameters.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn reset_prepared_statement(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, _table) = profile.given(&table_name, &["INTEGER"]).unwrap();
+    conn.execute(
+        &format!("INSERT INTO {table_name} (a) VALUES (1), (2), (3)"),
+        (),
+    )
+    .unwrap();
+    let mut prepared = conn
+        .prepare(&format!(
+            "SELECT a FROM {table_name} WHERE a > ? ORDER BY id"
+        ))
+        .unwrap();
+
+    let mut results = Vec::new();
+    for threshold in [2, 0] {
+        let cursor = prepared.execute(&threshold).unwrap().unwrap();
+        results.push(cursor_to_string(cursor));
+        prepared.reset().unwrap();
+        prepared.unbind().unwrap();
+    }
+
+    assert_eq!(vec!["3".to_owned(), "1\n2\n3".to_owned()], results);
+}
+
+/// Fetching stops once the row limit is reached, and the result is marked as partial.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn fetch_with_row_limit(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["INTEGER"]).unwrap();
+    conn.execute(
+        &format!("INSERT INTO {table_name} (a) VALUES (1), (2), (3), (4), (5)"),
+        (),
+    )
+    .unwrap();
+
+    let mut cursor = conn
+        .execute(&table.sql_all_ordered_by_id(), ())
+        .unwrap()
+        .unwrap();
+    let buffer = TextRowSet::for_cursor(2, &mut cursor, None).unwrap();
+    let mut block_cursor = cursor.bind_buffer(buffer).unwrap();
+    block_cursor.set_fetch_limits(FetchLimits {
+        max_rows: Some(3),
+        ..FetchLimits::default()
+    });
+    let mut values = Vec::new();
+    while let Some(batch) = block_cursor.fetch().unwrap() {
+        for row_index in 0..batch.num_rows() {
+            values.push(batch.at_as_str(0, row_index).unwrap().unwrap().to_owned());
+        }
+    }
+
+    assert_eq!(vec!["1", "2", "3"], values);
+    assert_eq!(Some(LimitExceeded::Rows), block_cursor.limit_exceeded());
+}
+
+/// The hash of a result set does not depend on batch size or the width of the integer buffer.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn hash_result_set(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, table) = profile
+        .given(&table_name, &["INTEGER", "VARCHAR(10)"])
+        .unwrap();
+    conn.execute(
+        &format!("INSERT INTO {table_name} (a, b) VALUES (1, 'one'), (NULL, 'two'), (3, NULL)"),
+        (),
+    )
+    .unwrap();
+
+    let hash = |batch_size, integer| {
+        let cursor = conn
+            .execute(&table.sql_all_ordered_by_id(), ())
+            .unwrap()
+            .unwrap();
+        let buffer = ColumnarAnyBuffer::try_from_descs(
+            batch_size,
+            [integer, BufferDesc::Text { max_str_len: 10 }],
+        )
+        .unwrap();
+        let mut block_cursor = cursor.bind_buffer(buffer).unwrap();
+        let mut hasher = ResultSetHasher::new(RowOrder::Ordered);
+        while let Some(batch) = block_cursor.fetch().unwrap() {
+            hasher.update(batch);
+        }
+        hasher.finish()
+    };
+
+    assert_eq!(
+        hash(1, BufferDesc::I32 { nullable: true }),
+        hash(10, BufferDesc::I64 { nullable: true })
+    );
+}
+
+/// Copy a table into another one, with different buffer types and a different batch size for the
+/// target.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn copy_between_tables(profile: &Profile) {
+    let source_name = table_name!();
+    let target_name = format!("{source_name}_target");
+    let (conn, source) = profile
+        .given(&source_name, &["INTEGER", "VARCHAR(10)"])
+        .unwrap();
+    let (_, target) = profile
+        .given(&target_name, &["BIGINT", "VARCHAR(10)"])
+        .unwrap();
+    conn.execute(
+        &format!("INSERT INTO {source_name} (a, b) VALUES (1, 'one'), (NULL, 'three'), (3, NULL)"),
+        (),
+    )
+    .unwrap();
+
+    let cursor = conn
+        .execute(&source.sql_all_ordered_by_id(), ())
+        .unwrap()
+        .unwrap();
+    let buffer = ColumnarAnyBuffer::try_from_descs(
+        2,
+        [
+            BufferDesc::I32 { nullable: true },
+            BufferDesc::Text { max_str_len: 10 },
+        ],
+    )
+    .unwrap();
+    let mut block_cursor = cursor.bind_buffer(buffer).unwrap();
+    let mut inserter = conn
+        .prepare(&target.sql_insert())
+        .unwrap()
+        .into_column_inserter(
+            1,
+            [
+                BufferDesc::I64 { nullable: true },
+                BufferDesc::Text { max_str_len: 3 },
+            ],
+        )
+        .unwrap();
+    let options = CopyOptions {
+        truncation: Truncation::Truncate,
+    };
+    let summary = copy(&mut block_cursor, &mut inserter, &options).unwrap();
+
+    assert_eq!(3, summary.num_rows);
+    assert_eq!(1, summary.num_truncated);
+    assert_eq!("1,one\nNULL,thr\n3,NULL", target.content_as_string(&conn));
+}
+
+/// Interrupt an extraction after the first batch and resume it from the persisted token.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn resume_extraction(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, _table) = profile
+        .given(&table_name, &["INTEGER", "VARCHAR(10)"])
+        .unwrap();
+    conn.execute(
+        &format!("INSERT INTO {table_name} (a, b) VALUES (1, 'x'), (1, 'y'), (2, 'x')"),
+        (),
+    )
+    .unwrap();
+    let extraction = ResumableExtraction::new(&table_name, &["a", "b"], &["a", "b"]);
+    let descs = [
+        BufferDesc::I32 { nullable: false },
+        BufferDesc::Text { max_str_len: 10 },
+    ];
+
+    let cursor = conn
+        .execute(&extraction.sql(None, &AnsiDialect), ())
+        .unwrap()
+        .unwrap();
+    let buffer = ColumnarAnyBuffer::try_from_descs(1, descs).unwrap();
+    let mut block_cursor = cursor.bind_buffer(buffer).unwrap();
+    let batch = block_cursor.fetch().unwrap().unwrap();
+    let checkpoint = extraction.resume_token(batch).unwrap().to_string();
+    drop(block_cursor);
+
+    let token: ResumeToken = checkpoint.parse().unwrap();
+    let parameters = extraction.parameters(Some(&token));
+    let cursor = conn
+        .execute(
+            &extraction.sql(Some(&token), &AnsiDialect),
+            parameters.as_slice(),
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!("1,y\n2,x", cursor_to_string(cursor));
+}
+
+/// Literals and row limits generated by the dialect of the connection must be understood by the
+/// data source.
+#[test_case(MSSQL, DbmsDialect::MsSql; "Microsoft SQL Server")]
+#[test_case(MARIADB, DbmsDialect::MySql; "Maria DB")]
+#[test_case(SQLITE_3, DbmsDialect::Sqlite; "SQLite 3")]
+#[test_case(POSTGRES, DbmsDialect::PostgreSql; "PostgreSQL")]
+fn dialect_of_connection(profile: &Profile, expected: DbmsDialect) {
+    let table_name = table_name!();
+    let (conn, _table) = profile.given(&table_name, &["VARCHAR(20)"]).unwrap();
+    let dialect = conn.dialect().unwrap();
+    let literal = dialect.quote_literal(r"it's \ fine");
+    conn.execute(
+        &format!("INSERT INTO {table_name} (a) VALUES ({literal}), ('second')"),
+        (),
+    )
+    .unwrap();
+
+    let query = dialect.limit_query(&format!("SELECT a FROM {table_name} ORDER BY id"), 1);
+    let cursor = conn.execute(&query, ()).unwrap().unwrap();
+
+    assert_eq!(expected, dialect);
+    assert_eq!(r"it's \ fine", cursor_to_string(cursor));
+}
+
+/// Checking a statement reports parameters and result columns, without executing it.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn check_sql_without_executing(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, table) = profile
+        .given(&table_name, &["INTEGER", "VARCHAR(10)"])
+        .unwrap();
+
+    let check = conn
+        .check_sql(&format!("SELECT a, b FROM {table_name} WHERE id = ?"))
+        .unwrap();
+    let insert = conn.check_sql(&table.sql_insert()).unwrap();
+
+    assert_eq!(1, check.num_params);
+    let names: Vec<_> = check
+        .columns
+        .iter()
+        .map(|column| column.name_to_string().unwrap())
+        .collect();
+    assert_eq!(vec!["a", "b"], names);
+    assert_eq!(2, insert.num_params);
+    assert!(insert.columns.is_empty());
+    // Nothing has been inserted
+    assert_eq!("", table.content_as_string(&conn));
+}
+
+/// Statements referencing columns which do not exist are rejected during preparation.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(SQLITE_3; "SQLite 3")]
+fn check_sql_with_unknown_column(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, _table) = profile.given(&table_name, &["INTEGER"]).unwrap();
+
+    let result = conn.check_sql(&format!("SELECT no_such_column FROM {table_name}"));
+
+    assert!(result.is_err());
+}
+
+/// National character columns are bound as wide text, all other text columns as narrow text.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+fn bind_text_wide_or_narrow_per_column(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, table) = profile
+        .given(&table_name, &["VARCHAR(10)", "NVARCHAR(10)"])
+        .unwrap();
+    conn.execute(
+        &table.sql_insert(),
+        (&"abc".into_parameter(), &"Grüße".into_parameter()),
+    )
+    .unwrap();
+
+    let mut cursor = conn
+        .execute(&format!("SELECT a, b FROM {table_name}"), ())
+        .unwrap()
+        .unwrap();
+    let descs = (1..=2)
+        .map(|column_number| {
+            let data_type = cursor.col_data_type(column_number).unwrap();
+            BufferDesc::from_data_type_with_text_binding(data_type, true, TextBinding::MatchColumn)
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    let buffer = ColumnarAnyBuffer::try_from_descs(1, descs).unwrap();
+    let mut block_cursor = cursor.bind_buffer(buffer).unwrap();
+    let batch = block_cursor.fetch().unwrap().unwrap();
+
+    let narrow = batch.column(0).as_text_view().unwrap();
+    let wide = batch.column(1).as_w_text_view().unwrap();
+    assert_eq!(Some(&b"abc"[..]), narrow.get(0));
+    assert_eq!("Grüße", wide.get(0).map(String::from_utf16_lossy).unwrap());
+}
+
+/// Decimals are fetched as text and an integer column converted to floating point by the driver.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn coerce_column_types(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, _table) = profile
+        .given(&table_name, &["DECIMAL(10,2)", "INTEGER"])
+        .unwrap();
+    conn.execute(
+        &format!("INSERT INTO {table_name} (a, b) VALUES (12.5, 42)"),
+        (),
+    )
+    .unwrap();
+
+    let mut cursor = conn
+        .execute(&format!("SELECT a, b FROM {table_name}"), ())
+        .unwrap()
+        .unwrap();
+    let coercion = TypeCoercion::new()
+        .class(TypeClass::Decimal, CoerceTo::Text)
+        .column("b", CoerceTo::F64);
+    let buffer = coercion.buffer_for_cursor(1, &mut cursor).unwrap();
+    let mut block_cursor = cursor.bind_buffer(buffer).unwrap();
+    let batch = block_cursor.fetch().unwrap().unwrap();
+
+    let a = batch.column(0).as_text_view().unwrap().get(0).unwrap();
+    let a: f64 = std::str::from_utf8(a).unwrap().parse().unwrap();
+    assert_eq!(12.5, a);
+    let b = batch.column(1).as_nullable_slice::<f64>().unwrap().get(0);
+    assert_eq!(Some(&42.0), b);
+}
+
+/// Fetch a small first row set, larger ones afterwards and finally rebind a larger buffer.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn change_row_array_size_between_fetches(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["INTEGER"]).unwrap();
+    conn.execute(
+        &format!("INSERT INTO {table_name} (a) VALUES (1), (2), (3), (4), (5), (6)"),
+        (),
+    )
+    .unwrap();
+    let cursor = conn
+        .execute(&table.sql_all_ordered_by_id(), ())
+        .unwrap()
+        .unwrap();
+    let buffer =
+        ColumnarAnyBuffer::try_from_descs(3, [BufferDesc::I32 { nullable: false }]).unwrap();
+    let mut block_cursor = cursor.bind_buffer(buffer).unwrap();
+    let values = |batch: &ColumnarAnyBuffer| batch.column(0).as_slice::<i32>().unwrap().to_vec();
+
+    block_cursor.set_row_array_size(1).unwrap();
+    let first = values(block_cursor.fetch().unwrap().unwrap());
+    block_cursor.set_row_array_size(3).unwrap();
+    let second = values(block_cursor.fetch().unwrap().unwrap());
+    let (cursor, _small) = block_cursor.unbind().unwrap();
+    let buffer =
+        ColumnarAnyBuffer::try_from_descs(10, [BufferDesc::I32 { nullable: false }]).unwrap();
+    let mut block_cursor = cursor.bind_buffer(buffer).unwrap();
+    let rest = values(block_cursor.fetch().unwrap().unwrap());
+
+    assert_eq!(vec![1], first);
+    assert_eq!(vec![2, 3, 4], second);
+    assert_eq!(vec![5, 6], rest);
+    assert!(block_cursor.fetch().unwrap().is_none());
+}
+
+/// Bind the same buffer to the result sets of two queries.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn reuse_buffer_for_next_query(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["INTEGER"]).unwrap();
+    conn.execute(
+        &format!("INSERT INTO {table_name} (a) VALUES (1), (2), (3)"),
+        (),
+    )
+    .unwrap();
+    let buffer =
+        ColumnarAnyBuffer::try_from_descs(10, [BufferDesc::I32 { nullable: false }]).unwrap();
+    let values = |batch: &ColumnarAnyBuffer| batch.column(0).as_slice::<i32>().unwrap().to_vec();
+
+    let cursor = conn
+        .execute(&table.sql_all_ordered_by_id(), ())
+        .unwrap()
+        .unwrap();
+    let mut block_cursor = cursor.bind_buffer(buffer).unwrap();
+    let first = values(block_cursor.fetch().unwrap().unwrap());
+    // Dropping the cursor closes it, so the connection is free for the next query.
+    let (_, buffer) = block_cursor.unbind().unwrap();
+    let cursor = conn
+        .execute(
+            &format!("SELECT a FROM {table_name} WHERE a > 1 ORDER BY id"),
+            (),
+        )
+        .unwrap()
+        .unwrap();
+    let mut block_cursor = cursor.bind_buffer(buffer).unwrap();
+    let second = values(block_cursor.fetch().unwrap().unwrap());
+
+    assert_eq!(vec![1, 2, 3], first);
+    assert_eq!(vec![2, 3], second);
+    assert!(block_cursor.fetch().unwrap().is_none());
+}
+
+/// Per column limits take precedence over the limit for all other columns.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn text_buffer_with_column_limits(profile: &Profile) {
+    let table_name = table_name!();
+    let conn = profile
+        .setup_empty_table(&table_name, &["VARCHAR(1000)", "VARCHAR(1000)"])
+        .unwrap();
+
+    let mut cursor = conn
+        .execute(&format!("SELECT a, b FROM {table_name}"), ())
+        .unwrap()
+        .unwrap();
+    let column_limits = HashMap::from([(2, 500)]);
+    let buffer =
+        TextRowSet::for_cursor_with_limits(1, &mut cursor, Some(10), &column_limits).unwrap();
+
+    assert_eq!(10, buffer.max_len(0));
+    assert_eq!(500, buffer.max_len(1));
+}
+
+#[cfg(feature = "json")]
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn fetch_rows_as_json(profile: &Profile) {
+    use odbc_api::json::JsonRows;
+
+    let table_name = table_name!();
+    let conn = profile
+        .setup_empty_table(&table_name, &["INTEGER", "VARCHAR(10)", "DECIMAL(5,2)"])
+        .unwrap();
+    conn.execute(
+        &format!(
+            "INSERT INTO {table_name} (a, b, c) VALUES (42, 'Hello', 1.5), (NULL, NULL, NULL)"
+        ),
+        (),
+    )
+    .unwrap();
+
+    let cursor = conn
+        .execute(&format!("SELECT a, b, c FROM {table_name} ORDER BY id"), ())
+        .unwrap()
+        .unwrap();
+    let mut rows = JsonRows::new(cursor, 10, None).unwrap();
+    let batch = rows.next_batch().unwrap().unwrap();
+
+    assert_eq!(
+        r#"[{"a":42,"b":"Hello","c":1.5},{"a":null,"b":null,"c":null}]"#,
+        batch.to_string()
+    );
+    assert!(rows.next().is_none());
+}
+
+#[cfg(feature = "avro")]
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn export_avro(profile: &Profile) {
+    use apache_avro::{types::Value, Reader};
+    use odbc_api::avro::AvroExport;
+
+    let table_name = table_name!();
+    let conn = profile
+        .setup_empty_table(&table_name, &["INTEGER", "VARCHAR(10)"])
+        .unwrap();
+    conn.execute(
+        &format!("INSERT INTO {table_name} (a, b) VALUES (42, 'Hello'), (NULL, NULL)"),
+        (),
+    )
+    .unwrap();
+
+    let cursor = conn
+        .execute(&format!("SELECT a, b FROM {table_name} ORDER BY id"), ())
+        .unwrap()
+        .unwrap();
+    let export = AvroExport::new(cursor, "Row", 10, None).unwrap();
+    assert_eq!(
+        r#"{"type":"record","name":"Row","fields":[{"name":"a","type":["null","int"],"default":null},{"name":"b","type":["null","string"],"default":null}]}"#,
+        export.schema()
+    );
+    let mut container = Vec::new();
+    let num_rows = export.write_to(&mut container).unwrap();
+
+    assert_eq!(2, num_rows);
+    let records = Reader::new(container.as_slice())
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        vec![
+            Value::Record(vec![
+                ("a".to_owned(), Value::Union(1, Box::new(Value::Int(42)))),
+                (
+                    "b".to_owned(),
+                    Value::Union(1, Box::new(Value::String("Hello".to_owned())))
+                ),
+            ]),
+            Value::Record(vec![
+                ("a".to_owned(), Value::Union(0, Box::new(Value::Null))),
+                ("b".to_owned(), Value::Union(0, Box::new(Value::Null))),
+            ]),
+        ],
+        records
+    );
+}
+
+#[cfg(feature = "parquet")]
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn export_parquet(profile: &Profile) {
+    use arrow_array::{Int32Array, StringArray};
+    use odbc_api::parquet::{write_parquet_to, ParquetCompression, ParquetOptions};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    let table_name = table_name!();
+    let conn = profile
+        .setup_empty_table(&table_name, &["INTEGER", "VARCHAR(10)"])
+        .unwrap();
+    conn.execute(
+        &format!("INSERT INTO {table_name} (a, b) VALUES (42, 'Hello'), (NULL, NULL)"),
+        (),
+    )
+    .unwrap();
+
+    let cursor = conn
+        .execute(&format!("SELECT a, b FROM {table_name} ORDER BY id"), ())
+        .unwrap()
+        .unwrap();
+    // One row per batch and row group, so each row ends up in a row group of its own.
+    let options = ParquetOptions {
+        batch_size: 1,
+        row_group_size: 1,
+        compression: ParquetCompression::Uncompressed,
+        ..ParquetOptions::default()
+    };
+    let mut file = tempfile::tempfile().unwrap();
+    let num_rows = write_parquet_to(cursor, &mut file, &options).unwrap();
+
+    assert_eq!(2, num_rows);
+    let reader = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+    assert_eq!(2, reader.metadata().num_row_groups());
+    let batches = reader
+        .build()
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let a: Vec<_> = batches
+        .iter()
+        .flat_map(|batch| {
+            let column = batch.column(0).as_any().downcast_ref::<Int32Array>();
+            column.unwrap().iter().collect::<Vec<_>>()
+        })
+        .collect();
+    let b: Vec<_> = batches
+        .iter()
+        .flat_map(|batch| {
+            let column = batch.column(1).as_any().downcast_ref::<StringArray>();
+            column
+                .unwrap()
+                .iter()
+                .map(|text| text.map(str::to_owned))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    assert_eq!(vec![Some(42), None], a);
+    assert_eq!(vec![Some("Hello".to_owned()), None], b);
+}
+
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn fetch_numeric_matrix(profile: &Profile) {
+    let table_name = table_name!();
+    let conn = profile
+        .setup_empty_table(&table_name, &["INTEGER", "INTEGER", "VARCHAR(10)"])
+        .unwrap();
+    conn.execute(
+        &format!(
+            "INSERT INTO {table_name} (a, b, c) VALUES (1, 2, 'x'), (3, NULL, 'y'), (5, 6, 'z')"
+        ),
+        (),
+    )
+    .unwrap();
+
+    // Batch size smaller than the number of rows, so the matrix is assembled from several batches.
+    let cursor = conn
+        .execute(&format!("SELECT a, b FROM {table_name} ORDER BY id"), ())
+        .unwrap()
+        .unwrap();
+    let matrix = Matrix::fetch(cursor, 2, NullPolicy::Fill(-1.)).unwrap();
+    assert_eq!((3, 2), matrix.shape());
+    assert_eq!(vec![1., 2., 3., -1., 5., 6.], matrix.values);
+
+    let cursor = conn
+        .execute(&format!("SELECT a, b FROM {table_name} ORDER BY id"), ())
+        .unwrap()
+        .unwrap();
+    let result = Matrix::fetch(cursor, 2, NullPolicy::Fail);
+    assert!(matches!(
+        result,
+        Err(MatrixError::UnexpectedNull { row: 1, column: 2 })
+    ));
+
+    let cursor = conn
+        .execute(&format!("SELECT a, c FROM {table_name} ORDER BY id"), ())
+        .unwrap()
+        .unwrap();
+    let result = Matrix::fetch(cursor, 2, NullPolicy::Fail);
+    assert!(matches!(
+        result,
+        Err(MatrixError::NonNumericColumn { column: 2, .. })
+    ));
+}
+
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn query_logger_sees_prepared_and_preallocated_statements(profile: &Profile) {
+    use odbc_api::QueryLogEntry;
+    use std::sync::{Arc, Mutex};
+
+    let table_name = table_name!();
+    let (mut conn, table) = profile.given(&table_name, &["INTEGER"]).unwrap();
+    let entries = Arc::new(Mutex::new(Vec::new()));
+    let sink = entries.clone();
+    conn.set_query_logger(Some(Box::new(move |entry: &QueryLogEntry| {
+        sink.lock()
+            .unwrap()
+            .push(format!("{} {:?}", entry.sql, entry.parameters));
+    })));
+
+    let insert = table.sql_insert();
+    let mut prepared = conn.prepare(&insert).unwrap();
+    prepared.execute(&1).unwrap();
+    let mut preallocated = conn.preallocate().unwrap();
+    preallocated.execute(&insert, &2).unwrap();
+    conn.execute_row_count(&insert, &3).unwrap();
+
+    let entries = entries.lock().unwrap();
+    assert_eq!(
+        vec![
+            format!("{insert} [\"1\"]"),
+            format!("{insert} [\"2\"]"),
+            format!("{insert} [\"3\"]"),
+        ],
+        *entries
+    );
+}
+
+#[cfg(feature = "metrics")]
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn query_metrics(profile: &Profile) {
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+    use odbc_api::metrics::{QUERIES_TOTAL, QUERY_ERRORS_TOTAL, ROWS_FETCHED_TOTAL};
+
+    let table_name = table_name!();
+    let (conn, _table) = profile.given(&table_name, &["INTEGER"]).unwrap();
+    conn.execute(&format!("INSERT INTO {table_name} (a) VALUES (1), (2)"), ())
+        .unwrap();
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+
+    metrics::with_local_recorder(&recorder, || {
+        let mut cursor = conn
+            .execute(&format!("SELECT a FROM {table_name}"), ())
+            .unwrap()
+            .unwrap();
+        let buffer = TextRowSet::for_cursor(10, &mut cursor, None).unwrap();
+        let mut block_cursor = cursor.bind_buffer(buffer).unwrap();
+        while block_cursor.fetch().unwrap().is_some() {}
+        let result = conn.execute("SELECT a FROM ThisTableDoesNotExist", ());
+        assert!(result.is_err());
+    });
+
+    let snapshot = snapshotter.snapshot().into_vec();
+    let counter = |name: &str, label: Option<(&str, &str)>| {
+        snapshot.iter().find_map(|(key, _, _, value)| {
+            let key = key.key();
+            let has_label = label.is_none_or(|(k, v)| {
+                key.labels()
+                    .any(|label| label.key() == k && label.value() == v)
+            });
+            match value {
+                DebugValue::Counter(value) if key.name() == name && has_label => Some(*value),
+                _ => None,
+            }
+        })
+    };
+    assert_eq!(Some(1), counter(QUERIES_TOTAL, Some(("status", "ok"))));
+    assert_eq!(Some(1), counter(QUERIES_TOTAL, Some(("status", "error"))));
+    assert_eq!(Some(1), counter(QUERY_ERRORS_TOTAL, None));
+    assert_eq!(Some(2), counter(ROWS_FETCHED_TOTAL, None));
+}
+
+/// Without Multiple Active Result Sets, SQL Server only allows one active statement per connection.
+#[test]
+fn concurrent_statements_require_mars() {
+    let conn = ENV
+        .connect_with_connection_string(MSSQL_CONNECTION)
+        .unwrap();
+
+    assert!(!conn.supports_multiple_active_result_sets().unwrap());
+    let Err(error) = conn.preallocate_concurrent(2) else {
+        panic!("Expected preallocating two concurrent statements to fail")
+    };
+    assert!(matches!(
+        error,
+        Error::TooManyConcurrentStatements {
+            requested: 2,
+            max_concurrent_activities: 1
+        }
+    ));
+    assert!(error
+        .to_string()
+        .contains("but the driver supports only 1 active statements per connection."));
+    assert_eq!(1, conn.preallocate_concurrent(1).unwrap().len());
+}
+
+#[test]
+fn interleaved_cursors_with_mars() {
+    let conn = ENV
+        .connect_with_connection_string(&format!("{MSSQL_CONNECTION}MARS_Connection=Yes;"))
+        .unwrap();
+    assert!(conn.supports_multiple_active_result_sets().unwrap());
+
+    let mut statements = conn.preallocate_concurrent(2).unwrap();
+    let (first, second) = statements.split_at_mut(1);
+    let mut outer = first[0]
+        .execute("SELECT 1 UNION ALL SELECT 2", ())
+        .unwrap()
+        .unwrap();
+    let mut sum = 0;
+    while let Some(mut row) = outer.next_row().unwrap() {
+        let mut value = 0i32;
+        row.get_data(1, &mut value).unwrap();
+        // Execute a second statement, while the first one still holds an open cursor.
+        let mut inner = second[0].execute("SELECT ? * 10", &value).unwrap().unwrap();
+        let mut inner_row = inner.next_row().unwrap().unwrap();
+        let mut product = 0i32;
+        inner_row.get_data(1, &mut product).unwrap();
+        sum += product;
+    }
+    assert_eq!(30, sum);
+}
+
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn lazy_columns(profile: &Profile) {
+    let table_name = table_name!();
+    let conn = profile
+        .setup_empty_table(&table_name, &["INTEGER", "VARCHAR(10)"])
+        .unwrap();
+    conn.execute(
+        &format!("INSERT INTO {table_name} (a, b) VALUES (42, 'Hello'), (5, NULL)"),
+        (),
+    )
+    .unwrap();
+
+    let cursor = conn
+        .execute(&format!("SELECT a, b FROM {table_name} ORDER BY id"), ())
+        .unwrap()
+        .unwrap();
+    let mut cursor = LazyColumnCursor::new(cursor, &[2], None).unwrap();
+    assert_eq!(vec![2], cursor.lazy_columns().collect::<Vec<_>>());
+
+    let mut row = cursor.next_row().unwrap().unwrap();
+    assert_eq!(CellValue::I64(42), row.get(1).unwrap());
+    assert!(!row.is_fetched(2));
+    assert_eq!(CellValue::Text(b"Hello".to_vec()), row.get(2).unwrap());
+    assert!(row.is_fetched(2));
+    // Repeated access does not fetch the value again
+    assert_eq!(CellValue::Text(b"Hello".to_vec()), row.get(2).unwrap());
+
+    // Lazy column is never accessed for the second row
+    let mut row = cursor.next_row().unwrap().unwrap();
+    assert_eq!(CellValue::I64(5), row.get(1).unwrap());
+    assert!(cursor.next_row().unwrap().is_none());
+}
+
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn mask_columns_by_name(profile: &Profile) {
+    let table_name = table_name!();
+    let conn = profile
+        .setup_empty_table(&table_name, &["INTEGER", "VARCHAR(20)", "VARCHAR(11)"])
+        .unwrap();
+    conn.execute(
+        &format!(
+            "INSERT INTO {table_name} (a, b, c) VALUES (1, 'alice@example.com', '123-45-6789')"
+        ),
+        (),
+    )
+    .unwrap();
+    let policy = CellTransforms::new()
+        .null_column("C")
+        .column("b", |value| match value {
+            CellValue::Text(text) => CellValue::I64(text.len() as i64),
+            other => other,
+        })
+        .null_column("not_in_result_set");
+
+    let mut cursor = conn
+        .execute(&format!("SELECT a, b, c FROM {table_name} ORDER BY id"), ())
+        .unwrap()
+        .unwrap();
+    let transform = policy.for_result_set(&mut cursor).unwrap();
+    let buffer = TypeCoercion::new()
+        .buffer_for_cursor(10, &mut cursor)
+        .unwrap();
+    let mut block_cursor = cursor.bind_buffer(buffer).unwrap();
+    let batch = block_cursor.fetch().unwrap().unwrap();
+    let row = transform.apply(batch.row(0));
+
+    assert_eq!(
+        Row::new(vec![CellValue::I64(1), CellValue::I64(17), CellValue::Null]),
+        row
+    );
+}
+
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn charset_report(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["VARCHAR(10)"]).unwrap();
+    conn.execute(&table.sql_insert(), &"fine".into_parameter())
+        .unwrap();
+    conn.execute(&table.sql_insert(), &"bell\u{7}".into_parameter())
+        .unwrap();
+
+    let cursor = conn
+        .execute(&table.sql_all_ordered_by_id(), ())
+        .unwrap()
+        .unwrap();
+    let buffer =
+        ColumnarAnyBuffer::try_from_descs(10, [BufferDesc::Text { max_str_len: 10 }]).unwrap();
+    let mut block_cursor = cursor.bind_buffer(buffer).unwrap();
+    let report = block_cursor.fetch().unwrap().unwrap().charset_report();
+
+    assert_eq!(
+        vec![CharsetFinding {
+            row: 1,
+            column: 0,
+            issue: CharsetIssue::ControlCharacter,
+            position: 4
+        }],
+        report.findings
+    );
+}
+
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn strip_nul_from_text_parameter(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["VARCHAR(10)"]).unwrap();
+
+    let parameter = NulPolicy::Strip.text_parameter(b"a\0b\0").unwrap();
+    conn.execute(&table.sql_insert(), &parameter).unwrap();
+    let error = NulPolicy::Error.text_parameter(b"a\0b").unwrap_err();
+
+    assert_eq!("ab", table.content_as_string(&conn));
+    assert_eq!(1, error.position);
+}
+
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn wide_text_parameters(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["VARCHAR(20)"]).unwrap();
+    let insert = table.sql_insert();
+
+    conn.execute(&insert, &Wide("Grüße").into_parameter())
+        .unwrap();
+    conn.execute(&insert, &U16String::from_str("Hello").into_parameter())
+        .unwrap();
+    conn.execute(&insert, &U16Str::from_slice(&[0x57, 0x6f]).into_parameter())
+        .unwrap();
+    conn.execute(&insert, &Wide(None::<&str>).into_parameter())
+        .unwrap();
+
+    assert_eq!("Grüße\nHello\nWo\nNULL", table.content_as_string(&conn));
+    let cursor = conn
+        .execute(
+            &format!("SELECT COUNT(*) FROM {table_name} WHERE a = ?"),
+            &Wide("Grüße").into_parameter(),
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!("1", cursor_to_string(cursor));
+}
+
+#[test_case(MSSQL, "BINARY(4)"; "Microsoft SQL Server")]
+#[test_case(MARIADB, "BINARY(4)"; "Maria DB")]
+#[test_case(SQLITE_3, "BLOB"; "SQLite 3")]
+#[test_case(POSTGRES, "BYTEA"; "PostgreSQL")]
+fn fixed_size_binary(profile: &Profile, column_type: &str) {
+    let table_name = table_name!();
+    let column_types = [column_type];
+    let (conn, table) = profile.given(&table_name, &column_types).unwrap();
+    conn.execute(&table.sql_insert(), &[1u8, 2, 3, 4].into_parameter())
+        .unwrap();
+    conn.execute(&table.sql_insert(), &None::<[u8; 4]>.into_parameter())
+        .unwrap();
+
+    let mut cursor = conn
+        .execute(&table.sql_all_ordered_by_id(), ())
+        .unwrap()
+        .unwrap();
+    let mut value = Binary::<4>::NULL;
+    let mut row = cursor.next_row().unwrap().unwrap();
+    row.get_data(1, &mut value).unwrap();
+    assert_eq!(Some(&[1, 2, 3, 4]), value.value());
+    let mut row = cursor.next_row().unwrap().unwrap();
+    row.get_data(1, &mut value).unwrap();
+    assert_eq!(None, value.as_bytes());
+}
+
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn bind_slices_as_parameter_arrays(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["INTEGER", "BIGINT"]).unwrap();
+    let ids = [1, 2, 3];
+    let amounts = [Some(10i64), None, Some(30)];
+
+    conn.execute(
+        &table.sql_insert(),
+        ParameterArrays::from((&ids[..], &amounts[..])),
+    )
+    .unwrap();
+
+    assert_eq!("1,10\n2,NULL\n3,30", table.content_as_string(&conn));
+}
+
+/// Rows of an iterator are executed in chunks. A failing chunk is reported to the hook and skipped.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn chunked_execution_from_iterator(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, table) = profile
+        .given(&table_name, &["INTEGER", "VARCHAR(10)"])
+        .unwrap();
+    let mut prepared = conn.prepare(&table.sql_insert()).unwrap();
+    let rows = (1..=5).map(|id| (id, Some(format!("row {id}"))));
+    let mut reports = Vec::new();
+
+    let summary = prepared
+        .chunked_execution()
+        .chunk_size(2)
+        .on_chunk(|report| {
+            reports.push((report.first_row, report.num_rows, report.result.is_ok()));
+            ControlFlow::Continue(())
+        })
+        .execute(rows)
+        .unwrap();
+
+    assert_eq!(vec![(0, 2, true), (2, 2, true), (4, 1, true)], reports);
+    assert_eq!(5, summary.rows_executed);
+    assert!(summary.is_complete());
+    assert_eq!(
+        "1,row 1\n2,row 2\n3,row 3\n4,row 4\n5,row 5",
+        table.content_as_string(&conn)
+    );
+}
+
+/// Slices and vectors of trait objects, as well as derived structs, bind one parameter per element.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn parameters_from_trait_objects(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, table) = profile
+        .given(&table_name, &["INTEGER", "VARCHAR(10)"])
+        .unwrap();
+    let text = "one".into_parameter();
+    let borrowed: [&dyn InputParameter; 2] = [&1, &text];
+    let owned: Vec<Box<dyn InputParameter>> = vec![Box::new(2), Box::new("two".into_parameter())];
+
+    conn.execute(&table.sql_insert(), &borrowed[..]).unwrap();
+    conn.execute(&table.sql_insert(), &owned).unwrap();
+
+    assert_eq!("1,one\n2,two", table.content_as_string(&conn));
+}
+
+/// Fields of a struct deriving `InputParameterCollection` are bound in order of declaration.
+#[cfg(feature = "derive")]
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn derive_input_parameter_collection(profile: &Profile) {
+    use odbc_api::{parameter::VarCharBox, InputParameterCollection};
+
+    #[derive(InputParameterCollection)]
+    struct Row {
+        id: i32,
+        name: VarCharBox,
+    }
+
+    let table_name = table_name!();
+    let (conn, table) = profile
+        .given(&table_name, &["INTEGER", "VARCHAR(10)"])
+        .unwrap();
+    let row = Row {
+        id: 42,
+        name: VarCharBox::from_string("answer".to_owned()),
+    };
+
+    conn.execute(&table.sql_insert(), &row).unwrap();
+
+    assert_eq!("42,answer", table.content_as_string(&conn));
+}
+
+/// Borrowed parameters are bound without copying them, both with a scoped cursor and within boxed
+/// trait objects.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn execute_scoped_with_borrowed_parameters(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, table) = profile
+        .given(&table_name, &["INTEGER", "VARCHAR(50)"])
+        .unwrap();
+    let text = "a".repeat(50);
+    let params: Vec<Box<dyn InputParameter + '_>> =
+        vec![Box::new(1), Box::new(text.as_str().into_parameter())];
+    conn.execute(&table.sql_insert(), &params).unwrap();
+    let query = format!("SELECT a FROM {table_name} WHERE b = ?");
+
+    let id = conn
+        .execute_scoped(&query, &text.as_str().into_parameter(), |cursor| {
+            let mut row = cursor.unwrap().next_row()?.unwrap();
+            let mut id = 0i32;
+            row.get_data(1, &mut id)?;
+            Ok(id)
+        })
+        .unwrap();
+    let mut prepared = conn.prepare(&query).unwrap();
+    let has_rows = prepared
+        .execute_scoped(&"b".into_parameter(), |cursor| {
+            Ok(cursor.unwrap().next_row()?.is_some())
+        })
+        .unwrap();
+
+    assert_eq!(1, id);
+    assert!(!has_rows);
+}
+
+/// Execute a prepared query into the same buffer twice, with different parameters.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn prepared_execute_into_buffer(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, _table) = profile.given(&table_name, &["INTEGER"]).unwrap();
+    conn.execute(
+        &format!("INSERT INTO {table_name} (a) VALUES (1), (2), (3), (4), (5)"),
+        (),
+    )
+    .unwrap();
+    let mut prepared = conn
+        .prepare(&format!(
+            "SELECT a FROM {table_name} WHERE a > ? ORDER BY id"
+        ))
+        .unwrap();
+    let mut buffer =
+        ColumnarAnyBuffer::try_from_descs(2, [BufferDesc::I32 { nullable: false }]).unwrap();
+    let mut batches = Vec::new();
+    let mut collect = |batch: &ColumnarAnyBuffer| {
+        batches.push(batch.column(0).as_slice::<i32>().unwrap().to_vec());
+        ControlFlow::Continue(())
+    };
+
+    let has_result_set = prepared
+        .execute_into(&1, &mut buffer, &mut collect)
+        .unwrap();
+    prepared
+        .execute_into(&3, &mut buffer, &mut collect)
+        .unwrap();
+
+    assert!(has_result_set);
+    assert_eq!(vec![vec![2, 3], vec![4, 5], vec![4, 5]], batches);
+}
+
+/// Cached result sets are returned until they expire, even if the table changed in the meantime.
+#[cfg(feature = "result_cache")]
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn result_cache(profile: &Profile) {
+    use odbc_api::ResultCache;
+
+    // Given
+    let table_name = table_name!();
+    let (conn, table) = profile
+        .given(&table_name, &["VARCHAR(10)", "VARCHAR(20)"])
+        .unwrap();
+    conn.execute(
+        &table.sql_insert(),
+        (&"DE".into_parameter(), &"Germany".into_parameter()),
+    )
+    .unwrap();
+    let cache = ResultCache::new(Duration::from_secs(3600));
+    let query = format!("SELECT b FROM {table_name} WHERE a = ?");
+
+    // When
+    let first = cache.fetch(&conn, &query, &[Some("DE")]).unwrap().unwrap();
+    conn.execute(&format!("DELETE FROM {table_name}"), ())
+        .unwrap();
+    let second = cache.fetch(&conn, &query, &[Some("DE")]).unwrap().unwrap();
+    let other = cache.fetch(&conn, &query, &[Some("FR")]).unwrap().unwrap();
+
+    // Then
+    assert_eq!(vec![vec![Some("Germany".to_owned())]], first.rows);
+    assert!(first.complete);
+    assert_eq!(first, second);
+    assert!(other.rows.is_empty());
+    assert_eq!(2, cache.len());
+}
+
+/// Result sets with more than `max_rows` rows are reported as incomplete and not cached.
+#[cfg(feature = "result_cache")]
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn result_cache_does_not_cache_incomplete_result_sets(profile: &Profile) {
+    use odbc_api::ResultCache;
+
+    // Given
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["INTEGER"]).unwrap();
+    let insert = table.sql_insert();
+    for value in 1..=3 {
+        conn.execute(&insert, &value).unwrap();
+    }
+    let cache = ResultCache::new(Duration::from_secs(3600)).with_max_rows(2);
+    let query = format!("SELECT a FROM {table_name} ORDER BY a");
+
+    // When
+    let result = cache.fetch(&conn, &query, &[]).unwrap().unwrap();
+
+    // Then
+    assert!(!result.complete);
+    assert_eq!(
+        vec![vec![Some("1".to_owned())], vec![Some("2".to_owned())]],
+        result.rows
+    );
+    assert!(cache.is_empty());
+}
+
+/// Upsert structs, updating existing rows and inserting new ones. Keys are not the first field.
+#[cfg(feature = "derive")]
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn upsert_many_records(profile: &Profile) {
+    use odbc_api::TextRecord;
+
+    #[derive(TextRecord)]
+    struct Birthday {
+        a: Option<i32>,
+        b: String,
+    }
+
+    // Given
+    let table_name = table_name!();
+    let conn = profile.connection().unwrap();
+    conn.execute(&format!("DROP TABLE IF EXISTS {table_name}"), ())
+        .unwrap();
+    conn.execute(
+        &format!("CREATE TABLE {table_name} (a INTEGER, b VARCHAR(50) PRIMARY KEY)"),
+        (),
+    )
+    .unwrap();
+    conn.execute(
+        &format!("INSERT INTO {table_name} (a, b) VALUES (1964, 'Keanu Reeves')"),
+        (),
+    )
+    .unwrap();
+    let records = [
+        Birthday {
+            a: Some(1965),
+            b: "Keanu Reeves".to_owned(),
+        },
+        Birthday {
+            a: None,
+            b: "Brad Pitt".to_owned(),
+        },
+    ];
+
+    // When
+    let summary = conn
+        .upsert_many(
+            &table_name,
+            &["b"],
+            &records,
+            &conn.dialect().unwrap(),
+            &InsertRowsOptions::default(),
+        )
+        .unwrap();
+
+    // Then
+    assert!(summary.is_complete());
+    assert_eq!(2, summary.rows_upserted);
+    let cursor = conn
+        .execute(&format!("SELECT b, a FROM {table_name} ORDER BY b"), ())
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        "Brad Pitt,NULL\nKeanu Reeves,1965",
+        cursor_to_string(cursor)
+    );
+}
+
+/// Randomly generated schemas and values survive a roundtrip through SQLite.
+#[cfg(feature = "proptest")]
+#[test]
+fn roundtrip_generated_cases_sqlite() {
+    use odbc_api::testing::{RoundtripCase, SqliteInMemory};
+    use proptest::{
+        prelude::any,
+        test_runner::{Config, TestRunner},
+    };
+
+    let sqlite = SqliteInMemory::new(&ENV).unwrap().unwrap();
+    let mut runner = TestRunner::new_with_rng(
+        Config::with_cases(20),
+        TestRunner::deterministic().new_rng(),
+    );
+    runner
+        .run(&any::<RoundtripCase>(), |case| {
+            case.assert_roundtrip(&sqlite, "Roundtrip").unwrap();
+            Ok(())
+        })
+        .unwrap();
+}
+
+/// Values growing in length force `insert_rows` to shrink the chunk size, in order to stay within
+/// the memory budget.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn insert_rows_in_chunks(profile: &Profile) {
+    let table_name = table_name!();
+    let conn = profile
+        .setup_empty_table(&table_name, &["VARCHAR(50)", "VARCHAR(50)"])
+        .unwrap();
+    let rows = [
+        [Some("1"), Some("a")],
+        [Some("2"), None],
+        [Some("3"), Some("abcdefghijklmnopqrstuvwxyz")],
+        [Some("4"), Some("b")],
+    ];
+    let options = InsertRowsOptions {
+        max_rows_per_chunk: 3,
+        max_bytes_per_chunk: 64,
+        commit_every_n_chunks: None,
+    };
+
+    let num_rows = conn
+        .insert_rows(&table_name, &["a", "b"], rows, &AnsiDialect, &options)
+        .unwrap();
+
+    assert_eq!(4, num_rows);
+    let cursor = conn
+        .execute(&format!("SELECT a, b FROM {table_name} ORDER BY id"), ())
+        .unwrap()
+        .unwrap();
+    let actual = cursor_to_string(cursor);
+    assert_eq!("1,a\n2,NULL\n3,abcdefghijklmnopqrstuvwxyz\n4,b", actual);
+}
+
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn upsert_updates_existing_row(profile: &Profile) {
+    let table_name = table_name!();
+    let conn = profile
+        .setup_empty_table(&table_name, &["INTEGER", "VARCHAR(10)"])
+        .unwrap();
+    conn.execute(
+        &format!("CREATE UNIQUE INDEX {table_name}_a ON {table_name} (a)"),
+        (),
+    )
+    .unwrap();
+    let upsert = Upsert::new(&table_name)
+        .key_columns(&["a"])
+        .value_columns(&["b"]);
+
+    let mut prepared = conn.prepare_upsert(&upsert, &AnsiDialect).unwrap();
+    prepared.execute((&1, &"old".into_parameter())).unwrap();
+    prepared.execute((&1, &"new".into_parameter())).unwrap();
+    prepared.execute((&2, &"other".into_parameter())).unwrap();
+
+    let cursor = conn
+        .execute(&format!("SELECT a, b FROM {table_name} ORDER BY a"), ())
+        .unwrap()
+        .unwrap();
+    let actual = cursor_to_string(cursor);
+    assert_eq!("1,new\n2,other", actual);
+}
+
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn preview_first_rows(profile: &Profile) {
+    let table_name = table_name!();
+    let conn = profile
+        .setup_empty_table(&table_name, &["INTEGER", "VARCHAR(10)"])
+        .unwrap();
+    conn.execute(
+        &format!("INSERT INTO {table_name} (a, b) VALUES (1, 'one'), (2, NULL), (3, 'three')"),
+        (),
+    )
+    .unwrap();
+
+    let preview = conn
+        .preview(&format!("SELECT a, b FROM {table_name} ORDER BY a"), 2)
+        .unwrap()
+        .unwrap();
+
+    let expected = Preview {
+        column_names: vec!["a".to_owned(), "b".to_owned()],
+        rows: vec![
+            vec![Some("1".to_owned()), Some("one".to_owned())],
+            vec![Some("2".to_owned()), None],
+        ],
+    };
+    assert_eq!(expected, preview);
+}
+
+// Check the max name length for the catalogs, schemas, tables, and columns.
+#[test_case(MSSQL, 128, 128, 128, 128; "Microsoft SQL Server")]
+#[test_case(MARIADB, 256, 0, 256, 255; "Maria DB")]
+#[test_case(SQLITE_3, 255, 255, 255, 255; "SQLite 3")]
+#[test_case(POSTGRES, 0, 63, 63, 63; "PostgreSQL")]
+fn name_limits(
+    profile: &Profile,
+    expected_max_catalog_name_len: u16,
+    expected_max_schema_name_len: u16,
+    expected_max_table_name_len: u16,
+    expected_max_column_name_len: u16,
+) {
+    let conn = profile.connection().unwrap();
+
+    assert_eq!(
+        conn.max_catalog_name_len().unwrap(),
+        expected_max_catalog_name_len
+    );
+    assert_eq!(
+        conn.max_schema_name_len().unwrap(),
+        expected_max_schema_name_len
+    );
+    assert_eq!(
+        conn.max_table_name_len().unwrap(),
+        expected_max_table_name_len
+    );
+    assert_eq!(
+        conn.max_column_name_len().unwrap(),
+        expected_max_column_name_len
+    );
+}
+
+// Check the current catalog being used by the connection.
+#[test_case(MSSQL, "master"; "Microsoft SQL Server")]
+#[test_case(MARIADB, "test_db"; "Maria DB")]
+#[test_case(SQLITE_3, ""; "SQLite 3")]
+#[test_case(POSTGRES, "test"; "PostgreSQL")]
+fn current_catalog(profile: &Profile, expected_catalog: &str) {
+    let conn = profile.connection().unwrap();
+
+    assert_eq!(conn.current_catalog().unwrap(), expected_catalog);
+}
+
+#[test_case(MSSQL, "dbo"; "Microsoft SQL Server")]
+#[test_case(MARIADB, ""; "Maria DB")]
+#[test_case(SQLITE_3, "dbo"; "SQLite 3")]
+// #[test_case(POSTGRES, "test"; "PostgreSQL")] Errors out in linux
+fn columns_query(profile: &Profile, schema: &str) {
+    let table_name = table_name!();
+    let conn = profile
+        .setup_empty_table(&table_name, &["VARCHAR(10)"])
+        .unwrap();
+
+    let row_set_buffer =
+        ColumnarAnyBuffer::try_from_descs(2, conn.columns_buffer_descs(255, 255, 255).unwrap())
+            .unwrap();
+    // Mariadb does not support schemas
+    let columns = conn
+        .columns(&conn.current_catalog().unwrap(), schema, &table_name, "a")
+        .unwrap();
+
+    let mut cursor = columns.bind_buffer(row_set_buffer).unwrap();
+    let batch = cursor.fetch().unwrap().unwrap();
+
+    const COLUMN_NAME_INDEX: usize = 3;
     let column_names = batch.column(COLUMN_NAME_INDEX).as_text_view().unwrap();
 
     const COLUMN_SIZE_INDEX: usize = 6;
@@ -3428,72 +5399,521 @@ fn row_count_prepared_insert(profile: &Profile) {
     let row_count = prepared.row_count().unwrap();
 
     // Then
-    assert_eq!(Some(2), row_count);
+    assert_eq!(Some(2), row_count);
+}
+
+/// Statement handles checked out from the pool of a connection are returned to it on drop and
+/// reused for the next checkout.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn reuse_pooled_statement(profile: &Profile) {
+    // Given
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["INTEGER"]).unwrap();
+    let insert = table.sql_insert();
+
+    // When
+    for i in 0..3 {
+        let mut statement = conn.checkout_statement().unwrap();
+        statement.execute(&insert, &i).unwrap();
+    }
+
+    // Then
+    assert_eq!(1, conn.num_pooled_statements());
+    assert_eq!("0\n1\n2", table.content_as_string(&conn));
+    conn.clear_statement_pool();
+    assert_eq!(0, conn.num_pooled_statements());
+}
+
+/// A limit on the number of rows set on a pooled statement must not cut off the result sets of the
+/// next checkout.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn pooled_statement_does_not_keep_max_rows(profile: &Profile) {
+    // Given
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["INTEGER"]).unwrap();
+    conn.execute(
+        &format!("INSERT INTO {table_name} (a) VALUES (1), (2), (3)"),
+        (),
+    )
+    .unwrap();
+    let query = table.sql_all_ordered_by_id();
+
+    // When
+    let mut statement = conn.checkout_statement().unwrap();
+    statement.set_max_rows(1).unwrap();
+    let limited = cursor_to_string(statement.execute(&query, ()).unwrap().unwrap());
+    drop(statement);
+    let mut statement = conn.checkout_statement().unwrap();
+    let unlimited = cursor_to_string(statement.execute(&query, ()).unwrap().unwrap());
+
+    // Then
+    assert_eq!(1, conn.num_pooled_statements());
+    assert_eq!("1", limited);
+    assert_eq!("1\n2\n3", unlimited);
+}
+
+/// Cursor type and sensitivity set on a pooled statement must not carry over to the next checkout.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+fn pooled_statement_does_not_keep_cursor_type(profile: &Profile) {
+    // Given
+    let conn = profile.connection().unwrap();
+
+    // When
+    let mut statement = conn.checkout_statement().unwrap();
+    statement.set_cursor_type(CursorType::KeysetDriven).unwrap();
+    statement
+        .set_cursor_sensitivity(CursorSensitivity::Sensitive)
+        .unwrap();
+    drop(statement);
+    let mut statement = conn.checkout_statement().unwrap();
+    let mut cursor_type: usize = usize::MAX;
+    let mut sensitivity: usize = usize::MAX;
+    unsafe {
+        let hstmt = statement.as_stmt_ref().as_sys();
+        let ret = sys::SQLGetStmtAttrW(
+            hstmt,
+            sys::StatementAttribute::CursorType,
+            &mut cursor_type as *mut usize as sys::Pointer,
+            0,
+            null_mut(),
+        );
+        assert_eq!(ret, sys::SqlReturn::SUCCESS);
+        let ret = sys::SQLGetStmtAttrW(
+            hstmt,
+            sys::StatementAttribute::CursorSensitivity,
+            &mut sensitivity as *mut usize as sys::Pointer,
+            0,
+            null_mut(),
+        );
+        assert_eq!(ret, sys::SqlReturn::SUCCESS);
+    }
+
+    // Then
+    assert_eq!(1, conn.num_pooled_statements());
+    assert_eq!(CursorType::ForwardOnly.as_attribute_value(), cursor_type);
+    assert_eq!(
+        CursorSensitivity::Unspecified.as_attribute_value(),
+        sensitivity
+    );
+}
+
+/// Fire an insert statement adding two rows directly on the connection and verify that the count
+/// of changed rows is 2.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn row_count_connection_execute(profile: &Profile) {
+    // Given
+    let table_name = table_name!();
+    let (conn, _table) = profile.given(&table_name, &["INTEGER"]).unwrap();
+    let insert = format!("INSERT INTO {table_name} (a) VALUES (?), (?)");
+
+    // When
+    let row_count = conn.execute_row_count(&insert, (&1, &2)).unwrap();
+
+    // Then
+    assert_eq!(Some(2), row_count);
+}
+
+/// Insert three rows using a columnar bulk inserter and verify that the count of changed rows is 3.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn row_count_bulk_insert(profile: &Profile) {
+    // Given
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["INTEGER"]).unwrap();
+    let prepared = conn.prepare(&table.sql_insert()).unwrap();
+    let desc = BufferDesc::I32 { nullable: false };
+    let mut inserter = prepared.into_column_inserter(3, [desc]).unwrap();
+    inserter.set_num_rows(3);
+    inserter
+        .column_mut(0)
+        .as_slice::<i32>()
+        .unwrap()
+        .copy_from_slice(&[1, 2, 3]);
+
+    // When
+    inserter.execute().unwrap();
+    let row_count = inserter.row_count().unwrap();
+
+    // Then
+    assert_eq!(Some(3), row_count);
+}
+
+/// Execute a script with multiple statements, letting the driver process it as one batch.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+fn execute_batch_driver(profile: &Profile) {
+    // Given
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["INTEGER"]).unwrap();
+    let script = format!(
+        "INSERT INTO {table_name} (a) VALUES (1), (2); SELECT a FROM {table_name}; \
+        DELETE FROM {table_name} WHERE a = 1;"
+    );
+
+    // When
+    let outcomes = conn.execute_batch(&script, BatchMode::Driver).unwrap();
+
+    // Then
+    assert_eq!(
+        vec![
+            StatementOutcome::RowCount(Some(2)),
+            StatementOutcome::ResultSet { num_cols: 1 },
+            StatementOutcome::RowCount(Some(1))
+        ],
+        outcomes
+    );
+    assert_eq!("2", table.content_as_string(&conn));
+}
+
+/// Execute a script with multiple statements, by splitting it on the client side.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn execute_batch_split(profile: &Profile) {
+    // Given
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["VARCHAR(10)"]).unwrap();
+    let script = format!(
+        "INSERT INTO {table_name} (a) VALUES ('a;b'); -- Not a statement;\n\
+        INSERT INTO {table_name} (a) VALUES ('c');\n\
+        SELECT a FROM {table_name};"
+    );
+
+    // When
+    let outcomes = conn.execute_batch(&script, BatchMode::Split).unwrap();
+
+    // Then
+    assert_eq!(
+        vec![
+            StatementOutcome::RowCount(Some(1)),
+            StatementOutcome::RowCount(Some(1)),
+            StatementOutcome::ResultSet { num_cols: 1 }
+        ],
+        outcomes
+    );
+    assert_eq!("a;b\nc", table.content_as_string(&conn));
+}
+
+/// DDL statements report success uniformly, even if drivers report no data or no row count.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn execute_ddl(profile: &Profile) {
+    // Given
+    let table_name = table_name!();
+    let conn = profile.connection().unwrap();
+
+    // When
+    conn.execute_ddl(&format!("DROP TABLE IF EXISTS {table_name}"))
+        .unwrap();
+    conn.execute_ddl(&format!("CREATE TABLE {table_name} (a INTEGER)"))
+        .unwrap();
+    conn.execute_ddl(&format!("DROP TABLE IF EXISTS {table_name}"))
+        .unwrap();
+    // Table does not exist anymore
+    conn.execute_ddl(&format!("DROP TABLE IF EXISTS {table_name}"))
+        .unwrap();
+
+    // Then
+    let error = conn
+        .execute_ddl(&format!("DROP TABLE {table_name}"))
+        .unwrap_err();
+    assert!(matches!(error, Error::Diagnostics { .. }));
+}
+
+/// Executing a script stops at the first failing statement and reports where it is located.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn execute_script_reports_failing_statement(profile: &Profile) {
+    // Given
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["INTEGER"]).unwrap();
+    let script = format!(
+        "INSERT INTO {table_name} (a) VALUES (1)\n\
+        GO\n\
+        -- Table does not exist\n\
+        INSERT INTO {table_name}_missing (a) VALUES (2)\n\
+        GO\n\
+        INSERT INTO {table_name} (a) VALUES (3)\n"
+    );
+
+    // When
+    let error = conn
+        .execute_script(&script, &ScriptDelimiter::Line("GO".to_owned()))
+        .unwrap_err();
+
+    // Then
+    assert_eq!(1, error.statement_index);
+    assert_eq!(3, error.line);
+    assert_eq!(vec![StatementOutcome::RowCount(Some(1))], error.outcomes);
+    assert_eq!("1", table.content_as_string(&conn));
+}
+
+#[test_case(MSSQL, None; "Microsoft SQL Server")]
+#[test_case(MARIADB, Some(0); "Maria DB")]
+#[test_case(SQLITE_3, Some(0); "SQLite 3")]
+#[test_case(POSTGRES, Some(0); "PostgreSQL")]
+fn row_count_create_table_preallocated(profile: &Profile, expectation: Option<usize>) {
+    // Given a name for a table which does not exist
+    let table_name = table_name!();
+    let conn = profile.connection().unwrap();
+    conn.execute(&format!("DROP TABLE IF EXISTS {table_name};"), ())
+        .unwrap();
+
+    // When
+    let mut preallocated = conn.preallocate().unwrap();
+    preallocated
+        .execute(&format!("CREATE TABLE {table_name} (a INTEGER);"), ())
+        .unwrap();
+    let row_count = preallocated.row_count().unwrap();
+
+    // Then
+    assert_eq!(expectation, row_count);
+}
+
+#[test_case(MSSQL, Some(0); "Microsoft SQL Server")]
+#[test_case(MARIADB, Some(0); "Maria DB")]
+#[test_case(SQLITE_3, Some(0); "SQLite 3")]
+#[test_case(POSTGRES, Some(0); "PostgreSQL")]
+fn row_count_create_table_prepared(profile: &Profile, expectation: Option<usize>) {
+    // Given a name for a table which does not exist
+    let table_name = table_name!();
+    let conn = profile.connection().unwrap();
+    conn.execute(&format!("DROP TABLE IF EXISTS {table_name};"), ())
+        .unwrap();
+
+    // When
+    let mut prepared = conn
+        .prepare(&format!("CREATE TABLE {table_name} (a INTEGER);"))
+        .unwrap();
+    prepared.execute(()).unwrap();
+    let row_count = prepared.row_count().unwrap();
+
+    // Then
+    assert_eq!(expectation, row_count);
+}
+
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+#[tokio::test]
+async fn async_preallocated_statement_execution(profile: &Profile) {
+    // Given a table
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["VARCHAR(50)"]).unwrap();
+    let query = format!("INSERT INTO {table_name} (a) VALUES ('Hello, World!')");
+    let sleep = || tokio::time::sleep(Duration::from_millis(10));
+
+    // When
+    let mut statement = conn.preallocate().unwrap().into_polling().unwrap();
+    statement.execute(&query, (), sleep).await.unwrap();
+
+    // Then
+    let actual = table.content_as_string(&conn);
+    assert_eq!("Hello, World!", actual);
+}
+
+/// Progress callback is invoked after each row set and may stop the iteration early.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn fetch_progress_callback(profile: &Profile) {
+    // Given a table with ten rows
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["INTEGER"]).unwrap();
+    let prepared = conn.prepare(&table.sql_insert()).unwrap();
+    let mut inserter = prepared.into_text_inserter(10, [5]).unwrap();
+    for index in 0..10 {
+        inserter
+            .append([Some(index.to_string().as_bytes())].iter().copied())
+            .unwrap();
+    }
+    inserter.execute().unwrap();
+
+    // When fetching in batches of three and stopping after the second one
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    let sink = reported.clone();
+    let cursor = conn
+        .execute(&table.sql_all_ordered_by_id(), ())
+        .unwrap()
+        .unwrap();
+    let buffer = TextRowSet::from_max_str_lens(3, [5usize]).unwrap();
+    let mut block_cursor = cursor.bind_buffer(buffer).unwrap();
+    block_cursor.set_progress_callback(move |progress| {
+        sink.lock()
+            .unwrap()
+            .push((progress.num_rows, progress.num_batches));
+        if progress.num_batches == 2 {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+    let mut num_batches = 0;
+    while block_cursor.fetch().unwrap().is_some() {
+        num_batches += 1;
+    }
+
+    // Then
+    assert_eq!(2, num_batches);
+    assert_eq!(vec![(3, 1), (6, 2)], *reported.lock().unwrap());
+}
+
+/// Statistics of a block cursor count rows, batches and truncated values.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn fetch_statistics_count_truncated_values(profile: &Profile) {
+    // Given a table with one value fitting into a buffer of length five and one which does not
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["VARCHAR(10)"]).unwrap();
+    conn.execute(&table.sql_insert(), &"abc".into_parameter())
+        .unwrap();
+    conn.execute(&table.sql_insert(), &"0123456789".into_parameter())
+        .unwrap();
+
+    // When fetching both rows with a buffer of length five
+    let cursor = conn
+        .execute(&table.sql_all_ordered_by_id(), ())
+        .unwrap()
+        .unwrap();
+    let buffer = TextRowSet::from_max_str_lens(1, [5usize]).unwrap();
+    let mut block_cursor = cursor.bind_buffer(buffer).unwrap();
+    while block_cursor.fetch().unwrap().is_some() {}
+    let statistics = *block_cursor.statistics();
+
+    // Then
+    assert_eq!(2, statistics.num_rows);
+    assert_eq!(2, statistics.num_batches);
+    assert_eq!(3 + 5, statistics.num_bytes);
+    assert_eq!(1, statistics.num_truncated);
 }
 
-#[test_case(MSSQL, None; "Microsoft SQL Server")]
-#[test_case(MARIADB, Some(0); "Maria DB")]
-#[test_case(SQLITE_3, Some(0); "SQLite 3")]
-#[test_case(POSTGRES, Some(0); "PostgreSQL")]
-fn row_count_create_table_preallocated(profile: &Profile, expectation: Option<usize>) {
-    // Given a name for a table which does not exist
+/// Sampling every second row set skips the other ones, for forward only and scrollable cursors.
+#[test_case(MSSQL, CursorType::ForwardOnly; "Microsoft SQL Server forward only")]
+#[test_case(MSSQL, CursorType::Static; "Microsoft SQL Server static")]
+#[test_case(MARIADB, CursorType::ForwardOnly; "Maria DB")]
+#[test_case(SQLITE_3, CursorType::ForwardOnly; "SQLite 3")]
+#[test_case(POSTGRES, CursorType::ForwardOnly; "PostgreSQL")]
+fn sample_every_nth_batch(profile: &Profile, cursor_type: CursorType) {
+    // Given a table with ten rows
     let table_name = table_name!();
-    let conn = profile.connection().unwrap();
-    conn.execute(&format!("DROP TABLE IF EXISTS {table_name};"), ())
-        .unwrap();
+    let (conn, table) = profile.given(&table_name, &["INTEGER"]).unwrap();
+    let prepared = conn.prepare(&table.sql_insert()).unwrap();
+    let mut inserter = prepared.into_text_inserter(10, [5]).unwrap();
+    for index in 0..10 {
+        inserter
+            .append([Some(index.to_string().as_bytes())].iter().copied())
+            .unwrap();
+    }
+    inserter.execute().unwrap();
 
-    // When
-    let mut preallocated = conn.preallocate().unwrap();
-    preallocated
-        .execute(&format!("CREATE TABLE {table_name} (a INTEGER);"), ())
+    // When fetching in batches of two and sampling every second one
+    let mut statement = conn.preallocate().unwrap();
+    statement.set_cursor_type(cursor_type).unwrap();
+    let cursor = statement
+        .execute(&table.sql_all_ordered_by_id(), ())
+        .unwrap()
         .unwrap();
-    let row_count = preallocated.row_count().unwrap();
+    let buffer = TextRowSet::from_max_str_lens(2, [5usize]).unwrap();
+    let mut block_cursor = cursor.bind_buffer(buffer).unwrap();
+    block_cursor.set_sampling(Sampling::EveryNthBatch(2));
+    let mut sampled = Vec::new();
+    while let Some(batch) = block_cursor.fetch().unwrap() {
+        for row_index in 0..batch.num_rows() {
+            sampled.push(batch.at_as_str(0, row_index).unwrap().unwrap().to_owned());
+        }
+    }
 
     // Then
-    assert_eq!(expectation, row_count);
+    assert_eq!(["0", "1", "4", "5", "8", "9"], sampled.as_slice());
+    assert_eq!(3, block_cursor.statistics().num_batches);
 }
 
-#[test_case(MSSQL, Some(0); "Microsoft SQL Server")]
-#[test_case(MARIADB, Some(0); "Maria DB")]
-#[test_case(SQLITE_3, Some(0); "SQLite 3")]
-#[test_case(POSTGRES, Some(0); "PostgreSQL")]
-fn row_count_create_table_prepared(profile: &Profile, expectation: Option<usize>) {
-    // Given a name for a table which does not exist
+/// A schema snapshot captures the columns and the primary key of a table.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+fn schema_snapshot_of_table(profile: &Profile) {
+    // Given a table with a primary key and a nullable text column
     let table_name = table_name!();
     let conn = profile.connection().unwrap();
     conn.execute(&format!("DROP TABLE IF EXISTS {table_name};"), ())
         .unwrap();
+    conn.execute(
+        &format!("CREATE TABLE {table_name} (id INTEGER NOT NULL PRIMARY KEY, a VARCHAR(10));"),
+        (),
+    )
+    .unwrap();
 
     // When
-    let mut prepared = conn
-        .prepare(&format!("CREATE TABLE {table_name} (a INTEGER);"))
-        .unwrap();
-    prepared.execute(()).unwrap();
-    let row_count = prepared.row_count().unwrap();
+    let filter = SchemaFilter {
+        table: table_name.clone(),
+        ..SchemaFilter::default()
+    };
+    let snapshot = schema_snapshot(&conn, &filter).unwrap();
 
     // Then
-    assert_eq!(expectation, row_count);
+    assert_eq!(1, snapshot.tables.len());
+    let table = &snapshot.tables[0];
+    assert_eq!(table_name.to_lowercase(), table.name.to_lowercase());
+    let column_names: Vec<_> = table
+        .columns
+        .iter()
+        .map(|column| column.name.to_lowercase())
+        .collect();
+    assert_eq!(["id", "a"], column_names.as_slice());
+    assert_eq!(Nullability::NoNulls, table.columns[0].nullability);
+    assert_eq!(Nullability::Nullable, table.columns[1].nullability);
+    assert_eq!(Some(10), table.columns[1].column_size);
+    assert_eq!(["id"], table.primary_key.as_slice());
+    // Comparing the snapshot with itself yields no changes
+    assert!(snapshot.diff(&snapshot).is_empty());
 }
 
+/// Dropping a future of a long running statement, should cancel the statement and leave it usable
+/// for the next query.
 #[test_case(MSSQL; "Microsoft SQL Server")]
-#[test_case(MARIADB; "Maria DB")]
-#[test_case(SQLITE_3; "SQLite 3")]
-#[test_case(POSTGRES; "PostgreSQL")]
 #[tokio::test]
-async fn async_preallocated_statement_execution(profile: &Profile) {
-    // Given a table
+async fn async_cancel_on_drop(profile: &Profile) {
+    // Given
     let table_name = table_name!();
-    let (conn, table) = profile.given(&table_name, &["VARCHAR(50)"]).unwrap();
-    let query = format!("INSERT INTO {table_name} (a) VALUES ('Hello, World!')");
+    let (conn, table) = profile.given(&table_name, &["INTEGER"]).unwrap();
     let sleep = || tokio::time::sleep(Duration::from_millis(10));
+    let mut statement = conn.preallocate().unwrap().into_polling().unwrap();
 
     // When
-    let mut statement = conn.preallocate().unwrap().into_polling().unwrap();
-    statement.execute(&query, (), sleep).await.unwrap();
+    let timed_out = tokio::time::timeout(
+        Duration::from_millis(100),
+        statement.execute("WAITFOR DELAY '0:0:05'", (), sleep),
+    )
+    .await
+    .is_err();
+    statement
+        .execute(&table.sql_insert(), &42, sleep)
+        .await
+        .unwrap();
 
     // Then
-    let actual = table.content_as_string(&conn);
-    assert_eq!("Hello, World!", actual);
+    assert!(timed_out);
+    assert_eq!("42", table.content_as_string(&conn));
 }
 
 #[test_case(MSSQL; "Microsoft SQL Server")]
@@ -3536,6 +5956,126 @@ async fn async_bulk_fetch(profile: &Profile) {
     assert_eq!(1000, sum_rows_fetched)
 }
 
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+#[tokio::test]
+async fn async_row_stream(profile: &Profile) {
+    // Given a table with more rows than fit into a single batch
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["INTEGER"]).unwrap();
+    conn.execute(
+        &format!("INSERT INTO {table_name} (a) VALUES (1), (2), (NULL)"),
+        (),
+    )
+    .unwrap();
+    let query = table.sql_all_ordered_by_id();
+    let sleep = || tokio::time::sleep(Duration::from_millis(50));
+
+    // When
+    let cursor = conn
+        .execute_polling(&query, (), sleep)
+        .await
+        .unwrap()
+        .unwrap();
+    let buffer =
+        ColumnarAnyBuffer::try_from_descs(2, [BufferDesc::I32 { nullable: true }]).unwrap();
+    let mut rows = cursor
+        .bind_buffer(buffer)
+        .unwrap()
+        .into_stream::<Row>(sleep);
+    let mut values = Vec::new();
+    while let Some(row) = rows.next().await {
+        values.push(row.unwrap().try_get::<Option<i32>>(0).unwrap());
+    }
+
+    // Then
+    assert_eq!(vec![Some(1), Some(2), None], values);
+    assert!(rows.next().await.is_none());
+}
+
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+#[tokio::test]
+async fn async_row_stream_with_filter(profile: &Profile) {
+    // Given a table whose first batch consists of rows only, which are filtered out
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["INTEGER"]).unwrap();
+    conn.execute(
+        &format!("INSERT INTO {table_name} (a) VALUES (-1), (-2), (3), (-4), (5)"),
+        (),
+    )
+    .unwrap();
+    let query = table.sql_all_ordered_by_id();
+    let sleep = || tokio::time::sleep(Duration::from_millis(50));
+
+    // When
+    let cursor = conn
+        .execute_polling(&query, (), sleep)
+        .await
+        .unwrap()
+        .unwrap();
+    let buffer =
+        ColumnarAnyBuffer::try_from_descs(2, [BufferDesc::I32 { nullable: false }]).unwrap();
+    let mut rows = cursor
+        .bind_buffer(buffer)
+        .unwrap()
+        .into_stream::<Row>(sleep)
+        .with_filter(|batch, row_index| batch.column(0).as_slice::<i32>().unwrap()[row_index] > 0);
+    let mut values = Vec::new();
+    while let Some(row) = rows.next().await {
+        values.push(row.unwrap().try_get::<i32>(0).unwrap());
+    }
+
+    // Then
+    assert_eq!(vec![3, 5], values);
+}
+
+#[cfg(feature = "stream")]
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+#[tokio::test]
+async fn async_row_stream_implements_stream(profile: &Profile) {
+    use futures_core::Stream;
+    use std::{future::poll_fn, pin::Pin};
+
+    // Given a table with more rows than fit into a single batch
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["INTEGER"]).unwrap();
+    conn.execute(
+        &format!("INSERT INTO {table_name} (a) VALUES (1), (2), (NULL)"),
+        (),
+    )
+    .unwrap();
+    let query = table.sql_all_ordered_by_id();
+    let sleep = || tokio::time::sleep(Duration::from_millis(50));
+
+    // When polling the rows through the `Stream` trait
+    let cursor = conn
+        .execute_polling(&query, (), sleep)
+        .await
+        .unwrap()
+        .unwrap();
+    let buffer =
+        ColumnarAnyBuffer::try_from_descs(2, [BufferDesc::I32 { nullable: true }]).unwrap();
+    let mut rows = cursor
+        .bind_buffer(buffer)
+        .unwrap()
+        .into_stream::<Row>(sleep);
+    let mut values = Vec::new();
+    while let Some(row) = poll_fn(|cx| Pin::new(&mut rows).poll_next(cx)).await {
+        values.push(row.unwrap().try_get::<Option<i32>>(0).unwrap());
+    }
+
+    // Then
+    assert_eq!(vec![Some(1), Some(2), None], values);
+}
+
 /// This test is inspired by a bug caused from a fetch statement generating a lot of diagnostic
 /// messages.
 #[test_case(MSSQL; "Microsoft SQL Server")]
@@ -3580,3 +6120,223 @@ fn many_diagnostic_messages(profile: &Profile) {
 
     // We do not have an explicit assertion, we are just happy if no integer addition overflows.
 }
+
+/// Text inserters grow according to their growth policy, or reject values too large for them.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn text_inserter_growth_policy(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, table) = profile.given(&table_name, &["VARCHAR(50)"]).unwrap();
+    let prepared = conn.prepare(&table.sql_insert()).unwrap();
+    let mut inserter = prepared.into_text_inserter(3, [2]).unwrap();
+
+    inserter.set_growth_policy(GrowthPolicy::Exponential);
+    inserter.append([Some(&b"abc"[..])].into_iter()).unwrap();
+    inserter.set_growth_policy(GrowthPolicy::Fail);
+    inserter.append([Some(&b"d"[..])].into_iter()).unwrap();
+    let result = inserter.append([Some(&b"efghi"[..])].into_iter());
+    inserter.execute().unwrap();
+
+    assert!(matches!(result, Err(Error::TooLargeValueForBuffer)));
+    assert_eq!("abc\nd", table.content_as_string(&conn));
+}
+
+/// Reserving space for longer values preserves the values already in the buffer.
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn column_inserter_reserve_for(profile: &Profile) {
+    let table_name = table_name!();
+    let (conn, table) = profile
+        .given(&table_name, &["VARCHAR(50)", "INTEGER", "VARCHAR(50)"])
+        .unwrap();
+    let prepared = conn.prepare(&table.sql_insert()).unwrap();
+    let descs = [
+        BufferDesc::Text { max_str_len: 2 },
+        BufferDesc::I32 { nullable: false },
+        BufferDesc::Text { max_str_len: 2 },
+    ];
+    let mut inserter = prepared.into_column_inserter(2, descs).unwrap();
+    inserter.set_num_rows(1);
+    inserter
+        .column_mut(0)
+        .as_text_view()
+        .unwrap()
+        .set_cell(0, Some(b"ab"));
+    inserter.column_mut(1).as_slice::<i32>().unwrap()[0] = 1;
+    inserter
+        .column_mut(2)
+        .as_text_view()
+        .unwrap()
+        .set_cell(0, Some(b"cd"));
+
+    inserter.reserve_for([Some(10), Some(10), None]).unwrap();
+    inserter.set_num_rows(2);
+    inserter
+        .column_mut(0)
+        .as_text_view()
+        .unwrap()
+        .set_cell(1, Some(b"abcdefghij"));
+    inserter.column_mut(1).as_slice::<i32>().unwrap()[1] = 2;
+    inserter
+        .column_mut(2)
+        .as_text_view()
+        .unwrap()
+        .set_cell(1, None);
+    inserter.execute().unwrap();
+
+    assert_eq!("ab,1,cd\nabcdefghij,2,NULL", table.content_as_string(&conn));
+}
+
+#[test_case(MSSQL; "Microsoft SQL Server")]
+#[test_case(MARIADB; "Maria DB")]
+#[test_case(SQLITE_3; "SQLite 3")]
+#[test_case(POSTGRES; "PostgreSQL")]
+fn trace_single_connection(profile: &Profile) {
+    // Given a trace file
+    let trace_file = NamedTempFile::new().unwrap();
+    let trace_path = trace_file.path().to_str().unwrap();
+
+    // When
+    let conn = ENV
+        .connect_with_connection_string_and_trace(profile.connection_string, trace_path)
+        .unwrap();
+    conn.execute("SELECT 42", ()).unwrap();
+    conn.set_trace(false).unwrap();
+
+    // Then tracing can be toggled on an existing connection, too
+    conn.set_trace_file(trace_path).unwrap();
+    conn.set_trace(true).unwrap();
+    conn.set_trace(false).unwrap();
+}
+
+#[test_case(MSSQL; "Microsoft SQL Server")]
+fn call_stored_procedure(profile: &Profile) {
+    // Given a procedure returning a result set, an output parameter and a return value
+    let conn = profile.connection().unwrap();
+    conn.execute("DROP PROCEDURE IF EXISTS CallStoredProcedure", ())
+        .unwrap();
+    conn.execute(
+        "CREATE PROCEDURE CallStoredProcedure @a INT, @b INT OUTPUT AS
+        BEGIN
+            SET NOCOUNT ON;
+            SELECT @a + 1 AS c;
+            SELECT @a + 2 AS d;
+            SET @b = @a * 2;
+            RETURN 7;
+        END",
+        (),
+    )
+    .unwrap();
+
+    // When
+    let a = 20;
+    let mut ret = Nullable::<i32>::null();
+    let mut b = Nullable::<i32>::null();
+    let mut call = conn
+        .call_with_return_value("CallStoredProcedure", (Out(&mut ret), &a, Out(&mut b)))
+        .unwrap();
+    let first = cursor_to_string(call.next_result_set().unwrap().unwrap());
+    let second = cursor_to_string(call.next_result_set().unwrap().unwrap());
+    let no_more = call.next_result_set().unwrap().is_none();
+    call.finish().unwrap();
+
+    // Then
+    assert_eq!("21", first);
+    assert_eq!("22", second);
+    assert!(no_more);
+    assert_eq!(Some(7), ret.into_opt());
+    assert_eq!(Some(40), b.into_opt());
+}
+
+#[test_case(MSSQL; "Microsoft SQL Server")]
+fn table_valued_parameter(profile: &Profile) {
+    // Given a table type and a procedure inserting rows of that type into a table
+    let table_name = table_name!();
+    let (conn, table) = profile
+        .given(&table_name, &["VARCHAR(50)", "INTEGER"])
+        .unwrap();
+    conn.execute("DROP PROCEDURE IF EXISTS InsertTableValuedParameter", ())
+        .unwrap();
+    conn.execute("DROP TYPE IF EXISTS TableValuedParameterRow", ())
+        .unwrap();
+    conn.execute(
+        "CREATE TYPE TableValuedParameterRow AS TABLE (a VARCHAR(50), b INTEGER)",
+        (),
+    )
+    .unwrap();
+    conn.execute(
+        &format!(
+            "CREATE PROCEDURE InsertTableValuedParameter @rows TableValuedParameterRow READONLY AS
+            INSERT INTO {table_name} (a, b) SELECT a, b FROM @rows"
+        ),
+        (),
+    )
+    .unwrap();
+    // Rows fetched from another connection into a columnar buffer
+    let source = profile.connection().unwrap();
+    let cursor = source
+        .execute(
+            "SELECT 'Hello' AS a, 1 AS b UNION ALL SELECT 'World', 2",
+            (),
+        )
+        .unwrap()
+        .unwrap();
+    let buffer = ColumnarAnyBuffer::try_from_descs(
+        10,
+        [
+            BufferDesc::Text { max_str_len: 50 },
+            BufferDesc::I32 { nullable: true },
+        ],
+    )
+    .unwrap();
+    let mut cursor = cursor.bind_buffer(buffer).unwrap();
+    let rows = cursor.fetch().unwrap().unwrap();
+
+    // When
+    let mut tvp = TableValuedParameter::new("TableValuedParameterRow", rows);
+    conn.execute("{call InsertTableValuedParameter(?)}", (&mut tvp,))
+        .unwrap();
+
+    // Then
+    let actual = table.content_as_string(&conn);
+    assert_eq!("Hello,1\nWorld,2", actual);
+}
+
+#[test_case(MSSQL; "Microsoft SQL Server")]
+fn streamed_output_parameter(profile: &Profile) {
+    // Given a procedure returning a large text through an output parameter
+    let conn = profile.connection().unwrap();
+    conn.execute("DROP PROCEDURE IF EXISTS StreamedOutputParameter", ())
+        .unwrap();
+    conn.execute(
+        "CREATE PROCEDURE StreamedOutputParameter @a INT, @b VARCHAR(MAX) OUTPUT AS
+        BEGIN
+            SET NOCOUNT ON;
+            SET @b = REPLICATE(CAST('a' AS VARCHAR(MAX)), @a);
+        END",
+        (),
+    )
+    .unwrap();
+
+    // When
+    let a = 10_000;
+    let mut b = OutputStream::new(DataType::LongVarchar { length: 0 });
+    let mut statement = conn.preallocate().unwrap();
+    let mut outputs = statement
+        .execute_with_output_streams("{call StreamedOutputParameter(?, ?)}", (&a, &mut b))
+        .unwrap();
+    let mut output = outputs.next_output().unwrap().unwrap();
+    let parameter_number = output.parameter_number();
+    let mut text = Vec::new();
+    let not_null = output.get_text(&mut text).unwrap();
+
+    // Then
+    assert_eq!(2, parameter_number);
+    assert!(not_null);
+    assert_eq!("a".repeat(10_000).as_bytes(), text);
+    assert!(outputs.next_output().unwrap().is_none());
+}